@@ -0,0 +1,175 @@
+use super::{reduced_motion::ReducedMotion, visible_grid::VisibleGrid, ModelViewEntityMap};
+use crate::nest::element::sprite_sheet::{get_element_index, ElementTextureAtlasHandle};
+use bevy::prelude::*;
+use simulation::{
+    common::{airdrop::Airdrop, grid::Grid, position::Position, Zone},
+    nest_simulation::element::{definition::ElementDefinitions, Element, ElementExposure},
+};
+
+// How far above its landing spot airdropped food appears when first dropped.
+const AIRDROP_FALL_HEIGHT: f32 = 8.0;
+// Falling food passes in front of everything else.
+const AIRDROP_Z_INDEX: f32 = 3.0;
+
+/// When food is airdropped into the simulation, render it falling toward where it will land.
+pub fn on_spawn_airdrop<Z: Zone>(
+    airdrop_query: Query<(Entity, &Airdrop, &Position), (Added<Airdrop>, With<Z>)>,
+    grid_query: Query<&Grid, With<Z>>,
+    element_texture_atlas_handle: Res<ElementTextureAtlasHandle>,
+    element_definitions: Res<ElementDefinitions>,
+    mut model_view_entity_map: ResMut<ModelViewEntityMap>,
+    visible_grid: Res<VisibleGrid>,
+    reduced_motion: ReducedMotion,
+    mut commands: Commands,
+) {
+    let visible_grid_entity = match visible_grid.0 {
+        Some(visible_grid_entity) => visible_grid_entity,
+        None => return,
+    };
+
+    let grid = match grid_query.get(visible_grid_entity) {
+        Ok(grid) => grid,
+        Err(_) => return,
+    };
+
+    for (airdrop_model_entity, airdrop, position) in airdrop_query.iter() {
+        spawn_airdrop_sprite(
+            airdrop_model_entity,
+            airdrop,
+            position,
+            grid,
+            reduced_motion.is_enabled(),
+            &element_texture_atlas_handle,
+            &element_definitions,
+            &mut model_view_entity_map,
+            &mut commands,
+        );
+    }
+}
+
+/// When user switches to a different scene all views are despawned. Airdrops still falling need to be redrawn.
+pub fn rerender_airdrops<Z: Zone>(
+    airdrop_query: Query<(Entity, &Airdrop, &Position), With<Z>>,
+    grid_query: Query<&Grid, With<Z>>,
+    element_texture_atlas_handle: Res<ElementTextureAtlasHandle>,
+    element_definitions: Res<ElementDefinitions>,
+    mut model_view_entity_map: ResMut<ModelViewEntityMap>,
+    reduced_motion: ReducedMotion,
+    mut commands: Commands,
+) {
+    let grid = grid_query.single();
+
+    for (airdrop_model_entity, airdrop, position) in airdrop_query.iter() {
+        spawn_airdrop_sprite(
+            airdrop_model_entity,
+            airdrop,
+            position,
+            grid,
+            reduced_motion.is_enabled(),
+            &element_texture_atlas_handle,
+            &element_definitions,
+            &mut model_view_entity_map,
+            &mut commands,
+        );
+    }
+}
+
+pub fn on_update_airdrop<Z: Zone>(
+    airdrop_query: Query<(Entity, &Airdrop, &Position), (Changed<Airdrop>, With<Z>)>,
+    mut airdrop_view_query: Query<(&mut Transform, &mut TextureAtlasSprite)>,
+    grid_query: Query<&Grid, With<Z>>,
+    model_view_entity_map: Res<ModelViewEntityMap>,
+    visible_grid: Res<VisibleGrid>,
+    reduced_motion: ReducedMotion,
+) {
+    let visible_grid_entity = match visible_grid.0 {
+        Some(visible_grid_entity) => visible_grid_entity,
+        None => return,
+    };
+
+    let grid = match grid_query.get(visible_grid_entity) {
+        Ok(grid) => grid,
+        Err(_) => return,
+    };
+
+    for (airdrop_model_entity, airdrop, position) in airdrop_query.iter() {
+        if let Some(airdrop_view_entity) = model_view_entity_map.get(&airdrop_model_entity) {
+            if let Ok((mut transform, mut sprite)) =
+                airdrop_view_query.get_mut(*airdrop_view_entity)
+            {
+                let is_reduced_motion = reduced_motion.is_enabled();
+
+                *transform = get_airdrop_transform(airdrop, position, grid, is_reduced_motion);
+                sprite
+                    .color
+                    .set_a(get_airdrop_alpha(airdrop, is_reduced_motion));
+            }
+        }
+    }
+}
+
+fn spawn_airdrop_sprite(
+    airdrop_model_entity: Entity,
+    airdrop: &Airdrop,
+    position: &Position,
+    grid: &Grid,
+    is_reduced_motion: bool,
+    element_texture_atlas_handle: &Res<ElementTextureAtlasHandle>,
+    element_definitions: &ElementDefinitions,
+    model_view_entity_map: &mut ResMut<ModelViewEntityMap>,
+    commands: &mut Commands,
+) {
+    let element_exposure = ElementExposure::ALL;
+
+    let mut sprite = TextureAtlasSprite::new(get_element_index(
+        element_exposure,
+        Element::Food,
+        Some(airdrop.food_kind()),
+        element_definitions,
+    ));
+    sprite.custom_size = Some(Vec2::splat(1.0));
+    sprite
+        .color
+        .set_a(get_airdrop_alpha(airdrop, is_reduced_motion));
+
+    let airdrop_view_entity = commands
+        .spawn(SpriteSheetBundle {
+            transform: get_airdrop_transform(airdrop, position, grid, is_reduced_motion),
+            sprite,
+            texture_atlas: element_texture_atlas_handle.0.clone(),
+            ..default()
+        })
+        .id();
+
+    model_view_entity_map.insert(airdrop_model_entity, airdrop_view_entity);
+}
+
+/// Food starts high up, and large, as if it's close to the camera. It shrinks to normal size as it falls into place.
+/// When motion is reduced, food appears where it's going to land instead.
+fn get_airdrop_transform(
+    airdrop: &Airdrop,
+    position: &Position,
+    grid: &Grid,
+    is_reduced_motion: bool,
+) -> Transform {
+    let remaining_fall = if is_reduced_motion {
+        0.0
+    } else {
+        1.0 - airdrop.progress()
+    };
+
+    let mut translation = grid.grid_to_world_position(*position);
+    translation.y += remaining_fall * AIRDROP_FALL_HEIGHT;
+    translation.z = AIRDROP_Z_INDEX;
+
+    Transform::from_translation(translation).with_scale(Vec3::splat(1.0 + remaining_fall))
+}
+
+/// Rather than falling, food fades in where it's going to land when motion is reduced.
+fn get_airdrop_alpha(airdrop: &Airdrop, is_reduced_motion: bool) -> f32 {
+    if is_reduced_motion {
+        airdrop.progress()
+    } else {
+        1.0
+    }
+}
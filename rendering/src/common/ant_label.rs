@@ -0,0 +1,36 @@
+use super::camera::RenderingCamera;
+use bevy::prelude::*;
+use simulation::device_quality::DeviceQuality;
+
+/// The name written beneath an ant. Names aren't drawn when they'd be too small to read, or at all on devices which
+/// can't spare the effort.
+#[derive(Component)]
+pub struct AntLabel;
+
+pub fn update_ant_label_visibility(
+    mut ant_label_query: Query<&mut Visibility, With<AntLabel>>,
+    added_ant_label_query: Query<(), Added<AntLabel>>,
+    camera_query: Query<Ref<OrthographicProjection>, With<RenderingCamera>>,
+    device_quality: Res<DeviceQuality>,
+) {
+    let Ok(projection) = camera_query.get_single() else {
+        return;
+    };
+
+    if !projection.is_changed() && !device_quality.is_changed() && added_ant_label_query.is_empty()
+    {
+        return;
+    }
+
+    let visibility = if device_quality.level().is_label_shown(projection.scale) {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+
+    for mut ant_label_visibility in ant_label_query.iter_mut() {
+        if *ant_label_visibility != visibility {
+            *ant_label_visibility = visibility;
+        }
+    }
+}
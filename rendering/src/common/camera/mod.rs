@@ -1,12 +1,25 @@
 mod pancam;
+pub mod tour;
 
-use self::pancam::{PanCam, PanCamPlugin};
-use super::visible_grid::VisibleGrid;
+use self::{
+    pancam::{PanCam, PanCamPlugin},
+    tour::{
+        cancel_camera_tour, note_tour_sights, remove_camera_tour, start_camera_tour, tour_camera,
+        CameraTour,
+    },
+};
+use super::{
+    keyboard_cursor::{is_keyboard_navigation_enabled, KeyboardCursor},
+    visible_grid::VisibleGrid,
+};
 use bevy::{
     prelude::*,
     window::{PrimaryWindow, WindowResized},
 };
-use simulation::common::grid::Grid;
+use simulation::{
+    app_state::AppState, common::grid::Grid, crater_simulation::crater::AtCrater, demo::DemoStory,
+    nest_simulation::nest::AtNest, CleanupSet,
+};
 
 #[derive(Component)]
 pub struct RenderingCamera;
@@ -114,6 +127,79 @@ fn insert_pancam(
     });
 }
 
+/// Keep the keyboard cursor in view. When it's moved off screen, the camera cuts straight to it rather than easing, so
+/// it's in view before it's moved again.
+fn follow_keyboard_cursor(
+    mut camera_query: Query<
+        (
+            &Camera,
+            &GlobalTransform,
+            &PanCam,
+            &mut Transform,
+            &OrthographicProjection,
+        ),
+        With<RenderingCamera>,
+    >,
+    primary_window_query: Query<&Window, With<PrimaryWindow>>,
+    keyboard_cursor: Res<KeyboardCursor>,
+    visible_grid: Res<VisibleGrid>,
+    grid_query: Query<&Grid>,
+) {
+    let position = match keyboard_cursor.position() {
+        Some(position) => position,
+        None => return,
+    };
+
+    let grid = match visible_grid
+        .0
+        .and_then(|visible_grid_entity| grid_query.get(visible_grid_entity).ok())
+    {
+        Some(grid) => grid,
+        None => return,
+    };
+
+    let (camera, global_transform, pancam, mut transform, projection) =
+        match camera_query.get_single_mut() {
+            Ok(camera) => camera,
+            Err(_) => return,
+        };
+
+    let window = primary_window_query.single();
+    let world_position = grid.grid_to_world_position(position);
+
+    let is_in_view = camera
+        .world_to_viewport(global_transform, world_position)
+        .map_or(false, |viewport_position| {
+            viewport_position.x >= 0.0
+                && viewport_position.x <= window.width()
+                && viewport_position.y >= 0.0
+                && viewport_position.y <= window.height()
+        });
+
+    if is_in_view {
+        return;
+    }
+
+    let mut translation = world_position.truncate().extend(transform.translation.z);
+
+    // Keep the camera within the grid, the same as when panning by hand.
+    let half_size = Vec2::new(window.width(), window.height()) * projection.scale / 2.0;
+    if let (Some(min_x), Some(max_x)) = (pancam.min_x, pancam.max_x) {
+        translation.x = translation
+            .x
+            .min(max_x - half_size.x)
+            .max(min_x + half_size.x);
+    }
+    if let (Some(min_y), Some(max_y)) = (pancam.min_y, pancam.max_y) {
+        translation.y = translation
+            .y
+            .min(max_y - half_size.y)
+            .max(min_y + half_size.y);
+    }
+
+    transform.translation = translation;
+}
+
 pub struct RenderingCameraPlugin;
 
 /// Rendering the simulation requires a camera capable of panning and zooming. This isn't a requirement for showing the main menu.
@@ -132,6 +218,49 @@ impl Plugin for RenderingCameraPlugin {
             Update,
             (insert_pancam, scale_projection).run_if(resource_exists::<VisibleGrid>()),
         );
+
+        app.add_systems(
+            Update,
+            follow_keyboard_cursor.run_if(
+                resource_exists::<KeyboardCursor>()
+                    .and_then(resource_exists::<VisibleGrid>())
+                    .and_then(is_keyboard_navigation_enabled)
+                    .and_then(in_state(AppState::TellStory)),
+            ),
+        );
+
+        app.add_systems(
+            Update,
+            (
+                cancel_camera_tour,
+                apply_deferred,
+                (
+                    note_tour_sights::<AtNest>,
+                    note_tour_sights::<AtCrater>,
+                    tour_camera::<AtNest>,
+                    tour_camera::<AtCrater>,
+                )
+                    .chain()
+                    .run_if(resource_exists::<CameraTour>()),
+            )
+                .chain()
+                .run_if(
+                    resource_exists::<CameraTour>()
+                        .and_then(resource_exists::<VisibleGrid>())
+                        .and_then(in_state(AppState::TellStory)),
+                ),
+        );
+
+        // The demo tours the colony from the start. Players take over the camera just by touching anything.
+        app.add_systems(
+            OnEnter(AppState::TellStory),
+            start_camera_tour.run_if(resource_exists::<DemoStory>()),
+        );
+
+        app.add_systems(
+            OnEnter(AppState::Cleanup),
+            remove_camera_tour.in_set(CleanupSet::BeforeSimulationCleanup),
+        );
     }
 }
 
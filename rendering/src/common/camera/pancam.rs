@@ -7,7 +7,7 @@ use bevy::{
     window::PrimaryWindow,
 };
 
-use crate::common::pointer::IsPointerCaptured;
+use crate::common::pointer::{is_painting, IsPointerCaptured};
 
 /// Plugin that adds the necessary systems for `PanCam` components to work
 #[derive(Default)]
@@ -44,7 +44,9 @@ impl Plugin for PanCamPlugin {
 
         app.init_resource::<EguiWantsFocus>().configure_sets(
             Update,
-            PanCamSystemSet.run_if(resource_exists_and_equals(IsPointerCaptured(false))),
+            PanCamSystemSet.run_if(
+                resource_exists_and_equals(IsPointerCaptured(false)).and_then(not(is_painting)),
+            ),
         );
     }
 }
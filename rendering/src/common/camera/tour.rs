@@ -0,0 +1,267 @@
+// An ambient, documentary-style camera which drifts between interesting sights in the colony.
+// Intended for leaving the simulation running on a second monitor. Any user input returns control to the user.
+// When motion is reduced, the camera cuts between sights and holds still on each rather than drifting and following.
+use super::{get_best_fit_scale, pancam::PanCam, RenderingCamera};
+use crate::common::{reduced_motion::ReducedMotion, visible_grid::VisibleGrid};
+use bevy::{
+    input::{mouse::MouseWheel, touch::TouchInput},
+    prelude::*,
+    window::PrimaryWindow,
+};
+use simulation::{
+    common::{grid::Grid, position::Position, Zone},
+    nest_simulation::{
+        ant::{Ant, AntInventory, AntRole, Dead},
+        element::Element,
+    },
+};
+
+// How long the camera lingers on a sight before moving on to the next.
+const TOUR_DWELL_SECONDS: f32 = 12.0;
+// How quickly the camera catches up with its target. Lower values give a slower, gentler pan.
+const TOUR_PAN_RATE: f32 = 0.5;
+// Zoom in far enough that individual ants can be seen, relative to fitting the whole grid on screen.
+const TOUR_ZOOM: f32 = 0.4;
+// Only the most recent sights are worth visiting. Older ones have likely moved on to doing something else.
+const MAX_TOUR_SIGHTS: usize = 10;
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+enum TourSightKind {
+    Digging,
+    Foraging,
+    Queen,
+}
+
+// Visited in this order, repeatedly, so the tour doesn't dwell on one kind of activity.
+const TOUR_SIGHT_ORDER: [TourSightKind; 3] = [
+    TourSightKind::Digging,
+    TourSightKind::Queen,
+    TourSightKind::Foraging,
+];
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+struct TourSight {
+    kind: TourSightKind,
+    ant_entity: Entity,
+}
+
+/// The camera is touring while this resource exists. Remove it to stop touring.
+#[derive(Resource)]
+pub struct CameraTour {
+    dwell_timer: Timer,
+    sight_index: usize,
+    target_ant_entity: Option<Entity>,
+    recent_sights: Vec<TourSight>,
+}
+
+impl Default for CameraTour {
+    fn default() -> Self {
+        Self {
+            // Start finished so a sight is chosen immediately.
+            dwell_timer: Timer::from_seconds(0.0, TimerMode::Once),
+            sight_index: 0,
+            target_ant_entity: None,
+            recent_sights: vec![],
+        }
+    }
+}
+
+impl CameraTour {
+    fn note_sight(&mut self, kind: TourSightKind, ant_entity: Entity) {
+        self.recent_sights
+            .retain(|sight| sight.ant_entity != ant_entity);
+
+        if self.recent_sights.len() >= MAX_TOUR_SIGHTS {
+            self.recent_sights.remove(0);
+        }
+
+        self.recent_sights.push(TourSight { kind, ant_entity });
+    }
+}
+
+/// Ants announce what they're up to by changing what they carry.
+/// Picking up dirt or sand means digging is underway. Picking up food means a forager is about to head home.
+pub fn note_tour_sights<Z: Zone>(
+    ants_query: Query<(Entity, &AntInventory), (Changed<AntInventory>, With<Z>, Without<Dead>)>,
+    elements_query: Query<&Element>,
+    mut camera_tour: ResMut<CameraTour>,
+) {
+    for (ant_entity, inventory) in ants_query.iter() {
+        let element = match inventory
+            .last()
+            .and_then(|element_entity| elements_query.get(element_entity).ok())
+        {
+            Some(element) => *element,
+            None => continue,
+        };
+
+        match element {
+            Element::Dirt | Element::Sand | Element::Mud => {
+                camera_tour.note_sight(TourSightKind::Digging, ant_entity)
+            }
+            Element::Food => camera_tour.note_sight(TourSightKind::Foraging, ant_entity),
+            _ => {}
+        }
+    }
+}
+
+pub fn tour_camera<Z: Zone>(
+    mut camera_query: Query<
+        (&PanCam, &mut Transform, &mut OrthographicProjection),
+        With<RenderingCamera>,
+    >,
+    ants_query: Query<(Entity, &Position, &AntRole), (With<Ant>, With<Z>, Without<Dead>)>,
+    grid_query: Query<&Grid, With<Z>>,
+    primary_window_query: Query<&Window, With<PrimaryWindow>>,
+    visible_grid: Res<VisibleGrid>,
+    mut camera_tour: ResMut<CameraTour>,
+    reduced_motion: ReducedMotion,
+    time: Res<Time>,
+) {
+    let visible_grid_entity = match visible_grid.0 {
+        Some(visible_grid_entity) => visible_grid_entity,
+        None => return,
+    };
+
+    let grid = match grid_query.get(visible_grid_entity) {
+        Ok(grid) => grid,
+        Err(_) => return,
+    };
+
+    let (pancam, mut transform, mut projection) = match camera_query.get_single_mut() {
+        Ok(camera) => camera,
+        Err(_) => return,
+    };
+
+    camera_tour.dwell_timer.tick(time.delta());
+
+    let is_target_gone = camera_tour
+        .target_ant_entity
+        .map_or(true, |ant_entity| !ants_query.contains(ant_entity));
+
+    let is_new_sight = camera_tour.dwell_timer.finished() || is_target_gone;
+    if is_new_sight {
+        choose_next_sight(&mut camera_tour, &ants_query);
+    }
+
+    if reduced_motion.is_enabled() && !is_new_sight {
+        return;
+    }
+
+    let target_position = match camera_tour
+        .target_ant_entity
+        .and_then(|ant_entity| ants_query.get(ant_entity).ok())
+    {
+        Some((_, position, _)) => *position,
+        None => return,
+    };
+
+    let window = primary_window_query.single();
+    let tour_scale = get_best_fit_scale(
+        window.width(),
+        window.height(),
+        grid.width() as f32,
+        grid.height() as f32,
+    ) * TOUR_ZOOM;
+
+    // Ease toward the target independent of frame rate, or cut straight to it when motion is reduced.
+    let ease = if reduced_motion.is_enabled() {
+        1.0
+    } else {
+        1.0 - (-TOUR_PAN_RATE * time.delta_seconds()).exp()
+    };
+
+    let scale = projection.scale + (tour_scale - projection.scale) * ease;
+    if (scale - projection.scale).abs() > f32::EPSILON {
+        projection.scale = scale;
+    }
+
+    let target_translation = grid
+        .grid_to_world_position(target_position)
+        .truncate()
+        .extend(transform.translation.z);
+    let mut translation = transform.translation.lerp(target_translation, ease);
+
+    // Keep the camera within the grid, the same as when panning by hand, using the size of the projection this frame.
+    let half_size = Vec2::new(window.width(), window.height()) * projection.scale / 2.0;
+    if let (Some(min_x), Some(max_x)) = (pancam.min_x, pancam.max_x) {
+        translation.x = translation
+            .x
+            .min(max_x - half_size.x)
+            .max(min_x + half_size.x);
+    }
+    if let (Some(min_y), Some(max_y)) = (pancam.min_y, pancam.max_y) {
+        translation.y = translation
+            .y
+            .min(max_y - half_size.y)
+            .max(min_y + half_size.y);
+    }
+
+    transform.translation = translation;
+}
+
+/// Move on to the next kind of sight. Fall back to the queen, or anyone at all, if nothing interesting has happened lately.
+fn choose_next_sight<Z: Zone>(
+    camera_tour: &mut CameraTour,
+    ants_query: &Query<(Entity, &Position, &AntRole), (With<Ant>, With<Z>, Without<Dead>)>,
+) {
+    camera_tour
+        .recent_sights
+        .retain(|sight| ants_query.contains(sight.ant_entity));
+
+    let kind = TOUR_SIGHT_ORDER[camera_tour.sight_index % TOUR_SIGHT_ORDER.len()];
+    camera_tour.sight_index += 1;
+
+    let queen_entity = ants_query
+        .iter()
+        .find(|(_, _, role)| **role == AntRole::Queen)
+        .map(|(ant_entity, _, _)| ant_entity);
+
+    let sight_entity = match kind {
+        TourSightKind::Queen => queen_entity,
+        _ => camera_tour
+            .recent_sights
+            .iter()
+            .rev()
+            .find(|sight| sight.kind == kind)
+            .map(|sight| sight.ant_entity),
+    };
+
+    camera_tour.target_ant_entity = sight_entity.or(queen_entity).or_else(|| {
+        ants_query
+            .iter()
+            .next()
+            .map(|(ant_entity, _, _)| ant_entity)
+    });
+
+    camera_tour.dwell_timer = Timer::from_seconds(TOUR_DWELL_SECONDS, TimerMode::Once);
+}
+
+/// Any input means the user wants control of the camera back.
+/// NOTE: Moving the mouse isn't considered input because the cursor passes over the window when using another monitor.
+pub fn cancel_camera_tour(
+    keyboard_input: Res<Input<KeyCode>>,
+    mouse_input: Res<Input<MouseButton>>,
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    mut touch_events: EventReader<TouchInput>,
+    mut commands: Commands,
+) {
+    let has_wheel_input = mouse_wheel_events.read().count() > 0;
+    let has_touch_input = touch_events.read().count() > 0;
+
+    if keyboard_input.get_just_pressed().next().is_some()
+        || mouse_input.get_just_pressed().next().is_some()
+        || has_wheel_input
+        || has_touch_input
+    {
+        commands.remove_resource::<CameraTour>();
+    }
+}
+
+pub fn start_camera_tour(mut commands: Commands) {
+    commands.init_resource::<CameraTour>();
+}
+
+pub fn remove_camera_tour(mut commands: Commands) {
+    commands.remove_resource::<CameraTour>();
+}
@@ -0,0 +1,243 @@
+use super::{
+    pointer::{
+        get_paint_positions, is_group_select_key_pressed, is_paint_key_pressed, GridTap, PaintShape,
+    },
+    visible_grid::VisibleGrid,
+};
+use bevy::prelude::*;
+use simulation::{
+    common::{grid::Grid, position::Position, Zone},
+    settings::Settings,
+};
+
+// Holding an arrow key keeps the cursor moving, after a short pause, the same as holding a key down while typing.
+const CURSOR_REPEAT_DELAY_SECONDS: f32 = 0.4;
+const CURSOR_REPEAT_INTERVAL_SECONDS: f32 = 0.05;
+const ARROW_KEYS: [KeyCode; 4] = [KeyCode::Up, KeyCode::Down, KeyCode::Left, KeyCode::Right];
+// Render the cursor above ants, and selection outlines, so it's never hidden.
+const CURSOR_Z_INDEX: f32 = 3.5;
+
+/// Menus have claimed the keyboard, e.g. because a button has focus or text is being typed, so keys shouldn't reach the grid.
+#[derive(Resource, Default, PartialEq)]
+pub struct IsKeyboardCaptured(pub bool);
+
+/// A cursor over the cells of the visible grid, moved with the arrow keys, for playing without a pointer.
+#[derive(Resource, Default)]
+pub struct KeyboardCursor {
+    position: Option<Position>,
+    // Painting a shape needs a start and an end, but there's no dragging without a pointer, so the start is marked first.
+    paint_start: Option<Position>,
+    repeat_timer: Option<Timer>,
+}
+
+impl KeyboardCursor {
+    pub fn position(&self) -> Option<Position> {
+        self.position
+    }
+}
+
+#[derive(Component)]
+pub struct KeyboardCursorSprite;
+
+pub fn initialize_keyboard_cursor_resources(mut commands: Commands) {
+    commands.init_resource::<KeyboardCursor>();
+    commands.init_resource::<IsKeyboardCaptured>();
+}
+
+pub fn remove_keyboard_cursor_resources(mut commands: Commands) {
+    commands.remove_resource::<KeyboardCursor>();
+    commands.remove_resource::<IsKeyboardCaptured>();
+}
+
+pub fn is_keyboard_navigation_enabled(settings: Option<Res<Settings>>) -> bool {
+    settings.map_or(false, |settings| settings.is_keyboard_navigation)
+}
+
+/// Positions differ between grids so the cursor starts over, from the center, whenever another grid is shown.
+pub fn reset_keyboard_cursor(mut keyboard_cursor: ResMut<KeyboardCursor>) {
+    *keyboard_cursor = KeyboardCursor::default();
+}
+
+/// The arrow keys move the cursor around the visible grid. Enter, or space, acts on the cell under the cursor exactly
+/// as tapping on it would, including holding control to select a group.
+/// Holding shift, while an action which can paint is selected, marks where a shape starts and then paints it to
+/// wherever the cursor is the next time.
+pub fn handle_keyboard_cursor<Z: Zone + Copy>(
+    keyboard_input: Res<Input<KeyCode>>,
+    time: Res<Time>,
+    grid_query: Query<(&Grid, &Z)>,
+    visible_grid: Res<VisibleGrid>,
+    is_keyboard_captured: Res<IsKeyboardCaptured>,
+    paint_shape: Res<PaintShape>,
+    mut keyboard_cursor: ResMut<KeyboardCursor>,
+    mut grid_tap: GridTap<Z>,
+) {
+    if is_keyboard_captured.0 {
+        return;
+    }
+
+    let visible_grid_entity = match visible_grid.0 {
+        Some(visible_grid_entity) => visible_grid_entity,
+        None => return,
+    };
+
+    let (grid, zone) = match grid_query.get(visible_grid_entity) {
+        Ok(grid) => grid,
+        Err(_) => return,
+    };
+
+    let mut position = keyboard_cursor
+        .position
+        .unwrap_or_else(|| Position::new(grid.width() / 2, grid.height() / 2));
+
+    let direction = get_arrow_key_direction(&keyboard_input);
+
+    let is_moving = if direction == Position::ZERO {
+        keyboard_cursor.repeat_timer = None;
+        false
+    } else if keyboard_input.any_just_pressed(ARROW_KEYS) {
+        keyboard_cursor.repeat_timer = Some(Timer::from_seconds(
+            CURSOR_REPEAT_DELAY_SECONDS,
+            TimerMode::Once,
+        ));
+        true
+    } else {
+        keyboard_cursor
+            .repeat_timer
+            .as_mut()
+            .map_or(false, |repeat_timer| {
+                repeat_timer.tick(time.delta());
+
+                if repeat_timer.just_finished() {
+                    *repeat_timer =
+                        Timer::from_seconds(CURSOR_REPEAT_INTERVAL_SECONDS, TimerMode::Once);
+                    true
+                } else {
+                    false
+                }
+            })
+    };
+
+    if is_moving {
+        position = Position::new(
+            (position.x + direction.x).clamp(0, grid.width() - 1),
+            (position.y + direction.y).clamp(0, grid.height() - 1),
+        );
+    }
+
+    keyboard_cursor.position = Some(position);
+
+    if !keyboard_input.any_just_pressed([KeyCode::Return, KeyCode::Space]) {
+        return;
+    }
+
+    if grid_tap.pointer_action().can_paint() && is_paint_key_pressed(&keyboard_input) {
+        match keyboard_cursor.paint_start.take() {
+            Some(paint_start) => {
+                let positions = get_paint_positions(
+                    paint_start,
+                    position,
+                    *paint_shape,
+                    grid,
+                    grid_tap.is_paint_mirrored(),
+                );
+
+                grid_tap.paint(positions, *zone);
+            }
+            None => keyboard_cursor.paint_start = Some(position),
+        }
+
+        return;
+    }
+
+    grid_tap.tap(
+        position,
+        grid,
+        *zone,
+        is_group_select_key_pressed(&keyboard_input),
+    );
+}
+
+fn get_arrow_key_direction(keyboard_input: &Input<KeyCode>) -> Position {
+    let mut direction = Position::ZERO;
+
+    // NOTE: Grid positions increase downward so up is negative y.
+    if keyboard_input.pressed(KeyCode::Up) {
+        direction.y -= 1;
+    }
+    if keyboard_input.pressed(KeyCode::Down) {
+        direction.y += 1;
+    }
+    if keyboard_input.pressed(KeyCode::Left) {
+        direction.x -= 1;
+    }
+    if keyboard_input.pressed(KeyCode::Right) {
+        direction.x += 1;
+    }
+
+    direction
+}
+
+/// Outline the cell under the cursor. The outline changes color while a shape's start has been marked, and is waiting
+/// for its end, so it's clear the next press will paint.
+pub fn update_keyboard_cursor_sprite(
+    keyboard_cursor: Res<KeyboardCursor>,
+    mut keyboard_cursor_sprite_query: Query<
+        (Entity, &mut Transform, &mut Sprite),
+        With<KeyboardCursorSprite>,
+    >,
+    grid_query: Query<&Grid>,
+    visible_grid: Res<VisibleGrid>,
+    settings: Res<Settings>,
+    asset_server: Res<AssetServer>,
+    mut commands: Commands,
+) {
+    let grid = visible_grid
+        .0
+        .and_then(|visible_grid_entity| grid_query.get(visible_grid_entity).ok());
+
+    let position = keyboard_cursor
+        .position
+        .filter(|_| settings.is_keyboard_navigation);
+
+    let (grid, position) = match (grid, position) {
+        (Some(grid), Some(position)) => (grid, position),
+        _ => {
+            for (sprite_entity, _, _) in keyboard_cursor_sprite_query.iter() {
+                commands.entity(sprite_entity).despawn();
+            }
+
+            return;
+        }
+    };
+
+    let mut translation = grid.grid_to_world_position(position);
+    translation.z = CURSOR_Z_INDEX;
+
+    let color = if keyboard_cursor.paint_start.is_some() {
+        Color::ORANGE
+    } else {
+        Color::CYAN
+    };
+
+    if let Ok((_, mut transform, mut sprite)) = keyboard_cursor_sprite_query.get_single_mut() {
+        transform.translation = translation;
+        sprite.color = color;
+
+        return;
+    }
+
+    commands.spawn((
+        SpriteBundle {
+            transform: Transform::from_translation(translation),
+            texture: asset_server.load("images/selection.png"),
+            sprite: Sprite {
+                color,
+                custom_size: Some(Vec2::ONE),
+                ..default()
+            },
+            ..default()
+        },
+        KeyboardCursorSprite,
+    ));
+}
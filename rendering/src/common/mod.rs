@@ -1,16 +1,38 @@
+pub mod airdrop;
+pub mod ant_label;
 pub mod camera;
+pub mod keyboard_cursor;
+pub mod placement_preview;
 pub mod pointer;
+pub mod reduced_motion;
 pub mod selection;
 pub mod visible_grid;
+pub mod visual_update;
 
 use self::{
+    ant_label::update_ant_label_visibility,
     camera::RenderingCameraPlugin,
-    pointer::{handle_pointer_tap, initialize_pointer_resources, remove_pointer_resources},
+    keyboard_cursor::{
+        handle_keyboard_cursor, initialize_keyboard_cursor_resources,
+        is_keyboard_navigation_enabled, remove_keyboard_cursor_resources, reset_keyboard_cursor,
+        update_keyboard_cursor_sprite, KeyboardCursorSprite,
+    },
+    placement_preview::{update_placement_preview, PlacementPreview},
+    pointer::{
+        handle_pointer_tap, initialize_pointer_resources, remove_pointer_resources,
+        update_player_cursor,
+    },
     selection::{
-        clear_selection, on_update_selected, on_update_selected_position, SelectedEntity,
+        clear_selection, on_update_selected, on_update_selected_position,
+        update_group_selection_sprites, GroupSelectionSprite, SelectedEntity, SelectedGroup,
         SelectionSprite,
     },
     visible_grid::{set_visible_grid_state_none, VisibleGrid, VisibleGridState},
+    visual_update::{
+        initialize_visual_update_resources, is_visual_update_forced,
+        remove_visual_update_resources, update_is_visual_update_due, IsVisualUpdateDue,
+        VisualUpdateSet,
+    },
 };
 use bevy::{prelude::*, utils::HashMap};
 use bevy_ecs_tilemap::TilemapPlugin;
@@ -108,32 +130,81 @@ impl Plugin for CommonRenderingPlugin {
 
         app.add_systems(
             OnEnter(AppState::FinishSetup),
-            (initialize_common_resources, initialize_pointer_resources)
+            (
+                initialize_common_resources,
+                initialize_pointer_resources,
+                initialize_keyboard_cursor_resources,
+                initialize_visual_update_resources,
+            )
                 .in_set(FinishSetupSet::BeforeSimulationFinishSetup),
         );
 
         app.add_systems(
+            PreUpdate,
+            update_is_visual_update_due.run_if(resource_exists::<IsVisualUpdateDue>()),
+        );
+
+        app.configure_sets(
             Update,
-            (on_update_selected, on_update_selected_position).run_if(in_state(AppState::TellStory)),
+            VisualUpdateSet.run_if(
+                resource_exists_and_equals(IsVisualUpdateDue(true))
+                    .or_else(is_visual_update_forced),
+            ),
+        );
+
+        app.add_systems(
+            Update,
+            (
+                on_update_selected,
+                on_update_selected_position,
+                update_group_selection_sprites,
+                update_keyboard_cursor_sprite,
+                update_ant_label_visibility,
+            )
+                .run_if(in_state(AppState::TellStory)),
         );
 
         // IMPORTANT: don't process user input in FixedUpdate/SimulationUpdate because event reads can be missed
         // https://github.com/bevyengine/bevy/issues/7691
         app.add_systems(
             Update,
-            (handle_pointer_tap::<AtNest>, handle_pointer_tap::<AtCrater>)
+            (
+                handle_pointer_tap::<AtNest>,
+                handle_pointer_tap::<AtCrater>,
+                (
+                    handle_keyboard_cursor::<AtNest>,
+                    handle_keyboard_cursor::<AtCrater>,
+                )
+                    .chain()
+                    .run_if(is_keyboard_navigation_enabled),
+                update_placement_preview::<AtNest>,
+                update_placement_preview::<AtCrater>,
+                update_player_cursor,
+            )
                 .run_if(in_state(AppState::TellStory))
                 .chain(),
         );
 
         app.add_systems(
             OnExit(VisibleGridState::Nest),
-            (clear_selection,).run_if(in_state(AppState::TellStory)),
+            (
+                clear_selection,
+                reset_keyboard_cursor,
+                despawn_view::<PlacementPreview>,
+                despawn_view::<KeyboardCursorSprite>,
+            )
+                .run_if(in_state(AppState::TellStory)),
         );
 
         app.add_systems(
             OnExit(VisibleGridState::Crater),
-            (clear_selection,).run_if(in_state(AppState::TellStory)),
+            (
+                clear_selection,
+                reset_keyboard_cursor,
+                despawn_view::<PlacementPreview>,
+                despawn_view::<KeyboardCursorSprite>,
+            )
+                .run_if(in_state(AppState::TellStory)),
         );
 
         app.add_systems(
@@ -154,6 +225,8 @@ impl Plugin for CommonRenderingPlugin {
                 despawn_common_entities,
                 remove_common_resources,
                 remove_pointer_resources,
+                remove_keyboard_cursor_resources,
+                remove_visual_update_resources,
                 set_visible_grid_state_none,
             )
                 .in_set(CleanupSet::BeforeSimulationCleanup),
@@ -164,11 +237,13 @@ impl Plugin for CommonRenderingPlugin {
 fn initialize_common_resources(mut commands: Commands) {
     commands.init_resource::<ModelViewEntityMap>();
     commands.init_resource::<SelectedEntity>();
+    commands.init_resource::<SelectedGroup>();
     commands.init_resource::<VisibleGrid>();
 }
 
 fn remove_common_resources(mut commands: Commands) {
     commands.remove_resource::<SelectedEntity>();
+    commands.remove_resource::<SelectedGroup>();
     commands.remove_resource::<VisibleGrid>();
     // TODO: removing this causes issues because camera Update runs expecting the resource to exist.
     //commands.remove_resource::<ModelViewEntityMap>();
@@ -176,9 +251,24 @@ fn remove_common_resources(mut commands: Commands) {
 
 fn despawn_common_entities(
     selection_sprite_query: Query<Entity, With<SelectionSprite>>,
+    group_selection_sprite_query: Query<Entity, With<GroupSelectionSprite>>,
+    placement_preview_query: Query<Entity, With<PlacementPreview>>,
+    keyboard_cursor_sprite_query: Query<Entity, With<KeyboardCursorSprite>>,
     mut commands: Commands,
 ) {
     if let Ok(selection_sprite_entity) = selection_sprite_query.get_single() {
         commands.entity(selection_sprite_entity).despawn();
     }
+
+    for group_selection_sprite_entity in group_selection_sprite_query.iter() {
+        commands.entity(group_selection_sprite_entity).despawn();
+    }
+
+    for placement_preview_entity in placement_preview_query.iter() {
+        commands.entity(placement_preview_entity).despawn();
+    }
+
+    for keyboard_cursor_sprite_entity in keyboard_cursor_sprite_query.iter() {
+        commands.entity(keyboard_cursor_sprite_entity).despawn();
+    }
 }
@@ -0,0 +1,205 @@
+use super::{
+    camera::RenderingCamera,
+    pointer::{
+        get_paint_positions, is_paint_key_pressed, mirror_positions, IsPaintMirrored,
+        IsPointerCaptured, PaintShape, PointerAction, PointerTapState,
+    },
+    visible_grid::VisibleGrid,
+};
+use crate::nest::element::sprite_sheet::{get_element_index, ElementTextureAtlasHandle};
+use bevy::{prelude::*, window::PrimaryWindow};
+use simulation::{
+    common::{
+        grid::{Grid, GridElements},
+        position::Position,
+        Zone,
+    },
+    nest_simulation::element::{
+        definition::ElementDefinitions, Element, ElementExposure, FoodKind,
+    },
+};
+
+// Previews are see-through so the elements they'd replace remain visible underneath.
+const PLACEMENT_PREVIEW_ALPHA: f32 = 0.5;
+// Previews are drawn in front of elements, but behind ants, so it's still clear which ants are where.
+const PLACEMENT_PREVIEW_Z_INDEX: f32 = 0.5;
+
+#[derive(Component)]
+pub struct PlacementPreview;
+
+/// Show what an element action would do if the user tapped, or finished painting, right now.
+/// Positions where the action would have no effect, e.g. placing food where there's already dirt, are shown in red.
+pub fn update_placement_preview<Z: Zone>(
+    mouse_input: Res<Input<MouseButton>>,
+    keyboard_input: Res<Input<KeyCode>>,
+    primary_window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<RenderingCamera>>,
+    grid_query: Query<&Grid, With<Z>>,
+    grid_elements: GridElements<Z>,
+    visible_grid: Res<VisibleGrid>,
+    is_pointer_captured: Res<IsPointerCaptured>,
+    pointer_action: Res<PointerAction>,
+    pointer_tap_state: Res<PointerTapState>,
+    paint_shape: Res<PaintShape>,
+    is_paint_mirrored: Res<IsPaintMirrored>,
+    placement_preview_query: Query<Entity, With<PlacementPreview>>,
+    element_texture_atlas_handle: Res<ElementTextureAtlasHandle>,
+    mut previewed_positions: Local<Vec<(Position, bool)>>,
+    mut commands: Commands,
+) {
+    let visible_grid_entity = match visible_grid.0 {
+        Some(visible_grid_entity) => visible_grid_entity,
+        None => return,
+    };
+
+    let grid = match grid_query.get(visible_grid_entity) {
+        Ok(grid) => grid,
+        Err(_) => return,
+    };
+
+    let paint_element = pointer_action.get_paint_element();
+    let paint_food_kind = pointer_action.get_paint_food_kind();
+
+    let cursor_grid_position = primary_window_query
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .and_then(|cursor_position| {
+            let (camera, camera_transform) = camera_query.single();
+            camera.viewport_to_world_2d(camera_transform, cursor_position)
+        })
+        .map(|world_position| grid.world_to_grid_position(world_position));
+
+    let positions = match (paint_element, cursor_grid_position) {
+        (Some(_), Some(cursor_grid_position)) if !is_pointer_captured.0 => {
+            let is_painting = is_paint_key_pressed(&keyboard_input)
+                && mouse_input.pressed(MouseButton::Left)
+                && pointer_tap_state.position.is_some();
+
+            if is_painting {
+                let (camera, camera_transform) = camera_query.single();
+                let start_grid_position = pointer_tap_state
+                    .position
+                    .and_then(|tap_position| {
+                        camera.viewport_to_world_2d(camera_transform, tap_position)
+                    })
+                    .map(|world_position| grid.world_to_grid_position(world_position))
+                    .unwrap_or(cursor_grid_position);
+
+                get_paint_positions(
+                    start_grid_position,
+                    cursor_grid_position,
+                    *paint_shape,
+                    grid,
+                    is_paint_mirrored.0,
+                )
+            } else {
+                mirror_positions(vec![cursor_grid_position], grid, is_paint_mirrored.0)
+            }
+        }
+        _ => vec![],
+    };
+
+    let paint_element = paint_element.unwrap_or(Element::Air);
+    let positions = positions
+        .into_iter()
+        .filter(|position| grid.is_within_bounds(position))
+        .map(|position| {
+            (
+                position,
+                is_valid_placement(position, paint_element, &grid_elements),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    // Previews may have been despawned when switching between zones so redraw them, too, if they've gone missing.
+    if positions == *previewed_positions
+        && placement_preview_query.iter().count() == previewed_positions.len()
+    {
+        return;
+    }
+
+    for placement_preview_entity in placement_preview_query.iter() {
+        commands.entity(placement_preview_entity).despawn();
+    }
+
+    for &(position, is_valid) in positions.iter() {
+        commands.spawn((
+            get_placement_preview_bundle(
+                position,
+                paint_element,
+                paint_food_kind,
+                is_valid,
+                grid,
+                grid_elements.definitions(),
+                &element_texture_atlas_handle,
+            ),
+            PlacementPreview,
+        ));
+    }
+
+    *previewed_positions = positions;
+}
+
+/// Placing only fills empty space and removing only clears occupied space. This mirrors how painting is processed.
+fn is_valid_placement<Z: Zone>(
+    position: Position,
+    element: Element,
+    grid_elements: &GridElements<Z>,
+) -> bool {
+    if element == Element::Air {
+        grid_elements.get_entity(position).is_some() && !grid_elements.is(position, Element::Air)
+    } else {
+        grid_elements.is(position, Element::Air)
+    }
+}
+
+fn get_placement_preview_bundle(
+    position: Position,
+    element: Element,
+    food_kind: Option<FoodKind>,
+    is_valid: bool,
+    grid: &Grid,
+    element_definitions: &ElementDefinitions,
+    element_texture_atlas_handle: &Res<ElementTextureAtlasHandle>,
+) -> SpriteSheetBundle {
+    let element_exposure = ElementExposure::ALL;
+
+    // There's no sprite for Air so removal is previewed as a darkened tile.
+    let mut sprite = if element == Element::Air {
+        TextureAtlasSprite::new(get_element_index(
+            element_exposure,
+            Element::Dirt,
+            None,
+            element_definitions,
+        ))
+    } else {
+        TextureAtlasSprite::new(get_element_index(
+            element_exposure,
+            element,
+            food_kind,
+            element_definitions,
+        ))
+    };
+
+    let color = if !is_valid {
+        Color::RED
+    } else if element == Element::Air {
+        Color::BLACK
+    } else {
+        Color::WHITE
+    };
+
+    sprite.custom_size = Some(Vec2::splat(1.0));
+    sprite.color = color.with_a(PLACEMENT_PREVIEW_ALPHA);
+
+    let mut translation = grid.grid_to_world_position(position);
+    translation.z = PLACEMENT_PREVIEW_Z_INDEX;
+
+    SpriteSheetBundle {
+        transform: Transform::from_translation(translation),
+        sprite,
+        texture_atlas: element_texture_atlas_handle.0.clone(),
+        ..default()
+    }
+}
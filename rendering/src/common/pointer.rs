@@ -1,5 +1,9 @@
-use super::{camera::RenderingCamera, selection::SelectedEntity, visible_grid::VisibleGrid};
-use bevy::{prelude::*, window::PrimaryWindow};
+use super::{
+    camera::RenderingCamera,
+    selection::{SelectedEntity, SelectedGroup},
+    visible_grid::VisibleGrid,
+};
+use bevy::{ecs::system::SystemParam, prelude::*, utils::HashSet, window::PrimaryWindow};
 use simulation::{
     common::{
         grid::{Grid, GridElements},
@@ -7,7 +11,12 @@ use simulation::{
         Zone,
     },
     external_event::ExternalSimulationEvent,
-    nest_simulation::ant::Ant,
+    nest_simulation::{
+        ant::{order::OrderTask, Ant},
+        element::{Element, FoodKind},
+        nest::AtNest,
+    },
+    settings::Settings,
 };
 
 #[derive(Resource, Default, PartialEq, Copy, Clone, Debug)]
@@ -15,35 +24,137 @@ pub enum PointerAction {
     #[default]
     Select,
     DespawnElement,
-    SpawnFood,
+    SpawnFood(FoodKind),
+    SpawnPrey,
     SpawnDirt,
     SpawnSand,
+    SpawnWater,
+    SpawnStone,
     KillAnt,
     SpawnWorkerAnt,
+    SpawnSoldierAnt,
     DespawnWorkerAnt,
+    TriggerCollapse,
+    MarkDig,
+    CancelDig,
+    OrderDig,
+    OrderGoTo,
+    Land,
 }
 
+/// The event sent when tapping on `position`. Selecting, and giving orders, aren't simulation events so they have none.
 pub fn pointer_action_to_simulation_event<Z: Zone>(
     pointer_action: PointerAction,
     position: Position,
     zone: Z,
-) -> ExternalSimulationEvent<Z> {
-    match pointer_action {
-        PointerAction::Select => {
-            panic!("Cannot convert PointerAction::Select to ExternalSimulationEvent")
+) -> Option<ExternalSimulationEvent<Z>> {
+    let event = match pointer_action {
+        PointerAction::Select | PointerAction::OrderDig | PointerAction::OrderGoTo => {
+            return None;
         }
         PointerAction::DespawnElement => ExternalSimulationEvent::DespawnElement(position, zone),
-        PointerAction::SpawnFood => ExternalSimulationEvent::SpawnFood(position, zone),
+        PointerAction::SpawnFood(food_kind) => {
+            ExternalSimulationEvent::SpawnFood(position, food_kind, zone)
+        }
+        PointerAction::SpawnPrey => ExternalSimulationEvent::SpawnPrey(position, zone),
         PointerAction::SpawnDirt => ExternalSimulationEvent::SpawnDirt(position, zone),
         PointerAction::SpawnSand => ExternalSimulationEvent::SpawnSand(position, zone),
+        PointerAction::SpawnWater => ExternalSimulationEvent::SpawnWater(position, zone),
+        PointerAction::SpawnStone => ExternalSimulationEvent::SpawnStone(position, zone),
         PointerAction::KillAnt => ExternalSimulationEvent::KillAnt(position, zone),
         PointerAction::SpawnWorkerAnt => ExternalSimulationEvent::SpawnWorkerAnt(position, zone),
+        PointerAction::SpawnSoldierAnt => ExternalSimulationEvent::SpawnSoldierAnt(position, zone),
         PointerAction::DespawnWorkerAnt => {
             ExternalSimulationEvent::DespawnWorkerAnt(position, zone)
         }
+        PointerAction::TriggerCollapse => ExternalSimulationEvent::TriggerCollapse(position, zone),
+        PointerAction::Land => ExternalSimulationEvent::Land(position, zone),
+        PointerAction::MarkDig => ExternalSimulationEvent::MarkDig {
+            zone,
+            positions: vec![position],
+        },
+        PointerAction::CancelDig => ExternalSimulationEvent::CancelDig {
+            zone,
+            positions: vec![position],
+        },
+    };
+
+    Some(event)
+}
+
+impl PointerAction {
+    /// The element painted by dragging out a shape. Only actions which place, or remove, elements can paint.
+    pub fn get_paint_element(&self) -> Option<Element> {
+        match self {
+            PointerAction::DespawnElement => Some(Element::Air),
+            PointerAction::SpawnFood(_) => Some(Element::Food),
+            PointerAction::SpawnPrey => Some(Element::Prey),
+            PointerAction::SpawnDirt => Some(Element::Dirt),
+            PointerAction::SpawnSand => Some(Element::Sand),
+            PointerAction::SpawnWater => Some(Element::Water),
+            PointerAction::SpawnStone => Some(Element::Stone),
+            _ => None,
+        }
+    }
+
+    /// The kind of food painted alongside the painted element. Only actions which place food have one.
+    pub fn get_paint_food_kind(&self) -> Option<FoodKind> {
+        match self {
+            PointerAction::SpawnFood(food_kind) => Some(*food_kind),
+            _ => None,
+        }
+    }
+
+    /// Painting places, or removes, elements and marks, or unmarks, dirt for digging.
+    pub fn can_paint(&self) -> bool {
+        self.get_paint_element().is_some()
+            || matches!(self, PointerAction::MarkDig | PointerAction::CancelDig)
+    }
+
+    /// The event sent once a shape has been dragged out over `positions`. Only actions which can paint send one.
+    pub fn get_paint_event<Z: Zone>(
+        &self,
+        positions: Vec<Position>,
+        zone: Z,
+    ) -> Option<ExternalSimulationEvent<Z>> {
+        match self {
+            PointerAction::MarkDig => Some(ExternalSimulationEvent::MarkDig { zone, positions }),
+            PointerAction::CancelDig => {
+                Some(ExternalSimulationEvent::CancelDig { zone, positions })
+            }
+            _ => self
+                .get_paint_element()
+                .map(|element| ExternalSimulationEvent::PaintElements {
+                    zone,
+                    positions,
+                    element,
+                    food_kind: self.get_paint_food_kind(),
+                }),
+        }
+    }
+
+    /// The order given to the selected group when tapping on `position`. Only order actions give orders.
+    pub fn get_order_task(&self, position: Position) -> Option<OrderTask> {
+        match self {
+            PointerAction::OrderDig => Some(OrderTask::Dig(position)),
+            PointerAction::OrderGoTo => Some(OrderTask::GoTo(position)),
+            _ => None,
+        }
     }
 }
 
+/// The shape painted when shift-dragging with an element action selected.
+#[derive(Resource, Default, PartialEq, Copy, Clone, Debug)]
+pub enum PaintShape {
+    #[default]
+    Line,
+    Rectangle,
+}
+
+/// Repeat actions on the opposite side of the grid, mirrored around its vertical center line.
+#[derive(Resource, Default, PartialEq, Copy, Clone, Debug)]
+pub struct IsPaintMirrored(pub bool);
+
 #[derive(Resource, Default)]
 pub struct PointerTapState {
     pub position: Option<Vec2>,
@@ -53,32 +164,177 @@ pub fn initialize_pointer_resources(mut commands: Commands) {
     commands.init_resource::<PointerAction>();
     commands.init_resource::<PointerTapState>();
     commands.init_resource::<IsPointerCaptured>();
+    commands.init_resource::<PaintShape>();
+    commands.init_resource::<IsPaintMirrored>();
 }
 
 pub fn remove_pointer_resources(mut commands: Commands) {
     commands.remove_resource::<PointerAction>();
     commands.remove_resource::<PointerTapState>();
     commands.remove_resource::<IsPointerCaptured>();
+    commands.remove_resource::<PaintShape>();
+    commands.remove_resource::<IsPaintMirrored>();
+}
+
+/// Holding shift, while an action which can paint is selected, turns dragging into painting rather than panning the camera.
+pub fn is_painting(
+    keyboard_input: Res<Input<KeyCode>>,
+    pointer_action: Option<Res<PointerAction>>,
+) -> bool {
+    pointer_action.map_or(false, |pointer_action| {
+        pointer_action.can_paint() && is_paint_key_pressed(&keyboard_input)
+    })
+}
+
+pub fn is_paint_key_pressed(keyboard_input: &Input<KeyCode>) -> bool {
+    keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight])
+}
+
+/// Holding control while selecting adds, or removes, ants from the selected group rather than inspecting them.
+pub fn is_group_select_key_pressed(keyboard_input: &Input<KeyCode>) -> bool {
+    keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight])
 }
 
 const DRAG_THRESHOLD: f32 = 4.0;
 
+/// Everything needed to act on a single grid position, whether it was tapped with a pointer or chosen with the keyboard
+/// cursor.
+#[derive(SystemParam)]
+pub struct GridTap<'w, 's, Z: Zone> {
+    grid_elements: GridElements<'w, 's, Z>,
+    pointer_action: ResMut<'w, PointerAction>,
+    is_paint_mirrored: Res<'w, IsPaintMirrored>,
+    external_simulation_event_writer: EventWriter<'w, ExternalSimulationEvent<Z>>,
+    ants_query: Query<'w, 's, (Entity, &'static Position), (With<Ant>, With<Z>)>,
+    selected_entity: ResMut<'w, SelectedEntity>,
+    selected_group: ResMut<'w, SelectedGroup>,
+    settings: Res<'w, Settings>,
+}
+
+impl<'w, 's, Z: Zone + Copy> GridTap<'w, 's, Z> {
+    pub fn pointer_action(&self) -> PointerAction {
+        *self.pointer_action
+    }
+
+    pub fn is_paint_mirrored(&self) -> bool {
+        self.is_paint_mirrored.0
+    }
+
+    /// Paint every one of `positions` at once. Actions which can't paint do nothing.
+    pub fn paint(&mut self, positions: Vec<Position>, zone: Z) {
+        if let Some(paint_event) = self.pointer_action.get_paint_event(positions, zone) {
+            self.external_simulation_event_writer.send(paint_event);
+        }
+    }
+
+    /// Carry out the selected action at `grid_position`. Orders are given to the selected group, elements and ants are
+    /// placed or removed, or, when selecting, whatever is at `grid_position` is selected.
+    /// Selecting as a group adds, or removes, the ant at `grid_position` from the selected group rather than inspecting it.
+    /// Ants can't be given orders in Story Mode so there are no groups to select and no orders to send.
+    pub fn tap(&mut self, grid_position: Position, grid: &Grid, zone: Z, is_group_select: bool) {
+        if let Some(task) = self.pointer_action.get_order_task(grid_position) {
+            if !self.settings.is_story_mode {
+                self.external_simulation_event_writer
+                    .send(ExternalSimulationEvent::OrderAnts {
+                        zone,
+                        ant_entities: self.selected_group.0.clone(),
+                        task,
+                    });
+            }
+
+            // Orders are one-off so go back to selecting rather than sending the same order on every tap.
+            *self.pointer_action = PointerAction::Select;
+
+            return;
+        }
+
+        // There's only the one queen to land so, like orders, landing is one-off and never mirrored.
+        if *self.pointer_action == PointerAction::Land {
+            if let Some(event) =
+                pointer_action_to_simulation_event(PointerAction::Land, grid_position, zone)
+            {
+                self.external_simulation_event_writer.send(event);
+            }
+
+            *self.pointer_action = PointerAction::Select;
+
+            return;
+        }
+
+        if *self.pointer_action != PointerAction::Select {
+            for position in mirror_positions(vec![grid_position], grid, self.is_paint_mirrored.0) {
+                if let Some(event) =
+                    pointer_action_to_simulation_event(*self.pointer_action, position, zone)
+                {
+                    self.external_simulation_event_writer.send(event);
+                }
+            }
+
+            return;
+        }
+
+        // TODO: Support multiple ants at a given position. Need to select them in a fixed order so that there's a "last ant" so that selecting Element is possible afterward.
+        let ant_entity_at_position = self
+            .ants_query
+            .iter()
+            .find(|(_, &position)| position == grid_position)
+            .map(|(entity, _)| entity);
+
+        if is_group_select && !self.settings.is_story_mode {
+            if let Some(ant_entity) = ant_entity_at_position {
+                self.selected_group.toggle(ant_entity);
+            }
+
+            return;
+        }
+
+        let element_entity_at_position = self.grid_elements.get_entity(grid_position);
+
+        let currently_selected_entity = self.selected_entity.0;
+
+        if let Some(ant_entity) = ant_entity_at_position {
+            // If tapping on an already selected ant then consider selecting element underneath ant instead.
+            if ant_entity_at_position == currently_selected_entity {
+                if let Some(element_entity) = element_entity_at_position {
+                    self.selected_entity.0 = Some(*element_entity);
+                } else {
+                    self.selected_entity.0 = None;
+                }
+            } else {
+                // If there is an ant at the given position, and it's not selected, but the element underneath it is selected
+                // then assume user wants to deselect element and not select the ant. They can select again after if they want the ant.
+                if element_entity_at_position == currently_selected_entity.as_ref() {
+                    self.selected_entity.0 = None;
+                } else {
+                    self.selected_entity.0 = Some(ant_entity);
+                }
+            }
+        } else if let Some(element_entity) = element_entity_at_position {
+            if element_entity_at_position == currently_selected_entity.as_ref() {
+                self.selected_entity.0 = None;
+            } else {
+                self.selected_entity.0 = Some(*element_entity);
+            }
+        } else {
+            self.selected_entity.0 = None;
+        }
+    }
+}
+
 // Map user input to simulation events which will be processed manually at the start of the next simulation run.
 // This needs to occur because events aren't reliably read from within systems which don't necessarily run this/next frame.
 pub fn handle_pointer_tap<Z: Zone + Copy>(
     mouse_input: Res<Input<MouseButton>>,
+    keyboard_input: Res<Input<KeyCode>>,
     touches: Res<Touches>,
     primary_window_query: Query<&Window, With<PrimaryWindow>>,
     mut camera_query: Query<(&Camera, &GlobalTransform), With<RenderingCamera>>,
     grid_query: Query<(Entity, &Grid, &Z)>,
-    grid_elements: GridElements<Z>,
     visible_grid: Res<VisibleGrid>,
     is_pointer_captured: Res<IsPointerCaptured>,
-    pointer_action: Res<PointerAction>,
-    mut external_simulation_event_writer: EventWriter<ExternalSimulationEvent<Z>>,
+    paint_shape: Res<PaintShape>,
     mut pointer_tap_state: ResMut<PointerTapState>,
-    ants_query: Query<(Entity, &Position), (With<Ant>, With<Z>)>,
-    mut selected_entity: ResMut<SelectedEntity>,
+    mut grid_tap: GridTap<Z>,
 ) {
     if is_pointer_captured.0 {
         return;
@@ -94,83 +350,194 @@ pub fn handle_pointer_tap<Z: Zone + Copy>(
         pointer_tap_state.position = pointer_pressed_position;
     }
 
-    if pointer_tap_state.position.is_none() {
+    let Some(pointer_pressed_position) = pointer_tap_state.position else {
         return;
-    }
+    };
 
-    let pointer_released_position = get_pointer_released_position(window, &mouse_input, &touches);
-    if pointer_released_position.is_none() {
+    let Some(pointer_released_position) =
+        get_pointer_released_position(window, &mouse_input, &touches)
+    else {
         return;
-    }
+    };
+
+    let pointer_distance = pointer_pressed_position.distance(pointer_released_position);
+    let is_dragging = pointer_distance >= DRAG_THRESHOLD;
 
-    let pointer_distance = pointer_tap_state
-        .position
-        .unwrap()
-        .distance(pointer_released_position.unwrap());
-    if pointer_distance >= DRAG_THRESHOLD {
+    // Dragging pans the camera unless something is being painted.
+    if is_dragging
+        && (!grid_tap.pointer_action().can_paint() || !is_paint_key_pressed(&keyboard_input))
+    {
         return;
     }
 
+    // The camera can't always map the pointer into the world, e.g. while its viewport is being resized, so skip the tap.
     let (camera, camera_transform) = camera_query.single_mut();
-    let world_position = camera
-        .viewport_to_world_2d(camera_transform, pointer_tap_state.position.unwrap())
-        .unwrap();
+    let Some(world_position) =
+        camera.viewport_to_world_2d(camera_transform, pointer_pressed_position)
+    else {
+        return;
+    };
 
     let (grid_entity, grid, zone) = grid_query.single();
-    if grid_entity != visible_grid.0.unwrap() {
+    if visible_grid.0 != Some(grid_entity) {
         return;
     }
 
     let grid_position = grid.world_to_grid_position(world_position);
 
-    if *pointer_action != PointerAction::Select {
-        external_simulation_event_writer.send(pointer_action_to_simulation_event(
-            *pointer_action,
+    if is_dragging {
+        let Some(released_world_position) =
+            camera.viewport_to_world_2d(camera_transform, pointer_released_position)
+        else {
+            return;
+        };
+        let released_grid_position = grid.world_to_grid_position(released_world_position);
+
+        let positions = get_paint_positions(
             grid_position,
-            *zone,
-        ));
+            released_grid_position,
+            *paint_shape,
+            grid,
+            grid_tap.is_paint_mirrored(),
+        );
+
+        grid_tap.paint(positions, *zone);
 
         return;
     }
 
-    // TODO: Support multiple ants at a given position. Need to select them in a fixed order so that there's a "last ant" so that selecting Element is possible afterward.
-    let ant_entity_at_position = ants_query
-        .iter()
-        .find(|(_, &position)| position == grid_position)
-        .map(|(entity, _)| entity);
+    grid_tap.tap(
+        grid_position,
+        grid,
+        *zone,
+        is_group_select_key_pressed(&keyboard_input),
+    );
+}
+
+/// Let the simulation know where, in the nest, the player is pointing. Touch screens have no hovering cursor so the last
+/// tapped position stands in for it. The cursor is sent, like any other input, as an event which is applied at the start
+/// of the next tick, so it only moves between ticks. It's only sent when it moves onto a new position.
+pub fn update_player_cursor(
+    primary_window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<RenderingCamera>>,
+    grid_query: Query<(Entity, &Grid), With<AtNest>>,
+    visible_grid: Res<VisibleGrid>,
+    pointer_tap_state: Res<PointerTapState>,
+    mut last_grid_position: Local<Option<Position>>,
+    mut external_simulation_event_writer: EventWriter<ExternalSimulationEvent<AtNest>>,
+) {
+    let window = match primary_window_query.get_single() {
+        Ok(window) => window,
+        Err(_) => return,
+    };
 
-    let element_entity_at_position = grid_elements.get_entity(grid_position);
+    let Some(cursor_position) = window.cursor_position().or(pointer_tap_state.position) else {
+        return;
+    };
 
-    let currently_selected_entity = selected_entity.0;
+    let (grid_entity, grid) = grid_query.single();
+    if visible_grid.0 != Some(grid_entity) {
+        return;
+    }
 
-    if let Some(ant_entity) = ant_entity_at_position {
-        // If tapping on an already selected ant then consider selecting element underneath ant instead.
-        if ant_entity_at_position == currently_selected_entity {
-            if let Some(element_entity) = element_entity_at_position {
-                selected_entity.0 = Some(*element_entity);
-            } else {
-                selected_entity.0 = None;
-            }
-        } else {
-            // If there is an ant at the given position, and it's not selected, but the element underneath it is selected
-            // then assume user wants to deselect element and not select the ant. They can select again after if they want the ant.
-            if element_entity_at_position == currently_selected_entity.as_ref() {
-                selected_entity.0 = None;
-            } else {
-                selected_entity.0 = Some(ant_entity);
-            }
+    let (camera, camera_transform) = camera_query.single();
+    let Some(world_position) = camera.viewport_to_world_2d(camera_transform, cursor_position)
+    else {
+        return;
+    };
+
+    let grid_position = grid.world_to_grid_position(world_position);
+    if !grid.is_within_bounds(&grid_position) {
+        return;
+    }
+
+    if *last_grid_position == Some(grid_position) {
+        return;
+    }
+
+    *last_grid_position = Some(grid_position);
+    external_simulation_event_writer.send(ExternalSimulationEvent::PointAt(grid_position, AtNest));
+}
+
+/// Every position painted by dragging from `start` to `end`, including any mirrored positions.
+pub fn get_paint_positions(
+    start: Position,
+    end: Position,
+    paint_shape: PaintShape,
+    grid: &Grid,
+    is_mirrored: bool,
+) -> Vec<Position> {
+    let shape_positions = match paint_shape {
+        PaintShape::Line => get_line_positions(start, end),
+        PaintShape::Rectangle => get_rectangle_positions(start, end),
+    };
+
+    mirror_positions(shape_positions, grid, is_mirrored)
+}
+
+/// Every position on a straight line between `start` and `end`, inclusive, using Bresenham's line algorithm.
+fn get_line_positions(start: Position, end: Position) -> Vec<Position> {
+    let delta_x = (end.x - start.x).abs();
+    let delta_y = -(end.y - start.y).abs();
+    let step_x = if start.x < end.x { 1 } else { -1 };
+    let step_y = if start.y < end.y { 1 } else { -1 };
+
+    let mut positions = vec![];
+    let mut position = start;
+    let mut error = delta_x + delta_y;
+
+    loop {
+        positions.push(position);
+
+        if position == end {
+            return positions;
         }
-    } else if let Some(element_entity) = element_entity_at_position {
-        if element_entity_at_position == currently_selected_entity.as_ref() {
-            selected_entity.0 = None;
-        } else {
-            selected_entity.0 = Some(*element_entity);
+
+        let doubled_error = error * 2;
+
+        if doubled_error >= delta_y {
+            error += delta_y;
+            position.x += step_x;
+        }
+
+        if doubled_error <= delta_x {
+            error += delta_x;
+            position.y += step_y;
         }
-    } else {
-        selected_entity.0 = None;
     }
 }
 
+/// Every position within the rectangle which has `start` and `end` as opposite corners.
+fn get_rectangle_positions(start: Position, end: Position) -> Vec<Position> {
+    let (min_x, max_x) = (start.x.min(end.x), start.x.max(end.x));
+    let (min_y, max_y) = (start.y.min(end.y), start.y.max(end.y));
+
+    (min_y..=max_y)
+        .flat_map(|y| (min_x..=max_x).map(move |x| Position::new(x, y)))
+        .collect()
+}
+
+/// Add the reflection of each position across the grid's vertical center line. Positions on the center line are only
+/// included once so an action isn't applied to them twice.
+pub fn mirror_positions(positions: Vec<Position>, grid: &Grid, is_mirrored: bool) -> Vec<Position> {
+    if !is_mirrored {
+        return positions;
+    }
+
+    let mirrored_positions = positions
+        .iter()
+        .map(|position| Position::new(grid.width() - 1 - position.x, position.y))
+        .collect::<Vec<_>>();
+
+    let mut unique_positions = HashSet::new();
+
+    positions
+        .into_iter()
+        .chain(mirrored_positions)
+        .filter(|position| unique_positions.insert(*position))
+        .collect()
+}
+
 fn get_pointer_pressed_position(
     window: &Window,
     mouse_input: &Res<Input<MouseButton>>,
@@ -0,0 +1,18 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+use simulation::settings::Settings;
+
+/// Every flourish which moves, flashes, or shakes something, or moves the camera on the player's behalf, checks this
+/// first and falls back to a gentle fade, or to holding still, when motion is reduced.
+#[derive(SystemParam)]
+pub struct ReducedMotion<'w> {
+    // Settings only exist while a story is being told. Outside of a story there's nothing to animate.
+    settings: Option<Res<'w, Settings>>,
+}
+
+impl<'w> ReducedMotion<'w> {
+    pub fn is_enabled(&self) -> bool {
+        self.settings
+            .as_ref()
+            .map_or(false, |settings| settings.is_reduced_motion)
+    }
+}
@@ -6,11 +6,34 @@ use super::VisibleGrid;
 #[derive(Resource, Default)]
 pub struct SelectedEntity(pub Option<Entity>);
 
+/// Ants selected together so they can be given orders as a group. Separate from SelectedEntity, which is for inspecting.
+#[derive(Resource, Default)]
+pub struct SelectedGroup(pub Vec<Entity>);
+
+impl SelectedGroup {
+    pub fn toggle(&mut self, entity: Entity) {
+        if let Some(index) = self.0.iter().position(|&selected| selected == entity) {
+            self.0.remove(index);
+        } else {
+            self.0.push(entity);
+        }
+    }
+}
+
 #[derive(Component)]
 pub struct SelectionSprite;
 
-pub fn clear_selection(mut selected_entity: ResMut<SelectedEntity>) {
+#[derive(Component)]
+pub struct GroupSelectionSprite {
+    model_entity: Entity,
+}
+
+pub fn clear_selection(
+    mut selected_entity: ResMut<SelectedEntity>,
+    mut selected_group: ResMut<SelectedGroup>,
+) {
     selected_entity.0 = None;
+    selected_group.0.clear();
 }
 
 /// When Selection is added to a component, decorate that component with a white outline sprite.
@@ -108,3 +131,77 @@ pub fn on_update_selected_position(
 
     transform.translation = world_position;
 }
+
+/// Outline every ant in the selected group. Groups are small so it's simpler to sync every sprite each frame than to
+/// track changes. Ants which no longer exist, e.g. because they were removed by the user, leave the group.
+pub fn update_group_selection_sprites(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut selected_group: ResMut<SelectedGroup>,
+    entity_position_query: Query<&Position>,
+    mut group_selection_sprite_query: Query<(Entity, &GroupSelectionSprite, &mut Transform)>,
+    grid_query: Query<&Grid>,
+    visible_grid: Res<VisibleGrid>,
+) {
+    selected_group
+        .0
+        .retain(|&model_entity| entity_position_query.contains(model_entity));
+
+    let grid = visible_grid
+        .0
+        .and_then(|visible_grid_entity| grid_query.get(visible_grid_entity).ok());
+
+    let mut outlined_entities = vec![];
+
+    for (sprite_entity, group_selection_sprite, mut transform) in
+        group_selection_sprite_query.iter_mut()
+    {
+        let model_entity = group_selection_sprite.model_entity;
+
+        match (grid, entity_position_query.get(model_entity)) {
+            (Some(grid), Ok(position)) if selected_group.0.contains(&model_entity) => {
+                transform.translation = get_group_selection_translation(grid, position);
+                outlined_entities.push(model_entity);
+            }
+            _ => commands.entity(sprite_entity).despawn(),
+        }
+    }
+
+    let grid = match grid {
+        Some(grid) => grid,
+        None => return,
+    };
+
+    for &model_entity in selected_group.0.iter() {
+        if outlined_entities.contains(&model_entity) {
+            continue;
+        }
+
+        let position = entity_position_query.get(model_entity).unwrap();
+
+        commands.spawn((
+            SpriteBundle {
+                transform: Transform::from_translation(get_group_selection_translation(
+                    grid, position,
+                )),
+                texture: asset_server.load("images/selection.png"),
+                sprite: Sprite {
+                    // Tint group selection so it's distinct from the inspected selection.
+                    color: Color::YELLOW,
+                    custom_size: Some(Vec2::ONE),
+                    ..default()
+                },
+                ..default()
+            },
+            GroupSelectionSprite { model_entity },
+        ));
+    }
+}
+
+fn get_group_selection_translation(grid: &Grid, position: &Position) -> Vec3 {
+    let mut world_position = grid.grid_to_world_position(*position);
+    // render selection UI above ants
+    world_position.z = 3.0;
+
+    world_position
+}
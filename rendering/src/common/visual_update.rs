@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+use simulation::{
+    app_state::AppState,
+    story_time::{StoryPlaybackState, TicksPerSecond},
+};
+
+use super::visible_grid::VisibleGridState;
+
+pub const DEFAULT_VISUAL_UPDATES_PER_SECOND: isize = 30;
+pub const MAX_VISUAL_UPDATES_PER_SECOND: isize = 60;
+
+/// Systems which sync views with their models. When the simulation ticks faster than views are updated, these
+/// systems don't try to keep up with every tick. Instead, they sample the simulation's state at their own cadence.
+///
+/// IMPORTANT: Only systems which rely on change detection belong here. Change detection accumulates between runs, but
+/// `RemovedComponents` and `Events` are cleared after a couple of frames, so systems reading them need to run every frame.
+/// Systems which spawn views (`Added<_>`) must be sampled alongside systems which update views (`Changed<_>`) otherwise
+/// a view could be spawned with state that's changed again before its update system next runs.
+#[derive(SystemSet, Debug, PartialEq, Eq, Clone, Hash)]
+pub struct VisualUpdateSet;
+
+/// Store separately from TicksPerSecond because it's user-configurable independent of how fast the simulation runs.
+#[derive(Resource)]
+pub struct VisualUpdatesPerSecond(pub isize);
+
+impl Default for VisualUpdatesPerSecond {
+    fn default() -> Self {
+        Self(DEFAULT_VISUAL_UPDATES_PER_SECOND)
+    }
+}
+
+#[derive(Resource, Default, PartialEq, Copy, Clone, Debug)]
+pub struct IsVisualUpdateDue(pub bool);
+
+pub fn initialize_visual_update_resources(mut commands: Commands) {
+    commands.init_resource::<VisualUpdatesPerSecond>();
+    commands.init_resource::<IsVisualUpdateDue>();
+}
+
+pub fn remove_visual_update_resources(mut commands: Commands) {
+    commands.remove_resource::<VisualUpdatesPerSecond>();
+    commands.remove_resource::<IsVisualUpdateDue>();
+}
+
+/// Decide, once per frame, whether views should be synced with the simulation this frame.
+/// If the simulation isn't ticking faster than views are updated then every frame is rendered.
+pub fn update_is_visual_update_due(
+    mut is_visual_update_due: ResMut<IsVisualUpdateDue>,
+    visual_updates_per_second: Res<VisualUpdatesPerSecond>,
+    ticks_per_second: Res<TicksPerSecond>,
+    story_playback_state: Res<State<StoryPlaybackState>>,
+    time: Res<Time<Real>>,
+    mut last_visual_update_time: Local<f32>,
+) {
+    let current_time = time.elapsed_seconds();
+
+    let is_sampling = *story_playback_state == StoryPlaybackState::FastForwarding
+        || ticks_per_second.0 > visual_updates_per_second.0;
+
+    is_visual_update_due.0 = !is_sampling
+        || current_time - *last_visual_update_time >= 1.0 / visual_updates_per_second.0 as f32;
+
+    if is_visual_update_due.0 {
+        *last_visual_update_time = current_time;
+    }
+}
+
+/// Views must be fully synced before changing which grid is visible. Rerendering a grid draws every model as it is now,
+/// so any `Added<_>` left unprocessed by a skipped sample would then be drawn a second time.
+/// Similarly, PostSetupClearChangeDetection exists to clear change detection so it must not be skipped.
+pub fn is_visual_update_forced(
+    next_visible_grid_state: Res<NextState<VisibleGridState>>,
+    app_state: Res<State<AppState>>,
+) -> bool {
+    next_visible_grid_state.0.is_some() || *app_state == AppState::PostSetupClearChangeDetection
+}
@@ -1,12 +1,21 @@
-use crate::common::{visible_grid::VisibleGrid, ModelViewEntityMap};
+use crate::{
+    common::{ant_label::AntLabel, visible_grid::VisibleGrid, ModelViewEntityMap},
+    nest::ant::{get_ant_role_color, get_ant_role_image},
+};
 use bevy::prelude::*;
 use simulation::{
-    common::{grid::Grid, position::Position},
-    crater_simulation::crater::{AtCrater, Crater},
-    nest_simulation::ant::{Ant, AntColor, AntInventory, AntName, AntOrientation, AntRole, Dead},
+    common::{colony::ColonyId, grid::Grid, position::Position},
+    crater_simulation::{
+        ant::CraterOrientation,
+        crater::{AtCrater, Crater},
+    },
+    nest_simulation::ant::{Ant, AntColor, AntInventory, AntName, AntRole, Dead},
 };
 use std::ops::Add;
 
+// Rival colonies are drawn in a warning red, whatever color the player has chosen for their own ants.
+const RIVAL_ANT_COLOR: Color = Color::rgb(0.8, 0.15, 0.1);
+
 #[derive(Component, Copy, Clone)]
 pub struct TranslationOffset(pub Vec3);
 
@@ -19,7 +28,8 @@ pub struct AntSpriteContainer {
     pub emote_entity: Option<Entity>,
 }
 
-/// When an ant model is added to the simulation, render an associated ant sprite.
+/// When an ant model is added to the simulation, or arrives in the crater from the nest, render an associated ant sprite.
+/// Crater ants are rendered once they've been given a `CraterOrientation`, which may be after they're added.
 /// This *only* handles the initial rendering of the ant sprite. Updates are handled by other systems.
 /// This does handle rendering the ant's held inventory item, it's role-associated hat, it's name label,
 /// and properly draws it as dead if the model is dead when spawned.
@@ -33,13 +43,14 @@ pub fn on_spawn_ant(
             Entity,
             &Position,
             &AntColor,
-            &AntOrientation,
+            &CraterOrientation,
             &AntName,
             &AntRole,
             &AntInventory,
             Option<&Dead>,
+            Option<&ColonyId>,
         ),
-        (Added<Ant>, With<AtCrater>),
+        (Added<CraterOrientation>, With<Ant>, With<AtCrater>),
     >,
     asset_server: Res<AssetServer>,
     crater_query: Query<&Grid, With<Crater>>,
@@ -57,7 +68,8 @@ pub fn on_spawn_ant(
         Err(_) => return,
     };
 
-    for (ant_model_entity, position, color, orientation, name, role, inventory, dead) in &ants_query
+    for (ant_model_entity, position, color, orientation, name, role, inventory, dead, colony_id) in
+        &ants_query
     {
         spawn_ant_sprite(
             &mut commands,
@@ -69,6 +81,7 @@ pub fn on_spawn_ant(
             role,
             inventory,
             dead,
+            colony_id,
             &asset_server,
             &grid,
             // &element_texture_atlas_handle,
@@ -86,11 +99,12 @@ pub fn rerender_ants(
             Entity,
             &Position,
             &AntColor,
-            &AntOrientation,
+            &CraterOrientation,
             &AntName,
             &AntRole,
             &AntInventory,
             Option<&Dead>,
+            Option<&ColonyId>,
         ),
         With<AtCrater>,
     >,
@@ -102,7 +116,7 @@ pub fn rerender_ants(
 ) {
     let grid = crater_query.single();
 
-    for (ant_model_entity, position, color, orientation, name, role, inventory, dead) in
+    for (ant_model_entity, position, color, orientation, name, role, inventory, dead, colony_id) in
         ant_model_query.iter()
     {
         spawn_ant_sprite(
@@ -115,6 +129,7 @@ pub fn rerender_ants(
             role,
             inventory,
             dead,
+            colony_id,
             &asset_server,
             &grid,
             // &element_texture_atlas_handle,
@@ -123,9 +138,83 @@ pub fn rerender_ants(
     }
 }
 
+pub fn on_update_ant_position(
+    ant_model_query: Query<(Entity, Ref<Position>), (With<Ant>, With<AtCrater>)>,
+    mut ant_view_query: Query<(&mut Transform, &TranslationOffset)>,
+    crater_query: Query<&Grid, With<Crater>>,
+    model_view_entity_map: Res<ModelViewEntityMap>,
+    visible_grid: Res<VisibleGrid>,
+) {
+    let visible_grid_entity = match visible_grid.0 {
+        Some(visible_grid_entity) => visible_grid_entity,
+        None => return,
+    };
+
+    let grid = match crater_query.get(visible_grid_entity) {
+        Ok(grid) => grid,
+        Err(_) => return,
+    };
+
+    for (ant_model_entity, position) in ant_model_query.iter() {
+        if position.is_added() || !position.is_changed() {
+            continue;
+        }
+
+        if let Some(&ant_view_entity) = model_view_entity_map.get(&ant_model_entity) {
+            if let Ok((mut transform, translation_offset)) = ant_view_query.get_mut(ant_view_entity)
+            {
+                transform.translation = grid
+                    .grid_to_world_position(*position)
+                    .add(translation_offset.0);
+            }
+        }
+    }
+}
+
+pub fn on_update_ant_orientation(
+    ant_model_query: Query<(Entity, Ref<CraterOrientation>), With<AtCrater>>,
+    ant_view_query: Query<&AntSpriteContainer>,
+    mut transform_query: Query<&mut Transform>,
+    model_view_entity_map: Res<ModelViewEntityMap>,
+    visible_grid: Res<VisibleGrid>,
+    crater_query: Query<&Grid, With<Crater>>,
+) {
+    let visible_grid_entity = match visible_grid.0 {
+        Some(visible_grid_entity) => visible_grid_entity,
+        None => return,
+    };
+
+    if crater_query.get(visible_grid_entity).is_err() {
+        return;
+    }
+
+    for (ant_model_entity, orientation) in ant_model_query.iter() {
+        if !orientation.is_changed() || orientation.is_added() {
+            continue;
+        }
+
+        if let Some(ant_view_entity) = model_view_entity_map.get(&ant_model_entity) {
+            let ant_sprite_container = ant_view_query.get(*ant_view_entity).unwrap();
+            let mut transform = transform_query
+                .get_mut(ant_sprite_container.sprite_entity)
+                .unwrap();
+
+            transform.rotation = orientation.as_world_rotation();
+        }
+    }
+}
+
 /// Remove resources, etc.
 pub fn cleanup_ants() {}
 
+/// Ants are drawn in their own color unless they belong to a rival colony.
+pub fn get_colony_color(color: &AntColor, colony_id: Option<&ColonyId>) -> AntColor {
+    match colony_id {
+        Some(colony_id) if !colony_id.is_player() => AntColor(RIVAL_ANT_COLOR),
+        _ => *color,
+    }
+}
+
 /// Non-System Helper Functions:
 
 fn spawn_ant_sprite(
@@ -134,10 +223,11 @@ fn spawn_ant_sprite(
     position: &Position,
     color: &AntColor,
     name: &AntName,
-    orientation: &AntOrientation,
+    orientation: &CraterOrientation,
     role: &AntRole,
     inventory: &AntInventory,
     dead: Option<&Dead>,
+    colony_id: Option<&ColonyId>,
     asset_server: &Res<AssetServer>,
     grid: &Grid,
     // element_texture_atlas_handle: &Res<ElementTextureAtlasHandle>,
@@ -149,7 +239,8 @@ fn spawn_ant_sprite(
     let (sprite_image, sprite_color) = if dead.is_some() {
         ("images/ant_dead.png", Color::GRAY)
     } else {
-        ("images/ant.png", color.0)
+        let color = get_colony_color(color, colony_id);
+        (get_ant_role_image(role), get_ant_role_color(&color, role))
     };
 
     // Spawn AntSprite with child inventory/hat
@@ -163,7 +254,6 @@ fn spawn_ant_sprite(
         },
         transform: Transform {
             rotation: orientation.as_world_rotation(),
-            scale: orientation.as_world_scale(),
             ..default()
         },
         ..default()
@@ -172,7 +262,7 @@ fn spawn_ant_sprite(
     // let mut inventory_item_entity = None;
 
     // ant_sprite.with_children(|parent: &mut ChildBuilder<'_, '_, '_>| {
-    //     // if let Some(element_entity) = inventory.0 {
+    //     // if let Some(element_entity) = inventory.last() {
     //     //     let bundle = get_inventory_item_bundle(
     //     //         element_entity,
     //     //         &grid_elements,
@@ -197,22 +287,25 @@ fn spawn_ant_sprite(
 
     let sprite_entity = ant_sprite.id();
 
-    let ant_label = commands.spawn(Text2dBundle {
-        transform: Transform {
-            translation: Vec3::new(0.0, -1.0, 1.0),
-            scale: Vec3::new(0.01, 0.01, 0.0),
-            ..default()
-        },
-        text: Text::from_section(
-            name.0.as_str(),
-            TextStyle {
-                color: Color::WHITE,
-                font_size: 60.0,
+    let ant_label = commands.spawn((
+        Text2dBundle {
+            transform: Transform {
+                translation: Vec3::new(0.0, -1.0, 1.0),
+                scale: Vec3::new(0.01, 0.01, 0.0),
                 ..default()
             },
-        ),
-        ..default()
-    });
+            text: Text::from_section(
+                name.0.as_str(),
+                TextStyle {
+                    color: Color::WHITE,
+                    font_size: 60.0,
+                    ..default()
+                },
+            ),
+            ..default()
+        },
+        AntLabel,
+    ));
 
     let label_entity = ant_label.id();
 
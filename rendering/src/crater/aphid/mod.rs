@@ -0,0 +1,121 @@
+use crate::common::{visible_grid::VisibleGrid, ModelViewEntityMap};
+use bevy::prelude::*;
+use simulation::{
+    common::{grid::Grid, position::Position},
+    crater_simulation::{
+        aphid::Aphid,
+        crater::{AtCrater, Crater},
+    },
+};
+
+// Aphids are drawn a sappy green. They brighten once they've honeydew to give so players can see which are ready.
+const APHID_COLOR: Color = Color::rgb(0.45, 0.7, 0.25);
+const APHID_HONEYDEW_COLOR: Color = Color::rgb(0.7, 0.95, 0.4);
+// Aphids sit above the crater floor but beneath the ants which tend them.
+const APHID_Z_INDEX: f32 = 0.5;
+
+/// When an aphid is born into the crater render an associated aphid sprite.
+pub fn on_spawn_aphid(
+    aphids_query: Query<(Entity, &Aphid, &Position), (Added<Aphid>, With<AtCrater>)>,
+    crater_query: Query<&Grid, With<Crater>>,
+    asset_server: Res<AssetServer>,
+    mut model_view_entity_map: ResMut<ModelViewEntityMap>,
+    visible_grid: Res<VisibleGrid>,
+    mut commands: Commands,
+) {
+    let visible_grid_entity = match visible_grid.0 {
+        Some(visible_grid_entity) => visible_grid_entity,
+        None => return,
+    };
+
+    let grid = match crater_query.get(visible_grid_entity) {
+        Ok(grid) => grid,
+        Err(_) => return,
+    };
+
+    for (aphid_model_entity, aphid, position) in aphids_query.iter() {
+        spawn_aphid_sprite(
+            aphid_model_entity,
+            aphid,
+            position,
+            grid,
+            &asset_server,
+            &mut model_view_entity_map,
+            &mut commands,
+        );
+    }
+}
+
+/// When user switches to a different scene (Crater->Nest) all Crater views are despawned.
+/// Aphids need to be redrawn once when switching back to the Crater.
+pub fn rerender_aphids(
+    aphids_query: Query<(Entity, &Aphid, &Position), With<AtCrater>>,
+    crater_query: Query<&Grid, With<Crater>>,
+    asset_server: Res<AssetServer>,
+    mut model_view_entity_map: ResMut<ModelViewEntityMap>,
+    mut commands: Commands,
+) {
+    let grid = crater_query.single();
+
+    for (aphid_model_entity, aphid, position) in aphids_query.iter() {
+        spawn_aphid_sprite(
+            aphid_model_entity,
+            aphid,
+            position,
+            grid,
+            &asset_server,
+            &mut model_view_entity_map,
+            &mut commands,
+        );
+    }
+}
+
+pub fn on_update_aphid(
+    aphids_query: Query<(Entity, &Aphid), (Changed<Aphid>, With<AtCrater>)>,
+    mut aphid_view_query: Query<&mut Sprite>,
+    model_view_entity_map: Res<ModelViewEntityMap>,
+) {
+    for (aphid_model_entity, aphid) in aphids_query.iter() {
+        if let Some(aphid_view_entity) = model_view_entity_map.get(&aphid_model_entity) {
+            if let Ok(mut sprite) = aphid_view_query.get_mut(*aphid_view_entity) {
+                sprite.color = get_aphid_color(aphid);
+            }
+        }
+    }
+}
+
+fn spawn_aphid_sprite(
+    aphid_model_entity: Entity,
+    aphid: &Aphid,
+    position: &Position,
+    grid: &Grid,
+    asset_server: &Res<AssetServer>,
+    model_view_entity_map: &mut ResMut<ModelViewEntityMap>,
+    commands: &mut Commands,
+) {
+    let mut translation = grid.grid_to_world_position(*position);
+    translation.z = APHID_Z_INDEX;
+
+    let aphid_view_entity = commands
+        .spawn(SpriteBundle {
+            texture: asset_server.load("images/aphid.png"),
+            sprite: Sprite {
+                color: get_aphid_color(aphid),
+                custom_size: Some(Vec2::splat(1.0)),
+                ..default()
+            },
+            transform: Transform::from_translation(translation),
+            ..default()
+        })
+        .id();
+
+    model_view_entity_map.insert(aphid_model_entity, aphid_view_entity);
+}
+
+fn get_aphid_color(aphid: &Aphid) -> Color {
+    if aphid.has_honeydew() {
+        APHID_HONEYDEW_COLOR
+    } else {
+        APHID_COLOR
+    }
+}
@@ -2,17 +2,32 @@ use bevy::prelude::*;
 use simulation::{
     common::grid::Grid,
     crater_simulation::crater::{AtCrater, Crater},
+    story_time::{Season, StoryTime},
 };
 
 #[derive(Component)]
 pub struct CraterBackground;
 
-pub fn spawn_background(mut commands: Commands, crater_query: Query<&Grid, With<Crater>>) {
+/// The crater floor is exposed to the elements so its palette follows the season.
+fn get_background_color(season: Season) -> Color {
+    match season {
+        Season::Spring => Color::rgb(0.8, 0.86, 0.65),
+        Season::Summer => Color::BEIGE,
+        Season::Autumn => Color::rgb(0.85, 0.75, 0.55),
+        Season::Winter => Color::rgb(0.93, 0.95, 0.97),
+    }
+}
+
+pub fn spawn_background(
+    mut commands: Commands,
+    crater_query: Query<&Grid, With<Crater>>,
+    story_time: Res<StoryTime>,
+) {
     let grid = crater_query.single();
 
     let crater_background_sprite = SpriteBundle {
         sprite: Sprite {
-            color: Color::BEIGE,
+            color: get_background_color(story_time.season()),
             custom_size: Some(Vec2::new(grid.width() as f32, grid.height() as f32)),
             ..default()
         },
@@ -22,5 +37,18 @@ pub fn spawn_background(mut commands: Commands, crater_query: Query<&Grid, With<
     commands.spawn((crater_background_sprite, CraterBackground, AtCrater));
 }
 
+pub fn update_background(
+    mut background_query: Query<&mut Sprite, With<CraterBackground>>,
+    story_time: Res<StoryTime>,
+) {
+    let color = get_background_color(story_time.season());
+
+    for mut sprite in background_query.iter_mut() {
+        if sprite.color != color {
+            sprite.color = color;
+        }
+    }
+}
+
 /// Remove resources, etc.
 pub fn cleanup_background() {}
@@ -11,7 +11,9 @@ use bevy_ecs_tilemap::prelude::*;
 use simulation::{
     common::{grid::Grid, position::Position},
     crater_simulation::crater::{AtCrater, Crater},
-    nest_simulation::element::{Air, Element, ElementExposure},
+    nest_simulation::element::{
+        definition::ElementDefinitions, Air, Element, ElementExposure, FoodKind,
+    },
 };
 
 #[derive(Component)]
@@ -46,7 +48,13 @@ pub fn spawn_element_tilemap(
 /// This *only* handles the initial rendering of the Element sprite. Updates are handled by other systems.
 pub fn on_spawn_element(
     mut element_query: Query<
-        (&Position, &Element, &ElementExposure, Entity),
+        (
+            &Position,
+            &Element,
+            &ElementExposure,
+            Entity,
+            Option<&FoodKind>,
+        ),
         (Added<Element>, With<AtCrater>, Without<Air>),
     >,
     crater_query: Query<&Grid, With<Crater>>,
@@ -54,6 +62,7 @@ pub fn on_spawn_element(
     mut tilemap_query: Query<(Entity, &mut TileStorage), With<ElementTilemap>>,
     mut model_view_entity_map: ResMut<ModelViewEntityMap>,
     visible_grid: Res<VisibleGrid>,
+    element_definitions: Res<ElementDefinitions>,
 ) {
     let visible_grid_entity = match visible_grid.0 {
         Some(visible_grid_entity) => visible_grid_entity,
@@ -67,15 +76,17 @@ pub fn on_spawn_element(
         Err(_) => return,
     };
 
-    for (element_position, element, element_exposure, element_model_entity) in
+    for (element_position, element, element_exposure, element_model_entity, food_kind) in
         element_query.iter_mut()
     {
         spawn_element_sprite(
             element_model_entity,
             element,
+            food_kind,
             element_position,
             // element_exposure,
             &grid,
+            &element_definitions,
             &mut commands,
             &mut tilemap_query,
             &mut model_view_entity_map,
@@ -88,23 +99,33 @@ pub fn on_spawn_element(
 /// have not been changed or added, though, so a separate rerender system is needed.
 pub fn rerender_elements(
     mut element_query: Query<
-        (&Position, &Element, &ElementExposure, Entity),
+        (
+            &Position,
+            &Element,
+            &ElementExposure,
+            Entity,
+            Option<&FoodKind>,
+        ),
         (With<AtCrater>, Without<Air>),
     >,
     crater_query: Query<&Grid, With<Crater>>,
     mut commands: Commands,
     mut tilemap_query: Query<(Entity, &mut TileStorage), With<ElementTilemap>>,
     mut model_view_entity_map: ResMut<ModelViewEntityMap>,
+    element_definitions: Res<ElementDefinitions>,
 ) {
     let grid = crater_query.single();
 
-    for (element_position, element, element_exposure, entity) in element_query.iter_mut() {
+    for (element_position, element, element_exposure, entity, food_kind) in element_query.iter_mut()
+    {
         spawn_element_sprite(
             entity,
             element,
+            food_kind,
             element_position,
             // element_exposure,
             &grid,
+            &element_definitions,
             &mut commands,
             &mut tilemap_query,
             &mut model_view_entity_map,
@@ -123,9 +144,11 @@ pub fn cleanup_elements(mut commands: Commands) {
 fn spawn_element_sprite(
     element_model_entity: Entity,
     element: &Element,
+    food_kind: Option<&FoodKind>,
     element_position: &Position,
     // element_exposure: &ElementExposure,
     grid: &Grid,
+    element_definitions: &ElementDefinitions,
     commands: &mut Commands,
     tilemap_query: &mut Query<(Entity, &mut TileStorage), With<ElementTilemap>>,
     model_view_entity_map: &mut ResMut<ModelViewEntityMap>,
@@ -133,19 +156,19 @@ fn spawn_element_sprite(
     let (tilemap_entity, mut tile_storage) = tilemap_query.single_mut();
     let tile_pos = grid_to_tile_pos(grid, *element_position);
 
-    let element_exposure = ElementExposure {
-        north: false,
-        east: false,
-        south: false,
-        west: false,
-    };
+    let element_exposure = ElementExposure::NONE;
 
     let tile_bundle = (
         AtCrater,
         TileBundle {
             position: tile_pos,
             tilemap_id: TilemapId(tilemap_entity),
-            texture_index: TileTextureIndex(get_element_index(element_exposure, *element) as u32),
+            texture_index: TileTextureIndex(get_element_index(
+                element_exposure,
+                *element,
+                food_kind.copied(),
+                element_definitions,
+            ) as u32),
             ..default()
         },
     );
@@ -0,0 +1,120 @@
+use bevy::{
+    prelude::*,
+    render::{
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+        texture::ImageSampler,
+    },
+};
+use simulation::{
+    common::{exploration::Exploration, grid::Grid, position::Position},
+    crater_simulation::crater::{AtCrater, Crater},
+};
+
+// Unexplored ground is darkened, rather than hidden outright, so players can still make out the crater's size.
+const FOG_COLOR: [u8; 4] = [24, 20, 16, 220];
+
+#[derive(Resource)]
+pub struct FogVisibility(pub Visibility);
+
+#[derive(Component)]
+pub struct CraterFog;
+
+/// Fog is a single image, with one pixel per tile, stretched over the crater. A sprite per tile would be far too many.
+pub fn spawn_fog(
+    crater_query: Query<(&Grid, &Exploration), With<Crater>>,
+    fog_visibility: Res<FogVisibility>,
+    mut images: ResMut<Assets<Image>>,
+    mut commands: Commands,
+) {
+    let Ok((grid, exploration)) = crater_query.get_single() else {
+        return;
+    };
+
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: grid.width() as u32,
+            height: grid.height() as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &FOG_COLOR,
+        TextureFormat::Rgba8UnormSrgb,
+    );
+    // Keep the edge of the fog crisp, like the tiles beneath it, rather than blurring it.
+    image.sampler = ImageSampler::nearest();
+    update_fog_image(&mut image, grid, exploration);
+
+    commands.spawn((
+        SpriteBundle {
+            texture: images.add(image),
+            sprite: Sprite {
+                custom_size: Some(Vec2::new(grid.width() as f32, grid.height() as f32)),
+                ..default()
+            },
+            // Fog goes above elements, and ants, so nothing in unexplored areas gives itself away.
+            transform: Transform::from_translation(Vec3::new(0.0, 0.0, 3.0)),
+            visibility: fog_visibility.0,
+            ..default()
+        },
+        CraterFog,
+        AtCrater,
+    ));
+}
+
+/// Lift the fog wherever the player's ants have explored since it was last drawn.
+pub fn on_update_fog(
+    crater_query: Query<(&Grid, Ref<Exploration>), With<Crater>>,
+    fog_query: Query<&Handle<Image>, With<CraterFog>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let Ok((grid, exploration)) = crater_query.get_single() else {
+        return;
+    };
+
+    if !exploration.is_changed() {
+        return;
+    }
+
+    for fog_image_handle in fog_query.iter() {
+        if let Some(fog_image) = images.get_mut(fog_image_handle) {
+            update_fog_image(fog_image, grid, &exploration);
+        }
+    }
+}
+
+pub fn on_update_fog_visibility(
+    mut fog_view_query: Query<&mut Visibility, With<CraterFog>>,
+    fog_visibility: Res<FogVisibility>,
+) {
+    if fog_visibility.is_changed() {
+        for mut visibility in fog_view_query.iter_mut() {
+            *visibility = fog_visibility.0;
+        }
+    }
+}
+
+/// Fog is shown by default so the crater reveals itself as the colony forages. It can be hidden to see the whole crater.
+pub fn initialize_fog_resources(mut commands: Commands) {
+    commands.insert_resource(FogVisibility(Visibility::Visible));
+}
+
+/// Remove resources, etc.
+pub fn cleanup_fog(mut commands: Commands) {
+    commands.remove_resource::<FogVisibility>();
+}
+
+/// Non-System Helper Functions:
+
+fn update_fog_image(fog_image: &mut Image, grid: &Grid, exploration: &Exploration) {
+    for y in 0..grid.height() {
+        for x in 0..grid.width() {
+            let alpha_index = ((y * grid.width() + x) * 4 + 3) as usize;
+
+            fog_image.data[alpha_index] = if exploration.is_explored(Position::new(x, y)) {
+                0
+            } else {
+                FOG_COLOR[3]
+            };
+        }
+    }
+}
@@ -1,25 +1,45 @@
 pub mod ant;
+pub mod aphid;
 pub mod background;
 pub mod element;
+pub mod fog;
+pub mod plant;
 
 use self::{
-    ant::{cleanup_ants, on_spawn_ant, rerender_ants},
-    background::{cleanup_background, spawn_background, CraterBackground},
+    ant::{
+        cleanup_ants, on_spawn_ant, on_update_ant_orientation, on_update_ant_position,
+        rerender_ants,
+    },
+    aphid::{on_spawn_aphid, on_update_aphid, rerender_aphids},
+    background::{cleanup_background, spawn_background, update_background, CraterBackground},
     element::{
         cleanup_elements, on_spawn_element, rerender_elements, spawn_element_tilemap,
         ElementTilemap,
     },
+    fog::{
+        cleanup_fog, initialize_fog_resources, on_update_fog, on_update_fog_visibility, spawn_fog,
+        CraterFog,
+    },
+    plant::{on_spawn_plant, on_update_plant, rerender_plants},
 };
 use crate::common::{
+    airdrop::{on_spawn_airdrop, on_update_airdrop, rerender_airdrops},
     despawn_view, despawn_view_by_model, on_despawn,
     visible_grid::{VisibleGrid, VisibleGridState},
+    visual_update::VisualUpdateSet,
 };
 use bevy::prelude::*;
 use simulation::{
     app_state::AppState,
-    crater_simulation::crater::{AtCrater, Crater},
+    common::airdrop::Airdrop,
+    crater_simulation::{
+        ant::CraterOrientation,
+        aphid::Aphid,
+        crater::{AtCrater, Crater},
+        plant::Plant,
+    },
     nest_simulation::{ant::Ant, element::Element},
-    CleanupSet,
+    CleanupSet, FinishSetupSet,
 };
 
 pub struct CraterRenderingPlugin;
@@ -30,13 +50,55 @@ pub struct CraterRenderingPlugin;
 
 impl Plugin for CraterRenderingPlugin {
     fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(AppState::FinishSetup),
+            initialize_fog_resources.in_set(FinishSetupSet::AfterSimulationFinishSetup),
+        );
+
         app.add_systems(
             Update,
             (
                 // Spawn
-                (on_spawn_ant, on_spawn_element),
+                (
+                    on_spawn_ant,
+                    on_spawn_element,
+                    on_spawn_airdrop::<AtCrater>,
+                    on_spawn_aphid,
+                    on_spawn_plant,
+                ),
+                // Updated
+                (
+                    on_update_ant_position,
+                    on_update_ant_orientation,
+                    on_update_airdrop::<AtCrater>,
+                    on_update_aphid,
+                    on_update_plant,
+                    on_update_fog,
+                    on_update_fog_visibility,
+                ),
+                // Misc
+                (update_background),
+            )
+                .in_set(VisualUpdateSet)
+                .run_if(
+                    in_state(AppState::TellStory)
+                        .or_else(in_state(AppState::PostSetupClearChangeDetection)),
+                ),
+        );
+
+        app.add_systems(
+            Update,
+            (
                 // Despawn
-                (on_despawn::<Ant, AtCrater>, on_despawn::<Element, AtCrater>),
+                (
+                    on_despawn::<Ant, AtCrater>,
+                    // Ants which head down into the nest leave the crater without being despawned.
+                    on_despawn::<CraterOrientation, AtCrater>,
+                    on_despawn::<Element, AtCrater>,
+                    on_despawn::<Airdrop, AtCrater>,
+                    on_despawn::<Aphid, AtCrater>,
+                    on_despawn::<Plant, AtCrater>,
+                ),
             )
                 .run_if(
                     in_state(AppState::TellStory)
@@ -53,6 +115,10 @@ impl Plugin for CraterRenderingPlugin {
                     spawn_background,
                     rerender_ants,
                     rerender_elements,
+                    rerender_airdrops::<AtCrater>,
+                    rerender_aphids,
+                    rerender_plants,
+                    spawn_fog,
                     mark_crater_visible,
                 ),
             )
@@ -67,6 +133,10 @@ impl Plugin for CraterRenderingPlugin {
                 despawn_view_by_model::<Ant, AtCrater>,
                 despawn_view_by_model::<Element, AtCrater>,
                 despawn_view::<ElementTilemap>,
+                despawn_view_by_model::<Airdrop, AtCrater>,
+                despawn_view_by_model::<Aphid, AtCrater>,
+                despawn_view_by_model::<Plant, AtCrater>,
+                despawn_view::<CraterFog>,
                 mark_crater_hidden,
             )
                 .run_if(in_state(AppState::TellStory)),
@@ -82,6 +152,11 @@ impl Plugin for CraterRenderingPlugin {
                 despawn_view_by_model::<Element, AtCrater>,
                 despawn_view::<ElementTilemap>,
                 cleanup_elements,
+                despawn_view_by_model::<Airdrop, AtCrater>,
+                despawn_view_by_model::<Aphid, AtCrater>,
+                despawn_view_by_model::<Plant, AtCrater>,
+                despawn_view::<CraterFog>,
+                cleanup_fog,
             )
                 .in_set(CleanupSet::BeforeSimulationCleanup),
         );
@@ -0,0 +1,132 @@
+use crate::common::{visible_grid::VisibleGrid, ModelViewEntityMap};
+use bevy::prelude::*;
+use simulation::{
+    common::{grid::Grid, position::Position},
+    crater_simulation::{
+        crater::{AtCrater, Crater},
+        plant::{Plant, PlantStage},
+    },
+};
+
+// Plants are drawn green. Sprouts are small and pale. Plants turn golden once they've gone to seed so players can see
+// which are ready to pick.
+const SPROUT_COLOR: Color = Color::rgb(0.6, 0.85, 0.45);
+const PLANT_COLOR: Color = Color::rgb(0.25, 0.6, 0.2);
+const SEEDING_PLANT_COLOR: Color = Color::rgb(0.75, 0.7, 0.3);
+const SPROUT_SIZE: f32 = 0.5;
+// Plants sit above the crater floor, and the aphids on it, but beneath the ants which forage among them.
+const PLANT_Z_INDEX: f32 = 0.6;
+
+/// When a plant sprouts in the crater render an associated plant sprite.
+pub fn on_spawn_plant(
+    plants_query: Query<(Entity, &Plant, &Position), (Added<Plant>, With<AtCrater>)>,
+    crater_query: Query<&Grid, With<Crater>>,
+    asset_server: Res<AssetServer>,
+    mut model_view_entity_map: ResMut<ModelViewEntityMap>,
+    visible_grid: Res<VisibleGrid>,
+    mut commands: Commands,
+) {
+    let visible_grid_entity = match visible_grid.0 {
+        Some(visible_grid_entity) => visible_grid_entity,
+        None => return,
+    };
+
+    let grid = match crater_query.get(visible_grid_entity) {
+        Ok(grid) => grid,
+        Err(_) => return,
+    };
+
+    for (plant_model_entity, plant, position) in plants_query.iter() {
+        spawn_plant_sprite(
+            plant_model_entity,
+            plant,
+            position,
+            grid,
+            &asset_server,
+            &mut model_view_entity_map,
+            &mut commands,
+        );
+    }
+}
+
+/// When user switches to a different scene (Crater->Nest) all Crater views are despawned.
+/// Plants need to be redrawn once when switching back to the Crater.
+pub fn rerender_plants(
+    plants_query: Query<(Entity, &Plant, &Position), With<AtCrater>>,
+    crater_query: Query<&Grid, With<Crater>>,
+    asset_server: Res<AssetServer>,
+    mut model_view_entity_map: ResMut<ModelViewEntityMap>,
+    mut commands: Commands,
+) {
+    let grid = crater_query.single();
+
+    for (plant_model_entity, plant, position) in plants_query.iter() {
+        spawn_plant_sprite(
+            plant_model_entity,
+            plant,
+            position,
+            grid,
+            &asset_server,
+            &mut model_view_entity_map,
+            &mut commands,
+        );
+    }
+}
+
+pub fn on_update_plant(
+    plants_query: Query<(Entity, &Plant), (Changed<Plant>, With<AtCrater>)>,
+    mut plant_view_query: Query<&mut Sprite>,
+    model_view_entity_map: Res<ModelViewEntityMap>,
+) {
+    for (plant_model_entity, plant) in plants_query.iter() {
+        if let Some(plant_view_entity) = model_view_entity_map.get(&plant_model_entity) {
+            if let Ok(mut sprite) = plant_view_query.get_mut(*plant_view_entity) {
+                sprite.color = get_plant_color(plant);
+                sprite.custom_size = Some(get_plant_size(plant));
+            }
+        }
+    }
+}
+
+fn spawn_plant_sprite(
+    plant_model_entity: Entity,
+    plant: &Plant,
+    position: &Position,
+    grid: &Grid,
+    asset_server: &Res<AssetServer>,
+    model_view_entity_map: &mut ResMut<ModelViewEntityMap>,
+    commands: &mut Commands,
+) {
+    let mut translation = grid.grid_to_world_position(*position);
+    translation.z = PLANT_Z_INDEX;
+
+    let plant_view_entity = commands
+        .spawn(SpriteBundle {
+            texture: asset_server.load("images/plant.png"),
+            sprite: Sprite {
+                color: get_plant_color(plant),
+                custom_size: Some(get_plant_size(plant)),
+                ..default()
+            },
+            transform: Transform::from_translation(translation),
+            ..default()
+        })
+        .id();
+
+    model_view_entity_map.insert(plant_model_entity, plant_view_entity);
+}
+
+fn get_plant_color(plant: &Plant) -> Color {
+    match plant.stage() {
+        PlantStage::Sprout => SPROUT_COLOR,
+        PlantStage::Grown => PLANT_COLOR,
+        PlantStage::Seeding => SEEDING_PLANT_COLOR,
+    }
+}
+
+fn get_plant_size(plant: &Plant) -> Vec2 {
+    match plant.stage() {
+        PlantStage::Sprout => Vec2::splat(SPROUT_SIZE),
+        PlantStage::Grown | PlantStage::Seeding => Vec2::splat(1.0),
+    }
+}
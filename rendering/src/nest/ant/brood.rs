@@ -0,0 +1,286 @@
+use crate::common::{reduced_motion::ReducedMotion, visible_grid::VisibleGrid, ModelViewEntityMap};
+use bevy::prelude::*;
+use simulation::{
+    common::{grid::Grid, position::Position},
+    nest_simulation::{
+        ant::brood::{Brood, Larva, Pupa},
+        nest::{AtNest, Nest},
+    },
+};
+use std::ops::Add;
+
+// Brood is drawn in front of elements, but behind ants, so the ants tending it aren't hidden.
+const BROOD_Z_INDEX: f32 = 0.75;
+// Brood placed on the same tile is spread out a little so it reads as a pile rather than a single piece.
+const BROOD_PILE_SPREAD: f32 = 0.12;
+const LARVA_WIGGLE_SPEED: f32 = 3.0;
+const LARVA_WIGGLE_ANGLE: f32 = 0.2;
+const PUPA_HATCHING_SPEED: f32 = 12.0;
+const PUPA_HATCHING_ANGLE: f32 = 0.15;
+// When motion is reduced, brood slowly fades in and out instead. Hatching pupae fade deeper, not faster.
+const BROOD_FADE_SPEED: f32 = 1.0;
+const LARVA_FADE_DEPTH: f32 = 0.2;
+const PUPA_HATCHING_FADE_DEPTH: f32 = 0.35;
+
+#[derive(Component)]
+pub struct BroodSprite {
+    // Offsets animations so all brood doesn't move in unison.
+    phase: f32,
+}
+
+pub fn on_spawn_brood(
+    brood_query: Query<
+        (Entity, &Position, Option<&Larva>, Option<&Pupa>),
+        (Added<Brood>, With<AtNest>),
+    >,
+    nest_query: Query<&Grid, With<Nest>>,
+    asset_server: Res<AssetServer>,
+    mut model_view_entity_map: ResMut<ModelViewEntityMap>,
+    visible_grid: Res<VisibleGrid>,
+    mut commands: Commands,
+) {
+    let visible_grid_entity = match visible_grid.0 {
+        Some(visible_grid_entity) => visible_grid_entity,
+        None => return,
+    };
+
+    let grid = match nest_query.get(visible_grid_entity) {
+        Ok(grid) => grid,
+        Err(_) => return,
+    };
+
+    for (brood_model_entity, position, larva, pupa) in brood_query.iter() {
+        spawn_brood_sprite(
+            &mut commands,
+            brood_model_entity,
+            position,
+            get_brood_stage(larva, pupa),
+            grid,
+            &asset_server,
+            &mut model_view_entity_map,
+        );
+    }
+}
+
+/// When user switches to a different scene (Nest->Crater) all Nest views are despawned.
+/// Thus, when switching back to Nest, all brood needs to be redrawn once.
+pub fn rerender_brood(
+    brood_query: Query<
+        (Entity, &Position, Option<&Larva>, Option<&Pupa>),
+        (With<Brood>, With<AtNest>),
+    >,
+    nest_query: Query<&Grid, With<Nest>>,
+    asset_server: Res<AssetServer>,
+    mut model_view_entity_map: ResMut<ModelViewEntityMap>,
+    mut commands: Commands,
+) {
+    let grid = nest_query.single();
+
+    for (brood_model_entity, position, larva, pupa) in brood_query.iter() {
+        spawn_brood_sprite(
+            &mut commands,
+            brood_model_entity,
+            position,
+            get_brood_stage(larva, pupa),
+            grid,
+            &asset_server,
+            &mut model_view_entity_map,
+        );
+    }
+}
+
+/// Eggs hatch into larvae, and larvae spin cocoons, without the brood being respawned so swap its sprite in place.
+pub fn on_added_brood_stage(
+    brood_query: Query<
+        (Entity, &Position, Option<&Larva>, Option<&Pupa>),
+        (Or<(Added<Larva>, Added<Pupa>)>, With<Brood>, With<AtNest>),
+    >,
+    mut brood_view_query: Query<
+        (&mut Handle<Image>, &mut Sprite, &mut Transform),
+        With<BroodSprite>,
+    >,
+    nest_query: Query<&Grid, With<Nest>>,
+    asset_server: Res<AssetServer>,
+    model_view_entity_map: Res<ModelViewEntityMap>,
+    visible_grid: Res<VisibleGrid>,
+) {
+    let visible_grid_entity = match visible_grid.0 {
+        Some(visible_grid_entity) => visible_grid_entity,
+        None => return,
+    };
+
+    let grid = match nest_query.get(visible_grid_entity) {
+        Ok(grid) => grid,
+        Err(_) => return,
+    };
+
+    for (brood_model_entity, position, larva, pupa) in brood_query.iter() {
+        let brood_view_entity = match model_view_entity_map.get(&brood_model_entity) {
+            Some(brood_view_entity) => *brood_view_entity,
+            None => continue,
+        };
+
+        if let Ok((mut image_handle, mut sprite, mut transform)) =
+            brood_view_query.get_mut(brood_view_entity)
+        {
+            let stage = get_brood_stage(larva, pupa);
+
+            *image_handle = asset_server.load(stage.image_path());
+            sprite.custom_size = Some(Vec2::splat(stage.size()));
+            transform.translation =
+                get_brood_translation(brood_model_entity, position, stage, grid);
+        }
+    }
+}
+
+/// Larvae wiggle, and pupae which are about to hatch rock back and forth, so brood looks alive.
+/// When motion is reduced they hold still and gently fade in and out instead.
+pub fn animate_brood(
+    brood_query: Query<(Entity, Option<&Larva>, Option<&Pupa>), (With<Brood>, With<AtNest>)>,
+    mut brood_view_query: Query<(&BroodSprite, &mut Transform, &mut Sprite)>,
+    model_view_entity_map: Res<ModelViewEntityMap>,
+    nest_query: Query<&Grid, With<Nest>>,
+    visible_grid: Res<VisibleGrid>,
+    reduced_motion: ReducedMotion,
+    time: Res<Time>,
+) {
+    let visible_grid_entity = match visible_grid.0 {
+        Some(visible_grid_entity) => visible_grid_entity,
+        None => return,
+    };
+
+    if nest_query.get(visible_grid_entity).is_err() {
+        return;
+    }
+
+    let is_reduced_motion = reduced_motion.is_enabled();
+
+    for (brood_model_entity, larva, pupa) in brood_query.iter() {
+        let (speed, angle, fade_depth) = if larva.is_some() {
+            (LARVA_WIGGLE_SPEED, LARVA_WIGGLE_ANGLE, LARVA_FADE_DEPTH)
+        } else if pupa.map_or(false, |pupa| pupa.is_hatching()) {
+            (
+                PUPA_HATCHING_SPEED,
+                PUPA_HATCHING_ANGLE,
+                PUPA_HATCHING_FADE_DEPTH,
+            )
+        } else {
+            (0.0, 0.0, 0.0)
+        };
+
+        let brood_view_entity = match model_view_entity_map.get(&brood_model_entity) {
+            Some(brood_view_entity) => *brood_view_entity,
+            None => continue,
+        };
+
+        if let Ok((brood_sprite, mut transform, mut sprite)) =
+            brood_view_query.get_mut(brood_view_entity)
+        {
+            let (rotation, alpha) = if is_reduced_motion {
+                let fade = (time.elapsed_seconds() * BROOD_FADE_SPEED + brood_sprite.phase).sin();
+
+                (Quat::IDENTITY, 1.0 - fade_depth * (fade + 1.0) / 2.0)
+            } else {
+                let wiggle = (time.elapsed_seconds() * speed + brood_sprite.phase).sin();
+
+                (Quat::from_rotation_z(wiggle * angle), 1.0)
+            };
+
+            if transform.rotation != rotation {
+                transform.rotation = rotation;
+            }
+
+            if sprite.color.a() != alpha {
+                sprite.color.set_a(alpha);
+            }
+        }
+    }
+}
+
+/// Non-System Helper Functions:
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+enum BroodStage {
+    Egg,
+    Larva,
+    Pupa,
+}
+
+impl BroodStage {
+    fn image_path(&self) -> &'static str {
+        match self {
+            BroodStage::Egg => "images/egg.png",
+            BroodStage::Larva => "images/larva.png",
+            BroodStage::Pupa => "images/pupa.png",
+        }
+    }
+
+    // Brood grows as it develops. Pupae are nearly the size of the ant they'll hatch into.
+    fn size(&self) -> f32 {
+        match self {
+            BroodStage::Egg => 0.6,
+            BroodStage::Larva => 0.8,
+            BroodStage::Pupa => 1.0,
+        }
+    }
+}
+
+// Brood which is neither a larva nor a pupa is still an egg.
+fn get_brood_stage(larva: Option<&Larva>, pupa: Option<&Pupa>) -> BroodStage {
+    match (larva, pupa) {
+        (Some(_), _) => BroodStage::Larva,
+        (_, Some(_)) => BroodStage::Pupa,
+        _ => BroodStage::Egg,
+    }
+}
+
+/// Brood rests on the floor of the tile it's in, rather than floating in the middle of it.
+/// Brood sprites are drawn centered within their image, with their lower edge a quarter of the way up, so
+/// shift them down by that much to sit them on the floor.
+fn get_brood_translation(
+    brood_model_entity: Entity,
+    position: &Position,
+    stage: BroodStage,
+    grid: &Grid,
+) -> Vec3 {
+    let floor_offset = -0.5 + stage.size() * 0.25;
+    let pile_offset = ((brood_model_entity.index() % 5) as f32 - 2.0) * BROOD_PILE_SPREAD;
+
+    grid.grid_to_world_position(*position)
+        .add(Vec3::new(pile_offset, floor_offset, BROOD_Z_INDEX))
+}
+
+fn spawn_brood_sprite(
+    commands: &mut Commands,
+    brood_model_entity: Entity,
+    position: &Position,
+    stage: BroodStage,
+    grid: &Grid,
+    asset_server: &Res<AssetServer>,
+    model_view_entity_map: &mut ResMut<ModelViewEntityMap>,
+) {
+    let brood_view_entity = commands
+        .spawn((
+            SpriteBundle {
+                texture: asset_server.load(stage.image_path()),
+                sprite: Sprite {
+                    custom_size: Some(Vec2::splat(stage.size())),
+                    ..default()
+                },
+                transform: Transform::from_translation(get_brood_translation(
+                    brood_model_entity,
+                    position,
+                    stage,
+                    grid,
+                )),
+                ..default()
+            },
+            BroodSprite {
+                phase: brood_model_entity.index() as f32,
+            },
+            AtNest,
+        ))
+        .id();
+
+    model_view_entity_map.insert(brood_model_entity, brood_view_entity);
+}
@@ -3,7 +3,8 @@ use crate::common::{ModelViewEntityMap, visible_grid::VisibleGrid};
 use bevy::prelude::*;
 use bevy_turborand::{DelegatedRng, GlobalRng};
 use simulation::{
-    common::grid::Grid,
+    common::{entity_budget::EntityBudget, grid::Grid},
+    device_quality::DeviceQuality,
     nest_simulation::{
         ant::{sleep::Asleep, AntAteFoodEvent},
         nest::{AtNest, Nest},
@@ -142,8 +143,21 @@ pub fn on_ant_ate_food(
     model_view_entity_map: Res<ModelViewEntityMap>,
     story_time: Res<StoryTime>,
     settings: Res<Settings>,
+    entity_budget: Res<EntityBudget>,
+    device_quality: Res<DeviceQuality>,
+    mut rng: ResMut<GlobalRng>,
 ) {
+    // Emotes are purely cosmetic so they're the first thing to go when memory is tight.
+    if entity_budget.is_exceeded() {
+        ant_action_events.clear();
+        return;
+    }
+
     for AntAteFoodEvent(ant_model_entity) in ant_action_events.read() {
+        if rng.f32() >= device_quality.level().flourish_density() {
+            continue;
+        }
+
         let ant_view_entity = match model_view_entity_map.get(ant_model_entity) {
             Some(ant_view_entity) => *ant_view_entity,
             None => continue,
@@ -164,14 +178,23 @@ pub fn ants_sleep_emote(
     settings: Res<Settings>,
     model_view_entity_map: Res<ModelViewEntityMap>,
     story_time: Res<StoryTime>,
+    entity_budget: Res<EntityBudget>,
+    device_quality: Res<DeviceQuality>,
 ) {
+    if entity_budget.is_exceeded() {
+        return;
+    }
+
+    let sleep_emote_chance =
+        settings.probabilities.sleep_emote * device_quality.level().flourish_density();
+
     for ant_model_entity in ants_query.iter() {
         let ant_view_entity = match model_view_entity_map.get(&ant_model_entity) {
             Some(ant_view_entity) => *ant_view_entity,
             None => continue,
         };
 
-        if rng.f32() >= settings.probabilities.sleep_emote {
+        if rng.f32() >= sleep_emote_chance {
             continue;
         }
         commands.entity(ant_view_entity).insert(Emote::new(
@@ -1,20 +1,40 @@
+pub mod brood;
 pub mod emote;
+pub mod speech;
 
 use crate::{
-    common::{visible_grid::VisibleGrid, ModelViewEntityMap},
+    common::{ant_label::AntLabel, visible_grid::VisibleGrid, ModelViewEntityMap},
     nest::element::sprite_sheet::{get_element_index, ElementTextureAtlasHandle},
 };
 use bevy::prelude::*;
 use simulation::{
     common::{grid::Grid, position::Position},
     nest_simulation::{
-        ant::{Ant, AntColor, AntInventory, AntName, AntOrientation, AntRole, Dead},
-        element::{Element, ElementExposure},
+        ant::{
+            carry::CarryGroup, collapse::Collapsed, sleep::Asleep, Ant, AntColor, AntInventory,
+            AntName, AntOrientation, AntRole, Dead,
+        },
+        element::{definition::ElementDefinitions, Element, ElementExposure, FoodKind},
         nest::{AtNest, Nest},
     },
 };
 use std::ops::Add;
 
+// Collapsed ants fade, but not entirely, because they can still be saved.
+const COLLAPSED_ANT_ALPHA: f32 = 0.4;
+// Sleeping ants are dimmed so a dormant colony looks settled for the night.
+const ASLEEP_ANT_SHADE: f32 = 0.6;
+// Queens share their colony's color, but darker, so they stand out from their workers.
+const QUEEN_COLOR_SHADE: f32 = 0.6;
+// Soldiers are shaded slightly, too, so they can be told apart from workers when zoomed out.
+const SOLDIER_COLOR_SHADE: f32 = 0.8;
+// Ants carrying alongside a leader share its tile. They crowd around it, and just beneath it, so each can be seen.
+const CARRY_FOLLOWER_OFFSETS: [Vec3; 3] = [
+    Vec3::new(-0.4, 0.0, -0.1),
+    Vec3::new(0.4, 0.0, -0.1),
+    Vec3::new(0.0, 0.4, -0.1),
+];
+
 #[derive(Component, Copy, Clone)]
 pub struct TranslationOffset(pub Vec3);
 
@@ -27,7 +47,7 @@ pub struct AntSpriteContainer {
     pub emote_entity: Option<Entity>,
 }
 
-/// When an ant model is added to the simulation, render an associated ant sprite.
+/// When an ant model is added to the simulation, or arrives in the nest from the crater, render an associated ant sprite.
 /// This *only* handles the initial rendering of the ant sprite. Updates are handled by other systems.
 /// This does handle rendering the ant's held inventory item, it's role-associated hat, it's name label,
 /// and properly draws it as dead if the model is dead when spawned.
@@ -46,13 +66,17 @@ pub fn on_spawn_ant(
             &AntRole,
             &AntInventory,
             Option<&Dead>,
+            Option<&Collapsed>,
+            Option<&Asleep>,
+            Option<&CarryGroup>,
         ),
-        (Added<Ant>, With<AtNest>),
+        (Or<(Added<Ant>, Added<AtNest>)>, With<Ant>, With<AtNest>),
     >,
     asset_server: Res<AssetServer>,
-    elements_query: Query<&Element>,
+    elements_query: Query<(&Element, Option<&FoodKind>)>,
     nest_query: Query<&Grid, With<Nest>>,
     element_texture_atlas_handle: Res<ElementTextureAtlasHandle>,
+    element_definitions: Res<ElementDefinitions>,
     mut model_view_entity_map: ResMut<ModelViewEntityMap>,
     visible_grid: Res<VisibleGrid>,
 ) {
@@ -66,7 +90,19 @@ pub fn on_spawn_ant(
         Err(_) => return,
     };
 
-    for (ant_model_entity, position, color, orientation, name, role, inventory, dead) in &ants_query
+    for (
+        ant_model_entity,
+        position,
+        color,
+        orientation,
+        name,
+        role,
+        inventory,
+        dead,
+        collapsed,
+        asleep,
+        carry_group,
+    ) in &ants_query
     {
         spawn_ant_sprite(
             &mut commands,
@@ -78,10 +114,14 @@ pub fn on_spawn_ant(
             role,
             inventory,
             dead,
+            collapsed,
+            asleep,
+            carry_group,
             &asset_server,
             &elements_query,
             &grid,
             &element_texture_atlas_handle,
+            &element_definitions,
             &mut model_view_entity_map,
         );
     }
@@ -101,20 +141,35 @@ pub fn rerender_ants(
             &AntRole,
             &AntInventory,
             Option<&Dead>,
+            Option<&Collapsed>,
+            Option<&Asleep>,
+            Option<&CarryGroup>,
         ),
         With<AtNest>,
     >,
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    elements_query: Query<&Element>,
+    elements_query: Query<(&Element, Option<&FoodKind>)>,
     nest_query: Query<&Grid, With<Nest>>,
     element_texture_atlas_handle: Res<ElementTextureAtlasHandle>,
+    element_definitions: Res<ElementDefinitions>,
     mut model_view_entity_map: ResMut<ModelViewEntityMap>,
 ) {
     let grid = nest_query.single();
 
-    for (ant_model_entity, position, color, orientation, name, role, inventory, dead) in
-        ant_model_query.iter()
+    for (
+        ant_model_entity,
+        position,
+        color,
+        orientation,
+        name,
+        role,
+        inventory,
+        dead,
+        collapsed,
+        asleep,
+        carry_group,
+    ) in ant_model_query.iter()
     {
         spawn_ant_sprite(
             &mut commands,
@@ -126,10 +181,14 @@ pub fn rerender_ants(
             role,
             inventory,
             dead,
+            collapsed,
+            asleep,
+            carry_group,
             &asset_server,
             &elements_query,
             &grid,
             &element_texture_atlas_handle,
+            &element_definitions,
             &mut model_view_entity_map,
         );
     }
@@ -150,8 +209,9 @@ pub fn on_update_ant_inventory(
     mut commands: Commands,
     ant_model_query: Query<(Entity, Ref<AntInventory>), With<AtNest>>,
     mut ant_view_query: Query<&mut AntSpriteContainer>,
-    elements_query: Query<&Element>,
+    elements_query: Query<(&Element, Option<&FoodKind>)>,
     element_texture_atlas_handle: Res<ElementTextureAtlasHandle>,
+    element_definitions: Res<ElementDefinitions>,
     model_view_entity_map: Res<ModelViewEntityMap>,
     nest_query: Query<&Grid, With<Nest>>,
     visible_grid: Res<VisibleGrid>,
@@ -171,7 +231,7 @@ pub fn on_update_ant_inventory(
         }
 
         // If inventory changed then, if there is an inventory sprite, need to despawn it.
-        // Then, if there is inventory currently, need to spawn it.
+        // Then, if there is inventory currently, need to spawn it. Only the most recently picked up item is drawn.
         if let Some(&ant_view_entity) = model_view_entity_map.get(&ant_model_entity) {
             let mut ant_sprite_container = ant_view_query.get_mut(ant_view_entity).unwrap();
 
@@ -184,11 +244,12 @@ pub fn on_update_ant_inventory(
                 ant_sprite_container.inventory_item_entity = None;
             }
 
-            if let Some(element_entity) = inventory.0 {
+            if let Some(element_entity) = inventory.last() {
                 let inventory_item_bundle = get_inventory_item_bundle(
                     element_entity,
                     &elements_query,
                     &element_texture_atlas_handle,
+                    &element_definitions,
                 );
 
                 let ant_inventory_item_entity = commands.spawn(inventory_item_bundle).id();
@@ -239,9 +300,65 @@ pub fn on_update_ant_position(
     }
 }
 
+/// When an Ant model joins, or leaves, a group carrying something heavy, its view needs to be moved to reflect whether
+/// it's sharing its leader's tile.
+pub fn on_update_ant_carry_group(
+    ant_model_query: Query<
+        (Entity, &CarryGroup, &Position),
+        (Changed<CarryGroup>, With<Ant>, With<AtNest>),
+    >,
+    ant_position_query: Query<&Position, (With<Ant>, With<AtNest>)>,
+    mut removed_carry_groups: RemovedComponents<CarryGroup>,
+    mut ant_view_query: Query<(&mut Transform, &mut TranslationOffset)>,
+    nest_query: Query<&Grid, With<Nest>>,
+    model_view_entity_map: Res<ModelViewEntityMap>,
+    visible_grid: Res<VisibleGrid>,
+) {
+    let visible_grid_entity = match visible_grid.0 {
+        Some(visible_grid_entity) => visible_grid_entity,
+        None => return,
+    };
+
+    let grid = match nest_query.get(visible_grid_entity) {
+        Ok(grid) => grid,
+        Err(_) => return,
+    };
+
+    let changed = ant_model_query
+        .iter()
+        .map(|(ant_model_entity, carry_group, position)| {
+            (ant_model_entity, Some(*carry_group), *position)
+        });
+
+    let removed = removed_carry_groups
+        .read()
+        .filter(|ant_model_entity| !ant_model_query.contains(*ant_model_entity))
+        .filter_map(|ant_model_entity| {
+            let position = ant_position_query.get(ant_model_entity).ok()?;
+            Some((ant_model_entity, None, *position))
+        });
+
+    for (ant_model_entity, carry_group, position) in changed.chain(removed) {
+        if let Some(&ant_view_entity) = model_view_entity_map.get(&ant_model_entity) {
+            if let Ok((mut transform, mut translation_offset)) =
+                ant_view_query.get_mut(ant_view_entity)
+            {
+                *translation_offset =
+                    get_translation_offset(ant_model_entity, carry_group.as_ref());
+                transform.translation = grid
+                    .grid_to_world_position(position)
+                    .add(translation_offset.0);
+            }
+        }
+    }
+}
+
 pub fn on_update_ant_color(
-    // TODO: Prefer not needing to exclude Dead here?
-    ant_model_query: Query<(Entity, Ref<AntColor>), (Without<Dead>, With<AtNest>)>,
+    // TODO: Prefer not needing to exclude Dead/Collapsed here?
+    ant_model_query: Query<
+        (Entity, Ref<AntColor>, &AntRole, Option<&Asleep>),
+        (Without<Dead>, Without<Collapsed>, With<AtNest>),
+    >,
     ant_view_query: Query<&AntSpriteContainer>,
     mut sprite_query: Query<&mut Sprite>,
     model_view_entity_map: Res<ModelViewEntityMap>,
@@ -257,7 +374,7 @@ pub fn on_update_ant_color(
         return;
     }
 
-    for (ant_model_entity, color) in ant_model_query.iter() {
+    for (ant_model_entity, color, role, asleep) in ant_model_query.iter() {
         if !color.is_changed() || color.is_added() {
             continue;
         }
@@ -268,7 +385,11 @@ pub fn on_update_ant_color(
                 .get_mut(ant_sprite_container.sprite_entity)
                 .unwrap();
 
-            sprite.color = color.0;
+            sprite.color = if asleep.is_some() {
+                get_asleep_ant_color(&color, role)
+            } else {
+                get_ant_role_color(&color, role)
+            };
         }
     }
 }
@@ -340,11 +461,188 @@ pub fn on_added_ant_dead(
     }
 }
 
+/// Starving ants collapse before dying. Fade them out to show they're in trouble.
+pub fn on_added_ant_collapsed(
+    ant_model_query: Query<(Entity, &AntColor, &AntRole), (Added<Collapsed>, With<AtNest>)>,
+    ant_view_query: Query<&AntSpriteContainer>,
+    mut sprite_query: Query<&mut Sprite>,
+    model_view_entity_map: Res<ModelViewEntityMap>,
+    nest_query: Query<&Grid, With<Nest>>,
+    visible_grid: Res<VisibleGrid>,
+) {
+    let visible_grid_entity = match visible_grid.0 {
+        Some(visible_grid_entity) => visible_grid_entity,
+        None => return,
+    };
+
+    if nest_query.get(visible_grid_entity).is_err() {
+        return;
+    }
+
+    for (ant_model_entity, color, role) in ant_model_query.iter() {
+        if let Some(ant_view_entity) = model_view_entity_map.get(&ant_model_entity) {
+            let ant_sprite_container = ant_view_query.get(*ant_view_entity).unwrap();
+            let mut sprite = sprite_query
+                .get_mut(ant_sprite_container.sprite_entity)
+                .unwrap();
+
+            sprite.color = get_ant_role_color(color, role).with_a(COLLAPSED_ANT_ALPHA);
+        }
+    }
+}
+
+/// Collapsed ants which were fed get back up and regain their color. Ants which died are handled by `on_added_ant_dead`.
+pub fn on_removed_ant_collapsed(
+    mut removed: RemovedComponents<Collapsed>,
+    ant_model_query: Query<(&AntColor, &AntRole), (Without<Dead>, With<AtNest>)>,
+    ant_view_query: Query<&AntSpriteContainer>,
+    mut sprite_query: Query<&mut Sprite>,
+    model_view_entity_map: Res<ModelViewEntityMap>,
+    nest_query: Query<&Grid, With<Nest>>,
+    visible_grid: Res<VisibleGrid>,
+) {
+    let visible_grid_entity = match visible_grid.0 {
+        Some(visible_grid_entity) => visible_grid_entity,
+        None => return,
+    };
+
+    if nest_query.get(visible_grid_entity).is_err() {
+        return;
+    }
+
+    for ant_model_entity in removed.read() {
+        let (color, role) = match ant_model_query.get(ant_model_entity) {
+            Ok(ant_model) => ant_model,
+            Err(_) => continue,
+        };
+
+        if let Some(ant_view_entity) = model_view_entity_map.get(&ant_model_entity) {
+            if let Ok(ant_sprite_container) = ant_view_query.get(*ant_view_entity) {
+                if let Ok(mut sprite) = sprite_query.get_mut(ant_sprite_container.sprite_entity) {
+                    sprite.color = get_ant_role_color(color, role);
+                }
+            }
+        }
+    }
+}
+
+/// Dim ants as they fall asleep. Collapsed ants are left faded because being asleep is the least of their worries.
+pub fn on_added_ant_asleep(
+    ant_model_query: Query<
+        (Entity, &AntColor, &AntRole),
+        (
+            Added<Asleep>,
+            Without<Dead>,
+            Without<Collapsed>,
+            With<AtNest>,
+        ),
+    >,
+    ant_view_query: Query<&AntSpriteContainer>,
+    mut sprite_query: Query<&mut Sprite>,
+    model_view_entity_map: Res<ModelViewEntityMap>,
+    nest_query: Query<&Grid, With<Nest>>,
+    visible_grid: Res<VisibleGrid>,
+) {
+    let visible_grid_entity = match visible_grid.0 {
+        Some(visible_grid_entity) => visible_grid_entity,
+        None => return,
+    };
+
+    if nest_query.get(visible_grid_entity).is_err() {
+        return;
+    }
+
+    for (ant_model_entity, color, role) in ant_model_query.iter() {
+        if let Some(ant_view_entity) = model_view_entity_map.get(&ant_model_entity) {
+            if let Ok(ant_sprite_container) = ant_view_query.get(*ant_view_entity) {
+                if let Ok(mut sprite) = sprite_query.get_mut(ant_sprite_container.sprite_entity) {
+                    sprite.color = get_asleep_ant_color(color, role);
+                }
+            }
+        }
+    }
+}
+
+/// Ants regain their color when they wake up.
+pub fn on_removed_ant_asleep(
+    mut removed: RemovedComponents<Asleep>,
+    ant_model_query: Query<
+        (&AntColor, &AntRole),
+        (Without<Dead>, Without<Collapsed>, With<AtNest>),
+    >,
+    ant_view_query: Query<&AntSpriteContainer>,
+    mut sprite_query: Query<&mut Sprite>,
+    model_view_entity_map: Res<ModelViewEntityMap>,
+    nest_query: Query<&Grid, With<Nest>>,
+    visible_grid: Res<VisibleGrid>,
+) {
+    let visible_grid_entity = match visible_grid.0 {
+        Some(visible_grid_entity) => visible_grid_entity,
+        None => return,
+    };
+
+    if nest_query.get(visible_grid_entity).is_err() {
+        return;
+    }
+
+    for ant_model_entity in removed.read() {
+        let (color, role) = match ant_model_query.get(ant_model_entity) {
+            Ok(ant_model) => ant_model,
+            Err(_) => continue,
+        };
+
+        if let Some(ant_view_entity) = model_view_entity_map.get(&ant_model_entity) {
+            if let Ok(ant_sprite_container) = ant_view_query.get(*ant_view_entity) {
+                if let Ok(mut sprite) = sprite_query.get_mut(ant_sprite_container.sprite_entity) {
+                    sprite.color = get_ant_role_color(color, role);
+                }
+            }
+        }
+    }
+}
+
 /// Remove resources, etc.
 pub fn cleanup_ants() {}
 
 /// Non-System Helper Functions:
 
+/// Ants are drawn in their colony's color, shaded by role, so it's clear at a glance who belongs to which colony
+/// and what they do there.
+pub fn get_ant_role_color(color: &AntColor, role: &AntRole) -> Color {
+    let shade = match role {
+        AntRole::Worker => return color.0,
+        AntRole::Queen => QUEEN_COLOR_SHADE,
+        AntRole::Soldier => SOLDIER_COLOR_SHADE,
+    };
+
+    Color::rgba(
+        color.0.r() * shade,
+        color.0.g() * shade,
+        color.0.b() * shade,
+        color.0.a(),
+    )
+}
+
+/// Sleeping ants keep their role's shading, just darker.
+pub fn get_asleep_ant_color(color: &AntColor, role: &AntRole) -> Color {
+    let color = get_ant_role_color(color, role);
+
+    Color::rgba(
+        color.r() * ASLEEP_ANT_SHADE,
+        color.g() * ASLEEP_ANT_SHADE,
+        color.b() * ASLEEP_ANT_SHADE,
+        color.a(),
+    )
+}
+
+/// Soldiers have oversized heads, and mandibles, for fighting so they're drawn with their own sprite.
+pub fn get_ant_role_image(role: &AntRole) -> &'static str {
+    match role {
+        AntRole::Soldier => "images/soldier.png",
+        AntRole::Worker | AntRole::Queen => "images/ant.png",
+    }
+}
+
 fn spawn_ant_sprite(
     commands: &mut Commands,
     model_entity: Entity,
@@ -355,19 +653,29 @@ fn spawn_ant_sprite(
     role: &AntRole,
     inventory: &AntInventory,
     dead: Option<&Dead>,
+    collapsed: Option<&Collapsed>,
+    asleep: Option<&Asleep>,
+    carry_group: Option<&CarryGroup>,
     asset_server: &Res<AssetServer>,
-    elements_query: &Query<&Element>,
+    elements_query: &Query<(&Element, Option<&FoodKind>)>,
     grid: &Grid,
     element_texture_atlas_handle: &Res<ElementTextureAtlasHandle>,
+    element_definitions: &ElementDefinitions,
     model_view_entity_map: &mut ResMut<ModelViewEntityMap>,
 ) {
-    // TODO: z-index is 1.0 here because ant can get hidden behind sand otherwise.
-    let translation_offset = TranslationOffset(Vec3::new(0.0, 0.0, 1.0));
+    let translation_offset = get_translation_offset(model_entity, carry_group);
 
     let (sprite_image, sprite_color) = if dead.is_some() {
         ("images/ant_dead.png", Color::GRAY)
+    } else if collapsed.is_some() {
+        (
+            get_ant_role_image(role),
+            get_ant_role_color(color, role).with_a(COLLAPSED_ANT_ALPHA),
+        )
+    } else if asleep.is_some() {
+        (get_ant_role_image(role), get_asleep_ant_color(color, role))
     } else {
-        ("images/ant.png", color.0)
+        (get_ant_role_image(role), get_ant_role_color(color, role))
     };
 
     // Spawn AntSprite with child inventory/hat
@@ -390,11 +698,12 @@ fn spawn_ant_sprite(
     let mut inventory_item_entity = None;
 
     ant_sprite.with_children(|parent: &mut ChildBuilder<'_, '_, '_>| {
-        if let Some(element_entity) = inventory.0 {
+        if let Some(element_entity) = inventory.last() {
             let bundle = get_inventory_item_bundle(
                 element_entity,
                 &elements_query,
                 &element_texture_atlas_handle,
+                element_definitions,
             );
 
             inventory_item_entity = Some(parent.spawn(bundle).id());
@@ -415,22 +724,25 @@ fn spawn_ant_sprite(
 
     let sprite_entity = ant_sprite.id();
 
-    let ant_label = commands.spawn(Text2dBundle {
-        transform: Transform {
-            translation: Vec3::new(0.0, -1.0, 1.0),
-            scale: Vec3::new(0.01, 0.01, 0.0),
-            ..default()
-        },
-        text: Text::from_section(
-            name.0.as_str(),
-            TextStyle {
-                color: Color::WHITE,
-                font_size: 60.0,
+    let ant_label = commands.spawn((
+        Text2dBundle {
+            transform: Transform {
+                translation: Vec3::new(0.0, -1.0, 1.0),
+                scale: Vec3::new(0.01, 0.01, 0.0),
                 ..default()
             },
-        ),
-        ..default()
-    });
+            text: Text::from_section(
+                name.0.as_str(),
+                TextStyle {
+                    color: Color::WHITE,
+                    font_size: 60.0,
+                    ..default()
+                },
+            ),
+            ..default()
+        },
+        AntLabel,
+    ));
 
     let label_entity = ant_label.id();
 
@@ -460,21 +772,40 @@ fn spawn_ant_sprite(
     model_view_entity_map.insert(model_entity, ant_view_entity);
 }
 
+fn get_translation_offset(
+    ant_model_entity: Entity,
+    carry_group: Option<&CarryGroup>,
+) -> TranslationOffset {
+    // TODO: z-index is 1.0 here because ant can get hidden behind sand otherwise.
+    let translation_offset = Vec3::new(0.0, 0.0, 1.0);
+
+    match carry_group {
+        Some(carry_group) if carry_group.is_helping(ant_model_entity) => {
+            let follower_offset = CARRY_FOLLOWER_OFFSETS
+                [ant_model_entity.index() as usize % CARRY_FOLLOWER_OFFSETS.len()];
+
+            TranslationOffset(translation_offset + follower_offset)
+        }
+        _ => TranslationOffset(translation_offset),
+    }
+}
+
 fn get_inventory_item_bundle(
     element_entity: Entity,
-    elements_query: &Query<&Element>,
+    elements_query: &Query<(&Element, Option<&FoodKind>)>,
     element_texture_atlas_handle: &Res<ElementTextureAtlasHandle>,
+    element_definitions: &ElementDefinitions,
 ) -> SpriteSheetBundle {
-    let element = elements_query.get(element_entity).unwrap();
+    let (element, food_kind) = elements_query.get(element_entity).unwrap();
 
-    let element_exposure = ElementExposure {
-        north: true,
-        east: true,
-        south: true,
-        west: true,
-    };
+    let element_exposure = ElementExposure::ALL;
 
-    let mut sprite = TextureAtlasSprite::new(get_element_index(element_exposure, *element));
+    let mut sprite = TextureAtlasSprite::new(get_element_index(
+        element_exposure,
+        *element,
+        food_kind.copied(),
+        element_definitions,
+    ));
     sprite.custom_size = Some(Vec2::splat(1.0));
 
     SpriteSheetBundle {
@@ -0,0 +1,222 @@
+use super::AntSpriteContainer;
+use crate::common::ModelViewEntityMap;
+use bevy::prelude::*;
+use bevy_turborand::prelude::*;
+use simulation::{
+    balance_config::BalanceConfig,
+    common::entity_budget::EntityBudget,
+    device_quality::DeviceQuality,
+    nest_simulation::{
+        ant::{
+            collapse::Collapsed, hunger::Hunger, sleep::Asleep, thirst::Thirst, AntInventory,
+            AntRole, Dead,
+        },
+        element::Element,
+        nest::AtNest,
+        task_allocation::WorkerTask,
+    },
+    settings::Settings,
+    story_time::{StoryTime, DEFAULT_TICKS_PER_SECOND},
+};
+
+/// What an ant has on its mind. Each mood has a handful of lines so ants don't all repeat the same thing.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum SpeechMood {
+    Starving,
+    Thirsty,
+    FoundFood,
+    CarryingCorpse,
+    CarryingDirt,
+    Nursing,
+    Queen,
+}
+
+impl SpeechMood {
+    pub fn lines(&self) -> &'static [&'static str] {
+        match self {
+            SpeechMood::Starving => &["so hungry…", "need food…", "is that food?"],
+            SpeechMood::Thirsty => &["so thirsty…", "need water…"],
+            SpeechMood::FoundFood => &["found food!", "dinner!", "yum!"],
+            SpeechMood::CarryingCorpse => &["rest well, friend", "to the midden…"],
+            SpeechMood::CarryingDirt => &["heavy…", "dig dig dig", "where does this go?"],
+            SpeechMood::Nursing => &["who's hungry?", "feeding time!"],
+            SpeechMood::Queen => &["long live me", "more eggs…"],
+        }
+    }
+}
+
+/// The bubble an ant is currently speaking, if any. Lives on the ant's view, alongside its emote,
+/// so it follows the ant without turning with it.
+#[derive(Component, Debug, PartialEq, Copy, Clone)]
+pub struct SpeechBubble {
+    bubble_entity: Entity,
+    expires_at: isize,
+}
+
+/// Ants say what's on their mind most pressingly. Ants with nothing on their mind keep quiet.
+fn get_speech_mood(
+    role: &AntRole,
+    hunger: &Hunger,
+    thirst: Option<&Thirst>,
+    inventory_element: Option<&Element>,
+    worker_task: Option<&WorkerTask>,
+    balance_config: &BalanceConfig,
+) -> Option<SpeechMood> {
+    if hunger.is_starving(&balance_config.hunger) {
+        return Some(SpeechMood::Starving);
+    }
+
+    if thirst.map_or(false, |thirst| thirst.is_thirsty()) {
+        return Some(SpeechMood::Thirsty);
+    }
+
+    match inventory_element {
+        Some(Element::Food) => return Some(SpeechMood::FoundFood),
+        Some(Element::Corpse) => return Some(SpeechMood::CarryingCorpse),
+        Some(Element::Dirt) | Some(Element::Sand) | Some(Element::Mud) => {
+            return Some(SpeechMood::CarryingDirt)
+        }
+        _ => {}
+    }
+
+    if worker_task == Some(&WorkerTask::Nursing) {
+        return Some(SpeechMood::Nursing);
+    }
+
+    (*role == AntRole::Queen).then_some(SpeechMood::Queen)
+}
+
+/// Occasionally have an ant speak its mind. Purely cosmetic, so speech is skipped while fast-forwarding
+/// and when memory is tight, and is rarer on devices which can't spare the effort.
+pub fn ants_speak(
+    ants_query: Query<
+        (
+            Entity,
+            &AntRole,
+            &Hunger,
+            Option<&Thirst>,
+            &AntInventory,
+            Option<&WorkerTask>,
+        ),
+        (
+            With<AtNest>,
+            Without<Dead>,
+            Without<Collapsed>,
+            Without<Asleep>,
+        ),
+    >,
+    ant_view_query: Query<&AntSpriteContainer, Without<SpeechBubble>>,
+    elements_query: Query<&Element>,
+    mut commands: Commands,
+    mut rng: ResMut<GlobalRng>,
+    settings: Res<Settings>,
+    balance_config: Res<BalanceConfig>,
+    model_view_entity_map: Res<ModelViewEntityMap>,
+    story_time: Res<StoryTime>,
+    entity_budget: Res<EntityBudget>,
+    device_quality: Res<DeviceQuality>,
+) {
+    if entity_budget.is_exceeded() {
+        return;
+    }
+
+    let speech_bubble_chance =
+        settings.probabilities.speech_bubble * device_quality.level().flourish_density();
+
+    for (ant_model_entity, role, hunger, thirst, inventory, worker_task) in ants_query.iter() {
+        if rng.f32() >= speech_bubble_chance {
+            continue;
+        }
+
+        let ant_view_entity = match model_view_entity_map.get(&ant_model_entity) {
+            Some(ant_view_entity) => *ant_view_entity,
+            None => continue,
+        };
+
+        if ant_view_query.get(ant_view_entity).is_err() {
+            continue;
+        }
+
+        let inventory_element = inventory
+            .last()
+            .and_then(|element_entity| elements_query.get(element_entity).ok());
+
+        let mood = match get_speech_mood(
+            role,
+            hunger,
+            thirst,
+            inventory_element,
+            worker_task,
+            &balance_config,
+        ) {
+            Some(mood) => mood,
+            None => continue,
+        };
+
+        let line = *rng.sample(mood.lines()).unwrap();
+        let bubble_entity = spawn_speech_bubble(line, &mut commands);
+
+        commands
+            .entity(ant_view_entity)
+            .insert(SpeechBubble {
+                bubble_entity,
+                expires_at: story_time.elapsed_ticks()
+                    + (settings.emote_duration * DEFAULT_TICKS_PER_SECOND),
+            })
+            .push_children(&[bubble_entity]);
+    }
+}
+
+pub fn despawn_expired_speech_bubbles(
+    ant_view_query: Query<(Entity, &SpeechBubble), With<AntSpriteContainer>>,
+    mut commands: Commands,
+    story_time: Res<StoryTime>,
+) {
+    for (ant_view_entity, speech_bubble) in ant_view_query.iter() {
+        if story_time.elapsed_ticks() < speech_bubble.expires_at {
+            continue;
+        }
+
+        // Surprisingly, Bevy doesn't fix parent/child relationship when despawning children, so do it manually.
+        commands
+            .entity(speech_bubble.bubble_entity)
+            .remove_parent()
+            .despawn_recursive();
+        commands.entity(ant_view_entity).remove::<SpeechBubble>();
+    }
+}
+
+/// A translucent backdrop, sized roughly to the line, with the line written over it. Sits above the ant's head.
+fn spawn_speech_bubble(line: &str, commands: &mut Commands) -> Entity {
+    let bubble_width = line.chars().count() as f32 * 0.3 + 0.5;
+
+    commands
+        .spawn(SpriteBundle {
+            transform: Transform::from_xyz(0.0, 1.5, 2.0),
+            sprite: Sprite {
+                color: Color::rgba(0.0, 0.0, 0.0, 0.6),
+                custom_size: Some(Vec2::new(bubble_width, 0.8)),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn(Text2dBundle {
+                transform: Transform {
+                    translation: Vec3::new(0.0, 0.0, 1.0),
+                    scale: Vec3::new(0.01, 0.01, 0.0),
+                    ..default()
+                },
+                text: Text::from_section(
+                    line,
+                    TextStyle {
+                        color: Color::WHITE,
+                        font_size: 50.0,
+                        ..default()
+                    },
+                ),
+                ..default()
+            });
+        })
+        .id()
+}
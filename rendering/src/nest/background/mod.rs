@@ -5,8 +5,11 @@ use crate::common::visible_grid::{grid_to_tile_pos, VisibleGrid};
 
 use simulation::{
     common::{grid::Grid, position::Position},
-    nest_simulation::nest::{AtNest, Nest},
-    story_time::{StoryTime, TimeInfo},
+    nest_simulation::{
+        nest::{AtNest, Nest},
+        weather::Weather,
+    },
+    story_time::{Season, StoryTime, TimeInfo},
 };
 
 #[derive(Component)]
@@ -34,11 +37,30 @@ fn interpolate_color(color_a: Color, color_b: Color, t: f32) -> Color {
     Color::rgba(r, g, b, a)
 }
 
+/// Each season has its own palette. The sky is washed out in Winter and warmer in Autumn.
+fn apply_season_palette(color: Color, season: Season) -> Color {
+    match season {
+        Season::Spring | Season::Summer => color,
+        Season::Autumn => interpolate_color(color, Color::rgba(0.9, 0.6, 0.3, color.a()), 0.1),
+        Season::Winter => interpolate_color(color, Color::rgba(0.7, 0.75, 0.8, color.a()), 0.25),
+    }
+}
+
+/// Clouds darken the sky while it rains, and more so during a storm. Clouds gather ahead of the rain, so even light rain
+/// noticeably darkens the sky.
+fn apply_weather_palette(color: Color, rain_intensity: f32) -> Color {
+    let overcast = Color::rgba(0.2, 0.22, 0.25, color.a());
+
+    interpolate_color(color, overcast, 0.6 * rain_intensity.sqrt())
+}
+
 // TODO: Instead of using sunrise/sunset, consider swapping to altitude and using the sun's altitude directly to define key moments.
 fn get_sky_gradient_color(
     current_decimal_hours: f32,
     sunrise_decimal_hours: f32,
     sunset_decimal_hours: f32,
+    season: Season,
+    rain_intensity: f32,
 ) -> (Color, Color) {
     let midnight = Color::rgba(0.0471, 0.0353, 0.0392, 1.0);
     let predawn = Color::rgba(0.0471, 0.0353, 0.0392, 1.0);
@@ -91,7 +113,10 @@ fn get_sky_gradient_color(
         south_color = interpolate_color(start_color, end_color, 1.0 - (1.0 - progress).powf(3.0));
     }
 
-    (north_color, south_color)
+    (
+        apply_weather_palette(apply_season_palette(north_color, season), rain_intensity),
+        apply_weather_palette(apply_season_palette(south_color, season), rain_intensity),
+    )
 }
 
 pub fn update_sky_background(
@@ -100,6 +125,7 @@ pub fn update_sky_background(
     nest_query: Query<&Nest>,
     visible_grid: Res<VisibleGrid>,
     story_time: Res<StoryTime>,
+    weather: Res<Weather>,
 ) {
     let visible_grid_entity = match visible_grid.0 {
         Some(visible_grid_entity) => visible_grid_entity,
@@ -113,8 +139,10 @@ pub fn update_sky_background(
     let nest = nest_query.single();
     let time_info = story_time.as_time_info();
 
-    // Update the sky's colors once a minute of elapsed *story time* not real-world time.
-    if time_info.days() == last_update_sky.0.days()
+    // Update the sky's colors once a minute of elapsed *story time* not real-world time, or right away as the rain picks
+    // up or eases off.
+    if !weather.is_changed()
+        && time_info.days() == last_update_sky.0.days()
         && time_info.hours() == last_update_sky.0.hours()
         // Check if difference between time_info and last_update_sky minutes is 1
         && (time_info.minutes() - last_update_sky.0.minutes()).abs() < 1
@@ -130,6 +158,8 @@ pub fn update_sky_background(
         current_decimal_hours,
         sunrise_decimal_hours,
         sunset_decimal_hours,
+        story_time.season(),
+        weather.intensity(),
     );
     for (mut tile_color, position) in sky_tile_query.iter_mut() {
         let t_y: f32 = position.y as f32 / nest.surface_level() as f32;
@@ -174,6 +204,7 @@ pub fn spawn_background(
     nest_query: Query<(&Grid, &Nest)>,
     mut tilemap_query: Query<(Entity, &mut TileStorage), With<BackgroundTilemap>>,
     story_time: Res<StoryTime>,
+    weather: Res<Weather>,
 ) {
     let (grid, nest) = nest_query.single();
     let air_height = nest.surface_level() + 1;
@@ -188,6 +219,8 @@ pub fn spawn_background(
         current_decimal_hours,
         sunrise_decimal_hours,
         sunset_decimal_hours,
+        story_time.season(),
+        weather.intensity(),
     );
 
     let width = grid.width();
@@ -0,0 +1,179 @@
+use bevy::prelude::*;
+
+use crate::common::visible_grid::VisibleGrid;
+
+use simulation::{
+    common::{
+        grid::{Grid, GridElements},
+        position::Position,
+    },
+    nest_simulation::{
+        element::{Element, ElementExposure},
+        nest::{AtNest, Nest},
+    },
+    story_time::{Season, StoryTime},
+};
+
+/// Decorations are purely cosmetic details (blooms, grass, fallen leaves) resting on the surface of the Nest.
+/// They aren't part of the simulation, and aren't persisted, because they're generated from the shape of the surface.
+#[derive(Component)]
+pub struct Decoration;
+
+struct DecorationSet {
+    colors: &'static [Color],
+    size: Vec2,
+    // One in every `spacing` columns of the surface is decorated.
+    spacing: isize,
+}
+
+const BLOOMS: DecorationSet = DecorationSet {
+    colors: &[
+        Color::rgb(1.0, 0.6, 0.8),
+        Color::rgb(1.0, 0.9, 0.3),
+        Color::rgb(0.95, 0.95, 0.95),
+    ],
+    size: Vec2::new(0.35, 0.35),
+    spacing: 4,
+};
+
+const GRASS: DecorationSet = DecorationSet {
+    colors: &[Color::rgb(0.3, 0.6, 0.2), Color::rgb(0.35, 0.5, 0.15)],
+    size: Vec2::new(0.15, 0.5),
+    spacing: 2,
+};
+
+const FALLEN_LEAVES: DecorationSet = DecorationSet {
+    colors: &[
+        Color::rgb(0.85, 0.45, 0.1),
+        Color::rgb(0.6, 0.25, 0.1),
+        Color::rgb(0.9, 0.7, 0.2),
+    ],
+    size: Vec2::new(0.4, 0.15),
+    spacing: 3,
+};
+
+// Winter doesn't need decorations because snow is drawn onto the surface elements directly.
+fn get_decoration_set(season: Season) -> Option<&'static DecorationSet> {
+    match season {
+        Season::Spring => Some(&BLOOMS),
+        Season::Summer => Some(&GRASS),
+        Season::Autumn => Some(&FALLEN_LEAVES),
+        Season::Winter => None,
+    }
+}
+
+pub fn spawn_decorations(
+    nest_query: Query<(&Grid, &Nest)>,
+    grid_elements: GridElements<AtNest>,
+    story_time: Res<StoryTime>,
+    mut commands: Commands,
+) {
+    let (grid, nest) = nest_query.single();
+
+    spawn_decoration_sprites(
+        grid,
+        nest,
+        &grid_elements,
+        story_time.season(),
+        &mut commands,
+    );
+}
+
+/// Regenerate decorations when the season changes or when the shape of the surface changes.
+/// Decorations are cheap to regenerate, and the surface changes infrequently, so it's simplest to start over.
+pub fn update_decorations(
+    changed_element_query: Query<&Position, (Changed<ElementExposure>, With<AtNest>)>,
+    decoration_query: Query<Entity, With<Decoration>>,
+    nest_query: Query<(&Grid, &Nest)>,
+    grid_elements: GridElements<AtNest>,
+    story_time: Res<StoryTime>,
+    visible_grid: Res<VisibleGrid>,
+    mut last_season: Local<Option<Season>>,
+    mut commands: Commands,
+) {
+    let visible_grid_entity = match visible_grid.0 {
+        Some(visible_grid_entity) => visible_grid_entity,
+        None => return,
+    };
+
+    // Early exit when Nest isn't visible because there's no view to update.
+    // Exit, rather than skipping system run, to prevent change detection from becoming backlogged.
+    let (grid, nest) = match nest_query.get(visible_grid_entity) {
+        Ok(nest_components) => nest_components,
+        Err(_) => return,
+    };
+
+    let season = story_time.season();
+    let is_season_changed = *last_season != Some(season);
+    *last_season = Some(season);
+
+    let is_surface_changed = changed_element_query
+        .iter()
+        .any(|position| position.y <= nest.surface_level() + 1);
+
+    if !is_season_changed && !is_surface_changed {
+        return;
+    }
+
+    for decoration_entity in decoration_query.iter() {
+        commands.entity(decoration_entity).despawn();
+    }
+
+    spawn_decoration_sprites(grid, nest, &grid_elements, season, &mut commands);
+}
+
+/// Non-System Helper Functions:
+
+/// Walk each column of the Nest from the sky downward and decorate the first Dirt found, if it's on the surface.
+/// Columns are picked by their position, rather than randomly, so decorations don't shuffle around when regenerated.
+fn spawn_decoration_sprites(
+    grid: &Grid,
+    nest: &Nest,
+    grid_elements: &GridElements<AtNest>,
+    season: Season,
+    commands: &mut Commands,
+) {
+    let decoration_set = match get_decoration_set(season) {
+        Some(decoration_set) => decoration_set,
+        None => return,
+    };
+
+    for x in 0..grid.width() {
+        if x % decoration_set.spacing != 0 {
+            continue;
+        }
+
+        let ground_position = (0..=nest.surface_level() + 1)
+            .map(|y| Position::new(x, y))
+            .find(|&position| !grid_elements.is(position, Element::Air));
+
+        let ground_position = match ground_position {
+            Some(ground_position) if grid_elements.is(ground_position, Element::Dirt) => {
+                ground_position
+            }
+            _ => continue,
+        };
+
+        // Rest the decoration on top of the ground rather than centering it in the tile above.
+        let mut translation = grid.grid_to_world_position(ground_position - Position::Y);
+        translation.y += (decoration_set.size.y - 1.0) / 2.0;
+        // Decorations render above Elements, but below Ants.
+        translation.z = 1.5;
+
+        let color_index = (x / decoration_set.spacing) as usize % decoration_set.colors.len();
+
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: decoration_set.colors[color_index],
+                    custom_size: Some(decoration_set.size),
+                    ..default()
+                },
+                transform: Transform::from_translation(translation),
+                ..default()
+            },
+            Decoration,
+            AtNest,
+        ));
+    }
+}
@@ -0,0 +1,212 @@
+use bevy::{
+    prelude::*,
+    render::{
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+        texture::ImageSampler,
+    },
+    utils::HashMap,
+};
+
+use crate::common::visible_grid::VisibleGrid;
+
+use simulation::{
+    common::{grid::Grid, position::Position},
+    nest_simulation::{
+        ant::dig_order::DigOrder,
+        nest::{AtNest, Nest},
+    },
+};
+
+const DIG_ORDER_TEXTURE_SIZE: u32 = 16;
+// Pixels are striped along diagonals. One in every few diagonals is colored in.
+const DIG_ORDER_STRIPE_SPACING: u32 = 4;
+const DIG_ORDER_STRIPE_COLOR: [u8; 4] = [255, 210, 60, 200];
+// Hatching goes above dirt, and its humidity, but beneath decorations and ants.
+const DIG_ORDER_Z_INDEX: f32 = 1.25;
+
+#[derive(Resource)]
+pub struct DigOrderTextureHandle(pub Handle<Image>);
+
+/// Elements are rendered as tiles, rather than sprites, so there's nothing to parent a dig order overlay to.
+/// Overlays are tracked separately from `ModelViewEntityMap` because that map already holds the element's tile.
+#[derive(Resource, Default)]
+pub struct DigOrderViewEntityMap(HashMap<Entity, Entity>);
+
+#[derive(Component)]
+pub struct DigOrderOverlay;
+
+pub fn on_spawn_dig_order(
+    dig_order_query: Query<(Entity, &Position), (Added<DigOrder>, With<AtNest>)>,
+    dig_order_texture_handle: Res<DigOrderTextureHandle>,
+    mut commands: Commands,
+    nest_query: Query<&Grid, With<Nest>>,
+    mut dig_order_view_entity_map: ResMut<DigOrderViewEntityMap>,
+    visible_grid: Res<VisibleGrid>,
+) {
+    let visible_grid_entity = match visible_grid.0 {
+        Some(visible_grid_entity) => visible_grid_entity,
+        None => return,
+    };
+
+    let grid = match nest_query.get(visible_grid_entity) {
+        Ok(grid) => grid,
+        Err(_) => return,
+    };
+
+    for (element_model_entity, position) in &dig_order_query {
+        spawn_dig_order(
+            element_model_entity,
+            position,
+            &dig_order_texture_handle,
+            grid,
+            &mut commands,
+            &mut dig_order_view_entity_map,
+        );
+    }
+}
+
+/// Marked dirt can fall, so keep its overlay in sync.
+pub fn on_update_dig_order_position(
+    dig_order_query: Query<(Entity, &Position), (Changed<Position>, With<DigOrder>, With<AtNest>)>,
+    mut dig_order_view_query: Query<&mut Transform, With<DigOrderOverlay>>,
+    nest_query: Query<&Grid, With<Nest>>,
+    dig_order_view_entity_map: Res<DigOrderViewEntityMap>,
+    visible_grid: Res<VisibleGrid>,
+) {
+    let visible_grid_entity = match visible_grid.0 {
+        Some(visible_grid_entity) => visible_grid_entity,
+        None => return,
+    };
+
+    let grid = match nest_query.get(visible_grid_entity) {
+        Ok(grid) => grid,
+        Err(_) => return,
+    };
+
+    for (element_model_entity, position) in &dig_order_query {
+        if let Some(dig_order_view_entity) = dig_order_view_entity_map.0.get(&element_model_entity)
+        {
+            if let Ok(mut transform) = dig_order_view_query.get_mut(*dig_order_view_entity) {
+                transform.translation = get_dig_order_translation(position, grid);
+            }
+        }
+    }
+}
+
+/// Marked dirt loses its overlay when it's dug out, when its order is cancelled, or when it's otherwise despawned.
+/// Noop instead of skipping to ensure `RemovedComponents` doesn't become backlogged.
+pub fn on_despawn_dig_order(
+    mut removed: RemovedComponents<DigOrder>,
+    mut commands: Commands,
+    mut dig_order_view_entity_map: ResMut<DigOrderViewEntityMap>,
+) {
+    for element_model_entity in removed.read() {
+        if let Some(dig_order_view_entity) =
+            dig_order_view_entity_map.0.remove(&element_model_entity)
+        {
+            commands.entity(dig_order_view_entity).despawn_recursive();
+        }
+    }
+}
+
+pub fn rerender_dig_orders(
+    dig_order_query: Query<(Entity, &Position), (With<DigOrder>, With<AtNest>)>,
+    dig_order_texture_handle: Res<DigOrderTextureHandle>,
+    mut commands: Commands,
+    nest_query: Query<&Grid, With<Nest>>,
+    mut dig_order_view_entity_map: ResMut<DigOrderViewEntityMap>,
+) {
+    let grid = nest_query.single();
+
+    for (element_model_entity, position) in &dig_order_query {
+        spawn_dig_order(
+            element_model_entity,
+            position,
+            &dig_order_texture_handle,
+            grid,
+            &mut commands,
+            &mut dig_order_view_entity_map,
+        );
+    }
+}
+
+pub fn despawn_dig_orders(
+    mut commands: Commands,
+    mut dig_order_view_entity_map: ResMut<DigOrderViewEntityMap>,
+) {
+    for (_, dig_order_view_entity) in dig_order_view_entity_map.0.drain() {
+        commands.entity(dig_order_view_entity).despawn_recursive();
+    }
+}
+
+/// Dig orders share a single hatched texture. It's generated, rather than loaded, because it's just a few stripes.
+pub fn initialize_dig_order_resources(mut images: ResMut<Assets<Image>>, mut commands: Commands) {
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: DIG_ORDER_TEXTURE_SIZE,
+            height: DIG_ORDER_TEXTURE_SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 0],
+        TextureFormat::Rgba8UnormSrgb,
+    );
+    // Keep the stripes crisp, like the tiles beneath them, rather than blurring them.
+    image.sampler = ImageSampler::nearest();
+
+    for y in 0..DIG_ORDER_TEXTURE_SIZE {
+        for x in 0..DIG_ORDER_TEXTURE_SIZE {
+            if (x + y) % DIG_ORDER_STRIPE_SPACING == 0 {
+                let index = ((y * DIG_ORDER_TEXTURE_SIZE + x) * 4) as usize;
+                image.data[index..index + 4].copy_from_slice(&DIG_ORDER_STRIPE_COLOR);
+            }
+        }
+    }
+
+    commands.insert_resource(DigOrderTextureHandle(images.add(image)));
+    commands.init_resource::<DigOrderViewEntityMap>();
+}
+
+/// Remove resources, etc.
+pub fn cleanup_dig_orders(mut commands: Commands) {
+    commands.remove_resource::<DigOrderTextureHandle>();
+    commands.remove_resource::<DigOrderViewEntityMap>();
+}
+
+/// Non-System Helper Functions:
+
+fn spawn_dig_order(
+    element_model_entity: Entity,
+    position: &Position,
+    dig_order_texture_handle: &DigOrderTextureHandle,
+    grid: &Grid,
+    commands: &mut Commands,
+    dig_order_view_entity_map: &mut ResMut<DigOrderViewEntityMap>,
+) {
+    let dig_order_view_entity = commands
+        .spawn((
+            SpriteBundle {
+                transform: Transform::from_translation(get_dig_order_translation(position, grid)),
+                sprite: Sprite {
+                    custom_size: Some(Vec2::splat(1.0)),
+                    ..default()
+                },
+                texture: dig_order_texture_handle.0.clone(),
+                ..default()
+            },
+            DigOrderOverlay,
+            AtNest,
+        ))
+        .id();
+
+    dig_order_view_entity_map
+        .0
+        .insert(element_model_entity, dig_order_view_entity);
+}
+
+fn get_dig_order_translation(position: &Position, grid: &Grid) -> Vec3 {
+    let mut translation = grid.grid_to_world_position(*position);
+    translation.z = DIG_ORDER_Z_INDEX;
+
+    translation
+}
@@ -1,6 +1,6 @@
 pub mod sprite_sheet;
 
-use self::sprite_sheet::{get_element_index, ElementSpriteSheetHandle};
+use self::sprite_sheet::{get_element_index, get_surface_element_index, ElementSpriteSheetHandle};
 use crate::common::{
     visible_grid::{grid_to_tile_pos, VisibleGrid},
     ModelViewEntityMap,
@@ -10,9 +10,10 @@ use bevy_ecs_tilemap::prelude::*;
 use simulation::{
     common::{grid::Grid, position::Position},
     nest_simulation::{
-        element::{Air, Element, ElementExposure},
+        element::{definition::ElementDefinitions, Air, Element, ElementExposure, FoodKind},
         nest::{AtNest, Nest},
     },
+    story_time::{Season, StoryTime},
 };
 
 #[derive(Component)]
@@ -47,14 +48,22 @@ pub fn spawn_element_tilemap(
 /// This *only* handles the initial rendering of the Element sprite. Updates are handled by other systems.
 pub fn on_spawn_element(
     mut element_query: Query<
-        (&Position, &Element, &ElementExposure, Entity),
+        (
+            &Position,
+            &Element,
+            &ElementExposure,
+            Entity,
+            Option<&FoodKind>,
+        ),
         (Added<Element>, With<AtNest>, Without<Air>),
     >,
-    nest_query: Query<&Grid, With<Nest>>,
+    nest_query: Query<(&Grid, &Nest)>,
     mut commands: Commands,
     mut tilemap_query: Query<(Entity, &mut TileStorage), With<ElementTilemap>>,
     mut model_view_entity_map: ResMut<ModelViewEntityMap>,
     visible_grid: Res<VisibleGrid>,
+    story_time: Res<StoryTime>,
+    element_definitions: Res<ElementDefinitions>,
 ) {
     let visible_grid_entity = match visible_grid.0 {
         Some(visible_grid_entity) => visible_grid_entity,
@@ -63,20 +72,26 @@ pub fn on_spawn_element(
 
     // Early exit when Nest isn't visible because there's no view to update.
     // Exit, rather than skipping system run, to prevent change detection from becoming backlogged.
-    let grid = match nest_query.get(visible_grid_entity) {
-        Ok(grid) => grid,
+    let (grid, nest) = match nest_query.get(visible_grid_entity) {
+        Ok(nest_components) => nest_components,
         Err(_) => return,
     };
 
-    for (element_position, element, element_exposure, element_model_entity) in
+    let season = story_time.season();
+
+    for (element_position, element, element_exposure, element_model_entity, food_kind) in
         element_query.iter_mut()
     {
         spawn_element_sprite(
             element_model_entity,
             element,
+            food_kind,
             element_position,
             element_exposure,
             &grid,
+            &nest,
+            season,
+            &element_definitions,
             &mut commands,
             &mut tilemap_query,
             &mut model_view_entity_map,
@@ -89,23 +104,37 @@ pub fn on_spawn_element(
 /// have not been changed or added, though, so a separate rerender system is needed.
 pub fn rerender_elements(
     mut element_query: Query<
-        (&Position, &Element, &ElementExposure, Entity),
+        (
+            &Position,
+            &Element,
+            &ElementExposure,
+            Entity,
+            Option<&FoodKind>,
+        ),
         (With<AtNest>, Without<Air>),
     >,
-    nest_query: Query<&Grid, With<Nest>>,
+    nest_query: Query<(&Grid, &Nest)>,
     mut commands: Commands,
     mut tilemap_query: Query<(Entity, &mut TileStorage), With<ElementTilemap>>,
     mut model_view_entity_map: ResMut<ModelViewEntityMap>,
+    story_time: Res<StoryTime>,
+    element_definitions: Res<ElementDefinitions>,
 ) {
-    let grid = nest_query.single();
+    let (grid, nest) = nest_query.single();
+    let season = story_time.season();
 
-    for (element_position, element, element_exposure, entity) in element_query.iter_mut() {
+    for (element_position, element, element_exposure, entity, food_kind) in element_query.iter_mut()
+    {
         spawn_element_sprite(
             entity,
             element,
+            food_kind,
             element_position,
             element_exposure,
             &grid,
+            &nest,
+            season,
+            &element_definitions,
             &mut commands,
             &mut tilemap_query,
             &mut model_view_entity_map,
@@ -116,14 +145,26 @@ pub fn rerender_elements(
 /// When an Element model has its Position updated, reflect the change in Position by updating the Translation
 /// on its associated view. Update TileStorage to reflect the change in position, too.
 /// This does not include the initial spawn of the Element model, which is handled by `on_spawn_element`.
+/// The TileTextureIndex is updated, too, because moving on or off the surface changes an Element's seasonal appearance.
 /// This relies on Ref<Position> instead of Changed<Position> to be able to filter against `is_added()`
 pub fn on_update_element_position(
-    element_query: Query<(Ref<Position>, Entity), (With<AtNest>, Without<Air>)>,
-    nest_query: Query<&Grid, With<Nest>>,
+    element_query: Query<
+        (
+            Ref<Position>,
+            &Element,
+            &ElementExposure,
+            Entity,
+            Option<&FoodKind>,
+        ),
+        (With<AtNest>, Without<Air>),
+    >,
+    nest_query: Query<(&Grid, &Nest)>,
     mut commands: Commands,
     mut tilemap_query: Query<&mut TileStorage, With<ElementTilemap>>,
     model_view_entity_map: Res<ModelViewEntityMap>,
     visible_grid: Res<VisibleGrid>,
+    story_time: Res<StoryTime>,
+    element_definitions: Res<ElementDefinitions>,
 ) {
     let visible_grid_entity = match visible_grid.0 {
         Some(visible_grid_entity) => visible_grid_entity,
@@ -132,14 +173,17 @@ pub fn on_update_element_position(
 
     // Early exit when Nest isn't visible because there's no view to update.
     // Exit, rather than skipping system run, to prevent change detection from becoming backlogged.
-    let grid = match nest_query.get(visible_grid_entity) {
-        Ok(grid) => grid,
+    let (grid, nest) = match nest_query.get(visible_grid_entity) {
+        Ok(nest_components) => nest_components,
         Err(_) => return,
     };
 
     let mut tile_storage = tilemap_query.single_mut();
+    let season = story_time.season();
 
-    for (element_position, element_model_entity) in element_query.iter() {
+    for (element_position, element, element_exposure, element_model_entity, food_kind) in
+        element_query.iter()
+    {
         // `on_spawn_element` handles `Added<Position>`
         if element_position.is_added() || !element_position.is_changed() {
             continue;
@@ -151,7 +195,18 @@ pub fn on_update_element_position(
         };
 
         let tile_pos = grid_to_tile_pos(grid, *element_position);
-        commands.entity(element_view_entity).insert(tile_pos);
+        let texture_index = TileTextureIndex(get_nest_element_index(
+            *element,
+            food_kind.copied(),
+            *element_position,
+            *element_exposure,
+            nest,
+            season,
+            &element_definitions,
+        ) as u32);
+        commands
+            .entity(element_view_entity)
+            .insert((tile_pos, texture_index));
         // NOTE: This leaves the previous `tile_pos` stale, but that's fine because it's just Air which isn't rendered.
         // TODO: Consider benefits of tracking PreviousPosition in Element and using that to clear stale tile_pos.
         tile_storage.set(&tile_pos, element_view_entity);
@@ -163,11 +218,22 @@ pub fn on_update_element_position(
 /// This does not include the initial spawn of the Element model, which is handled by `on_spawn_element`.
 /// This relies on Ref<ElementExposure> instead of Changed<ElementExposure> to be able to filter against `is_added()`
 pub fn on_update_element_exposure(
-    element_query: Query<(Ref<ElementExposure>, &Element, Entity), (With<AtNest>, Without<Air>)>,
-    nest_query: Query<&Grid, With<Nest>>,
+    element_query: Query<
+        (
+            Ref<ElementExposure>,
+            &Element,
+            &Position,
+            Entity,
+            Option<&FoodKind>,
+        ),
+        (With<AtNest>, Without<Air>),
+    >,
+    nest_query: Query<&Nest>,
     mut commands: Commands,
     model_view_entity_map: Res<ModelViewEntityMap>,
     visible_grid: Res<VisibleGrid>,
+    story_time: Res<StoryTime>,
+    element_definitions: Res<ElementDefinitions>,
 ) {
     let visible_grid_entity = match visible_grid.0 {
         Some(visible_grid_entity) => visible_grid_entity,
@@ -176,11 +242,16 @@ pub fn on_update_element_exposure(
 
     // Early exit when Nest isn't visible because there's no view to update.
     // Exit, rather than skipping system run, to prevent change detection from becoming backlogged.
-    if nest_query.get(visible_grid_entity).is_err() {
-        return;
-    }
+    let nest = match nest_query.get(visible_grid_entity) {
+        Ok(nest) => nest,
+        Err(_) => return,
+    };
+
+    let season = story_time.season();
 
-    for (element_exposure, element, element_model_entity) in element_query.iter() {
+    for (element_exposure, element, element_position, element_model_entity, food_kind) in
+        element_query.iter()
+    {
         // `on_spawn_element` handles `Added<ElementExposure>`
         if element_exposure.is_added() || !element_exposure.is_changed() {
             continue;
@@ -191,12 +262,81 @@ pub fn on_update_element_exposure(
             None => panic!("Expected to find view entity for model entity."),
         };
 
-        let texture_index = TileTextureIndex(get_element_index(*element_exposure, *element) as u32);
+        let texture_index = TileTextureIndex(get_nest_element_index(
+            *element,
+            food_kind.copied(),
+            *element_position,
+            *element_exposure,
+            nest,
+            season,
+            &element_definitions,
+        ) as u32);
 
         commands.entity(element_view_entity).insert(texture_index);
     }
 }
 
+/// When the season changes, Elements on the surface need to reflect the new season.
+/// Elements underground are unaffected by the season so they're left alone.
+pub fn on_update_season(
+    element_query: Query<
+        (
+            &Element,
+            &Position,
+            &ElementExposure,
+            Entity,
+            Option<&FoodKind>,
+        ),
+        (With<AtNest>, Without<Air>),
+    >,
+    nest_query: Query<&Nest>,
+    mut commands: Commands,
+    model_view_entity_map: Res<ModelViewEntityMap>,
+    visible_grid: Res<VisibleGrid>,
+    story_time: Res<StoryTime>,
+    element_definitions: Res<ElementDefinitions>,
+    mut last_season: Local<Option<Season>>,
+) {
+    let season = story_time.season();
+
+    if *last_season == Some(season) {
+        return;
+    }
+
+    *last_season = Some(season);
+
+    // Rerendering the Nest uses the current season so there's nothing to update if the Nest isn't visible.
+    let visible_grid_entity = match visible_grid.0 {
+        Some(visible_grid_entity) => visible_grid_entity,
+        None => return,
+    };
+
+    let nest = match nest_query.get(visible_grid_entity) {
+        Ok(nest) => nest,
+        Err(_) => return,
+    };
+
+    for (element, element_position, element_exposure, element_model_entity, food_kind) in
+        element_query.iter()
+    {
+        if !is_surface_element(element_position, nest) {
+            continue;
+        }
+
+        if let Some(&element_view_entity) = model_view_entity_map.get(&element_model_entity) {
+            let texture_index = TileTextureIndex(get_surface_element_index(
+                *element_exposure,
+                *element,
+                food_kind.copied(),
+                season,
+                &element_definitions,
+            ) as u32);
+
+            commands.entity(element_view_entity).insert(texture_index);
+        }
+    }
+}
+
 pub fn cleanup_elements(mut commands: Commands) {
     commands.remove_resource::<ElementSpriteSheetHandle>();
     commands.remove_resource::<ElementSpriteSheetHandle>();
@@ -208,9 +348,13 @@ pub fn cleanup_elements(mut commands: Commands) {
 fn spawn_element_sprite(
     element_model_entity: Entity,
     element: &Element,
+    food_kind: Option<&FoodKind>,
     element_position: &Position,
     element_exposure: &ElementExposure,
     grid: &Grid,
+    nest: &Nest,
+    season: Season,
+    element_definitions: &ElementDefinitions,
     commands: &mut Commands,
     tilemap_query: &mut Query<(Entity, &mut TileStorage), With<ElementTilemap>>,
     model_view_entity_map: &mut ResMut<ModelViewEntityMap>,
@@ -223,7 +367,15 @@ fn spawn_element_sprite(
         TileBundle {
             position: tile_pos,
             tilemap_id: TilemapId(tilemap_entity),
-            texture_index: TileTextureIndex(get_element_index(*element_exposure, *element) as u32),
+            texture_index: TileTextureIndex(get_nest_element_index(
+                *element,
+                food_kind.copied(),
+                *element_position,
+                *element_exposure,
+                nest,
+                season,
+                element_definitions,
+            ) as u32),
             ..default()
         },
     );
@@ -232,3 +384,31 @@ fn spawn_element_sprite(
     model_view_entity_map.insert(element_model_entity, element_view_entity);
     tile_storage.set(&tile_pos, element_view_entity);
 }
+
+/// The first row underground is included because it's the row which forms the surface before any digging occurs.
+fn is_surface_element(element_position: &Position, nest: &Nest) -> bool {
+    element_position.y <= nest.surface_level() + 1
+}
+
+/// Elements lining tunnels look the same all year, but Elements on the surface change with the season.
+fn get_nest_element_index(
+    element: Element,
+    food_kind: Option<FoodKind>,
+    element_position: Position,
+    element_exposure: ElementExposure,
+    nest: &Nest,
+    season: Season,
+    element_definitions: &ElementDefinitions,
+) -> usize {
+    if is_surface_element(&element_position, nest) {
+        get_surface_element_index(
+            element_exposure,
+            element,
+            food_kind,
+            season,
+            element_definitions,
+        )
+    } else {
+        get_element_index(element_exposure, element, food_kind, element_definitions)
+    }
+}
@@ -2,9 +2,34 @@ use bevy::{asset::LoadState, prelude::*};
 
 use simulation::{
     app_state::AppState,
-    nest_simulation::element::{Element, ElementExposure},
+    nest_simulation::element::{
+        definition::ElementDefinitions, Element, ElementExposure, FoodKind,
+    },
+    story_time::Season,
 };
 
+/// How the rows of the element sprite sheet correspond to which neighbors are exposed.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum ExposureLayout {
+    // One row for each combination of exposed sides. Corners are ignored.
+    Sides,
+    // One row for each combination of exposed sides and visible corners, i.e. a 47 tile set, for smooth tunnel corners.
+    SidesAndCorners,
+}
+
+impl ExposureLayout {
+    pub fn rows(&self) -> usize {
+        match self {
+            ExposureLayout::Sides => 16,
+            ExposureLayout::SidesAndCorners => 47,
+        }
+    }
+}
+
+// NOTE: 47 rows of 128px tiles makes for a tall sprite sheet. It fits within the 8192px texture limit of native and WebGPU
+// but wouldn't fit WebGL2's 2048px limit.
+pub const ELEMENT_SPRITE_SHEET_LAYOUT: ExposureLayout = ExposureLayout::SidesAndCorners;
+
 #[derive(Resource)]
 pub struct ElementSpriteSheetHandle(pub Handle<Image>);
 
@@ -20,6 +45,7 @@ pub fn start_load_element_sprite_sheet(asset_server: Res<AssetServer>, mut comma
 pub fn check_element_sprite_sheet_loaded(
     mut next_state: ResMut<NextState<AppState>>,
     element_sprite_sheet_handle: Res<ElementSpriteSheetHandle>,
+    element_definitions: Res<ElementDefinitions>,
     asset_server: Res<AssetServer>,
     mut commands: Commands,
     mut texture_atlases: ResMut<Assets<TextureAtlas>>,
@@ -30,8 +56,8 @@ pub fn check_element_sprite_sheet_loaded(
         let texture_atlas = TextureAtlas::from_grid(
             element_sprite_sheet_handle.0.clone(),
             Vec2::splat(128.0),
-            3,
-            16,
+            element_definitions.sprite_columns(),
+            ELEMENT_SPRITE_SHEET_LAYOUT.rows(),
             None,
             None,
         );
@@ -45,7 +71,7 @@ pub fn check_element_sprite_sheet_loaded(
 }
 
 // TODO: super hardcoded to the order they appear in sprite_sheet.png
-// Spritesheet is organized as:
+// With the Sides layout, the spritesheet is organized as:
 // 0 - none exposed
 // 1 - north exposed
 // 2 - east exposed
@@ -62,112 +88,77 @@ pub fn check_element_sprite_sheet_loaded(
 // 13 - south/west/north exposed
 // 14 - west/north/east exposed
 // 15 - all exposed
-pub fn get_element_index(exposure: ElementExposure, element: Element) -> usize {
-    let row_index = match exposure {
-        ElementExposure {
-            north: false,
-            east: false,
-            south: false,
-            west: false,
-        } => 0,
-        ElementExposure {
-            north: true,
-            east: false,
-            south: false,
-            west: false,
-        } => 1,
-        ElementExposure {
-            north: false,
-            east: true,
-            south: false,
-            west: false,
-        } => 2,
-        ElementExposure {
-            north: false,
-            east: false,
-            south: true,
-            west: false,
-        } => 3,
-        ElementExposure {
-            north: false,
-            east: false,
-            south: false,
-            west: true,
-        } => 4,
-        ElementExposure {
-            north: true,
-            east: true,
-            south: false,
-            west: false,
-        } => 5,
-        ElementExposure {
-            north: false,
-            east: true,
-            south: true,
-            west: false,
-        } => 6,
-        ElementExposure {
-            north: false,
-            east: false,
-            south: true,
-            west: true,
-        } => 7,
-        ElementExposure {
-            north: true,
-            east: false,
-            south: false,
-            west: true,
-        } => 8,
-        ElementExposure {
-            north: true,
-            east: false,
-            south: true,
-            west: false,
-        } => 9,
-        ElementExposure {
-            north: false,
-            east: true,
-            south: false,
-            west: true,
-        } => 10,
-        ElementExposure {
-            north: true,
-            east: true,
-            south: true,
-            west: false,
-        } => 11,
-        ElementExposure {
-            north: false,
-            east: true,
-            south: true,
-            west: true,
-        } => 12,
-        ElementExposure {
-            north: true,
-            east: false,
-            south: true,
-            west: true,
-        } => 13,
-        ElementExposure {
-            north: true,
-            east: true,
-            south: false,
-            west: true,
-        } => 14,
-        ElementExposure {
-            north: true,
-            east: true,
-            south: true,
-            west: true,
-        } => 15,
-    };
+// With the SidesAndCorners layout, rows are ordered as given by `ElementExposure::get_blob_index`.
+// Columns are given by each element's definition in assets/config/default.elements.ron, and food by its kind's.
+pub fn get_element_index(
+    exposure: ElementExposure,
+    element: Element,
+    food_kind: Option<FoodKind>,
+    element_definitions: &ElementDefinitions,
+) -> usize {
+    get_row_index(exposure) * element_definitions.sprite_columns()
+        + get_column_index(element, food_kind, element_definitions)
+}
 
-    let column_index = match element {
-        Element::Dirt => 0,
-        Element::Food => 1,
-        Element::Sand => 2,
-        _ => panic!("Element {:?} not supported", element),
+/// Elements on the surface reflect the season. Snow settles on top of surface elements during Winter, and surface water
+/// freezes over whether or not it's open to the sky.
+pub fn get_surface_element_index(
+    exposure: ElementExposure,
+    element: Element,
+    food_kind: Option<FoodKind>,
+    season: Season,
+    element_definitions: &ElementDefinitions,
+) -> usize {
+    let winter_column_index = element_definitions.get(element).winter_sprite_column();
+
+    let column_index = match (season, winter_column_index) {
+        (Season::Winter, Some(winter_column_index))
+            if exposure.north() || element == Element::Water =>
+        {
+            winter_column_index
+        }
+        _ => get_column_index(element, food_kind, element_definitions),
     };
 
-    row_index * 3 + column_index
+    get_row_index(exposure) * element_definitions.sprite_columns() + column_index
+}
+
+fn get_column_index(
+    element: Element,
+    food_kind: Option<FoodKind>,
+    element_definitions: &ElementDefinitions,
+) -> usize {
+    element_definitions
+        .sprite_column(element, food_kind)
+        .unwrap_or_else(|| panic!("Element {:?} not supported", element))
+}
+
+fn get_row_index(exposure: ElementExposure) -> usize {
+    if ELEMENT_SPRITE_SHEET_LAYOUT == ExposureLayout::SidesAndCorners {
+        return exposure.get_blob_index();
+    }
+
+    match (
+        exposure.north(),
+        exposure.east(),
+        exposure.south(),
+        exposure.west(),
+    ) {
+        (false, false, false, false) => 0,
+        (true, false, false, false) => 1,
+        (false, true, false, false) => 2,
+        (false, false, true, false) => 3,
+        (false, false, false, true) => 4,
+        (true, true, false, false) => 5,
+        (false, true, true, false) => 6,
+        (false, false, true, true) => 7,
+        (true, false, false, true) => 8,
+        (true, false, true, false) => 9,
+        (false, true, false, true) => 10,
+        (true, true, true, false) => 11,
+        (false, true, true, true) => 12,
+        (true, false, true, true) => 13,
+        (true, true, false, true) => 14,
+        (true, true, true, true) => 15,
+    }
 }
@@ -0,0 +1,199 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::common::visible_grid::VisibleGrid;
+
+use simulation::{
+    common::{grid::Grid, position::Position},
+    nest_simulation::{
+        element::water::{Moisture, MAX_MOISTURE},
+        nest::{AtNest, Nest},
+    },
+};
+
+#[derive(Resource)]
+pub struct HumidityVisibility(pub Visibility);
+
+/// Elements are rendered as tiles, rather than sprites, so there's nothing to parent a humidity overlay to.
+/// Overlays are tracked separately from `ModelViewEntityMap` because that map already holds the element's tile.
+#[derive(Resource, Default)]
+pub struct HumidityViewEntityMap(HashMap<Entity, Entity>);
+
+#[derive(Component)]
+pub struct HumidityOverlay;
+
+pub fn on_spawn_humidity(
+    moisture_query: Query<(Entity, &Position, &Moisture), (Added<Moisture>, With<AtNest>)>,
+    humidity_visibility: Res<HumidityVisibility>,
+    mut commands: Commands,
+    nest_query: Query<&Grid, With<Nest>>,
+    mut humidity_view_entity_map: ResMut<HumidityViewEntityMap>,
+    visible_grid: Res<VisibleGrid>,
+) {
+    let visible_grid_entity = match visible_grid.0 {
+        Some(visible_grid_entity) => visible_grid_entity,
+        None => return,
+    };
+
+    let grid = match nest_query.get(visible_grid_entity) {
+        Ok(grid) => grid,
+        Err(_) => return,
+    };
+
+    for (element_model_entity, position, moisture) in &moisture_query {
+        spawn_humidity(
+            element_model_entity,
+            position,
+            moisture,
+            &humidity_visibility,
+            grid,
+            &mut commands,
+            &mut humidity_view_entity_map,
+        );
+    }
+}
+
+/// Moist dirt can dry out, soak up more water, or fall, so keep its overlay in sync.
+pub fn on_update_humidity(
+    moisture_query: Query<
+        (Entity, Ref<Position>, Ref<Moisture>),
+        (Or<(Changed<Position>, Changed<Moisture>)>, With<AtNest>),
+    >,
+    mut humidity_view_query: Query<(&mut Transform, &mut Sprite), With<HumidityOverlay>>,
+    nest_query: Query<&Grid, With<Nest>>,
+    humidity_view_entity_map: Res<HumidityViewEntityMap>,
+    visible_grid: Res<VisibleGrid>,
+) {
+    let visible_grid_entity = match visible_grid.0 {
+        Some(visible_grid_entity) => visible_grid_entity,
+        None => return,
+    };
+
+    let grid = match nest_query.get(visible_grid_entity) {
+        Ok(grid) => grid,
+        Err(_) => return,
+    };
+
+    for (element_model_entity, position, moisture) in &moisture_query {
+        if moisture.is_added() {
+            continue;
+        }
+
+        if let Some(humidity_view_entity) = humidity_view_entity_map.0.get(&element_model_entity) {
+            if let Ok((mut transform, mut sprite)) =
+                humidity_view_query.get_mut(*humidity_view_entity)
+            {
+                transform.translation = grid.grid_to_world_position(*position);
+                *sprite = get_humidity_sprite(&moisture);
+            }
+        }
+    }
+}
+
+/// Dirt loses its overlay when it's dug up, decays, or is otherwise despawned.
+/// Noop instead of skipping to ensure `RemovedComponents` doesn't become backlogged.
+pub fn on_despawn_humidity(
+    mut removed: RemovedComponents<Moisture>,
+    mut commands: Commands,
+    mut humidity_view_entity_map: ResMut<HumidityViewEntityMap>,
+) {
+    for element_model_entity in removed.read() {
+        if let Some(humidity_view_entity) = humidity_view_entity_map.0.remove(&element_model_entity)
+        {
+            commands.entity(humidity_view_entity).despawn_recursive();
+        }
+    }
+}
+
+pub fn rerender_humidity(
+    moisture_query: Query<(Entity, &Position, &Moisture), With<AtNest>>,
+    humidity_visibility: Res<HumidityVisibility>,
+    mut commands: Commands,
+    nest_query: Query<&Grid, With<Nest>>,
+    mut humidity_view_entity_map: ResMut<HumidityViewEntityMap>,
+) {
+    let grid = nest_query.single();
+
+    for (element_model_entity, position, moisture) in &moisture_query {
+        spawn_humidity(
+            element_model_entity,
+            position,
+            moisture,
+            &humidity_visibility,
+            grid,
+            &mut commands,
+            &mut humidity_view_entity_map,
+        );
+    }
+}
+
+pub fn on_update_humidity_visibility(
+    mut humidity_view_query: Query<&mut Visibility, With<HumidityOverlay>>,
+    humidity_visibility: Res<HumidityVisibility>,
+) {
+    if humidity_visibility.is_changed() {
+        for mut visibility in humidity_view_query.iter_mut() {
+            *visibility = humidity_visibility.0;
+        }
+    }
+}
+
+pub fn despawn_humidity(
+    mut commands: Commands,
+    mut humidity_view_entity_map: ResMut<HumidityViewEntityMap>,
+) {
+    for (_, humidity_view_entity) in humidity_view_entity_map.0.drain() {
+        commands.entity(humidity_view_entity).despawn_recursive();
+    }
+}
+
+/// Humidity is hidden by default. It's only of interest to players who are tending to brood or fungus.
+pub fn initialize_humidity_resources(mut commands: Commands) {
+    commands.insert_resource(HumidityVisibility(Visibility::Hidden));
+    commands.init_resource::<HumidityViewEntityMap>();
+}
+
+/// Remove resources, etc.
+pub fn cleanup_humidity(mut commands: Commands) {
+    commands.remove_resource::<HumidityVisibility>();
+    commands.remove_resource::<HumidityViewEntityMap>();
+}
+
+/// Non-System Helper Functions:
+
+fn spawn_humidity(
+    element_model_entity: Entity,
+    position: &Position,
+    moisture: &Moisture,
+    humidity_visibility: &HumidityVisibility,
+    grid: &Grid,
+    commands: &mut Commands,
+    humidity_view_entity_map: &mut ResMut<HumidityViewEntityMap>,
+) {
+    let humidity_view_entity = commands
+        .spawn((
+            SpriteBundle {
+                transform: Transform::from_translation(grid.grid_to_world_position(*position)),
+                sprite: get_humidity_sprite(moisture),
+                visibility: humidity_visibility.0,
+                ..default()
+            },
+            HumidityOverlay,
+            AtNest,
+        ))
+        .id();
+
+    humidity_view_entity_map
+        .0
+        .insert(element_model_entity, humidity_view_entity);
+}
+
+/// Damp dirt is tinted a faint blue which deepens as it approaches saturation.
+fn get_humidity_sprite(moisture: &Moisture) -> Sprite {
+    let max_humidity_opacity = 0.6;
+    let humidity_opacity = max_humidity_opacity * (moisture.value() / MAX_MOISTURE).min(1.0);
+
+    Sprite {
+        color: Color::rgba(0.1, 0.35, 0.95, humidity_opacity),
+        ..default()
+    }
+}
@@ -1,48 +1,70 @@
 pub mod ant;
 pub mod background;
+pub mod decoration;
+pub mod dig_order;
 pub mod element;
+pub mod humidity;
 pub mod pheromone;
+pub mod weather;
 
 use crate::common::visible_grid::set_visible_grid_state_nest;
 
 use self::{
     ant::{
+        brood::{animate_brood, on_added_brood_stage, on_spawn_brood, rerender_brood},
         cleanup_ants,
         emote::{
             ants_sleep_emote, despawn_expired_emotes, on_added_ant_emote, on_ant_ate_food,
             on_ant_wake_up, on_removed_ant_emote,
         },
-        on_added_ant_dead, on_spawn_ant, on_update_ant_color, on_update_ant_inventory,
-        on_update_ant_orientation, on_update_ant_position, rerender_ants,
+        on_added_ant_asleep, on_added_ant_collapsed, on_added_ant_dead, on_removed_ant_asleep,
+        on_removed_ant_collapsed, on_spawn_ant, on_update_ant_carry_group, on_update_ant_color,
+        on_update_ant_inventory, on_update_ant_orientation, on_update_ant_position, rerender_ants,
+        speech::{ants_speak, despawn_expired_speech_bubbles},
     },
     background::{
         cleanup_background, initialize_background_resources, spawn_background,
         spawn_background_tilemap, update_sky_background, Background, BackgroundTilemap,
     },
+    decoration::{spawn_decorations, update_decorations, Decoration},
+    dig_order::{
+        cleanup_dig_orders, despawn_dig_orders, initialize_dig_order_resources,
+        on_despawn_dig_order, on_spawn_dig_order, on_update_dig_order_position,
+        rerender_dig_orders,
+    },
     element::{
         cleanup_elements, on_spawn_element, on_update_element_exposure, on_update_element_position,
-        rerender_elements, spawn_element_tilemap,
+        on_update_season, rerender_elements, spawn_element_tilemap,
         sprite_sheet::{check_element_sprite_sheet_loaded, start_load_element_sprite_sheet},
         ElementTilemap,
     },
+    humidity::{
+        cleanup_humidity, despawn_humidity, initialize_humidity_resources, on_despawn_humidity,
+        on_spawn_humidity, on_update_humidity, on_update_humidity_visibility, rerender_humidity,
+    },
     pheromone::{
         cleanup_pheromones, initialize_pheromone_resources, on_spawn_pheromone,
         on_update_pheromone_visibility, rerender_pheromones,
     },
+    weather::{spawn_rain_audio, update_rain_audio, update_raindrops, RainAudio, Raindrop},
 };
 use super::common::{
+    airdrop::{on_spawn_airdrop, on_update_airdrop, rerender_airdrops},
     despawn_view, despawn_view_by_model, on_despawn,
     visible_grid::{VisibleGrid, VisibleGridState},
+    visual_update::VisualUpdateSet,
 };
 use bevy::prelude::*;
 use simulation::{
     app_state::AppState,
+    common::airdrop::Airdrop,
     nest_simulation::{
-        ant::Ant,
+        ant::{brood::Brood, Ant},
         element::Element,
         nest::{AtNest, Nest},
         pheromone::Pheromone,
     },
+    story_time::{is_zone_playing, StoryPlaybackState},
     CleanupSet, FinishSetupSet,
 };
 
@@ -65,6 +87,8 @@ impl Plugin for NestRenderingPlugin {
             OnEnter(AppState::FinishSetup),
             (
                 initialize_pheromone_resources,
+                initialize_humidity_resources,
+                initialize_dig_order_resources,
                 initialize_background_resources,
             )
                 .chain()
@@ -75,26 +99,70 @@ impl Plugin for NestRenderingPlugin {
             Update,
             (
                 // Spawn
-                (on_spawn_ant, on_spawn_element, on_spawn_pheromone),
-                // Despawn
                 (
-                    on_despawn::<Ant, AtNest>,
-                    on_despawn::<Element, AtNest>,
-                    on_despawn::<Pheromone, AtNest>,
+                    on_spawn_ant,
+                    on_spawn_brood,
+                    on_spawn_element,
+                    on_spawn_pheromone,
+                    on_spawn_humidity,
+                    on_spawn_dig_order,
+                    on_spawn_airdrop::<AtNest>,
                 ),
                 // Added
-                (on_added_ant_emote, on_added_ant_dead),
-                // Removed
-                (on_removed_ant_emote),
+                (
+                    on_added_ant_dead,
+                    on_added_ant_collapsed,
+                    on_added_ant_asleep,
+                    on_added_brood_stage,
+                ),
                 // Updated
                 (
                     on_update_ant_position,
                     on_update_ant_orientation,
                     on_update_ant_color,
                     on_update_ant_inventory,
+                    on_update_ant_carry_group,
                     on_update_element_position,
                     on_update_element_exposure,
                     on_update_pheromone_visibility,
+                    on_update_humidity,
+                    on_update_humidity_visibility,
+                    on_update_dig_order_position,
+                    on_update_season,
+                    on_update_airdrop::<AtNest>,
+                ),
+                // Misc
+                (update_sky_background, update_decorations, animate_brood),
+            )
+                .in_set(VisualUpdateSet)
+                .run_if(
+                    in_state(AppState::TellStory)
+                        .or_else(in_state(AppState::PostSetupClearChangeDetection)),
+                ),
+        );
+
+        app.add_systems(
+            Update,
+            (
+                // Despawn
+                (
+                    on_despawn::<Ant, AtNest>,
+                    // Ants which head out into the crater leave the nest without being despawned.
+                    on_despawn::<AtNest, AtNest>,
+                    on_despawn::<Brood, AtNest>,
+                    on_despawn::<Element, AtNest>,
+                    on_despawn::<Pheromone, AtNest>,
+                    on_despawn::<Airdrop, AtNest>,
+                    on_despawn_humidity,
+                    on_despawn_dig_order,
+                ),
+                // Added
+                (on_added_ant_emote),
+                // Removed
+                (
+                    on_removed_ant_emote,
+                    on_removed_ant_collapsed,
+                    on_removed_ant_asleep,
                 ),
                 // Misc
                 (
@@ -103,7 +171,15 @@ impl Plugin for NestRenderingPlugin {
                     // TODO: naming inconsistencies, but probably want to go more this direction rather than away.
                     ants_sleep_emote,
                     despawn_expired_emotes,
-                    update_sky_background,
+                    // Speech would flash by unreadably while fast-forwarding.
+                    ants_speak.run_if(in_state(StoryPlaybackState::Playing)),
+                    despawn_expired_speech_bubbles,
+                    // Rain falls every frame, rather than only when the weather changes, and freezes with the nest.
+                    update_raindrops.run_if(
+                        not(in_state(StoryPlaybackState::Paused))
+                            .and_then(is_zone_playing::<AtNest>),
+                    ),
+                    update_rain_audio,
                 ),
             )
                 .run_if(
@@ -123,8 +199,14 @@ impl Plugin for NestRenderingPlugin {
                 (
                     spawn_background,
                     rerender_ants,
+                    rerender_brood,
                     rerender_elements,
                     rerender_pheromones,
+                    rerender_humidity,
+                    rerender_dig_orders,
+                    rerender_airdrops::<AtNest>,
+                    spawn_decorations,
+                    spawn_rain_audio,
                     mark_nest_visible,
                 ),
             )
@@ -138,9 +220,16 @@ impl Plugin for NestRenderingPlugin {
                 despawn_view::<Background>,
                 despawn_view::<BackgroundTilemap>,
                 despawn_view_by_model::<Ant, AtNest>,
+                despawn_view_by_model::<Brood, AtNest>,
                 despawn_view_by_model::<Element, AtNest>,
                 despawn_view::<ElementTilemap>,
                 despawn_view_by_model::<Pheromone, AtNest>,
+                despawn_humidity,
+                despawn_dig_orders,
+                despawn_view_by_model::<Airdrop, AtNest>,
+                despawn_view::<Decoration>,
+                despawn_view::<Raindrop>,
+                despawn_view::<RainAudio>,
                 mark_nest_hidden,
             )
                 .run_if(in_state(AppState::TellStory)),
@@ -154,11 +243,20 @@ impl Plugin for NestRenderingPlugin {
                 cleanup_background,
                 despawn_view_by_model::<Ant, AtNest>,
                 cleanup_ants,
+                despawn_view_by_model::<Brood, AtNest>,
                 despawn_view_by_model::<Element, AtNest>,
                 despawn_view::<ElementTilemap>,
                 cleanup_elements,
                 despawn_view_by_model::<Pheromone, AtNest>,
                 cleanup_pheromones,
+                despawn_humidity,
+                cleanup_humidity,
+                despawn_dig_orders,
+                cleanup_dig_orders,
+                despawn_view_by_model::<Airdrop, AtNest>,
+                despawn_view::<Decoration>,
+                despawn_view::<Raindrop>,
+                despawn_view::<RainAudio>,
             )
                 .in_set(CleanupSet::BeforeSimulationCleanup),
         );
@@ -0,0 +1,175 @@
+use bevy::{
+    audio::{AudioSinkPlayback, Volume},
+    prelude::*,
+};
+
+use crate::common::visible_grid::VisibleGrid;
+
+use simulation::{
+    common::{
+        grid::{Grid, GridElements},
+        position::Position,
+    },
+    nest_simulation::{
+        element::Element,
+        nest::{AtNest, Nest},
+        weather::Weather,
+    },
+    story_time::{StoryPlaybackState, ZonePlayback},
+};
+
+// How many raindrops are falling at once at the height of a storm. Lighter rain is scaled down by its intensity.
+const MAX_RAINDROP_COUNT: usize = 200;
+// Raindrops are only drawn, not simulated, so they fall at a steady speed measured in tiles per real-world second.
+const RAINDROP_SPEED: f32 = 30.0;
+// A light wind slants the rain. Tiles drifted sideways per tile fallen.
+const RAINDROP_SLANT: f32 = 0.15;
+const RAINDROP_SIZE: Vec2 = Vec2::new(0.06, 0.6);
+const RAINDROP_COLOR: Color = Color::rgba(0.7, 0.8, 0.95, 0.5);
+// Rain falls in front of ants, but behind airdrops.
+const RAINDROP_Z_INDEX: f32 = 2.5;
+// Raindrops are scattered by stepping through fractions of these irrational numbers (the golden ratio, and the square
+// root of two) which never repeat. Stepping through x and y at different rates keeps drops from lining up.
+const SCATTER_STEP_X: f64 = 0.618_033_988_75;
+const SCATTER_STEP_Y: f64 = 0.414_213_562_37;
+// How loud rain sounds at the height of a storm, relative to the sound's own volume.
+const MAX_RAIN_VOLUME: f32 = 0.6;
+
+/// Rain particles are purely cosmetic. The water which rain actually leaves behind is simulated separately.
+#[derive(Component)]
+pub struct Raindrop;
+
+#[derive(Component)]
+pub struct RainAudio;
+
+/// Keep as many raindrops falling as the storm's intensity calls for. Drops which hit the ground, or leave the nest,
+/// start over at the top of the sky, or vanish if the rain is letting up.
+pub fn update_raindrops(
+    mut raindrop_query: Query<(Entity, &mut Transform), With<Raindrop>>,
+    nest_query: Query<(&Grid, &Nest)>,
+    grid_elements: GridElements<AtNest>,
+    weather: Res<Weather>,
+    visible_grid: Res<VisibleGrid>,
+    time: Res<Time>,
+    mut scatter_index: Local<usize>,
+    mut commands: Commands,
+) {
+    let visible_grid_entity = match visible_grid.0 {
+        Some(visible_grid_entity) => visible_grid_entity,
+        None => return,
+    };
+
+    let (grid, nest) = match nest_query.get(visible_grid_entity) {
+        Ok(nest_components) => nest_components,
+        Err(_) => return,
+    };
+
+    let target_count = (MAX_RAINDROP_COUNT as f32 * weather.intensity()).round() as usize;
+    let mut count = raindrop_query.iter().count();
+
+    let fall = RAINDROP_SPEED * time.delta_seconds();
+    let sky_top = grid.height() as f32 / 2.0;
+    let half_width = grid.width() as f32 / 2.0;
+
+    for (raindrop_entity, mut transform) in raindrop_query.iter_mut() {
+        transform.translation.y -= fall;
+        // Wrap around, rather than running out of rain, on the side the wind is blowing away from.
+        transform.translation.x = (transform.translation.x + fall * RAINDROP_SLANT + half_width)
+            .rem_euclid(half_width * 2.0)
+            - half_width;
+
+        let is_in_sky = transform.translation.y > -sky_top
+            && grid_elements.is(
+                grid.world_to_grid_position(transform.translation.truncate()),
+                Element::Air,
+            );
+
+        if is_in_sky {
+            continue;
+        }
+
+        if count > target_count {
+            commands.entity(raindrop_entity).despawn();
+            count -= 1;
+        } else {
+            *scatter_index += 1;
+            transform.translation.x = get_scattered_x(*scatter_index, grid);
+            transform.translation.y = sky_top;
+        }
+    }
+
+    // New drops are scattered across the sky, rather than all starting at the top, so rain doesn't arrive as a wave.
+    let surface_y = grid
+        .grid_to_world_position(Position::new(0, nest.surface_level()))
+        .y;
+
+    for _ in count..target_count {
+        *scatter_index += 1;
+        let x = get_scattered_x(*scatter_index, grid);
+        let y = surface_y + (sky_top - surface_y) * get_scatter(*scatter_index, SCATTER_STEP_Y);
+
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: RAINDROP_COLOR,
+                    custom_size: Some(RAINDROP_SIZE),
+                    ..default()
+                },
+                transform: Transform::from_xyz(x, y, RAINDROP_Z_INDEX),
+                ..default()
+            },
+            Raindrop,
+            AtNest,
+        ));
+    }
+}
+
+pub fn spawn_rain_audio(
+    weather: Res<Weather>,
+    asset_server: Res<AssetServer>,
+    mut commands: Commands,
+) {
+    commands.spawn((
+        AudioBundle {
+            source: asset_server.load("sounds/rain.wav"),
+            settings: PlaybackSettings::LOOP
+                .with_volume(Volume::new_relative(weather.intensity() * MAX_RAIN_VOLUME)),
+        },
+        RainAudio,
+    ));
+}
+
+/// Rain sounds as heavy as it looks, and falls silent while the nest isn't being simulated.
+pub fn update_rain_audio(
+    rain_audio_query: Query<&AudioSink, With<RainAudio>>,
+    weather: Res<Weather>,
+    story_playback_state: Res<State<StoryPlaybackState>>,
+    zone_playback: Res<ZonePlayback>,
+) {
+    let is_playing =
+        *story_playback_state != StoryPlaybackState::Paused && !zone_playback.is_paused::<AtNest>();
+
+    for audio_sink in rain_audio_query.iter() {
+        if is_playing && audio_sink.is_paused() {
+            audio_sink.play();
+        } else if !is_playing && !audio_sink.is_paused() {
+            audio_sink.pause();
+        }
+
+        let volume = weather.intensity() * MAX_RAIN_VOLUME;
+        if audio_sink.volume() != volume {
+            audio_sink.set_volume(volume);
+        }
+    }
+}
+
+/// Non-System Helper Functions:
+
+/// Spread raindrops evenly, but unpredictably, without drawing from the simulation's source of randomness.
+fn get_scatter(scatter_index: usize, step: f64) -> f32 {
+    (scatter_index as f64 * step).fract() as f32
+}
+
+fn get_scattered_x(scatter_index: usize, grid: &Grid) -> f32 {
+    (get_scatter(scatter_index, SCATTER_STEP_X) - 0.5) * grid.width() as f32
+}
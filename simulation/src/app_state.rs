@@ -1,7 +1,13 @@
 use bevy::prelude::*;
 
 use crate::{
-    nest_simulation::ant::{AntRole, Dead},
+    common::colony::ColonyId,
+    nest_simulation::{
+        ant::{brood::Brood, Ant, Dead},
+        colony_status::ColonyStatus,
+        nest::AtNest,
+    },
+    save::close_ruins,
     story_time::StoryPlaybackState,
 };
 
@@ -11,6 +17,12 @@ pub enum AppState {
     BeginSetup,
     TryLoadSave,
     SelectStoryMode,
+    // Load the archived, final state of a previous story so its ruins can be viewed. Only the view-only app does this.
+    LoadRuins,
+    // Wait for the host of a broadcast story to send the story so far, and then replay it, view-only, as it's told.
+    JoinBroadcast,
+    // Stream the grid chunks of a loaded save, or ruins, in across a few frames rather than all at once.
+    LoadGridChunks,
     CreateNewStory,
     FinishSetup,
     // Bevy does not currently support adding systems at runtime. So, systems
@@ -22,6 +34,56 @@ pub enum AppState {
     Cleanup,
 }
 
+/// Present in the view-only app which shows the ruins of a story which has ended, see `ViewOnlySimulationPlugin`.
+/// The world is rendered as it was when the story ended, but nothing is simulated and nothing is saved, so the ruins are
+/// read-only.
+#[derive(Resource, Default)]
+pub struct ViewingRuins {
+    /// See `StoryRecord::id`.
+    pub story_id: i64,
+}
+
+/// Whether the simulation should be ticked. Every tick system lives in `SimulationUpdate`, and that schedule is only run
+/// while this holds, so no tick system needs to check for `ViewingRuins` itself.
+///
+/// ```
+/// use bevy::prelude::*;
+/// use simulation::{
+///     app_state::{is_simulating, AppState, ViewingRuins},
+///     SimulationUpdate,
+/// };
+///
+/// #[derive(Resource, Default)]
+/// struct Ticks(usize);
+///
+/// let mut app = App::new();
+/// app.add_state::<AppState>();
+/// app.init_resource::<Ticks>();
+/// app.add_systems(SimulationUpdate, |mut ticks: ResMut<Ticks>| ticks.0 += 1);
+/// app.add_systems(
+///     Update,
+///     (|world: &mut World| world.run_schedule(SimulationUpdate)).run_if(is_simulating),
+/// );
+///
+/// app.insert_resource(NextState(Some(AppState::TellStory)));
+/// app.init_resource::<ViewingRuins>();
+/// app.update();
+///
+/// // Ruins are never simulated.
+/// assert_eq!(app.world.resource::<Ticks>().0, 0);
+///
+/// app.world.remove_resource::<ViewingRuins>();
+/// app.update();
+///
+/// assert_eq!(app.world.resource::<Ticks>().0, 1);
+/// ```
+pub fn is_simulating(
+    app_state: Res<State<AppState>>,
+    viewing_ruins: Option<Res<ViewingRuins>>,
+) -> bool {
+    *app_state.get() == AppState::TellStory && viewing_ruins.is_none()
+}
+
 pub fn restart(
     mut next_app_state: ResMut<NextState<AppState>>,
     mut next_story_playback_state: ResMut<NextState<StoryPlaybackState>>,
@@ -35,12 +97,49 @@ pub fn continue_startup(
     mut next_app_state: ResMut<NextState<AppState>>,
 ) {
     if is_loading_existing_story {
-        next_app_state.set(AppState::FinishSetup);
+        next_app_state.set(AppState::LoadGridChunks);
     } else {
         next_app_state.set(AppState::SelectStoryMode);
     }
 }
 
+/// The view-only app only ever shows ruins so it loads them in place of the active save.
+pub fn continue_to_ruins(mut next_app_state: ResMut<NextState<AppState>>) {
+    next_app_state.set(AppState::LoadRuins);
+}
+
+/// There are no stories to tell in the view-only app so, if the ruins can't be loaded, return to the full app.
+pub fn continue_viewing_ruins(
+    In(is_loading_ruins): In<bool>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    if is_loading_ruins {
+        next_app_state.set(AppState::LoadGridChunks);
+    } else {
+        warn!("Failed to load ruins.");
+        close_ruins();
+    }
+}
+
+/// Finish setup once every grid chunk has loaded. Stories whose grid can't be loaded are started afresh, like stories
+/// without a save, unless they're ruins, which are left for the full app.
+pub fn continue_loading_grid_chunks(
+    In(is_grid_loaded): In<Option<bool>>,
+    viewing_ruins: Option<Res<ViewingRuins>>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    match is_grid_loaded {
+        // Still loading.
+        None => {}
+        Some(true) => next_app_state.set(AppState::FinishSetup),
+        Some(false) if viewing_ruins.is_some() => {
+            warn!("Failed to load ruins.");
+            close_ruins();
+        }
+        Some(false) => next_app_state.set(AppState::SelectStoryMode),
+    }
+}
+
 pub fn finalize_startup(mut next_app_state: ResMut<NextState<AppState>>) {
     next_app_state.set(AppState::FinishSetup);
 }
@@ -53,13 +152,22 @@ pub fn begin_story(mut next_app_state: ResMut<NextState<AppState>>) {
     next_app_state.set(AppState::TellStory);
 }
 
+/// The story is over once a colony which has lost its queen, with no heir to replace her, has died out. Ants anywhere,
+/// nest or crater, keep the story going, but only if they belong to the player's colony because rival colonies forage
+/// the crater, too.
 pub fn check_story_over(
-    dead_ants_query: Query<&AntRole, With<Dead>>,
+    living_ants_query: Query<Option<&ColonyId>, (With<Ant>, Without<Dead>)>,
+    brood_query: Query<(), (With<Brood>, With<AtNest>)>,
+    colony_status: Res<ColonyStatus>,
     mut next_app_state: ResMut<NextState<AppState>>,
 ) {
-    if dead_ants_query
+    if !colony_status.is_declining() || !brood_query.is_empty() {
+        return;
+    }
+
+    if !living_ants_query
         .iter()
-        .any(|ant_role| *ant_role == AntRole::Queen)
+        .any(|colony_id| colony_id.map_or(true, ColonyId::is_player))
     {
         next_app_state.set(AppState::EndStory);
     }
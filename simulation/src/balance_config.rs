@@ -0,0 +1,100 @@
+use crate::{
+    nest_simulation::ant::AntRole,
+    ron_asset::{RonAsset, RonConfig},
+};
+use bevy::{prelude::*, reflect::TypePath};
+use serde::Deserialize;
+
+pub const BALANCE_CONFIG_PATH: &str = "config/default.balance.ron";
+
+/// Percentages of Hunger.max at which an ant's behavior changes.
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct HungerBalance {
+    pub peckish: f32,
+    pub hungry: f32,
+    pub starving: f32,
+}
+
+/// How much each role eats. Appetites scale how quickly hunger builds, relative to Settings.max_hunger_time, so an ant
+/// with twice the appetite needs twice the food.
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct AppetiteBalance {
+    pub worker: f32,
+    // Queens burn through food laying eggs.
+    pub queen: f32,
+    pub soldier: f32,
+    // Percentage of Digestion.max a nurse feeds a larva at a time.
+    pub larva_meal: f32,
+    // Larvae which go this long without being fed starve. Pupae don't eat at all.
+    pub larva_starvation_seconds: isize,
+}
+
+impl AppetiteBalance {
+    pub fn get(&self, role: AntRole) -> f32 {
+        match role {
+            AntRole::Worker => self.worker,
+            AntRole::Queen => self.queen,
+            AntRole::Soldier => self.soldier,
+        }
+    }
+}
+
+/// How many ants a colony can support, and how brood fare when too many are raised together.
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct PopulationBalance {
+    // Open tiles underground needed for each ant.
+    pub tiles_per_ant: f32,
+    // Pieces of food stored underground needed for each ant.
+    pub food_per_ant: f32,
+    // Colonies can always support this many ants, so a newly founded colony can grow before it has dug or stored much.
+    pub founding_capacity: usize,
+    // How far apart brood can lie and still share a nursery.
+    pub nursery_radius: isize,
+    // Larvae sharing a nursery with more brood than this go hungry twice as fast.
+    pub nursery_crowding: usize,
+}
+
+/// How quickly ants dig. Each element's hardness, in its definition, is how many turns of digging it takes to loosen.
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct DigBalance {
+    // How much of a turn's worth of digging gets done at dry dirt, which is packed hard. Damp dirt crumbles right away.
+    pub dry_dirt_rate: f32,
+    // Dirt wedged in against stone has been packed down hard. It takes this many times longer to dig out.
+    pub compacted_dirt_hardness_multiplier: f32,
+}
+
+/// Tuning values for the simulation which aren't exposed to the user and aren't persisted with a save.
+/// Unlike Settings, these are loaded from an asset file so they can be tweaked without recompiling.
+/// When built with `dev-hot-reload`, changes to the file are applied while the app is running.
+/// The asset file is also built in, and used as the default, until the asset finishes loading.
+#[derive(Resource, Asset, TypePath, Clone, Copy, Debug, Deserialize)]
+pub struct BalanceConfig {
+    pub hunger: HungerBalance,
+    pub appetite: AppetiteBalance,
+    // Percentage of Digestion.max consumed when eating a piece of food, before accounting for the food's nutrition.
+    pub food_digestion: f32,
+    // Maximum percentage of Digestion.max one ant can regurgitate to another at a time.
+    pub regurgitation_transfer: f32,
+    // Fraction of a nestmate's hunger an ant passes along to the ants it touches. Lower values keep food from being
+    // relayed as far through the nest.
+    pub solicitation_relay: f32,
+    // Chance that unsupported sand slides diagonally rather than staying put.
+    pub sand_slide_chance: f64,
+    pub population: PopulationBalance,
+    pub dig: DigBalance,
+}
+
+impl Default for BalanceConfig {
+    fn default() -> Self {
+        ron::de::from_str(include_str!("../../assets/config/default.balance.ron"))
+            .expect("Built-in balance config should be valid")
+    }
+}
+
+impl RonAsset for BalanceConfig {
+    const EXTENSIONS: &'static [&'static str] = &["balance.ron"];
+}
+
+impl RonConfig for BalanceConfig {
+    const PATH: &'static str = BALANCE_CONFIG_PATH;
+}
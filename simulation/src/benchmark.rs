@@ -0,0 +1,356 @@
+use bevy::{
+    prelude::*,
+    utils::{Duration, HashSet, Instant},
+};
+use bevy_turborand::GlobalRng;
+use std::fmt;
+
+use crate::{
+    balance_config::BalanceConfig,
+    common::position::Position,
+    crater_simulation::ant::{
+        wander::{get_wander_step, ExplorationStrategy, Wander},
+        CraterOrientation,
+    },
+    headless::{create_headless_app, start_headless_story},
+    nest_simulation::{
+        ant::{Ant, Dead},
+        gravity::{get_fall_position, get_repose_run},
+    },
+    settings::Settings,
+    SimulationUpdate,
+};
+
+pub const BENCHMARK_TICKS: usize = 10_000;
+// Colonies are generated from the same seed, with the same settings, so results are comparable across devices.
+const BENCHMARK_SEED: u64 = 1_337;
+const BENCHMARK_WORKER_COUNT: isize = 100;
+// About an hour of wandering for a single ant.
+pub const EXPLORATION_BENCHMARK_STEPS: usize = 20_000;
+// About as much sand as workers haul out of a young nest.
+pub const SAND_BENCHMARK_GRAINS: usize = 2_000;
+// Piles still moving after this long are reported as unsettled.
+const SAND_BENCHMARK_MAX_TICKS: usize = 50_000;
+// Sand only slides some of the time so a pile isn't settled until it's been still for a little while.
+const SAND_BENCHMARK_SETTLED_TICKS: usize = 20;
+
+/// Present in the headless apps used for benchmarking and soak testing. Their colonies are throwaway so nothing is saved.
+#[derive(Resource, Default)]
+pub struct Benchmarking;
+
+#[derive(Clone, Debug)]
+pub struct BenchmarkReport {
+    pub ticks: usize,
+    pub elapsed_seconds: f32,
+    pub alive_ant_count: usize,
+    pub device_info: String,
+}
+
+impl BenchmarkReport {
+    pub fn ticks_per_second(&self) -> f32 {
+        self.ticks as f32 / self.elapsed_seconds
+    }
+}
+
+impl fmt::Display for BenchmarkReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Symbiants Benchmark")?;
+        writeln!(f, "Ticks/sec: {:.0}", self.ticks_per_second())?;
+        writeln!(f, "Ticks: {} in {:.2}s", self.ticks, self.elapsed_seconds)?;
+        writeln!(f, "Alive ants: {}", self.alive_ant_count)?;
+        write!(f, "Device: {}", self.device_info)
+    }
+}
+
+/// Create a synthetic colony in a separate, headless app and time how long it takes to simulate a fixed number of ticks.
+/// The user's story is unaffected, but the UI is unresponsive while the benchmark runs. See `BenchmarkRun` to spread the
+/// benchmark across frames instead.
+pub fn run_benchmark(ticks: usize) -> Option<BenchmarkReport> {
+    BenchmarkRun::new(ticks)?.run_ticks(ticks)
+}
+
+/// A benchmark which is simulated a few ticks at a time, e.g. a few each frame so the UI stays responsive while it runs.
+/// Only time spent simulating is measured so it doesn't matter how long is spent between ticks.
+pub struct BenchmarkRun {
+    // Only the headless app's world is kept, because that's all running the simulation schedule needs, so the benchmark
+    // can be held onto between frames.
+    world: World,
+    ticks: usize,
+    completed_ticks: usize,
+    elapsed: Duration,
+}
+
+impl BenchmarkRun {
+    pub fn new(ticks: usize) -> Option<Self> {
+        let settings = Settings {
+            initial_ant_worker_count: BENCHMARK_WORKER_COUNT,
+            ..default()
+        };
+
+        let mut app = create_headless_app(settings, BENCHMARK_SEED);
+        app.init_resource::<Benchmarking>();
+
+        if !start_headless_story(&mut app) {
+            warn!("Benchmark story failed to start.");
+            return None;
+        }
+
+        Some(Self {
+            world: std::mem::take(&mut app.world),
+            ticks,
+            completed_ticks: 0,
+            elapsed: Duration::ZERO,
+        })
+    }
+
+    /// Simulate up to `max_ticks` more ticks. Returns the report once every tick has been simulated.
+    pub fn run_ticks(&mut self, max_ticks: usize) -> Option<BenchmarkReport> {
+        let ticks = max_ticks.min(self.ticks - self.completed_ticks);
+
+        // Run the simulation schedule directly, rather than updating the app, so only simulation work is measured.
+        let start = Instant::now();
+        for _ in 0..ticks {
+            self.world.run_schedule(SimulationUpdate);
+        }
+        self.elapsed += start.elapsed();
+        self.completed_ticks += ticks;
+
+        if self.completed_ticks < self.ticks {
+            return None;
+        }
+
+        let alive_ant_count = self
+            .world
+            .query_filtered::<(), (With<Ant>, Without<Dead>)>()
+            .iter(&self.world)
+            .count();
+
+        Some(BenchmarkReport {
+            ticks: self.ticks,
+            elapsed_seconds: self.elapsed.as_secs_f32(),
+            alive_ant_count,
+            device_info: get_device_info(),
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ExplorationBenchmarkReport {
+    pub steps: usize,
+    pub width: isize,
+    pub height: isize,
+    /// The share of the crater floor each strategy covered.
+    pub coverage: Vec<(ExplorationStrategy, f32)>,
+}
+
+impl fmt::Display for ExplorationBenchmarkReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Symbiants Exploration Benchmark")?;
+        write!(
+            f,
+            "Share of an empty {}x{} crater covered in {} steps:",
+            self.width, self.height, self.steps
+        )?;
+
+        for (exploration_strategy, coverage) in self.coverage.iter() {
+            write!(f, "\n{:?}: {:.1}%", exploration_strategy, coverage * 100.0)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Let a single ant wander an empty crater, once for each exploration strategy, and measure how much of the crater
+/// floor it covers. Every strategy wanders from the same spot, with the same seed, so results are comparable.
+/// This doesn't need a story so it's quick enough to run whenever a strategy is tweaked.
+pub fn run_exploration_benchmark(steps: usize) -> ExplorationBenchmarkReport {
+    let settings = Settings::default();
+    let (width, height) = (settings.crater_width, settings.crater_height);
+    let is_open = |position: Position| {
+        position.x >= 0 && position.x < width && position.y >= 0 && position.y < height
+    };
+
+    let coverage = ExplorationStrategy::ALL
+        .iter()
+        .map(|exploration_strategy| {
+            let policy = exploration_strategy.policy(&settings);
+            let mut rng = GlobalRng::with_seed(BENCHMARK_SEED);
+            let mut wander = Wander::default();
+            let mut position = Position::new(width / 2, height / 2);
+            let mut orientation = CraterOrientation::default();
+            let mut visited_positions = HashSet::default();
+            visited_positions.insert(position);
+
+            for _ in 0..steps {
+                (position, orientation) = get_wander_step(
+                    policy.as_ref(),
+                    position,
+                    orientation,
+                    &mut wander,
+                    is_open,
+                    &mut rng,
+                );
+
+                visited_positions.insert(position);
+            }
+
+            (
+                *exploration_strategy,
+                visited_positions.len() as f32 / (width * height) as f32,
+            )
+        })
+        .collect();
+
+    ExplorationBenchmarkReport {
+        steps,
+        width,
+        height,
+        coverage,
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SandBenchmarkReport {
+    pub grains: usize,
+    pub ticks: usize,
+    pub is_settled: bool,
+    pub elapsed_seconds: f32,
+    pub repose_angle: f32,
+    /// How steep the pile ended up, in degrees, measured from its peak to its edges.
+    pub slope_angle: f32,
+    pub device_info: String,
+}
+
+impl fmt::Display for SandBenchmarkReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Symbiants Sand Benchmark")?;
+
+        if self.is_settled {
+            writeln!(f, "{} grains settled in {} ticks", self.grains, self.ticks)?;
+        } else {
+            writeln!(
+                f,
+                "{} grains still moving after {} ticks",
+                self.grains, self.ticks
+            )?;
+        }
+
+        writeln!(f, "Elapsed: {:.2}s", self.elapsed_seconds)?;
+        writeln!(
+            f,
+            "Pile slope: {:.1} degrees for a repose angle of {:.1} degrees",
+            self.slope_angle, self.repose_angle
+        )?;
+        write!(f, "Device: {}", self.device_info)
+    }
+}
+
+/// Pour sand, a grain at a time, onto the middle of an empty nest and let it settle into a pile. Measures how long a
+/// large dump takes to cascade down and whether the pile comes to rest at the given angle of repose.
+/// Like the exploration benchmark, this doesn't need a story. Only the rules for falling are exercised.
+pub fn run_sand_benchmark(grains: usize, repose_angle: f32) -> SandBenchmarkReport {
+    let settings = Settings::default();
+    let (width, height) = (settings.nest_width, settings.nest_height);
+    let repose_run = get_repose_run(repose_angle);
+    let slide_chance = BalanceConfig::default().sand_slide_chance;
+    let pour_position = Position::new(width / 2, 0);
+
+    let mut rng = GlobalRng::with_seed(BENCHMARK_SEED);
+    let mut sand_positions = HashSet::default();
+    let mut poured_grains = 0;
+    let mut still_ticks = 0;
+    let mut ticks = 0;
+
+    let start = Instant::now();
+    while ticks < SAND_BENCHMARK_MAX_TICKS && still_ticks < SAND_BENCHMARK_SETTLED_TICKS {
+        ticks += 1;
+
+        if poured_grains < grains && sand_positions.insert(pour_position) {
+            poured_grains += 1;
+        }
+
+        // The lowest grains move first so there's room for the grains above to fall into.
+        let mut grain_positions = sand_positions.iter().copied().collect::<Vec<_>>();
+        grain_positions.sort_by_key(|position| -position.y);
+
+        let mut has_moved = false;
+        for position in grain_positions {
+            let is_air = |position: Position| {
+                position.x >= 0
+                    && position.x < width
+                    && position.y >= 0
+                    && position.y < height
+                    && !sand_positions.contains(&position)
+            };
+
+            let Some(fall_position) =
+                get_fall_position(position, is_air, repose_run, slide_chance, &mut rng)
+            else {
+                continue;
+            };
+
+            sand_positions.remove(&position);
+            sand_positions.insert(fall_position);
+            has_moved = true;
+        }
+
+        still_ticks = if has_moved || poured_grains < grains {
+            0
+        } else {
+            still_ticks + 1
+        };
+    }
+    let elapsed_seconds = start.elapsed().as_secs_f32();
+
+    let peak_y = sand_positions.iter().map(|position| position.y).min();
+    let left_x = sand_positions.iter().map(|position| position.x).min();
+    let right_x = sand_positions.iter().map(|position| position.x).max();
+
+    let slope_angle = match (peak_y, left_x, right_x) {
+        (Some(peak_y), Some(left_x), Some(right_x)) => {
+            let pile_height = (height - peak_y) as f32;
+            let pile_half_width = (right_x - left_x + 1) as f32 / 2.0;
+
+            pile_height.atan2(pile_half_width).to_degrees()
+        }
+        _ => 0.0,
+    };
+
+    SandBenchmarkReport {
+        grains: poured_grains,
+        ticks,
+        is_settled: still_ticks >= SAND_BENCHMARK_SETTLED_TICKS,
+        elapsed_seconds,
+        repose_angle,
+        slope_angle,
+        device_info: get_device_info(),
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn get_device_info() -> String {
+    let navigator = match web_sys::window() {
+        Some(window) => window.navigator(),
+        None => return String::from("Unknown"),
+    };
+
+    format!(
+        "{} ({} logical cores)",
+        navigator.user_agent().unwrap_or_default(),
+        navigator.hardware_concurrency()
+    )
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn get_device_info() -> String {
+    let logical_cores = std::thread::available_parallelism()
+        .map(|parallelism| parallelism.get().to_string())
+        .unwrap_or_else(|_| String::from("unknown"));
+
+    format!(
+        "{} {} ({} logical cores)",
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        logical_cores
+    )
+}
@@ -0,0 +1,39 @@
+//! Simulate a colony, unattended, for weeks of in-game time and report on how it fared.
+//! Usage: cargo run --release -p simulation --bin soak_test -- [days]
+
+use simulation::soak_test::{run_soak_test, DEFAULT_SOAK_TEST_DAYS};
+use std::{env, process};
+
+fn main() {
+    let days = env::args()
+        .nth(1)
+        .map(|arg| {
+            arg.parse::<usize>().unwrap_or_else(|_| {
+                eprintln!("Expected a number of days, got '{}'.", arg);
+                process::exit(1);
+            })
+        })
+        .unwrap_or(DEFAULT_SOAK_TEST_DAYS);
+
+    let report = run_soak_test(days, |sample| {
+        println!(
+            "Day {:.1}: {} ants, ~{:.1}MB, {:.0} ticks/sec, {} violations",
+            sample.elapsed_days(),
+            sample.alive_ant_count,
+            sample.estimated_megabytes,
+            sample.ticks_per_second,
+            sample.violation_count
+        );
+    });
+
+    let Some(report) = report else {
+        eprintln!("The soak test's story failed to start.");
+        process::exit(1);
+    };
+
+    println!("{}", report);
+
+    if report.violation_count > 0 {
+        process::exit(1);
+    }
+}
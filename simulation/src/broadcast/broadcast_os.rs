@@ -0,0 +1,16 @@
+// TODO: Support broadcasting on non-WASM targets.
+use super::BroadcastMessage;
+
+pub fn is_broadcast_supported() -> bool {
+    false
+}
+
+pub fn connect_broadcast() {}
+
+pub fn disconnect_broadcast() {}
+
+pub fn send_broadcast_message(_message: &BroadcastMessage) {}
+
+pub fn receive_broadcast_messages() -> Vec<BroadcastMessage> {
+    vec![]
+}
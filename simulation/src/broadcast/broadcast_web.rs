@@ -0,0 +1,117 @@
+use bevy::prelude::*;
+use std::cell::RefCell;
+use wasm_bindgen::{prelude::Closure, JsCast};
+use web_sys::{MessageEvent, WebSocket};
+
+use super::{BroadcastMessage, BROADCAST_RELAY_URL};
+
+/// The socket to the relay along with its callbacks, which must be kept alive for as long as the socket is open.
+struct BroadcastConnection {
+    socket: WebSocket,
+    _on_open: Closure<dyn FnMut()>,
+    _on_message: Closure<dyn FnMut(MessageEvent)>,
+}
+
+thread_local! {
+    static CONNECTION: RefCell<Option<BroadcastConnection>> = RefCell::new(None);
+    // Messages sent before the socket opens are held until it does.
+    static OUTGOING_MESSAGES: RefCell<Vec<String>> = RefCell::new(vec![]);
+    // Messages arrive between frames so they're held until the next frame reads them.
+    static INCOMING_MESSAGES: RefCell<Vec<String>> = RefCell::new(vec![]);
+}
+
+pub fn is_broadcast_supported() -> bool {
+    true
+}
+
+pub fn connect_broadcast() {
+    disconnect_broadcast();
+
+    let socket = match WebSocket::new(BROADCAST_RELAY_URL) {
+        Ok(socket) => socket,
+        Err(error) => {
+            error!("Failed to connect to broadcast relay: {:?}", error);
+            return;
+        }
+    };
+
+    let on_open = Closure::wrap(Box::new(move || {
+        CONNECTION.with(|connection| {
+            if let Some(connection) = connection.borrow().as_ref() {
+                for text in OUTGOING_MESSAGES.with(|messages| messages.take()) {
+                    send_text(&connection.socket, &text);
+                }
+            }
+        });
+    }) as Box<dyn FnMut()>);
+
+    let on_message = Closure::wrap(Box::new(move |event: MessageEvent| {
+        if let Some(text) = event.data().as_string() {
+            INCOMING_MESSAGES.with(|messages| messages.borrow_mut().push(text));
+        }
+    }) as Box<dyn FnMut(MessageEvent)>);
+
+    socket.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+    socket.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+
+    CONNECTION.with(|connection| {
+        *connection.borrow_mut() = Some(BroadcastConnection {
+            socket,
+            _on_open: on_open,
+            _on_message: on_message,
+        });
+    });
+}
+
+pub fn disconnect_broadcast() {
+    CONNECTION.with(|connection| {
+        if let Some(connection) = connection.borrow_mut().take() {
+            connection.socket.set_onopen(None);
+            connection.socket.set_onmessage(None);
+            let _ = connection.socket.close();
+        }
+    });
+
+    OUTGOING_MESSAGES.with(|messages| messages.borrow_mut().clear());
+    INCOMING_MESSAGES.with(|messages| messages.borrow_mut().clear());
+}
+
+pub fn send_broadcast_message(message: &BroadcastMessage) {
+    let text = match ron::to_string(message) {
+        Ok(text) => text,
+        Err(error) => {
+            error!("Failed to serialize broadcast message: {:?}", error);
+            return;
+        }
+    };
+
+    CONNECTION.with(|connection| {
+        if let Some(connection) = connection.borrow().as_ref() {
+            if connection.socket.ready_state() == WebSocket::OPEN {
+                send_text(&connection.socket, &text);
+            } else {
+                OUTGOING_MESSAGES.with(|messages| messages.borrow_mut().push(text));
+            }
+        }
+    });
+}
+
+pub fn receive_broadcast_messages() -> Vec<BroadcastMessage> {
+    INCOMING_MESSAGES
+        .with(|messages| messages.take())
+        .iter()
+        .filter_map(|text| match ron::from_str(text) {
+            Ok(message) => Some(message),
+            Err(error) => {
+                warn!("Ignoring malformed broadcast message: {:?}", error);
+                None
+            }
+        })
+        .collect()
+}
+
+fn send_text(socket: &WebSocket, text: &str) {
+    if let Err(error) = socket.send_with_str(text) {
+        warn!("Failed to send broadcast message: {:?}", error);
+    }
+}
@@ -0,0 +1,221 @@
+#[cfg(not(target_arch = "wasm32"))]
+mod broadcast_os;
+#[cfg(target_arch = "wasm32")]
+mod broadcast_web;
+
+// Re-export the platform-specific implementation
+#[cfg(target_arch = "wasm32")]
+pub use crate::broadcast::broadcast_web::*;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use crate::broadcast::broadcast_os::*;
+
+use bevy::{prelude::*, utils::Duration};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app_state::AppState,
+    crater_simulation::crater::AtCrater,
+    nest_simulation::nest::AtNest,
+    replay::{insert_replay_resources, ExternalEventLog, RecordedEvent, SimulationRecording},
+    simulation_timestep::SimulationTime,
+    story_time::{
+        FastForwardingStateInfo, StoryPlaybackState, StoryTime, DEFAULT_TICKS_PER_SECOND,
+        MAX_SYSTEM_TICKS_PER_SECOND,
+    },
+};
+
+// Broadcasting is experimental so it expects a relay, which passes each message on to every other client, to be
+// running locally.
+pub const BROADCAST_RELAY_URL: &str = "ws://localhost:8787";
+
+// Spectators which fall further behind than this, e.g. because the host sped up time, fast-forward to catch up.
+const MAX_SPECTATOR_LAG_TICKS: isize = DEFAULT_TICKS_PER_SECOND * 2;
+
+/// Messages are sent, as RON, to everyone else connected to the relay.
+#[derive(Serialize, Deserialize, Debug)]
+pub enum BroadcastMessage {
+    // Sent by spectators as they join. The host answers with the story so far.
+    Hello,
+    Recording {
+        recording: SimulationRecording,
+        elapsed_ticks: isize,
+    },
+    // Sent by the host, each frame, with the events recorded since its last message.
+    Events {
+        nest_events: Vec<RecordedEvent<AtNest>>,
+        crater_events: Vec<RecordedEvent<AtCrater>>,
+        elapsed_ticks: isize,
+    },
+}
+
+/// Present in an app which broadcasts its story, as it's told, so spectators can replay it live.
+/// Hosted stories are always recorded from their beginning, see `RecordingSimulation`.
+#[derive(Resource, Default)]
+pub struct HostingBroadcast {
+    sent_nest_events: usize,
+    sent_crater_events: usize,
+    sent_elapsed_ticks: isize,
+}
+
+/// Present in a view-only app replaying a broadcast story. Spectators can't simulate a tick until the host has sent
+/// the events which reached it, so the simulation waits whenever it catches up with the host.
+#[derive(Resource)]
+pub struct SpectatingBroadcast {
+    host_elapsed_ticks: isize,
+}
+
+/// Whether a spectator has simulated every tick the host has told it about.
+pub fn is_waiting_for_host(world: &World) -> bool {
+    world
+        .get_resource::<SpectatingBroadcast>()
+        .map_or(false, |spectating_broadcast| {
+            world.resource::<StoryTime>().elapsed_ticks() >= spectating_broadcast.host_elapsed_ticks
+        })
+}
+
+pub fn start_hosting_broadcast() {
+    connect_broadcast();
+}
+
+pub fn join_broadcast() {
+    connect_broadcast();
+    send_broadcast_message(&BroadcastMessage::Hello);
+}
+
+/// Spectators who give up before the host answers are disconnected. Those who joined stay connected to keep watching.
+pub fn leave_broadcast() {
+    disconnect_broadcast();
+}
+
+/// Send newly recorded events to those already watching and then answer anyone who has just joined.
+/// Events are sent first so those already watching don't miss any which are also part of the recording.
+pub fn broadcast_story(world: &mut World) {
+    let elapsed_ticks = world.resource::<StoryTime>().elapsed_ticks();
+    let nest_event_log = world.resource::<ExternalEventLog<AtNest>>();
+    let crater_event_log = world.resource::<ExternalEventLog<AtCrater>>();
+    let hosting_broadcast = world.resource::<HostingBroadcast>();
+
+    if elapsed_ticks != hosting_broadcast.sent_elapsed_ticks
+        || nest_event_log.len() != hosting_broadcast.sent_nest_events
+        || crater_event_log.len() != hosting_broadcast.sent_crater_events
+    {
+        send_broadcast_message(&BroadcastMessage::Events {
+            nest_events: nest_event_log
+                .events_since(hosting_broadcast.sent_nest_events)
+                .to_vec(),
+            crater_events: crater_event_log
+                .events_since(hosting_broadcast.sent_crater_events)
+                .to_vec(),
+            elapsed_ticks,
+        });
+    }
+
+    let sent_nest_events = nest_event_log.len();
+    let sent_crater_events = crater_event_log.len();
+    *world.resource_mut::<HostingBroadcast>() = HostingBroadcast {
+        sent_nest_events,
+        sent_crater_events,
+        sent_elapsed_ticks: elapsed_ticks,
+    };
+
+    let is_spectator_joining = receive_broadcast_messages()
+        .iter()
+        .any(|message| matches!(message, BroadcastMessage::Hello));
+
+    if is_spectator_joining {
+        if let Some(recording) = SimulationRecording::from_world(world) {
+            send_broadcast_message(&BroadcastMessage::Recording {
+                recording,
+                elapsed_ticks,
+            });
+        }
+    }
+}
+
+/// Wait for the host to send the story so far and then create it, as a replay, to watch.
+/// Recordings sent to other spectators are ignored once one has been received, but events sent afterward are kept
+/// because the story takes a few frames to create.
+pub fn await_broadcast_recording(world: &mut World) {
+    for message in receive_broadcast_messages() {
+        match message {
+            BroadcastMessage::Recording {
+                recording,
+                elapsed_ticks,
+            } if !world.contains_resource::<SpectatingBroadcast>() => {
+                insert_replay_resources(world, recording);
+                world.insert_resource(SpectatingBroadcast {
+                    host_elapsed_ticks: elapsed_ticks,
+                });
+                world
+                    .resource_mut::<NextState<AppState>>()
+                    .set(AppState::CreateNewStory);
+            }
+            BroadcastMessage::Events {
+                nest_events,
+                crater_events,
+                elapsed_ticks,
+            } if world.contains_resource::<SpectatingBroadcast>() => {
+                extend_replay(world, nest_events, crater_events, elapsed_ticks);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Extend the replay with whatever the host has sent, fast-forwarding if the spectator has fallen too far behind.
+pub fn receive_broadcast_story(world: &mut World) {
+    for message in receive_broadcast_messages() {
+        if let BroadcastMessage::Events {
+            nest_events,
+            crater_events,
+            elapsed_ticks,
+        } = message
+        {
+            extend_replay(world, nest_events, crater_events, elapsed_ticks);
+        }
+    }
+
+    let host_elapsed_ticks = world.resource::<SpectatingBroadcast>().host_elapsed_ticks;
+    let elapsed_ticks = world.resource::<StoryTime>().elapsed_ticks();
+    let pending_ticks = world.resource::<FastForwardingStateInfo>().pending_ticks;
+
+    let lag_ticks = host_elapsed_ticks - elapsed_ticks - pending_ticks;
+    if lag_ticks <= MAX_SPECTATOR_LAG_TICKS {
+        return;
+    }
+
+    let mut fast_forward_state_info = world.resource_mut::<FastForwardingStateInfo>();
+    fast_forward_state_info.pending_ticks += lag_ticks;
+    fast_forward_state_info.initial_pending_ticks += lag_ticks;
+
+    world.resource_mut::<SimulationTime>().period =
+        Duration::from_secs_f32(1.0 / (MAX_SYSTEM_TICKS_PER_SECOND as f32));
+    world
+        .resource_mut::<NextState<StoryPlaybackState>>()
+        .set(StoryPlaybackState::FastForwarding);
+}
+
+fn extend_replay(
+    world: &mut World,
+    nest_events: Vec<RecordedEvent<AtNest>>,
+    crater_events: Vec<RecordedEvent<AtCrater>>,
+    elapsed_ticks: isize,
+) {
+    world
+        .resource_mut::<ExternalEventLog<AtNest>>()
+        .extend(nest_events);
+    world
+        .resource_mut::<ExternalEventLog<AtCrater>>()
+        .extend(crater_events);
+    world
+        .resource_mut::<SpectatingBroadcast>()
+        .host_elapsed_ticks = elapsed_ticks;
+}
+
+pub fn remove_broadcast_resources(mut commands: Commands) {
+    disconnect_broadcast();
+
+    commands.remove_resource::<HostingBroadcast>();
+    commands.remove_resource::<SpectatingBroadcast>();
+}
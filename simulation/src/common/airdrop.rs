@@ -0,0 +1,122 @@
+use super::{grid::GridElements, position::Position, Zone};
+use crate::{
+    nest_simulation::element::{commands::ElementCommandsExt, Element, FoodKind},
+    story_time::DEFAULT_TICKS_PER_SECOND,
+};
+use bevy::prelude::*;
+use bevy_turborand::prelude::*;
+
+/// Food takes a second to fall, at normal speed, so there's time to see where it's going to land.
+pub const AIRDROP_FALL_TICKS: isize = DEFAULT_TICKS_PER_SECOND;
+// Limit how far a cluster spreads when there's little room around its center. Food which doesn't fit isn't dropped.
+const MAX_AIRDROP_RADIUS: isize = 10;
+
+/// Food falling toward its Position. It becomes Element::Food once it lands.
+/// Airdrops are short-lived so they aren't saved. Any still falling when the app closes are lost.
+#[derive(Component, Debug, PartialEq, Copy, Clone)]
+pub struct Airdrop {
+    remaining_ticks: isize,
+    food_kind: FoodKind,
+}
+
+impl Airdrop {
+    pub fn new(food_kind: FoodKind) -> Self {
+        Self {
+            remaining_ticks: AIRDROP_FALL_TICKS,
+            food_kind,
+        }
+    }
+
+    pub fn food_kind(&self) -> FoodKind {
+        self.food_kind
+    }
+
+    /// How far the food has fallen. 0.0 when dropped and 1.0 once landed.
+    pub fn progress(&self) -> f32 {
+        1.0 - (self.remaining_ticks as f32 / AIRDROP_FALL_TICKS as f32)
+    }
+
+    pub fn tick(&mut self) {
+        self.remaining_ticks = (self.remaining_ticks - 1).max(0);
+    }
+
+    pub fn has_landed(&self) -> bool {
+        self.remaining_ticks == 0
+    }
+}
+
+/// Scatter `amount` pieces of food, of random kinds, around `center`. Each piece falls for a moment before landing.
+pub fn spawn_airdrop<Z: Zone + Copy>(
+    center: Position,
+    amount: isize,
+    zone: Z,
+    grid_elements: &GridElements<Z>,
+    rng: &mut Mut<GlobalRng>,
+    commands: &mut Commands,
+) {
+    for position in get_airdrop_positions(center, amount, grid_elements, rng) {
+        let food_kind = *rng.sample(&FoodKind::ALL).unwrap();
+
+        commands.spawn((Airdrop::new(food_kind), position, zone));
+    }
+}
+
+/// Pick up to `amount` unique, empty positions clustered around `center`.
+/// The cluster grows outward until there's enough room for all the food, or it has spread too far to look like one drop.
+fn get_airdrop_positions<Z: Zone>(
+    center: Position,
+    amount: isize,
+    grid_elements: &GridElements<Z>,
+    rng: &mut Mut<GlobalRng>,
+) -> Vec<Position> {
+    if amount <= 0 {
+        return vec![];
+    }
+
+    // Start with a square just large enough to fit the food if every position were empty.
+    let mut radius = ((amount as f32).sqrt() / 2.0).ceil() as isize;
+
+    loop {
+        let mut air_positions = (-radius..=radius)
+            .flat_map(|y| (-radius..=radius).map(move |x| center + Position::new(x, y)))
+            .filter(|&position| grid_elements.is(position, Element::Air))
+            .collect::<Vec<_>>();
+
+        if air_positions.len() >= amount as usize || radius >= MAX_AIRDROP_RADIUS {
+            rng.shuffle(&mut air_positions);
+            air_positions.truncate(amount as usize);
+
+            return air_positions;
+        }
+
+        radius += 1;
+    }
+}
+
+/// Airdropped food lands after falling for a moment. It's lost if something else occupied its landing spot in the meantime.
+pub fn airdrops_fall<Z: Zone + Copy>(
+    mut airdrop_query: Query<(Entity, &mut Airdrop, &Position, &Z)>,
+    grid_elements: GridElements<Z>,
+    mut commands: Commands,
+) {
+    for (airdrop_entity, mut airdrop, position, zone) in airdrop_query.iter_mut() {
+        airdrop.tick();
+
+        if !airdrop.has_landed() {
+            continue;
+        }
+
+        if grid_elements.is(*position, Element::Air) {
+            let entity = grid_elements.entity(*position);
+            commands.replace_element_with(
+                *position,
+                Element::Food,
+                *entity,
+                airdrop.food_kind,
+                *zone,
+            );
+        }
+
+        commands.entity(airdrop_entity).despawn();
+    }
+}
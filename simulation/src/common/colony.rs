@@ -0,0 +1,34 @@
+use crate::{nest_simulation::ant::Ant, save::ReflectPersist};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Which colony an ant belongs to, or which colony has claimed an element as part of its territory.
+/// The player's colony is always `ColonyId::PLAYER`. Every other colony is a rival.
+#[derive(
+    Component, Debug, PartialEq, Eq, Hash, Copy, Clone, Serialize, Deserialize, Reflect, Default,
+)]
+#[reflect(Component, Persist)]
+pub struct ColonyId(pub u32);
+
+impl ColonyId {
+    pub const PLAYER: ColonyId = ColonyId(0);
+    pub const RIVAL: ColonyId = ColonyId(1);
+
+    pub fn is_player(&self) -> bool {
+        *self == ColonyId::PLAYER
+    }
+}
+
+pub fn register_colony(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<ColonyId>();
+}
+
+/// Ants which aren't spawned into a particular colony, and ants from saves which predate `ColonyId`, belong to the player.
+pub fn ants_add_colony_id(
+    ants_query: Query<Entity, (With<Ant>, Without<ColonyId>)>,
+    mut commands: Commands,
+) {
+    for ant_entity in ants_query.iter() {
+        commands.entity(ant_entity).insert(ColonyId::PLAYER);
+    }
+}
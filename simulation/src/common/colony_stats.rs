@@ -0,0 +1,144 @@
+use super::{colony::ColonyId, position::Position};
+use crate::{
+    crater_simulation::crater::AtCrater,
+    nest_simulation::{
+        ant::{Ant, AntInventory, Dead},
+        element::Element,
+        nest::Nest,
+    },
+    save::{write_story_record, ReflectPersist},
+    story_time::{StoryRealWorldTime, StoryTime, DEFAULT_TICKS_PER_SECOND, SECONDS_PER_DAY},
+};
+use bevy::prelude::*;
+use chrono::{DateTime, LocalResult, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+// Records are tiny, but there's no reason to keep every story a long-time player has ever told.
+pub const MAX_STORY_RECORDS: usize = 100;
+
+/// Running totals for the player's colony over the course of a story. They're saved with the story and, once the
+/// story is over, summarized into a `StoryRecord`.
+#[derive(Resource, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Resource, Persist)]
+pub struct ColonyStats {
+    peak_population: usize,
+    food_gathered: usize,
+}
+
+impl ColonyStats {
+    pub fn peak_population(&self) -> usize {
+        self.peak_population
+    }
+
+    pub fn food_gathered(&self) -> usize {
+        self.food_gathered
+    }
+}
+
+/// A summary of a completed story. Records are kept apart from world saves, so they outlive the stories they describe,
+/// and let players compare their colonies over time.
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
+pub struct StoryRecord {
+    // When the story ended, as a UTC timestamp in milliseconds.
+    ended_at: i64,
+    elapsed_ticks: isize,
+    peak_population: usize,
+    food_gathered: usize,
+}
+
+impl StoryRecord {
+    /// Identifies the story, e.g. to find its ruins. Stories end one at a time so no two share the instant they ended.
+    pub fn id(&self) -> i64 {
+        self.ended_at
+    }
+
+    pub fn ended_at(&self) -> Option<DateTime<Utc>> {
+        match Utc.timestamp_millis_opt(self.ended_at) {
+            LocalResult::Single(datetime) => Some(datetime),
+            _ => None,
+        }
+    }
+
+    pub fn days_survived(&self) -> isize {
+        self.elapsed_ticks / (SECONDS_PER_DAY * DEFAULT_TICKS_PER_SECOND)
+    }
+
+    pub fn peak_population(&self) -> usize {
+        self.peak_population
+    }
+
+    pub fn food_gathered(&self) -> usize {
+        self.food_gathered
+    }
+}
+
+pub fn register_colony_stats(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<ColonyStats>();
+}
+
+pub fn initialize_colony_stats_resources(mut commands: Commands) {
+    commands.init_resource::<ColonyStats>();
+}
+
+pub fn remove_colony_stats_resources(mut commands: Commands) {
+    commands.remove_resource::<ColonyStats>();
+}
+
+pub fn update_colony_peak_population(
+    ants_query: Query<&ColonyId, (With<Ant>, Without<Dead>)>,
+    mut colony_stats: ResMut<ColonyStats>,
+) {
+    let population = ants_query
+        .iter()
+        .filter(|colony_id| colony_id.is_player())
+        .count();
+
+    if population > colony_stats.peak_population {
+        colony_stats.peak_population = population;
+    }
+}
+
+/// Food counts as gathered when the player's ants pick it up out in the world, i.e. in the crater or above the nest.
+/// Food moved around within the nest was already gathered.
+/// NOTE: Inventories are seen as newly added when a story is loaded so they're skipped to avoid counting food twice.
+/// Only newly picked up items count. Ants carrying two morsels still hold one after dropping the other.
+pub fn update_colony_food_gathered(
+    ants_query: Query<
+        (Ref<AntInventory>, &Position, &ColonyId, Option<&AtCrater>),
+        (With<Ant>, Without<Dead>),
+    >,
+    elements_query: Query<Ref<Element>>,
+    nest_query: Query<&Nest>,
+    mut colony_stats: ResMut<ColonyStats>,
+) {
+    let nest = nest_query.single();
+
+    for (inventory, position, colony_id, at_crater) in ants_query.iter() {
+        if !inventory.is_changed() || inventory.is_added() || !colony_id.is_player() {
+            continue;
+        }
+
+        let is_picked_up_food = inventory
+            .last()
+            .and_then(|element_entity| elements_query.get(element_entity).ok())
+            .map_or(false, |element| element.is_added() && element.is_food());
+
+        if is_picked_up_food && (at_crater.is_some() || nest.is_aboveground(position)) {
+            colony_stats.food_gathered += 1;
+        }
+    }
+}
+
+/// Summarize the story, as it ends, so it can be compared against other stories from the main menu.
+pub fn record_story(
+    story_time: Res<StoryTime>,
+    story_real_world_time: Res<StoryRealWorldTime>,
+    colony_stats: Res<ColonyStats>,
+) {
+    write_story_record(StoryRecord {
+        ended_at: story_real_world_time.0,
+        elapsed_ticks: story_time.elapsed_ticks(),
+        peak_population: colony_stats.peak_population,
+        food_gathered: colony_stats.food_gathered,
+    });
+}
@@ -0,0 +1,121 @@
+use bevy::{ecs::entity::Entities, prelude::*};
+
+use crate::{
+    nest_simulation::{ant::Ant, element::Element, pheromone::Pheromone},
+    settings::Settings,
+};
+
+// Rough, per-entity memory costs (in bytes) including component storage and bookkeeping.
+// These aren't precise. They only need to be close enough to notice when a colony is growing out of control.
+const ELEMENT_BYTES: usize = 128;
+const ANT_BYTES: usize = 512;
+const PHEROMONE_BYTES: usize = 128;
+// Everything else is, overwhelmingly, a view (sprites, tiles, labels, emotes) which carries render data.
+const OTHER_BYTES: usize = 1_024;
+
+const BYTES_PER_MEGABYTE: usize = 1_024 * 1_024;
+
+// Once exceeded, the budget isn't considered recovered until usage falls comfortably below it.
+// This prevents growth from toggling on and off every frame while hovering around the limit.
+const RECOVERY_RATIO: f32 = 0.9;
+
+/// Tracks how many entities exist, and roughly how much memory they consume, so that expensive growth
+/// (new ants, emotes) can be throttled before the app runs out of memory. Mobile WASM sessions have small memory limits
+/// and, when they're exceeded, the tab dies without any explanation to the user.
+#[derive(Resource, Default, Debug)]
+pub struct EntityBudget {
+    element_count: usize,
+    ant_count: usize,
+    pheromone_count: usize,
+    other_count: usize,
+    is_exceeded: bool,
+}
+
+impl EntityBudget {
+    pub fn element_count(&self) -> usize {
+        self.element_count
+    }
+
+    pub fn ant_count(&self) -> usize {
+        self.ant_count
+    }
+
+    pub fn pheromone_count(&self) -> usize {
+        self.pheromone_count
+    }
+
+    pub fn other_count(&self) -> usize {
+        self.other_count
+    }
+
+    pub fn entity_count(&self) -> usize {
+        self.element_count + self.ant_count + self.pheromone_count + self.other_count
+    }
+
+    pub fn estimated_bytes(&self) -> usize {
+        self.element_count * ELEMENT_BYTES
+            + self.ant_count * ANT_BYTES
+            + self.pheromone_count * PHEROMONE_BYTES
+            + self.other_count * OTHER_BYTES
+    }
+
+    pub fn estimated_megabytes(&self) -> f32 {
+        self.estimated_bytes() as f32 / BYTES_PER_MEGABYTE as f32
+    }
+
+    pub fn is_exceeded(&self) -> bool {
+        self.is_exceeded
+    }
+}
+
+pub fn initialize_entity_budget_resources(mut commands: Commands) {
+    commands.init_resource::<EntityBudget>();
+}
+
+pub fn remove_entity_budget_resources(mut commands: Commands) {
+    commands.remove_resource::<EntityBudget>();
+}
+
+pub fn update_entity_budget(
+    mut entity_budget: ResMut<EntityBudget>,
+    entities: &Entities,
+    element_query: Query<(), With<Element>>,
+    ant_query: Query<(), With<Ant>>,
+    pheromone_query: Query<(), With<Pheromone>>,
+    settings: Res<Settings>,
+) {
+    let element_count = element_query.iter().count();
+    let ant_count = ant_query.iter().count();
+    let pheromone_count = pheromone_query.iter().count();
+    let other_count =
+        (entities.len() as usize).saturating_sub(element_count + ant_count + pheromone_count);
+
+    let mut new_entity_budget = EntityBudget {
+        element_count,
+        ant_count,
+        pheromone_count,
+        other_count,
+        is_exceeded: entity_budget.is_exceeded,
+    };
+
+    let budget_megabytes = settings.memory_budget_megabytes as f32;
+    let estimated_megabytes = new_entity_budget.estimated_megabytes();
+
+    if !new_entity_budget.is_exceeded && estimated_megabytes > budget_megabytes {
+        warn!(
+            "Memory budget exceeded (~{:.0}MB of {:.0}MB). Throttling colony growth and effects.",
+            estimated_megabytes, budget_megabytes
+        );
+        new_entity_budget.is_exceeded = true;
+    } else if new_entity_budget.is_exceeded
+        && estimated_megabytes < budget_megabytes * RECOVERY_RATIO
+    {
+        info!(
+            "Memory usage recovered (~{:.0}MB of {:.0}MB). Resuming colony growth and effects.",
+            estimated_megabytes, budget_megabytes
+        );
+        new_entity_budget.is_exceeded = false;
+    }
+
+    *entity_budget = new_entity_budget;
+}
@@ -0,0 +1,116 @@
+use super::{colony::ColonyId, grid::Grid, position::Position, Zone};
+use crate::{
+    nest_simulation::ant::{Ant, Dead},
+    save::ReflectPersist,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Ants notice what's around them, not just what's underfoot, so paths are revealed a little wider than an ant.
+const EXPLORATION_SIGHT_RADIUS: isize = 2;
+
+/// Which tiles of a zone the player's ants have seen. Lives on the zone's grid entity.
+/// Stored as a bitmask, one bit per tile, because it covers the whole zone and is persisted.
+#[derive(Component, Debug, PartialEq, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Component, Persist)]
+pub struct Exploration {
+    width: isize,
+    height: isize,
+    bits: Vec<u64>,
+}
+
+impl Exploration {
+    pub fn new(width: isize, height: isize) -> Self {
+        let tile_count = (width * height).max(0) as usize;
+
+        Self {
+            width,
+            height,
+            bits: vec![0; (tile_count + 63) / 64],
+        }
+    }
+
+    fn get_index(&self, position: Position) -> Option<usize> {
+        let is_within_bounds = position.x >= 0
+            && position.x < self.width
+            && position.y >= 0
+            && position.y < self.height;
+
+        is_within_bounds.then_some((position.y * self.width + position.x) as usize)
+    }
+
+    pub fn is_explored(&self, position: Position) -> bool {
+        self.get_index(position).map_or(false, |index| {
+            self.bits[index / 64] & (1 << (index % 64)) != 0
+        })
+    }
+
+    /// Mark a position as explored. Returns true if it hadn't been explored before.
+    pub fn explore(&mut self, position: Position) -> bool {
+        let Some(index) = self.get_index(position) else {
+            return false;
+        };
+
+        let mask = 1 << (index % 64);
+        let is_unexplored = self.bits[index / 64] & mask == 0;
+        self.bits[index / 64] |= mask;
+
+        is_unexplored
+    }
+
+    pub fn explored_count(&self) -> usize {
+        self.bits
+            .iter()
+            .map(|bits| bits.count_ones() as usize)
+            .sum()
+    }
+}
+
+pub fn register_exploration(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<Exploration>();
+    app_type_registry.write().register::<Vec<u64>>();
+}
+
+/// New stories, and stories from saves which predate exploration, start out entirely unexplored.
+pub fn insert_exploration<Z: Zone>(
+    grid_query: Query<(Entity, &Grid), (With<Z>, Without<Exploration>)>,
+    mut commands: Commands,
+) {
+    for (grid_entity, grid) in grid_query.iter() {
+        commands
+            .entity(grid_entity)
+            .insert(Exploration::new(grid.width(), grid.height()));
+    }
+}
+
+/// The player's ants reveal the zone as they wander it. Rival ants don't share what they've seen.
+pub fn ants_explore<Z: Zone>(
+    ants_query: Query<(&Position, &ColonyId), (With<Ant>, With<Z>, Without<Dead>)>,
+    mut exploration_query: Query<&mut Exploration, With<Z>>,
+) {
+    let Ok(mut exploration) = exploration_query.get_single_mut() else {
+        return;
+    };
+
+    let mut is_changed = false;
+
+    for (position, _) in ants_query
+        .iter()
+        .filter(|(_, colony_id)| colony_id.is_player())
+    {
+        for y in -EXPLORATION_SIGHT_RADIUS..=EXPLORATION_SIGHT_RADIUS {
+            for x in -EXPLORATION_SIGHT_RADIUS..=EXPLORATION_SIGHT_RADIUS {
+                let seen_position = *position + Position::new(x, y);
+
+                // Avoid flagging a change every tick. Views redraw the whole zone whenever exploration changes.
+                if exploration.bypass_change_detection().explore(seen_position) {
+                    is_changed = true;
+                }
+            }
+        }
+    }
+
+    if is_changed {
+        exploration.set_changed();
+    }
+}
@@ -0,0 +1,71 @@
+use crate::{nest_simulation::ant::AntRole, save::ReflectPersist};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+const ROLES: [AntRole; 3] = [AntRole::Worker, AntRole::Queen, AntRole::Soldier];
+
+/// Who has fed whom, by role, over the course of the story. Shows how food flows socially through the colony, from
+/// the foragers who find it to the nestmates who never leave the nest.
+#[derive(Resource, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Resource, Persist)]
+pub struct FoodSharingNetwork {
+    // Indexed by the role of the ant giving food and then by the role of the ant receiving it.
+    shares: [[usize; 3]; 3],
+    // Shares received by ants which weren't hungry themselves, but were passing food along to nestmates who were.
+    relays: usize,
+}
+
+impl FoodSharingNetwork {
+    pub fn record_share(&mut self, giver_role: AntRole, receiver_role: AntRole, is_relay: bool) {
+        self.shares[get_role_index(giver_role)][get_role_index(receiver_role)] += 1;
+
+        if is_relay {
+            self.relays += 1;
+        }
+    }
+
+    /// Every pairing of roles which has shared food, along with how many times food was shared between them.
+    pub fn shares(&self) -> impl Iterator<Item = (AntRole, AntRole, usize)> + '_ {
+        ROLES
+            .iter()
+            .flat_map(|giver_role| {
+                ROLES
+                    .iter()
+                    .map(move |receiver_role| (*giver_role, *receiver_role))
+            })
+            .map(|(giver_role, receiver_role)| {
+                (
+                    giver_role,
+                    receiver_role,
+                    self.shares[get_role_index(giver_role)][get_role_index(receiver_role)],
+                )
+            })
+            .filter(|(_, _, count)| *count > 0)
+    }
+
+    pub fn relays(&self) -> usize {
+        self.relays
+    }
+}
+
+fn get_role_index(role: AntRole) -> usize {
+    match role {
+        AntRole::Worker => 0,
+        AntRole::Queen => 1,
+        AntRole::Soldier => 2,
+    }
+}
+
+pub fn register_food_sharing(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<FoodSharingNetwork>();
+    app_type_registry.write().register::<[usize; 3]>();
+    app_type_registry.write().register::<[[usize; 3]; 3]>();
+}
+
+pub fn initialize_food_sharing_resources(mut commands: Commands) {
+    commands.init_resource::<FoodSharingNetwork>();
+}
+
+pub fn remove_food_sharing_resources(mut commands: Commands) {
+    commands.remove_resource::<FoodSharingNetwork>();
+}
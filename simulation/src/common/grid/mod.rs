@@ -1,5 +1,13 @@
+pub mod pheromone_layer;
+
 use super::Zone;
-use crate::{common::position::Position, nest_simulation::element::Element};
+use crate::{
+    common::position::Position,
+    nest_simulation::element::{
+        definition::{ElementDefinition, ElementDefinitions},
+        Element,
+    },
+};
 use bevy::{ecs::system::SystemParam, prelude::*};
 
 /// Note the intentional omission of reflection/serialization.
@@ -71,6 +79,7 @@ impl Grid {
 pub struct GridElements<'w, 's, Z: Zone> {
     grid: Query<'w, 's, &'static Grid, With<Z>>,
     elements: Query<'w, 's, &'static Element, With<Z>>,
+    element_definitions: Res<'w, ElementDefinitions>,
 }
 
 // TODO: The interface here is a little unclear - sometimes querying by Entity other times by Position
@@ -104,6 +113,14 @@ impl<'w, 's, Z: Zone> GridElements<'w, 's, Z> {
         }
     }
 
+    pub fn definitions(&self) -> &ElementDefinitions {
+        &self.element_definitions
+    }
+
+    pub fn definition(&self, element: Element) -> &ElementDefinition {
+        self.element_definitions.get(element)
+    }
+
     pub fn is(&self, position: Position, element: Element) -> bool {
         self.get_entity(position).map_or(false, |&element_entity| {
             self.get_element(element_entity)
@@ -111,6 +128,14 @@ impl<'w, 's, Z: Zone> GridElements<'w, 's, Z> {
         })
     }
 
+    // True for food of any kind.
+    pub fn is_food(&self, position: Position) -> bool {
+        self.get_entity(position).map_or(false, |&element_entity| {
+            self.get_element(element_entity)
+                .map_or(false, |element| element.is_food())
+        })
+    }
+
     // Returns true if every element in `positions` matches the provided Element type.
     // NOTE: This returns true if given 0 positions.
     pub fn is_all(&self, positions: &[Position], element: Element) -> bool {
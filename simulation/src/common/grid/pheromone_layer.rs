@@ -0,0 +1,99 @@
+use crate::common::position::Position;
+
+// Strengths weaker than this are indistinguishable from nothing, so clear them rather than decaying them forever.
+const MIN_PHEROMONE_STRENGTH: f32 = 0.001;
+
+/// A dense layer of pheromone strengths, from 0.0 to 1.0, with one strength per grid position.
+/// Unlike tunnel and chamber pheromones, which are an entity per position, this spreads into neighboring
+/// positions every tick. Doing that with entities would mean constantly spawning and despawning them.
+#[derive(Debug, Clone)]
+pub struct PheromoneLayer {
+    width: isize,
+    height: isize,
+    strengths: Vec<f32>,
+    // Reused each tick to avoid allocating while diffusing.
+    scratch: Vec<f32>,
+    is_empty: bool,
+}
+
+impl PheromoneLayer {
+    pub fn new(width: isize, height: isize) -> Self {
+        let length = (width * height) as usize;
+
+        Self {
+            width,
+            height,
+            strengths: vec![0.0; length],
+            scratch: vec![0.0; length],
+            is_empty: true,
+        }
+    }
+
+    fn get_index(&self, position: Position) -> Option<usize> {
+        if position.x < 0 || position.x >= self.width || position.y < 0 || position.y >= self.height
+        {
+            return None;
+        }
+
+        Some((position.y * self.width + position.x) as usize)
+    }
+
+    /// Positions outside of the grid have no pheromone.
+    pub fn strength(&self, position: Position) -> f32 {
+        self.get_index(position)
+            .map_or(0.0, |index| self.strengths[index])
+    }
+
+    pub fn deposit(&mut self, position: Position, amount: f32) {
+        if let Some(index) = self.get_index(position) {
+            self.strengths[index] = (self.strengths[index] + amount).min(1.0);
+            self.is_empty = false;
+        }
+    }
+
+    /// Each position keeps `1 - diffusion_rate` of its strength and receives an even share of its neighbors' spread.
+    /// Afterward, everything weakens by `decay_rate`.
+    pub fn tick(&mut self, decay_rate: f32, diffusion_rate: f32) {
+        if self.is_empty {
+            return;
+        }
+
+        let mut is_empty = true;
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let position = Position::new(x, y);
+                let index = (y * self.width + x) as usize;
+
+                let neighbors = [
+                    Position::new(x - 1, y),
+                    Position::new(x + 1, y),
+                    Position::new(x, y - 1),
+                    Position::new(x, y + 1),
+                ];
+
+                let neighbor_spread = neighbors
+                    .iter()
+                    .map(|&neighbor| self.strength(neighbor))
+                    .sum::<f32>()
+                    * diffusion_rate
+                    / neighbors.len() as f32;
+
+                let mut strength = (self.strength(position) * (1.0 - diffusion_rate)
+                    + neighbor_spread)
+                    * (1.0 - decay_rate);
+
+                if strength < MIN_PHEROMONE_STRENGTH {
+                    strength = 0.0;
+                } else {
+                    is_empty = false;
+                }
+
+                self.scratch[index] = strength;
+            }
+        }
+
+        std::mem::swap(&mut self.strengths, &mut self.scratch);
+        self.is_empty = is_empty;
+    }
+}
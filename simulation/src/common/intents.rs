@@ -0,0 +1,139 @@
+use crate::{common::position::Position, story_time::StoryTime};
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+// Enough to see what an ant has been thinking over the last little while without the log scrolling forever.
+pub const MAX_INTENTS_LOG_ENTRIES: usize = 30;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct IntentsLogEntry {
+    tick: isize,
+    description: String,
+}
+
+impl IntentsLogEntry {
+    pub fn tick(&self) -> isize {
+        self.tick
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+}
+
+/// A rolling log of the decisions a single ant has made, and why, to answer "why is my ant doing that?"
+/// Only the ant being watched from the inspector has its decisions described. Nothing is logged while no ant is
+/// being watched so the log costs nothing when it isn't in use.
+/// NOTE: The log isn't persisted. It's a debugging aid rather than part of the story.
+///
+/// ```
+/// use bevy::prelude::*;
+/// use simulation::common::intents::IntentsLog;
+///
+/// let mut intents_log = IntentsLog::default();
+/// let ant_entity = Entity::from_raw(1);
+///
+/// // Nothing is logged until the ant is watched.
+/// intents_log.record(ant_entity, || "hungry: seeking food".to_string());
+/// assert!(intents_log.entries().is_empty());
+///
+/// intents_log.watch(Some(ant_entity));
+/// intents_log.record(ant_entity, || "hungry: seeking food".to_string());
+/// intents_log.record(ant_entity, || "hungry: seeking food".to_string());
+/// intents_log.record(Entity::from_raw(2), || "hungry: seeking food".to_string());
+///
+/// // Decisions repeated tick after tick are only logged once.
+/// assert_eq!(intents_log.entries().len(), 1);
+/// ```
+#[derive(Resource, Debug, Default)]
+pub struct IntentsLog {
+    subject: Option<Entity>,
+    tick: isize,
+    entries: VecDeque<IntentsLogEntry>,
+}
+
+impl IntentsLog {
+    pub fn subject(&self) -> Option<Entity> {
+        self.subject
+    }
+
+    /// Start watching another ant, or stop watching altogether. The log starts over whenever the subject changes.
+    pub fn watch(&mut self, subject: Option<Entity>) {
+        if self.subject != subject {
+            self.subject = subject;
+            self.entries.clear();
+        }
+    }
+
+    pub fn is_watching(&self, entity: Entity) -> bool {
+        self.subject == Some(entity)
+    }
+
+    pub fn entries(&self) -> &VecDeque<IntentsLogEntry> {
+        &self.entries
+    }
+
+    /// Describing a decision takes work so it's only done when the deciding ant is being watched.
+    /// Ants reconsider most decisions every tick so a decision is only logged when it differs from the last one.
+    pub fn record(&mut self, entity: Entity, describe: impl FnOnce() -> String) {
+        if !self.is_watching(entity) {
+            return;
+        }
+
+        let description = describe();
+        if self
+            .entries
+            .back()
+            .map_or(false, |entry| entry.description == description)
+        {
+            return;
+        }
+
+        if self.entries.len() >= MAX_INTENTS_LOG_ENTRIES {
+            self.entries.pop_front();
+        }
+
+        self.entries.push_back(IntentsLogEntry {
+            tick: self.tick,
+            description,
+        });
+    }
+}
+
+/// Which way `to` lies from `from`, in words, for describing which way an ant meant to go.
+///
+/// ```
+/// use simulation::common::{intents::get_direction_name, position::Position};
+///
+/// assert_eq!(get_direction_name(Position::new(1, 1), Position::new(1, 0)), "north");
+/// assert_eq!(get_direction_name(Position::new(1, 1), Position::new(2, 2)), "southeast");
+/// ```
+pub fn get_direction_name(from: Position, to: Position) -> &'static str {
+    // NOTE: Grid positions increase downward so north is negative y.
+    match ((to.x - from.x).signum(), (to.y - from.y).signum()) {
+        (0, -1) => "north",
+        (1, -1) => "northeast",
+        (1, 0) => "east",
+        (1, 1) => "southeast",
+        (0, 1) => "south",
+        (-1, 1) => "southwest",
+        (-1, 0) => "west",
+        (-1, -1) => "northwest",
+        _ => "nowhere",
+    }
+}
+
+pub fn initialize_intents_resources(mut commands: Commands) {
+    commands.init_resource::<IntentsLog>();
+}
+
+pub fn remove_intents_resources(mut commands: Commands) {
+    commands.remove_resource::<IntentsLog>();
+}
+
+/// Entries are stamped with the tick they were decided on.
+pub fn update_intents_log_tick(mut intents_log: ResMut<IntentsLog>, story_time: Res<StoryTime>) {
+    if intents_log.subject.is_some() {
+        intents_log.tick = story_time.elapsed_ticks();
+    }
+}
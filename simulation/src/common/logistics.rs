@@ -0,0 +1,205 @@
+use super::{colony::ColonyId, position::Position};
+use crate::{
+    crater_simulation::crater::AtCrater,
+    nest_simulation::{
+        ant::{Ant, AntInventory, Dead},
+        element::Element,
+        nest::Nest,
+    },
+    save::ReflectPersist,
+    story_time::{StoryTime, DEFAULT_TICKS_PER_SECOND, SECONDS_PER_HOUR},
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Flows are summarized over the last day, an hour at a time, so the report shifts gradually rather than emptying at midnight.
+const HOURS_PER_DAY: isize = 24;
+const TICKS_PER_HOUR: isize = SECONDS_PER_HOUR * DEFAULT_TICKS_PER_SECOND;
+
+/// Where foraged food was found. Food from the crater still has to be carried home while food found above the nest
+/// is already there.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum ForagingRoute {
+    Crater,
+    Surface,
+}
+
+/// How much food moved through the colony, and how, over a span of time.
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+pub struct LogisticsTally {
+    pub foraged_at_crater: usize,
+    pub foraged_at_surface: usize,
+    /// Crater food handed over at the nest entrance.
+    pub delivered: usize,
+    pub eaten: usize,
+    /// Stored food consumed by fungus rather than by ants.
+    pub spoiled: usize,
+}
+
+impl LogisticsTally {
+    pub fn foraged(&self) -> usize {
+        self.foraged_at_crater + self.foraged_at_surface
+    }
+
+    /// Food which reached the nest less food which was used up. Crater food only counts once it's been delivered
+    /// because, until then, it could still be lost along the way.
+    ///
+    /// ```
+    /// use simulation::common::logistics::LogisticsTally;
+    ///
+    /// let tally = LogisticsTally {
+    ///     foraged_at_crater: 5,
+    ///     foraged_at_surface: 2,
+    ///     delivered: 3,
+    ///     eaten: 4,
+    ///     spoiled: 2,
+    /// };
+    ///
+    /// assert_eq!(tally.net_change(), -1);
+    /// ```
+    pub fn net_change(&self) -> isize {
+        (self.delivered + self.foraged_at_surface) as isize - (self.eaten + self.spoiled) as isize
+    }
+
+    fn add(&self, other: &LogisticsTally) -> LogisticsTally {
+        LogisticsTally {
+            foraged_at_crater: self.foraged_at_crater + other.foraged_at_crater,
+            foraged_at_surface: self.foraged_at_surface + other.foraged_at_surface,
+            delivered: self.delivered + other.delivered,
+            eaten: self.eaten + other.eaten,
+            spoiled: self.spoiled + other.spoiled,
+        }
+    }
+}
+
+/// Food flowing through the player's colony over the last in-game day. Answers whether the colony is getting ahead or
+/// falling behind, and where the shortfall is, which the total amount of food on hand can't.
+#[derive(Resource, Debug, PartialEq, Clone, Serialize, Deserialize, Reflect)]
+#[reflect(Resource, Persist)]
+pub struct ColonyLogistics {
+    // One tally per hour of the day, indexed by the hour of the story they were tallied in, so old hours are reused.
+    hourly_tallies: Vec<LogisticsTally>,
+    hour: isize,
+}
+
+impl Default for ColonyLogistics {
+    fn default() -> Self {
+        Self {
+            hourly_tallies: vec![LogisticsTally::default(); HOURS_PER_DAY as usize],
+            hour: 0,
+        }
+    }
+}
+
+impl ColonyLogistics {
+    fn current_tally_mut(&mut self) -> &mut LogisticsTally {
+        let index = self.hour.rem_euclid(HOURS_PER_DAY) as usize;
+        &mut self.hourly_tallies[index]
+    }
+
+    pub fn record_foraged(&mut self, route: ForagingRoute) {
+        let tally = self.current_tally_mut();
+
+        match route {
+            ForagingRoute::Crater => tally.foraged_at_crater += 1,
+            ForagingRoute::Surface => tally.foraged_at_surface += 1,
+        }
+    }
+
+    pub fn record_delivered(&mut self) {
+        self.current_tally_mut().delivered += 1;
+    }
+
+    pub fn record_eaten(&mut self) {
+        self.current_tally_mut().eaten += 1;
+    }
+
+    pub fn record_spoiled(&mut self) {
+        self.current_tally_mut().spoiled += 1;
+    }
+
+    /// Totals for the last 24 hours, including the hour in progress.
+    pub fn last_day(&self) -> LogisticsTally {
+        self.hourly_tallies
+            .iter()
+            .fold(LogisticsTally::default(), |total, tally| total.add(tally))
+    }
+
+    /// Start tallying a new hour. Tallies for hours skipped over, e.g. while fast-forwarding, are cleared too.
+    fn advance_to(&mut self, hour: isize) {
+        if hour <= self.hour {
+            return;
+        }
+
+        let hours_passed = (hour - self.hour).min(HOURS_PER_DAY);
+
+        for offset in 1..=hours_passed {
+            let index = (self.hour + offset).rem_euclid(HOURS_PER_DAY) as usize;
+            self.hourly_tallies[index] = LogisticsTally::default();
+        }
+
+        self.hour = hour;
+    }
+}
+
+pub fn register_logistics(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<ColonyLogistics>();
+    app_type_registry.write().register::<LogisticsTally>();
+    app_type_registry.write().register::<Vec<LogisticsTally>>();
+}
+
+pub fn initialize_logistics_resources(mut commands: Commands) {
+    commands.init_resource::<ColonyLogistics>();
+}
+
+pub fn remove_logistics_resources(mut commands: Commands) {
+    commands.remove_resource::<ColonyLogistics>();
+}
+
+pub fn update_logistics_hour(
+    story_time: Res<StoryTime>,
+    mut colony_logistics: ResMut<ColonyLogistics>,
+) {
+    colony_logistics.advance_to(story_time.elapsed_ticks() / TICKS_PER_HOUR);
+}
+
+/// Food counts as foraged when the player's ants pick it up out in the world. Seeds count, too, when they're found in
+/// the crater, because harvesting them is how the crater feeds the colony.
+/// NOTE: Inventories are seen as newly added when a story is loaded so they're skipped to avoid counting food twice.
+/// Only newly picked up items count. Ants carrying two morsels still hold one after dropping the other.
+pub fn update_logistics_foraged(
+    ants_query: Query<
+        (Ref<AntInventory>, &Position, &ColonyId, Option<&AtCrater>),
+        (With<Ant>, Without<Dead>),
+    >,
+    elements_query: Query<Ref<Element>>,
+    nest_query: Query<&Nest>,
+    mut colony_logistics: ResMut<ColonyLogistics>,
+) {
+    let nest = nest_query.single();
+
+    for (inventory, position, colony_id, at_crater) in ants_query.iter() {
+        if !inventory.is_changed() || inventory.is_added() || !colony_id.is_player() {
+            continue;
+        }
+
+        let Some(element) = inventory
+            .last()
+            .and_then(|element_entity| elements_query.get(element_entity).ok())
+        else {
+            continue;
+        };
+
+        if !element.is_added() {
+            continue;
+        }
+
+        if at_crater.is_some() {
+            if element.is_forage() {
+                colony_logistics.record_foraged(ForagingRoute::Crater);
+            }
+        } else if element.is_food() && nest.is_aboveground(position) {
+            colony_logistics.record_foraged(ForagingRoute::Surface);
+        }
+    }
+}
@@ -1,31 +1,86 @@
+pub mod airdrop;
+pub mod colony;
+pub mod colony_stats;
+pub mod entity_budget;
+pub mod exploration;
+pub mod food_sharing;
 pub mod grid;
+pub mod intents;
+pub mod logistics;
+pub mod pathfinding;
 pub mod position;
+pub mod reservation;
 
 use crate::{
     app_state::check_story_over, crater_simulation::crater::AtCrater, nest_simulation::{element::update_element_exposure, nest::AtNest}, story_time::set_rate_of_time
 };
 
-use self::position::Position;
+use self::{
+    airdrop::{airdrops_fall, Airdrop},
+    colony::register_colony,
+    colony_stats::{
+        initialize_colony_stats_resources, record_story, register_colony_stats,
+        remove_colony_stats_resources, update_colony_food_gathered, update_colony_peak_population,
+    },
+    entity_budget::{
+        initialize_entity_budget_resources, remove_entity_budget_resources, update_entity_budget,
+    },
+    exploration::register_exploration,
+    food_sharing::{
+        initialize_food_sharing_resources, register_food_sharing, remove_food_sharing_resources,
+    },
+    intents::{initialize_intents_resources, remove_intents_resources, update_intents_log_tick},
+    logistics::{
+        initialize_logistics_resources, register_logistics, remove_logistics_resources,
+        update_logistics_foraged, update_logistics_hour,
+    },
+    position::Position,
+    reservation::{
+        clear_element_reservations, initialize_element_reservation_resources,
+        remove_element_reservation_resources,
+    },
+};
 use super::{
     app_state::{
-        begin_story, continue_startup, finalize_startup, post_setup_clear_change_detection,
-        restart, AppState,
+        begin_story, continue_loading_grid_chunks, continue_startup, continue_to_ruins,
+        continue_viewing_ruins, finalize_startup, post_setup_clear_change_detection, restart,
+        AppState, ViewingRuins,
+    },
+    balance_config::BalanceConfig,
+    benchmark::Benchmarking,
+    broadcast::{
+        await_broadcast_recording, broadcast_story, join_broadcast, leave_broadcast,
+        receive_broadcast_story, remove_broadcast_resources, start_hosting_broadcast,
+        HostingBroadcast, SpectatingBroadcast,
     },
+    content_pack::{
+        content_events_tick, initialize_content_event_resources, load_content_pack_index,
+        remove_content_event_resources, update_content_pack_handles, update_content_packs,
+        ContentPack, ContentPackHandles, ContentPackIndex, ContentPacks, StoryVignette,
+    },
+    demo::{demo_script_tick, initialize_demo_resources, remove_demo_resources, DemoStory},
     external_event::{
         initialize_external_event_resources, process_external_event,
-        remove_external_event_resources,
+        remove_external_event_resources, BlockedExternalEvent,
     },
     // TODO: Element should live in common once I finish adding it to Crater.
-    nest_simulation::element::denormalize_element,
+    nest_simulation::element::{definition::ElementDefinitions, denormalize_element},
+    replay::{
+        begin_recording, begin_replay, record_external_events, remove_replay_resources,
+        replay_external_events, ExternalEventLog, RecordingSimulation, ReplayingSimulation,
+    },
+    ron_asset::{RonAssetLoader, RonConfigPlugin},
     save::{
-        bind_save_onbeforeunload, delete_save_file, initialize_save_resources, load,
-        remove_save_resources, save, unbind_save_onbeforeunload,
+        archive_save, bind_save_onbeforeunload, delete_save_file, initialize_save_resources, load,
+        load_archived_save, load_grid_chunks, remove_save_resources, save, save_on_request,
+        unbind_save_onbeforeunload, IsSaveRequested,
     },
     settings::{initialize_settings_resources, register_settings, remove_settings_resources},
     story_time::{
-        initialize_story_time_resources, register_story_time, remove_story_time_resources,
-        setup_story_time, update_story_elapsed_ticks, update_story_real_world_time,
-        update_time_scale, StoryPlaybackState,
+        initialize_story_time_resources, is_zone_playing, process_time_travel_event,
+        register_story_time, remove_story_time_resources, setup_story_time,
+        update_story_elapsed_ticks, update_story_real_world_time, update_time_scale,
+        StoryPlaybackState, TimeTravelEvent,
     },
     CleanupSet,
     FinishSetupSet,
@@ -57,9 +112,39 @@ pub struct CommonSimulationPlugin;
 
 impl Plugin for CommonSimulationPlugin {
     fn build(&self, app: &mut App) {
+        app.add_plugins((
+            RonConfigPlugin::<BalanceConfig>::default(),
+            RonConfigPlugin::<ElementDefinitions>::default(),
+        ));
+
+        // Content packs aren't tied to a story either. Packs with problems are kept, but unused, so they can be listed.
+        app.init_asset::<ContentPackIndex>();
+        app.init_asset::<ContentPack>();
+        app.init_asset_loader::<RonAssetLoader<ContentPackIndex>>();
+        app.init_asset_loader::<RonAssetLoader<ContentPack>>();
+        app.init_resource::<ContentPackHandles>();
+        app.init_resource::<ContentPacks>();
+        app.add_event::<StoryVignette>();
+        app.add_systems(Startup, load_content_pack_index);
+        app.add_systems(
+            Update,
+            (update_content_pack_handles, update_content_packs).chain(),
+        );
+
+        app.add_event::<BlockedExternalEvent>();
+
         app.add_systems(
             OnEnter(AppState::BeginSetup),
-            (register_settings, register_common, register_story_time),
+            (
+                register_settings,
+                register_common,
+                register_colony,
+                register_colony_stats,
+                register_logistics,
+                register_food_sharing,
+                register_exploration,
+                register_story_time,
+            ),
         );
 
         app.add_systems(
@@ -67,14 +152,55 @@ impl Plugin for CommonSimulationPlugin {
             (
                 initialize_save_resources,
                 apply_deferred,
-                load.pipe(continue_startup),
+                // The view-only app only ever shows ruins so it skips straight to loading them.
+                load.pipe(continue_startup)
+                    .run_if(not(resource_exists::<ViewingRuins>())),
+                continue_to_ruins.run_if(resource_exists::<ViewingRuins>()),
+            )
+                .chain(),
+        );
+
+        // Save resources were initialized when trying to load the active save so they're already available here.
+        app.add_systems(
+            OnEnter(AppState::LoadRuins),
+            load_archived_save.pipe(continue_viewing_ruins),
+        );
+
+        app.add_systems(
+            Update,
+            load_grid_chunks
+                .pipe(continue_loading_grid_chunks)
+                .run_if(in_state(AppState::LoadGridChunks)),
+        );
+
+        app.add_systems(
+            OnEnter(AppState::CreateNewStory),
+            (
+                // Seed before the world is generated so that a recorded story can be recreated by its replay.
+                begin_recording.run_if(resource_exists::<RecordingSimulation>()),
+                begin_replay.run_if(resource_exists::<ReplayingSimulation>()),
+                initialize_settings_resources,
+                finalize_startup,
             )
                 .chain(),
         );
 
         app.add_systems(
             OnEnter(AppState::CreateNewStory),
-            (initialize_settings_resources, finalize_startup).chain(),
+            start_hosting_broadcast.run_if(resource_exists::<HostingBroadcast>()),
+        );
+
+        app.add_systems(OnEnter(AppState::JoinBroadcast), join_broadcast);
+
+        app.add_systems(
+            Update,
+            await_broadcast_recording.run_if(in_state(AppState::JoinBroadcast)),
+        );
+
+        // Spectators who joined stay connected to keep watching.
+        app.add_systems(
+            OnExit(AppState::JoinBroadcast),
+            leave_broadcast.run_if(not(resource_exists::<SpectatingBroadcast>())),
         );
 
         app.add_systems(
@@ -82,69 +208,78 @@ impl Plugin for CommonSimulationPlugin {
             (
                 initialize_story_time_resources,
                 initialize_external_event_resources,
-                bind_save_onbeforeunload,
+                initialize_entity_budget_resources,
+                initialize_element_reservation_resources,
+                initialize_colony_stats_resources,
+                initialize_logistics_resources,
+                initialize_food_sharing_resources,
+                initialize_content_event_resources,
+                initialize_intents_resources,
+                initialize_demo_resources.run_if(resource_exists::<DemoStory>()),
+                // Ruins are read-only, and benchmark colonies, demo colonies, and replays are throwaway, so there's nothing
+                // to save when the page unloads.
+                bind_save_onbeforeunload.run_if(
+                    not(resource_exists::<ViewingRuins>())
+                        .and_then(not(resource_exists::<Benchmarking>()))
+                        .and_then(not(resource_exists::<DemoStory>()))
+                        .and_then(not(resource_exists::<ReplayingSimulation>())),
+                ),
                 post_setup_clear_change_detection,
             )
                 .chain()
                 .in_set(FinishSetupSet::SimulationFinishSetup),
         );
 
-        // IMPORTANT: setup_story_time sets FixedTime.accumulated which is reset when transitioning between schedules.
-        // If this is ran OnEnter FinishSetup then the accumulated time will be reset to zero before FixedUpdate runs.
-        app.add_systems(OnExit(AppState::FinishSetup), setup_story_time);
-
         app.add_systems(
             OnEnter(AppState::PostSetupClearChangeDetection),
             begin_story,
         );
 
+        // Keep a copy of the world as it was when the story ended so its ruins can be revisited from the main menu.
+        // Keep a summary of the story, too, so it can be compared against past and future stories.
         app.add_systems(
-            SimulationUpdate,
-            (
-                process_external_event::<AtNest>,
-                process_external_event::<AtCrater>,
-                apply_deferred,
-                denormalize_element,
-                apply_deferred,
-            )
-                .chain()
-                .in_set(SimulationTickSet::First),
+            OnEnter(AppState::EndStory),
+            (archive_save, record_story).run_if(
+                not(resource_exists::<Benchmarking>())
+                    .and_then(not(resource_exists::<DemoStory>()))
+                    .and_then(not(resource_exists::<ReplayingSimulation>())),
+            ),
         );
 
+        app.add_event::<TimeTravelEvent>();
+
+        // Time travel must be processed before update_time_scale, which restores the tick rate unless fast-forwarding.
         app.add_systems(
-            SimulationUpdate,
-            (update_story_elapsed_ticks,)
+            Update,
+            (process_time_travel_event, update_time_scale)
                 .chain()
-                .in_set(SimulationTickSet::PostSimulationTick)
-                .run_if(not(in_state(StoryPlaybackState::Paused))),
+                .run_if(in_state(AppState::TellStory)),
         );
 
-        // TODO: Maybe (some?) of these should just run in Update?
-        // Ending story seems like it should check every tick, but updating element exposure/updating story time seems OK to run just in Update?
         app.add_systems(
-            SimulationUpdate,
-            (
-                // If this doesn't run then when user spawns elements they won't gain exposure if simulation is paused.
-                apply_deferred,
-                check_story_over,
-                update_element_exposure,
-                // real-world time should update even if the story is paused because real-world time doesn't pause
-                // rate_of_time needs to run when app is paused because fixed_time accumulations need to be cleared while app is paused
-                // to prevent running FixedUpdate schedule repeatedly (while no-oping) when coming back to a hidden tab with a paused sim.
-                (update_story_real_world_time, set_rate_of_time).chain(),
-            )
-                .chain()
-                .in_set(SimulationTickSet::Last),
+            Update,
+            update_story_real_world_time.run_if(in_state(AppState::TellStory)),
         );
 
+        // Runs after the frame's ticks so the host sends, and spectators receive, everything up to the latest tick.
+        // Hosted stories are only ever new stories, and so are always recorded from their beginning.
         app.add_systems(
             Update,
-            update_time_scale.run_if(in_state(AppState::TellStory)),
+            (
+                broadcast_story.run_if(
+                    resource_exists::<HostingBroadcast>()
+                        .and_then(resource_exists::<ExternalEventLog<AtNest>>()),
+                ),
+                receive_broadcast_story.run_if(resource_exists::<SpectatingBroadcast>()),
+            )
+                .run_if(in_state(AppState::TellStory)),
         );
 
+        // Measure once per frame, rather than per tick, because counting every entity isn't free
+        // and the budget only needs to react before memory runs out - not instantly.
         app.add_systems(
             Update,
-            update_story_real_world_time.run_if(in_state(AppState::TellStory)),
+            update_entity_budget.run_if(in_state(AppState::TellStory)),
         );
 
         // Saving in WASM writes to local storage which requires dedicated support.
@@ -154,7 +289,22 @@ impl Plugin for CommonSimulationPlugin {
             // TODO: It's weird (incorrect) that this is declared in `simulation` but that the `save` directory is external to simulation.
             // I think this should get moved up a level.
             save.run_if(
-                in_state(AppState::TellStory).and_then(in_state(StoryPlaybackState::Playing)),
+                in_state(AppState::TellStory)
+                    .and_then(in_state(StoryPlaybackState::Playing))
+                    .and_then(not(resource_exists::<Benchmarking>()))
+                    .and_then(not(resource_exists::<DemoStory>()))
+                    .and_then(not(resource_exists::<ReplayingSimulation>())),
+            ),
+        );
+
+        // Requested saves are written even while paused because that's when they're usually asked for.
+        app.add_systems(
+            PostUpdate,
+            save_on_request.run_if(
+                in_state(AppState::TellStory)
+                    .and_then(resource_exists_and_equals(IsSaveRequested(true)))
+                    .and_then(not(resource_exists::<Benchmarking>()))
+                    .and_then(not(resource_exists::<DemoStory>())),
             ),
         );
 
@@ -162,14 +312,115 @@ impl Plugin for CommonSimulationPlugin {
             OnEnter(AppState::Cleanup),
             (
                 unbind_save_onbeforeunload,
-                delete_save_file,
+                // The player's own story is left alone when leaving the demo.
+                delete_save_file.run_if(not(resource_exists::<DemoStory>())),
                 remove_story_time_resources,
                 remove_settings_resources,
                 remove_save_resources,
                 remove_external_event_resources,
+                remove_entity_budget_resources,
+                remove_element_reservation_resources,
+                remove_colony_stats_resources,
+                remove_logistics_resources,
+                remove_food_sharing_resources,
+                remove_content_event_resources,
+                remove_intents_resources,
+                remove_demo_resources,
+                remove_replay_resources,
+                remove_broadcast_resources,
+                despawn_model::<Airdrop>,
                 restart,
             )
                 .in_set(CleanupSet::SimulationCleanup),
         );
     }
 }
+
+/// Everything which advances a story. Ruins are viewed without it, see `ViewOnlySimulationPlugin`.
+pub struct CommonSimulationTickPlugin;
+
+impl Plugin for CommonSimulationTickPlugin {
+    fn build(&self, app: &mut App) {
+        // IMPORTANT: setup_story_time sets FixedTime.accumulated which is reset when transitioning between schedules.
+        // If this is ran OnEnter FinishSetup then the accumulated time will be reset to zero before FixedUpdate runs.
+        app.add_systems(OnExit(AppState::FinishSetup), setup_story_time);
+
+        app.add_systems(
+            SimulationUpdate,
+            (
+                // Replays ignore user input and inject the events which were recorded for this tick instead.
+                (
+                    replay_external_events::<AtNest>,
+                    replay_external_events::<AtCrater>,
+                )
+                    .run_if(resource_exists::<ReplayingSimulation>()),
+                // Stories loaded from a save weren't recorded from their beginning so they have no log to append to.
+                (
+                    record_external_events::<AtNest>,
+                    record_external_events::<AtCrater>,
+                )
+                    .run_if(
+                        resource_exists::<RecordingSimulation>()
+                            .and_then(resource_exists::<ExternalEventLog<AtNest>>()),
+                    ),
+                process_external_event::<AtNest>,
+                process_external_event::<AtCrater>,
+                apply_deferred,
+                denormalize_element,
+                apply_deferred,
+            )
+                .chain()
+                .in_set(SimulationTickSet::First),
+        );
+
+        // Airdrops land before ants act so food is available to them the tick it lands.
+        // Reservations from last tick are cleared before any ant has a chance to make new ones.
+        app.add_systems(
+            SimulationUpdate,
+            (
+                clear_element_reservations,
+                update_intents_log_tick,
+                airdrops_fall::<AtNest>.run_if(is_zone_playing::<AtNest>),
+                airdrops_fall::<AtCrater>.run_if(is_zone_playing::<AtCrater>),
+                apply_deferred,
+            )
+                .chain()
+                .in_set(SimulationTickSet::PreSimulationTick)
+                .run_if(not(in_state(StoryPlaybackState::Paused))),
+        );
+
+        app.add_systems(
+            SimulationUpdate,
+            (
+                update_story_elapsed_ticks,
+                update_colony_peak_population,
+                update_colony_food_gathered,
+                update_logistics_hour,
+                update_logistics_foraged,
+                content_events_tick,
+                demo_script_tick.run_if(resource_exists::<DemoStory>()),
+            )
+                .chain()
+                .in_set(SimulationTickSet::PostSimulationTick)
+                .run_if(not(in_state(StoryPlaybackState::Paused))),
+        );
+
+        // TODO: Maybe (some?) of these should just run in Update?
+        // Ending story seems like it should check every tick, but updating element exposure/updating story time seems OK to run just in Update?
+        app.add_systems(
+            SimulationUpdate,
+            (
+                // If this doesn't run then when user spawns elements they won't gain exposure if simulation is paused.
+                apply_deferred,
+                check_story_over,
+                update_element_exposure,
+                // real-world time should update even if the story is paused because real-world time doesn't pause
+                // rate_of_time needs to run when app is paused because fixed_time accumulations need to be cleared while app is paused
+                // to prevent running FixedUpdate schedule repeatedly (while no-oping) when coming back to a hidden tab with a paused sim.
+                (update_story_real_world_time, set_rate_of_time).chain(),
+            )
+                .chain()
+                .in_set(SimulationTickSet::Last),
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use super::{grid::GridElements, position::Position, Zone};
+use crate::nest_simulation::element::Element;
+use bevy::utils::{HashMap, HashSet};
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+// Give up searching after visiting this many positions so an unreachable goal doesn't stall the simulation.
+const MAX_SEARCHED_POSITIONS: usize = 4096;
+
+/// Ants walk through air while clinging to a neighboring element. Air with nothing around it to cling to isn't walkable.
+pub fn is_walkable<Z: Zone>(position: Position, grid_elements: &GridElements<Z>) -> bool {
+    grid_elements.is(position, Element::Air)
+        && position
+            .get_adjacent_positions()
+            .into_iter()
+            .any(|adjacent_position| {
+                grid_elements
+                    .get_entity(adjacent_position)
+                    .and_then(|entity| grid_elements.get_element(*entity))
+                    .map_or(false, |element| *element != Element::Air)
+            })
+}
+
+/// Find the shortest walkable route from `start` to `goal` using A*. Ants move one tile at a time, so a route
+/// only steps between adjacent positions.
+/// Returns the positions to walk through, in order, excluding `start` and including `goal`.
+/// Returns None if the goal can't be reached, or if it's too far away to find a route in reasonable time.
+pub fn find_path<Z: Zone>(
+    start: Position,
+    goal: Position,
+    grid_elements: &GridElements<Z>,
+) -> Option<Vec<Position>> {
+    if start == goal {
+        return Some(vec![]);
+    }
+
+    if !is_walkable(goal, grid_elements) {
+        return None;
+    }
+
+    let mut open_positions = BinaryHeap::new();
+    let mut closed_positions = HashSet::new();
+    let mut came_from: HashMap<Position, Position> = HashMap::new();
+    let mut costs: HashMap<Position, isize> = HashMap::new();
+
+    costs.insert(start, 0);
+    open_positions.push(Reverse((start.distance(&goal), start.x, start.y)));
+
+    while let Some(Reverse((_, x, y))) = open_positions.pop() {
+        let position = Position::new(x, y);
+
+        if position == goal {
+            let mut path = vec![goal];
+            let mut current_position = goal;
+
+            while let Some(&previous_position) = came_from.get(&current_position) {
+                if previous_position == start {
+                    break;
+                }
+
+                path.push(previous_position);
+                current_position = previous_position;
+            }
+
+            path.reverse();
+            return Some(path);
+        }
+
+        if !closed_positions.insert(position) {
+            continue;
+        }
+
+        if closed_positions.len() > MAX_SEARCHED_POSITIONS {
+            return None;
+        }
+
+        let cost = costs[&position];
+
+        for adjacent_position in position.get_adjacent_positions() {
+            if closed_positions.contains(&adjacent_position)
+                || !is_walkable(adjacent_position, grid_elements)
+            {
+                continue;
+            }
+
+            let adjacent_cost = cost + 1;
+            let is_cheaper = costs
+                .get(&adjacent_position)
+                .map_or(true, |&existing_cost| adjacent_cost < existing_cost);
+
+            if is_cheaper {
+                costs.insert(adjacent_position, adjacent_cost);
+                came_from.insert(adjacent_position, position);
+                open_positions.push(Reverse((
+                    adjacent_cost + adjacent_position.distance(&goal),
+                    adjacent_position.x,
+                    adjacent_position.y,
+                )));
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        common::grid::Grid,
+        nest_simulation::{element::definition::ElementDefinitions, nest::AtNest},
+    };
+    use bevy::{ecs::system::SystemState, prelude::*};
+
+    // Rows are listed top to bottom. '#' is dirt and '.' is air.
+    fn setup(rows: &[&str]) -> World {
+        let mut world = World::new();
+        world.insert_resource(ElementDefinitions::default());
+
+        let elements_cache = rows
+            .iter()
+            .map(|row| {
+                row.chars()
+                    .map(|tile| {
+                        let element = if tile == '#' {
+                            Element::Dirt
+                        } else {
+                            Element::Air
+                        };
+
+                        world.spawn((element, AtNest)).id()
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let width = rows.first().map_or(0, |row| row.len()) as isize;
+        let height = rows.len() as isize;
+        world.spawn((Grid::new(width, height, elements_cache), AtNest));
+
+        world
+    }
+
+    fn find_nest_path(world: &mut World, start: Position, goal: Position) -> Option<Vec<Position>> {
+        let mut system_state: SystemState<GridElements<AtNest>> = SystemState::new(world);
+        let grid_elements = system_state.get(world);
+
+        find_path(start, goal, &grid_elements)
+    }
+
+    #[test]
+    fn path_to_start_is_empty() {
+        let mut world = setup(&["...", "###"]);
+
+        let path = find_nest_path(&mut world, Position::new(1, 0), Position::new(1, 0));
+
+        assert_eq!(path, Some(vec![]));
+    }
+
+    #[test]
+    fn path_walks_along_surface() {
+        let mut world = setup(&["....", "####"]);
+
+        let path = find_nest_path(&mut world, Position::new(0, 0), Position::new(3, 0));
+
+        assert_eq!(
+            path,
+            Some(vec![
+                Position::new(1, 0),
+                Position::new(2, 0),
+                Position::new(3, 0),
+            ])
+        );
+    }
+
+    #[test]
+    fn path_follows_tunnel_around_dirt() {
+        let mut world = setup(&["#####", "#.#.#", "#...#", "#####"]);
+
+        let path = find_nest_path(&mut world, Position::new(1, 1), Position::new(3, 1));
+
+        assert_eq!(
+            path,
+            Some(vec![
+                Position::new(1, 2),
+                Position::new(2, 2),
+                Position::new(3, 2),
+                Position::new(3, 1),
+            ])
+        );
+    }
+
+    #[test]
+    fn path_to_dirt_is_none() {
+        let mut world = setup(&["...", "###"]);
+
+        let path = find_nest_path(&mut world, Position::new(0, 0), Position::new(1, 1));
+
+        assert_eq!(path, None);
+    }
+
+    #[test]
+    fn path_to_sealed_chamber_is_none() {
+        let mut world = setup(&["#####", "#.#.#", "#####"]);
+
+        let path = find_nest_path(&mut world, Position::new(1, 1), Position::new(3, 1));
+
+        assert_eq!(path, None);
+    }
+
+    #[test]
+    fn air_without_anything_to_cling_to_is_not_walkable() {
+        let mut world = setup(&[".....", ".....", "#####"]);
+
+        let path = find_nest_path(&mut world, Position::new(0, 1), Position::new(2, 0));
+
+        assert_eq!(path, None);
+    }
+}
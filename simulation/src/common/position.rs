@@ -1,3 +1,4 @@
+use crate::save::ReflectPersist;
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::ops::{Add, Mul, Sub};
@@ -5,7 +6,7 @@ use std::ops::{Add, Mul, Sub};
 #[derive(
     Component, Debug, Eq, PartialEq, Hash, Copy, Clone, Reflect, Default, Serialize, Deserialize,
 )]
-#[reflect(Component)]
+#[reflect(Component, Persist)]
 pub struct Position {
     pub x: isize,
     pub y: isize,
@@ -0,0 +1,41 @@
+use bevy::{prelude::*, utils::HashMap};
+
+/// Elements which an ant has claimed to dig up, or to drop something onto, this tick.
+///
+/// Digging and dropping are applied through deferred commands so, without reservations, several ants can choose the same
+/// element in a single tick. Only the first command succeeds and the others silently no-op, wasting those ants' turns.
+/// Ants reserve an element before issuing a command against it. Ants which find their target already reserved re-plan
+/// instead. Reservations are first-come, first-served and only last for the tick in which they're made.
+#[derive(Resource, Default, Debug)]
+pub struct ElementReservations(HashMap<Entity, Entity>);
+
+impl ElementReservations {
+    /// Claim an element on behalf of an ant. Returns false if another ant already claimed it this tick.
+    pub fn try_reserve(&mut self, element_entity: Entity, ant_entity: Entity) -> bool {
+        let reserving_ant_entity = self.0.entry(element_entity).or_insert(ant_entity);
+
+        *reserving_ant_entity == ant_entity
+    }
+
+    /// Whether an ant, other than the given one, has claimed the element this tick.
+    pub fn is_reserved_by_other(&self, element_entity: Entity, ant_entity: Entity) -> bool {
+        self.0
+            .get(&element_entity)
+            .map_or(false, |reserving_ant_entity| {
+                *reserving_ant_entity != ant_entity
+            })
+    }
+}
+
+pub fn initialize_element_reservation_resources(mut commands: Commands) {
+    commands.init_resource::<ElementReservations>();
+}
+
+pub fn remove_element_reservation_resources(mut commands: Commands) {
+    commands.remove_resource::<ElementReservations>();
+}
+
+/// Reservations only need to last as long as it takes for the commands issued against them to apply.
+pub fn clear_element_reservations(mut element_reservations: ResMut<ElementReservations>) {
+    element_reservations.0.clear();
+}
@@ -0,0 +1,402 @@
+use crate::{
+    common::{grid::Grid, position::Position},
+    external_event::ExternalSimulationEvent,
+    nest_simulation::nest::{AtNest, Nest},
+    ron_asset::RonAsset,
+    story_time::{DEFAULT_TICKS_PER_SECOND, SECONDS_PER_DAY},
+};
+use bevy::{asset::LoadState, prelude::*, reflect::TypePath};
+use bevy_turborand::prelude::*;
+use serde::Deserialize;
+
+/// Browsers can't list the files in a directory so content packs are listed, by path, in an index.
+pub const CONTENT_PACK_INDEX_PATH: &str = "content/index.packs.ron";
+// On average, how many vignettes are told each day of a story.
+const VIGNETTES_PER_DAY: f64 = 2.0;
+// Keep events from spawning so much that they overwhelm the entity budget.
+const MAX_CONTENT_EVENT_AMOUNT: isize = 50;
+
+#[derive(Asset, TypePath, Clone, Debug, Deserialize)]
+pub struct ContentPackIndex(pub Vec<String>);
+
+/// Content which can be added to the game without touching code. Packs are RON files listed in the content pack index.
+/// Everything but the name is optional so packs can focus on just one kind of content.
+#[derive(Asset, TypePath, Clone, Debug, Default, Deserialize)]
+pub struct ContentPack {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    // Ants are named from these as well as the built-in name list.
+    #[serde(default)]
+    pub names: Vec<String>,
+    // Short bits of narration told every so often while the story plays.
+    #[serde(default)]
+    pub vignettes: Vec<String>,
+    #[serde(default)]
+    pub encyclopedia: Vec<EncyclopediaEntry>,
+    #[serde(default)]
+    pub events: Vec<ContentEvent>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct EncyclopediaEntry {
+    pub title: String,
+    pub body: String,
+}
+
+/// Something which happens, at random, while the story plays. It's announced with its title and description.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ContentEvent {
+    pub title: String,
+    pub description: String,
+    // The chance, from 0.0 to 1.0, the event happens on any given day.
+    pub chance_per_day: f64,
+    #[serde(default)]
+    pub effect: ContentEventEffect,
+}
+
+/// What an event does to the nest. Effects are limited to what players could do themselves from the action menu.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize)]
+pub enum ContentEventEffect {
+    // The event is only told, not felt.
+    #[default]
+    None,
+    AirdropFood {
+        amount: isize,
+    },
+    SpawnWorkers {
+        amount: isize,
+    },
+}
+
+impl ContentPack {
+    /// Describe everything wrong with the pack. Packs with any problems aren't used, rather than being partially used,
+    /// so authors notice the problem.
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = vec![];
+
+        if self.name.trim().is_empty() {
+            problems.push("Pack has no name".to_string());
+        }
+
+        if self.names.iter().any(|name| name.trim().is_empty()) {
+            problems.push("Names can't be blank".to_string());
+        }
+
+        if self
+            .vignettes
+            .iter()
+            .any(|vignette| vignette.trim().is_empty())
+        {
+            problems.push("Vignettes can't be blank".to_string());
+        }
+
+        for (index, entry) in self.encyclopedia.iter().enumerate() {
+            if entry.title.trim().is_empty() || entry.body.trim().is_empty() {
+                problems.push(format!(
+                    "Encyclopedia entry {} needs a title and body",
+                    index + 1
+                ));
+            } else if self.encyclopedia[..index]
+                .iter()
+                .any(|other_entry| other_entry.title == entry.title)
+            {
+                problems.push(format!(
+                    "Encyclopedia entry \"{}\" is duplicated",
+                    entry.title
+                ));
+            }
+        }
+
+        for (index, event) in self.events.iter().enumerate() {
+            if event.title.trim().is_empty() || event.description.trim().is_empty() {
+                problems.push(format!("Event {} needs a title and description", index + 1));
+            }
+
+            if !(0.0..=1.0).contains(&event.chance_per_day) {
+                problems.push(format!(
+                    "Event \"{}\" must have a chance_per_day between 0.0 and 1.0",
+                    event.title
+                ));
+            }
+
+            match event.effect {
+                ContentEventEffect::None => {}
+                ContentEventEffect::AirdropFood { amount }
+                | ContentEventEffect::SpawnWorkers { amount } => {
+                    if !(1..=MAX_CONTENT_EVENT_AMOUNT).contains(&amount) {
+                        problems.push(format!(
+                            "Event \"{}\" must have an amount between 1 and {}",
+                            event.title, MAX_CONTENT_EVENT_AMOUNT
+                        ));
+                    }
+                }
+            }
+        }
+
+        problems
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ContentPackStatus {
+    Loading,
+    Loaded,
+    Failed,
+    Invalid(Vec<String>),
+}
+
+#[derive(Clone, Debug)]
+pub struct LoadedContentPack {
+    pub path: String,
+    pub status: ContentPackStatus,
+    pub pack: ContentPack,
+}
+
+impl LoadedContentPack {
+    pub fn is_loaded(&self) -> bool {
+        self.status == ContentPackStatus::Loaded
+    }
+}
+
+/// Every pack listed in the index, whether or not it loaded successfully, so problems can be shown in settings.
+/// Only packs which loaded, and are valid, contribute content.
+/// NOTE: Packs aren't tied to a story so they're loaded once and live for the lifetime of the app.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct ContentPacks {
+    packs: Vec<LoadedContentPack>,
+    // Flattened across packs because a name is picked for every ant that's born.
+    names: Vec<String>,
+}
+
+impl ContentPacks {
+    pub fn packs(&self) -> &[LoadedContentPack] {
+        &self.packs
+    }
+
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    pub fn vignettes(&self) -> impl Iterator<Item = &String> {
+        self.loaded_packs().flat_map(|pack| pack.vignettes.iter())
+    }
+
+    pub fn encyclopedia_entries(&self) -> impl Iterator<Item = &EncyclopediaEntry> {
+        self.loaded_packs()
+            .flat_map(|pack| pack.encyclopedia.iter())
+    }
+
+    pub fn events(&self) -> impl Iterator<Item = &ContentEvent> {
+        self.loaded_packs().flat_map(|pack| pack.events.iter())
+    }
+
+    fn loaded_packs(&self) -> impl Iterator<Item = &ContentPack> {
+        self.packs
+            .iter()
+            .filter(|loaded_pack| loaded_pack.is_loaded())
+            .map(|loaded_pack| &loaded_pack.pack)
+    }
+}
+
+/// Narration for the player. Sent when a vignette is told or a content event happens.
+#[derive(Event, Clone, Debug, PartialEq)]
+pub struct StoryVignette {
+    pub title: Option<String>,
+    pub text: String,
+}
+
+/// Content events are rolled with their own RNG so loading, or editing, packs doesn't change how the rest of the
+/// simulation plays out for a given seed.
+#[derive(Resource)]
+pub struct ContentEventRng(RngComponent);
+
+#[derive(Resource)]
+pub struct ContentPackIndexHandle(pub Handle<ContentPackIndex>);
+
+#[derive(Resource, Default)]
+pub struct ContentPackHandles(pub Vec<(String, Handle<ContentPack>)>);
+
+impl RonAsset for ContentPackIndex {
+    const EXTENSIONS: &'static [&'static str] = &["packs.ron"];
+}
+
+impl RonAsset for ContentPack {
+    const EXTENSIONS: &'static [&'static str] = &["pack.ron"];
+}
+
+pub fn load_content_pack_index(asset_server: Res<AssetServer>, mut commands: Commands) {
+    commands.insert_resource(ContentPackIndexHandle(
+        asset_server.load(CONTENT_PACK_INDEX_PATH),
+    ));
+}
+
+/// Load each pack listed in the index whenever the index finishes loading or is modified on disk.
+pub fn update_content_pack_handles(
+    mut asset_events: EventReader<AssetEvent<ContentPackIndex>>,
+    content_pack_index_assets: Res<Assets<ContentPackIndex>>,
+    content_pack_index_handle: Res<ContentPackIndexHandle>,
+    mut content_pack_handles: ResMut<ContentPackHandles>,
+    asset_server: Res<AssetServer>,
+) {
+    for asset_event in asset_events.read() {
+        let asset_id = match asset_event {
+            AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } => id,
+            _ => continue,
+        };
+
+        if *asset_id != content_pack_index_handle.0.id() {
+            continue;
+        }
+
+        if let Some(content_pack_index) = content_pack_index_assets.get(*asset_id) {
+            content_pack_handles.0 = content_pack_index
+                .0
+                .iter()
+                .map(|path| (path.clone(), asset_server.load(path)))
+                .collect();
+        }
+    }
+}
+
+/// Copy packs into the ContentPacks resource, once validated, as they finish loading or are modified on disk.
+/// Systems read the resource, rather than the assets, so they don't need to handle packs being unavailable.
+pub fn update_content_packs(
+    mut asset_events: EventReader<AssetEvent<ContentPack>>,
+    content_pack_assets: Res<Assets<ContentPack>>,
+    content_pack_handles: Res<ContentPackHandles>,
+    asset_server: Res<AssetServer>,
+    mut content_packs: ResMut<ContentPacks>,
+) {
+    let statuses = content_pack_handles
+        .0
+        .iter()
+        .map(|(_, handle)| match asset_server.get_load_state(handle) {
+            Some(LoadState::Failed) => ContentPackStatus::Failed,
+            _ if content_pack_assets.contains(handle) => ContentPackStatus::Loaded,
+            _ => ContentPackStatus::Loading,
+        })
+        .collect::<Vec<_>>();
+
+    let has_status_changed = content_packs.packs.len() != statuses.len()
+        || content_packs
+            .packs
+            .iter()
+            .zip(statuses.iter())
+            .any(|(loaded_pack, status)| {
+                // Invalid packs did load. They just didn't pass validation.
+                let load_status = match &loaded_pack.status {
+                    ContentPackStatus::Invalid(_) => &ContentPackStatus::Loaded,
+                    load_status => load_status,
+                };
+
+                load_status != status
+            });
+
+    if asset_events.read().count() == 0 && !has_status_changed {
+        return;
+    }
+
+    let packs = content_pack_handles
+        .0
+        .iter()
+        .zip(statuses)
+        .map(|((path, handle), status)| {
+            let pack = content_pack_assets.get(handle).cloned().unwrap_or_default();
+
+            let status = match status {
+                ContentPackStatus::Loaded => {
+                    let problems = pack.validate();
+
+                    if problems.is_empty() {
+                        ContentPackStatus::Loaded
+                    } else {
+                        warn!("Content pack {} is invalid: {:?}", path, problems);
+                        ContentPackStatus::Invalid(problems)
+                    }
+                }
+                ContentPackStatus::Failed => {
+                    warn!("Content pack {} failed to load", path);
+                    ContentPackStatus::Failed
+                }
+                status => status,
+            };
+
+            LoadedContentPack {
+                path: path.clone(),
+                status,
+                pack,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let names = packs
+        .iter()
+        .filter(|loaded_pack| loaded_pack.is_loaded())
+        .flat_map(|loaded_pack| loaded_pack.pack.names.iter().cloned())
+        .collect();
+
+    *content_packs = ContentPacks { packs, names };
+}
+
+pub fn initialize_content_event_resources(mut rng: ResMut<GlobalRng>, mut commands: Commands) {
+    commands.insert_resource(ContentEventRng(RngComponent::with_seed(rng.u64(..))));
+}
+
+pub fn remove_content_event_resources(mut commands: Commands) {
+    commands.remove_resource::<ContentEventRng>();
+}
+
+/// Every so often tell a vignette, and give each content event a chance to happen. Events are announced as they happen.
+/// Effects are applied by sending the same external events the action menu sends, so they're processed next tick.
+pub fn content_events_tick(
+    content_packs: Res<ContentPacks>,
+    mut content_event_rng: ResMut<ContentEventRng>,
+    nest_query: Query<&Grid, With<Nest>>,
+    mut external_simulation_events: ResMut<Events<ExternalSimulationEvent<AtNest>>>,
+    mut story_vignettes: EventWriter<StoryVignette>,
+) {
+    let ticks_per_day = (SECONDS_PER_DAY * DEFAULT_TICKS_PER_SECOND) as f64;
+    let rng = &mut content_event_rng.0;
+
+    let vignettes = content_packs.vignettes().collect::<Vec<_>>();
+    if !vignettes.is_empty() && rng.chance(VIGNETTES_PER_DAY / ticks_per_day) {
+        if let Some(vignette) = rng.sample(&vignettes) {
+            story_vignettes.send(StoryVignette {
+                title: None,
+                text: vignette.to_string(),
+            });
+        }
+    }
+
+    for event in content_packs.events() {
+        if !rng.chance(event.chance_per_day / ticks_per_day) {
+            continue;
+        }
+
+        story_vignettes.send(StoryVignette {
+            title: Some(event.title.clone()),
+            text: event.description.clone(),
+        });
+
+        match event.effect {
+            ContentEventEffect::None => {}
+            ContentEventEffect::AirdropFood { amount } => {
+                let grid = nest_query.single();
+
+                // The top row of the nest is always open sky.
+                external_simulation_events.send(ExternalSimulationEvent::AirdropFood {
+                    zone: AtNest,
+                    center: Position::new(rng.isize(0..grid.width()), 0),
+                    amount,
+                });
+            }
+            ContentEventEffect::SpawnWorkers { amount } => {
+                external_simulation_events.send(ExternalSimulationEvent::SpawnWorkerAnts {
+                    zone: AtNest,
+                    amount,
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use super::CraterOrientation;
+use crate::{
+    common::{colony::ColonyId, position::Position},
+    crater_simulation::crater::AtCrater,
+    nest_simulation::ant::{commands::AntCommandsExt, AntRole, Dead, Initiative},
+};
+use bevy::prelude::*;
+use bevy_turborand::{DelegatedRng, GlobalRng};
+
+// Soldiers are bred for fighting. Everyone else is as likely to lose a fight as to win it.
+const SOLDIER_WIN_CHANCE: f64 = 0.9;
+const WIN_CHANCE: f64 = 0.5;
+
+/// Ants fight any ant from another colony they're standing on, or facing. The loser is killed outright and drops
+/// whatever it was carrying.
+/// NOTE: Defeated ants are despawned, rather than marked `Dead`, because there are no corpses in the crater yet and
+/// a dead queen is taken to mean the story is over.
+pub fn ants_fight(
+    mut ants_query: Query<
+        (
+            Entity,
+            &Position,
+            &CraterOrientation,
+            &AntRole,
+            &ColonyId,
+            &mut Initiative,
+        ),
+        (With<AtCrater>, Without<Dead>),
+    >,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    let ants = ants_query
+        .iter()
+        .map(|(ant_entity, position, _, _, colony_id, _)| (ant_entity, *position, *colony_id))
+        .collect::<Vec<_>>();
+
+    let mut defeated_ant_entities = vec![];
+
+    for (ant_entity, position, orientation, role, colony_id, mut initiative) in
+        ants_query.iter_mut()
+    {
+        if !initiative.can_act() || defeated_ant_entities.contains(&ant_entity) {
+            continue;
+        }
+
+        let ahead_position = orientation.get_ahead_position(position);
+
+        let opponent = ants
+            .iter()
+            .find(|(other_ant_entity, other_position, other_colony_id)| {
+                other_colony_id != colony_id
+                    && !defeated_ant_entities.contains(other_ant_entity)
+                    && (*other_position == *position || *other_position == ahead_position)
+            });
+
+        let Some(&(opponent_entity, _, _)) = opponent else {
+            continue;
+        };
+
+        initiative.consume();
+
+        let win_chance = match role {
+            AntRole::Soldier => SOLDIER_WIN_CHANCE,
+            AntRole::Worker | AntRole::Queen => WIN_CHANCE,
+        };
+
+        if !rng.chance(win_chance) {
+            continue;
+        }
+
+        commands.despawn_ant(opponent_entity, AtCrater);
+        defeated_ant_entities.push(opponent_entity);
+    }
+}
@@ -0,0 +1,217 @@
+use super::CraterOrientation;
+use crate::{
+    common::{
+        colony::ColonyId,
+        grid::{Grid, GridElements},
+        logistics::ColonyLogistics,
+        position::Position,
+        reservation::ElementReservations,
+    },
+    crater_simulation::{
+        crater::{get_crater_nest_position, AtCrater, Crater},
+        plant::Plant,
+    },
+    nest_simulation::{
+        ant::{
+            commands::AntCommandsExt, recruitment::Recruitment, AntInventory, AntRole, Dead,
+            Initiative, InventoryItemBundle,
+        },
+        element::{commands::ElementCommandsExt, seed::get_seed_delivery_position, Element},
+        nest::{AtNest, Nest},
+    },
+};
+use bevy::prelude::*;
+use bevy_turborand::GlobalRng;
+
+// Workers only gather while they've got nothing but forage in hand.
+fn is_carrying_only_forage(inventory: &AntInventory, elements_query: &Query<&Element>) -> bool {
+    inventory.items().iter().all(|&item_entity| {
+        elements_query
+            .get(item_entity)
+            .map_or(false, Element::is_forage)
+    })
+}
+
+/// Workers pick ripe seeds straight off any plant they come across. Picking is quicker than harvesting a seed from the
+/// ground so it happens first.
+pub fn ants_gather_seeds(
+    mut ants_query: Query<
+        (
+            &Position,
+            &CraterOrientation,
+            &AntRole,
+            &mut AntInventory,
+            &mut Initiative,
+        ),
+        (With<AtCrater>, Without<Dead>),
+    >,
+    mut plants_query: Query<(&Position, &mut Plant), With<AtCrater>>,
+    elements_query: Query<&Element>,
+    mut commands: Commands,
+) {
+    for (position, orientation, role, mut inventory, mut initiative) in ants_query.iter_mut() {
+        if !initiative.can_act()
+            || *role != AntRole::Worker
+            || !inventory.has_room()
+            || !is_carrying_only_forage(&inventory, &elements_query)
+        {
+            continue;
+        }
+
+        let ahead_position = orientation.get_ahead_position(position);
+
+        let Some((_, mut plant)) = plants_query.iter_mut().find(|(plant_position, plant)| {
+            (**plant_position == *position || **plant_position == ahead_position)
+                && plant.has_seeds()
+        }) else {
+            continue;
+        };
+
+        let seed_entity = commands.spawn(InventoryItemBundle::new(Element::Seed)).id();
+
+        inventory.push(seed_entity);
+        plant.pick();
+        initiative.consume();
+    }
+}
+
+/// Workers pick up any seed they come across while wandering the crater. Rivals harvest too, so seeds are contested.
+/// Workers with room to spare pick up another seed on their way home rather than making another trip for it.
+pub fn ants_harvest_seeds(
+    ants_query: Query<
+        (
+            Entity,
+            &Position,
+            &CraterOrientation,
+            &AntRole,
+            &AntInventory,
+            &Initiative,
+        ),
+        (With<AtCrater>, Without<Dead>),
+    >,
+    elements_query: Query<&Element>,
+    grid_elements: GridElements<AtCrater>,
+    mut element_reservations: ResMut<ElementReservations>,
+    mut commands: Commands,
+) {
+    for (ant_entity, position, orientation, role, inventory, initiative) in ants_query.iter() {
+        if !initiative.can_act() || *role != AntRole::Worker || !inventory.has_room() {
+            continue;
+        }
+
+        if !is_carrying_only_forage(inventory, &elements_query) {
+            continue;
+        }
+
+        let ahead_position = orientation.get_ahead_position(position);
+        if !grid_elements.is(ahead_position, Element::Seed) {
+            continue;
+        }
+
+        let seed_entity = *grid_elements.entity(ahead_position);
+        if !element_reservations.try_reserve(seed_entity, ant_entity) {
+            continue;
+        }
+
+        commands.dig(ant_entity, ahead_position, seed_entity, AtCrater);
+    }
+}
+
+/// The player's harvesters carry their seeds, and honeydew, straight back to the nest, as do scouts heading home to
+/// recruit help. They still stray, now and then, while walking.
+pub fn ants_return_seeds(
+    mut ants_query: Query<
+        (
+            &Position,
+            &mut CraterOrientation,
+            &ColonyId,
+            &AntInventory,
+            &Initiative,
+            Option<&Recruitment>,
+        ),
+        (With<AtCrater>, Without<Dead>),
+    >,
+    crater_query: Query<&Grid, With<Crater>>,
+    elements_query: Query<&Element>,
+) {
+    let nest_position = get_crater_nest_position(crater_query.single());
+
+    for (position, mut orientation, colony_id, inventory, initiative, recruitment) in
+        ants_query.iter_mut()
+    {
+        if !initiative.can_move() || !colony_id.is_player() {
+            continue;
+        }
+
+        let is_carrying_forage = inventory.items().iter().any(|&element_entity| {
+            elements_query
+                .get(element_entity)
+                .map_or(false, Element::is_forage)
+        });
+
+        let is_returning_scout = matches!(recruitment, Some(Recruitment::Returning(_)));
+
+        if !(is_carrying_forage || is_returning_scout) || *position == nest_position {
+            continue;
+        }
+
+        *orientation = CraterOrientation::toward(position, &nest_position);
+    }
+}
+
+/// Harvesters hand their seeds, and honeydew, over at the nest entrance. They're left on the nest's surface for nest
+/// workers to carry down into storage. Harvesters hold onto what they're carrying until the nest has an entrance.
+pub fn ants_deliver_seeds(
+    mut ants_query: Query<
+        (&Position, &ColonyId, &mut AntInventory, &mut Initiative),
+        (With<AtCrater>, Without<Dead>),
+    >,
+    crater_query: Query<&Grid, With<Crater>>,
+    elements_query: Query<&Element>,
+    nest_query: Query<&Nest>,
+    nest_grid_elements: GridElements<AtNest>,
+    mut colony_logistics: ResMut<ColonyLogistics>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    let nest_position = get_crater_nest_position(crater_query.single());
+    let nest = nest_query.single();
+
+    for (position, colony_id, mut inventory, mut initiative) in ants_query.iter_mut() {
+        if !initiative.can_act() || !colony_id.is_player() || position.distance(&nest_position) > 1
+        {
+            continue;
+        }
+
+        let Some(element_entity) = inventory.last() else {
+            continue;
+        };
+
+        let Ok(&element) = elements_query.get(element_entity) else {
+            continue;
+        };
+
+        if !element.is_forage() {
+            continue;
+        }
+
+        let Some(delivery_position) =
+            get_seed_delivery_position(nest, &nest_grid_elements, &mut rng)
+        else {
+            continue;
+        };
+
+        if !nest_grid_elements.is(delivery_position, Element::Air) {
+            continue;
+        }
+
+        let air_entity = *nest_grid_elements.entity(delivery_position);
+        commands.replace_element(delivery_position, element, air_entity, AtNest);
+        commands.entity(element_entity).despawn();
+
+        inventory.pop();
+        initiative.consume();
+
+        colony_logistics.record_delivered();
+    }
+}
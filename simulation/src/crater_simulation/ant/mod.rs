@@ -0,0 +1,113 @@
+pub mod combat;
+pub mod harvest;
+pub mod scouting;
+pub mod tending;
+pub mod territory;
+pub mod walk;
+pub mod wander;
+
+use crate::{
+    common::position::Position, crater_simulation::crater::AtCrater, nest_simulation::ant::Ant,
+    save::ReflectPersist,
+};
+use bevy::prelude::*;
+use bevy_turborand::{DelegatedRng, GlobalRng};
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+
+/// The crater is seen from above so there's no gravity for ants to cling against. Ants are free to face any of the
+/// eight directions around them, and walk diagonally, unlike `AntOrientation` which describes an ant clinging to
+/// the surface of the nest.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Component, Persist)]
+pub enum CraterOrientation {
+    #[default]
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl CraterOrientation {
+    /// Listed clockwise, starting from North, so neighboring orientations are 45 degrees apart.
+    pub const ALL: [CraterOrientation; 8] = [
+        CraterOrientation::North,
+        CraterOrientation::NorthEast,
+        CraterOrientation::East,
+        CraterOrientation::SouthEast,
+        CraterOrientation::South,
+        CraterOrientation::SouthWest,
+        CraterOrientation::West,
+        CraterOrientation::NorthWest,
+    ];
+
+    pub fn random(rng: &mut Mut<GlobalRng>) -> Self {
+        Self::ALL[rng.usize(0..Self::ALL.len())]
+    }
+
+    fn index(&self) -> isize {
+        *self as isize
+    }
+
+    /// Turn by 45 degrees per step. Positive steps turn clockwise and negative steps turn counter-clockwise.
+    pub fn rotate(&self, steps: isize) -> Self {
+        let length = Self::ALL.len() as isize;
+        Self::ALL[(self.index() + steps).rem_euclid(length) as usize]
+    }
+
+    pub fn turn_around(&self) -> Self {
+        self.rotate(4)
+    }
+
+    // NOTE: Grid positions increase downward so North is negative y.
+    pub fn get_ahead_position(&self, position: &Position) -> Position {
+        let delta = match self {
+            CraterOrientation::North => Position::new(0, -1),
+            CraterOrientation::NorthEast => Position::new(1, -1),
+            CraterOrientation::East => Position::new(1, 0),
+            CraterOrientation::SouthEast => Position::new(1, 1),
+            CraterOrientation::South => Position::new(0, 1),
+            CraterOrientation::SouthWest => Position::new(-1, 1),
+            CraterOrientation::West => Position::new(-1, 0),
+            CraterOrientation::NorthWest => Position::new(-1, -1),
+        };
+
+        *position + delta
+    }
+
+    /// Whichever way takes a step closest to the goal. Ants steer by this rather than finding a route because the
+    /// crater floor is mostly open ground.
+    pub fn toward(position: &Position, goal: &Position) -> Self {
+        Self::ALL
+            .into_iter()
+            .min_by_key(|orientation| orientation.get_ahead_position(position).distance(goal))
+            .unwrap()
+    }
+
+    /// The ant sprite faces East when unrotated. Each step clockwise from East is a 45 degree clockwise rotation.
+    pub fn as_world_rotation(&self) -> Quat {
+        let east_index = CraterOrientation::East.index();
+        Quat::from_rotation_z((east_index - self.index()) as f32 * PI / 4.0)
+    }
+}
+
+pub fn register_crater_ant(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<CraterOrientation>();
+}
+
+/// Ants arriving in the crater, and ants from saves which predate `CraterOrientation`, look around for a direction to face.
+pub fn ants_add_crater_orientation(
+    ants_query: Query<Entity, (With<Ant>, With<AtCrater>, Without<CraterOrientation>)>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    for ant_entity in ants_query.iter() {
+        commands
+            .entity(ant_entity)
+            .insert(CraterOrientation::random(&mut rng.reborrow()));
+    }
+}
@@ -0,0 +1,249 @@
+use super::CraterOrientation;
+use crate::{
+    common::{
+        colony::ColonyId,
+        grid::{Grid, GridElements},
+        position::Position,
+    },
+    crater_simulation::crater::{get_crater_nest_position, AtCrater, Crater},
+    nest_simulation::{
+        ant::{
+            pathing::Path,
+            recruitment::{Recruitment, TandemRun, TANDEM_RUN_MAX_GAP},
+            AntInventory, AntRole, Dead, Initiative,
+        },
+        element::{Element, Seed},
+        nest::{get_nest_entrance, AtNest, Nest},
+    },
+};
+use bevy::{prelude::*, utils::HashMap};
+
+// Seeds are blown into the crater one at a time so a handful lying close together is a find worth fetching help for.
+const MIN_SEEDS_WORTH_RECRUITING: usize = 3;
+// How far around a seed a scout looks for others like it.
+const SCOUTING_RADIUS: isize = 3;
+
+/// The player's workers which come across a patch of seeds, rather than a lone seed, head home to recruit help
+/// harvesting it. They take the seed they found along with them. Patches someone else is already fetching help for
+/// are left to them.
+pub fn ants_scout_food(
+    ants_query: Query<
+        (
+            Entity,
+            &Position,
+            &CraterOrientation,
+            &AntRole,
+            &AntInventory,
+            &ColonyId,
+            &Initiative,
+        ),
+        (
+            With<AtCrater>,
+            Without<Recruitment>,
+            Without<TandemRun>,
+            Without<Dead>,
+        ),
+    >,
+    scouts_query: Query<&Recruitment, Without<Dead>>,
+    seeds_query: Query<&Position, (With<Seed>, With<AtCrater>)>,
+    grid_elements: GridElements<AtCrater>,
+    mut commands: Commands,
+) {
+    let mut food_positions = scouts_query
+        .iter()
+        .map(|recruitment| recruitment.food_position())
+        .collect::<Vec<_>>();
+
+    for (ant_entity, position, orientation, role, inventory, colony_id, initiative) in
+        ants_query.iter()
+    {
+        if !initiative.can_act()
+            || *role != AntRole::Worker
+            || !colony_id.is_player()
+            || !inventory.is_empty()
+        {
+            continue;
+        }
+
+        let ahead_position = orientation.get_ahead_position(position);
+        if !grid_elements.is(ahead_position, Element::Seed) {
+            continue;
+        }
+
+        let is_known = food_positions
+            .iter()
+            .any(|food_position| food_position.distance(&ahead_position) <= SCOUTING_RADIUS);
+
+        let nearby_seeds = seeds_query
+            .iter()
+            .filter(|seed_position| seed_position.distance(&ahead_position) <= SCOUTING_RADIUS)
+            .count();
+
+        if is_known || nearby_seeds < MIN_SEEDS_WORTH_RECRUITING {
+            continue;
+        }
+
+        // Remember where the scout stood, rather than where the seed lay, so it has somewhere it can walk back to.
+        commands
+            .entity(ant_entity)
+            .insert(Recruitment::Returning(*position));
+        food_positions.push(*position);
+    }
+}
+
+/// Scouts which have made it home, and handed over the seed they brought back, head down into the nest to recruit.
+/// They emerge from the nest's entrance. Scouts wait at the crater's center until the nest has an entrance.
+pub fn ants_enter_nest(
+    mut ants_query: Query<
+        (
+            Entity,
+            &Position,
+            &AntInventory,
+            &Recruitment,
+            &mut Initiative,
+        ),
+        (With<AtCrater>, Without<Dead>),
+    >,
+    crater_query: Query<&Grid, With<Crater>>,
+    nest_query: Query<&Nest>,
+    nest_grid_elements: GridElements<AtNest>,
+    mut commands: Commands,
+) {
+    let nest_position = get_crater_nest_position(crater_query.single());
+    let Some(entrance_position) = get_nest_entrance(nest_query.single(), &nest_grid_elements)
+    else {
+        return;
+    };
+
+    for (ant_entity, position, inventory, recruitment, mut initiative) in ants_query.iter_mut() {
+        let Recruitment::Returning(food_position) = *recruitment else {
+            continue;
+        };
+
+        if !initiative.can_act() || !inventory.is_empty() || position.distance(&nest_position) > 1 {
+            continue;
+        }
+
+        // NOTE: `CraterOrientation` is given back by `ants_add_crater_orientation` when the ant returns to the crater.
+        commands
+            .entity(ant_entity)
+            .remove::<(AtCrater, CraterOrientation)>()
+            .insert((
+                AtNest,
+                entrance_position,
+                Recruitment::recruiting(food_position),
+            ));
+        initiative.consume();
+    }
+}
+
+/// Scouts lead their recruits out of the nest once they've reached its entrance, or anywhere on the surface, and
+/// everyone emerges at the crater's center. Recruits which have fallen too far behind, or picked something up along
+/// the way, are left behind.
+pub fn ants_leave_nest(
+    scouts_query: Query<
+        (Entity, &Position, &AntInventory, &Recruitment),
+        (With<AtNest>, Without<Dead>),
+    >,
+    recruits_query: Query<
+        (Entity, &Position, &AntInventory, &TandemRun),
+        (With<AtNest>, Without<Dead>),
+    >,
+    crater_query: Query<&Grid, With<Crater>>,
+    nest_query: Query<&Nest>,
+    nest_grid_elements: GridElements<AtNest>,
+    mut commands: Commands,
+) {
+    let nest_position = get_crater_nest_position(crater_query.single());
+    let nest = nest_query.single();
+    let entrance_position = get_nest_entrance(nest, &nest_grid_elements);
+
+    for (scout_entity, scout_position, inventory, recruitment) in scouts_query.iter() {
+        let is_at_entrance = entrance_position.map_or(false, |entrance_position| {
+            scout_position.distance(&entrance_position) <= 1
+        });
+
+        if !recruitment.is_leading()
+            || !inventory.is_empty()
+            || !(is_at_entrance || nest.is_aboveground(scout_position))
+        {
+            continue;
+        }
+
+        let recruit_entities = recruits_query
+            .iter()
+            .filter(|(_, position, inventory, tandem_run)| {
+                tandem_run.0 == scout_entity
+                    && inventory.is_empty()
+                    && position.distance(scout_position) <= TANDEM_RUN_MAX_GAP
+            })
+            .map(|(recruit_entity, ..)| recruit_entity);
+
+        for ant_entity in std::iter::once(scout_entity).chain(recruit_entities) {
+            commands
+                .entity(ant_entity)
+                .remove::<(AtNest, Path)>()
+                .insert((AtCrater, nest_position));
+        }
+    }
+}
+
+/// Scouts lead their recruits across the crater to the food they found, and recruits follow close behind. The run is
+/// over once the scout reaches the food, or picks up a seed along the way. Recruits which lose track of their scout,
+/// or pick up a seed of their own, go back to foraging as usual.
+pub fn ants_tandem_run(
+    mut ants_query: Query<
+        (
+            Entity,
+            &Position,
+            &mut CraterOrientation,
+            &AntInventory,
+            &Initiative,
+            Option<&Recruitment>,
+            Option<&TandemRun>,
+        ),
+        (With<AtCrater>, Without<Dead>),
+    >,
+    mut commands: Commands,
+) {
+    let scout_positions = ants_query
+        .iter()
+        .filter(|(_, _, _, _, _, recruitment, _)| {
+            recruitment.map_or(false, |recruitment| recruitment.is_leading())
+        })
+        .map(|(scout_entity, position, ..)| (scout_entity, *position))
+        .collect::<HashMap<_, _>>();
+
+    for (ant_entity, position, mut orientation, inventory, initiative, recruitment, tandem_run) in
+        ants_query.iter_mut()
+    {
+        if let Some(&Recruitment::Leading(food_position)) = recruitment {
+            if !inventory.is_empty() || position.distance(&food_position) <= 1 {
+                commands.entity(ant_entity).remove::<Recruitment>();
+            } else if initiative.can_move() {
+                *orientation = CraterOrientation::toward(position, &food_position);
+            }
+
+            continue;
+        }
+
+        let Some(tandem_run) = tandem_run else {
+            continue;
+        };
+
+        let scout_position = scout_positions
+            .get(&tandem_run.0)
+            .filter(|scout_position| position.distance(scout_position) <= TANDEM_RUN_MAX_GAP);
+
+        match scout_position {
+            Some(scout_position) if inventory.is_empty() => {
+                if initiative.can_move() && position != scout_position {
+                    *orientation = CraterOrientation::toward(position, scout_position);
+                }
+            }
+            _ => {
+                commands.entity(ant_entity).remove::<TandemRun>();
+            }
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use super::CraterOrientation;
+use crate::{
+    common::{colony::ColonyId, position::Position},
+    crater_simulation::{aphid::Aphid, crater::AtCrater},
+    nest_simulation::{
+        ant::{AntInventory, AntRole, Dead, Initiative, InventoryItemBundle},
+        element::{Element, FoodKind},
+    },
+};
+use bevy::prelude::*;
+
+/// The player's workers stroke any aphid they come across which has honeydew to give and carry the honeydew home.
+/// Workers lingering around a herd, tending it, keep rivals from eating the aphids.
+pub fn ants_milk_aphids(
+    mut ants_query: Query<
+        (
+            &Position,
+            &CraterOrientation,
+            &AntRole,
+            &ColonyId,
+            &mut AntInventory,
+            &mut Initiative,
+        ),
+        (With<AtCrater>, Without<Dead>),
+    >,
+    mut aphids_query: Query<(&Position, &mut Aphid), With<AtCrater>>,
+    mut commands: Commands,
+) {
+    for (position, orientation, role, colony_id, mut inventory, mut initiative) in
+        ants_query.iter_mut()
+    {
+        if !initiative.can_act()
+            || *role != AntRole::Worker
+            || !colony_id.is_player()
+            || !inventory.is_empty()
+        {
+            continue;
+        }
+
+        let ahead_position = orientation.get_ahead_position(position);
+
+        let Some((_, mut aphid)) = aphids_query.iter_mut().find(|(aphid_position, aphid)| {
+            (**aphid_position == *position || **aphid_position == ahead_position)
+                && aphid.has_honeydew()
+        }) else {
+            continue;
+        };
+
+        let honeydew_entity = commands
+            .spawn((InventoryItemBundle::new(Element::Food), FoodKind::Sugar))
+            .id();
+
+        inventory.push(honeydew_entity);
+        aphid.milk();
+        initiative.consume();
+    }
+}
@@ -0,0 +1,32 @@
+use crate::{
+    common::{colony::ColonyId, grid::GridElements, position::Position},
+    crater_simulation::crater::AtCrater,
+    nest_simulation::{
+        ant::{Ant, Dead},
+        element::Element,
+    },
+};
+use bevy::prelude::*;
+
+/// Colonies lay claim to the ground their ants walk over. Ground is claimed by whichever colony was last there so
+/// territory shifts back and forth where colonies meet.
+pub fn ants_claim_territory(
+    ants_query: Query<(&Position, &ColonyId), (With<Ant>, With<AtCrater>, Without<Dead>)>,
+    elements_query: Query<Option<&ColonyId>, (With<Element>, With<AtCrater>)>,
+    grid_elements: GridElements<AtCrater>,
+    mut commands: Commands,
+) {
+    for (position, colony_id) in ants_query.iter() {
+        let Some(&element_entity) = grid_elements.get_entity(*position) else {
+            continue;
+        };
+
+        let Ok(element_colony_id) = elements_query.get(element_entity) else {
+            continue;
+        };
+
+        if element_colony_id != Some(colony_id) {
+            commands.entity(element_entity).insert(*colony_id);
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use super::{
+    wander::{get_wander_step, Wander},
+    CraterOrientation,
+};
+use crate::{
+    common::{grid::GridElements, position::Position},
+    crater_simulation::crater::AtCrater,
+    nest_simulation::{
+        ant::{Dead, Initiative},
+        element::Element,
+    },
+    settings::Settings,
+};
+use bevy::prelude::*;
+use bevy_turborand::GlobalRng;
+
+/// Ants wander the crater floor, searching it the way `Settings::exploration_strategy` says to. There's nothing to
+/// climb, or fall from, so ants simply walk until something blocks their way. Then they turn aside, or around, to find
+/// an open direction.
+pub fn ants_walk(
+    mut ants_query: Query<
+        (
+            &mut Initiative,
+            &mut Position,
+            &mut CraterOrientation,
+            &mut Wander,
+        ),
+        (With<AtCrater>, Without<Dead>),
+    >,
+    grid_elements: GridElements<AtCrater>,
+    settings: Res<Settings>,
+    mut rng: ResMut<GlobalRng>,
+) {
+    let policy = settings.exploration_strategy.policy(&settings);
+
+    for (mut initiative, mut position, mut orientation, mut wander) in ants_query.iter_mut() {
+        if !initiative.can_move() {
+            continue;
+        }
+
+        let (wandered_position, wandered_orientation) = get_wander_step(
+            policy.as_ref(),
+            *position,
+            *orientation,
+            &mut wander,
+            |candidate_position| grid_elements.is(candidate_position, Element::Air),
+            &mut rng,
+        );
+
+        if *position != wandered_position {
+            *position = wandered_position;
+        }
+
+        *orientation = wandered_orientation;
+        initiative.consume_movement();
+    }
+}
@@ -0,0 +1,196 @@
+use super::CraterOrientation;
+use crate::{
+    common::position::Position, crater_simulation::crater::AtCrater, nest_simulation::ant::Ant,
+    save::ReflectPersist, settings::Settings,
+};
+use bevy::prelude::*;
+use bevy_turborand::{DelegatedRng, GlobalRng};
+use serde::{Deserialize, Serialize};
+
+// Ants on a correlated random walk veer a little every twenty or so steps, so their heading drifts rather than jumps.
+const CORRELATED_TURN_CHANCE: f32 = 0.05;
+// Levy flights are mostly short hops with the occasional long, straight, run. Flights are at least this many steps.
+const LEVY_MIN_FLIGHT_LENGTH: isize = 3;
+// Flight lengths fall off as a power law. An exponent of 2 is thought to be ideal when food is sparse and scattered.
+const LEVY_EXPONENT: f32 = 2.0;
+// The power law has a very long tail. Nothing in the crater is further away than this anyway.
+const LEVY_MAX_FLIGHT_LENGTH: isize = 200;
+
+/// How ants search the crater when they've nowhere in particular to be. Chosen in Settings.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Reflect, Default, Serialize, Deserialize)]
+#[reflect(Default)]
+pub enum ExplorationStrategy {
+    /// Walk straight ahead, turning aside now and then.
+    RandomWalk,
+    /// Veer slightly, and often, so the heading drifts.
+    CorrelatedRandomWalk,
+    /// Mostly short hops, in random directions, with the occasional long, straight, run.
+    #[default]
+    LevyFlight,
+}
+
+impl ExplorationStrategy {
+    pub const ALL: [ExplorationStrategy; 3] = [
+        ExplorationStrategy::RandomWalk,
+        ExplorationStrategy::CorrelatedRandomWalk,
+        ExplorationStrategy::LevyFlight,
+    ];
+
+    pub fn policy(&self, settings: &Settings) -> Box<dyn ExplorationPolicy> {
+        match self {
+            ExplorationStrategy::RandomWalk => Box::new(RandomWalkPolicy {
+                turn_chance: settings.probabilities.random_turn,
+            }),
+            ExplorationStrategy::CorrelatedRandomWalk => Box::new(CorrelatedRandomWalkPolicy {
+                turn_chance: CORRELATED_TURN_CHANCE,
+            }),
+            ExplorationStrategy::LevyFlight => Box::new(LevyFlightPolicy {
+                min_flight_length: LEVY_MIN_FLIGHT_LENGTH,
+                exponent: LEVY_EXPONENT,
+            }),
+        }
+    }
+}
+
+/// How far along an ant is in its current leg of wandering. Only policies which plan legs ahead of time use it.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Component, Persist)]
+pub struct Wander {
+    remaining_steps: isize,
+}
+
+pub fn register_wander(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<Wander>();
+}
+
+/// A way for a wandering ant to decide which way to go next. New strategies implement this, and are added to
+/// `ExplorationStrategy`, so they can be chosen in Settings and compared with `run_exploration_benchmark`.
+pub trait ExplorationPolicy {
+    /// How many 45 degree steps to turn before walking on. Positive turns are clockwise. Zero keeps straight ahead.
+    fn get_turn(&self, wander: &mut Wander, rng: &mut GlobalRng) -> isize;
+}
+
+/// Ants walk straight ahead and, now and then, turn aside. This is how ants wander the nest, too.
+pub struct RandomWalkPolicy {
+    pub turn_chance: f32,
+}
+
+impl ExplorationPolicy for RandomWalkPolicy {
+    fn get_turn(&self, _: &mut Wander, rng: &mut GlobalRng) -> isize {
+        if !rng.chance(self.turn_chance.into()) {
+            return 0;
+        }
+
+        if rng.bool() {
+            1
+        } else {
+            -1
+        }
+    }
+}
+
+/// Ants veer slightly, and often, so each heading is similar to the last, but the path curves rather than running
+/// straight until something blocks it.
+pub struct CorrelatedRandomWalkPolicy {
+    pub turn_chance: f32,
+}
+
+impl ExplorationPolicy for CorrelatedRandomWalkPolicy {
+    fn get_turn(&self, _: &mut Wander, rng: &mut GlobalRng) -> isize {
+        if !rng.chance(self.turn_chance.into()) {
+            return 0;
+        }
+
+        // Turning back the way it came is rare, but it happens.
+        match rng.usize(0..10) {
+            0 => rng.isize(-3..=3),
+            _ if rng.bool() => 1,
+            _ => -1,
+        }
+    }
+}
+
+/// Ants fly straight for a randomly chosen distance, then set off in a random direction. Most flights are short so
+/// the nearby ground is searched thoroughly, but now and then a long flight carries the ant somewhere new.
+pub struct LevyFlightPolicy {
+    pub min_flight_length: isize,
+    pub exponent: f32,
+}
+
+impl ExplorationPolicy for LevyFlightPolicy {
+    fn get_turn(&self, wander: &mut Wander, rng: &mut GlobalRng) -> isize {
+        if wander.remaining_steps > 0 {
+            wander.remaining_steps -= 1;
+            return 0;
+        }
+
+        wander.remaining_steps =
+            get_levy_flight_length(rng.f32(), self.min_flight_length, self.exponent);
+
+        rng.isize(0..CraterOrientation::ALL.len() as isize)
+    }
+}
+
+/// How many steps a Levy flight lasts given a random `uniform` value in [0, 1). Lengths follow a power law with the
+/// given `exponent`: most flights are close to `min_flight_length` and each doubling in length is rarer than the last.
+///
+/// ```
+/// use simulation::crater_simulation::ant::wander::get_levy_flight_length;
+///
+/// assert_eq!(get_levy_flight_length(0.0, 3, 2.0), 3);
+/// // With an exponent of 2, half of all flights are at least twice the minimum and a quarter at least four times.
+/// assert_eq!(get_levy_flight_length(0.5, 3, 2.0), 6);
+/// assert_eq!(get_levy_flight_length(0.75, 3, 2.0), 12);
+/// // The longest flights are cut short.
+/// assert_eq!(get_levy_flight_length(0.9999, 3, 2.0), 200);
+/// ```
+pub fn get_levy_flight_length(uniform: f32, min_flight_length: isize, exponent: f32) -> isize {
+    let length = min_flight_length as f32 * (1.0 - uniform).powf(-1.0 / (exponent - 1.0));
+
+    (length.round() as isize).min(LEVY_MAX_FLIGHT_LENGTH)
+}
+
+/// Take one turn of wandering. The ant turns if its policy says to, otherwise it walks ahead if that's open ground.
+/// Blocked ants prefer turning slightly, in a random direction, so they slide along obstacles rather than bouncing off
+/// them, and give up on the leg they were on. Returns where the ant ends up, and which way it's facing.
+pub fn get_wander_step(
+    policy: &dyn ExplorationPolicy,
+    position: Position,
+    orientation: CraterOrientation,
+    wander: &mut Wander,
+    is_open: impl Fn(Position) -> bool,
+    rng: &mut GlobalRng,
+) -> (Position, CraterOrientation) {
+    let turn = policy.get_turn(wander, rng);
+    if turn != 0 {
+        return (position, orientation.rotate(turn));
+    }
+
+    let ahead_position = orientation.get_ahead_position(&position);
+    if is_open(ahead_position) {
+        return (ahead_position, orientation);
+    }
+
+    *wander = Wander::default();
+
+    let turn_direction = if rng.bool() { 1 } else { -1 };
+    let turned_orientation = (1..=3)
+        .flat_map(|steps| [steps * turn_direction, -steps * turn_direction])
+        .map(|steps| orientation.rotate(steps))
+        .find(|turned_orientation| is_open(turned_orientation.get_ahead_position(&position)));
+
+    (
+        position,
+        turned_orientation.unwrap_or_else(|| orientation.turn_around()),
+    )
+}
+
+/// Ants arriving in the crater, and ants from saves which predate `Wander`, start out between legs.
+pub fn ants_add_wander(
+    ants_query: Query<Entity, (With<Ant>, With<AtCrater>, Without<Wander>)>,
+    mut commands: Commands,
+) {
+    for ant_entity in ants_query.iter() {
+        commands.entity(ant_entity).insert(Wander::default());
+    }
+}
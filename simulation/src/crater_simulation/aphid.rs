@@ -0,0 +1,181 @@
+use super::{
+    ant::CraterOrientation,
+    crater::{get_crater_nest_position, AtCrater, Crater},
+};
+use crate::{
+    common::{colony::ColonyId, grid::Grid, position::Position},
+    nest_simulation::ant::{Dead, Initiative},
+    save::ReflectPersist,
+    settings::Settings,
+    story_time::DEFAULT_TICKS_PER_SECOND,
+};
+use bevy::prelude::*;
+use bevy_turborand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// A few small herds are scattered across a fresh crater.
+const INITIAL_HERD_COUNT: usize = 3;
+const INITIAL_HERD_SIZE: usize = 4;
+// Herds are kept away from the nest, and from the rival colony's corner, so they have to be found.
+const MIN_HERD_NEST_DISTANCE: isize = 20;
+// Aphids feed constantly and have honeydew to give every couple of hours.
+const HONEYDEW_TICKS: isize = 60 * 60 * 2 * DEFAULT_TICKS_PER_SECOND;
+// Aphids multiply quickly when nothing is eating them, up to however many the crater can feed.
+const APHID_BIRTH_CHANCE: f64 = 0.00002;
+const MAX_APHIDS: usize = 30;
+// Rival ants keep clear of aphids the player's workers are standing guard over.
+const GUARD_RADIUS: isize = 2;
+
+/// A sap-sucking insect the player's workers tend for its honeydew. Aphids don't wander. They stay put, feeding,
+/// wherever they were born.
+/// NOTE: Aphids don't depend on plants. They feed on the crater floor wherever they happen to be.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect)]
+#[reflect(Component, Persist)]
+pub struct Aphid {
+    // Ticks until there's honeydew to collect.
+    remaining_ticks: isize,
+}
+
+impl Default for Aphid {
+    fn default() -> Self {
+        Self {
+            remaining_ticks: HONEYDEW_TICKS,
+        }
+    }
+}
+
+impl Aphid {
+    pub fn has_honeydew(&self) -> bool {
+        self.remaining_ticks <= 0
+    }
+
+    /// Collect the aphid's honeydew. It takes a while for the aphid to make more.
+    pub fn milk(&mut self) {
+        self.remaining_ticks = HONEYDEW_TICKS;
+    }
+}
+
+pub fn register_aphid(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<Aphid>();
+}
+
+pub fn spawn_aphids(settings: Res<Settings>, mut rng: ResMut<GlobalRng>, mut commands: Commands) {
+    let nest_position = Position::new(settings.crater_width / 2, settings.crater_height / 2);
+    let rival_position = Position::new(settings.crater_width / 6, settings.crater_height / 6);
+
+    let mut herd_count = 0;
+
+    while herd_count < INITIAL_HERD_COUNT {
+        let center = Position::new(
+            rng.isize(0..settings.crater_width - INITIAL_HERD_SIZE as isize),
+            rng.isize(0..settings.crater_height),
+        );
+
+        if center.distance(&nest_position) < MIN_HERD_NEST_DISTANCE
+            || center.distance(&rival_position) < MIN_HERD_NEST_DISTANCE
+        {
+            continue;
+        }
+
+        for offset in 0..INITIAL_HERD_SIZE {
+            let position = center + Position::new(offset as isize, 0);
+            commands.spawn((Aphid::default(), position, AtCrater));
+        }
+
+        herd_count += 1;
+    }
+}
+
+pub fn aphids_make_honeydew(mut aphids_query: Query<&mut Aphid, With<AtCrater>>) {
+    for mut aphid in aphids_query.iter_mut() {
+        if !aphid.has_honeydew() {
+            aphid.remaining_ticks -= 1;
+        }
+    }
+}
+
+/// Aphids give birth to young beside them so herds grow in place.
+pub fn aphids_reproduce(
+    aphids_query: Query<&Position, (With<Aphid>, With<AtCrater>)>,
+    crater_query: Query<&Grid, With<Crater>>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    let grid = crater_query.single();
+    let nest_position = get_crater_nest_position(grid);
+    let mut aphid_positions = aphids_query.iter().copied().collect::<Vec<_>>();
+
+    for position in aphids_query.iter() {
+        if aphid_positions.len() >= MAX_APHIDS {
+            return;
+        }
+
+        if !rng.chance(APHID_BIRTH_CHANCE) {
+            continue;
+        }
+
+        let birth_positions = position
+            .get_adjacent_positions()
+            .into_iter()
+            .filter(|birth_position| {
+                grid.is_within_bounds(birth_position)
+                    && *birth_position != nest_position
+                    && !aphid_positions.contains(birth_position)
+            })
+            .collect::<Vec<_>>();
+
+        let Some(&birth_position) = rng.sample(&birth_positions) else {
+            continue;
+        };
+
+        commands.spawn((Aphid::default(), birth_position, AtCrater));
+        aphid_positions.push(birth_position);
+    }
+}
+
+/// Rival ants eat any aphid they come across, unless one of the player's workers is close enough to drive them off.
+pub fn aphids_get_eaten(
+    aphids_query: Query<(Entity, &Position), (With<Aphid>, With<AtCrater>)>,
+    mut ants_query: Query<
+        (&Position, &CraterOrientation, &ColonyId, &mut Initiative),
+        (With<AtCrater>, Without<Dead>),
+    >,
+    mut commands: Commands,
+) {
+    let guard_positions = ants_query
+        .iter()
+        .filter(|(_, _, colony_id, _)| colony_id.is_player())
+        .map(|(position, ..)| *position)
+        .collect::<Vec<_>>();
+
+    let mut eaten_aphid_entities = vec![];
+
+    for (position, orientation, colony_id, mut initiative) in ants_query.iter_mut() {
+        if !initiative.can_act() || colony_id.is_player() {
+            continue;
+        }
+
+        let ahead_position = orientation.get_ahead_position(position);
+
+        let aphid = aphids_query.iter().find(|(aphid_entity, aphid_position)| {
+            (**aphid_position == *position || **aphid_position == ahead_position)
+                && !eaten_aphid_entities.contains(aphid_entity)
+        });
+
+        let Some((aphid_entity, aphid_position)) = aphid else {
+            continue;
+        };
+
+        let is_guarded = guard_positions
+            .iter()
+            .any(|guard_position| guard_position.distance(aphid_position) <= GUARD_RADIUS);
+
+        if is_guarded {
+            continue;
+        }
+
+        commands.entity(aphid_entity).despawn();
+        eaten_aphid_entities.push(aphid_entity);
+        initiative.consume();
+    }
+}
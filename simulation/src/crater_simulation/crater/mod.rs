@@ -1,21 +1,37 @@
 use crate::{
-    common::{grid::Grid, position::Position, Zone},
+    balance_config::BalanceConfig,
+    common::{
+        colony::ColonyId,
+        grid::{Grid, GridElements},
+        position::Position,
+        Zone,
+    },
+    content_pack::ContentPacks,
+    crater_simulation::ant::CraterOrientation,
     // TODO: Move most of Element and Ant to Common
     nest_simulation::{
         ant::{
-            digestion::Digestion, hunger::Hunger, Angle, AntBundle, AntColor, AntInventory,
-            AntName, AntOrientation, AntRole, Facing, Initiative,
+            digestion::Digestion, hunger::Hunger, soldier::Intruder, Angle, AntBundle, AntColor,
+            AntInventory, AntName, AntOrientation, AntRole, Facing, Initiative,
         },
-        element::{Element, ElementBundle},
+        element::{commands::ElementCommandsExt, Element, ElementBundle, Seed},
     },
+    save::ReflectPersist,
     settings::Settings,
+    story_time::{Season, StoryTime},
 };
 use bevy::prelude::*;
-use bevy_turborand::GlobalRng;
+use bevy_turborand::prelude::*;
 use serde::{Deserialize, Serialize};
 
+// Enough rivals to contest the crater without immediately overrunning a young colony.
+const RIVAL_WORKER_COUNT: isize = 4;
+// A fresh crater has a light scattering of seeds. More are blown in over time, up to a limit.
+const INITIAL_SEED_CHANCE: f64 = 0.002;
+const MAX_CRATER_SEEDS: usize = 50;
+
 #[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
-#[reflect(Component)]
+#[reflect(Component, Persist)]
 pub struct AtCrater;
 
 impl Zone for AtCrater {}
@@ -23,7 +39,7 @@ impl Zone for AtCrater {}
 /// Note the intentional omission of reflection/serialization.
 /// This is because Crater is trivially regenerated on app startup from persisted state.
 #[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
-#[reflect(Component)]
+#[reflect(Component, Persist)]
 pub struct Crater;
 
 pub fn register_crater(app_type_registry: ResMut<AppTypeRegistry>) {
@@ -62,23 +78,94 @@ pub fn insert_crater_grid(
 /// `spawn_element` writes to the grid cache, which is not yet initialized. The grid cache will
 /// be updated after this function is called. This keeps cache initialization parity between
 /// creating a new world and loading an existing world.
-pub fn spawn_crater_elements(settings: Res<Settings>, mut commands: Commands) {
+pub fn spawn_crater_elements(
+    settings: Res<Settings>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
     for y in 0..settings.crater_height {
         for x in 0..settings.crater_width {
             let position = Position::new(x, y);
 
-            commands.spawn(ElementBundle::new(Element::Air, position, AtCrater));
+            let element = if rng.chance(INITIAL_SEED_CHANCE) {
+                Element::Seed
+            } else {
+                Element::Air
+            };
+
+            commands.spawn(ElementBundle::new(element, position, AtCrater));
 
             // TODO: Fill with some food at random locations.
         }
     }
 }
 
+/// The player's nest opens onto the center of the crater.
+pub fn get_crater_nest_position(grid: &Grid) -> Position {
+    Position::new(grid.width() / 2, grid.height() / 2)
+}
+
+/// How many more, or fewer, seeds are blown into the crater than usual during a given season. Plants go to seed through
+/// the Summer and shed the last of them in Autumn. Hardly anything is blown in during Winter.
+///
+/// ```
+/// use simulation::{crater_simulation::crater::get_seed_spawn_multiplier, story_time::Season};
+///
+/// assert_eq!(get_seed_spawn_multiplier(Season::Spring), 1.0);
+/// assert!(get_seed_spawn_multiplier(Season::Summer) > get_seed_spawn_multiplier(Season::Spring));
+/// assert!(get_seed_spawn_multiplier(Season::Winter) < get_seed_spawn_multiplier(Season::Autumn));
+/// ```
+pub fn get_seed_spawn_multiplier(season: Season) -> f32 {
+    match season {
+        Season::Spring => 1.0,
+        Season::Summer => 1.5,
+        Season::Autumn => 1.25,
+        Season::Winter => 0.1,
+    }
+}
+
+/// Seeds are blown into the crater over time. They land on open ground, anywhere, until the crater is well stocked.
+/// How often they're blown in depends on the season.
+pub fn crater_scatter_seeds(
+    seeds_query: Query<(), (With<Seed>, With<AtCrater>)>,
+    crater_query: Query<&Grid, With<Crater>>,
+    grid_elements: GridElements<AtCrater>,
+    settings: Res<Settings>,
+    story_time: Res<StoryTime>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    let seed_spawn_chance =
+        settings.probabilities.crater_seed_spawn * get_seed_spawn_multiplier(story_time.season());
+
+    if seeds_query.iter().count() >= MAX_CRATER_SEEDS || rng.f32() >= seed_spawn_chance {
+        return;
+    }
+
+    let grid = crater_query.single();
+    let position = Position::new(rng.isize(0..grid.width()), rng.isize(0..grid.height()));
+
+    // Seeds don't land where the nest opens onto the crater, where they'd be in the way.
+    if position == get_crater_nest_position(grid) || !grid_elements.is(position, Element::Air) {
+        return;
+    }
+
+    let air_entity = *grid_elements.entity(position);
+    commands.replace_element(position, Element::Seed, air_entity, AtCrater);
+}
+
 pub fn spawn_crater_ants(
     settings: Res<Settings>,
+    balance_config: Res<BalanceConfig>,
+    content_packs: Res<ContentPacks>,
     mut rng: ResMut<GlobalRng>,
     mut commands: Commands,
 ) {
+    // The queen has yet to raise any workers who could forage.
+    if settings.is_realistic_start {
+        return;
+    }
+
     let mut rng = rng.reborrow();
 
     // Just spawn one worker ant for now for prototyping.
@@ -86,16 +173,60 @@ pub fn spawn_crater_ants(
         // Spawn in the center of the crater for now. In the future will need to spawn around the Nest which will be in the center.
         Position::new(settings.crater_width / 2, settings.crater_height / 2),
         AntColor(settings.ant_color),
-        // TODO: No idea if this AntOrientation concept makes sense for "top-down" view, certainly wasn't designed with it in mind
+        // NOTE: Crater ants face using `CraterOrientation`. `AntOrientation` is only kept for when they head into the nest.
         AntOrientation::new(Facing::random(&mut rng), Angle::Zero),
-        AntInventory::default(),
+        AntInventory::new(AntRole::Worker),
         AntRole::Worker,
-        AntName::random(&mut rng),
+        AntName::random(&mut rng, &content_packs),
         Initiative::new(&mut rng),
         AtCrater,
-        Hunger::new(settings.max_hunger_time),
+        Hunger::new(
+            settings.max_hunger_time,
+            balance_config.appetite.get(AntRole::Worker),
+        ),
         Digestion::new(settings.max_digestion_time),
     );
 
-    commands.spawn(worker_ant_bundle);
+    commands.spawn((worker_ant_bundle, CraterOrientation::random(&mut rng)));
+}
+
+/// A rival colony forages the crater alongside the player's colony. Its ants start out clustered around their queen
+/// in a corner of the crater, well away from the player's nest in the center.
+/// Rival ants are intruders, and will be fought off by soldiers, if they ever find their way into the nest.
+/// NOTE: Rival colonies don't have a nest of their own yet. Their queen wanders the crater with her workers.
+pub fn spawn_rival_colony(
+    settings: Res<Settings>,
+    balance_config: Res<BalanceConfig>,
+    content_packs: Res<ContentPacks>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    let mut rng = rng.reborrow();
+    let center = Position::new(settings.crater_width / 6, settings.crater_height / 6);
+
+    let roles = std::iter::once(AntRole::Queen)
+        .chain(std::iter::repeat(AntRole::Worker).take(RIVAL_WORKER_COUNT as usize));
+
+    for (index, role) in roles.enumerate() {
+        let ant_bundle = AntBundle::new(
+            center + Position::new(index as isize, 0),
+            // Rival ants are tinted by colony when rendered so their own color goes unseen.
+            AntColor(settings.ant_color),
+            AntOrientation::new(Facing::random(&mut rng), Angle::Zero),
+            AntInventory::new(role),
+            role,
+            AntName::random(&mut rng, &content_packs),
+            Initiative::new(&mut rng),
+            AtCrater,
+            Hunger::new(settings.max_hunger_time, balance_config.appetite.get(role)),
+            Digestion::new(settings.max_digestion_time),
+        );
+
+        commands.spawn((
+            ant_bundle,
+            CraterOrientation::random(&mut rng),
+            ColonyId::RIVAL,
+            Intruder,
+        ));
+    }
 }
@@ -1,12 +1,45 @@
+pub mod ant;
+pub mod aphid;
 pub mod crater;
+pub mod plant;
 
 use self::{
+    ant::{
+        ants_add_crater_orientation,
+        combat::ants_fight,
+        harvest::{ants_deliver_seeds, ants_gather_seeds, ants_harvest_seeds, ants_return_seeds},
+        register_crater_ant,
+        scouting::{ants_enter_nest, ants_leave_nest, ants_scout_food, ants_tandem_run},
+        tending::ants_milk_aphids,
+        territory::ants_claim_territory,
+        walk::ants_walk,
+        wander::{ants_add_wander, register_wander},
+    },
+    aphid::{
+        aphids_get_eaten, aphids_make_honeydew, aphids_reproduce, register_aphid, spawn_aphids,
+        Aphid,
+    },
     crater::register_crater,
-    crater::{spawn_crater, spawn_crater_ants, spawn_crater_elements, Crater},
+    crater::{
+        crater_scatter_seeds, spawn_crater, spawn_crater_ants, spawn_crater_elements,
+        spawn_rival_colony, AtCrater, Crater,
+    },
+    plant::{plants_grow, register_plant, seeds_sprout, Plant},
 };
 use super::{
-    apply_deferred, despawn_model, insert_crater_grid, settings::initialize_settings_resources,
-    AppState, CleanupSet, FinishSetupSet,
+    apply_deferred,
+    common::{
+        colony::ants_add_colony_id,
+        exploration::{ants_explore, insert_exploration},
+    },
+    despawn_model, insert_crater_grid,
+    nest_simulation::{
+        ant::{ants_initiative, energy::ants_recharge_energy},
+        nest::AtNest,
+    },
+    settings::initialize_settings_resources,
+    story_time::is_zone_playing,
+    AppState, CleanupSet, FinishSetupSet, SimulationTickSet, SimulationUpdate, StoryPlaybackState,
 };
 use bevy::prelude::*;
 
@@ -14,7 +47,16 @@ pub struct CraterSimulationPlugin;
 
 impl Plugin for CraterSimulationPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(AppState::BeginSetup), register_crater);
+        app.add_systems(
+            OnEnter(AppState::BeginSetup),
+            (
+                register_crater,
+                register_crater_ant,
+                register_aphid,
+                register_plant,
+                register_wander,
+            ),
+        );
 
         app.add_systems(
             OnEnter(AppState::CreateNewStory),
@@ -23,7 +65,12 @@ impl Plugin for CraterSimulationPlugin {
                 apply_deferred,
                 spawn_crater,
                 apply_deferred,
-                (spawn_crater_elements, spawn_crater_ants),
+                (
+                    spawn_crater_elements,
+                    spawn_crater_ants,
+                    spawn_rival_colony,
+                    spawn_aphids,
+                ),
             )
                 .chain()
                 .after(initialize_settings_resources),
@@ -31,14 +78,66 @@ impl Plugin for CraterSimulationPlugin {
 
         app.add_systems(
             OnEnter(AppState::FinishSetup),
-            (insert_crater_grid,)
+            (
+                insert_crater_grid,
+                apply_deferred,
+                insert_exploration::<AtCrater>,
+            )
                 .chain()
                 .in_set(FinishSetupSet::SimulationFinishSetup),
         );
 
         app.add_systems(
             OnEnter(AppState::Cleanup),
-            (despawn_model::<Crater>,).in_set(CleanupSet::SimulationCleanup),
+            (
+                despawn_model::<Crater>,
+                despawn_model::<Aphid>,
+                despawn_model::<Plant>,
+            )
+                .in_set(CleanupSet::SimulationCleanup),
+        );
+    }
+}
+
+pub struct CraterSimulationTickPlugin;
+
+impl Plugin for CraterSimulationTickPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            SimulationUpdate,
+            (
+                // NOTE: This applies to every ant, not just those in the crater, but ColonyId only matters in the crater so far.
+                ants_add_colony_id,
+                (ants_add_crater_orientation, ants_add_wander),
+                (crater_scatter_seeds, seeds_sprout, plants_grow),
+                (aphids_make_honeydew, aphids_reproduce),
+                apply_deferred,
+                ants_return_seeds,
+                ants_tandem_run,
+                ants_walk,
+                ants_fight,
+                ants_scout_food,
+                // Ants which pick seeds off plants have no time left to harvest seeds from the ground.
+                (ants_gather_seeds, ants_harvest_seeds).chain(),
+                ants_milk_aphids,
+                // Rivals eat whichever aphids are left unguarded.
+                aphids_get_eaten,
+                ants_deliver_seeds,
+                // Scouts, and their recruits, come and go between the crater and the nest.
+                (ants_enter_nest, ants_leave_nest),
+                ants_claim_territory,
+                ants_explore::<AtCrater>,
+                apply_deferred,
+                ants_recharge_energy::<AtCrater>,
+                ants_initiative::<AtCrater>,
+            )
+                .chain()
+                // Crater ants share components with nest ants so keep them from running alongside one another.
+                .after(ants_initiative::<AtNest>)
+                .run_if(
+                    not(in_state(StoryPlaybackState::Paused)).and_then(is_zone_playing::<AtCrater>),
+                )
+                .in_set(SimulationTickSet::SimulationTick),
         );
     }
 }
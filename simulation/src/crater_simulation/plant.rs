@@ -0,0 +1,201 @@
+use super::crater::{get_crater_nest_position, AtCrater, Crater};
+use crate::{
+    common::{
+        grid::{Grid, GridElements},
+        position::Position,
+    },
+    nest_simulation::element::{commands::ElementCommandsExt, Element, Seed},
+    save::ReflectPersist,
+    story_time::{Season, StoryTime, DEFAULT_TICKS_PER_SECOND, SECONDS_PER_DAY},
+};
+use bevy::prelude::*;
+use bevy_turborand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Seeds left lying in the crater for a day or so take root, unless they're harvested first.
+const SEED_SPROUT_CHANCE: f64 = 0.000001;
+// Plants compete for light and water. The crater can't support more than this.
+const MAX_PLANTS: usize = 20;
+// A sprout takes a day to grow leaves and another couple of days to go to seed.
+const SPROUT_TICKS: isize = SECONDS_PER_DAY * DEFAULT_TICKS_PER_SECOND;
+const GROWTH_TICKS: isize = 2 * SECONDS_PER_DAY * DEFAULT_TICKS_PER_SECOND;
+// Once grown, a plant sets fresh seed every half a day.
+const SEED_TICKS: isize = SECONDS_PER_DAY / 2 * DEFAULT_TICKS_PER_SECOND;
+// Seeds nobody picks fall to the ground, after a few hours, where they're free to sprout.
+const SEED_DROP_CHANCE: f64 = 0.000005;
+// Plants wither over the first few hours of Winter.
+const WINTER_WITHER_CHANCE: f64 = 0.00002;
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Default)]
+pub enum PlantStage {
+    #[default]
+    Sprout,
+    Grown,
+    // Has ripe seeds for foragers to pick.
+    Seeding,
+}
+
+/// Something green growing out of the crater floor. Plants sprout from seeds nobody harvested, grow over a few days, and
+/// go to seed. Their seeds are picked by foragers or fall to the ground beside them. None survive the Winter.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect)]
+#[reflect(Component, Persist)]
+pub struct Plant {
+    stage: PlantStage,
+    // Ticks until the plant reaches its next stage.
+    remaining_ticks: isize,
+}
+
+impl Default for Plant {
+    fn default() -> Self {
+        Self {
+            stage: PlantStage::Sprout,
+            remaining_ticks: SPROUT_TICKS,
+        }
+    }
+}
+
+impl Plant {
+    pub fn stage(&self) -> PlantStage {
+        self.stage
+    }
+
+    pub fn has_seeds(&self) -> bool {
+        self.stage == PlantStage::Seeding
+    }
+
+    /// Grow for a tick. Sprouts grow into plants which then go to seed. Ripe seeds wait until they've been picked.
+    ///
+    /// ```
+    /// use simulation::crater_simulation::plant::{Plant, PlantStage};
+    ///
+    /// let mut plant = Plant::default();
+    /// assert_eq!(plant.stage(), PlantStage::Sprout);
+    ///
+    /// while plant.stage() == PlantStage::Sprout {
+    ///     plant.grow();
+    /// }
+    /// assert_eq!(plant.stage(), PlantStage::Grown);
+    ///
+    /// while !plant.has_seeds() {
+    ///     plant.grow();
+    /// }
+    /// plant.grow();
+    /// assert!(plant.has_seeds());
+    ///
+    /// plant.pick();
+    /// assert_eq!(plant.stage(), PlantStage::Grown);
+    /// ```
+    pub fn grow(&mut self) {
+        if self.has_seeds() {
+            return;
+        }
+
+        self.remaining_ticks -= 1;
+        if self.remaining_ticks > 0 {
+            return;
+        }
+
+        *self = match self.stage {
+            PlantStage::Sprout => Self {
+                stage: PlantStage::Grown,
+                remaining_ticks: GROWTH_TICKS,
+            },
+            PlantStage::Grown | PlantStage::Seeding => Self {
+                stage: PlantStage::Seeding,
+                remaining_ticks: 0,
+            },
+        };
+    }
+
+    /// Take the plant's seeds. It takes a while for the plant to set more.
+    pub fn pick(&mut self) {
+        *self = Self {
+            stage: PlantStage::Grown,
+            remaining_ticks: SEED_TICKS,
+        };
+    }
+}
+
+pub fn register_plant(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<Plant>();
+    app_type_registry.write().register::<PlantStage>();
+}
+
+/// Seeds lying on the crater floor take root, now and then, and grow into plants. Nothing takes root in Winter.
+pub fn seeds_sprout(
+    seeds_query: Query<(Entity, &Position), (With<Seed>, With<AtCrater>)>,
+    plants_query: Query<&Position, (With<Plant>, With<AtCrater>)>,
+    story_time: Res<StoryTime>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    if story_time.season() == Season::Winter {
+        return;
+    }
+
+    let mut plant_positions = plants_query.iter().copied().collect::<Vec<_>>();
+
+    for (seed_entity, position) in seeds_query.iter() {
+        if plant_positions.len() >= MAX_PLANTS {
+            return;
+        }
+
+        if !rng.chance(SEED_SPROUT_CHANCE) || plant_positions.contains(position) {
+            continue;
+        }
+
+        commands.replace_element(*position, Element::Air, seed_entity, AtCrater);
+        commands.spawn((Plant::default(), *position, AtCrater));
+        plant_positions.push(*position);
+    }
+}
+
+/// Plants grow, and go to seed, through Spring, Summer, and Autumn. Ripe seeds left unpicked fall to the ground beside
+/// the plant. Come Winter, plants wither away.
+pub fn plants_grow(
+    mut plants_query: Query<(Entity, &Position, &mut Plant), With<AtCrater>>,
+    crater_query: Query<&Grid, With<Crater>>,
+    grid_elements: GridElements<AtCrater>,
+    story_time: Res<StoryTime>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    let is_winter = story_time.season() == Season::Winter;
+    let grid = crater_query.single();
+    let nest_position = get_crater_nest_position(grid);
+
+    for (plant_entity, position, mut plant) in plants_query.iter_mut() {
+        if is_winter {
+            if rng.chance(WINTER_WITHER_CHANCE) {
+                commands.entity(plant_entity).despawn();
+            }
+
+            continue;
+        }
+
+        plant.grow();
+
+        if !plant.has_seeds() || !rng.chance(SEED_DROP_CHANCE) {
+            continue;
+        }
+
+        let drop_positions = position
+            .get_adjacent_positions()
+            .into_iter()
+            .filter(|drop_position| {
+                grid.is_within_bounds(drop_position)
+                    && *drop_position != nest_position
+                    && grid_elements.is(*drop_position, Element::Air)
+            })
+            .collect::<Vec<_>>();
+
+        let Some(&drop_position) = rng.sample(&drop_positions) else {
+            continue;
+        };
+
+        let air_entity = *grid_elements.entity(drop_position);
+        commands.replace_element(drop_position, Element::Seed, air_entity, AtCrater);
+        plant.pick();
+    }
+}
@@ -0,0 +1,171 @@
+use crate::{
+    common::{grid::Grid, position::Position},
+    content_pack::StoryVignette,
+    external_event::ExternalSimulationEvent,
+    nest_simulation::nest::{AtNest, Nest},
+    settings::Settings,
+    story_time::{StoryTime, DEFAULT_TICKS_PER_SECOND},
+};
+use bevy::prelude::*;
+use bevy_turborand::prelude::*;
+
+// The demo colony is built from the same seed every time so the demo plays out much the same way for everyone.
+pub const DEMO_SEED: u64 = 8_128;
+// Enough workers that there's always someone digging, or foraging, wherever the camera happens to be.
+const DEMO_WORKER_COUNT: isize = 20;
+// The queen lays every half minute, rather than every hour, so there's brood to see before the demo is over.
+const DEMO_BIRTHING_SECONDS: isize = 30;
+// Rain falls as a row of drops spread across the sky.
+const RAIN_DROP_SPACING: isize = 6;
+const DEMO_TUNNEL_DEPTH: isize = 10;
+const DEMO_AIRDROP_AMOUNT: isize = 20;
+
+/// Present while the demo story plays. The demo colony is throwaway, built fresh from the same seed each time, so it's
+/// never saved, archived, or recorded alongside the player's own stories.
+#[derive(Resource, Default)]
+pub struct DemoStory;
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+enum DemoBeatEffect {
+    // The beat is only told, pointing out something the colony is doing on its own.
+    None,
+    Dig,
+    Airdrop,
+    Rain,
+}
+
+/// Something the demo shows off at a set time. Effects are sent as the same external events the action menu sends.
+struct DemoBeat {
+    seconds: isize,
+    title: &'static str,
+    text: &'static str,
+    effect: DemoBeatEffect,
+}
+
+// Everything the demo shows off, in order, within its first five minutes.
+const DEMO_SCRIPT: [DemoBeat; 6] = [
+    DemoBeat {
+        seconds: 2,
+        title: "Welcome to Symbiants",
+        text: "This is a demo colony. Sit back while the camera tours the nest, or look around for yourself. Nothing here is saved.",
+        effect: DemoBeatEffect::None,
+    },
+    DemoBeat {
+        seconds: 30,
+        title: "Digging",
+        text: "A tunnel has been marked for digging. Workers carry the dirt up, grain by grain, and pile it on the surface.",
+        effect: DemoBeatEffect::Dig,
+    },
+    DemoBeat {
+        seconds: 75,
+        title: "Foraging",
+        text: "Food has fallen from the sky. Workers who find it carry it underground and share it with their nestmates.",
+        effect: DemoBeatEffect::Airdrop,
+    },
+    DemoBeat {
+        seconds: 130,
+        title: "Rain",
+        text: "A shower passes over the nest. Water runs down into the tunnels, where it can drown brood, before it soaks away.",
+        effect: DemoBeatEffect::Rain,
+    },
+    DemoBeat {
+        seconds: 190,
+        title: "Brood",
+        text: "The queen has been laying eggs. Nurses keep the larvae which hatch from them fed until they grow into workers.",
+        effect: DemoBeatEffect::None,
+    },
+    DemoBeat {
+        seconds: 270,
+        title: "Your Turn",
+        text: "That's the demo. Return to the main menu whenever you're ready to found a colony of your own.",
+        effect: DemoBeatEffect::None,
+    },
+];
+
+/// How far through the demo's script the story has played.
+#[derive(Resource, Default)]
+pub struct DemoScript {
+    next_beat_index: usize,
+}
+
+/// A colony which has already settled in, and is busy, so there's something to see right away.
+pub fn get_demo_settings(ant_color: Color) -> Settings {
+    let default_settings = Settings::default();
+
+    Settings {
+        ant_color,
+        initial_ant_worker_count: DEMO_WORKER_COUNT,
+        max_birthing_time: DEMO_BIRTHING_SECONDS,
+        // A short tunnel down to her chamber so the queen is settled, and laying, before the demo is over.
+        tunnel_length: default_settings.tunnel_length / 3,
+        ..default_settings
+    }
+}
+
+pub fn initialize_demo_resources(mut commands: Commands) {
+    commands.init_resource::<DemoScript>();
+}
+
+/// The demo's seed is only meant for the demo. Stories started afterward are seeded randomly, as usual.
+pub fn remove_demo_resources(mut commands: Commands) {
+    commands.remove_resource::<DemoStory>();
+    commands.remove_resource::<DemoScript>();
+    commands.insert_resource(GlobalRng::new());
+}
+
+/// Play each beat of the demo's script once its time comes. Beats are announced just like content events.
+pub fn demo_script_tick(
+    mut demo_script: ResMut<DemoScript>,
+    story_time: Res<StoryTime>,
+    nest_query: Query<(&Grid, &Nest)>,
+    mut external_simulation_events: ResMut<Events<ExternalSimulationEvent<AtNest>>>,
+    mut story_vignettes: EventWriter<StoryVignette>,
+) {
+    let Some(beat) = DEMO_SCRIPT.get(demo_script.next_beat_index) else {
+        return;
+    };
+
+    if story_time.elapsed_ticks() < beat.seconds * DEFAULT_TICKS_PER_SECOND {
+        return;
+    }
+
+    demo_script.next_beat_index += 1;
+
+    story_vignettes.send(StoryVignette {
+        title: Some(beat.title.to_string()),
+        text: beat.text.to_string(),
+    });
+
+    let (grid, nest) = nest_query.single();
+
+    match beat.effect {
+        DemoBeatEffect::None => {}
+        DemoBeatEffect::Dig => {
+            let x = grid.width() / 4;
+            let positions = (1..=DEMO_TUNNEL_DEPTH)
+                .map(|depth| Position::new(x, nest.surface_level() + depth))
+                .collect();
+
+            external_simulation_events.send(ExternalSimulationEvent::MarkDig {
+                zone: AtNest,
+                positions,
+            });
+        }
+        DemoBeatEffect::Airdrop => {
+            // The top row of the nest is always open sky.
+            external_simulation_events.send(ExternalSimulationEvent::AirdropFood {
+                zone: AtNest,
+                center: Position::new(grid.width() / 2, 0),
+                amount: DEMO_AIRDROP_AMOUNT,
+            });
+        }
+        DemoBeatEffect::Rain => {
+            for x in (0..grid.width()).step_by(RAIN_DROP_SPACING as usize) {
+                external_simulation_events.send(ExternalSimulationEvent::SpawnWater(
+                    Position::new(x, 0),
+                    AtNest,
+                ));
+            }
+        }
+    }
+}
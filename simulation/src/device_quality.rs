@@ -0,0 +1,146 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// A few hundred ticks is enough to tell a phone from a desktop without keeping the player waiting on first run.
+pub const DEVICE_QUALITY_BENCHMARK_TICKS: usize = 300;
+// The benchmark is spread across frames, this many ticks at a time, so the main menu stays responsive while it runs.
+pub const DEVICE_QUALITY_BENCHMARK_TICKS_PER_FRAME: usize = 10;
+// Frames drawn while the main menu first opens are timed, too, because a fast simulation is no good if drawing stutters.
+pub const DEVICE_QUALITY_SAMPLE_FRAMES: usize = 30;
+
+// Devices which simulate fewer ticks per second than this, in the benchmark's crowded colony, can't keep up at Medium.
+const LOW_QUALITY_MAX_TICKS_PER_SECOND: f32 = 600.0;
+// Devices which simulate at least this many ticks per second have headroom to spare.
+const HIGH_QUALITY_MIN_TICKS_PER_SECOND: f32 = 2_000.0;
+// Drawing slower than 30 frames per second is noticeably choppy. Drawing at nearly 60 is smooth.
+const LOW_QUALITY_MIN_FRAME_SECONDS: f32 = 1.0 / 30.0;
+const HIGH_QUALITY_MAX_FRAME_SECONDS: f32 = 1.0 / 55.0;
+
+// Ant names are only legible once zoomed in far enough that a tile spans a couple dozen pixels.
+const LABEL_MAX_PROJECTION_SCALE: f32 = 0.05;
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Serialize, Deserialize)]
+pub enum QualityLevel {
+    Low,
+    Medium,
+    High,
+}
+
+impl QualityLevel {
+    pub const ALL: [QualityLevel; 3] =
+        [QualityLevel::Low, QualityLevel::Medium, QualityLevel::High];
+
+    /// Share of flourishes, i.e. speech bubbles and emotes, which are shown. They're purely cosmetic so they're the
+    /// first thing to go on devices which are struggling.
+    pub fn flourish_density(&self) -> f32 {
+        match self {
+            QualityLevel::Low => 0.25,
+            QualityLevel::Medium => 0.75,
+            QualityLevel::High => 1.0,
+        }
+    }
+
+    /// Whether ant names are drawn at the camera's current zoom. Text is costly to draw so only the best devices draw
+    /// it when it's too small to read, and the weakest don't draw it at all.
+    ///
+    /// ```
+    /// use simulation::device_quality::QualityLevel;
+    ///
+    /// // Zoomed all the way in.
+    /// assert!(!QualityLevel::Low.is_label_shown(0.01));
+    /// assert!(QualityLevel::Medium.is_label_shown(0.01));
+    /// assert!(QualityLevel::High.is_label_shown(0.01));
+    ///
+    /// // Zoomed out to see the whole nest.
+    /// assert!(!QualityLevel::Medium.is_label_shown(0.2));
+    /// assert!(QualityLevel::High.is_label_shown(0.2));
+    /// ```
+    pub fn is_label_shown(&self, projection_scale: f32) -> bool {
+        match self {
+            QualityLevel::Low => false,
+            QualityLevel::Medium => projection_scale <= LABEL_MAX_PROJECTION_SCALE,
+            QualityLevel::High => true,
+        }
+    }
+
+    /// Autosaves are spaced further apart on slow devices because snapshotting the world stalls the simulation.
+    pub fn autosave_interval_multiplier(&self) -> isize {
+        match self {
+            QualityLevel::Low => 3,
+            QualityLevel::Medium | QualityLevel::High => 1,
+        }
+    }
+
+    /// The largest nest, or crater, in tiles along each side, which the device is expected to handle smoothly.
+    pub fn max_world_size(&self) -> isize {
+        match self {
+            QualityLevel::Low => 96,
+            QualityLevel::Medium => 144,
+            QualityLevel::High => 256,
+        }
+    }
+}
+
+/// Pick a quality level from how quickly the device simulates, and draws, a colony. Whichever is slower decides.
+///
+/// ```
+/// use simulation::device_quality::{get_detected_quality_level, QualityLevel};
+///
+/// assert_eq!(get_detected_quality_level(5_000.0, 1.0 / 60.0), QualityLevel::High);
+/// assert_eq!(get_detected_quality_level(1_000.0, 1.0 / 60.0), QualityLevel::Medium);
+/// assert_eq!(get_detected_quality_level(400.0, 1.0 / 60.0), QualityLevel::Low);
+///
+/// // A fast simulation doesn't make up for choppy drawing.
+/// assert_eq!(get_detected_quality_level(5_000.0, 1.0 / 20.0), QualityLevel::Low);
+/// ```
+pub fn get_detected_quality_level(ticks_per_second: f32, frame_seconds: f32) -> QualityLevel {
+    if ticks_per_second < LOW_QUALITY_MAX_TICKS_PER_SECOND
+        || frame_seconds > LOW_QUALITY_MIN_FRAME_SECONDS
+    {
+        QualityLevel::Low
+    } else if ticks_per_second >= HIGH_QUALITY_MIN_TICKS_PER_SECOND
+        && frame_seconds <= HIGH_QUALITY_MAX_FRAME_SECONDS
+    {
+        QualityLevel::High
+    } else {
+        QualityLevel::Medium
+    }
+}
+
+/// How much this device can handle. It's measured once, on first run, and kept with the device rather than with a
+/// story so every story told on the device shares it. Players can override what was measured from the settings menu.
+#[derive(Resource, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Default)]
+pub struct DeviceQuality {
+    detected_level: Option<QualityLevel>,
+    chosen_level: Option<QualityLevel>,
+}
+
+impl DeviceQuality {
+    /// Devices which haven't been measured yet are assumed to be middling.
+    pub fn level(&self) -> QualityLevel {
+        self.chosen_level
+            .or(self.detected_level)
+            .unwrap_or(QualityLevel::Medium)
+    }
+
+    pub fn detected_level(&self) -> Option<QualityLevel> {
+        self.detected_level
+    }
+
+    pub fn chosen_level(&self) -> Option<QualityLevel> {
+        self.chosen_level
+    }
+
+    pub fn is_detected(&self) -> bool {
+        self.detected_level.is_some()
+    }
+
+    pub fn set_detected_level(&mut self, detected_level: QualityLevel) {
+        self.detected_level = Some(detected_level);
+    }
+
+    /// Choose a level by hand, or pass None to go back to the level which was measured.
+    pub fn set_chosen_level(&mut self, chosen_level: Option<QualityLevel>) {
+        self.chosen_level = chosen_level;
+    }
+}
@@ -1,31 +1,165 @@
 use crate::{
-    common::{grid::GridElements, position::Position, Zone},
+    common::{
+        airdrop::spawn_airdrop,
+        entity_budget::EntityBudget,
+        grid::{Grid, GridElements},
+        position::Position,
+        Zone,
+    },
+    content_pack::ContentPacks,
     crater_simulation::crater::AtCrater,
     nest_simulation::{
         ant::commands::AntCommandsExt,
+        ant::curiosity::PlayerCursor,
+        ant::dig_order::DigOrder,
+        ant::nuptial_flight::Winged,
+        ant::order::{can_receive_order, Order, OrderTask},
         ant::{
-            Angle, AntColor, AntInventory, AntName, AntOrientation, AntRole, Dead, Facing,
-            Initiative,
+            Angle, AntColor, AntInventory, AntName, AntOrientation, AntRole, Facing, Initiative,
         },
-        element::{commands::ElementCommandsExt, Element},
+        cave_in::{cave_in, get_ceiling_span},
+        element::{commands::ElementCommandsExt, Element, FoodKind},
         nest::AtNest,
     },
     settings::Settings,
 };
 use bevy::prelude::*;
-use bevy_turborand::GlobalRng;
+use bevy_turborand::{DelegatedRng, GlobalRng};
+use serde::{Deserialize, Serialize};
 
-#[derive(Event, PartialEq, Copy, Clone, Debug)]
+/// Requests, typically from the user, to change the simulation. They're processed at the start of the next tick.
+///
+/// ```
+/// use bevy::prelude::*;
+/// use simulation::{
+///     common::position::Position,
+///     external_event::ExternalSimulationEvent,
+///     headless::{create_headless_app, start_headless_story},
+///     nest_simulation::{
+///         element::{Food, FoodKind},
+///         nest::AtNest,
+///     },
+///     settings::Settings,
+///     SimulationUpdate,
+/// };
+///
+/// let mut app = create_headless_app(Settings::default(), 1);
+/// assert!(start_headless_story(&mut app));
+///
+/// fn count_sugar(app: &mut App) -> usize {
+///     app.world
+///         .query::<&FoodKind>()
+///         .iter(&app.world)
+///         .filter(|food_kind| **food_kind == FoodKind::Sugar)
+///         .count()
+/// }
+///
+/// let initial_food_count = app.world.query::<&Food>().iter(&app.world).count();
+/// let initial_sugar_count = count_sugar(&mut app);
+///
+/// // The top row of the nest is always open sky.
+/// app.world
+///     .resource_mut::<Events<ExternalSimulationEvent<AtNest>>>()
+///     .send(ExternalSimulationEvent::SpawnFood(
+///         Position::new(10, 0),
+///         FoodKind::Sugar,
+///         AtNest,
+///     ));
+/// app.world.run_schedule(SimulationUpdate);
+///
+/// let food_count = app.world.query::<&Food>().iter(&app.world).count();
+/// assert_eq!(food_count, initial_food_count + 1);
+/// assert_eq!(count_sugar(&mut app), initial_sugar_count + 1);
+/// ```
+#[derive(Event, PartialEq, Clone, Debug, Serialize, Deserialize)]
 pub enum ExternalSimulationEvent<Z: Zone> {
     DespawnElement(Position, Z),
-    SpawnFood(Position, Z),
+    SpawnFood(Position, FoodKind, Z),
+    // Prey is too heavy for one ant. It takes a group of workers to carry it home.
+    SpawnPrey(Position, Z),
     SpawnDirt(Position, Z),
     SpawnSand(Position, Z),
+    SpawnWater(Position, Z),
+    // Stone can't be dug. Ants have to find a way around it.
+    SpawnStone(Position, Z),
     KillAnt(Position, Z),
     SpawnWorkerAnt(Position, Z),
+    SpawnSoldierAnt(Position, Z),
     DespawnWorkerAnt(Position, Z),
+    // Airdrops are a mix of every kind of food.
+    AirdropFood {
+        zone: Z,
+        center: Position,
+        amount: isize,
+    },
+    // Stress testing tool. Workers are scattered across random positions where they have something to stand on.
+    SpawnWorkerAnts {
+        zone: Z,
+        amount: isize,
+    },
+    // Placing only fills empty space, removing only clears occupied space, and the rest of the positions are ignored.
+    PaintElements {
+        zone: Z,
+        positions: Vec<Position>,
+        element: Element,
+        // Which kind of food to place when painting food.
+        food_kind: Option<FoodKind>,
+    },
+    OrderAnts {
+        zone: Z,
+        ant_entities: Vec<Entity>,
+        task: OrderTask,
+    },
+    // Only dirt can be marked for digging. Marking, or cancelling, anything else is ignored.
+    MarkDig {
+        zone: Z,
+        positions: Vec<Position>,
+    },
+    CancelDig {
+        zone: Z,
+        positions: Vec<Position>,
+    },
+    // Sandbox tool. The ceiling above the given open space caves in no matter how well supported it is.
+    TriggerCollapse(Position, Z),
+    // A queen still on the wing lands on whatever is beneath the given position.
+    Land(Position, Z),
+    // Where the player is pointing. There's only the one cursor, in the nest, so it's only sent for the nest.
+    PointAt(Position, Z),
+}
+
+impl<Z: Zone> ExternalSimulationEvent<Z> {
+    /// Where a sandbox tool would reach into the world. Empty for events which are part of ordinary play, e.g. marking
+    /// dirt for digging, because they leave it to the ants to do the work.
+    fn get_sandbox_positions(&self) -> Vec<Position> {
+        match self {
+            ExternalSimulationEvent::DespawnElement(position, _)
+            | ExternalSimulationEvent::SpawnFood(position, _, _)
+            | ExternalSimulationEvent::SpawnPrey(position, _)
+            | ExternalSimulationEvent::SpawnDirt(position, _)
+            | ExternalSimulationEvent::SpawnSand(position, _)
+            | ExternalSimulationEvent::SpawnWater(position, _)
+            | ExternalSimulationEvent::SpawnStone(position, _)
+            | ExternalSimulationEvent::KillAnt(position, _)
+            | ExternalSimulationEvent::SpawnWorkerAnt(position, _)
+            | ExternalSimulationEvent::SpawnSoldierAnt(position, _)
+            | ExternalSimulationEvent::DespawnWorkerAnt(position, _)
+            | ExternalSimulationEvent::TriggerCollapse(position, _) => vec![*position],
+            ExternalSimulationEvent::AirdropFood { center, .. } => vec![*center],
+            ExternalSimulationEvent::PaintElements { positions, .. } => positions.clone(),
+            ExternalSimulationEvent::SpawnWorkerAnts { .. }
+            | ExternalSimulationEvent::OrderAnts { .. }
+            | ExternalSimulationEvent::MarkDig { .. }
+            | ExternalSimulationEvent::CancelDig { .. }
+            | ExternalSimulationEvent::Land(..)
+            | ExternalSimulationEvent::PointAt(..) => vec![],
+        }
+    }
 }
 
+/// Sent when a request was refused because, in Story Mode, it would have reached too close to the queen.
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+pub struct BlockedExternalEvent;
+
 pub fn initialize_external_event_resources(mut commands: Commands) {
     // Calling init_resource prevents Bevy's automatic event cleanup. Need to do it manually.
     commands.init_resource::<Events<ExternalSimulationEvent<AtNest>>>();
@@ -40,20 +174,55 @@ pub fn remove_external_event_resources(mut commands: Commands) {
 /// Process user input events at the start of the FixedUpdate simulation loop.
 /// Need to process them manually because they'd be cleared at the end of the next Update
 /// which might occur before the next time FixedUpdate runs.
+/// In Story Mode, sandbox tools can't be used around the queen so the colony's fate is left up to the colony.
 pub fn process_external_event<Z: Zone + Copy>(
     mut external_simulation_events: ResMut<Events<ExternalSimulationEvent<Z>>>,
     mut commands: Commands,
     settings: Res<Settings>,
+    content_packs: Res<ContentPacks>,
     mut rng: ResMut<GlobalRng>,
-    ants_query: Query<(Entity, &Position, &AntRole, &AntInventory), With<Z>>,
+    ants_query: Query<(Entity, &Position, &AntRole), With<Z>>,
     grid_elements: GridElements<Z>,
+    grid_query: Query<&Grid, With<Z>>,
+    entity_budget: Res<EntityBudget>,
+    mut winged_query: Query<&mut Winged, With<Z>>,
+    mut blocked_events: EventWriter<BlockedExternalEvent>,
+    mut player_cursor: Option<ResMut<PlayerCursor>>,
 ) {
+    let queen_position = ants_query
+        .iter()
+        .find(|(_, _, role)| **role == AntRole::Queen)
+        .map(|(_, position, _)| *position);
+
     for event in external_simulation_events.drain() {
+        let is_near_queen = queen_position.map_or(false, |queen_position| {
+            event.get_sandbox_positions().iter().any(|position| {
+                position.distance(&queen_position) <= settings.queen_protection_radius
+            })
+        });
+
+        if settings.is_story_mode && is_near_queen {
+            blocked_events.send(BlockedExternalEvent);
+            continue;
+        }
+
         match event {
-            ExternalSimulationEvent::SpawnFood(grid_position, zone) => {
+            ExternalSimulationEvent::SpawnFood(grid_position, food_kind, zone) => {
                 if grid_elements.is(grid_position, Element::Air) {
                     let entity = grid_elements.entity(grid_position);
-                    commands.replace_element(grid_position, Element::Food, *entity, zone);
+                    commands.replace_element_with(
+                        grid_position,
+                        Element::Food,
+                        *entity,
+                        food_kind,
+                        zone,
+                    );
+                }
+            }
+            ExternalSimulationEvent::SpawnPrey(grid_position, zone) => {
+                if grid_elements.is(grid_position, Element::Air) {
+                    let entity = grid_elements.entity(grid_position);
+                    commands.replace_element(grid_position, Element::Prey, *entity, zone);
                 }
             }
             ExternalSimulationEvent::SpawnSand(grid_position, zone) => {
@@ -62,6 +231,18 @@ pub fn process_external_event<Z: Zone + Copy>(
                     commands.replace_element(grid_position, Element::Sand, *entity, zone);
                 }
             }
+            ExternalSimulationEvent::SpawnWater(grid_position, zone) => {
+                if grid_elements.is(grid_position, Element::Air) {
+                    let entity = grid_elements.entity(grid_position);
+                    commands.replace_element(grid_position, Element::Water, *entity, zone);
+                }
+            }
+            ExternalSimulationEvent::SpawnStone(grid_position, zone) => {
+                if grid_elements.is(grid_position, Element::Air) {
+                    let entity = grid_elements.entity(grid_position);
+                    commands.replace_element(grid_position, Element::Stone, *entity, zone);
+                }
+            }
             ExternalSimulationEvent::SpawnDirt(grid_position, zone) => {
                 if grid_elements.is(grid_position, Element::Air) {
                     let entity = grid_elements.entity(grid_position);
@@ -75,41 +256,195 @@ pub fn process_external_event<Z: Zone + Copy>(
                 }
             }
             ExternalSimulationEvent::SpawnWorkerAnt(grid_position, zone) => {
-                if grid_elements.is(grid_position, Element::Air) {
+                // The user is warned about the budget being exceeded so it's OK to silently ignore their request here.
+                if grid_elements.is(grid_position, Element::Air) && !entity_budget.is_exceeded() {
                     commands.spawn_ant(
                         grid_position,
                         AntColor(settings.ant_color),
                         AntOrientation::new(Facing::random(&mut rng.reborrow()), Angle::Zero),
-                        AntInventory::default(),
+                        AntInventory::new(AntRole::Worker),
                         AntRole::Worker,
-                        AntName::random(&mut rng.reborrow()),
+                        AntName::random(&mut rng.reborrow(), &content_packs),
+                        Initiative::new(&mut rng.reborrow()),
+                        zone,
+                    );
+                }
+            }
+            ExternalSimulationEvent::SpawnWorkerAnts { zone, amount } => {
+                if entity_budget.is_exceeded() {
+                    continue;
+                }
+
+                let positions = get_random_standing_positions(
+                    amount,
+                    grid_query.single(),
+                    &grid_elements,
+                    &mut rng.reborrow(),
+                );
+
+                for grid_position in positions {
+                    commands.spawn_ant(
+                        grid_position,
+                        AntColor(settings.ant_color),
+                        AntOrientation::new(Facing::random(&mut rng.reborrow()), Angle::Zero),
+                        AntInventory::new(AntRole::Worker),
+                        AntRole::Worker,
+                        AntName::random(&mut rng.reborrow(), &content_packs),
+                        Initiative::new(&mut rng.reborrow()),
+                        zone,
+                    );
+                }
+            }
+            ExternalSimulationEvent::SpawnSoldierAnt(grid_position, zone) => {
+                if grid_elements.is(grid_position, Element::Air) && !entity_budget.is_exceeded() {
+                    commands.spawn_ant(
+                        grid_position,
+                        AntColor(settings.ant_color),
+                        AntOrientation::new(Facing::random(&mut rng.reborrow()), Angle::Zero),
+                        AntInventory::new(AntRole::Soldier),
+                        AntRole::Soldier,
+                        AntName::random(&mut rng.reborrow(), &content_packs),
                         Initiative::new(&mut rng.reborrow()),
                         zone,
                     );
                 }
             }
             ExternalSimulationEvent::KillAnt(grid_position, zone) => {
-                if let Some((entity, _, _, _)) = ants_query
+                if let Some((entity, _, _)) = ants_query
                     .iter()
-                    .find(|(_, &position, _, _)| position == grid_position)
+                    .find(|(_, &position, _)| position == grid_position)
                 {
-                    commands.entity(entity).insert(Dead).remove::<Initiative>();
+                    commands.kill_ant(entity);
                 }
             }
             ExternalSimulationEvent::DespawnWorkerAnt(grid_position, zone) => {
-                if let Some((ant_entity, _, _, inventory)) =
-                    ants_query.iter().find(|(_, &position, &role, _)| {
-                        position == grid_position && role == AntRole::Worker
+                if let Some((ant_entity, _, _)) = ants_query.iter().find(|(_, &position, &role)| {
+                    position == grid_position && role == AntRole::Worker
+                }) {
+                    commands.despawn_ant(ant_entity, zone);
+                }
+            }
+            ExternalSimulationEvent::PaintElements {
+                zone,
+                positions,
+                element,
+                food_kind,
+            } => {
+                let replacements = positions
+                    .into_iter()
+                    .filter_map(|position| {
+                        let entity = grid_elements.get_entity(position)?;
+                        let is_air = grid_elements.is(position, Element::Air);
+                        let is_replaceable = (element == Element::Air) != is_air;
+
+                        is_replaceable.then_some((position, *entity))
                     })
-                {
-                    // TODO: This should happen automatically when an ant is despawned
-                    if let Some(element_entity) = &inventory.0 {
-                        commands.entity(*element_entity).despawn();
+                    .collect::<Vec<_>>();
+
+                match food_kind {
+                    Some(food_kind) => {
+                        commands.replace_elements_with(replacements, element, food_kind, zone)
+                    }
+                    None => commands.replace_elements(replacements, element, zone),
+                }
+            }
+            ExternalSimulationEvent::OrderAnts {
+                zone: _,
+                ant_entities,
+                task,
+            } => {
+                // Ants follow their own instincts in Story Mode. Orders are a sandbox tool.
+                if settings.is_story_mode {
+                    continue;
+                }
+
+                for ant_entity in ant_entities {
+                    if let Ok((_, _, role)) = ants_query.get(ant_entity) {
+                        if can_receive_order(role) {
+                            commands.entity(ant_entity).insert(Order::new(task));
+                        }
+                    }
+                }
+            }
+            ExternalSimulationEvent::MarkDig { zone: _, positions } => {
+                for position in positions {
+                    if grid_elements.is(position, Element::Dirt) {
+                        commands
+                            .entity(*grid_elements.entity(position))
+                            .insert(DigOrder);
                     }
+                }
+            }
+            ExternalSimulationEvent::CancelDig { zone: _, positions } => {
+                for position in positions {
+                    if let Some(element_entity) = grid_elements.get_entity(position) {
+                        commands.entity(*element_entity).remove::<DigOrder>();
+                    }
+                }
+            }
+            ExternalSimulationEvent::TriggerCollapse(grid_position, zone) => {
+                let span = get_ceiling_span(grid_position, &grid_elements);
 
-                    commands.entity(ant_entity).despawn_recursive();
+                cave_in(
+                    &span,
+                    &grid_elements,
+                    ants_query
+                        .iter()
+                        .map(|(ant_entity, &position, _)| (ant_entity, position)),
+                    zone,
+                    &mut commands,
+                );
+            }
+            ExternalSimulationEvent::AirdropFood {
+                zone,
+                center,
+                amount,
+            } => {
+                spawn_airdrop(
+                    center,
+                    amount,
+                    zone,
+                    &grid_elements,
+                    &mut rng.reborrow(),
+                    &mut commands,
+                );
+            }
+            ExternalSimulationEvent::Land(grid_position, _) => {
+                for mut winged in winged_query.iter_mut() {
+                    winged.land(grid_position);
+                }
+            }
+            ExternalSimulationEvent::PointAt(grid_position, _) => {
+                if let Some(player_cursor) = player_cursor.as_mut() {
+                    player_cursor.point_at(grid_position);
                 }
             }
         }
     }
 }
+
+/// Pick up to `amount` random positions where an ant could stand, i.e. air above something solid.
+/// Positions are allowed to repeat so large amounts of ants can be spawned into small nests.
+fn get_random_standing_positions<Z: Zone>(
+    amount: isize,
+    grid: &Grid,
+    grid_elements: &GridElements<Z>,
+    rng: &mut Mut<GlobalRng>,
+) -> Vec<Position> {
+    let standing_positions = (0..grid.height())
+        .flat_map(|y| (0..grid.width()).map(move |x| Position::new(x, y)))
+        .filter(|&position| {
+            grid_elements.is(position, Element::Air)
+                && !grid_elements.is(position + Position::Y, Element::Air)
+                && grid.is_within_bounds(&(position + Position::Y))
+        })
+        .collect::<Vec<_>>();
+
+    if standing_positions.is_empty() {
+        return vec![];
+    }
+
+    (0..amount.max(0))
+        .map(|_| standing_positions[rng.usize(0..standing_positions.len())])
+        .collect()
+}
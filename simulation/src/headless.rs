@@ -0,0 +1,86 @@
+use bevy::prelude::*;
+use bevy_turborand::prelude::*;
+
+use crate::{
+    app_state::AppState, settings::Settings, story_time::StoryPlaybackState, SimulationPlugin,
+};
+
+// Setting up a story takes a handful of frames. Bail out, rather than hang, if something prevents it from starting.
+const MAX_SETUP_FRAMES: usize = 100;
+
+/// Create an app which runs the simulation without a window, rendering, or UI.
+/// Colonies created from the same seed, with the same settings, are identical.
+///
+/// ```
+/// use bevy::prelude::*;
+/// use simulation::{
+///     headless::{create_headless_app, start_headless_story},
+///     nest_simulation::ant::{Ant, Dead},
+///     settings::Settings,
+///     SimulationUpdate,
+/// };
+///
+/// let settings = Settings {
+///     initial_ant_worker_count: 5,
+///     ..default()
+/// };
+///
+/// let mut app = create_headless_app(settings, 1);
+/// assert!(start_headless_story(&mut app));
+///
+/// for _ in 0..10 {
+///     app.world.run_schedule(SimulationUpdate);
+/// }
+///
+/// let alive_ant_count = app
+///     .world
+///     .query_filtered::<(), (With<Ant>, Without<Dead>)>()
+///     .iter(&app.world)
+///     .count();
+///
+/// // The queen, and her workers, are all alive and well.
+/// assert_eq!(alive_ant_count, 6);
+/// ```
+pub fn create_headless_app(settings: Settings, seed: u64) -> App {
+    let mut app = App::new();
+
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        RngPlugin::new().with_rng_seed(seed),
+        SimulationPlugin,
+    ));
+
+    // Settings are only initialized when missing so inserting them first overrides the defaults for a new story.
+    app.insert_resource(settings);
+
+    app.finish();
+    app.cleanup();
+
+    app
+}
+
+/// Create a new story and advance the app until the story is being told.
+/// Run the `SimulationUpdate` schedule directly, rather than updating the app, to advance the simulation a tick at a time.
+/// Returns false if the story failed to start.
+pub fn start_headless_story(app: &mut App) -> bool {
+    // Run BeginSetup and then skip the main menu because there's no save to load nor story mode to select.
+    app.update();
+    app.world
+        .resource_mut::<NextState<AppState>>()
+        .set(AppState::CreateNewStory);
+
+    let mut setup_frames = 0;
+    while *app.world.resource::<State<AppState>>() != AppState::TellStory
+        || *app.world.resource::<State<StoryPlaybackState>>() != StoryPlaybackState::Playing
+    {
+        if setup_frames >= MAX_SETUP_FRAMES {
+            return false;
+        }
+
+        app.update();
+        setup_frames += 1;
+    }
+
+    true
+}
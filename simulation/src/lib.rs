@@ -1,16 +1,29 @@
 pub mod app_state;
+pub mod balance_config;
+pub mod benchmark;
+pub mod broadcast;
 pub mod common;
+pub mod content_pack;
 pub mod crater_simulation;
+pub mod demo;
+pub mod device_quality;
 pub mod external_event;
+pub mod headless;
 pub mod nest_simulation;
+pub mod replay;
+pub mod ron_asset;
 pub mod save;
 pub mod settings;
 pub mod simulation_timestep;
+pub mod soak_test;
 pub mod story_time;
+pub mod tick_profiler;
 
 use self::{
-    app_state::AppState, common::despawn_model,
-    simulation_timestep::run_simulation_update_schedule, story_time::StoryPlaybackState,
+    app_state::{is_simulating, AppState, ViewingRuins},
+    common::despawn_model,
+    simulation_timestep::run_simulation_update_schedule,
+    story_time::StoryPlaybackState,
 };
 use bevy::{
     app::{MainScheduleOrder, RunFixedUpdateLoop},
@@ -18,9 +31,12 @@ use bevy::{
     prelude::*,
 };
 use bevy_save::SavePlugin;
-use common::CommonSimulationPlugin;
-use crater_simulation::{crater::insert_crater_grid, CraterSimulationPlugin};
-use nest_simulation::NestSimulationPlugin;
+use common::{CommonSimulationPlugin, CommonSimulationTickPlugin};
+use crater_simulation::{
+    crater::insert_crater_grid, CraterSimulationPlugin, CraterSimulationTickPlugin,
+};
+use nest_simulation::{NestSimulationPlugin, NestSimulationTickPlugin};
+use save::load_device_quality;
 
 #[derive(ScheduleLabel, Debug, PartialEq, Eq, Clone, Hash)]
 pub struct RunSimulationUpdateLoop;
@@ -55,26 +71,17 @@ pub enum SimulationTickSet {
     Last,
 }
 
-pub struct SimulationPlugin;
+/// Sets up, loads, and tears down stories but never simulates them. Shared by `SimulationPlugin`, which tells stories,
+/// and `ViewOnlySimulationPlugin`, which only shows the ruins of stories which have ended.
+struct SimulationSetupPlugin;
 
-impl Plugin for SimulationPlugin {
+impl Plugin for SimulationSetupPlugin {
     fn build(&self, app: &mut App) {
         // Only want SavePlugin not SavePlugins - just need basic snapshot logic not UI persistence or save/load methods.
         app.add_plugins(SavePlugin);
-        // Be aggressive in preventing ambiguous systems from running in parallel to prevent unintended headaches.
-        app.edit_schedule(SimulationUpdate, |schedule| {
-            schedule.set_build_settings(ScheduleBuildSettings {
-                ambiguity_detection: LogLevel::Error,
-                ..default()
-            });
-        });
 
-        app.add_systems(PreStartup, insert_simulation_schedule);
-        app.init_schedule(RunSimulationUpdateLoop);
-        app.add_systems(
-            RunSimulationUpdateLoop,
-            run_simulation_update_schedule.run_if(in_state(AppState::TellStory)),
-        );
+        // What the device can handle is known before any story begins, and outlasts every story, so it's never removed.
+        app.insert_resource(load_device_quality().unwrap_or_default());
 
         app.add_state::<StoryPlaybackState>();
         // TODO: AppState feels weird to live in Simulation
@@ -90,18 +97,6 @@ impl Plugin for SimulationPlugin {
                 .chain(),
         );
 
-        app.configure_sets(
-            SimulationUpdate,
-            (
-                SimulationTickSet::First,
-                SimulationTickSet::PreSimulationTick,
-                SimulationTickSet::SimulationTick,
-                SimulationTickSet::PostSimulationTick,
-                SimulationTickSet::Last,
-            )
-                .chain(),
-        );
-
         app.configure_sets(
             OnEnter(AppState::Cleanup),
             (
@@ -120,6 +115,65 @@ impl Plugin for SimulationPlugin {
     }
 }
 
+pub struct SimulationPlugin;
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(SimulationSetupPlugin);
+
+        // Be aggressive in preventing ambiguous systems from running in parallel to prevent unintended headaches.
+        app.edit_schedule(SimulationUpdate, |schedule| {
+            schedule.set_build_settings(ScheduleBuildSettings {
+                ambiguity_detection: LogLevel::Error,
+                ..default()
+            });
+        });
+
+        app.add_systems(PreStartup, insert_simulation_schedule);
+        app.init_schedule(RunSimulationUpdateLoop);
+        app.add_systems(
+            RunSimulationUpdateLoop,
+            run_simulation_update_schedule.run_if(is_simulating),
+        );
+
+        app.configure_sets(
+            SimulationUpdate,
+            (
+                SimulationTickSet::First,
+                SimulationTickSet::PreSimulationTick,
+                SimulationTickSet::SimulationTick,
+                SimulationTickSet::PostSimulationTick,
+                SimulationTickSet::Last,
+            )
+                .chain(),
+        );
+
+        app.add_plugins((
+            CommonSimulationTickPlugin,
+            NestSimulationTickPlugin,
+            CraterSimulationTickPlugin,
+        ));
+    }
+}
+
+/// A render-only path for viewing the ruins of a story which has ended. None of the plugins which simulate a story are
+/// added, so nothing about the ruins can change, and the app goes straight to loading the archive rather than
+/// offering a main menu. Bevy can't remove systems at runtime so ruins are viewed in an app of their own.
+pub struct ViewOnlySimulationPlugin {
+    /// The story whose ruins are shown. See `StoryRecord::id`.
+    pub story_id: i64,
+}
+
+impl Plugin for ViewOnlySimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(SimulationSetupPlugin);
+
+        app.insert_resource(ViewingRuins {
+            story_id: self.story_id,
+        });
+    }
+}
+
 pub fn insert_simulation_schedule(mut main_schedule_order: ResMut<MainScheduleOrder>) {
     main_schedule_order.insert_after(RunFixedUpdateLoop, RunSimulationUpdateLoop);
 }
@@ -0,0 +1,89 @@
+use super::{commands::AntCommandsExt, Ant, AntRole, Dead};
+use crate::{
+    nest_simulation::nest::AtNest, save::ReflectPersist, settings::Settings,
+    story_time::DEFAULT_TICKS_PER_SECOND,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// How long an ant has been alive. Ants which outlive their role's lifespan die of old age.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Component, Persist)]
+pub struct Age {
+    ticks: isize,
+}
+
+impl Age {
+    pub fn new(ticks: isize) -> Self {
+        Self { ticks }
+    }
+
+    pub fn ticks(&self) -> isize {
+        self.ticks
+    }
+}
+
+/// Workers take on different work as they age. Young workers stay deep in the nest caring for brood, middle-aged
+/// workers dig, and old workers, with the least life left to lose, forage in the dangerous crater.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum LifeStage {
+    Young,
+    MiddleAged,
+    Old,
+}
+
+/// Which third of its lifespan an ant is in.
+///
+/// ```
+/// use simulation::nest_simulation::ant::age::{get_life_stage, LifeStage};
+///
+/// assert_eq!(get_life_stage(0, 90), LifeStage::Young);
+/// assert_eq!(get_life_stage(30, 90), LifeStage::MiddleAged);
+/// assert_eq!(get_life_stage(89, 90), LifeStage::Old);
+/// ```
+pub fn get_life_stage(age_ticks: isize, max_lifespan_ticks: isize) -> LifeStage {
+    if age_ticks * 3 < max_lifespan_ticks {
+        LifeStage::Young
+    } else if age_ticks * 3 < max_lifespan_ticks * 2 {
+        LifeStage::MiddleAged
+    } else {
+        LifeStage::Old
+    }
+}
+
+/// Queens outlive their workers by a wide margin so a colony sees many generations of workers.
+pub fn get_max_lifespan_ticks(role: &AntRole, settings: &Settings) -> isize {
+    let max_lifespan_seconds = match role {
+        AntRole::Worker | AntRole::Soldier => settings.max_worker_lifespan,
+        AntRole::Queen => settings.max_queen_lifespan,
+    };
+
+    max_lifespan_seconds * DEFAULT_TICKS_PER_SECOND
+}
+
+pub fn register_age(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<Age>();
+}
+
+/// Start aging newly spawned ants.
+/// NOTE: Ants from saves which predate aging are considered newborn when they're loaded.
+pub fn ants_add_age(ants_query: Query<Entity, (With<Ant>, Without<Age>)>, mut commands: Commands) {
+    for ant_entity in ants_query.iter() {
+        commands.entity(ant_entity).insert(Age::default());
+    }
+}
+
+/// Ants die of old age the same way they die of starvation, except there's no collapsing first.
+pub fn ants_age(
+    mut ants_query: Query<(Entity, &mut Age, &AntRole), (With<AtNest>, Without<Dead>)>,
+    settings: Res<Settings>,
+    mut commands: Commands,
+) {
+    for (ant_entity, mut age, role) in ants_query.iter_mut() {
+        age.ticks += 1;
+
+        if age.ticks >= get_max_lifespan_ticks(role, &settings) {
+            commands.kill_ant(ant_entity);
+        }
+    }
+}
@@ -1,18 +1,17 @@
 use crate::{
-    common::position::Position, nest_simulation::nest::AtNest, story_time::DEFAULT_TICKS_PER_SECOND,
+    common::{entity_budget::EntityBudget, position::Position},
+    nest_simulation::{nest::AtNest, population::CarryingCapacity},
+    save::ReflectPersist,
+    story_time::DEFAULT_TICKS_PER_SECOND,
 };
 
-use super::{
-    commands::AntCommandsExt, Angle, AntColor, AntInventory, AntName, AntOrientation, AntRole,
-    Facing, Initiative,
-};
+use super::{commands::AntCommandsExt, AntColor, Initiative};
 
 use bevy::prelude::*;
-use bevy_turborand::GlobalRng;
 use serde::{Deserialize, Serialize};
 
 #[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
-#[reflect(Component)]
+#[reflect(Component, Persist)]
 pub struct Birthing {
     value: f32,
     max: f32,
@@ -35,8 +34,9 @@ impl Birthing {
         self.value
     }
 
-    pub fn tick(&mut self) {
-        self.value = (self.value + self.rate).min(self.max);
+    /// Fertility slows birthing as the colony nears the number of ants it can support.
+    pub fn tick(&mut self, fertility: f32) {
+        self.value = (self.value + self.rate * fertility).min(self.max);
     }
 
     pub fn is_ready(&self) -> bool {
@@ -54,22 +54,15 @@ pub fn register_birthing(app_type_registry: ResMut<AppTypeRegistry>) {
 
 pub fn ants_birthing(
     mut ants_birthing_query: Query<
-        (
-            &mut Birthing,
-            &Position,
-            &AntColor,
-            &AntOrientation,
-            &mut Initiative,
-        ),
+        (&mut Birthing, &Position, &AntColor, &mut Initiative),
         With<AtNest>,
     >,
+    carrying_capacity: Res<CarryingCapacity>,
     mut commands: Commands,
-    mut rng: ResMut<GlobalRng>,
+    entity_budget: Res<EntityBudget>,
 ) {
-    for (mut birthing, position, color, orientation, mut initiative) in
-        ants_birthing_query.iter_mut()
-    {
-        birthing.tick();
+    for (mut birthing, position, color, mut initiative) in ants_birthing_query.iter_mut() {
+        birthing.tick(carrying_capacity.fertility());
 
         if !initiative.can_act() {
             continue;
@@ -78,22 +71,10 @@ pub fn ants_birthing(
         // Once an ant starts giving birth - they're incapacitated and cannot do anything low priority.
         initiative.consume();
 
-        if birthing.is_ready() {
-            // NOTE: As written, this could spawn directly into a piece of dirt/food/etc.
-            // This isn't going to cause the application to panic, but isn't visually appealing, either.
-            // Could introduce a custom command and prevent spawning if the tile is occupied and/or find nearest open tile
-            // but since ants can get covered by sand already (when it falls on them) its low priority.
-            // Spawn worker ant (TODO: egg instead)
-            commands.spawn_ant(
-                orientation.get_behind_position(position),
-                AntColor(color.0),
-                AntOrientation::new(Facing::random(&mut rng.reborrow()), Angle::Zero),
-                AntInventory::default(),
-                AntRole::Worker,
-                AntName::random(&mut rng.reborrow()),
-                Initiative::new(&mut rng.reborrow()),
-                AtNest,
-            );
+        // Hold off on giving birth while over budget. Birthing stays ready so growth resumes once there's room again.
+        if birthing.is_ready() && !entity_budget.is_exceeded() {
+            // The queen lays eggs where she stands. Workers hatch from them once they've been raised.
+            commands.spawn_egg(*position, AntColor(color.0), AtNest);
 
             birthing.reset();
         }
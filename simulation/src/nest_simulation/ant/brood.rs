@@ -0,0 +1,305 @@
+use super::{
+    commands::AntCommandsExt, digestion::Digestion, hunger::Hunger,
+    thermoregulation::get_brood_development_rate, Angle, AntColor, AntInventory, AntName,
+    AntOrientation, AntRole, Dead, Facing, Initiative,
+};
+use crate::{
+    balance_config::BalanceConfig,
+    common::{grid::GridElements, position::Position, Zone},
+    content_pack::ContentPacks,
+    nest_simulation::{
+        colony_status::{ColonyStatus, Heir},
+        element::{commands::ElementCommandsExt, Element},
+        nest::AtNest,
+        temperature::TemperatureField,
+    },
+    save::ReflectPersist,
+    settings::Settings,
+    story_time::DEFAULT_TICKS_PER_SECOND,
+};
+use bevy::prelude::*;
+use bevy_turborand::{DelegatedRng, GlobalRng};
+use serde::{Deserialize, Serialize};
+
+const EGG_MAX_TICKS: isize = 60 * 60 * DEFAULT_TICKS_PER_SECOND; // 1 hour
+const PUPA_MAX_TICKS: isize = 60 * 60 * DEFAULT_TICKS_PER_SECOND; // 1 hour
+
+// Pupae near the end of their development are hatching. This is only of interest to rendering. 2 minutes.
+const PUPA_HATCHING_TICKS: isize = 60 * 2 * DEFAULT_TICKS_PER_SECOND;
+// Expressed on the same scale as Digestion so feeding a larva is the same as regurgitating to an ant.
+const LARVA_MAX_NOURISHMENT: f32 = 100.0;
+
+/// Eggs, larvae, and pupae are all brood. Brood isn't an ant. It can't move, or act, and relies on its nestmates.
+/// Each brood entity has exactly one of `Egg`, `Larva`, or `Pupa`, reflecting its current stage of development.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Component, Persist)]
+pub struct Brood;
+
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect)]
+#[reflect(Component, Persist)]
+pub struct Egg {
+    remaining_ticks: isize,
+}
+
+impl Default for Egg {
+    fn default() -> Self {
+        Self {
+            remaining_ticks: EGG_MAX_TICKS,
+        }
+    }
+}
+
+/// Larvae grow by being fed. Once they've eaten enough they pupate.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Component, Persist)]
+pub struct Larva {
+    nourishment: f32,
+    unfed_ticks: isize,
+}
+
+impl Larva {
+    pub fn nourishment(&self) -> f32 {
+        self.nourishment
+    }
+
+    pub fn is_fully_grown(&self) -> bool {
+        self.nourishment >= LARVA_MAX_NOURISHMENT
+    }
+}
+
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect)]
+#[reflect(Component, Persist)]
+pub struct Pupa {
+    remaining_ticks: isize,
+}
+
+impl Default for Pupa {
+    fn default() -> Self {
+        Self {
+            remaining_ticks: PUPA_MAX_TICKS,
+        }
+    }
+}
+
+impl Pupa {
+    pub fn is_hatching(&self) -> bool {
+        self.remaining_ticks <= PUPA_HATCHING_TICKS
+    }
+}
+
+#[derive(Bundle)]
+pub struct BroodBundle<Z: Zone> {
+    brood: Brood,
+    egg: Egg,
+    position: Position,
+    color: AntColor,
+    zone: Z,
+}
+
+impl<Z: Zone> BroodBundle<Z> {
+    pub fn new(position: Position, color: AntColor, zone: Z) -> Self {
+        Self {
+            brood: Brood,
+            egg: Egg::default(),
+            position,
+            color,
+            zone,
+        }
+    }
+}
+
+pub fn register_brood(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<Brood>();
+    app_type_registry.write().register::<Egg>();
+    app_type_registry.write().register::<Larva>();
+    app_type_registry.write().register::<Pupa>();
+}
+
+/// Eggs hatch into larvae after a while. They don't need any care, but they develop slowly when it's too cold, or too
+/// warm, for comfort.
+pub fn brood_egg_tick(
+    mut eggs_query: Query<(Entity, &Position, &mut Egg), (With<Brood>, With<AtNest>)>,
+    temperature_field_query: Query<&TemperatureField>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    let temperature_field = temperature_field_query.single();
+
+    for (brood_entity, position, mut egg) in eggs_query.iter_mut() {
+        let development_rate = get_brood_development_rate(temperature_field.temperature(*position));
+        if !rng.chance(development_rate.into()) {
+            continue;
+        }
+
+        egg.remaining_ticks -= 1;
+
+        if egg.remaining_ticks <= 0 {
+            commands
+                .entity(brood_entity)
+                .remove::<Egg>()
+                .insert(Larva::default());
+        }
+    }
+}
+
+/// Ants feed larvae they come across, the same as they would regurgitate for a hungry ant, but larvae only grow on
+/// protein so ants without any protein in their stomach have nothing to give.
+/// Larvae can't turn to face whoever is feeding them so it's enough to be facing, or standing on, the larva.
+/// Workers only feed larvae when they aren't hungry themselves. The queen raises her first brood alone, though,
+/// so she feeds larvae until she's starving.
+/// A colony in decline has no queen to raise brood for so its larvae go unfed.
+pub fn ants_feed_larvae(
+    mut larvae_query: Query<(&Position, &mut Larva), (With<Brood>, With<AtNest>)>,
+    mut ants_query: Query<
+        (
+            &Position,
+            &AntOrientation,
+            &AntRole,
+            &AntInventory,
+            &Hunger,
+            &mut Digestion,
+            &mut Initiative,
+        ),
+        (With<AtNest>, Without<Dead>),
+    >,
+    colony_status: Res<ColonyStatus>,
+    balance_config: Res<BalanceConfig>,
+) {
+    if colony_status.is_declining() {
+        return;
+    }
+
+    for (larva_position, mut larva) in larvae_query.iter_mut() {
+        if larva.is_fully_grown() {
+            continue;
+        }
+
+        let feeder = ants_query.iter_mut().find(
+            |(position, orientation, role, inventory, hunger, digestion, initiative)| {
+                let is_willing = match role {
+                    AntRole::Worker => !hunger.is_hungry(&balance_config.hunger),
+                    AntRole::Queen => !hunger.is_starving(&balance_config.hunger),
+                    AntRole::Soldier => false,
+                };
+
+                is_willing
+                    && digestion.protein() > 0.0
+                    && initiative.can_act()
+                    && inventory.is_empty()
+                    && (*position == larva_position
+                        || orientation.get_ahead_position(position) == *larva_position)
+            },
+        );
+
+        if let Some((_, _, _, _, _, mut feeder_digestion, mut feeder_initiative)) = feeder {
+            let transfer_amount = feeder_digestion.max() * balance_config.appetite.larva_meal;
+            let protein_amount = feeder_digestion.regurgitate_protein(transfer_amount);
+
+            larva.nourishment += protein_amount;
+            larva.unfed_ticks = 0;
+            feeder_initiative.consume();
+        }
+    }
+}
+
+/// Larvae which have eaten enough spin a cocoon and pupate. Larvae which go unfed for too long starve, and their remains
+/// are left as waste for the workers to clear away. Pupae don't eat.
+/// Larvae raised in a crowded nursery compete for their nurses' attention. They go hungry faster than those with
+/// room to themselves, and are more likely to starve, so an overcrowded nursery raises fewer ants.
+pub fn brood_larva_tick(
+    mut larvae_query: Query<(Entity, &Position, &mut Larva), (With<Brood>, With<AtNest>)>,
+    brood_query: Query<&Position, (With<Brood>, With<AtNest>)>,
+    grid_elements: GridElements<AtNest>,
+    balance_config: Res<BalanceConfig>,
+    mut commands: Commands,
+) {
+    let population_balance = &balance_config.population;
+
+    for (brood_entity, position, mut larva) in larvae_query.iter_mut() {
+        if larva.is_fully_grown() {
+            commands
+                .entity(brood_entity)
+                .remove::<Larva>()
+                .insert(Pupa::default());
+
+            continue;
+        }
+
+        let nursery_size = brood_query
+            .iter()
+            .filter(|brood_position| {
+                position.distance(brood_position) <= population_balance.nursery_radius
+            })
+            .count();
+
+        larva.unfed_ticks += if nursery_size > population_balance.nursery_crowding {
+            2
+        } else {
+            1
+        };
+
+        if larva.unfed_ticks
+            >= balance_config.appetite.larva_starvation_seconds * DEFAULT_TICKS_PER_SECOND
+        {
+            commands.entity(brood_entity).despawn();
+
+            if grid_elements.is(*position, Element::Air) {
+                commands.replace_element(
+                    *position,
+                    Element::Waste,
+                    *grid_elements.entity(*position),
+                    AtNest,
+                );
+            }
+        }
+    }
+}
+
+/// Pupae hatch into workers once they've finished developing. Some hatch into soldiers instead. The colony's heir
+/// doesn't hatch. It emerges as the new queen once the succession is over.
+/// Like eggs, pupae develop slowly when it's too cold, or too warm, for comfort.
+pub fn brood_pupa_tick(
+    mut pupae_query: Query<
+        (Entity, &Position, &AntColor, &mut Pupa),
+        (With<Brood>, With<AtNest>, Without<Heir>),
+    >,
+    temperature_field_query: Query<&TemperatureField>,
+    settings: Res<Settings>,
+    content_packs: Res<ContentPacks>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    let temperature_field = temperature_field_query.single();
+
+    for (brood_entity, position, color, mut pupa) in pupae_query.iter_mut() {
+        let development_rate = get_brood_development_rate(temperature_field.temperature(*position));
+        if !rng.chance(development_rate.into()) {
+            continue;
+        }
+
+        pupa.remaining_ticks -= 1;
+
+        if pupa.remaining_ticks > 0 {
+            continue;
+        }
+
+        commands.entity(brood_entity).despawn();
+
+        let role = if rng.chance(settings.soldier_spawn_ratio.into()) {
+            AntRole::Soldier
+        } else {
+            AntRole::Worker
+        };
+
+        commands.spawn_ant(
+            *position,
+            AntColor(color.0),
+            AntOrientation::new(Facing::random(&mut rng.reborrow()), Angle::Zero),
+            AntInventory::new(role),
+            role,
+            AntName::random(&mut rng.reborrow(), &content_packs),
+            Initiative::new(&mut rng.reborrow()),
+            AtNest,
+        );
+    }
+}
@@ -0,0 +1,316 @@
+use super::{commands::AntCommandsExt, AntInventory, AntOrientation, AntRole, Dead, Initiative};
+use crate::{
+    common::{grid::GridElements, position::Position, reservation::ElementReservations},
+    nest_simulation::{
+        element::{definition::ElementDefinitions, Element},
+        nest::AtNest,
+    },
+    save::ReflectPersist,
+};
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+    utils::HashMap,
+};
+use bevy_turborand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Ants waiting beside prey for help to arrive might give up, each turn, and go back to what they were doing.
+const GATHERING_GIVE_UP_CHANCE: f32 = 0.02;
+
+/// Heavy elements, like prey, are carried by a group of ants working together. One ant, the leader, holds onto the
+/// element and goes wherever it would go if it were carrying the element alone. The rest of the group hold on too and
+/// move in lockstep with the leader.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect)]
+#[reflect(Component, MapEntities, Persist)]
+pub enum CarryGroup {
+    // Waiting beside a heavy element for enough help to arrive to lift it.
+    Gathering(Entity),
+    // Carrying a heavy element alongside the given leader. The leader belongs to its own group.
+    Carrying(Entity),
+}
+
+impl Default for CarryGroup {
+    fn default() -> Self {
+        CarryGroup::Gathering(Entity::PLACEHOLDER)
+    }
+}
+
+impl MapEntities for CarryGroup {
+    fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+        match self {
+            CarryGroup::Gathering(entity) | CarryGroup::Carrying(entity) => {
+                *entity = entity_mapper.get_or_reserve(*entity);
+            }
+        }
+    }
+}
+
+impl CarryGroup {
+    pub fn is_leader(&self, ant_entity: Entity) -> bool {
+        *self == CarryGroup::Carrying(ant_entity)
+    }
+
+    /// Carrying alongside a leader, rather than leading or waiting for help to arrive.
+    pub fn is_helping(&self, ant_entity: Entity) -> bool {
+        matches!(*self, CarryGroup::Carrying(leader_entity) if leader_entity != ant_entity)
+    }
+
+    /// Gathering ants, and those carrying alongside a leader, don't decide where to go for themselves.
+    pub fn is_follower(&self, ant_entity: Entity) -> bool {
+        !self.is_leader(ant_entity)
+    }
+}
+
+/// Groups fall apart when the heavy element they were gathered around has gone, or once their leader has put it down.
+pub fn carry_groups_disband(
+    ants_query: Query<(Entity, &CarryGroup, &Position, &AntInventory, Option<&Dead>), With<AtNest>>,
+    elements_query: Query<&Position, With<Element>>,
+    inventory_elements_query: Query<&Element>,
+    grid_elements: GridElements<AtNest>,
+    mut commands: Commands,
+) {
+    for (ant_entity, carry_group, position, _, dead) in ants_query.iter() {
+        let is_together = match *carry_group {
+            CarryGroup::Gathering(element_entity) => elements_query
+                .get(element_entity)
+                .map_or(false, |element_position| {
+                    position.distance(element_position) <= 1
+                }),
+            CarryGroup::Carrying(leader_entity) => {
+                ants_query
+                    .get(leader_entity)
+                    .map_or(false, |(_, _, _, inventory, dead)| {
+                        dead.is_none()
+                            && is_carrying_heavy(
+                                inventory,
+                                &inventory_elements_query,
+                                &grid_elements,
+                            )
+                    })
+            }
+        };
+
+        if dead.is_some() || !is_together {
+            commands.entity(ant_entity).remove::<CarryGroup>();
+        }
+    }
+}
+
+/// Once enough ants have gathered around a heavy element, one of them lifts it and leads the rest of the group home.
+/// It takes as many ants, leader included, as the element weighs.
+pub fn carry_groups_lift(
+    mut ants_query: Query<(Entity, &Initiative, &mut CarryGroup), (With<AtNest>, Without<Dead>)>,
+    elements_query: Query<(&Position, &Element)>,
+    element_definitions: Res<ElementDefinitions>,
+    mut element_reservations: ResMut<ElementReservations>,
+    mut commands: Commands,
+) {
+    let mut gatherings: HashMap<Entity, Vec<(Entity, bool)>> = HashMap::default();
+
+    for (ant_entity, initiative, carry_group) in ants_query.iter() {
+        if let CarryGroup::Gathering(element_entity) = *carry_group {
+            gatherings
+                .entry(element_entity)
+                .or_default()
+                .push((ant_entity, initiative.can_act()));
+        }
+    }
+
+    for (element_entity, gatherers) in gatherings {
+        let Ok((element_position, element)) = elements_query.get(element_entity) else {
+            continue;
+        };
+
+        if gatherers.len() < element_definitions.get(*element).weight() {
+            continue;
+        }
+
+        // Whoever is ready to act leads. Ties are broken consistently so the same ant leads no matter the query order.
+        let leader_entity = gatherers
+            .iter()
+            .filter(|(_, can_act)| *can_act)
+            .map(|(ant_entity, _)| *ant_entity)
+            .min();
+
+        let Some(leader_entity) = leader_entity else {
+            continue;
+        };
+
+        if !element_reservations.try_reserve(element_entity, leader_entity) {
+            continue;
+        }
+
+        commands.lift(leader_entity, *element_position, element_entity, AtNest);
+
+        for (ant_entity, _) in gatherers {
+            if let Ok((_, _, mut carry_group)) = ants_query.get_mut(ant_entity) {
+                *carry_group = CarryGroup::Carrying(leader_entity);
+            }
+        }
+    }
+}
+
+/// Followers spend their turns holding on, rather than heading off on their own, and sometimes tire of waiting for help.
+/// Leaders who've lost their help can't carry on alone. They put the element down, if there's room, and otherwise wait.
+pub fn carry_groups_hold(
+    mut ants_query: Query<
+        (
+            Entity,
+            &mut Initiative,
+            &CarryGroup,
+            &Position,
+            &AntOrientation,
+            &AntInventory,
+        ),
+        (With<AtNest>, Without<Dead>),
+    >,
+    elements_query: Query<&Element>,
+    grid_elements: GridElements<AtNest>,
+    mut rng: ResMut<GlobalRng>,
+    mut element_reservations: ResMut<ElementReservations>,
+    mut commands: Commands,
+) {
+    let mut group_sizes: HashMap<Entity, usize> = HashMap::default();
+
+    for (_, _, carry_group, _, _, _) in ants_query.iter() {
+        if let CarryGroup::Carrying(leader_entity) = *carry_group {
+            *group_sizes.entry(leader_entity).or_default() += 1;
+        }
+    }
+
+    for (ant_entity, mut initiative, carry_group, position, orientation, inventory) in
+        ants_query.iter_mut()
+    {
+        if !initiative.can_act() {
+            continue;
+        }
+
+        if let CarryGroup::Gathering(_) = carry_group {
+            if rng.f32() < GATHERING_GIVE_UP_CHANCE {
+                commands.entity(ant_entity).remove::<CarryGroup>();
+                continue;
+            }
+        }
+
+        if carry_group.is_follower(ant_entity) {
+            initiative.consume();
+            continue;
+        }
+
+        let carriers_needed = inventory
+            .last()
+            .and_then(|element_entity| elements_query.get(element_entity).ok())
+            .map_or(1, |element| grid_elements.definition(*element).weight());
+
+        if group_sizes.get(&ant_entity).copied().unwrap_or_default() >= carriers_needed {
+            continue;
+        }
+
+        // Avoid dropping the element when facing upwards since it'll fall on the group.
+        let ahead_position = orientation.get_ahead_position(position);
+        let air_entity = grid_elements
+            .get_entity(ahead_position)
+            .copied()
+            .filter(|_| {
+                !orientation.is_facing_north() && grid_elements.is(ahead_position, Element::Air)
+            })
+            .filter(|&air_entity| element_reservations.try_reserve(air_entity, ant_entity));
+
+        match air_entity {
+            Some(air_entity) => commands.drop(ant_entity, ahead_position, air_entity, AtNest),
+            None => initiative.consume(),
+        }
+    }
+}
+
+/// Workers happening upon a heavy element stop and wait beside it for help to arrive.
+pub fn ants_join_carry_group(
+    mut ants_query: Query<
+        (
+            Entity,
+            &mut Initiative,
+            &Position,
+            &AntOrientation,
+            &AntRole,
+            &AntInventory,
+        ),
+        (With<AtNest>, Without<CarryGroup>, Without<Dead>),
+    >,
+    grid_elements: GridElements<AtNest>,
+    mut commands: Commands,
+) {
+    for (ant_entity, mut initiative, position, orientation, role, inventory) in
+        ants_query.iter_mut()
+    {
+        if *role != AntRole::Worker || !initiative.can_act() || !inventory.is_empty() {
+            continue;
+        }
+
+        let ahead_position = orientation.get_ahead_position(position);
+        let Some(&element_entity) = grid_elements.get_entity(ahead_position) else {
+            continue;
+        };
+
+        let element = *grid_elements.element(element_entity);
+        if !grid_elements.definition(element).is_heavy() {
+            continue;
+        }
+
+        commands
+            .entity(ant_entity)
+            .insert(CarryGroup::Gathering(element_entity));
+        initiative.consume();
+    }
+}
+
+/// Followers move wherever their leader moved this turn so the whole group stays together on the leader's tile.
+pub fn carry_groups_keep_pace(
+    mut ants_query: Query<(Entity, &CarryGroup, &mut Position, &mut AntOrientation), With<AtNest>>,
+) {
+    let leaders = ants_query
+        .iter()
+        .filter(|(ant_entity, carry_group, _, _)| carry_group.is_leader(*ant_entity))
+        .map(|(ant_entity, _, position, orientation)| (ant_entity, (*position, *orientation)))
+        .collect::<HashMap<_, _>>();
+
+    for (ant_entity, carry_group, mut position, mut orientation) in ants_query.iter_mut() {
+        let CarryGroup::Carrying(leader_entity) = *carry_group else {
+            continue;
+        };
+
+        let Some(&(leader_position, leader_orientation)) = leaders.get(&leader_entity) else {
+            continue;
+        };
+
+        if ant_entity == leader_entity {
+            continue;
+        }
+
+        // Avoid triggering change detection, and needlessly updating the view, for followers which haven't moved.
+        if *position != leader_position {
+            *position = leader_position;
+        }
+
+        if *orientation != leader_orientation {
+            *orientation = leader_orientation;
+        }
+    }
+}
+
+// Inventory items aren't in the grid so they're looked up directly rather than through `grid_elements`.
+fn is_carrying_heavy(
+    inventory: &AntInventory,
+    elements_query: &Query<&Element>,
+    grid_elements: &GridElements<AtNest>,
+) -> bool {
+    inventory
+        .last()
+        .and_then(|element_entity| elements_query.get(element_entity).ok())
+        .map_or(false, |element| {
+            grid_elements.definition(*element).is_heavy()
+        })
+}
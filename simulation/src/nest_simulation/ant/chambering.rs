@@ -3,13 +3,14 @@ use crate::{
     common::{
         grid::{Grid, GridElements},
         position::Position,
+        reservation::ElementReservations,
     },
     nest_simulation::{
         ant::{commands::AntCommandsExt, AntInventory, AntOrientation, Initiative},
-        element::Element,
         nest::{AtNest, Nest},
         pheromone::{commands::PheromoneCommandsExt, Pheromone, PheromoneMap, PheromoneStrength},
     },
+    save::ReflectPersist,
     settings::Settings,
 };
 use bevy::prelude::*;
@@ -17,7 +18,7 @@ use bevy_turborand::{DelegatedRng, GlobalRng};
 use serde::{Deserialize, Serialize};
 
 #[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
-#[reflect(Component)]
+#[reflect(Component, Persist)]
 pub struct Chambering(pub isize);
 
 /// If covered in Chamber pheromone then the following things need to occur:
@@ -42,6 +43,7 @@ pub fn ants_chamber_pheromone_act(
     mut commands: Commands,
     settings: Res<Settings>,
     mut rng: ResMut<GlobalRng>,
+    mut element_reservations: ResMut<ElementReservations>,
 ) {
     for (ant_orientation, inventory, initiative, ant_position, ant_entity, chambering) in
         ants_query.iter()
@@ -51,7 +53,7 @@ pub fn ants_chamber_pheromone_act(
         }
 
         // Safeguard, but not expected to run because shouldn't have Chambering pheromone with full inventory.
-        if inventory.0 != None {
+        if !inventory.is_empty() {
             continue;
         }
 
@@ -68,6 +70,7 @@ pub fn ants_chamber_pheromone_act(
             &position,
             &grid_query,
             &grid_elements,
+            &mut element_reservations,
             &mut commands,
         ) {
             // Subtract 1 because not placing pheromone at ant_position but instead placing it at a position adjacent
@@ -78,7 +81,6 @@ pub fn ants_chamber_pheromone_act(
                     PheromoneStrength::new(chambering.0 - 1, settings.chamber_size),
                 );
             }
-            return;
         }
     }
 }
@@ -100,7 +102,7 @@ pub fn ants_add_chamber_pheromone(
     mut commands: Commands,
 ) {
     for (ant_entity, ant_position, inventory) in ants_query.iter() {
-        if inventory.0 != None {
+        if !inventory.is_empty() {
             continue;
         }
 
@@ -137,7 +139,7 @@ pub fn ants_remove_chamber_pheromone(
     let nest = nest_query.single();
 
     for (entity, position, inventory, chambering) in ants_query.iter_mut() {
-        if inventory.0 != None {
+        if !inventory.is_empty() {
             commands.entity(entity).remove::<Chambering>();
         } else if nest.is_aboveground(position) {
             commands.entity(entity).remove::<Chambering>();
@@ -152,6 +154,7 @@ fn try_dig(
     dig_position: &Position,
     grid_query: &Query<&Grid, With<AtNest>>,
     grid_elements: &GridElements<AtNest>,
+    element_reservations: &mut ResMut<ElementReservations>,
     commands: &mut Commands,
 ) -> bool {
     if !grid_query.single().is_within_bounds(&dig_position) {
@@ -161,7 +164,11 @@ fn try_dig(
     // Check if hitting a solid element and, if so, consider digging through it.
     let element_entity = grid_elements.entity(*dig_position);
     let element = grid_elements.element(*element_entity);
-    if *element == Element::Air {
+    if !grid_elements.definition(*element).is_diggable() {
+        return false;
+    }
+
+    if !element_reservations.try_reserve(*element_entity, *ant_entity) {
         return false;
     }
 
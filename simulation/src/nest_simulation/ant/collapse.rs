@@ -0,0 +1,129 @@
+use super::{
+    commands::AntCommandsExt,
+    digestion::Digestion,
+    hunger::{get_regurgitation_amount, Hunger},
+    AntAteFoodEvent, AntOrientation, AntRole, Dead, Initiative,
+};
+use crate::{
+    balance_config::BalanceConfig, common::position::Position, nest_simulation::nest::AtNest,
+    save::ReflectPersist, story_time::DEFAULT_TICKS_PER_SECOND,
+};
+use bevy::prelude::*;
+use bevy_turborand::GlobalRng;
+use serde::{Deserialize, Serialize};
+
+// How long a starved ant clings to life, waiting to be fed, before it dies.
+const COLLAPSED_MAX_TICKS: isize = 60 * 60 * DEFAULT_TICKS_PER_SECOND; // 1 hour
+
+/// Ants which starve don't die immediately. They collapse, unable to act, and die only if no one feeds them in time.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect)]
+#[reflect(Component, Persist)]
+pub struct Collapsed {
+    remaining_ticks: isize,
+}
+
+impl Default for Collapsed {
+    fn default() -> Self {
+        Self {
+            remaining_ticks: COLLAPSED_MAX_TICKS,
+        }
+    }
+}
+
+impl Collapsed {
+    pub fn remaining_ticks(&self) -> isize {
+        self.remaining_ticks
+    }
+}
+
+/// Workers regurgitate food to collapsed ants they come across, the same as they would for a hungry ant.
+/// Collapsed ants can't turn to face their rescuer so it's enough for the worker to be facing them.
+pub fn ants_revive_collapsed(
+    mut collapsed_ants_query: Query<
+        (Entity, &Position, &mut Digestion),
+        (With<Collapsed>, With<AtNest>, Without<Dead>),
+    >,
+    mut rescuer_ants_query: Query<
+        (
+            Entity,
+            &Position,
+            &AntOrientation,
+            &AntRole,
+            &Hunger,
+            &mut Digestion,
+            &mut Initiative,
+        ),
+        (With<AtNest>, Without<Collapsed>, Without<Dead>),
+    >,
+    mut ant_ate_food_event_writer: EventWriter<AntAteFoodEvent>,
+    balance_config: Res<BalanceConfig>,
+) {
+    for (collapsed_ant_entity, collapsed_ant_position, mut collapsed_ant_digestion) in
+        collapsed_ants_query.iter_mut()
+    {
+        let rescuer = rescuer_ants_query.iter_mut().find(
+            |(
+                rescuer_ant_entity,
+                rescuer_position,
+                rescuer_orientation,
+                role,
+                hunger,
+                _,
+                initiative,
+            )| {
+                *rescuer_ant_entity != collapsed_ant_entity
+                    && **role == AntRole::Worker
+                    && initiative.can_act()
+                    && !hunger.is_hungry(&balance_config.hunger)
+                    && (*rescuer_position == collapsed_ant_position
+                        || rescuer_orientation.get_ahead_position(rescuer_position)
+                            == *collapsed_ant_position)
+            },
+        );
+
+        if let Some((_, _, _, _, _, mut rescuer_digestion, mut rescuer_initiative)) = rescuer {
+            let digestion_transfer_amount = get_regurgitation_amount(
+                &rescuer_digestion,
+                &collapsed_ant_digestion,
+                balance_config.regurgitation_transfer,
+            );
+
+            if digestion_transfer_amount <= 0.0 {
+                continue;
+            }
+
+            rescuer_digestion
+                .regurgitate_to(&mut collapsed_ant_digestion, digestion_transfer_amount);
+            rescuer_initiative.consume();
+
+            ant_ate_food_event_writer.send(AntAteFoodEvent(collapsed_ant_entity));
+        }
+    }
+}
+
+/// Collapsed ants get back up once they've been fed enough to no longer be starved. Otherwise, they die when time runs out.
+pub fn ants_collapsed_tick(
+    mut collapsed_ants_query: Query<
+        (Entity, &mut Collapsed, &Hunger),
+        (With<AtNest>, Without<Dead>),
+    >,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    for (ant_entity, mut collapsed, hunger) in collapsed_ants_query.iter_mut() {
+        if !hunger.is_starved() {
+            commands
+                .entity(ant_entity)
+                .remove::<Collapsed>()
+                .insert(Initiative::new(&mut rng.reborrow()));
+
+            continue;
+        }
+
+        collapsed.remaining_ticks -= 1;
+
+        if collapsed.remaining_ticks <= 0 {
+            commands.kill_ant(ant_entity);
+        }
+    }
+}
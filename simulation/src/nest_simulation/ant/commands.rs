@@ -1,4 +1,5 @@
 use crate::{
+    balance_config::BalanceConfig,
     common::{
         grid::{GridElements, GridElementsMut},
         position::Position,
@@ -6,7 +7,12 @@ use crate::{
     },
     nest_simulation::{
         ant::AntInventory,
-        element::{Element, ElementBundle},
+        element::{
+            definition::ElementDefinitions,
+            hardness::{get_hardness, DigProgress},
+            water::{get_dirt_dig_rate, Moisture},
+            Element, ElementBundle, FoodKind,
+        },
     },
     settings::Settings,
 };
@@ -17,10 +23,36 @@ use bevy::{
 use core::panic;
 
 use super::{
-    digestion::Digestion, hunger::Hunger, Ant, AntBundle, AntColor, AntName, AntOrientation,
-    AntRole, Initiative, InventoryItemBundle,
+    brood::BroodBundle, collapse::Collapsed, digestion::Digestion, drowning::Drowning,
+    energy::Energy, hunger::Hunger, sleep::Asleep, Ant, AntBundle, AntColor, AntName,
+    AntOrientation, AntRole, Dead, Initiative, InventoryItemBundle,
 };
 
+/// Spawn ants, and brood, and have ants interact with elements, through `Commands`.
+///
+/// ```
+/// use bevy::{ecs::system::CommandQueue, prelude::*};
+/// use simulation::{
+///     common::position::Position,
+///     headless::{create_headless_app, start_headless_story},
+///     nest_simulation::{
+///         ant::{brood::Egg, commands::AntCommandsExt, AntColor},
+///         nest::AtNest,
+///     },
+///     settings::Settings,
+/// };
+///
+/// let mut app = create_headless_app(Settings::default(), 1);
+/// assert!(start_headless_story(&mut app));
+///
+/// let mut queue = CommandQueue::default();
+/// let mut commands = Commands::new(&mut queue, &app.world);
+/// commands.spawn_egg(Position::new(10, 10), AntColor(Color::WHITE), AtNest);
+/// queue.apply(&mut app.world);
+///
+/// let egg_count = app.world.query::<&Egg>().iter(&app.world).count();
+/// assert_eq!(egg_count, 1);
+/// ```
 pub trait AntCommandsExt {
     fn spawn_ant<Z: Zone>(
         &mut self,
@@ -33,6 +65,15 @@ pub trait AntCommandsExt {
         initiative: Initiative,
         zone: Z,
     );
+    fn spawn_egg<Z: Zone>(&mut self, position: Position, color: AntColor, zone: Z);
+    /// Every ant which is removed from the simulation should be despawned through here, rather than despawned directly,
+    /// so whatever it was carrying isn't left orphaned.
+    fn despawn_ant<Z: Zone>(&mut self, ant_entity: Entity, zone: Z);
+    /// Every ant which dies should die through here, rather than having `Dead` inserted directly, so it stops acting
+    /// and is no longer considered collapsed, drowning, or asleep.
+    fn kill_ant(&mut self, ant_entity: Entity);
+    /// Lay down whatever the ant is carrying where it stands, or discard it if there's no room.
+    fn release_inventory<Z: Zone>(&mut self, ant_entity: Entity, zone: Z);
     fn dig<Z: Zone>(
         &mut self,
         ant_entity: Entity,
@@ -40,6 +81,13 @@ pub trait AntCommandsExt {
         target_element_entity: Entity,
         zone: Z,
     );
+    fn lift<Z: Zone>(
+        &mut self,
+        ant_entity: Entity,
+        target_position: Position,
+        target_element_entity: Entity,
+        zone: Z,
+    );
     fn drop<Z: Zone>(
         &mut self,
         ant_entity: Entity,
@@ -47,6 +95,16 @@ pub trait AntCommandsExt {
         target_element_entity: Entity,
         zone: Z,
     );
+    /// Work whatever the ant is carrying into `element` in place of the air at the target position, e.g. packing loose
+    /// sand into a dirt wall.
+    fn build_element<Z: Zone>(
+        &mut self,
+        ant_entity: Entity,
+        target_position: Position,
+        target_element_entity: Entity,
+        element: Element,
+        zone: Z,
+    );
 }
 
 impl<'w, 's> AntCommandsExt for Commands<'w, 's> {
@@ -73,6 +131,28 @@ impl<'w, 's> AntCommandsExt for Commands<'w, 's> {
         });
     }
 
+    fn spawn_egg<Z: Zone>(&mut self, position: Position, color: AntColor, zone: Z) {
+        self.add(SpawnEggCommand {
+            position,
+            color,
+            zone,
+        });
+    }
+
+    fn despawn_ant<Z: Zone>(&mut self, ant_entity: Entity, zone: Z) {
+        self.add(DespawnAntCommand { ant_entity, zone });
+    }
+
+    fn kill_ant(&mut self, ant_entity: Entity) {
+        self.entity(ant_entity)
+            .insert(Dead)
+            .remove::<(Initiative, Collapsed, Drowning, Asleep)>();
+    }
+
+    fn release_inventory<Z: Zone>(&mut self, ant_entity: Entity, zone: Z) {
+        self.add(ReleaseInventoryCommand { ant_entity, zone });
+    }
+
     fn dig<Z: Zone>(
         &mut self,
         ant_entity: Entity,
@@ -84,6 +164,24 @@ impl<'w, 's> AntCommandsExt for Commands<'w, 's> {
             ant_entity,
             target_position,
             target_element_entity,
+            can_lift_heavy: false,
+            zone,
+        });
+    }
+
+    /// Dig out an element, heavy or not, on behalf of a group of ants carrying it together.
+    fn lift<Z: Zone>(
+        &mut self,
+        ant_entity: Entity,
+        target_position: Position,
+        target_element_entity: Entity,
+        zone: Z,
+    ) {
+        self.add(DigElementCommand {
+            ant_entity,
+            target_position,
+            target_element_entity,
+            can_lift_heavy: true,
             zone,
         });
     }
@@ -99,6 +197,24 @@ impl<'w, 's> AntCommandsExt for Commands<'w, 's> {
             ant_entity,
             target_position,
             target_element_entity,
+            built_element: None,
+            zone,
+        });
+    }
+
+    fn build_element<Z: Zone>(
+        &mut self,
+        ant_entity: Entity,
+        target_position: Position,
+        target_element_entity: Entity,
+        element: Element,
+        zone: Z,
+    ) {
+        self.add(DropElementCommand {
+            ant_entity,
+            target_position,
+            target_element_entity,
+            built_element: Some(element),
             zone,
         });
     }
@@ -108,12 +224,16 @@ struct DigElementCommand<Z: Zone> {
     ant_entity: Entity,
     target_element_entity: Entity,
     target_position: Position,
+    // Heavy elements are only lifted by the leader of a group of ants carrying it together.
+    can_lift_heavy: bool,
     zone: Z,
 }
 
 // TODO: Confirm that ant and element are adjacent to one another at time action is taken.
 impl<Z: Zone> Command for DigElementCommand<Z> {
     fn apply(self, world: &mut World) {
+        let dig_balance = world.resource::<BalanceConfig>().dig;
+
         let mut system_state: SystemState<GridElements<Z>> = SystemState::new(world);
         let grid_elements = system_state.get(world);
 
@@ -141,6 +261,59 @@ impl<Z: Zone> Command for DigElementCommand<Z> {
             }
         };
 
+        let hardness = get_hardness(element, self.target_position, &grid_elements, &dig_balance);
+
+        let is_heavy = world
+            .resource::<ElementDefinitions>()
+            .get(element)
+            .is_heavy();
+        if is_heavy && !self.can_lift_heavy {
+            info!("Element {:?} is too heavy for one ant to lift.", element);
+            return;
+        }
+
+        let has_room = match world.get::<AntInventory>(self.ant_entity) {
+            Some(inventory) => inventory.has_room(),
+            None => panic!("Failed to get inventory for ant {:?}", self.ant_entity),
+        };
+        if !has_room {
+            info!(
+                "Ant {:?} has no room to carry anything else.",
+                self.ant_entity
+            );
+            return;
+        }
+
+        // Dry dirt is packed hard so less digging gets done each turn.
+        let dig_rate = if element == Element::Dirt {
+            let moisture = world
+                .get::<Moisture>(element_entity)
+                .map_or(0.0, |moisture| moisture.value());
+
+            get_dirt_dig_rate(moisture, dig_balance.dry_dirt_rate)
+        } else {
+            1.0
+        };
+
+        // Hard elements take more than one turn to dig out. The ant spends its turn chipping at it, and tries again next
+        // turn, until it comes loose.
+        let mut dig_progress = world
+            .get::<DigProgress>(element_entity)
+            .copied()
+            .unwrap_or_default();
+
+        if !dig_progress.dig(dig_rate, hardness) {
+            world.entity_mut(element_entity).insert(dig_progress);
+
+            if let Some(mut initiative) = world.get_mut::<Initiative>(self.ant_entity) {
+                initiative.consume();
+            }
+
+            return;
+        }
+
+        let mut food_kind = world.get::<FoodKind>(element_entity).copied();
+
         world.entity_mut(element_entity).despawn();
 
         let air_entity = world
@@ -162,12 +335,24 @@ impl<Z: Zone> Command for DigElementCommand<Z> {
             inventory_element = Element::Sand;
         }
 
+        // Fungus is harvested as food so ants eat, share, and store it just like any other food.
+        // It's rich in protein, which is what larvae need to grow.
+        if inventory_element == Element::Fungus {
+            inventory_element = Element::Food;
+            food_kind = Some(FoodKind::Protein);
+        }
+
         let inventory_item_entity = world
             .spawn(InventoryItemBundle::new(inventory_element))
             .id();
 
+        // Food keeps its kind while it's being carried.
+        if let Some(food_kind) = food_kind {
+            world.entity_mut(inventory_item_entity).insert(food_kind);
+        }
+
         match world.get_mut::<AntInventory>(self.ant_entity) {
-            Some(mut inventory) => inventory.0 = Some(inventory_item_entity),
+            Some(mut inventory) => inventory.push(inventory_item_entity),
             None => panic!("Failed to get inventory for ant {:?}", self.ant_entity),
         };
 
@@ -175,6 +360,11 @@ impl<Z: Zone> Command for DigElementCommand<Z> {
             Some(mut initiative) => initiative.consume(),
             None => panic!("Failed to get initiative for ant {:?}", self.ant_entity),
         };
+
+        let dig_cost = world.resource::<Settings>().energy.dig_cost;
+        if let Some(mut energy) = world.get_mut::<Energy>(self.ant_entity) {
+            energy.exert(dig_cost);
+        }
     }
 }
 
@@ -182,6 +372,8 @@ struct DropElementCommand<Z: Zone> {
     ant_entity: Entity,
     target_element_entity: Entity,
     target_position: Position,
+    // Building works what the ant is carrying into this element rather than setting it down as it is.
+    built_element: Option<Element>,
     zone: Z,
 }
 
@@ -211,22 +403,31 @@ impl<Z: Zone> Command for DropElementCommand<Z> {
             None => panic!("Failed to get inventory for ant {:?}", self.ant_entity),
         };
 
-        let inventory_item_entity = match inventory.0.clone() {
+        let inventory_item_entity = match inventory.last() {
             Some(element_id) => element_id,
             None => panic!("Ant {:?} has no element in inventory", self.ant_entity),
         };
 
-        let element = world.get::<Element>(inventory_item_entity).unwrap();
+        let element = self
+            .built_element
+            .unwrap_or_else(|| *world.get::<Element>(inventory_item_entity).unwrap());
+
+        // Food is set down as the same kind of food it was picked up as. Built elements are something else entirely.
+        let food_kind = self
+            .built_element
+            .is_none()
+            .then(|| world.get::<FoodKind>(inventory_item_entity).copied())
+            .flatten();
 
         // Add element to world.
         let element_entity = world
-            .spawn(ElementBundle::new(
-                *element,
-                self.target_position,
-                self.zone,
-            ))
+            .spawn(ElementBundle::new(element, self.target_position, self.zone))
             .id();
 
+        if let Some(food_kind) = food_kind {
+            world.entity_mut(element_entity).insert(food_kind);
+        }
+
         let mut system_state: SystemState<GridElementsMut<Z>> = SystemState::new(world);
         let mut grid_elements = system_state.get_mut(world);
 
@@ -236,7 +437,7 @@ impl<Z: Zone> Command for DropElementCommand<Z> {
         world.entity_mut(inventory_item_entity).despawn();
 
         match world.get_mut::<AntInventory>(self.ant_entity) {
-            Some(mut inventory) => inventory.0 = None,
+            Some(mut inventory) => inventory.pop(),
             None => panic!("Failed to get inventory for ant {:?}", self.ant_entity),
         };
 
@@ -246,6 +447,14 @@ impl<Z: Zone> Command for DropElementCommand<Z> {
             // Conceptually, placing an item takes initative but dropping from lack of ability does not.
             None => info!("Failed to get initiative for ant {:?}", self.ant_entity),
         };
+
+        // Packing material into place is hard work, like digging it out.
+        if self.built_element.is_some() {
+            let dig_cost = world.resource::<Settings>().energy.dig_cost;
+            if let Some(mut energy) = world.get_mut::<Energy>(self.ant_entity) {
+                energy.exert(dig_cost);
+            }
+        }
     }
 }
 
@@ -264,6 +473,7 @@ struct SpawnAntCommand<Z: Zone> {
 impl<Z: Zone> Command for SpawnAntCommand<Z> {
     fn apply(self, world: &mut World) {
         let settings = world.resource::<Settings>();
+        let balance_config = world.resource::<BalanceConfig>();
 
         world.spawn(AntBundle {
             ant: Ant,
@@ -275,8 +485,97 @@ impl<Z: Zone> Command for SpawnAntCommand<Z> {
             name: self.name,
             color: self.color,
             zone: self.zone,
-            hunger: Hunger::new(settings.max_hunger_time),
+            hunger: Hunger::new(
+                settings.max_hunger_time,
+                balance_config.appetite.get(self.role),
+            ),
             digestion: Digestion::new(settings.max_digestion_time),
         });
     }
 }
+
+struct DespawnAntCommand<Z: Zone> {
+    ant_entity: Entity,
+    zone: Z,
+}
+
+impl<Z: Zone> Command for DespawnAntCommand<Z> {
+    fn apply(self, world: &mut World) {
+        ReleaseInventoryCommand {
+            ant_entity: self.ant_entity,
+            zone: self.zone,
+        }
+        .apply(world);
+
+        // NOTE: Views, including the ant's name label, are despawned by rendering once it notices the ant is gone.
+        match world.get_entity_mut(self.ant_entity) {
+            Some(ant_entity_mut) => ant_entity_mut.despawn_recursive(),
+            None => info!("Ant {:?} was already despawned", self.ant_entity),
+        }
+    }
+}
+
+struct ReleaseInventoryCommand<Z: Zone> {
+    ant_entity: Entity,
+    zone: Z,
+}
+
+impl<Z: Zone> Command for ReleaseInventoryCommand<Z> {
+    fn apply(self, world: &mut World) {
+        let Some(inventory_item_entity) = world
+            .get::<AntInventory>(self.ant_entity)
+            .and_then(|inventory| inventory.last())
+        else {
+            return;
+        };
+
+        let Some(&position) = world.get::<Position>(self.ant_entity) else {
+            panic!("Failed to get position for ant {:?}", self.ant_entity);
+        };
+
+        let mut system_state: SystemState<GridElements<Z>> = SystemState::new(world);
+        let grid_elements = system_state.get(world);
+
+        let air_entity = grid_elements
+            .is(position, Element::Air)
+            .then(|| *grid_elements.entity(position));
+
+        match air_entity {
+            Some(air_entity) => DropElementCommand {
+                ant_entity: self.ant_entity,
+                target_element_entity: air_entity,
+                target_position: position,
+                built_element: None,
+                zone: self.zone,
+            }
+            .apply(world),
+            None => {
+                world.entity_mut(inventory_item_entity).despawn();
+            }
+        }
+
+        // Only one item fits where the ant stands. Anything else it was carrying is lost.
+        let remaining_item_entities = match world.get_mut::<AntInventory>(self.ant_entity) {
+            Some(mut inventory) => std::mem::take(&mut inventory.items),
+            None => panic!("Failed to get inventory for ant {:?}", self.ant_entity),
+        };
+
+        for remaining_item_entity in remaining_item_entities {
+            if remaining_item_entity != inventory_item_entity {
+                world.entity_mut(remaining_item_entity).despawn();
+            }
+        }
+    }
+}
+
+struct SpawnEggCommand<Z: Zone> {
+    position: Position,
+    color: AntColor,
+    zone: Z,
+}
+
+impl<Z: Zone> Command for SpawnEggCommand<Z> {
+    fn apply(self, world: &mut World) {
+        world.spawn(BroodBundle::new(self.position, self.color, self.zone));
+    }
+}
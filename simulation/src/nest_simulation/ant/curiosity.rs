@@ -0,0 +1,148 @@
+use super::{
+    carry::CarryGroup,
+    collapse::Collapsed,
+    infection::Infected,
+    order::Order,
+    recruitment::{Recruitment, TandemRun},
+    sleep::Asleep,
+    walk::get_turned_orientation,
+    AntInventory, AntOrientation, AntRole, Dead, Initiative,
+};
+use crate::{
+    common::{grid::GridElements, intents::IntentsLog, position::Position},
+    nest_simulation::nest::{AtNest, Nest},
+    settings::Settings,
+    story_time::DEFAULT_TICKS_PER_SECOND,
+};
+use bevy::prelude::*;
+use bevy_turborand::{DelegatedRng, GlobalRng};
+
+// Ants only notice the cursor when it's close by. Any further and the whole surface would crowd around it.
+const CURIOSITY_RADIUS: isize = 10;
+// A cursor left in one place stops being interesting after 30 seconds.
+const PLAYER_CURSOR_INTEREST_TICKS: isize = 30 * DEFAULT_TICKS_PER_SECOND;
+
+/// Where, in the nest, the player last pointed or tapped. It's moved by `ExternalSimulationEvent::PointAt`, at the start
+/// of a tick, so every system sees the same cursor for the whole of a tick, and replays see the cursor the host saw. Headless stories never have a
+/// cursor. Not persisted because the player's cursor isn't part of the story.
+#[derive(Resource, Default, Debug, PartialEq, Copy, Clone)]
+pub struct PlayerCursor {
+    position: Option<Position>,
+    remaining_ticks: isize,
+}
+
+impl PlayerCursor {
+    /// Moving the cursor somewhere new rekindles interest in it. Leaving it where it is doesn't.
+    pub fn point_at(&mut self, position: Position) {
+        if self.position == Some(position) {
+            return;
+        }
+
+        self.position = Some(position);
+        self.remaining_ticks = PLAYER_CURSOR_INTEREST_TICKS;
+    }
+
+    /// Where the cursor is, as long as it's still interesting.
+    pub fn get_position(&self) -> Option<Position> {
+        self.position.filter(|_| self.remaining_ticks > 0)
+    }
+}
+
+pub fn initialize_curiosity_resources(mut commands: Commands) {
+    commands.init_resource::<PlayerCursor>();
+}
+
+pub fn remove_curiosity_resources(mut commands: Commands) {
+    commands.remove_resource::<PlayerCursor>();
+}
+
+pub fn player_cursor_tick(mut player_cursor: ResMut<PlayerCursor>) {
+    if player_cursor.remaining_ticks > 0 {
+        player_cursor.remaining_ticks -= 1;
+    }
+}
+
+/// Idle, empty-handed, workers on the surface now and then wander over to see what the player is pointing at, and stop
+/// to sniff at it once they arrive. Only in Story Mode, where the colony is otherwise left to fend for itself.
+pub fn ants_investigate_cursor(
+    mut ants_query: Query<
+        (
+            Entity,
+            &mut Initiative,
+            &Position,
+            &mut AntOrientation,
+            &AntInventory,
+            &AntRole,
+        ),
+        (
+            With<AtNest>,
+            Without<Order>,
+            Without<CarryGroup>,
+            Without<Recruitment>,
+            Without<TandemRun>,
+            Without<Asleep>,
+            Without<Collapsed>,
+            Without<Infected>,
+            Without<Dead>,
+        ),
+    >,
+    player_cursor: Res<PlayerCursor>,
+    nest_query: Query<&Nest>,
+    grid_elements: GridElements<AtNest>,
+    settings: Res<Settings>,
+    mut rng: ResMut<GlobalRng>,
+    mut intents_log: ResMut<IntentsLog>,
+) {
+    if !settings.is_story_mode || !settings.is_cursor_curiosity_enabled {
+        return;
+    }
+
+    let nest = nest_query.single();
+
+    let Some(cursor_position) = player_cursor
+        .get_position()
+        .filter(|cursor_position| nest.is_aboveground(cursor_position))
+    else {
+        return;
+    };
+
+    for (ant_entity, mut initiative, position, mut orientation, inventory, role) in
+        ants_query.iter_mut()
+    {
+        if !initiative.can_move()
+            || *role != AntRole::Worker
+            || !inventory.is_empty()
+            || !nest.is_aboveground(position)
+        {
+            continue;
+        }
+
+        let distance = position.distance(&cursor_position);
+        if distance > CURIOSITY_RADIUS {
+            continue;
+        }
+
+        if !rng.chance(settings.probabilities.cursor_curiosity.into()) {
+            continue;
+        }
+
+        if distance <= 1 {
+            intents_log.record(ant_entity, || "curious: sniffing at the cursor".to_string());
+
+            initiative.consume();
+            continue;
+        }
+
+        let ahead_position = orientation.get_ahead_position(position);
+        if ahead_position.distance(&cursor_position) < distance {
+            continue;
+        }
+
+        intents_log.record(ant_entity, || "curious: heading for the cursor".to_string());
+
+        *orientation =
+            get_turned_orientation(&orientation, &position, &nest, &mut rng, &grid_elements);
+
+        initiative.consume_movement();
+    }
+}
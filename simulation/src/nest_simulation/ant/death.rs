@@ -1,9 +1,9 @@
-use super::Dead;
+use super::{AntRole, Dead};
 use crate::{
     common::{grid::GridElements, position::Position},
     nest_simulation::{
-        ant::{commands::AntCommandsExt, AntInventory},
-        element::Element,
+        ant::commands::AntCommandsExt,
+        element::{commands::ElementCommandsExt, Element},
         nest::AtNest,
     },
 };
@@ -13,19 +13,37 @@ use bevy::prelude::*;
 /// TODO:
 ///     * It might be preferable to find an adjacent, available zone to move inventory to rather than despawning.
 pub fn on_ants_add_dead(
-    ants_query: Query<(Entity, &Position, &AntInventory), (Added<Dead>, With<AtNest>)>,
+    ants_query: Query<Entity, (Added<Dead>, With<AtNest>)>,
     mut commands: Commands,
+) {
+    for ant_entity in ants_query.iter() {
+        commands.release_inventory(ant_entity, AtNest);
+    }
+}
+
+/// Dead ants are left behind as corpses for their nestmates to clear away. The corpse lies where the ant died unless
+/// something already occupies that spot, e.g. the ant's dropped inventory, in which case it's laid beside it.
+/// The queen is left as she is because her death ends the story.
+pub fn ants_become_corpses(
+    ants_query: Query<(Entity, &Position, &AntRole), (With<Dead>, With<AtNest>)>,
     grid_elements: GridElements<AtNest>,
+    mut commands: Commands,
 ) {
-    for (ant_entity, ant_position, ant_inventory) in ants_query.iter() {
-        if ant_inventory.0 != None {
-            let element_entity = grid_elements.entity(*ant_position);
+    for (ant_entity, position, role) in ants_query.iter() {
+        if *role == AntRole::Queen {
+            continue;
+        }
 
-            if grid_elements.is(*ant_position, Element::Air) {
-                commands.drop(ant_entity, *ant_position, *element_entity, AtNest);
-            } else {
-                commands.entity(*element_entity).remove_parent().despawn();
-            }
+        let corpse_position = std::iter::once(*position)
+            .chain(std::iter::once(*position - Position::Y))
+            .chain(position.get_adjacent_positions())
+            .find(|&corpse_position| grid_elements.is(corpse_position, Element::Air));
+
+        if let Some(corpse_position) = corpse_position {
+            let air_entity = grid_elements.entity(corpse_position);
+            commands.replace_element(corpse_position, Element::Corpse, *air_entity, AtNest);
         }
+
+        commands.despawn_ant(ant_entity, AtNest);
     }
 }
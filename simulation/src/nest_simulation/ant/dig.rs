@@ -1,14 +1,22 @@
-use super::{commands::AntCommandsExt, AntInventory, AntOrientation, AntRole, Initiative};
+use super::{
+    commands::AntCommandsExt,
+    midden::{get_midden_position, get_refuse_position, is_at_midden, is_at_refuse},
+    thermoregulation::BroodTransport,
+    AntInventory, AntOrientation, AntRole, Initiative,
+};
 use crate::{
     common::{
         grid::{Grid, GridElements},
         position::Position,
+        reservation::ElementReservations,
     },
     nest_simulation::{
+        colony_planner::ColonyPlanner,
         element::Element,
         nest::{AtNest, Nest},
     },
     settings::Settings,
+    story_time::StoryTime,
 };
 use bevy::prelude::*;
 use bevy_turborand::prelude::*;
@@ -26,42 +34,87 @@ pub fn ants_dig(
         With<AtNest>,
     >,
     nest_query: Query<(&Grid, &Nest)>,
+    elements_query: Query<&Element>,
+    brood_transports_query: Query<&BroodTransport>,
     grid_elements: GridElements<AtNest>,
+    colony_planner: Res<ColonyPlanner>,
     settings: Res<Settings>,
+    story_time: Res<StoryTime>,
     mut rng: ResMut<GlobalRng>,
+    mut element_reservations: ResMut<ElementReservations>,
     mut commands: Commands,
 ) {
+    let (grid, nest) = nest_query.single();
+    let midden_position = get_midden_position(grid, nest, &grid_elements);
+    let refuse_position = get_refuse_position(&colony_planner, midden_position, &grid_elements);
+
     for (orientation, inventory, initiative, position, role, ant_entity) in ants_query.iter() {
         if !initiative.can_act() {
             continue;
         }
 
         // Consider digging / picking up the element under various circumstances.
-        if inventory.0 != None {
+        if !inventory.has_room() {
+            continue;
+        }
+
+        // Ants carrying brood have their mandibles full.
+        if brood_transports_query
+            .get(ant_entity)
+            .map_or(false, |brood_transport| brood_transport.is_carrying())
+        {
             continue;
         }
 
+        // Workers with room to spare top up on forage, but don't pick up forage while carrying sand, corpses, or waste.
+        let is_topping_up = !inventory.is_empty();
+        if is_topping_up
+            && !inventory.items().iter().all(|&item_entity| {
+                elements_query
+                    .get(item_entity)
+                    .map_or(false, Element::is_forage)
+            })
+        {
+            continue;
+        }
+
+        // Elements another ant has already claimed aren't worth considering. Choose from what's left.
         let positions = [
             orientation.get_ahead_position(position),
             orientation.get_below_position(position),
             orientation.get_above_position(position),
-        ];
-
-        let position = rng.sample(&positions).unwrap();
+        ]
+        .into_iter()
+        .filter(|position| {
+            grid_elements
+                .get_entity(*position)
+                .map_or(true, |element_entity| {
+                    !element_reservations.is_reserved_by_other(*element_entity, ant_entity)
+                })
+        })
+        .collect::<Vec<_>>();
+
+        let Some(position) = rng.sample(&positions) else {
+            continue;
+        };
 
-        if try_dig(
+        try_dig(
             ant_entity,
             role,
             *position,
+            is_topping_up,
             &ants_query,
             &nest_query,
             &grid_elements,
+            &colony_planner,
+            midden_position,
+            refuse_position,
+            &mut element_reservations,
             &mut commands,
             &settings,
+            &story_time,
             &mut rng,
-        ) {
-            return;
-        }
+        );
     }
 }
 
@@ -69,6 +122,7 @@ fn try_dig(
     ant_entity: Entity,
     ant_role: &AntRole,
     dig_position: Position,
+    is_topping_up: bool,
     ants_query: &Query<
         (
             &AntOrientation,
@@ -82,8 +136,13 @@ fn try_dig(
     >,
     nest_query: &Query<(&Grid, &Nest)>,
     grid_elements: &GridElements<AtNest>,
+    colony_planner: &ColonyPlanner,
+    midden_position: Option<Position>,
+    refuse_position: Option<Position>,
+    element_reservations: &mut ResMut<ElementReservations>,
     commands: &mut Commands,
     settings: &Res<Settings>,
+    story_time: &Res<StoryTime>,
     rng: &mut ResMut<GlobalRng>,
 ) -> bool {
     let (grid, nest) = nest_query.single();
@@ -100,7 +159,20 @@ fn try_dig(
     }
 
     // NOTE: can remove this in the future when adding more elements
-    if *element != Element::Sand && *element != Element::Food {
+    if !matches!(
+        *element,
+        Element::Sand
+            | Element::Food
+            | Element::Corpse
+            | Element::Seed
+            | Element::Fungus
+            | Element::Waste
+    ) {
+        return false;
+    }
+
+    // Ants topping up only gather more forage from the surface rather than rummaging through what's been stored away.
+    if is_topping_up && !(element.is_forage() && nest.is_aboveground(&dig_position)) {
         return false;
     }
 
@@ -111,7 +183,8 @@ fn try_dig(
             role == AntRole::Queen && dig_position.distance(position) <= 1
         });
 
-        if adjacent_queen.is_some() {
+        if adjacent_queen.is_some() && element_reservations.try_reserve(*element_entity, ant_entity)
+        {
             commands.dig(ant_entity, dig_position, *element_entity, AtNest);
 
             return true;
@@ -120,20 +193,37 @@ fn try_dig(
 
     let mut dig = false;
 
-    if *element == Element::Food && *ant_role == AntRole::Worker {
+    if element.is_food() && *ant_role == AntRole::Worker {
         // When above ground, workers prioritize picking up food. Queen needs to focus on nest construction.
         if nest.is_aboveground(&dig_position) {
-            dig = rng.f32() < settings.probabilities.above_surface_food_dig;
+            // Foraging on the surface depends on the time of day. It's hard to find food in the dark or in the heat of midday.
+            dig = rng.f32()
+                < settings.probabilities.above_surface_food_dig
+                    * story_time.get_foraging_efficiency();
         } else {
             dig = rng.f32() < settings.probabilities.below_surface_food_dig;
         }
+    } else if *element == Element::Fungus && *ant_role == AntRole::Worker {
+        // Workers tend fungus gardens by harvesting them about as readily as they'd pick up stored food.
+        dig = rng.f32() < settings.probabilities.below_surface_food_dig;
+    } else if *element == Element::Seed && *ant_role == AntRole::Worker {
+        // Workers gather seeds left on the surface. Seeds underground have already been stored away.
+        dig = nest.is_aboveground(&dig_position);
+    } else if *element == Element::Corpse && *ant_role == AntRole::Worker {
+        // Workers clear away corpses wherever they find them, except for those already laid to rest at the midden.
+        // There's nowhere to take corpses while the nest has no way out, e.g. while a founding queen is sealed in.
+        dig = midden_position.is_some() && !is_at_midden(dig_position, midden_position);
+    } else if *element == Element::Waste && *ant_role == AntRole::Worker {
+        // Workers clear away waste wherever they find it, except for what's already been put away.
+        dig = refuse_position.is_some()
+            && !is_at_refuse(dig_position, colony_planner, midden_position);
     } else if *element == Element::Sand && nest.is_underground(&dig_position) {
         // When underground, prioritize clearing out sand and allow for digging tunnels through dirt. Leave food underground.
         // It's OK for queen to pick up sand because sometimes it'll get in the way of nest building.
         dig = *element == Element::Sand && nest.is_underground(&dig_position);
     }
 
-    if dig {
+    if dig && element_reservations.try_reserve(*element_entity, ant_entity) {
         commands.dig(ant_entity, dig_position, *element_entity, AtNest);
 
         return true;
@@ -0,0 +1,62 @@
+use super::{
+    commands::AntCommandsExt,
+    order::{can_receive_order, Order},
+    AntInventory, AntRole, Dead, Initiative,
+};
+use crate::{
+    common::{grid::GridElements, position::Position, reservation::ElementReservations},
+    nest_simulation::nest::AtNest,
+    save::ReflectPersist,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Dirt the player has marked for excavation. The colony sets idle workers to digging it out.
+/// NOTE: The marker lives on the dirt itself so it's cleared, along with the dirt, once the dirt has been dug out.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Component, Persist)]
+pub struct DigOrder;
+
+/// Workers dig out any marked dirt they find themselves beside. Orders given directly to an ant take priority.
+pub fn ants_excavate(
+    ants_query: Query<
+        (Entity, &Initiative, &Position, &AntRole, &AntInventory),
+        (With<AtNest>, Without<Order>, Without<Dead>),
+    >,
+    dig_orders_query: Query<(), (With<DigOrder>, With<AtNest>)>,
+    grid_elements: GridElements<AtNest>,
+    mut element_reservations: ResMut<ElementReservations>,
+    mut commands: Commands,
+) {
+    for (ant_entity, initiative, position, role, inventory) in ants_query.iter() {
+        // Ants drop what they're carrying, on their own, before they're able to dig again.
+        if !can_receive_order(role) || !initiative.can_act() || !inventory.is_empty() {
+            continue;
+        }
+
+        let marked_dirt = position
+            .get_adjacent_positions()
+            .into_iter()
+            .filter_map(|adjacent_position| {
+                let element_entity = *grid_elements.get_entity(adjacent_position)?;
+
+                dig_orders_query
+                    .contains(element_entity)
+                    .then_some((adjacent_position, element_entity))
+            })
+            .find(|(_, element_entity)| {
+                !element_reservations.is_reserved_by_other(*element_entity, ant_entity)
+            });
+
+        let Some((dirt_position, dirt_entity)) = marked_dirt else {
+            continue;
+        };
+
+        if !element_reservations.try_reserve(dirt_entity, ant_entity) {
+            continue;
+        }
+
+        // Digging uses up the ant's initiative once the dig goes through.
+        commands.dig(ant_entity, dirt_position, dirt_entity, AtNest);
+    }
+}
@@ -1,16 +1,30 @@
-use crate::{nest_simulation::nest::AtNest, story_time::DEFAULT_TICKS_PER_SECOND};
+use crate::{
+    nest_simulation::{element::FoodKind, nest::AtNest},
+    save::ReflectPersist,
+    story_time::DEFAULT_TICKS_PER_SECOND,
+};
 
 use super::{hunger::Hunger, Dead};
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
+/// How full an ant's crop is, and with what. `value` counts up toward `max` as food is digested so undigested food is
+/// whatever's left between the two. Each kind of food is tracked separately, so food passed from ant to ant carries
+/// its mix along with it, and mixes with whatever the receiver already had.
 #[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
-#[reflect(Component)]
+// Reflecting Default lets saves written before plant and sugar were tracked be loaded. Their crops hold untracked food,
+// which is digested first, until it's gone.
+#[reflect(Component, Default, Persist)]
 pub struct Digestion {
     // TODO: Figure out interface
     pub value: f32,
     max: f32,
     rate: f32,
+    // How much of the undigested food is of each kind. Sugar burns off first and protein is digested last so there's
+    // some left to feed larvae.
+    plant: f32,
+    sugar: f32,
+    protein: f32,
 }
 
 impl Digestion {
@@ -23,6 +37,9 @@ impl Digestion {
             value: 100.0,
             max,
             rate,
+            plant: 0.0,
+            sugar: 0.0,
+            protein: 0.0,
         }
     }
 
@@ -38,14 +55,108 @@ impl Digestion {
         self.max
     }
 
+    pub fn protein(&self) -> f32 {
+        self.protein
+    }
+
+    /// How much undigested food there is, of any kind.
+    pub fn undigested(&self) -> f32 {
+        self.max - self.value
+    }
+
+    /// How much undigested food there is of the given kind.
+    pub fn food(&self, food_kind: FoodKind) -> f32 {
+        match food_kind {
+            FoodKind::Plant => self.plant,
+            FoodKind::Sugar => self.sugar,
+            FoodKind::Protein => self.protein,
+        }
+    }
+
+    fn food_mut(&mut self, food_kind: FoodKind) -> &mut f32 {
+        match food_kind {
+            FoodKind::Plant => &mut self.plant,
+            FoodKind::Sugar => &mut self.sugar,
+            FoodKind::Protein => &mut self.protein,
+        }
+    }
+
     pub fn increment(&mut self, percent: f32) {
         self.value += (self.max() * percent).min(self.value());
     }
 
+    /// Swallow a piece of food which fills `percent` of the stomach.
+    pub fn eat(&mut self, percent: f32, food_kind: FoodKind) {
+        let previous_value = self.value;
+        self.increment(-percent);
+
+        *self.food_mut(food_kind) += previous_value - self.value;
+    }
+
+    /// Pass `amount` of undigested food to a nestmate. Each kind of food goes along with it in proportion to how much
+    /// of the crop it makes up, so food is diluted a little more with each nestmate it's passed through.
+    ///
+    /// ```
+    /// use simulation::nest_simulation::{ant::digestion::Digestion, element::FoodKind};
+    ///
+    /// let mut forager = Digestion::new(3_600);
+    /// forager.eat(0.4, FoodKind::Sugar);
+    /// forager.eat(0.1, FoodKind::Protein);
+    ///
+    /// let mut nurse = Digestion::new(3_600);
+    /// nurse.eat(0.1, FoodKind::Plant);
+    ///
+    /// forager.regurgitate_to(&mut nurse, 20.0);
+    ///
+    /// assert_eq!(forager.food(FoodKind::Sugar), 24.0);
+    /// assert_eq!(forager.food(FoodKind::Protein), 6.0);
+    /// assert_eq!(nurse.undigested(), 30.0);
+    /// assert_eq!(nurse.food(FoodKind::Plant), 10.0);
+    /// assert_eq!(nurse.food(FoodKind::Sugar), 16.0);
+    /// assert_eq!(nurse.food(FoodKind::Protein), 4.0);
+    /// ```
+    pub fn regurgitate_to(&mut self, other: &mut Digestion, amount: f32) {
+        let undigested = self.undigested();
+        let share = if undigested > 0.0 {
+            (amount / undigested).min(1.0)
+        } else {
+            0.0
+        };
+
+        for food_kind in FoodKind::ALL {
+            let food = self.food(food_kind) * share;
+
+            *self.food_mut(food_kind) -= food;
+            *other.food_mut(food_kind) += food;
+        }
+
+        self.value += amount;
+        other.value -= amount;
+    }
+
+    /// Pass along up to `amount` of protein, and nothing else, returning how much was given.
+    pub fn regurgitate_protein(&mut self, amount: f32) -> f32 {
+        let protein = amount.min(self.protein);
+
+        self.value += protein;
+        self.protein -= protein;
+
+        protein
+    }
+
     pub fn tick(&mut self) -> f32 {
         let new_value = (self.value + self.rate).min(self.max);
         let change = new_value - self.value;
         self.value = new_value;
+
+        // Whatever isn't left undigested has been digested, starting with sugar and ending with protein.
+        let mut remaining = self.undigested();
+        for food_kind in [FoodKind::Protein, FoodKind::Plant, FoodKind::Sugar] {
+            let food = self.food_mut(food_kind);
+            *food = food.min(remaining);
+            remaining -= *food;
+        }
+
         change
     }
 }
@@ -1,11 +1,18 @@
-use super::{commands::AntCommandsExt, AntInventory, AntOrientation, AntRole, Initiative};
+use super::{
+    commands::AntCommandsExt,
+    flood_sealing::FloodSeal,
+    midden::{get_midden_position, get_refuse_position, is_at_midden, is_at_refuse},
+    AntInventory, AntOrientation, AntRole, Initiative,
+};
 use crate::{
     common::{
         grid::{Grid, GridElements},
         position::Position,
+        reservation::ElementReservations,
     },
     nest_simulation::{
-        element::Element,
+        colony_planner::ColonyPlanner,
+        element::{mud::is_wall_pocket, seed::is_granary_position, Element},
         nest::{AtNest, Nest},
     },
     settings::Settings,
@@ -23,23 +30,28 @@ pub fn ants_drop(
             &AntRole,
             Entity,
         ),
-        With<AtNest>,
+        // Workers sealing a flood hold on to their sand until it's packed into place.
+        (With<AtNest>, Without<FloodSeal>),
     >,
     elements_query: Query<&Element>,
     nest_query: Query<(&Grid, &Nest)>,
     grid_elements: GridElements<AtNest>,
+    colony_planner: Res<ColonyPlanner>,
     settings: Res<Settings>,
     mut rng: ResMut<GlobalRng>,
+    mut element_reservations: ResMut<ElementReservations>,
     mut commands: Commands,
 ) {
     let (grid, nest) = nest_query.single();
+    let midden_position = get_midden_position(grid, nest, &grid_elements);
+    let refuse_position = get_refuse_position(&colony_planner, midden_position, &grid_elements);
 
     for (orientation, inventory, initiative, position, role, ant_entity) in ants_query.iter() {
         if !initiative.can_act() {
             continue;
         }
 
-        if inventory.0 == None {
+        if inventory.is_empty() {
             continue;
         }
 
@@ -48,16 +60,21 @@ pub fn ants_drop(
             continue;
         }
 
+        // Another ant is already dropping something there this tick.
+        let target_element_entity = grid_elements.entity(ahead_position);
+        if element_reservations.is_reserved_by_other(*target_element_entity, ant_entity) {
+            continue;
+        }
+
         // Use ahead position for random inventory drop.
         if rng.f32() < settings.probabilities.random_drop {
-            let target_element_entity = grid_elements.entity(ahead_position);
+            element_reservations.try_reserve(*target_element_entity, ant_entity);
             commands.drop(ant_entity, ahead_position, *target_element_entity, AtNest);
             continue;
         }
 
         // Check if hitting a solid element and, if so, consider digging through it.
-        let entity = grid_elements.entity(ahead_position);
-        let element = grid_elements.element(*entity);
+        let element = grid_elements.element(*target_element_entity);
         if *element != Element::Air {
             continue;
         }
@@ -68,7 +85,7 @@ pub fn ants_drop(
         }
 
         // There is an air gap directly ahead of the ant. Consider dropping inventory.
-        let inventory_item_element = elements_query.get(inventory.0.unwrap()).unwrap();
+        let inventory_item_element = elements_query.get(inventory.last().unwrap()).unwrap();
 
         // Prioritize dropping sand above ground and food below ground.
         let drop_sand = *inventory_item_element == Element::Sand
@@ -76,7 +93,7 @@ pub fn ants_drop(
             && rng.f32() < settings.probabilities.above_surface_sand_drop;
 
         let mut drop_food = false;
-        if *inventory_item_element == Element::Food {
+        if inventory_item_element.is_food() {
             if nest.is_underground(&ahead_position) {
                 // Don't let ants drop food in tunnels that don't have space for them to navigate around dropped food.
                 if grid_elements.is(
@@ -88,7 +105,7 @@ pub fn ants_drop(
 
                     // If ant is adjacent to food then strongly consider dropping food (creates food piles)
                     let is_food_below =
-                        grid_elements.is(orientation.get_below_position(position), Element::Food);
+                        grid_elements.is_food(orientation.get_below_position(position));
 
                     if is_food_below
                         && rng.f32() < settings.probabilities.below_surface_food_adjacent_food_drop
@@ -103,9 +120,32 @@ pub fn ants_drop(
             }
         }
 
-        if drop_sand || drop_food {
+        // Corpses are always laid to rest at the midden. They're set back down if the way out of the nest is lost.
+        let drop_corpse = *inventory_item_element == Element::Corpse
+            && (midden_position.is_none() || is_at_midden(ahead_position, midden_position));
+
+        // Waste is put away in the refuse chamber, or on the midden, and set back down if there's nowhere to put it.
+        let drop_waste = *inventory_item_element == Element::Waste
+            && (refuse_position.is_none()
+                || is_at_refuse(ahead_position, &colony_planner, midden_position));
+
+        // Seeds are always stored in a granary.
+        let drop_seed = *inventory_item_element == Element::Seed
+            && is_granary_position(ahead_position, nest, &grid_elements);
+
+        // Prey is brought underground, where it's butchered into protein, rather than being left anywhere on the surface.
+        let drop_prey =
+            *inventory_item_element == Element::Prey && nest.is_underground(&ahead_position);
+
+        // Mud is plastered into pockets in tunnel walls. Once it dries, the wall is smooth.
+        let drop_mud = *inventory_item_element == Element::Mud
+            && nest.is_underground(&ahead_position)
+            && is_wall_pocket(ahead_position, *position, &grid_elements);
+
+        if drop_sand || drop_food || drop_corpse || drop_waste || drop_seed || drop_prey || drop_mud
+        {
             // Drop inventory in front of ant
-            let target_element_entity = grid_elements.entity(ahead_position);
+            element_reservations.try_reserve(*target_element_entity, ant_entity);
             commands.drop(ant_entity, ahead_position, *target_element_entity, AtNest);
             continue;
         }
@@ -0,0 +1,70 @@
+use super::{commands::AntCommandsExt, Dead};
+use crate::{
+    common::{grid::GridElements, position::Position},
+    nest_simulation::{element::Element, nest::AtNest},
+    save::ReflectPersist,
+    story_time::DEFAULT_TICKS_PER_SECOND,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// How long an ant can hold its breath underwater before it drowns.
+const DROWNING_MAX_TICKS: isize = 60 * DEFAULT_TICKS_PER_SECOND; // 1 minute
+
+/// Ants never walk into water on their own, but water can flow over them. Ants caught in shallow water can still
+/// reach the surface to breathe. Ants caught in deep water, with more water above them, start drowning.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect)]
+#[reflect(Component, Persist)]
+pub struct Drowning {
+    remaining_ticks: isize,
+}
+
+impl Default for Drowning {
+    fn default() -> Self {
+        Self {
+            remaining_ticks: DROWNING_MAX_TICKS,
+        }
+    }
+}
+
+impl Drowning {
+    pub fn remaining_ticks(&self) -> isize {
+        self.remaining_ticks
+    }
+}
+
+pub fn register_drowning(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<Drowning>();
+}
+
+/// Ants in deep water drown if they aren't washed out of it in time. Ants which make it out catch their breath.
+pub fn ants_drown(
+    mut ants_query: Query<
+        (Entity, &Position, Option<&mut Drowning>),
+        (With<AtNest>, Without<Dead>),
+    >,
+    grid_elements: GridElements<AtNest>,
+    mut commands: Commands,
+) {
+    for (ant_entity, position, drowning) in ants_query.iter_mut() {
+        let is_in_deep_water = grid_elements.is(*position, Element::Water)
+            && grid_elements.is(*position - Position::Y, Element::Water);
+
+        match (is_in_deep_water, drowning) {
+            (true, None) => {
+                commands.entity(ant_entity).insert(Drowning::default());
+            }
+            (true, Some(mut drowning)) => {
+                drowning.remaining_ticks -= 1;
+
+                if drowning.remaining_ticks <= 0 {
+                    commands.kill_ant(ant_entity);
+                }
+            }
+            (false, Some(_)) => {
+                commands.entity(ant_entity).remove::<Drowning>();
+            }
+            (false, None) => {}
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use super::{sleep::Asleep, Ant, Dead};
+use crate::{
+    common::Zone,
+    save::ReflectPersist,
+    settings::{EnergySettings, Settings},
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// How much an ant has left in it. Every turn spent walking costs energy, and carrying or digging costs extra.
+/// Energy recharges a little every tick, and quickly while the ant sleeps. Exhausted ants wait for their energy to recharge
+/// before taking another turn so the hardest working ants slow down.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Component, Persist)]
+pub struct Energy {
+    value: f32,
+    max: f32,
+    // Energy spent on actions this turn, e.g. digging, on top of the cost of the turn itself.
+    exertion: f32,
+    // Energy spent on the most recent turn. Spending energy works up an appetite.
+    spent: f32,
+}
+
+impl Energy {
+    pub fn new(max: f32) -> Self {
+        Self {
+            value: max,
+            max,
+            exertion: 0.0,
+            spent: 0.0,
+        }
+    }
+
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    pub fn max(&self) -> f32 {
+        self.max
+    }
+
+    /// Spend extra energy on an action taken this turn. It's deducted when the turn ends.
+    pub fn exert(&mut self, amount: f32) {
+        self.exertion += amount;
+    }
+
+    /// Deduct the cost of the turn which just ended. Turns where the ant didn't move cost nothing beyond any exertion.
+    pub fn spend_turn(&mut self, has_moved: bool, is_carrying: bool, settings: &EnergySettings) {
+        let mut cost = self.exertion;
+
+        if has_moved {
+            cost += settings.walk_cost;
+
+            if is_carrying {
+                cost += settings.carry_cost;
+            }
+        }
+
+        self.value = (self.value - cost).max(0.0);
+        self.exertion = 0.0;
+        self.spent = cost;
+    }
+
+    /// Energy spent on the most recent turn. It's only reported once so it isn't counted twice.
+    pub fn take_spent(&mut self) -> f32 {
+        std::mem::take(&mut self.spent)
+    }
+
+    /// How long the ant needs to rest before it has the energy to take another step.
+    pub fn get_recovery_ticks(&self, settings: &EnergySettings) -> isize {
+        if self.value >= settings.walk_cost || settings.recharge <= 0.0 {
+            return 0;
+        }
+
+        ((settings.walk_cost - self.value) / settings.recharge).ceil() as isize
+    }
+}
+
+/// Give newly spawned ants a full store of energy.
+/// NOTE: This applies to every ant, not just those in the nest, so ants from saves which predate energy are well rested.
+pub fn ants_add_energy(
+    ants_query: Query<Entity, (With<Ant>, Without<Energy>)>,
+    settings: Res<Settings>,
+    mut commands: Commands,
+) {
+    for ant_entity in ants_query.iter() {
+        commands
+            .entity(ant_entity)
+            .insert(Energy::new(settings.energy.max));
+    }
+}
+
+/// Ants recharge a little every tick, whether or not they're taking a turn, and recharge much faster while asleep.
+pub fn ants_recharge_energy<Z: Zone>(
+    mut ants_query: Query<(&mut Energy, Option<&Asleep>), (With<Z>, Without<Dead>)>,
+    settings: Res<Settings>,
+) {
+    for (mut energy, asleep) in ants_query.iter_mut() {
+        let recharge = if asleep.is_some() {
+            settings.energy.recharge * settings.energy.resting_recharge_multiplier
+        } else {
+            settings.energy.recharge
+        };
+
+        energy.value = (energy.value + recharge).min(energy.max);
+    }
+}
@@ -0,0 +1,382 @@
+use super::{
+    carry::CarryGroup,
+    collapse::Collapsed,
+    commands::AntCommandsExt,
+    food_delivery::FoodDelivery,
+    hunger::Hunger,
+    infection::Infected,
+    order::Order,
+    recruitment::{Recruitment, TandemRun},
+    sleep::Asleep,
+    thermoregulation::BroodTransport,
+    AntInventory, AntRole, Dead, Initiative,
+};
+use crate::{
+    balance_config::BalanceConfig,
+    common::{
+        grid::GridElements, intents::IntentsLog, pathfinding::is_walkable, position::Position,
+        reservation::ElementReservations,
+    },
+    nest_simulation::{
+        element::{Element, Water},
+        nest::{AtNest, Nest},
+    },
+    save::ReflectPersist,
+    story_time::DEFAULT_TICKS_PER_SECOND,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Workers only drop what they're doing for a breach this close by. Further off, others are better placed to help.
+const SEAL_RADIUS: isize = 20;
+// A handful of workers is enough to plug the worst of a flood. Any more and they'd only get in one another's way.
+const MAX_SEALERS: usize = 4;
+// Empty-handed workers look this far around themselves for sand, or dirt, to plug a breach with.
+const MATERIAL_SEARCH_RADIUS: isize = 8;
+// Workers which can't plug their breach within 2 minutes give up, e.g. because the water got there first.
+const SEAL_TICKS: isize = 2 * 60 * DEFAULT_TICKS_PER_SECOND;
+
+/// A worker plugging a tunnel which water is flooding into. Empty-handed workers first fetch sand, or dirt, from
+/// beside `dig_site`. Workers with sand in hand head for `build_site`, beside the breach, and pack it into a plug.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect)]
+#[reflect(Component, Persist)]
+pub struct FloodSeal {
+    breach: Position,
+    build_site: Position,
+    dig_site: Option<Position>,
+    remaining_ticks: isize,
+}
+
+impl Default for FloodSeal {
+    fn default() -> Self {
+        Self {
+            breach: Position::default(),
+            build_site: Position::default(),
+            dig_site: None,
+            remaining_ticks: SEAL_TICKS,
+        }
+    }
+}
+
+impl FloodSeal {
+    pub fn breach(&self) -> Position {
+        self.breach
+    }
+
+    /// Where the worker is headed: for sand, while empty-handed, and then for the breach.
+    pub fn get_path_goal(&self, is_carrying: bool) -> Option<Position> {
+        if is_carrying {
+            Some(self.build_site)
+        } else {
+            self.dig_site
+        }
+    }
+}
+
+/// Tunnels water is about to flood into: underground air, per `is_underground_air`, which water is flowing down, or
+/// sideways, into. Listed nearest the top of the nest first, and without repeats.
+///
+/// ```
+/// use simulation::{
+///     common::position::Position, nest_simulation::ant::flood_sealing::get_flood_breaches,
+/// };
+///
+/// // A puddle on the surface, at y = 5, over a tunnel running down from (10, 6).
+/// let water_positions = [Position::new(10, 5), Position::new(11, 5)];
+/// let is_underground_air = |position: Position| position == Position::new(10, 6);
+///
+/// assert_eq!(get_flood_breaches(&water_positions, is_underground_air), vec![Position::new(10, 6)]);
+/// ```
+pub fn get_flood_breaches(
+    water_positions: &[Position],
+    is_underground_air: impl Fn(Position) -> bool,
+) -> Vec<Position> {
+    let mut breaches = water_positions
+        .iter()
+        .flat_map(|water_position| {
+            [
+                *water_position + Position::Y,
+                *water_position + Position::NEG_X,
+                *water_position + Position::X,
+            ]
+        })
+        .filter(|position| is_underground_air(*position))
+        .collect::<Vec<_>>();
+
+    breaches.sort_by_key(|position| (position.y, position.x));
+    breaches.dedup();
+
+    breaches
+}
+
+fn is_next_to_water(position: Position, grid_elements: &GridElements<AtNest>) -> bool {
+    position
+        .get_adjacent_positions()
+        .into_iter()
+        .any(|adjacent_position| grid_elements.is(adjacent_position, Element::Water))
+}
+
+/// Sand and dirt make good plugs, but not where digging them out would let the water through somewhere else.
+fn is_seal_material(position: Position, grid_elements: &GridElements<AtNest>) -> bool {
+    (grid_elements.is(position, Element::Sand) || grid_elements.is(position, Element::Dirt))
+        && !is_next_to_water(position, grid_elements)
+}
+
+/// The nearest place an ant at `position` could stand to dig out sand, or dirt, for a plug.
+fn get_dig_site(position: Position, grid_elements: &GridElements<AtNest>) -> Option<Position> {
+    (-MATERIAL_SEARCH_RADIUS..=MATERIAL_SEARCH_RADIUS)
+        .flat_map(|y| {
+            (-MATERIAL_SEARCH_RADIUS..=MATERIAL_SEARCH_RADIUS)
+                .map(move |x| position + Position::new(x, y))
+        })
+        .filter(|dig_site| {
+            is_walkable(*dig_site, grid_elements)
+                && !is_next_to_water(*dig_site, grid_elements)
+                && dig_site
+                    .get_adjacent_positions()
+                    .into_iter()
+                    .any(|adjacent_position| is_seal_material(adjacent_position, grid_elements))
+        })
+        .min_by_key(|dig_site| position.distance(dig_site))
+}
+
+/// When water floods into the nest, the nearest idle workers rush to plug the tunnels it's pouring into. Workers
+/// already carrying sand pack it straight into the breach. Empty-handed workers fetch some first. Each breach gets a
+/// single worker, and only a few breaches are tackled at once, so the rest of the colony carries on as usual.
+pub fn ants_plan_flood_seals(
+    ants_query: Query<
+        (Entity, &Position, &AntRole, &AntInventory, &Hunger),
+        (
+            With<AtNest>,
+            Without<FloodSeal>,
+            Without<Order>,
+            Without<CarryGroup>,
+            Without<Recruitment>,
+            Without<TandemRun>,
+            Without<FoodDelivery>,
+            Without<BroodTransport>,
+            Without<Asleep>,
+            Without<Collapsed>,
+            Without<Infected>,
+            Without<Dead>,
+        ),
+    >,
+    seals_query: Query<&FloodSeal, With<AtNest>>,
+    water_query: Query<&Position, (With<Water>, With<AtNest>)>,
+    elements_query: Query<&Element>,
+    nest_query: Query<&Nest>,
+    grid_elements: GridElements<AtNest>,
+    balance_config: Res<BalanceConfig>,
+    mut intents_log: ResMut<IntentsLog>,
+    mut commands: Commands,
+) {
+    if water_query.is_empty() {
+        return;
+    }
+
+    let mut sealed_breaches = seals_query
+        .iter()
+        .map(|flood_seal| flood_seal.breach)
+        .collect::<Vec<_>>();
+
+    if sealed_breaches.len() >= MAX_SEALERS {
+        return;
+    }
+
+    let nest = nest_query.single();
+    let water_positions = water_query.iter().copied().collect::<Vec<_>>();
+    let breaches = get_flood_breaches(&water_positions, |position| {
+        nest.is_underground(&position) && grid_elements.is(position, Element::Air)
+    });
+
+    let mut idle_workers = ants_query
+        .iter()
+        .filter(|(_, _, role, inventory, hunger)| {
+            let is_empty_handed_or_carrying_sand = inventory.last().map_or(true, |item_entity| {
+                elements_query
+                    .get(item_entity)
+                    .map_or(false, |element| *element == Element::Sand)
+            });
+
+            **role == AntRole::Worker
+                && is_empty_handed_or_carrying_sand
+                && !hunger.is_hungry(&balance_config.hunger)
+        })
+        .map(|(ant_entity, position, _, inventory, _)| {
+            (ant_entity, *position, !inventory.is_empty())
+        })
+        .collect::<Vec<_>>();
+
+    for breach in breaches {
+        if sealed_breaches.len() >= MAX_SEALERS {
+            break;
+        }
+
+        if sealed_breaches.contains(&breach) {
+            continue;
+        }
+
+        // Workers pack the plug in from the dry side of the breach.
+        let Some(build_site) = breach
+            .get_adjacent_positions()
+            .into_iter()
+            .find(|position| {
+                is_walkable(*position, &grid_elements)
+                    && !is_next_to_water(*position, &grid_elements)
+            })
+        else {
+            continue;
+        };
+
+        let Some((index, dig_site)) = idle_workers
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, position, _))| position.distance(&breach) <= SEAL_RADIUS)
+            .filter_map(|(index, (_, position, is_carrying))| {
+                if *is_carrying {
+                    Some((index, None))
+                } else {
+                    get_dig_site(*position, &grid_elements).map(|dig_site| (index, Some(dig_site)))
+                }
+            })
+            .min_by_key(|(index, _)| idle_workers[*index].1.distance(&breach))
+        else {
+            continue;
+        };
+
+        let (sealer_entity, ..) = idle_workers.remove(index);
+
+        intents_log.record(sealer_entity, || {
+            format!(
+                "sealing flood: plugging tunnel at {}, {}",
+                breach.x, breach.y
+            )
+        });
+
+        commands.entity(sealer_entity).insert(FloodSeal {
+            breach,
+            build_site,
+            dig_site,
+            ..default()
+        });
+
+        sealed_breaches.push(breach);
+    }
+}
+
+/// Empty-handed workers dig out sand, or dirt, once they reach where they're fetching it from. Workers with sand in
+/// hand pack it into the breach, as dirt, once they're beside it.
+pub fn ants_seal_floods(
+    ants_query: Query<
+        (Entity, &Position, &AntInventory, &Initiative, &FloodSeal),
+        (With<AtNest>, Without<Dead>),
+    >,
+    grid_elements: GridElements<AtNest>,
+    mut element_reservations: ResMut<ElementReservations>,
+    mut intents_log: ResMut<IntentsLog>,
+    mut commands: Commands,
+) {
+    for (ant_entity, position, inventory, initiative, flood_seal) in ants_query.iter() {
+        if !initiative.can_act() {
+            continue;
+        }
+
+        if inventory.is_empty() {
+            if flood_seal.dig_site != Some(*position) {
+                continue;
+            }
+
+            // Any neighboring sand, or dirt, will do.
+            let Some((material_position, material_entity)) = position
+                .get_adjacent_positions()
+                .into_iter()
+                .filter(|adjacent_position| is_seal_material(*adjacent_position, &grid_elements))
+                .map(|adjacent_position| {
+                    (adjacent_position, *grid_elements.entity(adjacent_position))
+                })
+                .find(|(_, material_entity)| {
+                    element_reservations.try_reserve(*material_entity, ant_entity)
+                })
+            else {
+                continue;
+            };
+
+            intents_log.record(ant_entity, || {
+                "sealing flood: dug out sand for a plug".to_string()
+            });
+
+            commands.dig(ant_entity, material_position, material_entity, AtNest);
+        } else {
+            if position.distance(&flood_seal.breach) != 1 {
+                continue;
+            }
+
+            let breach_entity = *grid_elements.entity(flood_seal.breach);
+            if !element_reservations.try_reserve(breach_entity, ant_entity) {
+                continue;
+            }
+
+            intents_log.record(ant_entity, || {
+                "sealing flood: plugged the tunnel".to_string()
+            });
+
+            commands.build_element(
+                ant_entity,
+                flood_seal.breach,
+                breach_entity,
+                Element::Dirt,
+                AtNest,
+            );
+            commands.entity(ant_entity).remove::<FloodSeal>();
+        }
+    }
+}
+
+/// Seals are called off once the breach is no longer letting water in, e.g. because it's been plugged, has flooded
+/// already, or the water has soaked away. Workers give up when they've taken too long, when they've picked up
+/// something other than sand, or when something more pressing comes along.
+pub fn flood_seals_cancel(
+    mut sealers_query: Query<(Entity, &mut FloodSeal, &AntInventory, &Hunger), With<AtNest>>,
+    distracted_query: Query<
+        (),
+        Or<(
+            With<Order>,
+            With<CarryGroup>,
+            With<Recruitment>,
+            With<TandemRun>,
+            With<FoodDelivery>,
+            With<BroodTransport>,
+            With<Asleep>,
+            With<Collapsed>,
+            With<Infected>,
+            With<Dead>,
+        )>,
+    >,
+    elements_query: Query<&Element>,
+    grid_elements: GridElements<AtNest>,
+    balance_config: Res<BalanceConfig>,
+    mut commands: Commands,
+) {
+    for (ant_entity, mut flood_seal, inventory, hunger) in sealers_query.iter_mut() {
+        flood_seal.remaining_ticks -= 1;
+
+        let is_breached = grid_elements.is(flood_seal.breach, Element::Air)
+            && is_next_to_water(flood_seal.breach, &grid_elements);
+
+        let is_carrying_other = inventory.last().map_or(false, |item_entity| {
+            elements_query
+                .get(item_entity)
+                .map_or(true, |element| *element != Element::Sand)
+        });
+
+        if flood_seal.remaining_ticks <= 0
+            || !is_breached
+            || is_carrying_other
+            || (inventory.is_empty() && flood_seal.dig_site.is_none())
+            || hunger.is_hungry(&balance_config.hunger)
+            || distracted_query.get(ant_entity).is_ok()
+        {
+            commands.entity(ant_entity).remove::<FloodSeal>();
+        }
+    }
+}
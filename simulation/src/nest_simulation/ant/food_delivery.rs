@@ -0,0 +1,383 @@
+use super::{
+    carry::CarryGroup,
+    collapse::Collapsed,
+    digestion::Digestion,
+    hunger::{get_regurgitation_amount, is_in_contact, Hunger},
+    infection::Infected,
+    order::Order,
+    recruitment::{Recruitment, TandemRun},
+    sleep::Asleep,
+    thermoregulation::BroodTransport,
+    AntAteFoodEvent, AntInventory, AntOrientation, AntRole, Dead, Initiative,
+};
+use crate::{
+    balance_config::BalanceConfig,
+    common::{food_sharing::FoodSharingNetwork, intents::IntentsLog, position::Position},
+    nest_simulation::nest::AtNest,
+    save::ReflectPersist,
+};
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+// Couriers hand food off to a relay, rather than walking it the whole way, when the queen is further away than this.
+pub const FOOD_DELIVERY_HOP_DISTANCE: isize = 15;
+// Workers with less than this share of their crop full don't have enough to be worth sending to the queen.
+const MIN_DELIVERY_FOOD: f32 = 0.1;
+
+/// A worker carrying food, in its crop, to the hungry queen. `recipients` are who the food is handed to, in order,
+/// ending with the queen. Each relay takes over from the ant before it, so food crosses the nest like a bucket brigade
+/// rather than in one long walk.
+#[derive(Component, Debug, PartialEq, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Component, MapEntities, Persist)]
+pub struct FoodDelivery {
+    recipients: Vec<Entity>,
+}
+
+impl FoodDelivery {
+    /// Who the courier is heading for next.
+    pub fn recipient(&self) -> Option<Entity> {
+        self.recipients.first().copied()
+    }
+}
+
+impl MapEntities for FoodDelivery {
+    fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+        for entity in self.recipients.iter_mut() {
+            *entity = entity_mapper.get_or_reserve(*entity);
+        }
+    }
+}
+
+/// Relays food is handed between, in order, on its way from `courier_position` to `queen_position`. Each hop is to
+/// whichever relay gets the food closest to the queen without straying more than `FOOD_DELIVERY_HOP_DISTANCE` from the
+/// ant before it. Food is walked straight to the queen once she's close enough, or when there's nobody to relay it.
+///
+/// ```
+/// use bevy::prelude::Entity;
+/// use simulation::{
+///     common::position::Position,
+///     nest_simulation::ant::food_delivery::get_food_delivery_relays,
+/// };
+///
+/// let relays = [
+///     (Entity::from_raw(1), Position::new(10, 0)),
+///     (Entity::from_raw(2), Position::new(14, 0)),
+///     (Entity::from_raw(3), Position::new(28, 0)),
+///     // Too far off to the side to reach in one hop.
+///     (Entity::from_raw(4), Position::new(5, 20)),
+/// ];
+///
+/// assert_eq!(
+///     get_food_delivery_relays(Position::new(0, 0), Position::new(40, 0), &relays),
+///     vec![Entity::from_raw(2), Entity::from_raw(3)]
+/// );
+/// assert!(get_food_delivery_relays(Position::new(30, 0), Position::new(40, 0), &relays).is_empty());
+/// ```
+pub fn get_food_delivery_relays(
+    courier_position: Position,
+    queen_position: Position,
+    relays: &[(Entity, Position)],
+) -> Vec<Entity> {
+    let mut relay_entities = vec![];
+    let mut position = courier_position;
+
+    // Each hop brings the food strictly closer to the queen so this always ends.
+    while position.distance(&queen_position) > FOOD_DELIVERY_HOP_DISTANCE {
+        let Some((relay_entity, relay_position)) = relays
+            .iter()
+            .filter(|(_, relay_position)| {
+                position.distance(relay_position) <= FOOD_DELIVERY_HOP_DISTANCE
+                    && relay_position.distance(&queen_position) < position.distance(&queen_position)
+            })
+            .min_by_key(|(_, relay_position)| relay_position.distance(&queen_position))
+        else {
+            break;
+        };
+
+        relay_entities.push(*relay_entity);
+        position = *relay_position;
+    }
+
+    relay_entities
+}
+
+/// How much food, as a share of its crop, an ant has yet to digest.
+fn get_undigested_share(digestion: &Digestion) -> f32 {
+    digestion.undigested() / digestion.max()
+}
+
+/// When the queen is hungry, the nearest idle, empty-handed, worker with food in its crop is sent to feed her, rather
+/// than leaving her to chance encounters. Idle workers along the way are enlisted as relays when she's far off.
+/// Only one delivery is underway at a time so the whole colony doesn't descend on the queen at once.
+pub fn ants_plan_food_deliveries(
+    ants_query: Query<
+        (
+            Entity,
+            &Position,
+            &AntRole,
+            &AntInventory,
+            &Digestion,
+            Option<&FoodDelivery>,
+        ),
+        (
+            With<AtNest>,
+            Without<Order>,
+            Without<CarryGroup>,
+            Without<Recruitment>,
+            Without<TandemRun>,
+            Without<BroodTransport>,
+            Without<Asleep>,
+            Without<Collapsed>,
+            Without<Infected>,
+            Without<Dead>,
+        ),
+    >,
+    deliveries_query: Query<(), (With<FoodDelivery>, With<AtNest>)>,
+    queen_query: Query<(Entity, &Position, &AntRole, &Hunger), (With<AtNest>, Without<Dead>)>,
+    balance_config: Res<BalanceConfig>,
+    mut intents_log: ResMut<IntentsLog>,
+    mut commands: Commands,
+) {
+    let Some((queen_entity, queen_position, _, _)) =
+        queen_query.iter().find(|(_, _, role, hunger)| {
+            **role == AntRole::Queen && hunger.is_hungry(&balance_config.hunger)
+        })
+    else {
+        return;
+    };
+
+    if !deliveries_query.is_empty() {
+        return;
+    }
+
+    let idle_workers = ants_query
+        .iter()
+        .filter(|(_, _, role, inventory, _, food_delivery)| {
+            **role == AntRole::Worker && inventory.is_empty() && food_delivery.is_none()
+        })
+        .collect::<Vec<_>>();
+
+    let Some(&(courier_entity, courier_position, ..)) = idle_workers
+        .iter()
+        .filter(|(_, _, _, _, digestion, _)| get_undigested_share(digestion) >= MIN_DELIVERY_FOOD)
+        .min_by_key(|(_, position, ..)| position.distance(queen_position))
+    else {
+        return;
+    };
+
+    // Relays need room in their crop to take the food on.
+    let relays = idle_workers
+        .iter()
+        .filter(|(relay_entity, _, _, _, digestion, _)| {
+            *relay_entity != courier_entity
+                && digestion.value() >= digestion.max() * MIN_DELIVERY_FOOD
+        })
+        .map(|(relay_entity, relay_position, ..)| (*relay_entity, **relay_position))
+        .collect::<Vec<_>>();
+
+    let mut recipients = get_food_delivery_relays(*courier_position, *queen_position, &relays);
+    recipients.push(queen_entity);
+
+    intents_log.record(courier_entity, || {
+        format!(
+            "queen hungry: delivering food by way of {} relays",
+            recipients.len() - 1
+        )
+    });
+
+    commands
+        .entity(courier_entity)
+        .insert(FoodDelivery { recipients });
+}
+
+/// Couriers which reach whoever they're delivering to hand over what they can. Relays take over the rest of the
+/// delivery. Relays which have no room for more food are skipped and the courier carries on to whoever is next.
+pub fn ants_deliver_food(
+    mut ants_query: Query<
+        (
+            Entity,
+            &Position,
+            &AntOrientation,
+            &mut Digestion,
+            &mut Initiative,
+            &AntRole,
+            Option<&FoodDelivery>,
+        ),
+        (With<AtNest>, Without<Dead>),
+    >,
+    balance_config: Res<BalanceConfig>,
+    mut food_sharing_network: ResMut<FoodSharingNetwork>,
+    mut ant_ate_food_event_writer: EventWriter<AntAteFoodEvent>,
+    mut intents_log: ResMut<IntentsLog>,
+    mut commands: Commands,
+) {
+    let mut results = vec![];
+
+    for (
+        courier_entity,
+        courier_position,
+        courier_orientation,
+        courier_digestion,
+        courier_initiative,
+        _,
+        food_delivery,
+    ) in ants_query.iter()
+    {
+        let Some(food_delivery) = food_delivery else {
+            continue;
+        };
+
+        let Some(recipient_entity) = food_delivery.recipient() else {
+            continue;
+        };
+
+        let Ok((
+            _,
+            recipient_position,
+            recipient_orientation,
+            recipient_digestion,
+            recipient_initiative,
+            _,
+            _,
+        )) = ants_query.get(recipient_entity)
+        else {
+            continue;
+        };
+
+        if !courier_initiative.can_act()
+            || !recipient_initiative.can_act()
+            || !is_in_contact(
+                courier_entity,
+                courier_position,
+                courier_orientation,
+                recipient_entity,
+                recipient_position,
+                recipient_orientation,
+            )
+        {
+            continue;
+        }
+
+        let digestion_transfer_amount = get_regurgitation_amount(
+            courier_digestion,
+            recipient_digestion,
+            balance_config.regurgitation_transfer,
+        );
+
+        results.push((
+            courier_entity,
+            recipient_entity,
+            digestion_transfer_amount,
+            food_delivery.recipients[1..].to_vec(),
+        ));
+    }
+
+    for (courier_entity, recipient_entity, digestion_transfer_amount, remaining_recipients) in
+        results
+    {
+        let [(_, _, _, mut courier_digestion, mut courier_initiative, courier_role, _), (_, _, _, mut recipient_digestion, mut recipient_initiative, recipient_role, _)] =
+            ants_query
+                .get_many_mut([courier_entity, recipient_entity])
+                .unwrap();
+
+        // Initiative is checked again because an ant may already have handed off, or been handed, food this tick.
+        if !courier_initiative.can_act() || !recipient_initiative.can_act() {
+            continue;
+        }
+
+        if digestion_transfer_amount <= 0.0 {
+            if remaining_recipients.is_empty() {
+                commands.entity(courier_entity).remove::<FoodDelivery>();
+            } else {
+                intents_log.record(courier_entity, || {
+                    "delivering food: relay is full, carrying on".to_string()
+                });
+
+                commands.entity(courier_entity).insert(FoodDelivery {
+                    recipients: remaining_recipients,
+                });
+            }
+
+            continue;
+        }
+
+        courier_digestion.regurgitate_to(&mut recipient_digestion, digestion_transfer_amount);
+
+        courier_initiative.consume();
+        recipient_initiative.consume();
+
+        let is_relay = !remaining_recipients.is_empty();
+        food_sharing_network.record_share(*courier_role, *recipient_role, is_relay);
+
+        commands.entity(courier_entity).remove::<FoodDelivery>();
+
+        if is_relay {
+            intents_log.record(courier_entity, || {
+                "delivering food: handed off to a relay".to_string()
+            });
+
+            commands.entity(recipient_entity).insert(FoodDelivery {
+                recipients: remaining_recipients,
+            });
+        } else {
+            intents_log.record(courier_entity, || {
+                "delivering food: fed the queen".to_string()
+            });
+
+            ant_ate_food_event_writer.send(AntAteFoodEvent(recipient_entity));
+        }
+    }
+}
+
+/// Deliveries are called off once the queen has eaten her fill. Couriers give up once they've nothing left to give,
+/// or once something more pressing comes along, and anyone who has died is dropped from the brigade.
+pub fn food_deliveries_cancel(
+    mut couriers_query: Query<(Entity, &mut FoodDelivery, &Digestion, &AntInventory), With<AtNest>>,
+    distracted_query: Query<
+        (),
+        Or<(
+            With<Order>,
+            With<CarryGroup>,
+            With<Recruitment>,
+            With<TandemRun>,
+            With<Asleep>,
+            With<Collapsed>,
+            With<Infected>,
+            With<Dead>,
+        )>,
+    >,
+    living_ants_query: Query<(&AntRole, &Hunger), (With<AtNest>, Without<Dead>)>,
+    balance_config: Res<BalanceConfig>,
+    mut commands: Commands,
+) {
+    let is_queen_peckish = living_ants_query
+        .iter()
+        .any(|(role, hunger)| *role == AntRole::Queen && hunger.is_peckish(&balance_config.hunger));
+
+    for (courier_entity, mut food_delivery, digestion, inventory) in couriers_query.iter_mut() {
+        if food_delivery
+            .recipients
+            .iter()
+            .any(|recipient_entity| living_ants_query.get(*recipient_entity).is_err())
+        {
+            food_delivery
+                .recipients
+                .retain(|recipient_entity| living_ants_query.get(*recipient_entity).is_ok());
+        }
+
+        if !is_queen_peckish
+            || food_delivery.recipients.is_empty()
+            || !digestion.is_digesting()
+            || !inventory.is_empty()
+            || distracted_query.get(courier_entity).is_ok()
+        {
+            commands.entity(courier_entity).remove::<FoodDelivery>();
+        }
+    }
+}
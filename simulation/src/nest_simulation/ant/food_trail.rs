@@ -0,0 +1,204 @@
+use super::{
+    walk::get_turned_orientation, Ant, AntInventory, AntOrientation, AntRole, Dead, Initiative,
+};
+use crate::{
+    common::{
+        grid::{pheromone_layer::PheromoneLayer, GridElements},
+        intents::IntentsLog,
+        position::Position,
+    },
+    nest_simulation::{
+        element::Element,
+        nest::{AtNest, Nest},
+    },
+    save::ReflectPersist,
+};
+use bevy::prelude::*;
+use bevy_turborand::{DelegatedRng, GlobalRng};
+use serde::{Deserialize, Serialize};
+
+// Trails fade to a third of their strength after a minute, at normal speed, unless they're reinforced.
+const FOOD_PHEROMONE_DECAY_RATE: f32 = 0.002;
+// How much of a position's pheromone spreads to its neighbors each tick. Spreading widens trails so they're easier to find.
+const FOOD_PHEROMONE_DIFFUSION_RATE: f32 = 0.05;
+// How much pheromone an ant carrying food lays per tick, at most.
+const FOOD_PHEROMONE_DEPOSIT: f32 = 0.2;
+// Ants lay their strongest trail once they're this far from the queen.
+const FOOD_PHEROMONE_MAX_DEPOSIT_DISTANCE: f32 = 50.0;
+// Even on the strongest trail, ants sometimes wander off to explore.
+const DEFAULT_FORAGING_EXPLORATION: f32 = 0.2;
+// Faint traces are as likely to lead nowhere as anywhere, so they're ignored.
+const MIN_FOLLOWED_FOOD_PHEROMONE_STRENGTH: f32 = 0.05;
+// Ants differ from the colony's norm by up to this fraction, either way, in how they respond to trails.
+const FORAGING_TRAIT_VARIATION: f32 = 0.25;
+
+/// How an individual ant responds to food trails. A colony of keen trail followers forms a few strong trails while a
+/// colony of restless explorers spreads out. Ants vary a little so every colony has some of each.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect)]
+#[reflect(Component, Persist)]
+pub struct ForagingTraits {
+    /// Scales how strong trails smell to the ant. Keen ants notice fainter trails and stick to them more closely.
+    pub pheromone_sensitivity: f32,
+    /// The chance the ant wanders off a trail, no matter how strong it is, to explore.
+    pub exploration: f32,
+}
+
+impl Default for ForagingTraits {
+    fn default() -> Self {
+        Self {
+            pheromone_sensitivity: 1.0,
+            exploration: DEFAULT_FORAGING_EXPLORATION,
+        }
+    }
+}
+
+impl ForagingTraits {
+    pub fn random(rng: &mut Mut<GlobalRng>) -> Self {
+        let default = Self::default();
+        let mut vary = |value: f32| value * (1.0 + rng.f32_normalized() * FORAGING_TRAIT_VARIATION);
+
+        Self {
+            pheromone_sensitivity: vary(default.pheromone_sensitivity),
+            exploration: vary(default.exploration).clamp(0.0, 1.0),
+        }
+    }
+
+    /// How strong a trail of the given strength smells to the ant.
+    pub fn get_perceived_strength(&self, strength: f32) -> f32 {
+        (strength * self.pheromone_sensitivity).min(1.0)
+    }
+
+    /// How likely the ant is to turn back toward food after finding itself walking away from it.
+    pub fn get_follow_chance(&self, strength: f32) -> f32 {
+        self.get_perceived_strength(strength) * (1.0 - self.exploration)
+    }
+}
+
+/// Give newly spawned ants their foraging traits.
+/// NOTE: Ants from saves which predate foraging traits are given some when they're loaded.
+pub fn ants_add_foraging_traits(
+    ants_query: Query<Entity, (With<Ant>, Without<ForagingTraits>)>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    for ant_entity in ants_query.iter() {
+        commands
+            .entity(ant_entity)
+            .insert(ForagingTraits::random(&mut rng.reborrow()));
+    }
+}
+
+/// Pheromone laid by ants carrying food, which leads other ants to food.
+/// Note the intentional omission of reflection/serialization.
+/// Trails fade within minutes so they aren't worth persisting. Ants lay new ones soon after the story is loaded.
+#[derive(Component, Debug, Clone)]
+pub struct FoodPheromoneLayer(pub PheromoneLayer);
+
+pub fn food_pheromone_tick(mut food_pheromone_layer_query: Query<&mut FoodPheromoneLayer>) {
+    for mut food_pheromone_layer in food_pheromone_layer_query.iter_mut() {
+        food_pheromone_layer
+            .0
+            .tick(FOOD_PHEROMONE_DECAY_RATE, FOOD_PHEROMONE_DIFFUSION_RATE);
+    }
+}
+
+/// Ants carrying food lay a trail as they go. The trail is weak near the queen and strong far from her.
+/// So, ants following the trail toward stronger pheromone are led away from the nest and toward food.
+pub fn ants_deposit_food_pheromone(
+    ants_query: Query<(&Position, &AntInventory, &AntRole), (With<AtNest>, Without<Dead>)>,
+    mut food_pheromone_layer_query: Query<&mut FoodPheromoneLayer>,
+    elements_query: Query<&Element>,
+) {
+    let mut food_pheromone_layer = match food_pheromone_layer_query.get_single_mut() {
+        Ok(food_pheromone_layer) => food_pheromone_layer,
+        Err(_) => return,
+    };
+
+    let queen_position = ants_query
+        .iter()
+        .find(|(_, _, role)| **role == AntRole::Queen)
+        .map(|(position, _, _)| *position);
+
+    for (position, inventory, _) in ants_query.iter() {
+        let is_carrying_food = inventory.items().iter().any(|&element_entity| {
+            elements_query
+                .get(element_entity)
+                .map_or(false, |element| element.is_food())
+        });
+
+        if !is_carrying_food {
+            continue;
+        }
+
+        // Without a queen there's no nest to lead away from so lay an even trail.
+        let distance_scale = queen_position.map_or(1.0, |queen_position| {
+            (position.distance(&queen_position) as f32 / FOOD_PHEROMONE_MAX_DEPOSIT_DISTANCE)
+                .clamp(0.1, 1.0)
+        });
+
+        food_pheromone_layer
+            .0
+            .deposit(*position, FOOD_PHEROMONE_DEPOSIT * distance_scale);
+    }
+}
+
+/// Empty-handed workers which catch the scent of food turn around when they find themselves walking away from it.
+/// The stronger the scent, the more likely they are to stay on the trail. Each ant's foraging traits decide how
+/// strong the scent seems to them and how readily they wander off regardless.
+pub fn ants_follow_food_pheromone(
+    mut ants_query: Query<
+        (
+            Entity,
+            &mut Initiative,
+            &Position,
+            &mut AntOrientation,
+            &AntInventory,
+            &AntRole,
+            &ForagingTraits,
+        ),
+        With<AtNest>,
+    >,
+    food_pheromone_layer_query: Query<&FoodPheromoneLayer>,
+    nest_query: Query<&Nest>,
+    grid_elements: GridElements<AtNest>,
+    mut rng: ResMut<GlobalRng>,
+    mut intents_log: ResMut<IntentsLog>,
+) {
+    let food_pheromone_layer = match food_pheromone_layer_query.get_single() {
+        Ok(food_pheromone_layer) => &food_pheromone_layer.0,
+        Err(_) => return,
+    };
+
+    let nest = nest_query.single();
+
+    for (ant_entity, mut initiative, position, mut orientation, inventory, role, foraging_traits) in
+        ants_query.iter_mut()
+    {
+        if !initiative.can_move() || *role != AntRole::Worker || !inventory.is_empty() {
+            continue;
+        }
+
+        let strength = food_pheromone_layer.strength(*position);
+        if foraging_traits.get_perceived_strength(strength) < MIN_FOLLOWED_FOOD_PHEROMONE_STRENGTH {
+            continue;
+        }
+
+        let ahead_position = orientation.get_ahead_position(position);
+        if food_pheromone_layer.strength(ahead_position) >= strength {
+            continue;
+        }
+
+        if !rng.chance(foraging_traits.get_follow_chance(strength) as f64) {
+            continue;
+        }
+
+        intents_log.record(ant_entity, || {
+            format!("following trail strength {:.1}", strength)
+        });
+
+        *orientation =
+            get_turned_orientation(&orientation, &position, &nest, &mut rng, &grid_elements);
+
+        initiative.consume_movement();
+    }
+}
@@ -0,0 +1,155 @@
+use super::{infection::Infected, AntInventory, AntOrientation, AntRole, Dead, Initiative};
+use crate::{
+    common::position::Position, nest_simulation::nest::AtNest, save::ReflectPersist,
+    story_time::DEFAULT_TICKS_PER_SECOND,
+};
+use bevy::prelude::*;
+use bevy_turborand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Workers don't stop to groom every nestmate they meet or nothing else would get done.
+const GROOM_CHANCE: f32 = 0.05;
+// How long a groomed ant stays clean before spores and debris build back up. 1 hour.
+const GROOMED_TICKS: isize = 60 * 60 * DEFAULT_TICKS_PER_SECOND;
+// Grooming removes most, but not all, of the spores which land on an ant.
+const GROOMED_INFECTION_MULTIPLIER: f32 = 0.25;
+// A nestmate grooms more thoroughly than an ant can groom itself so it's more likely to clear an infection.
+const GROOMING_RECOVERY_CHANCE: f32 = 0.1;
+// Clean ants move a little more freely. Each turn, there's a chance their wait until the next turn is a tick shorter.
+pub const GROOMED_QUICK_TURN_CHANCE: f32 = 0.25;
+
+/// Ants which have recently been groomed by a nestmate. They're less likely to catch an infection, and move a little
+/// more quickly, until spores and debris build back up on them.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect)]
+#[reflect(Component, Persist)]
+pub struct Groomed {
+    remaining_ticks: isize,
+}
+
+impl Default for Groomed {
+    fn default() -> Self {
+        Self {
+            remaining_ticks: GROOMED_TICKS,
+        }
+    }
+}
+
+impl Groomed {
+    pub fn remaining_ticks(&self) -> isize {
+        self.remaining_ticks
+    }
+}
+
+/// How likely an ant is to catch an infection, as a multiple of the usual chance.
+pub fn get_infection_multiplier(groomed: Option<&Groomed>) -> f32 {
+    groomed.map_or(1.0, |_| GROOMED_INFECTION_MULTIPLIER)
+}
+
+/// Groomed ants gradually get dirty again.
+pub fn ants_groomed_tick(
+    mut ants_query: Query<(Entity, &mut Groomed), With<AtNest>>,
+    mut commands: Commands,
+) {
+    for (ant_entity, mut groomed) in ants_query.iter_mut() {
+        groomed.remaining_ticks -= 1;
+
+        if groomed.remaining_ticks <= 0 {
+            commands.entity(ant_entity).remove::<Groomed>();
+        }
+    }
+}
+
+// Step 1: Find all empty-handed workers which are ready to act.
+// Step 2: For each worker, look for a nestmate which needs grooming directly in front of it, or beneath it.
+// Step 3: If the nestmate in front is facing the worker, or is standing on the same spot, then groom it.
+/// Workers which meet a nestmate face-to-face sometimes stop to groom it, removing spores and debris. Groomed ants are
+/// protected from infection for a while, and grooming might clear an infection the nestmate has already caught.
+/// Grooming takes both ants' turns.
+pub fn ants_groom(
+    mut ants_query: Query<
+        (
+            Entity,
+            &AntOrientation,
+            &Position,
+            &AntRole,
+            &AntInventory,
+            &mut Initiative,
+            Option<&Groomed>,
+        ),
+        (With<AtNest>, Without<Dead>),
+    >,
+    infected_query: Query<(), (With<Infected>, With<AtNest>)>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    let groomers = ants_query
+        .iter()
+        .filter(|(_, _, _, role, inventory, initiative, _)| {
+            **role == AntRole::Worker && inventory.is_empty() && initiative.can_act()
+        })
+        .map(|(ant_entity, orientation, position, ..)| (ant_entity, *orientation, *position))
+        .collect::<Vec<_>>();
+
+    let mut results = vec![];
+
+    for (groomer_entity, groomer_orientation, groomer_position) in groomers {
+        let ahead_position = groomer_orientation.get_ahead_position(&groomer_position);
+
+        let groomee_entity = ants_query
+            .iter()
+            .find(
+                |(
+                    other_ant_entity,
+                    other_ant_orientation,
+                    &other_ant_position,
+                    _,
+                    _,
+                    other_ant_initiative,
+                    other_ant_groomed,
+                )| {
+                    if *other_ant_entity == groomer_entity
+                        || other_ant_groomed.is_some()
+                        || !other_ant_initiative.can_act()
+                    {
+                        return false;
+                    }
+
+                    // Like regurgitation, grooming happens face-to-face or when standing on top of one another.
+                    (other_ant_position == ahead_position
+                        && other_ant_orientation.get_ahead_position(&other_ant_position)
+                            == groomer_position)
+                        || other_ant_position == groomer_position
+                },
+            )
+            .map(|(other_ant_entity, ..)| other_ant_entity);
+
+        if let Some(groomee_entity) = groomee_entity {
+            if rng.f32() < GROOM_CHANCE {
+                results.push((groomer_entity, groomee_entity));
+            }
+        }
+    }
+
+    for (groomer_entity, groomee_entity) in results {
+        let Ok(
+            [(_, _, _, _, _, mut groomer_initiative, _), (_, _, _, _, _, mut groomee_initiative, _)],
+        ) = ants_query.get_many_mut([groomer_entity, groomee_entity])
+        else {
+            continue;
+        };
+
+        // Initiative is checked again because an ant may have already groomed, or been groomed, earlier this tick.
+        if !groomer_initiative.can_act() || !groomee_initiative.can_act() {
+            continue;
+        }
+
+        groomer_initiative.consume();
+        groomee_initiative.consume();
+
+        commands.entity(groomee_entity).insert(Groomed::default());
+
+        if infected_query.contains(groomee_entity) && rng.f32() < GROOMING_RECOVERY_CHANCE {
+            commands.entity(groomee_entity).remove::<Infected>();
+        }
+    }
+}
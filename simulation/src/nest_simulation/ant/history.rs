@@ -0,0 +1,168 @@
+use super::{collapse::Collapsed, hunger::Hunger, Ant, AntInventory, Dead};
+use crate::{
+    balance_config::BalanceConfig, nest_simulation::element::Element, save::ReflectPersist,
+    story_time::StoryTime,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Every ant is saved, so keep their histories short. Older entries are forgotten to make room for new ones.
+pub const MAX_ANT_HISTORY_ENTRIES: usize = 20;
+
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+pub enum AntHistoryEvent {
+    #[default]
+    Born,
+    FirstForaged,
+    NearStarvation,
+    Died,
+    Collapsed,
+    Revived,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+pub struct AntHistoryEntry {
+    tick: isize,
+    event: AntHistoryEvent,
+}
+
+impl AntHistoryEntry {
+    pub fn tick(&self) -> isize {
+        self.tick
+    }
+
+    pub fn event(&self) -> AntHistoryEvent {
+        self.event
+    }
+}
+
+/// Notable moments in an ant's life, oldest first, along with the tick at which they occurred.
+#[derive(Component, Debug, PartialEq, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Component, Persist)]
+pub struct AntHistory {
+    entries: Vec<AntHistoryEntry>,
+    // Tracked separately from entries because the entry may have been forgotten by the time the ant forages again.
+    has_foraged: bool,
+}
+
+impl AntHistory {
+    pub fn entries(&self) -> &Vec<AntHistoryEntry> {
+        &self.entries
+    }
+
+    pub fn record(&mut self, event: AntHistoryEvent, tick: isize) {
+        if self.entries.len() >= MAX_ANT_HISTORY_ENTRIES {
+            self.entries.remove(0);
+        }
+
+        self.entries.push(AntHistoryEntry { tick, event });
+    }
+}
+
+pub fn register_ant_history(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<AntHistory>();
+    app_type_registry.write().register::<AntHistoryEntry>();
+    app_type_registry.write().register::<AntHistoryEvent>();
+    app_type_registry.write().register::<Vec<AntHistoryEntry>>();
+}
+
+/// Start a history for newly spawned ants.
+/// NOTE: Ants from saves which predate histories are considered born when they're loaded.
+pub fn ants_history_born(
+    ants_query: Query<Entity, (With<Ant>, Without<AntHistory>)>,
+    story_time: Res<StoryTime>,
+    mut commands: Commands,
+) {
+    for ant_entity in ants_query.iter() {
+        let mut history = AntHistory::default();
+        history.record(AntHistoryEvent::Born, story_time.elapsed_ticks());
+
+        commands.entity(ant_entity).insert(history);
+    }
+}
+
+pub fn ants_history_first_foraged(
+    mut ants_query: Query<(&mut AntHistory, &AntInventory), Changed<AntInventory>>,
+    elements_query: Query<&Element>,
+    story_time: Res<StoryTime>,
+) {
+    for (mut history, inventory) in ants_query.iter_mut() {
+        if history.has_foraged {
+            continue;
+        }
+
+        let is_carrying_food = inventory.items().iter().any(|&element_entity| {
+            elements_query
+                .get(element_entity)
+                .map_or(false, |element| element.is_food())
+        });
+
+        if is_carrying_food {
+            history.has_foraged = true;
+            history.record(AntHistoryEvent::FirstForaged, story_time.elapsed_ticks());
+        }
+    }
+}
+
+pub fn ants_history_near_starvation(
+    mut ants_query: Query<(&mut AntHistory, &Hunger), (Changed<Hunger>, Without<Dead>)>,
+    story_time: Res<StoryTime>,
+    balance_config: Res<BalanceConfig>,
+) {
+    for (mut history, hunger) in ants_query.iter_mut() {
+        if hunger.has_started_starving(&balance_config.hunger) {
+            history.record(AntHistoryEvent::NearStarvation, story_time.elapsed_ticks());
+        }
+    }
+}
+
+/// NOTE: Every dead ant is seen as newly dead when a story is loaded, so don't record an ant's death twice.
+pub fn ants_history_died(
+    mut ants_query: Query<&mut AntHistory, Added<Dead>>,
+    story_time: Res<StoryTime>,
+) {
+    for mut history in ants_query.iter_mut() {
+        let has_died = history
+            .entries
+            .last()
+            .map_or(false, |entry| entry.event == AntHistoryEvent::Died);
+
+        if has_died {
+            continue;
+        }
+
+        history.record(AntHistoryEvent::Died, story_time.elapsed_ticks());
+    }
+}
+
+/// NOTE: Every collapsed ant is seen as newly collapsed when a story is loaded, so don't record an ant's collapse twice.
+pub fn ants_history_collapsed(
+    mut ants_query: Query<&mut AntHistory, Added<Collapsed>>,
+    story_time: Res<StoryTime>,
+) {
+    for mut history in ants_query.iter_mut() {
+        let has_collapsed = history
+            .entries
+            .last()
+            .map_or(false, |entry| entry.event == AntHistoryEvent::Collapsed);
+
+        if has_collapsed {
+            continue;
+        }
+
+        history.record(AntHistoryEvent::Collapsed, story_time.elapsed_ticks());
+    }
+}
+
+/// Collapsed ants which are no longer collapsed, but aren't dead, were revived.
+pub fn ants_history_revived(
+    mut removed_collapsed: RemovedComponents<Collapsed>,
+    mut ants_query: Query<&mut AntHistory, Without<Dead>>,
+    story_time: Res<StoryTime>,
+) {
+    for ant_entity in removed_collapsed.read() {
+        if let Ok(mut history) = ants_query.get_mut(ant_entity) {
+            history.record(AntHistoryEvent::Revived, story_time.elapsed_ticks());
+        }
+    }
+}
@@ -1,17 +1,30 @@
 use super::{
-    commands::AntCommandsExt, digestion::Digestion, AntAteFoodEvent, AntInventory, AntOrientation,
-    AntRole, Dead, Initiative,
+    collapse::Collapsed, commands::AntCommandsExt, digestion::Digestion, energy::Energy,
+    sleep::Asleep, Ant, AntAteFoodEvent, AntInventory, AntOrientation, AntRole, Dead, Initiative,
 };
 use crate::{
-    common::{grid::GridElements, position::Position},
-    nest_simulation::{element::Element, nest::AtNest},
+    balance_config::{BalanceConfig, HungerBalance},
+    common::{
+        food_sharing::FoodSharingNetwork, grid::GridElements, intents::IntentsLog,
+        logistics::ColonyLogistics, position::Position, reservation::ElementReservations,
+    },
+    nest_simulation::{
+        element::{commands::ElementCommandsExt, Element, FoodKind},
+        nest::AtNest,
+    },
+    save::ReflectPersist,
+    settings::Settings,
     story_time::DEFAULT_TICKS_PER_SECOND,
 };
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashMap};
+use bevy_turborand::prelude::*;
 use serde::{Deserialize, Serialize};
 
+// Hunger relayed from nestmates is forgotten over half a minute or so once an ant stops touching hungry nestmates.
+const SOLICITATION_DECAY: f32 = 0.003;
+
 #[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
-#[reflect(Component)]
+#[reflect(Component, Persist)]
 pub struct Hunger {
     value: f32,
     max: f32,
@@ -19,9 +32,22 @@ pub struct Hunger {
 }
 
 impl Hunger {
-    pub fn new(max_time_seconds: isize) -> Self {
+    /// An ant with no appetite modifier starves after `max_time_seconds` without food. Ants with a bigger appetite,
+    /// such as queens, starve sooner.
+    ///
+    /// ```
+    /// use simulation::nest_simulation::ant::hunger::Hunger;
+    ///
+    /// let mut worker_hunger = Hunger::new(60, 1.0);
+    /// let mut queen_hunger = Hunger::new(60, 2.0);
+    /// worker_hunger.tick();
+    /// queen_hunger.tick();
+    ///
+    /// assert_eq!(queen_hunger.value(), worker_hunger.value() * 2.0);
+    /// ```
+    pub fn new(max_time_seconds: isize, appetite: f32) -> Self {
         let max = 100.0;
-        let rate = max / (max_time_seconds * DEFAULT_TICKS_PER_SECOND) as f32;
+        let rate = max / (max_time_seconds * DEFAULT_TICKS_PER_SECOND) as f32 * appetite;
 
         Self {
             value: 0.0,
@@ -42,20 +68,41 @@ impl Hunger {
         self.value = (self.value + self.rate).min(self.max);
     }
 
-    pub fn is_full(&self) -> bool {
-        self.value < self.max * 0.25
+    /// Sleeping ants get hungry more slowly.
+    pub fn rest(&mut self, asleep_hunger_multiplier: f32) {
+        self.value = (self.value + self.rate * asleep_hunger_multiplier).min(self.max);
+    }
+
+    /// Sickness burns through an ant's reserves. Adds `multiplier` ticks worth of hunger.
+    pub fn sicken(&mut self, multiplier: f32) {
+        self.value = (self.value + self.rate * multiplier).min(self.max);
+    }
+
+    /// Hard work builds an appetite. Each unit of energy spent adds `hunger_per_energy` ticks worth of hunger.
+    pub fn exert(&mut self, energy: f32, hunger_per_energy: f32) {
+        self.value = (self.value + self.rate * energy * hunger_per_energy).min(self.max);
+    }
+
+    pub fn is_full(&self, hunger_balance: &HungerBalance) -> bool {
+        self.value < self.max * hunger_balance.peckish
+    }
+
+    pub fn is_peckish(&self, hunger_balance: &HungerBalance) -> bool {
+        self.value >= self.max * hunger_balance.peckish
     }
 
-    pub fn is_peckish(&self) -> bool {
-        self.value >= self.max * 0.25
+    pub fn is_hungry(&self, hunger_balance: &HungerBalance) -> bool {
+        self.value >= self.max * hunger_balance.hungry
     }
 
-    pub fn is_hungry(&self) -> bool {
-        self.value >= self.max * 0.50
+    pub fn is_starving(&self, hunger_balance: &HungerBalance) -> bool {
+        self.value >= self.max * hunger_balance.starving
     }
 
-    pub fn is_starving(&self) -> bool {
-        self.value >= self.max * 0.75
+    /// True only on the tick hunger crossed the starving threshold, so the moment can be noticed without extra state.
+    pub fn has_started_starving(&self, hunger_balance: &HungerBalance) -> bool {
+        self.is_starving(hunger_balance)
+            && self.value - self.rate < self.max * hunger_balance.starving
     }
 
     pub fn is_starved(&self) -> bool {
@@ -63,11 +110,47 @@ impl Hunger {
     }
 }
 
-// TODO: Ants stop getting hungry while asleep which isn't really intended, but I haven't thought through Initative removal enough clearly
-// because sometimes I want it for Dead + Sleep, sometimes just one or the other, and it's becoming a leaky abstraction.
-pub fn ants_hunger_tick(mut ants_hunger_query: Query<&mut Hunger, (Without<Dead>, With<AtNest>)>) {
-    for mut hunger in ants_hunger_query.iter_mut() {
-        hunger.tick();
+/// How hungry the nestmates an ant has recently touched are, as a fraction of max hunger. Ants pass this along to
+/// the nestmates they touch, so ants which aren't hungry themselves know to fetch food for those who are.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Component, Persist)]
+pub struct Solicitation(f32);
+
+impl Solicitation {
+    pub fn value(&self) -> f32 {
+        self.0
+    }
+}
+
+/// NOTE: This applies to every ant, not just those in the nest, so ants from saves which predate solicitation can
+/// share food, too.
+pub fn ants_add_solicitation(
+    ants_query: Query<Entity, (With<Ant>, Without<Solicitation>)>,
+    mut commands: Commands,
+) {
+    for ant_entity in ants_query.iter() {
+        commands.entity(ant_entity).insert(Solicitation::default());
+    }
+}
+
+// NOTE: Sleeping ants still get hungry, just more slowly, so a colony which sleeps through the night wakes up peckish.
+pub fn ants_hunger_tick(
+    mut ants_hunger_query: Query<
+        (&mut Hunger, Option<&mut Energy>, Option<&Asleep>),
+        (Without<Dead>, With<AtNest>),
+    >,
+    settings: Res<Settings>,
+) {
+    for (mut hunger, energy, asleep) in ants_hunger_query.iter_mut() {
+        if asleep.is_some() {
+            hunger.rest(settings.asleep_hunger_multiplier);
+        } else {
+            hunger.tick();
+        }
+
+        if let Some(mut energy) = energy {
+            hunger.exert(energy.take_spent(), settings.energy.hunger_per_energy);
+        }
     }
 }
 
@@ -85,54 +168,215 @@ pub fn ants_hunger_act(
         ),
         With<AtNest>,
     >,
+    elements_query: Query<(&Element, Option<&FoodKind>)>,
     grid_elements: GridElements<AtNest>,
+    mut element_reservations: ResMut<ElementReservations>,
     mut commands: Commands,
     mut ant_ate_food_event_writer: EventWriter<AntAteFoodEvent>,
+    balance_config: Res<BalanceConfig>,
+    settings: Res<Settings>,
+    mut rng: ResMut<GlobalRng>,
+    mut intents_log: ResMut<IntentsLog>,
+    mut colony_logistics: ResMut<ColonyLogistics>,
 ) {
     for (ant_entity, hunger, mut digestion, orientation, position, mut inventory, mut initiative) in
         ants_hunger_query.iter_mut()
     {
         if hunger.is_starved() {
+            intents_log.record(ant_entity, || "starved: collapsing".to_string());
+
+            // Starved ants collapse rather than dying outright, giving other ants a chance to feed them.
             commands
                 .entity(ant_entity)
-                .insert(Dead)
+                .insert(Collapsed::default())
                 .remove::<Initiative>();
-        } else if hunger.is_peckish() {
+        } else if hunger.is_peckish(&balance_config.hunger) {
             if !initiative.can_act() {
                 continue;
             }
 
-            // If there is food near the hungry ant then pick it up and if the ant is holding food then eat it.
-            if inventory.0 == None {
+            // If there is food, or fungus, near the hungry ant then pick it up and if the ant is holding food then eat it.
+            if inventory.is_empty() {
                 let ahead_position = orientation.get_ahead_position(position);
-                if grid_elements.is(ahead_position, Element::Food) {
+                if grid_elements.is_food(ahead_position)
+                    || grid_elements.is(ahead_position, Element::Fungus)
+                {
                     let food_entity = grid_elements.entity(ahead_position);
-                    commands.dig(ant_entity, ahead_position, *food_entity, AtNest);
+                    if element_reservations.try_reserve(*food_entity, ant_entity) {
+                        intents_log.record(ant_entity, || "hungry: picking up food".to_string());
+                        commands.dig(ant_entity, ahead_position, *food_entity, AtNest);
+                    } else {
+                        intents_log.record(ant_entity, || {
+                            "hungry: food ahead already taken".to_string()
+                        });
+                    }
+                } else {
+                    intents_log.record(ant_entity, || "hungry: seeking food".to_string());
                 }
             } else {
-                let element = grid_elements.element(inventory.0.unwrap());
+                // Inventory items aren't in the grid so they're looked up directly rather than through `grid_elements`.
+                let item_entity = inventory.last().unwrap();
+                let (element, food_kind) = elements_query.get(item_entity).unwrap();
 
-                if *element == Element::Food {
-                    inventory.0 = None;
+                if element.is_food() {
+                    let food_kind = food_kind.copied().unwrap_or_default();
+                    let nutrition = grid_elements
+                        .definitions()
+                        .get_food_kind(food_kind)
+                        .nutrition();
 
-                    digestion.increment(-0.20);
+                    intents_log.record(ant_entity, || format!("hungry: eating {:?}", food_kind));
+
+                    inventory.pop();
+                    commands.entity(item_entity).despawn();
+
+                    digestion.eat(balance_config.food_digestion * nutrition, food_kind);
                     initiative.consume();
 
                     ant_ate_food_event_writer.send(AntAteFoodEvent(ant_entity));
+                    colony_logistics.record_eaten();
+
+                    // Meals sometimes leave scraps behind, which are left wherever the ant happened to eat.
+                    let ahead_position = orientation.get_ahead_position(position);
+                    if grid_elements.is(ahead_position, Element::Air)
+                        && rng.chance(settings.probabilities.meal_waste.into())
+                    {
+                        let air_entity = *grid_elements.entity(ahead_position);
+                        if element_reservations.try_reserve(air_entity, ant_entity) {
+                            commands.replace_element(
+                                ahead_position,
+                                Element::Waste,
+                                air_entity,
+                                AtNest,
+                            );
+                        }
+                    }
                 }
             }
         }
     }
 }
 
+/// How much food one ant can regurgitate to another. Ants can't give away more than they've yet to digest, nor more
+/// than the other ant has room for.
+///
+/// ```
+/// use simulation::nest_simulation::{
+///     ant::{digestion::Digestion, hunger::get_regurgitation_amount},
+///     element::FoodKind,
+/// };
+///
+/// let mut fed_ant = Digestion::new(3_600);
+/// fed_ant.eat(0.3, FoodKind::Plant);
+/// let empty_ant = Digestion::new(3_600);
+///
+/// assert_eq!(get_regurgitation_amount(&fed_ant, &empty_ant, 0.2), 20.0);
+/// assert_eq!(get_regurgitation_amount(&empty_ant, &fed_ant, 0.2), 0.0);
+/// ```
+pub fn get_regurgitation_amount(
+    giver_digestion: &Digestion,
+    receiver_digestion: &Digestion,
+    regurgitation_transfer: f32,
+) -> f32 {
+    (giver_digestion.max() * regurgitation_transfer)
+        .min(giver_digestion.undigested())
+        .min(receiver_digestion.value())
+        .max(0.0)
+}
+
+/// Ants touching face-to-face, or standing on top of one another, can pass food between them.
+pub fn is_in_contact(
+    ant_entity: Entity,
+    ant_position: &Position,
+    ant_orientation: &AntOrientation,
+    other_ant_entity: Entity,
+    other_ant_position: &Position,
+    other_ant_orientation: &AntOrientation,
+) -> bool {
+    if ant_entity == other_ant_entity {
+        return false;
+    }
+
+    // Support ontop of as well as in front because its kinda challenging to ensure queen can have an ant directly in front of them.
+    if ant_position == other_ant_position {
+        return true;
+    }
+
+    ant_orientation.get_ahead_position(ant_position) == *other_ant_position
+        && other_ant_orientation.get_ahead_position(other_ant_position) == *ant_position
+}
+
+/// How badly an ant wants food, as a fraction of max hunger, whether for itself or on behalf of nestmates it's touched.
+fn get_need(hunger: &Hunger, solicitation: &Solicitation) -> f32 {
+    (hunger.value() / hunger.max).max(solicitation.0)
+}
+
+/// Ants pass on the hunger of the nestmates they touch, a little weakened each time, and forget it over time.
+/// This is what lets food be relayed, over successive ticks, toward hungry ants deep in the nest.
+pub fn ants_solicit(
+    mut ants_query: Query<
+        (
+            Entity,
+            &Hunger,
+            &AntOrientation,
+            &Position,
+            &mut Solicitation,
+        ),
+        (With<AtNest>, Without<Dead>),
+    >,
+    balance_config: Res<BalanceConfig>,
+) {
+    let mut ants_by_position: HashMap<Position, Vec<(Entity, AntOrientation, f32)>> =
+        HashMap::new();
+
+    for (ant_entity, hunger, orientation, position, solicitation) in ants_query.iter() {
+        ants_by_position.entry(*position).or_default().push((
+            ant_entity,
+            *orientation,
+            get_need(hunger, solicitation),
+        ));
+    }
+
+    for (ant_entity, _, orientation, position, mut solicitation) in ants_query.iter_mut() {
+        let ahead_position = orientation.get_ahead_position(position);
+
+        let relayed_need = [*position, ahead_position]
+            .into_iter()
+            .flat_map(|contact_position| {
+                ants_by_position
+                    .get(&contact_position)
+                    .into_iter()
+                    .flatten()
+                    .filter(move |(other_ant_entity, other_ant_orientation, _)| {
+                        is_in_contact(
+                            ant_entity,
+                            position,
+                            orientation,
+                            *other_ant_entity,
+                            &contact_position,
+                            other_ant_orientation,
+                        )
+                    })
+            })
+            .map(|(_, _, other_ant_need)| other_ant_need * balance_config.solicitation_relay)
+            .fold(0.0, f32::max);
+
+        solicitation.0 = (solicitation.0 - SOLICITATION_DECAY)
+            .max(relayed_need)
+            .max(0.0);
+    }
+}
+
 // If an ant is face-to-face with another ant then it is able to regurgitate food from itself to the other ant.
-// It will only do this if the other ant is hungry.
-// If the queen is starving then a worker will transfer food to it irrespective of the workers hunger level. The worker gives all it has up to 20%.
+// It will only do this if the other ant needs food more than it does, whether for itself or for a hungrier nestmate.
+// If the queen needs food then a worker will transfer food to it irrespective of the workers hunger level. The worker gives all it has up to BalanceConfig.regurgitation_transfer.
 // If the other ant is hungry, then a worker will transfer food if it is well fed. This ensures workers don't spend time transferring food to a hungry ant but, in the process, make themselves hungry.
+// Ants which aren't hungry themselves still accept food on behalf of hungry nestmates they've touched. They pass it along
+// when next face-to-face with someone hungrier so food is relayed, hop by hop, toward whoever needs it most.
 
-// Step 1: Find all ants which are hungry or worse.
-// Step 2: For each hungry-or-worse ant, look at the position directly in front of it.
-// Step 3: If there is an ant in that position, and if that ant is facing towards the hungry ant, then transfer food to the hungry ant.
+// Step 1: Find all ants which need food, for themselves or others.
+// Step 2: For each needy ant, look at the position directly in front of it.
+// Step 3: If there is an ant in that position, and if that ant is facing towards the needy ant, then transfer food to the needy ant.
 pub fn ants_regurgitate(
     mut ants_hunger_query: Query<
         (
@@ -143,77 +387,122 @@ pub fn ants_regurgitate(
             &Position,
             &mut AntInventory,
             &mut Initiative,
-            &mut AntRole,
+            &AntRole,
+            &Solicitation,
         ),
         With<AtNest>,
     >,
     mut ant_ate_food_event_writer: EventWriter<AntAteFoodEvent>,
+    balance_config: Res<BalanceConfig>,
+    mut food_sharing_network: ResMut<FoodSharingNetwork>,
 ) {
-    let peckish_ants = ants_hunger_query
+    let hunger_balance = &balance_config.hunger;
+
+    let needy_ants = ants_hunger_query
         .iter()
-        .filter(|(_, hunger, _, _, _, inventory, initiative, _)| {
-            initiative.can_act() && hunger.is_peckish() && inventory.0 == None
-        })
+        .filter(
+            |(_, hunger, _, _, _, inventory, initiative, _, solicitation)| {
+                initiative.can_act()
+                    && get_need(hunger, solicitation) >= hunger_balance.peckish
+                    && inventory.is_empty()
+            },
+        )
         .collect::<Vec<_>>();
 
     let mut results = vec![];
 
-    for (ant_entity, ant_hunger, _, ant_orientation, ant_position, _, _, ant_role) in peckish_ants {
-        let ahead_position = ant_orientation.get_ahead_position(ant_position);
+    for (
+        ant_entity,
+        ant_hunger,
+        ant_digestion,
+        ant_orientation,
+        ant_position,
+        _,
+        _,
+        ant_role,
+        ant_solicitation,
+    ) in needy_ants
+    {
+        let ant_need = get_need(ant_hunger, ant_solicitation);
 
-        if let Some((other_ant_entity, other_ant_hunger, other_ant_digestion, _, _, _, _, _)) =
-            ants_hunger_query
-                .iter()
-                // Support ontop of as well as in front because its kinda challenging to ensure queen can have an ant directly in front of them.
-                .find(
-                    |(
-                        other_ant_entity,
-                        _,
-                        _,
+        let other_ant = ants_hunger_query.iter().find(
+            |(
+                other_ant_entity,
+                _,
+                _,
+                other_ant_orientation,
+                other_ant_position,
+                other_ant_inventory,
+                other_ant_initiative,
+                _,
+                _,
+            )| {
+                other_ant_initiative.can_act()
+                    && other_ant_inventory.is_empty()
+                    && is_in_contact(
+                        ant_entity,
+                        ant_position,
+                        ant_orientation,
+                        *other_ant_entity,
+                        other_ant_position,
                         other_ant_orientation,
-                        &other_ant_position,
-                        other_ant_inventory,
-                        other_ant_initiative,
-                        _,
-                    )| {
-                        if !other_ant_initiative.can_act() || other_ant_inventory.0 != None {
-                            return false;
-                        }
+                    )
+            },
+        );
 
-                        // If ants are adjacent and facing one another - allow regurgitation.
-                        if other_ant_position == ahead_position
-                            && other_ant_orientation.get_ahead_position(&other_ant_position)
-                                == *ant_position
-                        {
-                            return true;
-                        }
+        let Some((
+            other_ant_entity,
+            other_ant_hunger,
+            other_ant_digestion,
+            _,
+            _,
+            _,
+            _,
+            other_ant_role,
+            other_ant_solicitation,
+        )) = other_ant
+        else {
+            continue;
+        };
 
-                        // If ants are standing ontop of one another (and not the same ant) - allow regurgitation
-                        if other_ant_position == *ant_position && *other_ant_entity != ant_entity {
-                            return true;
-                        }
+        // Food only flows toward ants which need it more so it isn't passed back and forth between relaying ants.
+        if get_need(other_ant_hunger, other_ant_solicitation) >= ant_need {
+            continue;
+        }
 
-                        return false;
-                    },
-                )
+        if *ant_role == AntRole::Queen
+            || (ant_need >= hunger_balance.starving && !other_ant_hunger.is_hungry(hunger_balance))
+            || (ant_need >= hunger_balance.hungry && other_ant_hunger.is_full(hunger_balance))
         {
-            if *ant_role == AntRole::Queen
-                || (ant_hunger.is_starving() && !other_ant_hunger.is_hungry())
-                || (ant_hunger.is_hungry() && other_ant_hunger.is_full())
-            {
-                // Transfer up to 20% digestion from other_ant to ant.
-                let digestion_transfer_amount =
-                    (other_ant_digestion.max() * 0.20).min(other_ant_digestion.value());
-
-                if digestion_transfer_amount > 0.0 {
-                    results.push((ant_entity, other_ant_entity, digestion_transfer_amount));
-                }
+            let digestion_transfer_amount = get_regurgitation_amount(
+                other_ant_digestion,
+                ant_digestion,
+                balance_config.regurgitation_transfer,
+            );
+
+            if digestion_transfer_amount > 0.0 {
+                results.push((
+                    ant_entity,
+                    other_ant_entity,
+                    digestion_transfer_amount,
+                    *other_ant_role,
+                    *ant_role,
+                    !ant_hunger.is_peckish(hunger_balance),
+                ));
             }
         }
     }
 
-    for (ant_entity, other_ant_entity, digestion_transfer_amount) in results {
-        let [(_, _, mut digestion, _, _, _, mut ant_initiative, _), (_, _, mut other_ant_digestion, _, _, _, mut other_ant_initiative, _)] =
+    for (
+        ant_entity,
+        other_ant_entity,
+        digestion_transfer_amount,
+        giver_role,
+        receiver_role,
+        is_relay,
+    ) in results
+    {
+        let [(_, _, mut digestion, _, _, _, mut ant_initiative, _, _), (_, _, mut other_ant_digestion, _, _, _, mut other_ant_initiative, _, _)] =
             ants_hunger_query
                 .get_many_mut([ant_entity, other_ant_entity])
                 .unwrap();
@@ -225,14 +514,16 @@ pub fn ants_regurgitate(
             continue;
         }
 
-        // TODO: Consider `is_digesting` because an ant remains hungry for a while, even if its digesting food,
-        // so it would be possible to transfer it too much food if don't consider how full its stomach is
-        digestion.value -= digestion_transfer_amount;
-        other_ant_digestion.value += digestion_transfer_amount;
+        other_ant_digestion.regurgitate_to(&mut digestion, digestion_transfer_amount);
 
         ant_initiative.consume();
         other_ant_initiative.consume();
 
-        ant_ate_food_event_writer.send(AntAteFoodEvent(ant_entity));
+        // Relaying ants are only holding food for someone else so they haven't eaten.
+        if !is_relay {
+            ant_ate_food_event_writer.send(AntAteFoodEvent(ant_entity));
+        }
+
+        food_sharing_network.record_share(giver_role, receiver_role, is_relay);
     }
 }
@@ -0,0 +1,207 @@
+use super::{
+    brood::Brood,
+    commands::AntCommandsExt,
+    grooming::{get_infection_multiplier, Groomed},
+    hunger::Hunger,
+    AntInventory, Dead, Initiative,
+};
+use crate::{
+    common::{grid::GridElements, position::Position},
+    nest_simulation::{
+        element::{Element, Waste},
+        nest::AtNest,
+    },
+    save::ReflectPersist,
+    settings::Settings,
+    story_time::{DEFAULT_TICKS_PER_SECOND, SECONDS_PER_DAY},
+};
+use bevy::prelude::*;
+use bevy_turborand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// How long an infection takes to kill an ant which doesn't shake it off. 3 days.
+const INFECTION_TICKS: isize = 3 * SECONDS_PER_DAY * DEFAULT_TICKS_PER_SECOND;
+// Infected ants grow hungrier faster as the infection takes hold. By the end they're going hungry this much faster.
+const INFECTION_HUNGER_MULTIPLIER: f32 = 1.0;
+// Infected ants stop to groom spores off themselves, now and then, and sometimes that's enough to clear the infection.
+const SELF_GROOM_CHANCE: f32 = 0.001;
+const GROOM_RECOVERY_CHANCE: f32 = 0.05;
+// Infected ants which keep away from their nestmates might recover on their own.
+const ISOLATION_RADIUS: isize = 3;
+const ISOLATION_RECOVERY_CHANCE: f32 = 0.0002;
+// Waste left lying this close to the brood breeds spores which ants tending the brood might catch.
+const WASTE_BROOD_RADIUS: isize = 4;
+
+/// Fungal spores which have taken hold of an ant. Infections spread on contact, make the ant hungrier the longer
+/// they last, and kill the ant unless it grooms the spores away or keeps to itself long enough to recover.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect)]
+#[reflect(Component, Persist)]
+pub struct Infected {
+    remaining_ticks: isize,
+}
+
+impl Default for Infected {
+    fn default() -> Self {
+        Self {
+            remaining_ticks: INFECTION_TICKS,
+        }
+    }
+}
+
+impl Infected {
+    pub fn remaining_ticks(&self) -> isize {
+        self.remaining_ticks
+    }
+
+    /// How far along the infection is. Zero when freshly caught and one when it's about to kill.
+    pub fn severity(&self) -> f32 {
+        1.0 - self.remaining_ticks as f32 / INFECTION_TICKS as f32
+    }
+}
+
+/// Infections worsen over time. Infected ants grow hungry more quickly and die once the infection has run its course.
+/// Turning disease off in settings cures every ant.
+pub fn ants_infection_tick(
+    mut ants_query: Query<(Entity, &mut Infected, &mut Hunger), (With<AtNest>, Without<Dead>)>,
+    settings: Res<Settings>,
+    mut commands: Commands,
+) {
+    for (ant_entity, mut infected, mut hunger) in ants_query.iter_mut() {
+        if !settings.is_disease_enabled {
+            commands.entity(ant_entity).remove::<Infected>();
+            continue;
+        }
+
+        infected.remaining_ticks -= 1;
+        hunger.sicken(infected.severity() * INFECTION_HUNGER_MULTIPLIER);
+
+        if infected.remaining_ticks <= 0 {
+            commands.kill_ant(ant_entity);
+            commands.entity(ant_entity).remove::<Infected>();
+        }
+    }
+}
+
+/// Healthy ants catch infections from infected nestmates they touch. Spores also linger on corpses so ants which
+/// handle the dead, or brush past them, might catch an infection from them. Ants tending brood which has waste piled up
+/// around it are at risk too, more so the more waste has piled up. Recently groomed ants are less likely to catch
+/// anything.
+pub fn ants_catch_infection(
+    ants_query: Query<
+        (
+            Entity,
+            &Position,
+            &AntInventory,
+            Option<&Infected>,
+            Option<&Groomed>,
+        ),
+        (With<AtNest>, Without<Dead>),
+    >,
+    brood_query: Query<&Position, (With<Brood>, With<AtNest>)>,
+    waste_query: Query<&Position, (With<Waste>, With<AtNest>)>,
+    elements_query: Query<&Element>,
+    grid_elements: GridElements<AtNest>,
+    settings: Res<Settings>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    if !settings.is_disease_enabled {
+        return;
+    }
+
+    // How much waste has piled up around each brood.
+    let brood_waste = brood_query
+        .iter()
+        .map(|brood_position| {
+            let waste_count = waste_query
+                .iter()
+                .filter(|waste_position| {
+                    brood_position.distance(waste_position) <= WASTE_BROOD_RADIUS
+                })
+                .count();
+
+            (*brood_position, waste_count)
+        })
+        .filter(|(_, waste_count)| *waste_count > 0)
+        .collect::<Vec<_>>();
+
+    let infected_positions = ants_query
+        .iter()
+        .filter(|(_, _, _, infected, _)| infected.is_some())
+        .map(|(_, position, ..)| *position)
+        .collect::<Vec<_>>();
+
+    for (ant_entity, position, inventory, infected, groomed) in ants_query.iter() {
+        if infected.is_some() {
+            continue;
+        }
+
+        let is_touching_infected = infected_positions
+            .iter()
+            .any(|infected_position| position.distance(infected_position) <= 1);
+
+        let is_touching_corpse = inventory
+            .items()
+            .iter()
+            .any(|&element_entity| elements_query.get(element_entity) == Ok(&Element::Corpse))
+            || position
+                .get_adjacent_positions()
+                .into_iter()
+                .any(|adjacent_position| grid_elements.is(adjacent_position, Element::Corpse));
+
+        let nearby_waste_count = brood_waste
+            .iter()
+            .filter(|(brood_position, _)| position.distance(brood_position) <= 1)
+            .map(|(_, waste_count)| *waste_count)
+            .max()
+            .unwrap_or(0);
+
+        let infection_multiplier = get_infection_multiplier(groomed);
+        let infection_spread = settings.probabilities.infection_spread * infection_multiplier;
+        let corpse_infection = settings.probabilities.corpse_infection * infection_multiplier;
+        let waste_infection = (settings.probabilities.waste_infection
+            * nearby_waste_count as f32
+            * infection_multiplier)
+            .min(1.0);
+
+        let is_infected = (is_touching_infected && rng.chance(infection_spread.into()))
+            || (is_touching_corpse && rng.chance(corpse_infection.into()))
+            || (nearby_waste_count > 0 && rng.chance(waste_infection.into()));
+
+        if is_infected {
+            commands.entity(ant_entity).insert(Infected::default());
+        }
+    }
+}
+
+/// Infected ants sometimes spend a turn grooming themselves, which might clear the infection. Infected ants which
+/// have kept away from their nestmates might also shake it off on their own.
+pub fn ants_recover_from_infection(
+    mut infected_ants_query: Query<
+        (Entity, &Position, &mut Initiative),
+        (With<Infected>, With<AtNest>, Without<Dead>),
+    >,
+    ants_query: Query<(Entity, &Position), (With<AtNest>, Without<Dead>)>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    for (ant_entity, position, mut initiative) in infected_ants_query.iter_mut() {
+        if initiative.can_act() && rng.f32() < SELF_GROOM_CHANCE {
+            initiative.consume();
+
+            if rng.f32() < GROOM_RECOVERY_CHANCE {
+                commands.entity(ant_entity).remove::<Infected>();
+            }
+
+            continue;
+        }
+
+        let is_isolated = ants_query.iter().all(|(other_ant_entity, other_position)| {
+            other_ant_entity == ant_entity || position.distance(other_position) > ISOLATION_RADIUS
+        });
+
+        if is_isolated && rng.f32() < ISOLATION_RECOVERY_CHANCE {
+            commands.entity(ant_entity).remove::<Infected>();
+        }
+    }
+}
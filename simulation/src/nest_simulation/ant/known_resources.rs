@@ -0,0 +1,140 @@
+use super::{Ant, AntRole, Dead};
+use crate::{
+    common::{grid::GridElements, position::Position},
+    nest_simulation::nest::{AtNest, Nest},
+    save::ReflectPersist,
+    story_time::DEFAULT_TICKS_PER_SECOND,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Ants only keep a few places in mind at once. Older memories make room for newer ones.
+const MAX_KNOWN_RESOURCES: usize = 3;
+// Food found on the surface tends to be picked clean, or blown away, before long. 30 minutes.
+const KNOWN_RESOURCE_TICKS: isize = 60 * 30 * DEFAULT_TICKS_PER_SECOND;
+
+/// Somewhere an ant stood when it saw food. It's remembered as the place to stand, rather than where the food lay,
+/// so the ant has somewhere it can walk to when it heads back.
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+pub struct KnownResource {
+    position: Position,
+    remaining_ticks: isize,
+}
+
+impl KnownResource {
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    pub fn remaining_ticks(&self) -> isize {
+        self.remaining_ticks
+    }
+}
+
+/// Where an ant has seen food while foraging. Empty-handed workers head back to food they remember before wandering
+/// off to find more. Memories fade with time and are forgotten early when the ant returns to find the food gone.
+///
+/// ```
+/// use simulation::{common::position::Position, nest_simulation::ant::known_resources::KnownResources};
+///
+/// let mut known_resources = KnownResources::default();
+/// for x in 0..5 {
+///     known_resources.remember(Position::new(x, 0));
+/// }
+///
+/// // Only the most recent memories are kept.
+/// assert_eq!(known_resources.entries().len(), 3);
+/// assert_eq!(known_resources.get_nearest(Position::new(0, 0)), Some(Position::new(2, 0)));
+///
+/// known_resources.forget(Position::new(2, 0));
+/// assert_eq!(known_resources.get_nearest(Position::new(0, 0)), Some(Position::new(3, 0)));
+/// ```
+#[derive(Component, Debug, PartialEq, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Component, Persist)]
+pub struct KnownResources {
+    entries: Vec<KnownResource>,
+}
+
+impl KnownResources {
+    pub fn entries(&self) -> &[KnownResource] {
+        &self.entries
+    }
+
+    /// Remembering somewhere already known refreshes the memory rather than taking up more room.
+    pub fn remember(&mut self, position: Position) {
+        self.forget(position);
+
+        if self.entries.len() >= MAX_KNOWN_RESOURCES {
+            self.entries.remove(0);
+        }
+
+        self.entries.push(KnownResource {
+            position,
+            remaining_ticks: KNOWN_RESOURCE_TICKS,
+        });
+    }
+
+    pub fn forget(&mut self, position: Position) {
+        self.entries.retain(|entry| entry.position != position);
+    }
+
+    pub fn get_nearest(&self, position: Position) -> Option<Position> {
+        self.entries
+            .iter()
+            .min_by_key(|entry| position.distance(&entry.position))
+            .map(|entry| entry.position)
+    }
+
+    fn tick(&mut self) {
+        for entry in self.entries.iter_mut() {
+            entry.remaining_ticks -= 1;
+        }
+
+        self.entries.retain(|entry| entry.remaining_ticks > 0);
+    }
+}
+
+/// Give newly spawned ants somewhere to keep their memories.
+/// NOTE: Ants from saves which predate memories start off remembering nothing.
+pub fn ants_add_known_resources(
+    ants_query: Query<Entity, (With<Ant>, Without<KnownResources>)>,
+    mut commands: Commands,
+) {
+    for ant_entity in ants_query.iter() {
+        commands
+            .entity(ant_entity)
+            .insert(KnownResources::default());
+    }
+}
+
+/// Workers remember food they pass by on the surface. Food stored underground is the colony's, not a find worth
+/// returning to. Workers back where they remember seeing food, who find it's gone, forget about it.
+pub fn ants_remember_food(
+    mut ants_query: Query<
+        (&Position, &AntRole, &mut KnownResources),
+        (With<AtNest>, Without<Dead>),
+    >,
+    nest_query: Query<&Nest>,
+    grid_elements: GridElements<AtNest>,
+) {
+    let nest = nest_query.single();
+
+    for (position, role, mut known_resources) in ants_query.iter_mut() {
+        known_resources.tick();
+
+        if *role != AntRole::Worker {
+            continue;
+        }
+
+        let is_beside_food = position
+            .get_adjacent_positions()
+            .into_iter()
+            .any(|adjacent_position| grid_elements.is_food(adjacent_position));
+
+        if is_beside_food && nest.is_aboveground(position) {
+            known_resources.remember(*position);
+        } else if !is_beside_food && known_resources.get_nearest(*position) == Some(*position) {
+            known_resources.forget(*position);
+        }
+    }
+}
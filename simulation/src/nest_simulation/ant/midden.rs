@@ -0,0 +1,87 @@
+use crate::{
+    common::{
+        grid::{Grid, GridElements},
+        pathfinding::is_walkable,
+        position::Position,
+    },
+    nest_simulation::{
+        colony_planner::{ChamberPurpose, ColonyPlanner},
+        element::Element,
+        nest::{get_nest_entrance, AtNest, Nest},
+    },
+};
+
+// Far enough from the entrance that refuse isn't tracked back into the nest, but close enough to be a short walk.
+const MIDDEN_ENTRANCE_DISTANCE: isize = 16;
+// Corpses anywhere this close to the midden have been taken far enough.
+const MIDDEN_RADIUS: isize = 3;
+
+/// Ants pile their refuse on the surface a short way from the entrance. The midden is on whichever side of the
+/// entrance has more room so the pile doesn't spill over the edge of the world.
+/// There's no midden until there's an entrance to carry refuse out of.
+pub fn get_midden_position(
+    grid: &Grid,
+    nest: &Nest,
+    grid_elements: &GridElements<AtNest>,
+) -> Option<Position> {
+    let nest_width = grid.width();
+    let entrance_position = get_nest_entrance(nest, grid_elements)?;
+
+    let midden_x = if entrance_position.x < nest_width / 2 {
+        entrance_position.x + MIDDEN_ENTRANCE_DISTANCE
+    } else {
+        entrance_position.x - MIDDEN_ENTRANCE_DISTANCE
+    }
+    .clamp(0, nest_width - 1);
+
+    // Refuse piles up over time so the midden is on top of whatever has already been dropped there.
+    let ground_y = (0..=nest.surface_level() + 1)
+        .find(|&y| !grid_elements.is(Position::new(midden_x, y), Element::Air))?;
+
+    (ground_y > 0).then_some(Position::new(midden_x, ground_y - 1))
+}
+
+pub fn is_at_midden(position: Position, midden_position: Option<Position>) -> bool {
+    midden_position.map_or(false, |midden_position| {
+        position.distance(&midden_position) <= MIDDEN_RADIUS
+    })
+}
+
+/// Waste is kept in the refuse chamber, deep in the nest and away from the brood, once workers have dug it out. It's
+/// piled up from the floor so there's always somewhere to stand while dropping more. Until the chamber is dug out, and
+/// once it's full, waste is carried out to the midden along with the corpses.
+pub fn get_refuse_position(
+    colony_planner: &ColonyPlanner,
+    midden_position: Option<Position>,
+    grid_elements: &GridElements<AtNest>,
+) -> Option<Position> {
+    colony_planner
+        .get_planned_chamber(ChamberPurpose::Refuse)
+        .map(|refuse_chamber| refuse_chamber.positions())
+        .filter(|positions| {
+            !positions
+                .iter()
+                .any(|position| grid_elements.is(*position, Element::Dirt))
+        })
+        .and_then(|positions| {
+            positions
+                .into_iter()
+                .rev()
+                .find(|position| is_walkable(*position, grid_elements))
+        })
+        .or(midden_position)
+}
+
+/// Waste has been put away once it's in the refuse chamber or on the midden.
+pub fn is_at_refuse(
+    position: Position,
+    colony_planner: &ColonyPlanner,
+    midden_position: Option<Position>,
+) -> bool {
+    colony_planner
+        .get_planned_chamber(ChamberPurpose::Refuse)
+        .map_or(false, |refuse_chamber| {
+            refuse_chamber.positions().contains(&position)
+        })
+        || is_at_midden(position, midden_position)
+}
@@ -1,25 +1,73 @@
+pub mod age;
 pub mod birthing;
+pub mod brood;
+pub mod carry;
 pub mod chambering;
+pub mod collapse;
 pub mod commands;
+pub mod curiosity;
 pub mod death;
 pub mod dig;
+pub mod dig_order;
 pub mod digestion;
 pub mod drop;
+pub mod drowning;
+pub mod energy;
+pub mod flood_sealing;
+pub mod food_delivery;
+pub mod food_trail;
+pub mod grooming;
+pub mod history;
 pub mod hunger;
+pub mod infection;
+pub mod known_resources;
+pub mod midden;
 mod name_list;
 pub mod nest_expansion;
 pub mod nesting;
+pub mod nuptial_flight;
+pub mod nursing;
+pub mod order;
+pub mod pathing;
+pub mod recruitment;
 pub mod sleep;
+pub mod soldier;
+pub mod thermoregulation;
+pub mod thirst;
 pub mod tunneling;
 pub mod walk;
 
 use self::{
-    birthing::Birthing, chambering::Chambering, digestion::Digestion, hunger::Hunger,
-    name_list::get_random_name, sleep::Asleep, tunneling::Tunneling,
+    birthing::Birthing,
+    carry::CarryGroup,
+    chambering::Chambering,
+    collapse::Collapsed,
+    dig_order::DigOrder,
+    digestion::Digestion,
+    energy::Energy,
+    flood_sealing::FloodSeal,
+    food_delivery::FoodDelivery,
+    food_trail::ForagingTraits,
+    grooming::{Groomed, GROOMED_QUICK_TURN_CHANCE},
+    hunger::{Hunger, Solicitation},
+    infection::Infected,
+    known_resources::{KnownResource, KnownResources},
+    name_list::get_random_name,
+    nuptial_flight::{Claustral, Winged},
+    recruitment::{Recruitment, TandemRun},
+    sleep::Asleep,
+    thermoregulation::BroodTransport,
+    tunneling::Tunneling,
 };
 use crate::{
-    common::{position::Position, Zone},
-    nest_simulation::{element::Element, nest::AtNest},
+    common::{grid::GridElements, position::Position, Zone},
+    content_pack::ContentPacks,
+    nest_simulation::{
+        element::{mud::MUD_DELAY_TICKS, Element},
+        temperature::{get_cold_delay_ticks, TemperatureField},
+    },
+    save::ReflectPersist,
+    settings::Settings,
 };
 use bevy::{
     ecs::{
@@ -82,27 +130,80 @@ impl<Z: Zone> AntBundle<Z> {
 }
 
 #[derive(Component, Debug, PartialEq, Clone, Serialize, Deserialize, Reflect, Default)]
-#[reflect(Component)]
+#[reflect(Component, Persist)]
 pub struct AntName(pub String);
 
 impl AntName {
-    pub fn random(rng: &mut Mut<GlobalRng>) -> Self {
-        AntName(get_random_name(&mut rng.reborrow()))
+    pub fn random(rng: &mut Mut<GlobalRng>, content_packs: &ContentPacks) -> Self {
+        AntName(get_random_name(&mut rng.reborrow(), content_packs.names()))
     }
 }
 
 #[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
-#[reflect(Component)]
+#[reflect(Component, Persist)]
 pub struct AntColor(pub Color);
 
-#[derive(Component, Debug, PartialEq, Clone, Serialize, Deserialize, Reflect, Default)]
-#[reflect(Component, MapEntities)]
-pub struct AntInventory(pub Option<Entity>);
+/// What an ant is carrying. Workers have room for a second morsel of food, so foragers bring home more each trip,
+/// but everyone else carries one thing at a time. Items are put down, or eaten, in the reverse order they were picked up.
+#[derive(Component, Debug, PartialEq, Clone, Serialize, Deserialize, Reflect)]
+#[reflect(Component, MapEntities, Persist)]
+pub struct AntInventory {
+    items: Vec<Entity>,
+    capacity: usize,
+}
+
+impl Default for AntInventory {
+    fn default() -> Self {
+        Self {
+            items: vec![],
+            capacity: 1,
+        }
+    }
+}
+
+impl AntInventory {
+    pub fn new(role: AntRole) -> Self {
+        let capacity = match role {
+            AntRole::Worker => 2,
+            AntRole::Queen | AntRole::Soldier => 1,
+        };
+
+        Self {
+            items: vec![],
+            capacity,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn has_room(&self) -> bool {
+        self.items.len() < self.capacity
+    }
+
+    pub fn items(&self) -> &[Entity] {
+        &self.items
+    }
+
+    /// The item picked up most recently. It's the next to be put down or eaten.
+    pub fn last(&self) -> Option<Entity> {
+        self.items.last().copied()
+    }
+
+    pub fn push(&mut self, item_entity: Entity) {
+        self.items.push(item_entity);
+    }
+
+    pub fn pop(&mut self) -> Option<Entity> {
+        self.items.pop()
+    }
+}
 
 impl MapEntities for AntInventory {
     fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
-        if let Some(entity) = self.0 {
-            self.0 = Some(entity_mapper.get_or_reserve(entity));
+        for entity in self.items.iter_mut() {
+            *entity = entity_mapper.get_or_reserve(*entity);
         }
     }
 }
@@ -111,25 +212,27 @@ impl MapEntities for AntInventory {
 pub struct AntAteFoodEvent(pub Entity);
 
 #[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
-#[reflect(Component)]
+#[reflect(Component, Persist)]
 pub struct Dead;
 
 #[derive(Component, Debug, PartialEq, Clone, Serialize, Deserialize, Reflect, Default)]
-#[reflect(Component)]
+#[reflect(Component, Persist)]
 pub struct Ant;
 
 impl Ant {}
 
 #[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
-#[reflect(Component)]
+#[reflect(Component, Persist)]
 pub enum AntRole {
     #[default]
     Worker,
     Queen,
+    // Soldiers leave foraging and brood care to workers. They guard the nest entrance and fight off intruders.
+    Soldier,
 }
 
 #[derive(Component, Debug, PartialEq, Clone, Serialize, Deserialize, Reflect, Default)]
-#[reflect(Component)]
+#[reflect(Component, Persist)]
 pub struct InventoryItem;
 
 #[derive(Bundle)]
@@ -148,7 +251,7 @@ impl InventoryItemBundle {
 }
 
 #[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
-#[reflect(Component)]
+#[reflect(Component, Persist)]
 pub struct Initiative {
     has_action: bool,
     has_movement: bool,
@@ -249,7 +352,7 @@ impl Angle {
 }
 
 #[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
-#[reflect(Component)]
+#[reflect(Component, Persist)]
 pub struct AntOrientation {
     facing: Facing,
     angle: Angle,
@@ -403,11 +506,32 @@ impl AntOrientation {
 // Each ant maintains an internal timer that determines when it will act next.
 // This adds a little realism by varying when movements occur and allows for flexibility
 // in the simulation run speed.
-pub fn ants_initiative(
-    mut alive_ants_query: Query<&mut Initiative, With<AtNest>>,
+// Each turn taken costs the ant energy. Ants which have run out wait, for as long as it takes to recharge, before acting again.
+// Freshly groomed ants sometimes get their next turn a little sooner. Ants in cold spots, or standing in mud, get theirs
+// a little later.
+pub fn ants_initiative<Z: Zone>(
+    mut alive_ants_query: Query<
+        (
+            &mut Initiative,
+            &Position,
+            &AntOrientation,
+            &AntInventory,
+            Option<&mut Energy>,
+            Option<&Groomed>,
+        ),
+        With<Z>,
+    >,
+    temperature_field_query: Query<&TemperatureField, With<Z>>,
+    grid_elements: GridElements<Z>,
+    settings: Res<Settings>,
     mut rng: ResMut<GlobalRng>,
 ) {
-    for mut initiative in alive_ants_query.iter_mut() {
+    // Only the nest keeps track of its temperature.
+    let temperature_field = temperature_field_query.get_single().ok();
+
+    for (mut initiative, position, orientation, inventory, energy, groomed) in
+        alive_ants_query.iter_mut()
+    {
         if initiative.timer > 0 {
             initiative.timer -= 1;
 
@@ -419,7 +543,27 @@ pub fn ants_initiative(
             continue;
         }
 
+        let has_moved = !initiative.has_movement;
         *initiative = Initiative::new(&mut rng.reborrow());
+
+        if groomed.is_some() && rng.f32() < GROOMED_QUICK_TURN_CHANCE {
+            initiative.timer -= 1;
+        }
+
+        if let Some(temperature_field) = temperature_field {
+            initiative.timer += get_cold_delay_ticks(temperature_field.temperature(*position));
+        }
+
+        if grid_elements.is(orientation.get_below_position(position), Element::Mud) {
+            initiative.timer += MUD_DELAY_TICKS;
+        }
+
+        if let Some(mut energy) = energy {
+            energy.spend_turn(has_moved, !inventory.is_empty(), &settings.energy);
+
+            let recovery_ticks = energy.get_recovery_ticks(&settings.energy);
+            initiative.timer = initiative.timer.max(recovery_ticks);
+        }
     }
 }
 
@@ -429,18 +573,38 @@ pub fn register_ant(app_type_registry: ResMut<AppTypeRegistry>) {
     app_type_registry.write().register::<AntColor>();
     app_type_registry.write().register::<Dead>();
     app_type_registry.write().register::<Asleep>();
+    app_type_registry.write().register::<Collapsed>();
     app_type_registry.write().register::<Initiative>();
     app_type_registry.write().register::<AntOrientation>();
     app_type_registry.write().register::<Facing>();
     app_type_registry.write().register::<Angle>();
     app_type_registry.write().register::<AntRole>();
     app_type_registry.write().register::<Hunger>();
+    app_type_registry.write().register::<Solicitation>();
     app_type_registry.write().register::<Digestion>();
     app_type_registry.write().register::<AntInventory>();
+    app_type_registry.write().register::<Vec<Entity>>();
     app_type_registry.write().register::<InventoryItem>();
     app_type_registry.write().register::<Birthing>();
     app_type_registry.write().register::<Tunneling>();
     app_type_registry.write().register::<Chambering>();
+    app_type_registry.write().register::<ForagingTraits>();
+    app_type_registry.write().register::<DigOrder>();
+    app_type_registry.write().register::<Energy>();
+    app_type_registry.write().register::<CarryGroup>();
+    app_type_registry.write().register::<KnownResources>();
+    app_type_registry.write().register::<KnownResource>();
+    app_type_registry.write().register::<Vec<KnownResource>>();
+    app_type_registry.write().register::<Recruitment>();
+    app_type_registry.write().register::<TandemRun>();
+    app_type_registry.write().register::<FoodDelivery>();
+    app_type_registry.write().register::<BroodTransport>();
+    app_type_registry.write().register::<FloodSeal>();
+    app_type_registry.write().register::<Option<Position>>();
+    app_type_registry.write().register::<Infected>();
+    app_type_registry.write().register::<Groomed>();
+    app_type_registry.write().register::<Winged>();
+    app_type_registry.write().register::<Claustral>();
 }
 
 // TODO: tests
@@ -4,8 +4,14 @@
 use bevy::prelude::Mut;
 use bevy_turborand::prelude::*;
 
-pub fn get_random_name(rng: &mut Mut<GlobalRng>) -> String {
-  rng.sample(&NAMES).unwrap().to_string()
+// Names from content packs are picked alongside the built-in names.
+pub fn get_random_name(rng: &mut Mut<GlobalRng>, extra_names: &[String]) -> String {
+  let index = rng.usize(0..NAMES.len() + extra_names.len());
+
+  match NAMES.get(index) {
+    Some(name) => name.to_string(),
+    None => extra_names[index - NAMES.len()].clone(),
+  }
 }
 
 const NAMES: [&'static str; 18239]= [
@@ -1,6 +1,6 @@
 use super::{AntInventory, AntOrientation, AntRole, Initiative};
 use crate::{
-    common::{grid::GridElements, position::Position},
+    common::{grid::GridElements, position::Position, reservation::ElementReservations},
     nest_simulation::{
         ant::commands::AntCommandsExt,
         element::Element,
@@ -32,6 +32,7 @@ pub fn ants_nest_expansion(
     settings: Res<Settings>,
     mut rng: ResMut<GlobalRng>,
     mut commands: Commands,
+    mut element_reservations: ResMut<ElementReservations>,
     nest_query: Query<&Nest>,
 ) {
     let nest = nest_query.single();
@@ -49,7 +50,7 @@ pub fn ants_nest_expansion(
         }
 
         if *ant_role != AntRole::Worker
-            || inventory.0 != None
+            || !inventory.is_empty()
             || nest.is_aboveground(ant_position)
             || ant_orientation.is_facing_north()
         {
@@ -81,6 +82,10 @@ pub fn ants_nest_expansion(
             }
 
             let dig_target_entity = *grid_elements.entity(dirt_position);
+            if !element_reservations.try_reserve(dig_target_entity, ant_entity) {
+                continue;
+            }
+
             commands.dig(ant_entity, dirt_position, dig_target_entity, AtNest);
             commands.spawn_pheromone(
                 dirt_position,
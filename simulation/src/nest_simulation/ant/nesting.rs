@@ -3,13 +3,14 @@ use super::{
     Facing, Initiative,
 };
 use crate::{
-    common::{grid::GridElements, position::Position},
+    common::{grid::GridElements, position::Position, reservation::ElementReservations},
     nest_simulation::{
         ant::birthing::Birthing,
         element::Element,
         nest::{AtNest, Nest},
         pheromone::{commands::PheromoneCommandsExt, Pheromone, PheromoneStrength},
     },
+    save::ReflectPersist,
     settings::Settings,
 };
 use bevy::prelude::*;
@@ -17,7 +18,7 @@ use bevy_turborand::prelude::*;
 use serde::{Deserialize, Serialize};
 
 #[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
-#[reflect(Component)]
+#[reflect(Component, Persist)]
 pub enum Nesting {
     #[default]
     NotStarted,
@@ -25,7 +26,7 @@ pub enum Nesting {
 }
 
 #[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
-#[reflect(Component)]
+#[reflect(Component, Persist)]
 pub struct Nested;
 
 pub fn register_nesting(app_type_registry: ResMut<AppTypeRegistry>) {
@@ -70,7 +71,7 @@ pub fn ants_nesting_movement(
             continue;
         }
 
-        if nest.is_underground(&position) || inventory.0 != None {
+        if nest.is_underground(&position) || !inventory.is_empty() {
             continue;
         }
 
@@ -115,6 +116,7 @@ pub fn ants_nesting_action(
     grid_elements: GridElements<AtNest>,
     settings: Res<Settings>,
     mut rng: ResMut<GlobalRng>,
+    mut element_reservations: ResMut<ElementReservations>,
     mut commands: Commands,
 ) {
     let nest = nest_query.single();
@@ -134,6 +136,8 @@ pub fn ants_nesting_action(
             &orientation,
             &nest,
             &grid_elements,
+            &element_reservations,
+            ant_entity,
             &settings,
         ) {
             start_digging_nest(
@@ -142,6 +146,7 @@ pub fn ants_nesting_action(
                 ant_entity,
                 &mut nesting,
                 &grid_elements,
+                &mut element_reservations,
                 &mut commands,
                 &settings,
             );
@@ -156,6 +161,7 @@ pub fn ants_nesting_action(
                 &inventory,
                 &mut initiative,
                 &grid_elements,
+                &mut element_reservations,
                 &mut commands,
                 &settings,
             );
@@ -172,6 +178,7 @@ pub fn ants_nesting_action(
 ///     4) The ant must be aboveground.
 ///     5) The ant must not be too close to the edge of the world.
 ///     6) The ant must be standing on a diggable element.
+///     7) No other ant must have claimed that element this tick.
 /// TODO:
 ///     * Instead of arbitrarily checking if ant is near edge of the map, place immovable rocks which dissuade ant from digging.
 fn can_start_nesting(
@@ -182,11 +189,13 @@ fn can_start_nesting(
     ant_orientation: &AntOrientation,
     nest: &Nest,
     grid_elements: &GridElements<AtNest>,
+    element_reservations: &ElementReservations,
+    ant_entity: Entity,
     settings: &Settings,
 ) -> bool {
     let should_consider_digging = *nesting == Nesting::NotStarted
         && rng.f32() < settings.probabilities.above_surface_queen_nest_dig
-        && inventory.0 == None;
+        && inventory.is_empty();
 
     if !should_consider_digging {
         return false;
@@ -204,14 +213,18 @@ fn can_start_nesting(
 
     let is_element_diggable = grid_elements
         .get_element(*dig_target_entity)
-        .map_or(false, |element| element.is_diggable());
+        .map_or(false, |element| {
+            grid_elements.definition(*element).is_diggable()
+        });
+
+    let is_element_claimed =
+        element_reservations.is_reserved_by_other(*dig_target_entity, ant_entity);
 
-    has_valid_dig_site && is_element_diggable
+    has_valid_dig_site && is_element_diggable && !is_element_claimed
 }
 
 /// Start digging a nest by digging its entrance underneath the ant's current position
 /// TODO:
-///     * `commands.dig` could fail (conceptually, if worker ants existed) and, if it did fail, it would be wrong to mark the nest as having been started.
 ///     * Prefer marking nest zone with pheromone rather than tracking position.
 fn start_digging_nest(
     ant_position: &Position,
@@ -219,12 +232,15 @@ fn start_digging_nest(
     ant_entity: Entity,
     nesting: &mut Nesting,
     grid_elements: &GridElements<AtNest>,
+    element_reservations: &mut ElementReservations,
     commands: &mut Commands,
     settings: &Settings,
 ) {
     // TODO: consider just marking tile with pheromone rather than digging immediately
     let dig_position = ant_orientation.get_below_position(ant_position);
     let dig_target_entity = grid_elements.entity(dig_position);
+    // Claim the dig site so the nest is never marked as started on a dig which was beaten to by another ant.
+    element_reservations.try_reserve(*dig_target_entity, ant_entity);
     commands.dig(ant_entity, dig_position, *dig_target_entity, AtNest);
 
     *nesting = Nesting::Started(dig_position);
@@ -297,6 +313,7 @@ fn finish_digging_nest(
     ant_inventory: &AntInventory,
     initiative: &mut Initiative,
     grid_elements: &GridElements<AtNest>,
+    element_reservations: &mut ElementReservations,
     commands: &mut Commands,
     settings: &Res<Settings>,
 ) {
@@ -306,10 +323,13 @@ fn finish_digging_nest(
         .insert(Nested)
         .insert(Birthing::new(settings.max_birthing_time));
 
-    if ant_inventory.0 != None {
+    if !ant_inventory.is_empty() {
         let drop_position = ant_orientation.get_ahead_position(ant_position);
         let drop_target_entity = grid_elements.entity(drop_position);
-        commands.drop(ant_entity, drop_position, *drop_target_entity, AtNest);
+        // If someone else is dropping there then hold onto it. It'll be dropped, like anything else, later on.
+        if element_reservations.try_reserve(*drop_target_entity, ant_entity) {
+            commands.drop(ant_entity, drop_position, *drop_target_entity, AtNest);
+        }
     } else {
         // TODO: This seems wrong. Everywhere else initiative is hidden behind custom action commands.
         // Ensure that ant doesn't try to move or act after settling down
@@ -0,0 +1,142 @@
+use super::{Angle, AntOrientation, Facing, Initiative};
+use crate::{
+    common::{grid::GridElements, position::Position},
+    nest_simulation::{
+        ant::nesting::Nested,
+        element::{commands::ElementCommandsExt, Element},
+        nest::{get_nest_entrance, AtNest, Nest},
+    },
+    save::ReflectPersist,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Queens circle this far above the crater floor while the player chooses where they'll land.
+const FLIGHT_ALTITUDE: isize = 4;
+
+/// A queen fresh from her nuptial flight. She circles above the crater floor, rather than walking, until the player
+/// chooses where she lands. She sheds her wings on landing, as real queens do, and sets about founding the colony.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Component, Persist)]
+pub struct Winged {
+    // The queen lands on whatever is beneath this column.
+    landing_x: Option<isize>,
+}
+
+impl Winged {
+    pub fn is_landing(&self) -> bool {
+        self.landing_x.is_some()
+    }
+
+    pub fn land(&mut self, position: Position) {
+        self.landing_x = Some(position.x);
+    }
+}
+
+/// A founding queen walls herself in once her chamber is dug and raises her first brood in safety. Her first workers
+/// will dig their own way out.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Component, Persist)]
+pub enum Claustral {
+    #[default]
+    Unsealed,
+    Sealed,
+}
+
+pub fn get_flight_altitude(nest: &Nest) -> isize {
+    (nest.surface_level() - FLIGHT_ALTITUDE).max(0)
+}
+
+/// Winged queens glide back and forth until they've been told where to land and then fly there and touch down.
+/// Flying takes the whole of a queen's turn so she doesn't walk, or act, while in the air.
+pub fn ants_fly(
+    mut ants_query: Query<
+        (
+            Entity,
+            &Winged,
+            &mut Position,
+            &mut AntOrientation,
+            &mut Initiative,
+        ),
+        With<AtNest>,
+    >,
+    nest_query: Query<&Nest>,
+    grid_elements: GridElements<AtNest>,
+    mut commands: Commands,
+) {
+    let nest = nest_query.single();
+
+    for (ant_entity, winged, mut position, mut orientation, mut initiative) in ants_query.iter_mut()
+    {
+        if !initiative.can_move() {
+            continue;
+        }
+
+        initiative.consume();
+
+        let Some(landing_x) = winged.landing_x else {
+            let ahead_position = orientation.get_ahead_position(&position);
+
+            if grid_elements.is(ahead_position, Element::Air) {
+                *position = ahead_position;
+            } else {
+                // Turn around at the edge of the crater.
+                let facing = match orientation.get_facing() {
+                    Facing::Left => Facing::Right,
+                    Facing::Right => Facing::Left,
+                };
+
+                *orientation = AntOrientation::new(facing, Angle::Zero);
+            }
+
+            continue;
+        };
+
+        if position.x != landing_x {
+            let facing = if landing_x < position.x {
+                Facing::Left
+            } else {
+                Facing::Right
+            };
+
+            *orientation = AntOrientation::new(facing, Angle::Zero);
+            *position = orientation.get_ahead_position(&position);
+            continue;
+        }
+
+        let below_position = orientation.get_below_position(&position);
+
+        if grid_elements.is(below_position, Element::Air) && nest.is_aboveground(&below_position) {
+            *position = below_position;
+            continue;
+        }
+
+        commands
+            .entity(ant_entity)
+            .remove::<Winged>()
+            .insert(Claustral::default());
+    }
+}
+
+/// Once her chamber is dug the queen plugs the tunnel leading down to it.
+pub fn ants_seal_chamber(
+    mut ants_query: Query<&mut Claustral, (With<Nested>, With<AtNest>)>,
+    nest_query: Query<&Nest>,
+    grid_elements: GridElements<AtNest>,
+    mut commands: Commands,
+) {
+    let nest = nest_query.single();
+
+    for mut claustral in ants_query.iter_mut() {
+        if *claustral == Claustral::Sealed {
+            continue;
+        }
+
+        if let Some(entrance_position) = get_nest_entrance(nest, &grid_elements) {
+            let air_entity = *grid_elements.entity(entrance_position);
+            commands.replace_element(entrance_position, Element::Dirt, air_entity, AtNest);
+        }
+
+        *claustral = Claustral::Sealed;
+    }
+}
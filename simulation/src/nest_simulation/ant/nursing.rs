@@ -0,0 +1,16 @@
+use crate::common::position::Position;
+
+/// Nurses tend to the queen first because the colony depends on her. Otherwise, they feed the closest larva.
+/// Feeding itself happens the same as for any other ant which comes across someone hungry. Nurses just go looking.
+pub fn get_nurse_path_goal(
+    position: Position,
+    hungry_queen_position: Option<Position>,
+    larva_positions: &[Position],
+) -> Option<Position> {
+    hungry_queen_position.or_else(|| {
+        larva_positions
+            .iter()
+            .min_by_key(|larva_position| position.distance(larva_position))
+            .copied()
+    })
+}
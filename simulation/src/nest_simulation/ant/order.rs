@@ -0,0 +1,214 @@
+use super::{
+    carry::CarryGroup, commands::AntCommandsExt, walk::get_turned_orientation, AntInventory,
+    AntOrientation, AntRole, Dead, Initiative,
+};
+use crate::{
+    common::{grid::GridElements, position::Position, reservation::ElementReservations},
+    nest_simulation::{
+        element::Element,
+        nest::{AtNest, Nest},
+    },
+    save::ReflectPersist,
+    story_time::DEFAULT_TICKS_PER_SECOND,
+};
+use bevy::prelude::*;
+use bevy_turborand::GlobalRng;
+use serde::{Deserialize, Serialize};
+
+// Ants can get stuck trying to reach places they can't walk to. Give up, and go back to their own business, eventually.
+const ORDER_MAX_TICKS: isize = 60 * 10 * DEFAULT_TICKS_PER_SECOND; // 10 minutes
+
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+pub enum OrderTask {
+    Dig(Position),
+    GoTo(Position),
+    #[default]
+    ReturnToNest,
+}
+
+/// A task given to an ant by the user in sandbox mode. It overrides the ant's own wandering until it's
+/// fulfilled, or it expires, and then the ant goes back to behaving autonomously.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Component, Persist)]
+pub struct Order {
+    task: OrderTask,
+    remaining_ticks: isize,
+}
+
+impl Order {
+    pub fn new(task: OrderTask) -> Self {
+        Self {
+            task,
+            remaining_ticks: ORDER_MAX_TICKS,
+        }
+    }
+
+    pub fn task(&self) -> OrderTask {
+        self.task
+    }
+}
+
+pub fn register_order(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<Order>();
+    app_type_registry.write().register::<OrderTask>();
+}
+
+/// Orders can only be given to workers. The queen is too busy looking after the nest.
+pub fn can_receive_order(role: &AntRole) -> bool {
+    *role == AntRole::Worker
+}
+
+/// Expire orders which have gone unfulfilled for too long. Dead ants can't carry out orders.
+pub fn ants_order_tick(
+    mut ants_query: Query<(Entity, &mut Order, Option<&Dead>), With<AtNest>>,
+    mut commands: Commands,
+) {
+    for (ant_entity, mut order, dead) in ants_query.iter_mut() {
+        order.remaining_ticks -= 1;
+
+        if order.remaining_ticks <= 0 || dead.is_some() {
+            commands.entity(ant_entity).remove::<Order>();
+        }
+    }
+}
+
+/// Ants which have been ordered somewhere turn around whenever they find themselves walking away from their goal.
+/// This is naive, compared to pathfinding, but it's consistent with how ants otherwise find their way around.
+pub fn ants_order_movement(
+    mut ants_query: Query<(&mut Initiative, &Position, &mut AntOrientation, &Order), With<AtNest>>,
+    queen_query: Query<(&Position, &AntRole), (With<AtNest>, Without<Dead>)>,
+    nest_query: Query<&Nest>,
+    mut rng: ResMut<GlobalRng>,
+    grid_elements: GridElements<AtNest>,
+) {
+    let nest = nest_query.single();
+
+    for (mut initiative, position, mut orientation, order) in ants_query.iter_mut() {
+        if !initiative.can_move() {
+            continue;
+        }
+
+        let goal_position = match get_goal_position(order, &queen_query) {
+            Some(goal_position) => goal_position,
+            None => continue,
+        };
+
+        // Don't fuss with distance logic when close to the goal because it's naive and edge cases can cause infinite loops
+        if position.distance(&goal_position) <= 1 {
+            continue;
+        }
+
+        let ahead_position = orientation.get_ahead_position(position);
+        if position.distance(&goal_position) > ahead_position.distance(&goal_position) {
+            continue;
+        }
+
+        *orientation =
+            get_turned_orientation(&orientation, &position, &nest, &mut rng, &grid_elements);
+
+        initiative.consume_movement();
+    }
+}
+
+/// Fulfill orders once the ant has reached its goal. Digging takes the ant's action so it can't dig twice in one tick.
+pub fn ants_order_action(
+    mut ants_query: Query<
+        (
+            Entity,
+            &mut Initiative,
+            &Position,
+            &AntOrientation,
+            &AntInventory,
+            &Order,
+        ),
+        With<AtNest>,
+    >,
+    queen_query: Query<(&Position, &AntRole), (With<AtNest>, Without<Dead>)>,
+    grid_elements: GridElements<AtNest>,
+    mut element_reservations: ResMut<ElementReservations>,
+    mut commands: Commands,
+) {
+    for (ant_entity, mut initiative, position, orientation, inventory, order) in
+        ants_query.iter_mut()
+    {
+        let goal_position = match get_goal_position(order, &queen_query) {
+            Some(goal_position) => goal_position,
+            None => {
+                // There's nowhere to go, e.g. the queen has died, so there's no way to fulfill the order.
+                commands.entity(ant_entity).remove::<Order>();
+                continue;
+            }
+        };
+
+        match order.task {
+            OrderTask::GoTo(_) | OrderTask::ReturnToNest => {
+                if position.distance(&goal_position) <= 1 {
+                    commands.entity(ant_entity).remove::<Order>();
+                }
+            }
+            OrderTask::Dig(_) => {
+                // Someone else may have already dug it out.
+                if grid_elements.is(goal_position, Element::Air) {
+                    commands.entity(ant_entity).remove::<Order>();
+                    continue;
+                }
+
+                // Ants drop what they're carrying, on their own, before they're able to dig again.
+                if !initiative.can_act() || !inventory.is_empty() {
+                    continue;
+                }
+
+                let is_within_reach = [
+                    orientation.get_ahead_position(position),
+                    orientation.get_below_position(position),
+                    orientation.get_above_position(position),
+                ]
+                .contains(&goal_position);
+
+                if !is_within_reach {
+                    continue;
+                }
+
+                let element_entity = match grid_elements.get_entity(goal_position) {
+                    Some(element_entity) => *element_entity,
+                    None => continue,
+                };
+
+                let element = *grid_elements.element(element_entity);
+
+                // Heavy elements can't be dug out alone. Grab hold and wait for help to carry it off instead.
+                if grid_elements.definition(element).is_heavy() {
+                    commands
+                        .entity(ant_entity)
+                        .insert(CarryGroup::Gathering(element_entity))
+                        .remove::<Order>();
+                    initiative.consume();
+                    continue;
+                }
+
+                // Another ant is digging it out this tick. Hold onto the order until it's confirmed gone.
+                if !element_reservations.try_reserve(element_entity, ant_entity) {
+                    continue;
+                }
+
+                // Digging uses up the ant's initiative once the dig goes through.
+                commands.dig(ant_entity, goal_position, element_entity, AtNest);
+                commands.entity(ant_entity).remove::<Order>();
+            }
+        }
+    }
+}
+
+/// The nest is wherever the queen is.
+fn get_goal_position(
+    order: &Order,
+    queen_query: &Query<(&Position, &AntRole), (With<AtNest>, Without<Dead>)>,
+) -> Option<Position> {
+    match order.task {
+        OrderTask::Dig(position) | OrderTask::GoTo(position) => Some(position),
+        OrderTask::ReturnToNest => queen_query
+            .iter()
+            .find(|(_, role)| **role == AntRole::Queen)
+            .map(|(position, _)| *position),
+    }
+}
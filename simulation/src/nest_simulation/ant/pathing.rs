@@ -0,0 +1,427 @@
+use super::{
+    brood::{Brood, Larva},
+    flood_sealing::FloodSeal,
+    food_delivery::FoodDelivery,
+    hunger::Hunger,
+    infection::Infected,
+    known_resources::KnownResources,
+    midden::{get_midden_position, get_refuse_position},
+    nursing::get_nurse_path_goal,
+    order::{Order, OrderTask},
+    recruitment::{Recruitment, TandemRun},
+    sleep::is_bedtime,
+    soldier::{get_soldier_path_goal, Intruder},
+    thermoregulation::BroodTransport,
+    walk::is_valid_location,
+    AntInventory, AntOrientation, AntRole, Dead, Initiative,
+};
+use crate::{
+    balance_config::BalanceConfig,
+    common::{
+        grid::{Grid, GridElements},
+        intents::IntentsLog,
+        pathfinding::find_path,
+        position::Position,
+    },
+    nest_simulation::{
+        colony_planner::ColonyPlanner,
+        element::Element,
+        nest::{get_nest_entrance, AtNest, Nest},
+        task_allocation::{get_work_path_goal, TaskAllocation, WorkerTask},
+    },
+    settings::Settings,
+    story_time::{StoryTime, DEFAULT_TICKS_PER_SECOND},
+};
+use bevy::prelude::*;
+
+// Goals which couldn't be reached may become reachable as the nest is dug out so try again every so often.
+const PATH_RETRY_TICKS: isize = 60 * DEFAULT_TICKS_PER_SECOND; // 1 minute
+
+/// A route an ant is following to reach its goal. Ants without a route to their goal wander as usual.
+/// NOTE: Paths aren't persisted. They're cheap enough to find again after loading.
+#[derive(Component, Debug, PartialEq, Clone, Reflect, Default)]
+#[reflect(Component)]
+pub struct Path {
+    goal: Position,
+    // Stored in reverse so the next position to walk to is at the end.
+    positions: Vec<Position>,
+    // How long ago the route was found. Used to retry unreachable goals.
+    ticks: isize,
+}
+
+impl Path {
+    pub fn new(goal: Position, mut positions: Vec<Position>) -> Self {
+        positions.reverse();
+
+        Self {
+            goal,
+            positions,
+            ticks: 0,
+        }
+    }
+
+    pub fn goal(&self) -> Position {
+        self.goal
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    pub fn next_position(&self) -> Option<Position> {
+        self.positions.last().copied()
+    }
+}
+
+pub fn register_pathing(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<Path>();
+    app_type_registry.write().register::<Vec<Position>>();
+}
+
+/// What the ant is carrying, if anything. Carried items are held apart from the grid, and aren't in any zone, so they're
+/// looked up directly rather than through `GridElements`.
+///
+/// ```
+/// use bevy::{ecs::system::SystemState, prelude::*};
+/// use simulation::nest_simulation::{
+///     ant::{pathing::get_carried_element, AntInventory, AntRole},
+///     element::Element,
+/// };
+///
+/// let mut world = World::new();
+/// let food_entity = world.spawn(Element::Food).id();
+///
+/// let mut system_state: SystemState<Query<&Element>> = SystemState::new(&mut world);
+/// let elements_query = system_state.get(&world);
+///
+/// // Food in hand is found even though it is nowhere in the grid.
+/// let mut inventory = AntInventory::new(AntRole::Worker);
+/// inventory.push(food_entity);
+/// assert_eq!(
+///     get_carried_element(&inventory, &elements_query),
+///     Some(&Element::Food)
+/// );
+///
+/// assert_eq!(get_carried_element(&AntInventory::default(), &elements_query), None);
+/// ```
+pub fn get_carried_element<'a>(
+    inventory: &AntInventory,
+    elements_query: &'a Query<&Element>,
+) -> Option<&'a Element> {
+    inventory
+        .last()
+        .and_then(|element_entity| elements_query.get(element_entity).ok())
+}
+
+/// Workers sealing a flooded tunnel head for sand, and then for the breach, whatever else they'd otherwise do.
+/// Workers carrying food, seeds, or prey, and workers returning to the nest, head for the queen. Workers carrying corpses head for
+/// the midden, as do empty-handed workers which are infected, to keep their infection away from the brood. Workers carrying waste
+/// head for wherever it's being put away. Empty-handed workers delivering food to the queen head for whoever they're handing it
+/// to next, and those moving brood head for the brood and then for wherever they're taking it. Empty-handed scouts, and their
+/// recruits, are busy fetching help for food found in the crater. Other empty-handed workers head for whatever work the colony
+/// has set them to and otherwise have nowhere in particular to be.
+fn get_path_goal(
+    inventory: &AntInventory,
+    is_returning_to_nest: bool,
+    queen_position: Option<Position>,
+    midden_position: Option<Position>,
+    refuse_position: Option<Position>,
+    is_infected: bool,
+    seal_path_goal: Option<Position>,
+    delivery_path_goal: Option<Position>,
+    transport_path_goal: Option<Position>,
+    recruitment_path_goal: Option<Position>,
+    task_path_goal: Option<Position>,
+    elements_query: &Query<&Element>,
+) -> Option<Position> {
+    let inventory_element = get_carried_element(inventory, elements_query);
+    if seal_path_goal.is_some() {
+        seal_path_goal
+    } else if matches!(
+        inventory_element,
+        Some(&Element::Food) | Some(&Element::Seed) | Some(&Element::Prey)
+    ) || is_returning_to_nest
+    {
+        queen_position
+    } else if inventory_element == Some(&Element::Corpse) {
+        midden_position
+    } else if inventory_element == Some(&Element::Waste) {
+        refuse_position
+    } else if inventory_element.is_none() && is_infected {
+        midden_position
+    } else if inventory_element.is_none() {
+        delivery_path_goal
+            .or(transport_path_goal)
+            .or(recruitment_path_goal)
+            .or(task_path_goal)
+    } else {
+        None
+    }
+}
+
+/// Find a route for ants which have somewhere to be. Routes are found again when the goal moves, when the ant is
+/// knocked off its route, and periodically when the goal couldn't be reached.
+pub fn ants_find_path(
+    ants_query: Query<
+        (
+            Entity,
+            &Position,
+            &AntRole,
+            &AntInventory,
+            Option<&Order>,
+            Option<&Path>,
+            Option<&WorkerTask>,
+            Option<&KnownResources>,
+            Option<&Recruitment>,
+            Option<&TandemRun>,
+            Option<&Infected>,
+            Option<&FoodDelivery>,
+            Option<&BroodTransport>,
+            Option<&FloodSeal>,
+        ),
+        (
+            With<AtNest>,
+            With<Initiative>,
+            Without<Dead>,
+            Without<Intruder>,
+        ),
+    >,
+    queen_query: Query<(&Position, &AntRole, &Hunger), (With<AtNest>, Without<Dead>)>,
+    brood_query: Query<(&Position, Option<&Larva>), (With<Brood>, With<AtNest>)>,
+    intruders_query: Query<&Position, (With<Intruder>, With<AtNest>, Without<Dead>)>,
+    nest_query: Query<(&Grid, &Nest)>,
+    elements_query: Query<&Element>,
+    grid_elements: GridElements<AtNest>,
+    balance_config: Res<BalanceConfig>,
+    story_time: Res<StoryTime>,
+    settings: Res<Settings>,
+    task_allocation: Res<TaskAllocation>,
+    colony_planner: Res<ColonyPlanner>,
+    mut intents_log: ResMut<IntentsLog>,
+    mut commands: Commands,
+) {
+    let is_bedtime = is_bedtime(&story_time, &settings);
+    let queen = queen_query
+        .iter()
+        .find(|(_, role, _)| **role == AntRole::Queen);
+    let queen_position = queen.map(|(position, _, _)| *position);
+    let hungry_queen_position = queen
+        .filter(|(_, _, hunger)| hunger.is_peckish(&balance_config.hunger))
+        .map(|(position, _, _)| *position);
+
+    let growing_larva_positions = brood_query
+        .iter()
+        .filter(|(_, larva)| larva.map_or(false, |larva| !larva.is_fully_grown()))
+        .map(|(position, _)| *position)
+        .collect::<Vec<_>>();
+
+    let intruder_positions = intruders_query.iter().copied().collect::<Vec<_>>();
+    let (grid, nest) = nest_query.single();
+    let entrance_position = get_nest_entrance(nest, &grid_elements);
+    let midden_position = get_midden_position(grid, nest, &grid_elements);
+    let refuse_position = get_refuse_position(&colony_planner, midden_position, &grid_elements);
+
+    for (
+        ant_entity,
+        position,
+        role,
+        inventory,
+        order,
+        path,
+        worker_task,
+        known_resources,
+        recruitment,
+        tandem_run,
+        infected,
+        food_delivery,
+        brood_transport,
+        flood_seal,
+    ) in ants_query.iter()
+    {
+        // Ants which have given up on reaching a work site, and are wandering instead, are free to pick another.
+        let current_goal = path
+            .filter(|path| !path.is_finished())
+            .map(|path| path.goal);
+
+        // Workers still out at nightfall head home to sleep.
+        let is_returning_to_nest = order
+            .map_or(false, |order| order.task() == OrderTask::ReturnToNest)
+            || (is_bedtime && nest.is_aboveground(position));
+
+        // Scouts gather recruits from among the nestmates gathered around the queen, and then lead them out of the nest.
+        // Recruits stick with their scout.
+        let recruitment_path_goal = match (recruitment, tandem_run) {
+            (Some(Recruitment::Recruiting { .. }), _) => queen_position,
+            (Some(Recruitment::Leading(_)), _) => entrance_position,
+            (_, Some(tandem_run)) => ants_query
+                .get(tandem_run.0)
+                .ok()
+                .map(|(_, scout_position, ..)| *scout_position),
+            _ => None,
+        };
+
+        // Couriers track down whoever they're handing food to next, wherever they've wandered off to.
+        let delivery_path_goal = food_delivery
+            .and_then(|food_delivery| food_delivery.recipient())
+            .and_then(|recipient_entity| queen_query.get(recipient_entity).ok())
+            .map(|(recipient_position, _, _)| *recipient_position);
+
+        // Nurses moving brood fetch it, from wherever it is, and then carry it to where it's going.
+        let transport_path_goal = brood_transport.and_then(|brood_transport| {
+            if brood_transport.is_carrying() {
+                Some(brood_transport.destination())
+            } else {
+                brood_query
+                    .get(brood_transport.brood())
+                    .ok()
+                    .map(|(brood_position, _)| *brood_position)
+            }
+        });
+
+        let goal = match role {
+            AntRole::Worker => get_path_goal(
+                inventory,
+                is_returning_to_nest,
+                queen_position,
+                midden_position,
+                refuse_position,
+                infected.is_some(),
+                flood_seal.and_then(|flood_seal| flood_seal.get_path_goal(!inventory.is_empty())),
+                delivery_path_goal,
+                transport_path_goal,
+                recruitment_path_goal,
+                // Orders given directly to an ant take priority over the work the colony has set it to.
+                match worker_task.filter(|_| order.is_none()) {
+                    Some(WorkerTask::Nursing) => get_nurse_path_goal(
+                        *position,
+                        hungry_queen_position,
+                        &growing_larva_positions,
+                    ),
+                    Some(WorkerTask::Digging) => {
+                        get_work_path_goal(*position, current_goal, task_allocation.dig_sites())
+                    }
+                    // Foragers head back to food they remember seeing before looking for more.
+                    Some(WorkerTask::Foraging) => known_resources
+                        .and_then(|known_resources| known_resources.get_nearest(*position))
+                        .or_else(|| {
+                            get_work_path_goal(
+                                *position,
+                                current_goal,
+                                task_allocation.forage_sites(),
+                            )
+                        }),
+                    Some(WorkerTask::WasteManagement) => {
+                        get_work_path_goal(*position, current_goal, task_allocation.refuse_sites())
+                    }
+                    None => None,
+                },
+                &elements_query,
+            ),
+            AntRole::Soldier => {
+                get_soldier_path_goal(*position, &intruder_positions, entrance_position)
+            }
+            AntRole::Queen => None,
+        };
+
+        let goal = match goal {
+            Some(goal) => goal,
+            None => {
+                intents_log.record(ant_entity, || "nowhere to be: wandering".to_string());
+
+                if path.is_some() {
+                    commands.entity(ant_entity).remove::<Path>();
+                }
+
+                continue;
+            }
+        };
+
+        let is_path_current = path.map_or(false, |path| {
+            path.goal == goal && (!path.is_finished() || path.ticks < PATH_RETRY_TICKS)
+        });
+
+        if is_path_current {
+            continue;
+        }
+
+        let route = find_path(*position, goal, &grid_elements);
+
+        intents_log.record(ant_entity, || match &route {
+            Some(positions) => format!(
+                "heading for {}, {}: {} steps away",
+                goal.x,
+                goal.y,
+                positions.len()
+            ),
+            None => format!("heading for {}, {}: no route, wandering", goal.x, goal.y),
+        });
+
+        let positions = route.unwrap_or_default();
+
+        commands
+            .entity(ant_entity)
+            .insert(Path::new(goal, positions));
+    }
+}
+
+/// Walk ants along their route one tile at a time. Ants turn to cling to whatever is next to the tile they're moving
+/// into, preferring to face the way they're walking.
+pub fn ants_follow_path(
+    mut ants_query: Query<
+        (
+            &mut Initiative,
+            &mut Position,
+            &mut AntOrientation,
+            &mut Path,
+        ),
+        (With<AtNest>, Without<Dead>),
+    >,
+    nest_query: Query<&Nest>,
+    grid_elements: GridElements<AtNest>,
+) {
+    let nest = nest_query.single();
+
+    for (mut initiative, mut position, mut orientation, mut path) in ants_query.iter_mut() {
+        path.ticks += 1;
+
+        if !initiative.can_move() {
+            continue;
+        }
+
+        let Some(next_position) = path.next_position() else {
+            continue;
+        };
+
+        // Ants can fall, or be pushed, off their route. Forget the route so a new one is found.
+        if position.distance(&next_position) != 1 {
+            path.positions.clear();
+            path.ticks = PATH_RETRY_TICKS;
+            continue;
+        }
+
+        let ahead_orientation = AntOrientation::all_orientations()
+            .into_iter()
+            .filter(|next_orientation| {
+                is_valid_location(*next_orientation, next_position, nest, &grid_elements)
+            })
+            .max_by_key(|next_orientation| {
+                (
+                    next_orientation.get_ahead_position(&position) == next_position,
+                    *next_orientation == *orientation,
+                )
+            });
+
+        // The nest has changed since the route was found, e.g. sand has fallen into the way, so find a new one.
+        let Some(next_orientation) = ahead_orientation else {
+            path.positions.clear();
+            path.ticks = PATH_RETRY_TICKS;
+            continue;
+        };
+
+        *position = next_position;
+        *orientation = next_orientation;
+        path.positions.pop();
+
+        initiative.consume_movement();
+    }
+}
@@ -0,0 +1,197 @@
+use super::{
+    age::Age, carry::CarryGroup, collapse::Collapsed, infection::Infected, order::Order,
+    sleep::Asleep, soldier::Intruder, AntInventory, AntRole, Dead,
+};
+use crate::{
+    common::position::Position,
+    nest_simulation::{nest::AtNest, task_allocation::WorkerTask},
+    save::ReflectPersist,
+    story_time::DEFAULT_TICKS_PER_SECOND,
+};
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+use serde::{Deserialize, Serialize};
+
+// How many nestmates a scout leads out to the food it found. Any more and the run strings out too far to keep together.
+const MAX_RECRUITS: usize = 3;
+// Scouts only recruit nestmates close enough to be tapped on the shoulder.
+const RECRUITMENT_RADIUS: isize = 5;
+// Recruits which fall further behind their scout than this lose track of it.
+pub const TANDEM_RUN_MAX_GAP: isize = 5;
+// Scouts head back out, with however many recruits they've gathered, rather than waiting forever for more. 30 seconds.
+const RECRUITMENT_TICKS: isize = 30 * DEFAULT_TICKS_PER_SECOND;
+
+/// Scouts which find plenty of food in the crater fetch help from the nest rather than harvesting it one seed at a
+/// time. The scout carries word home, gathers idle nestmates, and then leads them, nose to tail, back to the food.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect)]
+#[reflect(Component, Persist)]
+pub enum Recruitment {
+    // Heading home across the crater from the food it found.
+    Returning(Position),
+    // Back in the nest, gathering idle nestmates to follow it out to the food.
+    Recruiting {
+        food_position: Position,
+        remaining_ticks: isize,
+    },
+    // Leading its recruits out of the nest, and across the crater, to the food.
+    Leading(Position),
+}
+
+impl Default for Recruitment {
+    fn default() -> Self {
+        Recruitment::Returning(Position::default())
+    }
+}
+
+impl Recruitment {
+    pub fn recruiting(food_position: Position) -> Self {
+        Recruitment::Recruiting {
+            food_position,
+            remaining_ticks: RECRUITMENT_TICKS,
+        }
+    }
+
+    /// Where, in the crater, the scout found food.
+    pub fn food_position(&self) -> Position {
+        match *self {
+            Recruitment::Returning(food_position)
+            | Recruitment::Recruiting { food_position, .. }
+            | Recruitment::Leading(food_position) => food_position,
+        }
+    }
+
+    pub fn is_leading(&self) -> bool {
+        matches!(self, Recruitment::Leading(_))
+    }
+}
+
+/// A recruit following the given scout to food the scout found. Recruits go wherever their scout goes, rather than
+/// deciding for themselves, until they reach the food or lose track of their scout.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect)]
+#[reflect(Component, MapEntities, Persist)]
+pub struct TandemRun(pub Entity);
+
+impl Default for TandemRun {
+    fn default() -> Self {
+        TandemRun(Entity::PLACEHOLDER)
+    }
+}
+
+impl MapEntities for TandemRun {
+    fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+        self.0 = entity_mapper.get_or_reserve(self.0);
+    }
+}
+
+/// Recruits still in the nest give up on their scout if it's died, or has headed out without them.
+pub fn tandem_runs_disband(
+    recruits_query: Query<(Entity, &TandemRun, Option<&Dead>), With<AtNest>>,
+    scouts_query: Query<(), (With<Recruitment>, With<AtNest>, Without<Dead>)>,
+    mut commands: Commands,
+) {
+    for (recruit_entity, tandem_run, dead) in recruits_query.iter() {
+        if dead.is_some() || scouts_query.get(tandem_run.0).is_err() {
+            commands.entity(recruit_entity).remove::<TandemRun>();
+        }
+    }
+}
+
+/// Scouts back in the nest recruit idle, empty-handed, workers they come across, but don't draw nurses away from the brood.
+/// The oldest workers are recruited first, leaving younger workers to the safety of the nest.
+/// Once they've gathered enough recruits, or have given up waiting for more, they lead whoever they've gathered back out
+/// to the food.
+pub fn ants_recruit(
+    mut scouts_query: Query<(Entity, &Position, &mut Recruitment), (With<AtNest>, Without<Dead>)>,
+    idle_workers_query: Query<
+        (
+            Entity,
+            &Position,
+            &AntRole,
+            &AntInventory,
+            Option<&Age>,
+            Option<&WorkerTask>,
+        ),
+        (
+            With<AtNest>,
+            Without<Recruitment>,
+            Without<TandemRun>,
+            Without<Order>,
+            Without<CarryGroup>,
+            Without<Asleep>,
+            Without<Collapsed>,
+            Without<Dead>,
+            Without<Intruder>,
+            Without<Infected>,
+        ),
+    >,
+    recruits_query: Query<&TandemRun, With<AtNest>>,
+    mut commands: Commands,
+) {
+    let mut recruit_counts: HashMap<Entity, usize> = HashMap::default();
+    for tandem_run in recruits_query.iter() {
+        *recruit_counts.entry(tandem_run.0).or_default() += 1;
+    }
+
+    // Commands are deferred so keep track of who has been recruited this tick to avoid two scouts sharing a recruit.
+    let mut recruited = HashSet::new();
+
+    for (scout_entity, scout_position, mut recruitment) in scouts_query.iter_mut() {
+        let Recruitment::Recruiting {
+            food_position,
+            remaining_ticks,
+        } = *recruitment
+        else {
+            continue;
+        };
+
+        let mut recruit_count = recruit_counts
+            .get(&scout_entity)
+            .copied()
+            .unwrap_or_default();
+
+        let mut candidates = idle_workers_query
+            .iter()
+            .filter(
+                |(worker_entity, position, role, inventory, _, worker_task)| {
+                    **role == AntRole::Worker
+                        && inventory.is_empty()
+                        && *worker_task != Some(&WorkerTask::Nursing)
+                        && scout_position.distance(position) <= RECRUITMENT_RADIUS
+                        && !recruited.contains(worker_entity)
+                },
+            )
+            .map(|(worker_entity, _, _, _, age, _)| {
+                (worker_entity, age.map_or(0, |age| age.ticks()))
+            })
+            .collect::<Vec<_>>();
+
+        candidates.sort_by_key(|(_, age_ticks)| std::cmp::Reverse(*age_ticks));
+
+        for (worker_entity, _) in candidates {
+            if recruit_count >= MAX_RECRUITS {
+                break;
+            }
+
+            commands
+                .entity(worker_entity)
+                .insert(TandemRun(scout_entity));
+            recruited.insert(worker_entity);
+            recruit_count += 1;
+        }
+
+        *recruitment = if recruit_count >= MAX_RECRUITS || remaining_ticks <= 0 {
+            Recruitment::Leading(food_position)
+        } else {
+            Recruitment::Recruiting {
+                food_position,
+                remaining_ticks: remaining_ticks - 1,
+            }
+        };
+    }
+}
@@ -5,25 +5,40 @@ use serde::{Deserialize, Serialize};
 use crate::{
     common::position::Position,
     nest_simulation::nest::{AtNest, Nest},
+    save::ReflectPersist,
+    settings::Settings,
     story_time::StoryTime,
 };
 
-use super::{AntInventory, AntOrientation, Initiative};
+use super::{soldier::Intruder, AntInventory, AntOrientation, Dead, Initiative};
 
 #[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
-#[reflect(Component)]
+#[reflect(Component, Persist)]
 pub struct Asleep;
 
+/// Ants stay up when sleeping at night is turned off.
+pub fn is_bedtime(story_time: &StoryTime, settings: &Settings) -> bool {
+    settings.is_sleeping_at_night && story_time.is_nighttime()
+}
+
+/// Ants which are home for the night, settled in, and empty-handed fall asleep. Workers still out at nightfall head back
+/// to the nest first, see `ants_find_path`. Raiders are far from home so they stay awake.
 pub fn ants_sleep(
     ants_query: Query<
         (Entity, &Position, &AntOrientation, &AntInventory),
-        (With<Initiative>, With<AtNest>),
+        (
+            With<Initiative>,
+            With<AtNest>,
+            Without<Intruder>,
+            Without<Dead>,
+        ),
     >,
     mut commands: Commands,
     nest_query: Query<&Nest>,
     story_time: Res<StoryTime>,
+    settings: Res<Settings>,
 ) {
-    if !story_time.is_nighttime() {
+    if !is_bedtime(&story_time, &settings) {
         return;
     }
 
@@ -32,7 +47,7 @@ pub fn ants_sleep(
     for (ant_entity, ant_position, ant_orientation, ant_inventory) in ants_query.iter() {
         if nest.is_underground(ant_position)
             && ant_orientation.is_rightside_up()
-            && ant_inventory.0 == None
+            && ant_inventory.is_empty()
         {
             commands
                 .entity(ant_entity)
@@ -42,13 +57,15 @@ pub fn ants_sleep(
     }
 }
 
+/// Ants wake at sunrise, or straight away if sleeping at night has been turned off.
 pub fn ants_wake(
-    ants_query: Query<Entity, (With<Asleep>, With<AtNest>)>,
+    ants_query: Query<Entity, (With<Asleep>, With<AtNest>, Without<Dead>)>,
     mut commands: Commands,
     story_time: Res<StoryTime>,
+    settings: Res<Settings>,
     mut rng: ResMut<GlobalRng>,
 ) {
-    if story_time.is_nighttime() {
+    if is_bedtime(&story_time, &settings) {
         return;
     }
 
@@ -0,0 +1,72 @@
+use super::{commands::AntCommandsExt, AntOrientation, AntRole, Dead, Initiative};
+use crate::{common::position::Position, nest_simulation::nest::AtNest, save::ReflectPersist};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Soldiers wander freely while they're close enough to the entrance to respond to anything coming through it.
+const SOLDIER_PATROL_DISTANCE: isize = 12;
+
+/// Anything which doesn't belong in the nest and should be fought off, e.g. ants from a rival colony.
+/// NOTE: Rival colony ants are intruders. They keep to the crater except when they raid the nest at night.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Component, Persist)]
+pub struct Intruder;
+
+pub fn register_soldier(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<Intruder>();
+}
+
+/// Soldiers head for the closest intruder. Without intruders they return to the entrance when they've strayed from it.
+pub fn get_soldier_path_goal(
+    position: Position,
+    intruder_positions: &[Position],
+    entrance_position: Option<Position>,
+) -> Option<Position> {
+    let closest_intruder_position = intruder_positions
+        .iter()
+        .min_by_key(|intruder_position| position.distance(intruder_position))
+        .copied();
+
+    if closest_intruder_position.is_some() {
+        return closest_intruder_position;
+    }
+
+    entrance_position
+        .filter(|entrance_position| position.distance(entrance_position) > SOLDIER_PATROL_DISTANCE)
+}
+
+/// Soldiers attack intruders they're standing on, or facing, and kill them.
+pub fn soldiers_attack(
+    mut soldiers_query: Query<
+        (&mut Initiative, &Position, &AntOrientation, &AntRole),
+        (With<AtNest>, Without<Dead>, Without<Intruder>),
+    >,
+    intruders_query: Query<(Entity, &Position), (With<Intruder>, With<AtNest>, Without<Dead>)>,
+    mut commands: Commands,
+) {
+    let mut defeated_intruder_entities = vec![];
+
+    for (mut initiative, position, orientation, role) in soldiers_query.iter_mut() {
+        if *role != AntRole::Soldier || !initiative.can_act() {
+            continue;
+        }
+
+        let ahead_position = orientation.get_ahead_position(position);
+
+        let target_intruder =
+            intruders_query
+                .iter()
+                .find(|(intruder_entity, intruder_position)| {
+                    !defeated_intruder_entities.contains(intruder_entity)
+                        && (**intruder_position == *position
+                            || **intruder_position == ahead_position)
+                });
+
+        if let Some((intruder_entity, _)) = target_intruder {
+            commands.kill_ant(intruder_entity);
+
+            defeated_intruder_entities.push(intruder_entity);
+            initiative.consume();
+        }
+    }
+}
@@ -0,0 +1,370 @@
+use super::{
+    brood::{Brood, Egg, Larva},
+    carry::CarryGroup,
+    collapse::Collapsed,
+    food_delivery::FoodDelivery,
+    hunger::Hunger,
+    infection::Infected,
+    order::Order,
+    recruitment::{Recruitment, TandemRun},
+    sleep::Asleep,
+    AntInventory, AntOrientation, AntRole, Dead, Initiative,
+};
+use crate::{
+    balance_config::BalanceConfig,
+    common::{
+        grid::{Grid, GridElements},
+        intents::IntentsLog,
+        pathfinding::is_walkable,
+        position::Position,
+    },
+    nest_simulation::{
+        nest::{AtNest, Nest},
+        temperature::TemperatureField,
+    },
+    save::ReflectPersist,
+    story_time::DEFAULT_TICKS_PER_SECOND,
+};
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+};
+use bevy_turborand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Eggs and larvae develop best within this band, in degrees Celsius, and ideally right in the middle of it.
+const BROOD_MIN_TEMPERATURE: f32 = 24.0;
+const BROOD_MAX_TEMPERATURE: f32 = 30.0;
+const BROOD_IDEAL_TEMPERATURE: f32 = 27.0;
+// Brood colder than this, or warmer than that, in degrees Celsius, barely develops at all.
+const BROOD_STALL_COLD_TEMPERATURE: f32 = 12.0;
+const BROOD_STALL_HOT_TEMPERATURE: f32 = 38.0;
+// Even stalled brood creeps along so a cold snap sets the colony back rather than freezing it in place.
+const BROOD_MIN_DEVELOPMENT_RATE: f32 = 0.1;
+// Brood isn't moved for the sake of a degree or two. It's only worth the trip if it'll be noticeably more comfortable.
+const BROOD_MIN_IMPROVEMENT: f32 = 2.0;
+// Soil warms and cools slowly so brood is checked on every ten seconds or so rather than every tick.
+const BROOD_CHECK_CHANCE: f64 = 0.01;
+// Nurses which can't get brood where it's going within 5 minutes set it down wherever they are.
+const BROOD_TRANSPORT_TICKS: isize = 5 * 60 * DEFAULT_TICKS_PER_SECOND;
+
+/// A nurse moving an egg, or larva, somewhere with a more comfortable temperature. The nurse first fetches the brood
+/// and then carries it to `destination`. Carried brood keeps pace with the nurse carrying it.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect)]
+#[reflect(Component, MapEntities, Persist)]
+pub struct BroodTransport {
+    brood: Entity,
+    destination: Position,
+    is_carrying: bool,
+    remaining_ticks: isize,
+}
+
+impl Default for BroodTransport {
+    fn default() -> Self {
+        Self {
+            brood: Entity::PLACEHOLDER,
+            destination: Position::default(),
+            is_carrying: false,
+            remaining_ticks: BROOD_TRANSPORT_TICKS,
+        }
+    }
+}
+
+impl MapEntities for BroodTransport {
+    fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+        self.brood = entity_mapper.get_or_reserve(self.brood);
+    }
+}
+
+impl BroodTransport {
+    pub fn brood(&self) -> Entity {
+        self.brood
+    }
+
+    pub fn destination(&self) -> Position {
+        self.destination
+    }
+
+    pub fn is_carrying(&self) -> bool {
+        self.is_carrying
+    }
+}
+
+/// Where brood at `brood_position` would be more comfortable, choosing from `sites` and how warm each is. Brood which
+/// is neither too cold nor too warm stays put. Otherwise, it's moved to the nearest site within the comfortable band or,
+/// when nowhere is that comfortable, to whichever site is closest to ideal as long as it's a real improvement.
+///
+/// ```
+/// use simulation::{
+///     common::position::Position,
+///     nest_simulation::ant::thermoregulation::get_brood_destination,
+/// };
+///
+/// let sites = [
+///     (Position::new(0, 5), 26.0),
+///     (Position::new(0, 10), 22.0),
+///     (Position::new(0, 20), 28.0),
+/// ];
+///
+/// // Comfortable brood stays put.
+/// assert_eq!(get_brood_destination(Position::new(0, 12), 27.0, &sites), None);
+///
+/// // Cold brood is moved to the nearest comfortable site.
+/// assert_eq!(get_brood_destination(Position::new(0, 12), 20.0, &sites), Some(Position::new(0, 5)));
+///
+/// // When nowhere is comfortable, brood is moved somewhere noticeably better, if there is anywhere.
+/// let cold_sites = [(Position::new(0, 5), 12.0), (Position::new(0, 20), 19.0)];
+/// assert_eq!(get_brood_destination(Position::new(0, 12), 16.0, &cold_sites), Some(Position::new(0, 20)));
+/// assert_eq!(get_brood_destination(Position::new(0, 12), 18.0, &cold_sites), None);
+/// ```
+pub fn get_brood_destination(
+    brood_position: Position,
+    brood_temperature: f32,
+    sites: &[(Position, f32)],
+) -> Option<Position> {
+    let is_comfortable =
+        |temperature: f32| (BROOD_MIN_TEMPERATURE..=BROOD_MAX_TEMPERATURE).contains(&temperature);
+
+    if is_comfortable(brood_temperature) {
+        return None;
+    }
+
+    let comfortable_site = sites
+        .iter()
+        .filter(|(_, temperature)| is_comfortable(*temperature))
+        .min_by_key(|(position, _)| brood_position.distance(position));
+
+    if let Some((position, _)) = comfortable_site {
+        return Some(*position);
+    }
+
+    let discomfort = |temperature: f32| (temperature - BROOD_IDEAL_TEMPERATURE).abs();
+
+    sites
+        .iter()
+        .filter(|(_, temperature)| {
+            discomfort(*temperature) <= discomfort(brood_temperature) - BROOD_MIN_IMPROVEMENT
+        })
+        .min_by(|(_, a), (_, b)| discomfort(*a).total_cmp(&discomfort(*b)))
+        .map(|(position, _)| *position)
+}
+
+/// How quickly eggs, and pupae, develop at the given temperature compared to how quickly they develop in comfort.
+/// Development slows the further brood is from the comfortable band, in either direction, until it all but stalls.
+///
+/// ```
+/// use simulation::nest_simulation::ant::thermoregulation::get_brood_development_rate;
+///
+/// assert_eq!(get_brood_development_rate(27.0), 1.0);
+/// assert_eq!(get_brood_development_rate(18.0), 0.5);
+/// assert_eq!(get_brood_development_rate(34.0), 0.5);
+/// assert_eq!(get_brood_development_rate(5.0), 0.1);
+/// ```
+pub fn get_brood_development_rate(temperature: f32) -> f32 {
+    let rate = if temperature < BROOD_MIN_TEMPERATURE {
+        (temperature - BROOD_STALL_COLD_TEMPERATURE)
+            / (BROOD_MIN_TEMPERATURE - BROOD_STALL_COLD_TEMPERATURE)
+    } else if temperature > BROOD_MAX_TEMPERATURE {
+        (BROOD_STALL_HOT_TEMPERATURE - temperature)
+            / (BROOD_STALL_HOT_TEMPERATURE - BROOD_MAX_TEMPERATURE)
+    } else {
+        1.0
+    };
+
+    rate.clamp(BROOD_MIN_DEVELOPMENT_RATE, 1.0)
+}
+
+/// Now and then, brood which is too cold, or too warm, is moved somewhere more comfortable by the nearest idle,
+/// empty-handed, worker. Shallow soil warms through the day and cools through the night, faster than deep soil, so
+/// brood tends to be carried up toward the surface by day and back down at night. Pupae are left to finish developing
+/// undisturbed. Only one brood is moved at a time so the nest isn't emptied of workers every morning.
+pub fn ants_plan_brood_moves(
+    ants_query: Query<
+        (Entity, &Position, &AntRole, &AntInventory, &Hunger),
+        (
+            With<AtNest>,
+            Without<Order>,
+            Without<CarryGroup>,
+            Without<Recruitment>,
+            Without<TandemRun>,
+            Without<FoodDelivery>,
+            Without<Asleep>,
+            Without<Collapsed>,
+            Without<Infected>,
+            Without<Dead>,
+        ),
+    >,
+    transports_query: Query<(), (With<BroodTransport>, With<AtNest>)>,
+    brood_query: Query<
+        (Entity, &Position),
+        (With<Brood>, With<AtNest>, Or<(With<Egg>, With<Larva>)>),
+    >,
+    all_brood_query: Query<&Position, (With<Brood>, With<AtNest>)>,
+    nest_query: Query<(&Grid, &Nest, &TemperatureField)>,
+    grid_elements: GridElements<AtNest>,
+    balance_config: Res<BalanceConfig>,
+    mut rng: ResMut<GlobalRng>,
+    mut intents_log: ResMut<IntentsLog>,
+    mut commands: Commands,
+) {
+    if !transports_query.is_empty() || !rng.chance(BROOD_CHECK_CHANCE) {
+        return;
+    }
+
+    let (grid, nest, temperature_field) = nest_query.single();
+    let brood_positions = all_brood_query.iter().copied().collect::<Vec<_>>();
+
+    // Brood is set down somewhere underground, where a nurse could stand, and where no other brood already is.
+    let sites = (nest.surface_level() + 1..grid.height())
+        .flat_map(|y| (0..grid.width()).map(move |x| Position::new(x, y)))
+        .filter(|position| {
+            is_walkable(*position, &grid_elements) && !brood_positions.contains(position)
+        })
+        .map(|position| (position, temperature_field.temperature(position)))
+        .collect::<Vec<_>>();
+
+    let Some((brood_entity, brood_position, destination)) =
+        brood_query
+            .iter()
+            .find_map(|(brood_entity, brood_position)| {
+                let brood_temperature = temperature_field.temperature(*brood_position);
+
+                get_brood_destination(*brood_position, brood_temperature, &sites)
+                    .map(|destination| (brood_entity, *brood_position, destination))
+            })
+    else {
+        return;
+    };
+
+    let Some((nurse_entity, ..)) = ants_query
+        .iter()
+        .filter(|(_, _, role, inventory, hunger)| {
+            **role == AntRole::Worker
+                && inventory.is_empty()
+                && !hunger.is_hungry(&balance_config.hunger)
+        })
+        .min_by_key(|(_, position, ..)| position.distance(&brood_position))
+    else {
+        return;
+    };
+
+    intents_log.record(nurse_entity, || {
+        "thermoregulating: moving brood somewhere more comfortable".to_string()
+    });
+
+    commands.entity(nurse_entity).insert(BroodTransport {
+        brood: brood_entity,
+        destination,
+        ..default()
+    });
+}
+
+/// Nurses pick up the brood they've come to fetch once they reach it, and set it down once they've carried it to where
+/// it's going.
+pub fn ants_move_brood(
+    mut ants_query: Query<
+        (
+            Entity,
+            &Position,
+            &AntOrientation,
+            &mut Initiative,
+            &mut BroodTransport,
+        ),
+        (With<AtNest>, Without<Dead>),
+    >,
+    brood_query: Query<&Position, (With<Brood>, With<AtNest>)>,
+    mut intents_log: ResMut<IntentsLog>,
+    mut commands: Commands,
+) {
+    for (ant_entity, position, orientation, mut initiative, mut brood_transport) in
+        ants_query.iter_mut()
+    {
+        if !initiative.can_act() {
+            continue;
+        }
+
+        if brood_transport.is_carrying {
+            if *position != brood_transport.destination {
+                continue;
+            }
+
+            intents_log.record(ant_entity, || {
+                "thermoregulating: set brood down".to_string()
+            });
+
+            commands.entity(ant_entity).remove::<BroodTransport>();
+            initiative.consume();
+        } else {
+            let Ok(brood_position) = brood_query.get(brood_transport.brood) else {
+                continue;
+            };
+
+            if position != brood_position
+                && orientation.get_ahead_position(position) != *brood_position
+            {
+                continue;
+            }
+
+            intents_log.record(ant_entity, || {
+                "thermoregulating: picked up brood".to_string()
+            });
+
+            brood_transport.is_carrying = true;
+            initiative.consume();
+        }
+    }
+}
+
+/// Carried brood goes wherever the nurse carrying it goes.
+pub fn carried_brood_keep_pace(
+    carriers_query: Query<(&Position, &BroodTransport), With<AtNest>>,
+    mut brood_query: Query<&mut Position, (With<Brood>, With<AtNest>, Without<BroodTransport>)>,
+) {
+    for (carrier_position, brood_transport) in carriers_query.iter() {
+        if !brood_transport.is_carrying {
+            continue;
+        }
+
+        if let Ok(mut brood_position) = brood_query.get_mut(brood_transport.brood) {
+            *brood_position = *carrier_position;
+        }
+    }
+}
+
+/// Brood moves are called off when the brood is gone, e.g. because it hatched on the way, when the nurse has taken too
+/// long getting it there, or when something more pressing comes along. Carried brood is set down wherever the nurse is.
+pub fn brood_transports_cancel(
+    mut carriers_query: Query<(Entity, &mut BroodTransport, &Hunger), With<AtNest>>,
+    distracted_query: Query<
+        (),
+        Or<(
+            With<Order>,
+            With<CarryGroup>,
+            With<Recruitment>,
+            With<TandemRun>,
+            With<FoodDelivery>,
+            With<Asleep>,
+            With<Collapsed>,
+            With<Infected>,
+            With<Dead>,
+        )>,
+    >,
+    brood_query: Query<(), (With<Brood>, With<AtNest>)>,
+    balance_config: Res<BalanceConfig>,
+    mut commands: Commands,
+) {
+    for (ant_entity, mut brood_transport, hunger) in carriers_query.iter_mut() {
+        brood_transport.remaining_ticks -= 1;
+
+        if brood_transport.remaining_ticks <= 0
+            || brood_query.get(brood_transport.brood).is_err()
+            || hunger.is_hungry(&balance_config.hunger)
+            || distracted_query.get(ant_entity).is_ok()
+        {
+            commands.entity(ant_entity).remove::<BroodTransport>();
+        }
+    }
+}
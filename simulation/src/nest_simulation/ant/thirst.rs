@@ -0,0 +1,260 @@
+use super::{
+    commands::AntCommandsExt, Ant, AntAteFoodEvent, AntInventory, AntOrientation, AntRole, Dead,
+    Initiative,
+};
+use crate::{
+    common::{grid::GridElements, position::Position},
+    nest_simulation::{
+        element::{water::Moisture, Element},
+        nest::AtNest,
+    },
+    save::ReflectPersist,
+    settings::Settings,
+    story_time::DEFAULT_TICKS_PER_SECOND,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Ants look for something to drink once they're half way to dehydrating.
+const THIRSTY: f32 = 0.5;
+// Parched ants are given water by their nestmates the same way hungry ants are given food.
+const PARCHED: f32 = 0.75;
+// Food is mostly water so eating quenches a little thirst, but not enough to go without drinking forever.
+const FOOD_THIRST_RELIEF: f32 = 0.2;
+// How much moisture an ant drinks from moist dirt in one sip, and how much of its thirst that quenches.
+const MOISTURE_SIP: f32 = 0.25;
+const MOISTURE_SIP_THIRST_RELIEF: f32 = 0.5;
+// The share of its max thirst a worker passes along when giving water to a nestmate.
+const WATER_SHARE_TRANSFER: f32 = 0.2;
+
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Component, Persist)]
+pub struct Thirst {
+    value: f32,
+    max: f32,
+    rate: f32,
+}
+
+impl Thirst {
+    pub fn new(max_time_seconds: isize) -> Self {
+        let max = 100.0;
+        let rate = max / (max_time_seconds * DEFAULT_TICKS_PER_SECOND) as f32;
+
+        Self {
+            value: 0.0,
+            max,
+            rate,
+        }
+    }
+
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    pub fn max(&self) -> f32 {
+        self.max
+    }
+
+    pub fn set_value(&mut self, value: f32) {
+        self.value = value.min(self.max).max(0.0);
+    }
+
+    pub fn tick(&mut self) {
+        self.value = (self.value + self.rate).min(self.max);
+    }
+
+    pub fn is_thirsty(&self) -> bool {
+        self.value >= self.max * THIRSTY
+    }
+
+    pub fn is_parched(&self) -> bool {
+        self.value >= self.max * PARCHED
+    }
+
+    pub fn is_dehydrated(&self) -> bool {
+        self.value >= self.max
+    }
+}
+
+pub fn register_thirst(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<Thirst>();
+}
+
+/// Start making newly spawned ants thirsty.
+/// NOTE: Ants from saves which predate thirst are considered to have just had a drink when they're loaded.
+pub fn ants_add_thirst(
+    ants_query: Query<Entity, (With<Ant>, Without<Thirst>)>,
+    settings: Res<Settings>,
+    mut commands: Commands,
+) {
+    for ant_entity in ants_query.iter() {
+        commands
+            .entity(ant_entity)
+            .insert(Thirst::new(settings.max_thirst_time));
+    }
+}
+
+/// Ants grow thirstier over time, but every meal quenches a little of it. Ants which go too long without water die.
+pub fn ants_thirst_tick(
+    mut ants_query: Query<(Entity, &mut Thirst), (With<AtNest>, Without<Dead>)>,
+    mut ant_ate_food_event_reader: EventReader<AntAteFoodEvent>,
+    mut commands: Commands,
+) {
+    for AntAteFoodEvent(ant_entity) in ant_ate_food_event_reader.read() {
+        if let Ok((_, mut thirst)) = ants_query.get_mut(*ant_entity) {
+            let value = thirst.value() - thirst.max() * FOOD_THIRST_RELIEF;
+            thirst.set_value(value);
+        }
+    }
+
+    for (ant_entity, mut thirst) in ants_query.iter_mut() {
+        thirst.tick();
+
+        if thirst.is_dehydrated() {
+            commands.kill_ant(ant_entity);
+        }
+    }
+}
+
+/// Thirsty ants drink from water, or moist dirt, that they're facing or standing on.
+/// Water is plentiful so a drink from it quenches all thirst. Moist dirt only gives up a sip at a time, and dries out
+/// as it's drunk from.
+pub fn ants_thirst_act(
+    mut ants_query: Query<
+        (&mut Thirst, &AntOrientation, &Position, &mut Initiative),
+        (With<AtNest>, Without<Dead>),
+    >,
+    mut moisture_query: Query<&mut Moisture, With<AtNest>>,
+    grid_elements: GridElements<AtNest>,
+) {
+    for (mut thirst, orientation, position, mut initiative) in ants_query.iter_mut() {
+        if !thirst.is_thirsty() || !initiative.can_act() {
+            continue;
+        }
+
+        let drink_positions = [
+            orientation.get_ahead_position(position),
+            orientation.get_below_position(position),
+            *position,
+        ];
+
+        if drink_positions
+            .iter()
+            .any(|&drink_position| grid_elements.is(drink_position, Element::Water))
+        {
+            thirst.set_value(0.0);
+            initiative.consume();
+            continue;
+        }
+
+        let moist_dirt_entity = drink_positions
+            .iter()
+            .filter(|&&drink_position| grid_elements.is(drink_position, Element::Dirt))
+            .filter_map(|&drink_position| grid_elements.get_entity(drink_position))
+            .find(|&&dirt_entity| {
+                moisture_query
+                    .get(dirt_entity)
+                    .map_or(false, |moisture| moisture.value() > 0.0)
+            });
+
+        if let Some(&dirt_entity) = moist_dirt_entity {
+            if let Ok(mut moisture) = moisture_query.get_mut(dirt_entity) {
+                let drained_amount = moisture.drain(MOISTURE_SIP);
+                let value = thirst.value()
+                    - thirst.max() * MOISTURE_SIP_THIRST_RELIEF * (drained_amount / MOISTURE_SIP);
+                thirst.set_value(value);
+                initiative.consume();
+            }
+        }
+    }
+}
+
+/// Ants share water with their nestmates the same way they regurgitate food.
+/// A parched ant which is face-to-face with, or standing on, a nestmate who isn't thirsty is given some of their water.
+/// Workers always give water to a thirsty queen.
+pub fn ants_share_water(
+    mut ants_query: Query<
+        (
+            Entity,
+            &mut Thirst,
+            &AntOrientation,
+            &Position,
+            &AntInventory,
+            &AntRole,
+            &mut Initiative,
+        ),
+        (With<AtNest>, Without<Dead>),
+    >,
+) {
+    let thirsty_ants = ants_query
+        .iter()
+        .filter(|(_, thirst, _, _, inventory, role, initiative)| {
+            initiative.can_act()
+                && inventory.is_empty()
+                && (thirst.is_parched() || (**role == AntRole::Queen && thirst.is_thirsty()))
+        })
+        .map(|(ant_entity, _, orientation, position, _, _, _)| {
+            (
+                ant_entity,
+                orientation.get_ahead_position(position),
+                *position,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let mut results = vec![];
+
+    for (ant_entity, ahead_position, ant_position) in thirsty_ants {
+        let giver = ants_query.iter().find(
+            |(
+                other_ant_entity,
+                other_thirst,
+                other_orientation,
+                &other_position,
+                other_inventory,
+                _,
+                other_initiative,
+            )| {
+                if *other_ant_entity == ant_entity
+                    || !other_initiative.can_act()
+                    || !other_inventory.is_empty()
+                    || other_thirst.is_thirsty()
+                {
+                    return false;
+                }
+
+                (other_position == ahead_position
+                    && other_orientation.get_ahead_position(&other_position) == ant_position)
+                    || other_position == ant_position
+            },
+        );
+
+        if let Some((other_ant_entity, ..)) = giver {
+            results.push((ant_entity, other_ant_entity));
+        }
+    }
+
+    for (ant_entity, other_ant_entity) in results {
+        let Ok(
+            [(_, mut thirst, _, _, _, _, mut initiative), (_, mut other_thirst, _, _, _, _, mut other_initiative)],
+        ) = ants_query.get_many_mut([ant_entity, other_ant_entity])
+        else {
+            continue;
+        };
+
+        // Several ants may have picked the same nestmate to drink from, but each ant can only act once per tick.
+        if !initiative.can_act() || !other_initiative.can_act() {
+            continue;
+        }
+
+        let transfer_amount = other_thirst.max() * WATER_SHARE_TRANSFER;
+
+        let value = thirst.value() - transfer_amount;
+        thirst.set_value(value);
+        let other_value = other_thirst.value() + transfer_amount;
+        other_thirst.set_value(other_value);
+
+        initiative.consume();
+        other_initiative.consume();
+    }
+}
@@ -3,6 +3,7 @@ use crate::{
     common::{
         grid::{Grid, GridElements},
         position::Position,
+        reservation::ElementReservations,
     },
     nest_simulation::{
         ant::{
@@ -13,6 +14,7 @@ use crate::{
         nest::{AtNest, Nest},
         pheromone::{commands::PheromoneCommandsExt, Pheromone, PheromoneMap, PheromoneStrength},
     },
+    save::ReflectPersist,
     settings::Settings,
 };
 use bevy::prelude::*;
@@ -20,7 +22,7 @@ use bevy_turborand::GlobalRng;
 use serde::{Deserialize, Serialize};
 
 #[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
-#[reflect(Component)]
+#[reflect(Component, Persist)]
 pub struct Tunneling(pub isize);
 
 // "Whenever ant walks over tile with nesting pheromone, they gain "Nesting: 8". Then, they attempt to take a step forward and decrement Nesting to 7. If they end up digging, nesting is "forgotten" and they shift back to hauling dirt
@@ -119,6 +121,7 @@ pub fn ants_tunnel_pheromone_act(
     >,
     grid_query: Query<&Grid, With<AtNest>>,
     grid_elements: GridElements<AtNest>,
+    mut element_reservations: ResMut<ElementReservations>,
     mut commands: Commands,
     settings: Res<Settings>,
 ) {
@@ -130,7 +133,7 @@ pub fn ants_tunnel_pheromone_act(
         }
 
         // Safeguard, but not expected to run because shouldn't have Tunneling pheromone with full inventory.
-        if inventory.0 != None {
+        if !inventory.is_empty() {
             continue;
         }
 
@@ -149,12 +152,17 @@ pub fn ants_tunnel_pheromone_act(
         let entity = grid_elements.entity(ahead_position);
         let element = grid_elements.element(*entity);
 
-        if *element == Element::Air {
+        // Prey is too heavy to tunnel through, and stone too hard. Blocked ants wander off and find another way.
+        if !grid_elements.definition(*element).is_diggable() {
             continue;
         }
 
         let dig_position = orientation.get_ahead_position(position);
         let dig_target_entity = *grid_elements.entity(dig_position);
+        if !element_reservations.try_reserve(dig_target_entity, ant_entity) {
+            continue;
+        }
+
         commands.dig(ant_entity, dig_position, dig_target_entity, AtNest);
 
         // Reduce PheromoneStrength by 1 because not digging at ant_position, but ant_position + 1.
@@ -188,7 +196,7 @@ pub fn ants_add_tunnel_pheromone(
     mut commands: Commands,
 ) {
     for (ant_entity, ant_position, inventory, ant_orientation) in ants_query.iter() {
-        if inventory.0 != None {
+        if !inventory.is_empty() {
             continue;
         }
 
@@ -234,7 +242,7 @@ pub fn ants_remove_tunnel_pheromone(
     let nest = nest_query.single();
 
     for (ant_entity, ant_position, inventory, tunneling) in ants_query.iter_mut() {
-        if inventory.0 != None {
+        if !inventory.is_empty() {
             commands.entity(ant_entity).remove::<Tunneling>();
         } else if nest.is_aboveground(ant_position) {
             commands.entity(ant_entity).remove::<Tunneling>();
@@ -1,6 +1,10 @@
 use super::{AntOrientation, Initiative};
 use crate::{
-    common::{grid::GridElements, position::Position},
+    common::{
+        grid::GridElements,
+        intents::{get_direction_name, IntentsLog},
+        position::Position,
+    },
     nest_simulation::{
         element::Element,
         nest::{AtNest, Nest},
@@ -44,15 +48,19 @@ pub fn ants_stabilize_footing_movement(
 
 // Update the position and orientation of all ants. Does not affect the external environment.
 pub fn ants_walk(
-    mut ants_query: Query<(&mut Initiative, &mut Position, &mut AntOrientation), With<AtNest>>,
+    mut ants_query: Query<
+        (Entity, &mut Initiative, &mut Position, &mut AntOrientation),
+        With<AtNest>,
+    >,
     nest_query: Query<&Nest>,
     settings: Res<Settings>,
     mut rng: ResMut<GlobalRng>,
     grid_elements: GridElements<AtNest>,
+    mut intents_log: ResMut<IntentsLog>,
 ) {
     let nest = nest_query.single();
 
-    for (mut initiative, mut position, mut orientation) in ants_query.iter_mut() {
+    for (ant_entity, mut initiative, mut position, mut orientation) in ants_query.iter_mut() {
         if !initiative.can_move() {
             continue;
         }
@@ -71,6 +79,16 @@ pub fn ants_walk(
         let is_turning_randomly = rng.chance(settings.probabilities.random_turn.into());
 
         if !has_air_ahead || is_turning_randomly {
+            intents_log.record(ant_entity, || {
+                let direction = get_direction_name(*position, ahead_position);
+
+                if has_air_ahead {
+                    format!("wanted to walk {}: turned aside at random", direction)
+                } else {
+                    format!("wanted to walk {}: blocked", direction)
+                }
+            });
+
             *orientation =
                 get_turned_orientation(&orientation, &position, &nest, &mut rng, &grid_elements);
 
@@ -143,7 +161,7 @@ pub fn get_turned_orientation(
     all_orientations[rng.usize(0..all_orientations.len())]
 }
 
-fn is_valid_location(
+pub fn is_valid_location(
     orientation: AntOrientation,
     position: Position,
     nest: &Nest,
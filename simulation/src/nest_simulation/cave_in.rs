@@ -0,0 +1,143 @@
+use crate::{
+    common::{
+        grid::{Grid, GridElements},
+        position::Position,
+        Zone,
+    },
+    nest_simulation::{
+        ant::{commands::AntCommandsExt, Ant, Dead},
+        element::{commands::ElementCommandsExt, Element},
+        nest::{AtNest, Nest},
+    },
+    settings::Settings,
+};
+use bevy::{prelude::*, utils::HashSet};
+use bevy_turborand::{DelegatedRng, GlobalRng};
+
+// Dirt can bridge this much open space before it's at risk of caving in. Chambers fit comfortably beneath it.
+const MAX_SUPPORTED_SPAN: usize = 16;
+// Checking every row, every tick, is expensive in a large nest. A few rows are checked each tick instead.
+const SUPPORT_CHECK_ROWS_PER_TICK: isize = 4;
+
+/// Open space with dirt directly above it. The dirt is held up only by whatever is at either end of the open space.
+fn is_beneath_ceiling<Z: Zone>(position: Position, grid_elements: &GridElements<Z>) -> bool {
+    grid_elements.is(position, Element::Air)
+        && grid_elements.is(position + Position::NEG_Y, Element::Dirt)
+}
+
+/// The unbroken run of open space, along a row, which shares a dirt ceiling with the given position.
+/// The span ends wherever something props up the ceiling or the ceiling itself ends.
+pub fn get_ceiling_span<Z: Zone>(
+    position: Position,
+    grid_elements: &GridElements<Z>,
+) -> Vec<Position> {
+    if !is_beneath_ceiling(position, grid_elements) {
+        return vec![];
+    }
+
+    let is_span_x = |x: isize| is_beneath_ceiling(Position::new(x, position.y), grid_elements);
+    let start_x = (0..=position.x)
+        .rev()
+        .take_while(|&x| is_span_x(x))
+        .last()
+        .unwrap_or(position.x);
+    let end_x = (position.x..)
+        .take_while(|&x| is_span_x(x))
+        .last()
+        .unwrap_or(position.x);
+
+    (start_x..=end_x)
+        .map(|x| Position::new(x, position.y))
+        .collect()
+}
+
+/// The dirt ceiling above a span loosens into sand and falls into the open space below, crushing any ants beneath it.
+pub fn cave_in<Z: Zone + Copy>(
+    span: &[Position],
+    grid_elements: &GridElements<Z>,
+    ants: impl Iterator<Item = (Entity, Position)>,
+    zone: Z,
+    commands: &mut Commands,
+) {
+    let ceiling_replacements = span
+        .iter()
+        .filter_map(|&position| {
+            let ceiling_position = position + Position::NEG_Y;
+            let ceiling_entity = grid_elements.get_entity(ceiling_position)?;
+
+            Some((ceiling_position, *ceiling_entity))
+        })
+        .collect::<Vec<_>>();
+
+    commands.replace_elements(ceiling_replacements, Element::Sand, zone);
+
+    // The falling sand fills the open space all the way down to the floor.
+    let crushed_positions = span
+        .iter()
+        .flat_map(|&position| {
+            (position.y..)
+                .map(move |y| Position::new(position.x, y))
+                .take_while(|&below_position| grid_elements.is(below_position, Element::Air))
+        })
+        .collect::<HashSet<_>>();
+
+    for (ant_entity, ant_position) in ants {
+        if crushed_positions.contains(&ant_position) {
+            commands.kill_ant(ant_entity);
+        }
+    }
+}
+
+/// Tunnels and chambers which are too wide for the dirt above them to bridge are at risk of caving in.
+/// Unsupported spans don't collapse as soon as they're dug. They're at risk each time they're checked so there's a chance
+/// for ants to prop them up, or for the player to notice, first.
+pub fn cave_in_unsupported_spans(
+    ants_query: Query<(Entity, &Position), (With<Ant>, With<AtNest>, Without<Dead>)>,
+    nest_query: Query<(&Grid, &Nest)>,
+    grid_elements: GridElements<AtNest>,
+    settings: Res<Settings>,
+    mut rng: ResMut<GlobalRng>,
+    mut next_row: Local<isize>,
+    mut commands: Commands,
+) {
+    let (grid, nest) = nest_query.single();
+
+    // Only underground ceilings are checked. Dirt aboveground isn't held up by anything so it falls under gravity instead.
+    let first_row = nest.surface_level() + 2;
+    if first_row >= grid.height() {
+        return;
+    }
+
+    for _ in 0..SUPPORT_CHECK_ROWS_PER_TICK {
+        if *next_row < first_row || *next_row >= grid.height() {
+            *next_row = first_row;
+        }
+
+        let y = *next_row;
+        *next_row += 1;
+
+        let mut x = 0;
+        while x < grid.width() {
+            let span = get_ceiling_span(Position::new(x, y), &grid_elements);
+
+            if span.is_empty() {
+                x += 1;
+                continue;
+            }
+
+            x += span.len() as isize;
+
+            if span.len() > MAX_SUPPORTED_SPAN && rng.f32() < settings.probabilities.cave_in {
+                cave_in(
+                    &span,
+                    &grid_elements,
+                    ants_query
+                        .iter()
+                        .map(|(ant_entity, position)| (ant_entity, *position)),
+                    AtNest,
+                    &mut commands,
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,356 @@
+use crate::{
+    common::{
+        grid::{Grid, GridElements},
+        pathfinding::is_walkable,
+        position::Position,
+    },
+    nest_simulation::{
+        ant::{dig_order::DigOrder, AntRole, Dead},
+        element::{
+            water::{Moisture, MAX_MOISTURE},
+            Element,
+        },
+        nest::{AtNest, Nest},
+    },
+    save::ReflectPersist,
+    story_time::{DEFAULT_TICKS_PER_SECOND, SECONDS_PER_HOUR},
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// The colony takes stock of its nest hourly. Digging out a chamber takes a while so there's no need to plan more often.
+const PLAN_INTERVAL_TICKS: isize = SECONDS_PER_HOUR * DEFAULT_TICKS_PER_SECOND;
+// Chambers are kept away from the surface, where they'd be exposed to the weather, and away from one another so
+// there's dirt left between them to hold up the ceiling.
+const MIN_CHAMBER_DEPTH: isize = 6;
+const CHAMBER_SPACING: isize = 3;
+// The queen's chamber is kept humid, but not damp, to protect her eggs until there's a brood chamber to move them to.
+const QUEEN_CHAMBER_IDEAL_MOISTURE: f32 = 0.4;
+// Brood dries out easily. Brood chambers are dug where the dirt is moist.
+const BROOD_CHAMBER_IDEAL_MOISTURE: f32 = 0.7;
+
+/// What a chamber is dug out to hold. Chambers are planned, and dug, in this order as the colony grows.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+pub enum ChamberPurpose {
+    #[default]
+    QueenChamber,
+    Brood,
+    FoodStorage,
+    Refuse,
+}
+
+impl ChamberPurpose {
+    pub fn all() -> [ChamberPurpose; 4] {
+        [
+            ChamberPurpose::QueenChamber,
+            ChamberPurpose::Brood,
+            ChamberPurpose::FoodStorage,
+            ChamberPurpose::Refuse,
+        ]
+    }
+
+    /// Chambers are wide and low. None are wide enough to be at risk of caving in.
+    pub fn get_size(&self) -> (isize, isize) {
+        match self {
+            ChamberPurpose::QueenChamber => (7, 3),
+            ChamberPurpose::Brood => (9, 3),
+            ChamberPurpose::FoodStorage => (11, 3),
+            ChamberPurpose::Refuse => (7, 3),
+        }
+    }
+
+    /// There's no sense planning chambers faster than the colony has workers to dig them out.
+    fn get_min_worker_count(&self) -> usize {
+        match self {
+            ChamberPurpose::QueenChamber => 2,
+            ChamberPurpose::Brood => 5,
+            ChamberPurpose::FoodStorage => 10,
+            ChamberPurpose::Refuse => 15,
+        }
+    }
+
+    /// How well suited a site is for the chamber. `depth` and `moisture` are normalized to [0, 1] where 0 is just
+    /// beneath the surface, or bone dry, and 1 is the bottom of the nest, or saturated.
+    ///     1) The queen is kept as deep as possible, where she's safest from intruders.
+    ///     2) Brood is kept somewhere moist, and not so deep that nurses have far to carry food.
+    ///     3) Food is stored somewhere dry, so it keeps, and shallow, so foragers don't have far to carry it.
+    ///     4) Waste is kept deep, out of the way, and somewhere dry, where spores are slow to take hold.
+    fn get_site_score(&self, depth: f32, moisture: f32) -> f32 {
+        match self {
+            ChamberPurpose::QueenChamber => {
+                depth - (moisture - QUEEN_CHAMBER_IDEAL_MOISTURE).abs() * 0.5
+            }
+            ChamberPurpose::Brood => {
+                (1.0 - (moisture - BROOD_CHAMBER_IDEAL_MOISTURE).abs()) - (depth - 0.5).abs()
+            }
+            ChamberPurpose::FoodStorage => (1.0 - moisture) + (1.0 - depth),
+            ChamberPurpose::Refuse => depth + (1.0 - moisture) * 0.5,
+        }
+    }
+}
+
+/// A chamber the colony has decided to dig out. Its dirt is marked for digging when it's planned.
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+pub struct PlannedChamber {
+    purpose: ChamberPurpose,
+    center: Position,
+}
+
+impl PlannedChamber {
+    pub fn new(purpose: ChamberPurpose, center: Position) -> Self {
+        Self { purpose, center }
+    }
+
+    pub fn purpose(&self) -> ChamberPurpose {
+        self.purpose
+    }
+
+    pub fn center(&self) -> Position {
+        self.center
+    }
+
+    /// Every position within the chamber. Chambers have odd dimensions so they're centered on their center.
+    pub fn positions(&self) -> Vec<Position> {
+        let (width, height) = self.purpose.get_size();
+        let top_left = self.center - Position::new(width / 2, height / 2);
+
+        (0..height)
+            .flat_map(|y| (0..width).map(move |x| top_left + Position::new(x, y)))
+            .collect()
+    }
+
+    /// Whether the two chambers would overlap, or come within `spacing` tiles of one another.
+    fn is_near(&self, other: &PlannedChamber, spacing: isize) -> bool {
+        let (width, height) = self.purpose.get_size();
+        let (other_width, other_height) = other.purpose.get_size();
+
+        (self.center.x - other.center.x).abs() <= (width + other_width) / 2 + spacing
+            && (self.center.y - other.center.y).abs() <= (height + other_height) / 2 + spacing
+    }
+}
+
+/// Decides where the colony's chambers belong and marks their dirt for workers to dig out. Each kind of chamber is planned
+/// once, when the colony is large enough to need it, and the plan is kept with the story so chambers aren't planned twice.
+///
+/// ```
+/// use bevy::prelude::*;
+/// use simulation::{
+///     common::position::Position,
+///     external_event::ExternalSimulationEvent,
+///     headless::{create_headless_app, start_headless_story},
+///     nest_simulation::{
+///         ant::dig_order::DigOrder,
+///         colony_planner::{ChamberPurpose, ColonyPlanner},
+///         element::Element,
+///         nest::{AtNest, Nest},
+///     },
+///     settings::Settings,
+///     SimulationUpdate,
+/// };
+///
+/// fn plan_queen_chamber(seed: u64) -> (Option<Position>, usize, isize) {
+///     let settings = Settings {
+///         initial_ant_worker_count: 10,
+///         ..default()
+///     };
+///
+///     let mut app = create_headless_app(settings, seed);
+///     assert!(start_headless_story(&mut app));
+///
+///     // Dig a shaft straight down from the surface for the queen's chamber to branch off of.
+///     let surface_level = app.world.query::<&Nest>().single(&app.world).surface_level();
+///     app.world
+///         .resource_mut::<Events<ExternalSimulationEvent<AtNest>>>()
+///         .send(ExternalSimulationEvent::PaintElements {
+///             zone: AtNest,
+///             positions: (surface_level + 1..surface_level + 40)
+///                 .map(|y| Position::new(72, y))
+///                 .collect(),
+///             element: Element::Air,
+///             food_kind: None,
+///         });
+///     app.world.run_schedule(SimulationUpdate);
+///
+///     let queen_chamber_center = app
+///         .world
+///         .resource::<ColonyPlanner>()
+///         .get_planned_chamber(ChamberPurpose::QueenChamber)
+///         .map(|planned_chamber| planned_chamber.center());
+///     let dig_order_count = app.world.query::<&DigOrder>().iter(&app.world).count();
+///
+///     (queen_chamber_center, dig_order_count, surface_level)
+/// }
+///
+/// let (queen_chamber_center, dig_order_count, surface_level) = plan_queen_chamber(1);
+/// let queen_chamber_center = queen_chamber_center.expect("queen's chamber wasn't planned");
+///
+/// // The queen's chamber is dug as deep as the shaft allows and every tile of it is marked for digging.
+/// assert!(queen_chamber_center.y > surface_level + 30);
+/// assert_eq!(dig_order_count, 7 * 3);
+///
+/// // The same seed always plans the same nest.
+/// assert_eq!(plan_queen_chamber(1).0, Some(queen_chamber_center));
+/// ```
+#[derive(Resource, Debug, PartialEq, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Resource, Persist)]
+pub struct ColonyPlanner {
+    planned_chambers: Vec<PlannedChamber>,
+    ticks_until_plan: isize,
+}
+
+impl ColonyPlanner {
+    pub fn planned_chambers(&self) -> &[PlannedChamber] {
+        &self.planned_chambers
+    }
+
+    pub fn get_planned_chamber(&self, purpose: ChamberPurpose) -> Option<&PlannedChamber> {
+        self.planned_chambers
+            .iter()
+            .find(|planned_chamber| planned_chamber.purpose == purpose)
+    }
+
+    /// The next chamber the colony needs, if it's large enough to dig one out.
+    fn get_next_purpose(&self, worker_count: usize) -> Option<ChamberPurpose> {
+        ChamberPurpose::all()
+            .into_iter()
+            .find(|purpose| self.get_planned_chamber(*purpose).is_none())
+            .filter(|purpose| worker_count >= purpose.get_min_worker_count())
+    }
+}
+
+pub fn register_colony_planner(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<ColonyPlanner>();
+    app_type_registry.write().register::<PlannedChamber>();
+    app_type_registry.write().register::<Vec<PlannedChamber>>();
+    app_type_registry.write().register::<ChamberPurpose>();
+}
+
+/// Stories loaded from saves keep the plan they were saved with.
+pub fn initialize_colony_planner_resources(mut commands: Commands) {
+    commands.init_resource::<ColonyPlanner>();
+}
+
+pub fn remove_colony_planner_resources(mut commands: Commands) {
+    commands.remove_resource::<ColonyPlanner>();
+}
+
+/// Find the best site for a chamber. Sites must be solid dirt, so nothing is already there, and must border walkable
+/// space, so workers can reach them. Returns None if there's nowhere suitable, e.g. before any tunnels have been dug.
+pub fn find_chamber_site(
+    purpose: ChamberPurpose,
+    planned_chambers: &[PlannedChamber],
+    grid: &Grid,
+    nest: &Nest,
+    moisture_query: &Query<&Moisture>,
+    grid_elements: &GridElements<AtNest>,
+) -> Option<PlannedChamber> {
+    let (width, height) = purpose.get_size();
+    let min_y = nest.surface_level() + MIN_CHAMBER_DEPTH + height / 2;
+    // Leave the bottom row of the nest intact so the chamber has a floor.
+    let max_y = grid.height() - 2 - height / 2;
+    let underground_height = (grid.height() - nest.surface_level()) as f32;
+
+    let mut best_site = None;
+    let mut best_score = f32::MIN;
+
+    for y in min_y..=max_y {
+        for x in width / 2..grid.width() - width / 2 {
+            let site = PlannedChamber::new(purpose, Position::new(x, y));
+
+            if planned_chambers
+                .iter()
+                .any(|planned_chamber| planned_chamber.is_near(&site, CHAMBER_SPACING))
+            {
+                continue;
+            }
+
+            let positions = site.positions();
+
+            if !grid_elements.is_all(&positions, Element::Dirt)
+                || !is_reachable_site(&positions, grid_elements)
+            {
+                continue;
+            }
+
+            let depth = (y - nest.surface_level()) as f32 / underground_height;
+            let moisture = positions
+                .iter()
+                .filter_map(|position| grid_elements.get_entity(*position))
+                .filter_map(|element_entity| moisture_query.get(*element_entity).ok())
+                .map(|moisture| moisture.value() / MAX_MOISTURE)
+                .sum::<f32>()
+                / positions.len() as f32;
+
+            // Ties go to the first site found so plans don't depend on anything but the nest itself.
+            let score = purpose.get_site_score(depth, moisture.min(1.0));
+            if score > best_score {
+                best_score = score;
+                best_site = Some(site);
+            }
+        }
+    }
+
+    best_site
+}
+
+/// A site is reachable if an ant could stand beside it, i.e. some tile bordering the site is walkable.
+fn is_reachable_site(positions: &[Position], grid_elements: &GridElements<AtNest>) -> bool {
+    positions
+        .iter()
+        .flat_map(|position| position.get_adjacent_positions())
+        .filter(|adjacent_position| !positions.contains(adjacent_position))
+        .any(|adjacent_position| is_walkable(adjacent_position, grid_elements))
+}
+
+/// Plan the colony's next chamber once it's large enough to need one. Planned chambers are dug out by idle workers just
+/// like dirt marked by the player.
+pub fn plan_colony_chambers(
+    ants_query: Query<&AntRole, (With<AtNest>, Without<Dead>)>,
+    dig_orders_query: Query<(), (With<DigOrder>, With<AtNest>)>,
+    moisture_query: Query<&Moisture>,
+    nest_query: Query<(&Grid, &Nest)>,
+    grid_elements: GridElements<AtNest>,
+    mut colony_planner: ResMut<ColonyPlanner>,
+    mut commands: Commands,
+) {
+    if colony_planner.ticks_until_plan > 0 {
+        colony_planner.ticks_until_plan -= 1;
+        return;
+    }
+
+    colony_planner.ticks_until_plan = PLAN_INTERVAL_TICKS;
+
+    // Marked dirt, whether it was marked by the player or by the planner, is dug out before more is marked.
+    if !dig_orders_query.is_empty() {
+        return;
+    }
+
+    let worker_count = ants_query
+        .iter()
+        .filter(|role| **role == AntRole::Worker)
+        .count();
+
+    let Some(purpose) = colony_planner.get_next_purpose(worker_count) else {
+        return;
+    };
+
+    let (grid, nest) = nest_query.single();
+    let Some(site) = find_chamber_site(
+        purpose,
+        &colony_planner.planned_chambers,
+        grid,
+        nest,
+        &moisture_query,
+        &grid_elements,
+    ) else {
+        return;
+    };
+
+    for position in site.positions() {
+        commands
+            .entity(*grid_elements.entity(position))
+            .insert(DigOrder);
+    }
+
+    colony_planner.planned_chambers.push(site);
+}
@@ -0,0 +1,140 @@
+use super::{
+    ant::{
+        birthing::Birthing, brood::Brood, digestion::Digestion, hunger::Hunger, nesting::Nested,
+        soldier::Intruder, Angle, AntBundle, AntColor, AntInventory, AntName, AntOrientation,
+        AntRole, Dead, Facing, Initiative,
+    },
+    nest::AtNest,
+};
+use crate::{
+    balance_config::BalanceConfig,
+    common::position::Position,
+    save::ReflectPersist,
+    settings::Settings,
+    story_time::{DEFAULT_TICKS_PER_SECOND, SECONDS_PER_HOUR},
+};
+use bevy::prelude::*;
+use bevy_turborand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Raising a new queen takes a while. The colony carries on without one for a couple of hours.
+const SUCCESSION_TICKS: isize = 2 * SECONDS_PER_HOUR * DEFAULT_TICKS_PER_SECOND;
+
+/// How the colony is getting on with, or without, its queen. The story doesn't end the moment the queen dies. If
+/// there's brood left, one is raised into a new queen. Otherwise, the colony declines: workers stop raising brood and
+/// live out their days until none are left.
+#[derive(Resource, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Resource, Persist)]
+pub enum ColonyStatus {
+    #[default]
+    Thriving,
+    // The queen has died and an heir, chosen from among the brood, is being raised to take her place.
+    Succession {
+        remaining_ticks: isize,
+    },
+    // The queen has died with no brood left to raise a successor from.
+    Declining,
+}
+
+impl ColonyStatus {
+    pub fn is_declining(&self) -> bool {
+        *self == ColonyStatus::Declining
+    }
+}
+
+/// The brood chosen to become the colony's next queen. Heirs don't hatch into workers.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Component, Persist)]
+pub struct Heir;
+
+pub fn register_colony_status(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<ColonyStatus>();
+    app_type_registry.write().register::<Heir>();
+}
+
+pub fn initialize_colony_status_resources(mut commands: Commands) {
+    commands.init_resource::<ColonyStatus>();
+}
+
+pub fn remove_colony_status_resources(mut commands: Commands) {
+    commands.remove_resource::<ColonyStatus>();
+}
+
+/// Keep track of whether the colony has a queen. When she dies, an heir is chosen from among the brood and, if it
+/// survives long enough, emerges as the new queen. Another heir is chosen if the first is lost, e.g. because it starved
+/// or was carried off by raiders. The colony goes into decline once there's no brood left to choose from.
+/// A queen turning up some other way, e.g. placed by the player, puts an end to the succession, or decline.
+pub fn update_colony_status(
+    ants_query: Query<&AntRole, (With<AtNest>, Without<Dead>, Without<Intruder>)>,
+    heirs_query: Query<(Entity, &Position, &AntColor), (With<Heir>, With<Brood>, With<AtNest>)>,
+    brood_query: Query<Entity, (With<Brood>, With<AtNest>)>,
+    settings: Res<Settings>,
+    balance_config: Res<BalanceConfig>,
+    mut colony_status: ResMut<ColonyStatus>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    let has_queen = ants_query.iter().any(|role| *role == AntRole::Queen);
+    if has_queen {
+        if *colony_status != ColonyStatus::Thriving {
+            *colony_status = ColonyStatus::Thriving;
+        }
+
+        return;
+    }
+
+    let remaining_ticks = match *colony_status {
+        ColonyStatus::Thriving => SUCCESSION_TICKS,
+        ColonyStatus::Succession { remaining_ticks } => remaining_ticks,
+        ColonyStatus::Declining => return,
+    };
+
+    let Some((heir_entity, heir_position, heir_color)) = heirs_query.iter().next() else {
+        match brood_query.iter().next() {
+            Some(brood_entity) => {
+                commands.entity(brood_entity).insert(Heir);
+                *colony_status = ColonyStatus::Succession { remaining_ticks };
+            }
+            None => *colony_status = ColonyStatus::Declining,
+        }
+
+        return;
+    };
+
+    if remaining_ticks > 0 {
+        *colony_status = ColonyStatus::Succession {
+            remaining_ticks: remaining_ticks - 1,
+        };
+
+        return;
+    }
+
+    commands.entity(heir_entity).despawn();
+
+    let mut rng = rng.reborrow();
+
+    // The new queen has no need to found a nest. She takes over her mother's and starts laying right away.
+    let queen_ant_bundle = AntBundle::new(
+        *heir_position,
+        AntColor(heir_color.0),
+        AntOrientation::new(Facing::random(&mut rng), Angle::Zero),
+        AntInventory::new(AntRole::Queen),
+        AntRole::Queen,
+        AntName(String::from("Queen")),
+        Initiative::new(&mut rng),
+        AtNest,
+        Hunger::new(
+            settings.max_hunger_time,
+            balance_config.appetite.get(AntRole::Queen),
+        ),
+        Digestion::new(settings.max_digestion_time),
+    );
+
+    commands.spawn((
+        queen_ant_bundle,
+        Nested,
+        Birthing::new(settings.max_birthing_time),
+    ));
+
+    *colony_status = ColonyStatus::Thriving;
+}
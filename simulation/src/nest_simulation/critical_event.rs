@@ -0,0 +1,65 @@
+use super::{
+    ant::{brood::Brood, hunger::Hunger, AntRole, Dead},
+    element::Element,
+    nest::AtNest,
+};
+use crate::{
+    balance_config::BalanceConfig,
+    common::{grid::GridElements, position::Position},
+};
+use bevy::{prelude::*, utils::HashSet};
+
+/// Pivotal moments in a colony's story which are easy to miss when time is passing quickly.
+/// Sent once, when the moment happens, rather than every tick it remains true.
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+pub enum CriticalEvent {
+    QueenStarving,
+    BroodFlooded,
+    // Sent when raiders reach the nest entrance. See `raiders_arrive`.
+    NestRaided,
+}
+
+pub fn queen_notify_starving(
+    ants_query: Query<(&Hunger, &AntRole), (Changed<Hunger>, With<AtNest>, Without<Dead>)>,
+    balance_config: Res<BalanceConfig>,
+    mut critical_events: EventWriter<CriticalEvent>,
+) {
+    let is_queen_starving = ants_query.iter().any(|(hunger, role)| {
+        *role == AntRole::Queen && hunger.has_started_starving(&balance_config.hunger)
+    });
+
+    if is_queen_starving {
+        critical_events.send(CriticalEvent::QueenStarving);
+    }
+}
+
+/// Brood is flooded when water reaches it, or flows next to it. Only brood which wasn't already flooded is noticed,
+/// so water sloshing around a nursery doesn't raise the alarm over and over.
+pub fn brood_notify_flooded(
+    brood_query: Query<(Entity, &Position), (With<Brood>, With<AtNest>)>,
+    grid_elements: GridElements<AtNest>,
+    mut flooded_brood: Local<HashSet<Entity>>,
+    mut critical_events: EventWriter<CriticalEvent>,
+) {
+    let current_flooded_brood = brood_query
+        .iter()
+        .filter(|(_, position)| {
+            grid_elements.is(**position, Element::Water)
+                || position
+                    .get_adjacent_positions()
+                    .into_iter()
+                    .any(|adjacent_position| grid_elements.is(adjacent_position, Element::Water))
+        })
+        .map(|(brood_entity, _)| brood_entity)
+        .collect::<HashSet<_>>();
+
+    let is_newly_flooded = current_flooded_brood
+        .iter()
+        .any(|brood_entity| !flooded_brood.contains(brood_entity));
+
+    if is_newly_flooded {
+        critical_events.send(CriticalEvent::BroodFlooded);
+    }
+
+    *flooded_brood = current_flooded_brood;
+}
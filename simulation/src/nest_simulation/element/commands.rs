@@ -18,6 +18,30 @@ pub trait ElementCommandsExt {
         target_element: Entity,
         zone: Z,
     );
+    /// Replace an element, as with `replace_element`, and give the new element some extra components from the start,
+    /// e.g. the kind of food which a germinated seed becomes.
+    fn replace_element_with<Z: Zone, B: Bundle>(
+        &mut self,
+        position: Position,
+        element: Element,
+        target_element: Entity,
+        bundle: B,
+        zone: Z,
+    );
+    fn replace_elements<Z: Zone + Copy>(
+        &mut self,
+        replacements: Vec<(Position, Entity)>,
+        element: Element,
+        zone: Z,
+    );
+    /// Replace many elements, as with `replace_elements`, and give each new element a copy of some extra components.
+    fn replace_elements_with<Z: Zone + Copy, B: Bundle + Clone>(
+        &mut self,
+        replacements: Vec<(Position, Entity)>,
+        element: Element,
+        bundle: B,
+        zone: Z,
+    );
     fn spawn_element<Z: Zone>(&mut self, position: Position, element: Element, zone: Z);
     fn toggle_element_command<C: Component, Z: Zone>(
         &mut self,
@@ -41,6 +65,53 @@ impl<'w, 's> ElementCommandsExt for Commands<'w, 's> {
             position,
             target_element,
             element,
+            bundle: (),
+            zone,
+        })
+    }
+
+    fn replace_element_with<Z: Zone, B: Bundle>(
+        &mut self,
+        position: Position,
+        element: Element,
+        target_element: Entity,
+        bundle: B,
+        zone: Z,
+    ) {
+        self.add(ReplaceElementCommand {
+            position,
+            target_element,
+            element,
+            bundle,
+            zone,
+        })
+    }
+
+    fn replace_elements<Z: Zone + Copy>(
+        &mut self,
+        replacements: Vec<(Position, Entity)>,
+        element: Element,
+        zone: Z,
+    ) {
+        self.add(ReplaceElementsCommand {
+            replacements,
+            element,
+            bundle: (),
+            zone,
+        })
+    }
+
+    fn replace_elements_with<Z: Zone + Copy, B: Bundle + Clone>(
+        &mut self,
+        replacements: Vec<(Position, Entity)>,
+        element: Element,
+        bundle: B,
+        zone: Z,
+    ) {
+        self.add(ReplaceElementsCommand {
+            replacements,
+            element,
+            bundle,
             zone,
         })
     }
@@ -71,15 +142,17 @@ impl<'w, 's> ElementCommandsExt for Commands<'w, 's> {
     }
 }
 
-struct ReplaceElementCommand<Z: Zone> {
+struct ReplaceElementCommand<Z: Zone, B: Bundle> {
     target_element: Entity,
     element: Element,
     position: Position,
+    // Spawned alongside the new element.
+    bundle: B,
     // TODO: maybe just infer this from target_element
     zone: Z,
 }
 
-impl<Z: Zone> Command for ReplaceElementCommand<Z> {
+impl<Z: Zone, B: Bundle> Command for ReplaceElementCommand<Z, B> {
     fn apply(self, world: &mut World) {
         let mut system_state: SystemState<GridElements<Z>> = SystemState::new(world);
         let grid_elements = system_state.get(world);
@@ -100,7 +173,10 @@ impl<Z: Zone> Command for ReplaceElementCommand<Z> {
         world.entity_mut(*existing_entity).despawn();
 
         let entity = world
-            .spawn(ElementBundle::new(self.element, self.position, self.zone))
+            .spawn((
+                ElementBundle::new(self.element, self.position, self.zone),
+                self.bundle,
+            ))
             .id();
 
         let mut system_state: SystemState<GridElementsMut<Z>> = SystemState::new(world);
@@ -110,6 +186,55 @@ impl<Z: Zone> Command for ReplaceElementCommand<Z> {
     }
 }
 
+/// Replace many elements with the same type of element at once. Useful when painting large areas because the grid
+/// is only looked up once, rather than once per element, and the world sees every replacement in the same instant.
+struct ReplaceElementsCommand<Z: Zone, B: Bundle + Clone> {
+    replacements: Vec<(Position, Entity)>,
+    element: Element,
+    // Copied onto each new element.
+    bundle: B,
+    zone: Z,
+}
+
+impl<Z: Zone + Copy, B: Bundle + Clone> Command for ReplaceElementsCommand<Z, B> {
+    fn apply(self, world: &mut World) {
+        let mut system_state: SystemState<GridElements<Z>> = SystemState::new(world);
+        let grid_elements = system_state.get(world);
+
+        // Skip stale replacements. The element may have changed since the replacement was requested.
+        let replacements = self
+            .replacements
+            .into_iter()
+            .filter(|(position, target_element)| {
+                grid_elements.get_entity(*position) == Some(target_element)
+            })
+            .collect::<Vec<_>>();
+
+        let spawned_elements = replacements
+            .into_iter()
+            .map(|(position, target_element)| {
+                world.entity_mut(target_element).despawn();
+
+                let entity = world
+                    .spawn((
+                        ElementBundle::new(self.element, position, self.zone),
+                        self.bundle.clone(),
+                    ))
+                    .id();
+
+                (position, entity)
+            })
+            .collect::<Vec<_>>();
+
+        let mut system_state: SystemState<GridElementsMut<Z>> = SystemState::new(world);
+        let mut grid_elements = system_state.get_mut(world);
+
+        for (position, entity) in spawned_elements {
+            grid_elements.set(position, entity);
+        }
+    }
+}
+
 struct SpawnElementCommand<Z: Zone> {
     element: Element,
     position: Position,
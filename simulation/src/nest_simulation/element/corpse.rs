@@ -0,0 +1,59 @@
+use super::{commands::ElementCommandsExt, Corpse, Element};
+use crate::{
+    common::position::Position, nest_simulation::nest::AtNest, save::ReflectPersist,
+    story_time::DEFAULT_TICKS_PER_SECOND,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// How long a corpse lies in the nest before it has decayed back into dirt.
+const CORPSE_DECAY_TICKS: isize = 60 * 60 * 24 * DEFAULT_TICKS_PER_SECOND; // 1 day
+
+/// Corpses decay over time. Only corpses lying in the nest decay. Carried corpses are spared.
+/// NOTE: Elements are respawned when they're picked up and dropped, so a corpse's decay starts over once it's moved.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect)]
+#[reflect(Component, Persist)]
+pub struct Decay {
+    remaining_ticks: isize,
+}
+
+impl Default for Decay {
+    fn default() -> Self {
+        Self {
+            remaining_ticks: CORPSE_DECAY_TICKS,
+        }
+    }
+}
+
+impl Decay {
+    pub fn remaining_ticks(&self) -> isize {
+        self.remaining_ticks
+    }
+}
+
+pub fn register_corpse(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<Decay>();
+}
+
+/// Start decaying corpses as they're laid down in the nest.
+pub fn corpses_add_decay(
+    corpses_query: Query<Entity, (With<Corpse>, With<AtNest>, Without<Decay>)>,
+    mut commands: Commands,
+) {
+    for corpse_entity in corpses_query.iter() {
+        commands.entity(corpse_entity).insert(Decay::default());
+    }
+}
+
+pub fn corpses_decay(
+    mut corpses_query: Query<(Entity, &Position, &mut Decay), (With<Corpse>, With<AtNest>)>,
+    mut commands: Commands,
+) {
+    for (corpse_entity, position, mut decay) in corpses_query.iter_mut() {
+        decay.remaining_ticks -= 1;
+
+        if decay.remaining_ticks <= 0 {
+            commands.replace_element(*position, Element::Dirt, corpse_entity, AtNest);
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use super::{Element, FoodKind};
+use crate::ron_asset::{RonAsset, RonConfig};
+use bevy::{prelude::*, reflect::TypePath, utils::HashMap};
+use serde::Deserialize;
+
+pub const ELEMENT_DEFINITIONS_PATH: &str = "config/default.elements.ron";
+
+// Elements without a definition are inert. They can't be dug and don't fall.
+const UNDEFINED_ELEMENT: ElementDefinition = ElementDefinition {
+    diggable: false,
+    hardness: 1.0,
+    weight: 1,
+    sprite_column: None,
+    winter_sprite_column: None,
+    thumbnail_color: None,
+    gravity: ElementGravity::None,
+};
+
+/// How an element responds to having air beneath it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+pub enum ElementGravity {
+    // Stays put. Some elements, like water, move according to their own rules instead.
+    None,
+    // Falls into air below and tips off of ledges.
+    Falls,
+    // Packed in underground, but falls like sand on the surface where there's nothing behind it to cling to.
+    FallsAboveground,
+}
+
+/// Everything about an element that isn't specific to one system. Adding an element takes an entry in the definitions
+/// file, and a column in the element sprite sheet, rather than another arm in every `match` on Element.
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct ElementDefinition {
+    diggable: bool,
+    // How many turns of digging it takes to loosen the element. Most elements come loose on the first.
+    #[serde(default = "default_hardness")]
+    hardness: f32,
+    // How many ants it takes to carry the element. Elements heavier than one ant are carried by a group of ants.
+    #[serde(default = "default_weight")]
+    weight: usize,
+    // Which column of the element sprite sheet the element is drawn from. Elements without one aren't drawn.
+    #[serde(default)]
+    sprite_column: Option<usize>,
+    // Drawn instead when the element lies on the surface, exposed to the sky, during winter.
+    #[serde(default)]
+    winter_sprite_column: Option<usize>,
+    // A flat color, roughly matching the element's sprite, for save thumbnails where sprites are too detailed to use.
+    #[serde(default)]
+    thumbnail_color: Option<(u8, u8, u8)>,
+    gravity: ElementGravity,
+}
+
+// Kinds of food without a definition are as filling as food_digestion and are drawn like any other food.
+const UNDEFINED_FOOD_KIND: FoodKindDefinition = FoodKindDefinition {
+    nutrition: 1.0,
+    sprite_column: None,
+};
+
+/// What sets one kind of food apart from another. Everything else about food is shared by every kind and comes from
+/// the Food element's definition.
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct FoodKindDefinition {
+    // How filling the food is, as a multiple of BalanceConfig.food_digestion.
+    nutrition: f32,
+    // Drawn instead of the Food element's sprite column.
+    #[serde(default)]
+    sprite_column: Option<usize>,
+}
+
+impl FoodKindDefinition {
+    pub fn nutrition(&self) -> f32 {
+        self.nutrition
+    }
+
+    pub fn sprite_column(&self) -> Option<usize> {
+        self.sprite_column
+    }
+}
+
+fn default_hardness() -> f32 {
+    1.0
+}
+
+fn default_weight() -> usize {
+    1
+}
+
+impl ElementDefinition {
+    pub fn is_diggable(&self) -> bool {
+        self.diggable
+    }
+
+    pub fn hardness(&self) -> f32 {
+        self.hardness
+    }
+
+    pub fn weight(&self) -> usize {
+        self.weight
+    }
+
+    /// Heavy elements can't be dug out by a lone ant. They're only picked up by a group of ants working together.
+    pub fn is_heavy(&self) -> bool {
+        self.weight > 1
+    }
+
+    pub fn sprite_column(&self) -> Option<usize> {
+        self.sprite_column
+    }
+
+    pub fn winter_sprite_column(&self) -> Option<usize> {
+        self.winter_sprite_column
+    }
+
+    pub fn thumbnail_color(&self) -> Option<(u8, u8, u8)> {
+        self.thumbnail_color
+    }
+
+    pub fn gravity(&self) -> ElementGravity {
+        self.gravity
+    }
+}
+
+/// Every element's definition, keyed by element, and every kind of food's definition, keyed by kind. Like
+/// BalanceConfig, these are loaded from an asset file when the app starts and, when built with `dev-hot-reload`,
+/// changes to the file are applied while the app is running.
+///
+/// NOTE: The definitions file is also compiled in, and used by Default, so definitions are available before the asset
+/// finishes loading without needing to be kept in sync by hand.
+///
+/// ```
+/// use simulation::nest_simulation::element::{definition::ElementDefinitions, Element, FoodKind};
+///
+/// let element_definitions = ElementDefinitions::default();
+///
+/// assert!(element_definitions.get(Element::Dirt).is_diggable());
+/// assert!(!element_definitions.get(Element::Stone).is_diggable());
+/// assert!(element_definitions.get(Element::Fungus).hardness() > element_definitions.get(Element::Sand).hardness());
+/// assert!(element_definitions.get(Element::Prey).is_heavy());
+/// assert!(
+///     element_definitions.get_food_kind(FoodKind::Sugar).nutrition()
+///         > element_definitions.get_food_kind(FoodKind::Protein).nutrition()
+/// );
+///
+/// // Every kind of food is drawn from its own column, and plain food is drawn like plant food.
+/// assert_eq!(
+///     element_definitions.sprite_column(Element::Food, None),
+///     element_definitions.sprite_column(Element::Food, Some(FoodKind::Plant))
+/// );
+/// assert_ne!(
+///     element_definitions.sprite_column(Element::Food, Some(FoodKind::Plant)),
+///     element_definitions.sprite_column(Element::Food, Some(FoodKind::Sugar))
+/// );
+/// ```
+#[derive(Resource, Asset, TypePath, Clone, Debug, Deserialize)]
+pub struct ElementDefinitions {
+    elements: HashMap<Element, ElementDefinition>,
+    food_kinds: HashMap<FoodKind, FoodKindDefinition>,
+}
+
+impl Default for ElementDefinitions {
+    fn default() -> Self {
+        ron::de::from_str(include_str!(
+            "../../../../assets/config/default.elements.ron"
+        ))
+        .expect("Built-in element definitions should be valid")
+    }
+}
+
+impl ElementDefinitions {
+    pub fn get(&self, element: Element) -> &ElementDefinition {
+        self.elements.get(&element).unwrap_or(&UNDEFINED_ELEMENT)
+    }
+
+    pub fn get_food_kind(&self, food_kind: FoodKind) -> &FoodKindDefinition {
+        self.food_kinds
+            .get(&food_kind)
+            .unwrap_or(&UNDEFINED_FOOD_KIND)
+    }
+
+    /// Which column of the element sprite sheet an element is drawn from. Food is drawn according to its kind.
+    pub fn sprite_column(&self, element: Element, food_kind: Option<FoodKind>) -> Option<usize> {
+        food_kind
+            .filter(|_| element.is_food())
+            .and_then(|food_kind| self.get_food_kind(food_kind).sprite_column)
+            .or(self.get(element).sprite_column)
+    }
+
+    /// The element sprite sheet is as wide as it needs to be to fit every column an element is drawn from.
+    pub fn sprite_columns(&self) -> usize {
+        self.elements
+            .values()
+            .flat_map(|definition| [definition.sprite_column, definition.winter_sprite_column])
+            .chain(
+                self.food_kinds
+                    .values()
+                    .map(|definition| definition.sprite_column),
+            )
+            .flatten()
+            .max()
+            .map_or(0, |column| column + 1)
+    }
+}
+
+impl RonAsset for ElementDefinitions {
+    const EXTENSIONS: &'static [&'static str] = &["elements.ron"];
+}
+
+impl RonConfig for ElementDefinitions {
+    const PATH: &'static str = ELEMENT_DEFINITIONS_PATH;
+}
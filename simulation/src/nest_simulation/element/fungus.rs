@@ -0,0 +1,119 @@
+use super::{commands::ElementCommandsExt, water::Moisture, Element, Food, Fungus};
+use crate::{
+    common::{grid::GridElements, logistics::ColonyLogistics, position::Position},
+    nest_simulation::nest::{AtNest, Nest},
+    story_time::{Season, StoryTime},
+};
+use bevy::{prelude::*, utils::HashSet};
+use bevy_turborand::prelude::*;
+
+// Fungus is slow growing. A garden takes a while to establish once food has been laid down in it.
+const FUNGUS_GROWTH_CHANCE: f64 = 0.001;
+// Each time fungus grows it feeds on the food beside it. Food is eventually used up unless the colony restocks it.
+const FUNGUS_SUBSTRATE_CONSUME_CHANCE: f64 = 0.1;
+// Neglected fungus withers slowly rather than all at once.
+const FUNGUS_WITHER_CHANCE: f64 = 0.0005;
+// Dirt must be at least this moist for fungus to grow against it.
+const FUNGUS_MIN_MOISTURE: f32 = 0.25;
+
+/// Fungus only thrives underground, away from the cold of winter, and beside moist dirt.
+/// NOTE: There's no model of temperature, so being underground outside of winter stands in for being warm.
+fn is_fungus_habitable(
+    position: Position,
+    nest: &Nest,
+    season: Season,
+    moisture_query: &Query<&Moisture>,
+    grid_elements: &GridElements<AtNest>,
+) -> bool {
+    if season == Season::Winter || !nest.is_underground(&position) {
+        return false;
+    }
+
+    position
+        .get_adjacent_positions()
+        .into_iter()
+        .filter_map(|adjacent_position| grid_elements.get_entity(adjacent_position))
+        .any(|adjacent_entity| {
+            moisture_query
+                .get(*adjacent_entity)
+                .map_or(false, |moisture| moisture.value() >= FUNGUS_MIN_MOISTURE)
+        })
+}
+
+/// Fungus grows into the open space beside food stored in warm, moist chambers. The food is the fungus's substrate
+/// and is slowly consumed as the fungus grows.
+pub fn fungus_grow(
+    food_query: Query<(Entity, &Position), (With<Food>, With<AtNest>)>,
+    moisture_query: Query<&Moisture>,
+    nest_query: Query<&Nest>,
+    grid_elements: GridElements<AtNest>,
+    story_time: Res<StoryTime>,
+    mut colony_logistics: ResMut<ColonyLogistics>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    let nest = nest_query.single();
+    let season = story_time.season();
+
+    // Several pieces of food may border the same open space. Only grow fungus there once.
+    let mut grown_positions = HashSet::new();
+
+    for (food_entity, food_position) in food_query.iter() {
+        if !rng.chance(FUNGUS_GROWTH_CHANCE) {
+            continue;
+        }
+
+        let growth_positions = food_position
+            .get_adjacent_positions()
+            .into_iter()
+            .filter(|&position| {
+                grid_elements.is(position, Element::Air)
+                    && !grown_positions.contains(&position)
+                    && is_fungus_habitable(position, nest, season, &moisture_query, &grid_elements)
+            })
+            .collect::<Vec<_>>();
+
+        let Some(&growth_position) = rng.sample(&growth_positions) else {
+            continue;
+        };
+
+        let air_entity = *grid_elements.entity(growth_position);
+        commands.replace_element(growth_position, Element::Fungus, air_entity, AtNest);
+        grown_positions.insert(growth_position);
+
+        if rng.chance(FUNGUS_SUBSTRATE_CONSUME_CHANCE) {
+            commands.replace_element(*food_position, Element::Air, food_entity, AtNest);
+            colony_logistics.record_spoiled();
+        }
+    }
+}
+
+/// Fungus which has dried out, been exposed to the cold, or been carried to the surface slowly withers away.
+pub fn fungus_wither(
+    fungus_query: Query<(Entity, &Position), (With<Fungus>, With<AtNest>)>,
+    moisture_query: Query<&Moisture>,
+    nest_query: Query<&Nest>,
+    grid_elements: GridElements<AtNest>,
+    story_time: Res<StoryTime>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    let nest = nest_query.single();
+    let season = story_time.season();
+
+    for (fungus_entity, fungus_position) in fungus_query.iter() {
+        if is_fungus_habitable(
+            *fungus_position,
+            nest,
+            season,
+            &moisture_query,
+            &grid_elements,
+        ) {
+            continue;
+        }
+
+        if rng.chance(FUNGUS_WITHER_CHANCE) {
+            commands.replace_element(*fungus_position, Element::Air, fungus_entity, AtNest);
+        }
+    }
+}
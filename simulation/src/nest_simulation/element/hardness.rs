@@ -0,0 +1,69 @@
+use super::Element;
+use crate::{
+    balance_config::DigBalance,
+    common::{grid::GridElements, position::Position, Zone},
+    save::ReflectPersist,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// How much digging has gone into an element which is too hard to dig out in a single turn. Progress is kept on the
+/// element, rather than the ant, so whoever digs at it next picks up where the last ant left off.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Component, Persist)]
+pub struct DigProgress {
+    value: f32,
+}
+
+impl DigProgress {
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    /// Spend a turn digging. `rate` is how much of a turn's worth of digging gets done, e.g. less when the element is
+    /// dry and packed hard. Returns whether the element has come loose.
+    ///
+    /// ```
+    /// use simulation::nest_simulation::element::hardness::DigProgress;
+    ///
+    /// // Soft elements come loose on the first turn.
+    /// assert!(DigProgress::default().dig(1.0, 1.0));
+    ///
+    /// let mut dig_progress = DigProgress::default();
+    /// assert!(!dig_progress.dig(0.5, 1.5));
+    /// assert!(!dig_progress.dig(0.5, 1.5));
+    /// assert!(dig_progress.dig(0.5, 1.5));
+    /// ```
+    pub fn dig(&mut self, rate: f32, hardness: f32) -> bool {
+        self.value += rate;
+
+        self.value >= hardness
+    }
+}
+
+pub fn register_hardness(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<DigProgress>();
+}
+
+/// How many turns of digging it takes to loosen the element at a given position. Dirt beside stone is compacted and
+/// takes longer to dig out than dirt elsewhere.
+pub fn get_hardness<Z: Zone>(
+    element: Element,
+    position: Position,
+    grid_elements: &GridElements<Z>,
+    dig_balance: &DigBalance,
+) -> f32 {
+    let hardness = grid_elements.definition(element).hardness();
+
+    let is_compacted = element == Element::Dirt
+        && position
+            .get_adjacent_positions()
+            .into_iter()
+            .any(|adjacent_position| grid_elements.is(adjacent_position, Element::Stone));
+
+    if is_compacted {
+        hardness * dig_balance.compacted_dirt_hardness_multiplier
+    } else {
+        hardness
+    }
+}
@@ -1,45 +1,134 @@
 pub mod commands;
+pub mod corpse;
+pub mod definition;
+pub mod fungus;
+pub mod hardness;
+pub mod mud;
+pub mod prey;
+pub mod seed;
+pub mod water;
 
+use self::prey::Butchering;
 use super::nest::AtNest;
-use crate::common::{grid::GridElements, position::Position, Zone};
+use crate::{
+    common::{grid::GridElements, position::Position, Zone},
+    save::ReflectPersist,
+};
 use bevy::{prelude::*, utils::HashSet};
 use serde::{Deserialize, Serialize};
 
 #[derive(Component, Reflect, Default)]
-#[reflect(Component)]
+#[reflect(Component, Persist)]
 pub struct Air;
 
 #[derive(Component, Reflect, Default)]
-#[reflect(Component)]
+#[reflect(Component, Persist)]
 pub struct Dirt;
 
 #[derive(Component, Reflect, Default)]
-#[reflect(Component)]
+#[reflect(Component, Persist)]
 pub struct Sand;
 #[derive(Component, Reflect, Default)]
-#[reflect(Component)]
+#[reflect(Component, Persist)]
 pub struct Food;
 
+#[derive(Component, Reflect, Default)]
+#[reflect(Component, Persist)]
+pub struct Water;
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component, Persist)]
+pub struct Corpse;
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component, Persist)]
+pub struct Seed;
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component, Persist)]
+pub struct Fungus;
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component, Persist)]
+pub struct Prey;
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component, Persist)]
+pub struct Waste;
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component, Persist)]
+pub struct Stone;
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component, Persist)]
+pub struct Mud;
+
+/// Food differs in how filling it is. Adults can live on any of it, but larvae only grow when fed protein.
+/// Kept alongside Element::Food, on elements and on carried food, rather than in the Element itself so saves from
+/// before food had kinds still load. Food without a kind is plant food.
 #[derive(
     Component, Eq, Hash, PartialEq, Copy, Clone, Debug, Serialize, Deserialize, Reflect, Default,
 )]
-#[reflect(Component)]
+#[reflect(Component, Persist)]
+pub enum FoodKind {
+    // Plant matter, like seeds which have germinated in the nest. Seeds themselves are `Element::Seed` and aren't eaten
+    // until they've germinated.
+    #[default]
+    Plant,
+    // Honeydew and nectar. Very filling, but nothing to grow on.
+    Sugar,
+    // Insects, and fungus cultivated in the nest. The only food larvae can grow on.
+    Protein,
+}
+
+impl FoodKind {
+    pub const ALL: [FoodKind; 3] = [FoodKind::Plant, FoodKind::Sugar, FoodKind::Protein];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FoodKind::Plant => "Plant",
+            FoodKind::Sugar => "Sugar",
+            FoodKind::Protein => "Protein",
+        }
+    }
+}
+
+#[derive(
+    Component, Eq, Hash, PartialEq, Copy, Clone, Debug, Serialize, Deserialize, Reflect, Default,
+)]
+#[reflect(Component, Persist)]
 pub enum Element {
     #[default]
     Air,
     Dirt,
     Sand,
     Food,
+    Water,
+    // The remains of a nestmate. Workers carry corpses out to the midden where they decay back into dirt.
+    Corpse,
+    // Scattered across the crater. Harvesters store seeds in the nest where they germinate into food.
+    Seed,
+    // Cultivated on stored food in warm, moist chambers. Ants harvest it as food.
+    Fungus,
+    // A large insect, too heavy for one ant. Workers carry it home together where it's butchered into protein.
+    Prey,
+    // Leftovers from meals and the remains of brood which didn't survive. Workers haul it to the refuse chamber.
+    Waste,
+    // Runs through the dirt in veins. Too hard for ants to dig, so tunnels have to find a way around it.
+    Stone,
+    // Dirt churned up by standing water. Slow going underfoot, but ants plaster it into tunnel walls. Dries back to dirt.
+    Mud,
 }
 
 impl Element {
-    pub fn is_diggable(&self) -> bool {
-        match self {
-            Element::Dirt => true,
-            Element::Sand => true,
-            Element::Food => true,
-            Element::Air => false,
-        }
+    pub fn is_food(&self) -> bool {
+        *self == Element::Food
+    }
+
+    // Seeds aren't eaten until they've germinated but, like food, they're gathered and brought home.
+    pub fn is_forage(&self) -> bool {
+        self.is_food() || *self == Element::Seed
     }
 }
 
@@ -65,10 +154,20 @@ impl<Z: Zone> ElementBundle<Z> {
 
 pub fn register_element(app_type_registry: ResMut<AppTypeRegistry>) {
     app_type_registry.write().register::<Element>();
+    app_type_registry.write().register::<FoodKind>();
     app_type_registry.write().register::<Air>();
     app_type_registry.write().register::<Food>();
     app_type_registry.write().register::<Dirt>();
     app_type_registry.write().register::<Sand>();
+    app_type_registry.write().register::<Water>();
+    app_type_registry.write().register::<Corpse>();
+    app_type_registry.write().register::<Seed>();
+    app_type_registry.write().register::<Fungus>();
+    app_type_registry.write().register::<Prey>();
+    app_type_registry.write().register::<Waste>();
+    app_type_registry.write().register::<Stone>();
+    app_type_registry.write().register::<Mud>();
+    app_type_registry.write().register::<Butchering>();
 }
 
 /// Element entities are represented by their Element enum, but the value of this enum isn't Queryable.
@@ -77,7 +176,20 @@ pub fn register_element(app_type_registry: ResMut<AppTypeRegistry>) {
 pub fn denormalize_element(
     element_query: Query<
         (Entity, &Element),
-        (Without<Air>, Without<Dirt>, Without<Sand>, Without<Food>),
+        (
+            Without<Air>,
+            Without<Dirt>,
+            Without<Sand>,
+            Without<Food>,
+            Without<Water>,
+            Without<Corpse>,
+            Without<Seed>,
+            Without<Fungus>,
+            Without<Prey>,
+            Without<Waste>,
+            Without<Stone>,
+            Without<Mud>,
+        ),
     >,
     mut commands: Commands,
 ) {
@@ -95,21 +207,193 @@ pub fn denormalize_element(
             Element::Food => {
                 commands.entity(entity).insert(Food);
             }
+            Element::Water => {
+                commands.entity(entity).insert(Water);
+            }
+            Element::Corpse => {
+                commands.entity(entity).insert(Corpse);
+            }
+            Element::Seed => {
+                commands.entity(entity).insert(Seed);
+            }
+            Element::Fungus => {
+                commands.entity(entity).insert(Fungus);
+            }
+            Element::Prey => {
+                commands.entity(entity).insert(Prey);
+            }
+            Element::Waste => {
+                commands.entity(entity).insert(Waste);
+            }
+            Element::Stone => {
+                commands.entity(entity).insert(Stone);
+            }
+            Element::Mud => {
+                commands.entity(entity).insert(Mud);
+            }
+        }
+    }
+}
+
+// Neighbors are ordered clockwise from north. Each has its own bit in `ElementExposure`.
+const EXPOSURE_NEIGHBORS: [Position; 8] = [
+    Position::NEG_Y,
+    Position::new(1, -1),
+    Position::X,
+    Position::ONE,
+    Position::Y,
+    Position::new(-1, 1),
+    Position::NEG_X,
+    Position::NEG_ONE,
+];
+
+// Tilesets with a tile for every way corners can be exposed, alongside sides, have 47 tiles. Each is indexed by its
+// place among the 47 distinct masks of covered neighbors, in ascending order, so 0 is fully exposed and 46 fully covered.
+const BLOB_INDICES: [u8; 256] = get_blob_indices();
+
+const fn get_blob_indices() -> [u8; 256] {
+    let mut indices = [0; 256];
+    let mut index = 0;
+    let mut covered = 0;
+
+    while covered < 256 {
+        if get_blob_mask(!(covered as u8)) == covered as u8 {
+            indices[covered] = index;
+            index += 1;
+        }
+
+        covered += 1;
+    }
+
+    indices
+}
+
+/// Which neighbors are covered, keeping only the corners which show, i.e. those whose sides are both covered.
+/// An exposed corner between exposed sides looks no different from a covered one.
+const fn get_blob_mask(exposed: u8) -> u8 {
+    let covered = !exposed;
+    let mut mask = covered
+        & (ElementExposure::NORTH
+            | ElementExposure::EAST
+            | ElementExposure::SOUTH
+            | ElementExposure::WEST);
+
+    let corners = [
+        (
+            ElementExposure::NORTH_EAST,
+            ElementExposure::NORTH,
+            ElementExposure::EAST,
+        ),
+        (
+            ElementExposure::SOUTH_EAST,
+            ElementExposure::SOUTH,
+            ElementExposure::EAST,
+        ),
+        (
+            ElementExposure::SOUTH_WEST,
+            ElementExposure::SOUTH,
+            ElementExposure::WEST,
+        ),
+        (
+            ElementExposure::NORTH_WEST,
+            ElementExposure::NORTH,
+            ElementExposure::WEST,
+        ),
+    ];
+
+    let mut i = 0;
+    while i < corners.len() {
+        let (corner, side, other_side) = corners[i];
+
+        if covered & side != 0 && covered & other_side != 0 {
+            mask |= covered & corner;
         }
+
+        i += 1;
     }
+
+    mask
 }
 
+/// Which of an element's eight neighbors are open to Air, one bit per neighbor. Corners are kept, as well as sides,
+/// so renderers with enough tiles can round off the corners of tunnels rather than leaving blocky seams.
 // TODO: ***technically*** this is only a view concern but keeping it here for now.
-#[derive(Component, Copy, Clone)]
-pub struct ElementExposure {
-    pub north: bool,
-    pub east: bool,
-    pub south: bool,
-    pub west: bool,
+#[derive(Component, Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub struct ElementExposure(u8);
+
+impl ElementExposure {
+    pub const NORTH: u8 = 1 << 0;
+    pub const NORTH_EAST: u8 = 1 << 1;
+    pub const EAST: u8 = 1 << 2;
+    pub const SOUTH_EAST: u8 = 1 << 3;
+    pub const SOUTH: u8 = 1 << 4;
+    pub const SOUTH_WEST: u8 = 1 << 5;
+    pub const WEST: u8 = 1 << 6;
+    pub const NORTH_WEST: u8 = 1 << 7;
+
+    pub const NONE: Self = Self(0);
+    pub const ALL: Self = Self(u8::MAX);
+
+    pub fn new(exposed: u8) -> Self {
+        Self(exposed)
+    }
+
+    /// Find which of the neighbors around `position` are exposed. Bits follow `EXPOSURE_NEIGHBORS`.
+    pub fn from_neighbors(position: Position, is_exposed: impl Fn(Position) -> bool) -> Self {
+        let exposed = EXPOSURE_NEIGHBORS
+            .iter()
+            .enumerate()
+            .filter(|&(_, &offset)| is_exposed(position + offset))
+            .fold(0, |exposed, (bit, _)| exposed | 1 << bit);
+
+        Self(exposed)
+    }
+
+    pub fn is_exposed(&self, neighbor: u8) -> bool {
+        self.0 & neighbor != 0
+    }
+
+    pub fn north(&self) -> bool {
+        self.is_exposed(Self::NORTH)
+    }
+
+    pub fn east(&self) -> bool {
+        self.is_exposed(Self::EAST)
+    }
+
+    pub fn south(&self) -> bool {
+        self.is_exposed(Self::SOUTH)
+    }
+
+    pub fn west(&self) -> bool {
+        self.is_exposed(Self::WEST)
+    }
+
+    /// Index into a 47 tile set, where each tile is a distinct combination of exposed sides and visible corners.
+    ///
+    /// ```
+    /// use simulation::nest_simulation::element::ElementExposure;
+    ///
+    /// assert_eq!(ElementExposure::ALL.get_blob_index(), 0);
+    /// assert_eq!(ElementExposure::NONE.get_blob_index(), 46);
+    ///
+    /// // A tunnel turning a corner leaves a notch which only a corner tile can show.
+    /// let notch = ElementExposure::new(ElementExposure::NORTH_EAST);
+    /// assert_eq!(notch.get_blob_index(), 45);
+    ///
+    /// // Corners between exposed sides are indistinguishable from covered ones.
+    /// let north = ElementExposure::new(ElementExposure::NORTH);
+    /// let north_and_corner = ElementExposure::new(ElementExposure::NORTH | ElementExposure::NORTH_EAST);
+    /// assert_eq!(north.get_blob_index(), north_and_corner.get_blob_index());
+    /// ```
+    pub fn get_blob_index(&self) -> usize {
+        BLOB_INDICES[get_blob_mask(self.0) as usize] as usize
+    }
 }
 
-/// Eagerly calculate which sides of a given Element are exposed to Air.
+/// Eagerly calculate which neighbors of a given Element are exposed to Air.
 /// Run against all elements changing position - this supports recalculating on Element removal by responding to Air being added.
+/// Corners are exposed, too, so elements diagonal to the change need recalculating alongside those beside it.
 pub fn update_element_exposure(
     changed_elements_query: Query<(Entity, &Position, &Element), Changed<Position>>,
     mut commands: Commands,
@@ -122,23 +406,25 @@ pub fn update_element_exposure(
             entities.insert((entity, *position));
         }
 
-        for adjacent_position in position.get_adjacent_positions() {
-            if let Some(adjacent_element_entity) = grid_elements.get_entity(adjacent_position) {
-                let adjacent_element = grid_elements.element(*adjacent_element_entity);
+        for offset in EXPOSURE_NEIGHBORS {
+            let neighbor_position = *position + offset;
+
+            if let Some(neighbor_element_entity) = grid_elements.get_entity(neighbor_position) {
+                let neighbor_element = grid_elements.element(*neighbor_element_entity);
 
-                if *adjacent_element != Element::Air {
-                    entities.insert((*adjacent_element_entity, adjacent_position));
+                if *neighbor_element != Element::Air {
+                    entities.insert((*neighbor_element_entity, neighbor_position));
                 }
             }
         }
     }
 
     for (entity, position) in entities {
-        commands.entity(entity).insert(ElementExposure {
-            north: grid_elements.is(position - Position::Y, Element::Air),
-            east: grid_elements.is(position + Position::X, Element::Air),
-            south: grid_elements.is(position + Position::Y, Element::Air),
-            west: grid_elements.is(position - Position::X, Element::Air),
-        });
+        commands
+            .entity(entity)
+            .insert(ElementExposure::from_neighbors(
+                position,
+                |neighbor_position| grid_elements.is(neighbor_position, Element::Air),
+            ));
     }
 }
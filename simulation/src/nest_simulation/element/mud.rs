@@ -0,0 +1,149 @@
+use super::{
+    commands::ElementCommandsExt,
+    water::{get_evaporation, Moisture, MAX_MOISTURE},
+    Dirt, Element, Mud, Water,
+};
+use crate::{
+    common::{grid::GridElements, position::Position},
+    nest_simulation::{
+        nest::{AtNest, Nest},
+        temperature::TemperatureField,
+    },
+    story_time::{DEFAULT_TICKS_PER_SECOND, SECONDS_PER_DAY},
+};
+use bevy::{prelude::*, utils::HashSet};
+use bevy_turborand::{DelegatedRng, GlobalRng};
+
+// Mud sucks at an ant's feet. Ants standing on it wait about twice as long between turns.
+pub const MUD_DELAY_TICKS: isize = 4;
+// Water pooled on saturated dirt churns it into mud, but far more slowly than dirt soaks water up.
+const MUD_FORM_CHANCE: f64 = 0.001;
+// Mud too deep to dry out in the air seeps into the dirt around it instead, over a couple of days.
+const MUD_SEEP_TICKS: isize = 2 * SECONDS_PER_DAY * DEFAULT_TICKS_PER_SECOND;
+
+/// How likely mud is to dry back into dirt each tick, given how far below the surface it lies and how warm it is.
+/// Mud near the surface dries out like moist dirt does. Deeper mud only dries by seeping away.
+///
+/// ```
+/// use simulation::nest_simulation::element::mud::get_mud_dry_chance;
+///
+/// // Shallow, warm, mud dries out faster than deep, or frozen, mud.
+/// assert!(get_mud_dry_chance(1, 25.0) > get_mud_dry_chance(20, 25.0));
+/// assert!(get_mud_dry_chance(1, 25.0) > get_mud_dry_chance(1, -5.0));
+///
+/// // Even deep mud dries out eventually.
+/// assert!(get_mud_dry_chance(20, 25.0) > 0.0);
+/// assert_eq!(get_mud_dry_chance(20, 25.0), get_mud_dry_chance(1, -5.0));
+/// ```
+pub fn get_mud_dry_chance(depth: isize, temperature: f32) -> f64 {
+    let evaporation_chance = get_evaporation(depth, temperature) / MAX_MOISTURE;
+    let seep_chance = 1.0 / MUD_SEEP_TICKS as f32;
+
+    (evaporation_chance + seep_chance) as f64
+}
+
+/// Whether the air at `position` is a pocket in the wall of the passage an ant at `ant_position` is in, e.g. a pit in the
+/// floor of a tunnel. Mud plastered into pockets leaves tunnel walls smooth. The far end of a tunnel isn't a pocket, so
+/// tunnels being dug aren't plastered shut.
+pub fn is_wall_pocket(
+    position: Position,
+    ant_position: Position,
+    grid_elements: &GridElements<AtNest>,
+) -> bool {
+    if !grid_elements.is(position, Element::Air) {
+        return false;
+    }
+
+    let is_enclosed = position
+        .get_adjacent_positions()
+        .into_iter()
+        .filter(|&adjacent_position| adjacent_position != ant_position)
+        .all(|adjacent_position| !grid_elements.is(adjacent_position, Element::Air));
+
+    // The passage runs across the pocket's mouth, rather than on into it.
+    let delta = position - ant_position;
+    let across = Position::new(delta.y, delta.x);
+    let is_beside_passage = grid_elements.is(ant_position + across, Element::Air)
+        || grid_elements.is(ant_position - across, Element::Air);
+
+    is_enclosed && is_beside_passage
+}
+
+/// Saturated dirt can't soak up any more water, but water left pooled against it churns it into mud. The water is
+/// taken up into the mud.
+pub fn dirt_turn_to_mud(
+    water_query: Query<(Entity, &Position), (With<Water>, With<AtNest>)>,
+    moisture_query: Query<&Moisture, (With<Dirt>, With<AtNest>)>,
+    grid_elements: GridElements<AtNest>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    // Dirt only turns to mud once. Any other water against it this tick stays put.
+    let mut muddied_entities = HashSet::new();
+
+    for (water_entity, water_position) in water_query.iter() {
+        if !rng.chance(MUD_FORM_CHANCE) {
+            continue;
+        }
+
+        let Some((dirt_position, dirt_entity)) = [
+            *water_position + Position::Y,
+            *water_position + Position::NEG_X,
+            *water_position + Position::X,
+        ]
+        .into_iter()
+        .filter(|&position| grid_elements.is(position, Element::Dirt))
+        .map(|position| (position, *grid_elements.entity(position)))
+        .find(|(_, dirt_entity)| {
+            !muddied_entities.contains(dirt_entity)
+                && moisture_query
+                    .get(*dirt_entity)
+                    .map_or(false, |moisture| moisture.is_saturated())
+        }) else {
+            continue;
+        };
+
+        muddied_entities.insert(dirt_entity);
+        commands.replace_element(dirt_position, Element::Mud, dirt_entity, AtNest);
+        commands.replace_element(*water_position, Element::Air, water_entity, AtNest);
+    }
+}
+
+/// Mud with no water against it slowly dries back into dirt, faster when it's near the surface and warm. Freshly dried
+/// dirt is still saturated and dries out the rest of the way like any other moist dirt.
+pub fn mud_dry(
+    mud_query: Query<(Entity, &Position), (With<Mud>, With<AtNest>)>,
+    nest_query: Query<(&Nest, &TemperatureField)>,
+    grid_elements: GridElements<AtNest>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    let (nest, temperature_field) = nest_query.single();
+
+    for (mud_entity, position) in mud_query.iter() {
+        let is_wet = position
+            .get_adjacent_positions()
+            .into_iter()
+            .any(|adjacent_position| grid_elements.is(adjacent_position, Element::Water));
+        if is_wet {
+            continue;
+        }
+
+        let dry_chance = get_mud_dry_chance(
+            position.y - nest.surface_level(),
+            temperature_field.temperature(*position),
+        );
+
+        if !rng.chance(dry_chance) {
+            continue;
+        }
+
+        commands.replace_element_with(
+            *position,
+            Element::Dirt,
+            mud_entity,
+            Moisture::saturated(),
+            AtNest,
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use super::{commands::ElementCommandsExt, Element, FoodKind, Prey};
+use crate::{
+    common::position::Position,
+    nest_simulation::nest::{AtNest, Nest},
+    save::ReflectPersist,
+    story_time::DEFAULT_TICKS_PER_SECOND,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// How long prey lies in the nest before it has been butchered into protein. 1 hour.
+const PREY_BUTCHER_TICKS: isize = 60 * 60 * DEFAULT_TICKS_PER_SECOND;
+
+/// Prey is too big to eat whole. Once it has been carried underground it's butchered into protein which a single ant can
+/// carry, share, and store like any other food. Prey lying on the surface is left whole.
+/// NOTE: Elements are respawned when they're picked up and dropped, so butchering starts over once prey is moved.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect)]
+#[reflect(Component, Persist)]
+pub struct Butchering {
+    remaining_ticks: isize,
+}
+
+impl Default for Butchering {
+    fn default() -> Self {
+        Self {
+            remaining_ticks: PREY_BUTCHER_TICKS,
+        }
+    }
+}
+
+impl Butchering {
+    pub fn remaining_ticks(&self) -> isize {
+        self.remaining_ticks
+    }
+}
+
+/// Start butchering prey as it's laid down underground.
+pub fn prey_add_butchering(
+    prey_query: Query<(Entity, &Position), (With<Prey>, With<AtNest>, Without<Butchering>)>,
+    nest_query: Query<&Nest>,
+    mut commands: Commands,
+) {
+    let nest = nest_query.single();
+
+    for (prey_entity, position) in prey_query.iter() {
+        if nest.is_underground(position) {
+            commands.entity(prey_entity).insert(Butchering::default());
+        }
+    }
+}
+
+pub fn prey_butcher(
+    mut prey_query: Query<(Entity, &Position, &mut Butchering), (With<Prey>, With<AtNest>)>,
+    mut commands: Commands,
+) {
+    for (prey_entity, position, mut butchering) in prey_query.iter_mut() {
+        butchering.remaining_ticks -= 1;
+
+        if butchering.remaining_ticks <= 0 {
+            commands.replace_element_with(
+                *position,
+                Element::Food,
+                prey_entity,
+                FoodKind::Protein,
+                AtNest,
+            );
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use super::{commands::ElementCommandsExt, Element, FoodKind, Seed};
+use crate::{
+    common::{grid::GridElements, position::Position},
+    nest_simulation::nest::{get_nest_entrance, AtNest, Nest},
+    save::ReflectPersist,
+    story_time::DEFAULT_TICKS_PER_SECOND,
+};
+use bevy::prelude::*;
+use bevy_turborand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// How long a seed must be kept in a granary before it has germinated into food. 12 hours.
+const SEED_GERMINATION_TICKS: isize = 60 * 60 * 12 * DEFAULT_TICKS_PER_SECOND;
+// Seeds handed over by foragers are left lying on the surface this close to the entrance.
+const SEED_DELIVERY_RADIUS: isize = 3;
+
+/// Seeds germinate while they're stored in a granary. Seeds lying anywhere else don't.
+/// NOTE: Elements are respawned when they're picked up and dropped, so germination starts over once a seed is moved.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect)]
+#[reflect(Component, Persist)]
+pub struct Germination {
+    remaining_ticks: isize,
+}
+
+impl Default for Germination {
+    fn default() -> Self {
+        Self {
+            remaining_ticks: SEED_GERMINATION_TICKS,
+        }
+    }
+}
+
+impl Germination {
+    pub fn remaining_ticks(&self) -> isize {
+        self.remaining_ticks
+    }
+}
+
+pub fn register_seed(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<Germination>();
+}
+
+/// Granaries are chambers, rather than tunnels, so there's room for ants to walk over the stored seeds.
+/// A position is part of a granary if it's underground, on the chamber's dirt floor, and has open space above it.
+/// Seeds are kept in a single layer, rather than piled up, so each one has room to germinate.
+pub fn is_granary_position(
+    position: Position,
+    nest: &Nest,
+    grid_elements: &GridElements<AtNest>,
+) -> bool {
+    nest.is_underground(&position)
+        && grid_elements.is(position + Position::Y, Element::Dirt)
+        && grid_elements.is_all(
+            &[
+                position + Position::NEG_Y,
+                position + Position::NEG_Y + Position::NEG_Y,
+            ],
+            Element::Air,
+        )
+}
+
+/// Foragers hand seeds over at the nest entrance. They're left on the surface beside it for nest workers to carry down.
+/// There's nowhere to leave them until there's an entrance.
+pub fn get_seed_delivery_position(
+    nest: &Nest,
+    grid_elements: &GridElements<AtNest>,
+    rng: &mut GlobalRng,
+) -> Option<Position> {
+    let entrance_position = get_nest_entrance(nest, grid_elements)?;
+    let x = entrance_position.x + rng.isize(-SEED_DELIVERY_RADIUS..=SEED_DELIVERY_RADIUS);
+
+    let ground_y = (0..=nest.surface_level() + 1)
+        .take_while(|&y| grid_elements.get_entity(Position::new(x, y)).is_some())
+        .find(|&y| !grid_elements.is(Position::new(x, y), Element::Air))?;
+
+    (ground_y > 0).then_some(Position::new(x, ground_y - 1))
+}
+
+/// Start germinating seeds once they've been laid down in a granary. Seeds which have fallen out of one start over.
+pub fn seeds_update_germination(
+    seeds_query: Query<(Entity, &Position, Option<&Germination>), (With<Seed>, With<AtNest>)>,
+    nest_query: Query<&Nest>,
+    grid_elements: GridElements<AtNest>,
+    mut commands: Commands,
+) {
+    let nest = nest_query.single();
+
+    for (seed_entity, position, germination) in seeds_query.iter() {
+        let is_stored = is_granary_position(*position, nest, &grid_elements);
+
+        if is_stored && germination.is_none() {
+            commands.entity(seed_entity).insert(Germination::default());
+        } else if !is_stored && germination.is_some() {
+            commands.entity(seed_entity).remove::<Germination>();
+        }
+    }
+}
+
+pub fn seeds_germinate(
+    mut seeds_query: Query<(Entity, &Position, &mut Germination), (With<Seed>, With<AtNest>)>,
+    mut commands: Commands,
+) {
+    for (seed_entity, position, mut germination) in seeds_query.iter_mut() {
+        germination.remaining_ticks -= 1;
+
+        if germination.remaining_ticks <= 0 {
+            commands.replace_element_with(
+                *position,
+                Element::Food,
+                seed_entity,
+                FoodKind::Plant,
+                AtNest,
+            );
+        }
+    }
+}
@@ -0,0 +1,279 @@
+use super::{commands::ElementCommandsExt, Element, Water};
+use crate::{
+    common::{
+        grid::{GridElements, GridElementsMut},
+        position::Position,
+    },
+    nest_simulation::{
+        nest::{AtNest, Nest},
+        temperature::TemperatureField,
+    },
+    save::ReflectPersist,
+    story_time::{DEFAULT_TICKS_PER_SECOND, SECONDS_PER_HOUR},
+};
+use bevy::{prelude::*, utils::HashMap};
+use bevy_turborand::{DelegatedRng, GlobalRng};
+use serde::{Deserialize, Serialize};
+
+// Dirt can only soak up so much water before it's saturated.
+pub const MAX_MOISTURE: f32 = 1.0;
+// How much wetter dirt becomes by soaking up a single tile of water.
+const WATER_MOISTURE: f32 = 0.25;
+// Dirt soaks up water slowly so puddles linger for a while before disappearing.
+const WATER_ABSORB_CHANCE: f64 = 0.01;
+// Moisture only evaporates from dirt near the surface. Deeper dirt stays damp until something drinks it.
+const EVAPORATION_DEPTH: isize = 12;
+// Saturated dirt right at the surface dries out over about six hours on a warm day.
+const EVAPORATION_TICKS: isize = 6 * SECONDS_PER_HOUR * DEFAULT_TICKS_PER_SECOND;
+// How warm a warm day is, in degrees Celsius. Dirt dries faster when it's warmer than this, and slower when cooler.
+const EVAPORATION_TEMPERATURE: f32 = 25.0;
+// Dirt this moist, or moister, crumbles right away when dug.
+const DAMP_MOISTURE: f32 = 0.5;
+
+/// Dirt which has soaked up water. Dirt without `Moisture` is dry.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Component, Persist)]
+pub struct Moisture {
+    value: f32,
+}
+
+impl Moisture {
+    /// Dirt which has soaked up all the water it can hold.
+    pub fn saturated() -> Self {
+        Self {
+            value: MAX_MOISTURE,
+        }
+    }
+
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    pub fn is_saturated(&self) -> bool {
+        self.value >= MAX_MOISTURE
+    }
+
+    /// Remove up to `amount` of moisture from the dirt. Returns how much was removed.
+    pub fn drain(&mut self, amount: f32) -> f32 {
+        let drained_amount = amount.min(self.value);
+        self.value -= drained_amount;
+        drained_amount
+    }
+}
+
+/// How much moisture dirt loses to evaporation each tick, given how far below the surface it lies and how warm it is.
+///
+/// ```
+/// use simulation::nest_simulation::element::water::get_evaporation;
+///
+/// // Shallow dirt dries out faster than deeper dirt, and warm dirt faster than cool dirt.
+/// assert!(get_evaporation(1, 25.0) > get_evaporation(6, 25.0));
+/// assert!(get_evaporation(1, 30.0) > get_evaporation(1, 15.0));
+///
+/// // Deep dirt, and frozen dirt, doesn't dry out at all.
+/// assert_eq!(get_evaporation(12, 25.0), 0.0);
+/// assert_eq!(get_evaporation(1, -5.0), 0.0);
+/// ```
+pub fn get_evaporation(depth: isize, temperature: f32) -> f32 {
+    if depth >= EVAPORATION_DEPTH || temperature <= 0.0 {
+        return 0.0;
+    }
+
+    let shallowness = 1.0 - depth.max(0) as f32 / EVAPORATION_DEPTH as f32;
+    let warmth = temperature / EVAPORATION_TEMPERATURE;
+
+    MAX_MOISTURE / EVAPORATION_TICKS as f32 * shallowness * warmth
+}
+
+/// How much of a turn's worth of digging an ant gets done at dirt, given how moist the dirt is. Dry dirt only gets
+/// `dry_dirt_rate` worth of digging each turn while damp dirt comes away at the full rate.
+///
+/// ```
+/// use simulation::nest_simulation::element::water::get_dirt_dig_rate;
+///
+/// assert_eq!(get_dirt_dig_rate(0.0, 0.5), 0.5);
+/// assert_eq!(get_dirt_dig_rate(0.25, 0.5), 0.75);
+/// assert_eq!(get_dirt_dig_rate(0.5, 0.5), 1.0);
+/// assert_eq!(get_dirt_dig_rate(1.0, 0.5), 1.0);
+/// ```
+pub fn get_dirt_dig_rate(moisture: f32, dry_dirt_rate: f32) -> f32 {
+    let dampness = (moisture / DAMP_MOISTURE).min(1.0);
+
+    dry_dirt_rate + (1.0 - dry_dirt_rate) * dampness
+}
+
+pub fn register_water(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<Moisture>();
+}
+
+// Water falls straight down when it can. Otherwise, it spreads sideways, but only when there's somewhere lower for it
+// to go or more water above pushing it outward. This lets puddles level out and then come to rest.
+fn get_water_flow_position(
+    position: Position,
+    grid_elements: &GridElements<AtNest>,
+    rng: &mut Mut<GlobalRng>,
+) -> Option<Position> {
+    let below_position = position + Position::Y;
+    if grid_elements.is(below_position, Element::Air) {
+        return Some(below_position);
+    }
+
+    let is_pressured = grid_elements.is(position - Position::Y, Element::Water);
+
+    let left_position = position + Position::NEG_X;
+    let mut go_left = grid_elements.is(left_position, Element::Air)
+        && (is_pressured || grid_elements.is(left_position + Position::Y, Element::Air));
+
+    let right_position = position + Position::X;
+    let mut go_right = grid_elements.is(right_position, Element::Air)
+        && (is_pressured || grid_elements.is(right_position + Position::Y, Element::Air));
+
+    // Flip a coin and choose a direction randomly to resolve ambiguity in flow direction.
+    if go_left && go_right {
+        go_left = rng.bool();
+        go_right = !go_left;
+    }
+
+    if go_left {
+        Some(left_position)
+    } else if go_right {
+        Some(right_position)
+    } else {
+        None
+    }
+}
+
+/// Move water one tile per tick by swapping it with the air it flows into.
+/// Water is moved bottom-up, and the grid is updated after each swap, so a column of water falls together
+/// and two tiles of water never flow into the same tile of air.
+pub fn water_flow(
+    mut element_position_queries: ParamSet<(
+        Query<&Position, (With<Water>, With<AtNest>)>,
+        Query<&mut Position, (With<Element>, With<AtNest>)>,
+    )>,
+    mut grid_elements_queries: ParamSet<(GridElements<AtNest>, GridElementsMut<AtNest>)>,
+    mut rng: ResMut<GlobalRng>,
+) {
+    let mut water_positions = element_position_queries
+        .p0()
+        .iter()
+        .copied()
+        .collect::<Vec<_>>();
+    water_positions.sort_by(|a, b| b.y.cmp(&a.y));
+
+    for water_position in water_positions {
+        let grid_elements = grid_elements_queries.p0();
+
+        let Some(air_position) =
+            get_water_flow_position(water_position, &grid_elements, &mut rng.reborrow())
+        else {
+            continue;
+        };
+
+        let (Some(&water_entity), Some(&air_entity)) = (
+            grid_elements.get_entity(water_position),
+            grid_elements.get_entity(air_position),
+        ) else {
+            continue;
+        };
+
+        let mut element_position_query = element_position_queries.p1();
+
+        let Ok([mut air_element_position, mut water_element_position]) =
+            element_position_query.get_many_mut([air_entity, water_entity])
+        else {
+            continue;
+        };
+
+        // Swap element positions internally.
+        *water_element_position = air_position;
+        *air_element_position = water_position;
+
+        // Update indices since they're indexed by position and track where elements are at.
+        grid_elements_queries.p1().set(air_position, water_entity);
+        grid_elements_queries.p1().set(water_position, air_entity);
+    }
+}
+
+/// Dirt beneath, or beside, water gradually soaks it up. The water disappears and the dirt becomes moist.
+/// Saturated dirt can't soak up any more so water pooled on top of it stays put.
+pub fn water_absorb(
+    water_query: Query<(Entity, &Position), (With<Water>, With<AtNest>)>,
+    mut moisture_query: Query<Option<&mut Moisture>, (With<Element>, With<AtNest>)>,
+    grid_elements: GridElements<AtNest>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    // Several tiles of water may soak into the same dirt this tick so total up how much each dirt receives.
+    let mut absorbed_moisture: HashMap<Entity, f32> = HashMap::new();
+
+    for (water_entity, water_position) in water_query.iter() {
+        if !rng.chance(WATER_ABSORB_CHANCE) {
+            continue;
+        }
+
+        let absorbing_dirt_entity = [
+            *water_position + Position::Y,
+            *water_position + Position::NEG_X,
+            *water_position + Position::X,
+        ]
+        .into_iter()
+        .filter(|&position| grid_elements.is(position, Element::Dirt))
+        .filter_map(|position| grid_elements.get_entity(position).copied())
+        .find(|dirt_entity| {
+            let moisture = moisture_query
+                .get(*dirt_entity)
+                .ok()
+                .flatten()
+                .map_or(0.0, |moisture| moisture.value);
+            let pending_moisture = absorbed_moisture.get(dirt_entity).copied().unwrap_or(0.0);
+
+            moisture + pending_moisture < MAX_MOISTURE
+        });
+
+        if let Some(dirt_entity) = absorbing_dirt_entity {
+            *absorbed_moisture.entry(dirt_entity).or_insert(0.0) += WATER_MOISTURE;
+            commands.replace_element(*water_position, Element::Air, water_entity, AtNest);
+        }
+    }
+
+    for (dirt_entity, moisture_amount) in absorbed_moisture {
+        match moisture_query.get_mut(dirt_entity) {
+            Ok(Some(mut moisture)) => {
+                moisture.value = (moisture.value + moisture_amount).min(MAX_MOISTURE);
+            }
+            Ok(None) => {
+                commands.entity(dirt_entity).insert(Moisture {
+                    value: moisture_amount.min(MAX_MOISTURE),
+                });
+            }
+            Err(_) => {}
+        }
+    }
+}
+
+/// Moist dirt near the surface slowly dries out, faster when it's warm. Dirt which dries out completely is dry again.
+pub fn moisture_evaporate(
+    mut moisture_query: Query<(Entity, &Position, &mut Moisture), With<AtNest>>,
+    nest_query: Query<(&Nest, &TemperatureField)>,
+    mut commands: Commands,
+) {
+    let (nest, temperature_field) = nest_query.single();
+
+    for (dirt_entity, position, mut moisture) in moisture_query.iter_mut() {
+        let evaporation = get_evaporation(
+            position.y - nest.surface_level(),
+            temperature_field.temperature(*position),
+        );
+
+        if evaporation == 0.0 {
+            continue;
+        }
+
+        moisture.drain(evaporation);
+
+        if moisture.value <= 0.0 {
+            commands.entity(dirt_entity).remove::<Moisture>();
+        }
+    }
+}
@@ -1,12 +1,17 @@
 use super::nest::AtNest;
 use crate::{
+    balance_config::BalanceConfig,
     common::{
         grid::{Grid, GridElements, GridElementsMut},
         position::Position,
     },
     nest_simulation::{
-        ant::{AntOrientation, Dead, Initiative},
-        element::{commands::ElementCommandsExt, Air, Element},
+        ant::{nuptial_flight::Winged, AntOrientation, Dead, Initiative},
+        element::{
+            commands::ElementCommandsExt,
+            definition::{ElementDefinitions, ElementGravity},
+            Air, Element,
+        },
         nest::Nest,
     },
     settings::Settings,
@@ -23,6 +28,7 @@ pub struct Unstable;
 // Don't infer Stable implicitly from lack of Unstable for performance.
 // It's important for a System to be able to apply Unstable to newly added Elements without
 // iterating all Element each run in an attempt to do so.
+// NOTE: Stability isn't persisted. It's derived from the grid and reassigned by `gravity_set_stability` after loading.
 #[derive(Component, Reflect, Default)]
 #[reflect(Component)]
 pub struct Stable;
@@ -36,44 +42,93 @@ pub fn register_gravity(app_type_registry: ResMut<AppTypeRegistry>) {
 // TODO: Add tests for ant gravity
 // TODO: It would be nice to be able to assert an entire map using shorthand like element_grid
 
-// Search for a valid position for an element to fall into by searching to the
-// bottom left/center/right of a given position. Prioritize falling straight down
-// and do not fall if surrounded by non-air
-fn get_element_fall_position(
+/// How many cells across a grain can slide, to reach somewhere lower, for a pile to settle at the given angle of repose.
+/// Piles can't be any steeper than one cell of rise for every cell across so the angle is only approximate.
+///
+/// ```
+/// use simulation::nest_simulation::gravity::get_repose_run;
+///
+/// assert_eq!(get_repose_run(45.0), 1);
+/// assert_eq!(get_repose_run(30.0), 2);
+/// assert_eq!(get_repose_run(20.0), 3);
+/// // Steeper piles than 45 degrees aren't possible on the grid.
+/// assert_eq!(get_repose_run(60.0), 1);
+/// ```
+pub fn get_repose_run(repose_angle: f32) -> isize {
+    ((1.0 / repose_angle.to_radians().tan()).round() as isize).max(1)
+}
+
+// How far, across open air, there is to go in the given direction before there's somewhere lower to drop into.
+fn get_slide_distance(
+    position: Position,
+    direction: isize,
+    is_air: &impl Fn(Position) -> bool,
+    repose_run: isize,
+) -> Option<isize> {
+    (1..=repose_run)
+        .take_while(|&distance| is_air(position + Position::new(direction * distance, 0)))
+        .find(|&distance| is_air(position + Position::new(direction * distance, 1)))
+}
+
+/// Where an element which falls, like sand, moves next. Falling straight down is preferred. Otherwise, elements slide
+/// toward any drop within `repose_run` cells to either side, so piles spread out until they're no steeper than their
+/// angle of repose. Elements slide one cell at a time so a large pile cascades down over several ticks.
+///
+/// ```
+/// use bevy_turborand::GlobalRng;
+/// use simulation::{common::position::Position, nest_simulation::gravity::get_fall_position};
+/// use std::collections::HashSet;
+///
+/// let mut rng = GlobalRng::with_seed(42);
+/// // A floor at y = 3 with a single grain of sand resting on it.
+/// let sand_positions = HashSet::from([Position::new(0, 2)]);
+/// let is_air = |position: Position| position.y < 3 && !sand_positions.contains(&position);
+///
+/// // Falls straight down when there's nothing beneath it.
+/// assert_eq!(get_fall_position(Position::new(0, 0), is_air, 1, 1.0, &mut rng), Some(Position::new(0, 1)));
+/// // Tips off the top of a steep pile.
+/// assert!(get_fall_position(Position::new(0, 1), is_air, 1, 1.0, &mut rng).is_some());
+/// // Rests on the floor.
+/// assert_eq!(get_fall_position(Position::new(0, 2), is_air, 2, 1.0, &mut rng), None);
+/// ```
+pub fn get_fall_position(
     position: Position,
-    grid_elements: &GridElements<AtNest>,
-    rng: &mut Mut<GlobalRng>,
+    is_air: impl Fn(Position) -> bool,
+    repose_run: isize,
+    slide_chance: f64,
+    rng: &mut GlobalRng,
 ) -> Option<Position> {
     // If there is air below then continue falling down.
     let below_position = position + Position::Y;
-    if grid_elements.is(below_position, Element::Air) {
+    if is_air(below_position) {
         return Some(below_position);
     }
 
-    // Otherwise, likely at rest, but potential for tipping off a precarious ledge.
-    // Look for a column of air two units tall to either side and consider going in one of those directions.
-    let left_position = position + Position::NEG_X;
-    let left_below_position = position + Position::new(-1, 1);
-    let mut go_left = grid_elements.is_all(&[left_position, left_below_position], Element::Air)
-        && rng.chance(0.66);
-
-    let right_position = position + Position::X;
-    let right_below_position = position + Position::ONE;
-    let mut go_right = grid_elements.is_all(&[right_position, right_below_position], Element::Air)
-        && rng.chance(0.66);
+    // Otherwise, likely at rest, but potential for tipping off a precarious ledge, or sliding down a steep slope.
+    let left_distance =
+        get_slide_distance(position, -1, &is_air, repose_run).filter(|_| rng.chance(slide_chance));
+    let right_distance =
+        get_slide_distance(position, 1, &is_air, repose_run).filter(|_| rng.chance(slide_chance));
 
     // Flip a coin and choose a direction randomly to resolve ambiguity in fall direction.
-    if go_left && go_right {
-        go_left = rng.bool();
-        go_right = !go_left;
-    }
-
-    if go_left {
-        Some(left_below_position)
-    } else if go_right {
-        Some(right_below_position)
+    let (direction, distance) = match (left_distance, right_distance) {
+        (Some(left_distance), Some(right_distance)) => {
+            if rng.bool() {
+                (-1, left_distance)
+            } else {
+                (1, right_distance)
+            }
+        }
+        (Some(left_distance), None) => (-1, left_distance),
+        (None, Some(right_distance)) => (1, right_distance),
+        (None, None) => return None,
+    };
+
+    // Tip off the ledge once it's right beside. Until then, slide toward it.
+    if distance == 1 {
+        Some(position + Position::new(direction, 1))
     } else {
-        None
+        Some(position + Position::new(direction, 0))
     }
 }
 
@@ -84,18 +139,29 @@ pub fn gravity_elements(
     )>,
     mut rng: ResMut<GlobalRng>,
     mut grid_elements_queries: ParamSet<(GridElements<AtNest>, GridElementsMut<AtNest>)>,
+    balance_config: Res<BalanceConfig>,
+    settings: Res<Settings>,
 ) {
+    let repose_run = get_repose_run(settings.sand_repose_angle);
+    let grid_elements = grid_elements_queries.p0();
+
     let element_air_swaps: Vec<_> = element_position_queries
         .p0()
         .iter()
         .filter_map(|&position| {
-            get_element_fall_position(position, &grid_elements_queries.p0(), &mut rng.reborrow())
-                .and_then(|air_position| {
-                    Some((
-                        *grid_elements_queries.p0().get_entity(position)?,
-                        *grid_elements_queries.p0().get_entity(air_position)?,
-                    ))
-                })
+            get_fall_position(
+                position,
+                |position| grid_elements.is(position, Element::Air),
+                repose_run,
+                balance_config.sand_slide_chance,
+                &mut rng,
+            )
+            .and_then(|air_position| {
+                Some((
+                    *grid_elements.get_entity(position)?,
+                    *grid_elements.get_entity(air_position)?,
+                ))
+            })
         })
         .collect();
 
@@ -131,7 +197,8 @@ pub fn gravity_ants(
             Option<&mut Initiative>,
             Option<&Dead>,
         ),
-        With<AtNest>,
+        // Queens on the wing are held aloft.
+        (With<AtNest>, Without<Winged>),
     >,
     nest_query: Query<(&Grid, &Nest)>,
     grid_elements: GridElements<AtNest>,
@@ -194,41 +261,32 @@ pub fn gravity_mark_unstable(
     mut commands: Commands,
     nest_query: Query<&Nest>,
     grid_elements: GridElements<AtNest>,
+    settings: Res<Settings>,
 ) {
     let mut positions = HashSet::new();
+    // Anything which could slide down into the gap, from as far away as a pile's slope allows, needs to be woken up.
+    let repose_run = get_repose_run(settings.sand_repose_angle);
 
     for &position in air_query.iter() {
-        positions.insert(position + Position::new(-1, -1));
-        positions.insert(position + Position::new(0, -1));
-        positions.insert(position + Position::new(1, -1));
+        for x in -repose_run..=repose_run {
+            positions.insert(position + Position::new(x, -1));
+        }
     }
 
     let nest = nest_query.single();
 
     for &position in &positions {
-        // If the current position contains a sand or food element, mark it as unstable
+        // If the current position contains an element which falls, mark it as unstable
         if let Some(entity) = grid_elements.get_entity(position) {
             if let Some(element) = grid_elements.get_element(*entity) {
-                if matches!(*element, Element::Sand | Element::Food) {
-                    commands.toggle_element_command(
-                        *entity,
-                        position,
-                        true,
-                        Unstable,
-                        PhantomData::<AtNest>,
-                    );
-
-                    commands.toggle_element_command(
-                        *entity,
-                        position,
-                        false,
-                        Stable,
-                        PhantomData::<AtNest>,
-                    );
-                }
-
-                // Special Case - dirt aboveground doesn't have "background" supporting dirt to keep it stable - so it falls.
-                if *element == Element::Dirt && nest.is_aboveground(&position) {
+                let is_falling = match grid_elements.definition(*element).gravity() {
+                    ElementGravity::Falls => true,
+                    // Dirt aboveground doesn't have "background" supporting dirt to keep it stable - so it falls.
+                    ElementGravity::FallsAboveground => nest.is_aboveground(&position),
+                    ElementGravity::None => false,
+                };
+
+                if is_falling {
                     commands.toggle_element_command(
                         *entity,
                         position,
@@ -281,31 +339,25 @@ pub fn gravity_set_stability(
         (Without<Unstable>, Without<Stable>, With<AtNest>),
     >,
     nest_query: Query<&Nest>,
+    element_definitions: Res<ElementDefinitions>,
     mut commands: Commands,
 ) {
     let nest = nest_query.single();
 
     // TODO: Consider for_each for perf
     for (entity, element, position) in element_query.iter() {
-        match element {
-            Element::Air => {
-                commands.entity(entity).insert(Stable);
-            }
-            Element::Dirt => {
-                // Dirt that spawns below surface level is not unstable but dirt that is above is unstable.
-                if nest.is_underground(position) {
-                    commands.entity(entity).insert(Stable);
-                } else {
-                    commands.entity(entity).insert(Unstable);
-                }
-            }
-            // Any sand or food that has just appeared starts off unstable. They'll be marked Stable later.
-            Element::Sand => {
-                commands.entity(entity).insert(Unstable);
-            }
-            Element::Food => {
-                commands.entity(entity).insert(Unstable);
-            }
+        let is_stable = match element_definitions.get(*element).gravity() {
+            ElementGravity::None => true,
+            // Dirt that spawns below surface level is not unstable but dirt that is above is unstable.
+            ElementGravity::FallsAboveground => nest.is_underground(position),
+            // Anything that falls, and has just appeared, starts off unstable. They'll be marked Stable later.
+            ElementGravity::Falls => false,
+        };
+
+        if is_stable {
+            commands.entity(entity).insert(Stable);
+        } else {
+            commands.entity(entity).insert(Unstable);
         }
     }
 }
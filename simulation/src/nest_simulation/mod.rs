@@ -1,27 +1,82 @@
 pub mod ant;
+pub mod cave_in;
+pub mod colony_planner;
+pub mod colony_status;
+pub mod critical_event;
 pub mod element;
 pub mod gravity;
 pub mod nest;
 pub mod pheromone;
+pub mod population;
+pub mod raid;
+pub mod task_allocation;
+pub mod temperature;
+pub mod weather;
 
 use self::{
     ant::{
+        age::{ants_add_age, ants_age, register_age},
         ants_initiative,
         birthing::{ants_birthing, register_birthing},
+        brood::{
+            ants_feed_larvae, brood_egg_tick, brood_larva_tick, brood_pupa_tick, register_brood,
+            Brood,
+        },
+        carry::{
+            ants_join_carry_group, carry_groups_disband, carry_groups_hold, carry_groups_keep_pace,
+            carry_groups_lift,
+        },
         chambering::{
             ants_add_chamber_pheromone, ants_chamber_pheromone_act, ants_fade_chamber_pheromone,
             ants_remove_chamber_pheromone,
         },
-        death::on_ants_add_dead,
+        collapse::{ants_collapsed_tick, ants_revive_collapsed},
+        curiosity::{
+            ants_investigate_cursor, initialize_curiosity_resources, player_cursor_tick,
+            remove_curiosity_resources,
+        },
+        death::{ants_become_corpses, on_ants_add_dead},
         dig::ants_dig,
+        dig_order::ants_excavate,
         digestion::ants_digestion,
         drop::ants_drop,
-        hunger::{ants_hunger_act, ants_hunger_tick, ants_regurgitate},
+        drowning::{ants_drown, register_drowning},
+        energy::{ants_add_energy, ants_recharge_energy},
+        flood_sealing::{ants_plan_flood_seals, ants_seal_floods, flood_seals_cancel},
+        food_delivery::{ants_deliver_food, ants_plan_food_deliveries, food_deliveries_cancel},
+        food_trail::{
+            ants_add_foraging_traits, ants_deposit_food_pheromone, ants_follow_food_pheromone,
+            food_pheromone_tick,
+        },
+        grooming::{ants_groom, ants_groomed_tick},
+        history::{
+            ants_history_born, ants_history_collapsed, ants_history_died,
+            ants_history_first_foraged, ants_history_near_starvation, ants_history_revived,
+            register_ant_history,
+        },
+        hunger::{
+            ants_add_solicitation, ants_hunger_act, ants_hunger_tick, ants_regurgitate,
+            ants_solicit,
+        },
+        infection::{ants_catch_infection, ants_infection_tick, ants_recover_from_infection},
+        known_resources::{ants_add_known_resources, ants_remember_food},
         nest_expansion::ants_nest_expansion,
         nesting::ants_nesting_start,
         nesting::{ants_nesting_action, ants_nesting_movement, register_nesting},
+        nuptial_flight::{ants_fly, ants_seal_chamber},
+        order::{ants_order_action, ants_order_movement, ants_order_tick, register_order},
+        pathing::{ants_find_path, ants_follow_path, register_pathing},
+        recruitment::{ants_recruit, tandem_runs_disband},
         register_ant,
         sleep::{ants_sleep, ants_wake},
+        soldier::{register_soldier, soldiers_attack},
+        thermoregulation::{
+            ants_move_brood, ants_plan_brood_moves, brood_transports_cancel,
+            carried_brood_keep_pace,
+        },
+        thirst::{
+            ants_add_thirst, ants_share_water, ants_thirst_act, ants_thirst_tick, register_thirst,
+        },
         tunneling::{
             ants_add_tunnel_pheromone, ants_fade_tunnel_pheromone, ants_remove_tunnel_pheromone,
             ants_tunnel_pheromone_act, ants_tunnel_pheromone_move,
@@ -29,22 +84,60 @@ use self::{
         walk::{ants_stabilize_footing_movement, ants_walk},
         Ant, AntAteFoodEvent,
     },
-    element::{register_element, update_element_exposure, Element},
+    cave_in::cave_in_unsupported_spans,
+    colony_planner::{
+        initialize_colony_planner_resources, plan_colony_chambers, register_colony_planner,
+        remove_colony_planner_resources,
+    },
+    colony_status::{
+        initialize_colony_status_resources, register_colony_status, remove_colony_status_resources,
+        update_colony_status,
+    },
+    critical_event::{brood_notify_flooded, queen_notify_starving, CriticalEvent},
+    element::{
+        corpse::{corpses_add_decay, corpses_decay, register_corpse},
+        fungus::{fungus_grow, fungus_wither},
+        hardness::register_hardness,
+        mud::{dirt_turn_to_mud, mud_dry},
+        prey::{prey_add_butchering, prey_butcher},
+        register_element,
+        seed::{register_seed, seeds_germinate, seeds_update_germination},
+        update_element_exposure,
+        water::{moisture_evaporate, register_water, water_absorb, water_flow},
+        Element,
+    },
     gravity::{
         gravity_ants, gravity_elements, gravity_mark_stable, gravity_mark_unstable,
         gravity_set_stability, register_gravity,
     },
     nest::{
-        insert_nest_grid, register_nest, spawn_nest, spawn_nest_ants, spawn_nest_elements, Nest,
+        insert_nest_grid, register_nest, spawn_nest, spawn_nest_ants, spawn_nest_elements, AtNest,
+        Nest,
     },
     pheromone::{
         initialize_pheromone_resources, pheromone_duration_tick, register_pheromone,
         remove_pheromone_resources, Pheromone,
     },
+    population::{
+        initialize_population_resources, remove_population_resources, update_carrying_capacity,
+    },
+    raid::{
+        ants_repel_raiders, initialize_raid_resources, raiders_arrive, raiders_find_path,
+        raiders_raid, register_raid, remove_raid_resources, schedule_night_raids,
+    },
+    task_allocation::{
+        ants_allocate_tasks, initialize_task_allocation_resources, register_task_allocation,
+        remove_task_allocation_resources,
+    },
+    temperature::update_temperature_field,
+    weather::{
+        initialize_weather_resources, rain_fall, register_weather, remove_weather_resources,
+        update_weather, update_weather_intensity,
+    },
 };
 use super::{
-    despawn_model, settings::initialize_settings_resources, AppState, CleanupSet, FinishSetupSet,
-    SimulationTickSet, SimulationUpdate, StoryPlaybackState,
+    despawn_model, settings::initialize_settings_resources, story_time::is_zone_playing, AppState,
+    CleanupSet, FinishSetupSet, SimulationTickSet, SimulationUpdate, StoryPlaybackState,
 };
 use bevy::prelude::*;
 
@@ -53,17 +146,36 @@ pub struct NestSimulationPlugin;
 impl Plugin for NestSimulationPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<AntAteFoodEvent>();
+        app.add_event::<CriticalEvent>();
 
         app.add_systems(
             OnEnter(AppState::BeginSetup),
             (
                 register_nesting,
+                register_task_allocation,
+                register_order,
+                register_pathing,
+                register_ant_history,
+                register_age,
                 register_birthing,
+                register_drowning,
+                register_thirst,
+                register_soldier,
+                register_brood,
                 register_element,
+                (register_water, register_hardness),
+                register_corpse,
+                register_seed,
                 register_gravity,
                 register_ant,
                 register_pheromone,
                 register_nest,
+                (
+                    register_colony_planner,
+                    register_raid,
+                    register_colony_status,
+                ),
+                register_weather,
             ),
         );
 
@@ -87,6 +199,13 @@ impl Plugin for NestSimulationPlugin {
                 apply_deferred,
                 (
                     initialize_pheromone_resources,
+                    initialize_colony_planner_resources,
+                    initialize_colony_status_resources,
+                    initialize_population_resources,
+                    initialize_raid_resources,
+                    initialize_task_allocation_resources,
+                    initialize_curiosity_resources,
+                    initialize_weather_resources,
                     // IMPORTANT:
                     // `ElementExposure` isn't persisted because it's derivable. It is required for rendering.
                     // Don't rely on `SimulationUpdate` to set `ElementExposure` because it should be possible to render
@@ -98,45 +217,209 @@ impl Plugin for NestSimulationPlugin {
                 .in_set(FinishSetupSet::SimulationFinishSetup),
         );
 
+        app.add_systems(
+            OnEnter(AppState::Cleanup),
+            (
+                despawn_model::<Ant>,
+                despawn_model::<Brood>,
+                despawn_model::<Element>,
+                despawn_model::<Pheromone>,
+                despawn_model::<Nest>,
+                remove_pheromone_resources,
+                remove_colony_planner_resources,
+                remove_colony_status_resources,
+                remove_population_resources,
+                remove_raid_resources,
+                remove_task_allocation_resources,
+                remove_curiosity_resources,
+                remove_weather_resources,
+            )
+                .in_set(CleanupSet::SimulationCleanup),
+        );
+    }
+}
+
+pub struct NestSimulationTickPlugin;
+
+impl Plugin for NestSimulationTickPlugin {
+    fn build(&self, app: &mut App) {
         app.add_systems(
             SimulationUpdate,
             (
                 // TODO: Consider whether gravity is special enough to warrant being placed in PreSimulationTick
                 (
+                    // The temperature field is built on the first tick, so it's there for everything which follows.
+                    update_temperature_field,
                     gravity_set_stability,
                     apply_deferred,
                     // It's helpful to apply gravity first because position updates are applied instantly and are seen by subsequent systems.
                     // Thus, ant actions can take into consideration where an element is this frame rather than where it was last frame.
                     gravity_elements,
+                    water_flow,
                     gravity_ants,
                     // Gravity side-effects can run whenever with little difference.
                     gravity_mark_stable,
                     gravity_mark_unstable,
+                    water_absorb,
+                    moisture_evaporate,
+                    dirt_turn_to_mud,
+                    mud_dry,
+                    // Ceilings which cave in are replaced with sand which begins falling next tick.
+                    cave_in_unsupported_spans,
+                    apply_deferred,
+                )
+                    .chain(),
+                // Ants lay, and raise, brood according to how many ants the colony can support as of the start of the tick.
+                update_carrying_capacity,
+                // Raiders arrive before anyone acts so the colony has the whole tick to notice them.
+                (schedule_night_raids, raiders_arrive, apply_deferred).chain(),
+                // Rain falls at the start of the tick so it's flowing, like any other water, from the next tick on.
+                (
+                    update_weather,
+                    update_weather_intensity,
+                    rain_fall,
                     apply_deferred,
                 )
                     .chain(),
                 (
                     // Apply specific ant actions in priority order because ants take a maximum of one action per tick.
                     // An ant should not starve to hunger due to continually choosing to dig a tunnel, etc.
-                    ants_stabilize_footing_movement,
+                    // Winged queens spend their turns flying so they're kept from clinging to the air beneath them.
+                    (ants_fly, ants_stabilize_footing_movement).chain(),
+                    (
+                        ants_add_age,
+                        ants_add_energy,
+                        ants_add_solicitation,
+                        apply_deferred,
+                        ants_age,
+                        ants_recharge_energy::<AtNest>,
+                        apply_deferred,
+                    )
+                        .chain(),
                     // TODO: I'm just aggressively applying deferred until something like https://github.com/bevyengine/bevy/pull/9822 lands
                     (
                         ants_digestion,
                         ants_hunger_tick,
                         ants_hunger_act,
                         apply_deferred,
+                        ants_solicit,
                         ants_regurgitate,
                         apply_deferred,
+                        // Food is sent to a hungry queen, rather than waiting on chance encounters, once those are done.
+                        (
+                            food_deliveries_cancel,
+                            apply_deferred,
+                            ants_plan_food_deliveries,
+                            apply_deferred,
+                            ants_deliver_food,
+                            apply_deferred,
+                        )
+                            .chain(),
+                        ants_revive_collapsed,
+                        ants_collapsed_tick,
+                        ants_drown,
+                        apply_deferred,
+                        (
+                            ants_infection_tick,
+                            ants_catch_infection,
+                            ants_groomed_tick,
+                            ants_groom,
+                            ants_recover_from_infection,
+                            apply_deferred,
+                        )
+                            .chain(),
+                    )
+                        .chain(),
+                    (
+                        ants_add_thirst,
+                        apply_deferred,
+                        ants_thirst_tick,
+                        ants_thirst_act,
+                        ants_share_water,
+                        apply_deferred,
+                        // Workers drop what they're doing to plug tunnels which water is pouring into.
+                        (
+                            flood_seals_cancel,
+                            apply_deferred,
+                            ants_plan_flood_seals,
+                            apply_deferred,
+                            ants_seal_floods,
+                            apply_deferred,
+                        )
+                            .chain(),
+                    )
+                        .chain(),
+                    // Caring for brood takes priority over laying more of it.
+                    (
+                        // A queen who has died is replaced before her brood is cared for.
+                        update_colony_status,
+                        apply_deferred,
+                        ants_feed_larvae,
+                        ants_birthing,
+                        brood_egg_tick,
+                        brood_larva_tick,
+                        brood_pupa_tick,
+                        apply_deferred,
+                        // Brood is kept comfortable once it's been fed and has finished developing for the tick.
+                        (
+                            brood_transports_cancel,
+                            apply_deferred,
+                            ants_plan_brood_moves,
+                            apply_deferred,
+                            ants_move_brood,
+                            apply_deferred,
+                        )
+                            .chain(),
+                        ants_allocate_tasks,
+                        apply_deferred,
                     )
                         .chain(),
-                    (ants_birthing, apply_deferred).chain(),
                     (ants_sleep, ants_wake, apply_deferred).chain(),
+                    // Ants with somewhere to be follow a route there, rather than wandering, when one can be found.
+                    // Soldiers fight off intruders they've reached before chasing down any others.
+                    // Ants carrying something heavy together stick with their group rather than heading off alone.
+                    (
+                        (
+                            carry_groups_disband,
+                            apply_deferred,
+                            carry_groups_lift,
+                            apply_deferred,
+                            carry_groups_hold,
+                            ants_join_carry_group,
+                            apply_deferred,
+                        )
+                            .chain(),
+                        soldiers_attack,
+                        // Workers fight back against raiders they run into, and raiders against whoever's in their way.
+                        ants_repel_raiders,
+                        raiders_raid,
+                        apply_deferred,
+                        // Scouts back from the crater gather their recruits before anyone decides where to go.
+                        (tandem_runs_disband, ants_recruit, apply_deferred).chain(),
+                        ants_find_path,
+                        raiders_find_path,
+                        apply_deferred,
+                        ants_follow_path,
+                    )
+                        .chain(),
+                    // Orders from the user take priority over the ant's own plans, but not over staying alive.
+                    // Orders given to a particular ant take priority over dirt marked for any ant to dig.
+                    (
+                        ants_order_tick,
+                        ants_order_movement,
+                        ants_order_action,
+                        ants_excavate,
+                        apply_deferred,
+                    )
+                        .chain(),
                     (
                         // Apply Nesting Logic
                         ants_nesting_start,
                         ants_nesting_movement,
                         ants_nesting_action,
                         apply_deferred,
+                        ants_seal_chamber,
+                        apply_deferred,
                     )
                         .chain(),
                     (ants_nest_expansion, apply_deferred).chain(),
@@ -171,10 +454,25 @@ impl Plugin for NestSimulationPlugin {
                         apply_deferred,
                     )
                         .chain(),
+                    // Food Pheromone:
+                    (
+                        ants_add_foraging_traits,
+                        ants_add_known_resources,
+                        apply_deferred,
+                        food_pheromone_tick,
+                        ants_remember_food,
+                        ants_deposit_food_pheromone,
+                        ants_follow_food_pheromone,
+                        // Food comes before curiosity. Ants on a trail don't stop to see what the player is up to.
+                        ants_investigate_cursor,
+                    )
+                        .chain(),
                     // Ants move before acting because positions update instantly, but actions use commands to mutate the world and are deferred + batched.
                     // By applying movement first, commands do not need to anticipate ants having moved, but the opposite would not be true.
                     (
                         ants_walk,
+                        carry_groups_keep_pace,
+                        carried_brood_keep_pace,
                         ants_dig,
                         apply_deferred,
                         ants_drop,
@@ -182,26 +480,54 @@ impl Plugin for NestSimulationPlugin {
                     )
                         .chain(),
                     on_ants_add_dead,
+                    // Record history once everything that could happen to an ant this tick has happened.
+                    (
+                        ants_history_born,
+                        apply_deferred,
+                        ants_history_first_foraged,
+                        ants_history_near_starvation,
+                        ants_history_collapsed,
+                        ants_history_revived,
+                        ants_history_died,
+                    )
+                        .chain(),
+                    // Dead ants are only cleared away once their deaths have been recorded.
+                    (
+                        ants_become_corpses,
+                        corpses_add_decay,
+                        corpses_decay,
+                        apply_deferred,
+                    )
+                        .chain(),
+                    (queen_notify_starving, brood_notify_flooded).chain(),
                     // Reset initiative only after all actions have occurred to ensure initiative properly throttles actions-per-tick.
-                    ants_initiative,
+                    ants_initiative::<AtNest>,
+                )
+                    .chain(),
+                // Seeds, fungus, and prey are checked once ants have finished moving things around for the tick.
+                (
+                    seeds_update_germination,
+                    apply_deferred,
+                    seeds_germinate,
+                    apply_deferred,
+                    fungus_grow,
+                    apply_deferred,
+                    fungus_wither,
+                    apply_deferred,
+                    prey_add_butchering,
+                    prey_butcher,
+                    apply_deferred,
                 )
                     .chain(),
+                // Chambers are planned around the nest as it stands once the tick's digging is done.
+                (plan_colony_chambers, apply_deferred).chain(),
+                player_cursor_tick,
             )
-                .run_if(not(in_state(StoryPlaybackState::Paused)))
+                .run_if(
+                    not(in_state(StoryPlaybackState::Paused)).and_then(is_zone_playing::<AtNest>),
+                )
                 .chain()
                 .in_set(SimulationTickSet::SimulationTick),
         );
-
-        app.add_systems(
-            OnEnter(AppState::Cleanup),
-            (
-                despawn_model::<Ant>,
-                despawn_model::<Element>,
-                despawn_model::<Pheromone>,
-                despawn_model::<Nest>,
-                remove_pheromone_resources,
-            )
-                .in_set(CleanupSet::SimulationCleanup),
-        );
     }
 }
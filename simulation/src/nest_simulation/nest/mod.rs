@@ -1,26 +1,48 @@
 use crate::{
-    common::{grid::Grid, position::Position, Zone},
+    balance_config::BalanceConfig,
+    common::{
+        grid::{pheromone_layer::PheromoneLayer, Grid, GridElements},
+        pathfinding::is_walkable,
+        position::Position,
+        Zone,
+    },
+    content_pack::ContentPacks,
     nest_simulation::{
         ant::{
-            digestion::Digestion, hunger::Hunger, Angle, AntBundle, AntColor, AntInventory,
-            AntName, AntOrientation, AntRole, Facing, Initiative,
+            age::{get_max_lifespan_ticks, Age},
+            digestion::Digestion,
+            food_trail::FoodPheromoneLayer,
+            hunger::Hunger,
+            nuptial_flight::{get_flight_altitude, Winged},
+            Angle, AntBundle, AntColor, AntInventory, AntName, AntOrientation, AntRole, Facing,
+            Initiative,
         },
         element::{Element, ElementBundle},
     },
+    save::ReflectPersist,
     settings::Settings,
 };
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashSet};
 use bevy_turborand::{DelegatedRng, GlobalRng};
 use serde::{Deserialize, Serialize};
 
+// Roughly one vein of stone runs through every this many cells of dirt.
+const STONE_VEIN_DIRT_CELLS: isize = 1_500;
+const STONE_VEIN_MIN_LENGTH: isize = 8;
+const STONE_VEIN_MAX_LENGTH: isize = 24;
+// Veins start well below the surface so there's always room to dig out a founding chamber.
+const STONE_VEIN_MIN_DEPTH: isize = 10;
+// Veins mostly run level, like strata, but drift up or down a step every few cells.
+const STONE_VEIN_DRIFT_CHANCE: f64 = 0.3;
+
 #[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
-#[reflect(Component)]
+#[reflect(Component, Persist)]
 pub struct AtNest;
 
 impl Zone for AtNest {}
 
 #[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
-#[reflect(Component)]
+#[reflect(Component, Persist)]
 pub struct Nest {
     surface_level: isize,
 }
@@ -43,6 +65,16 @@ impl Nest {
     }
 }
 
+/// The entrance is where a tunnel breaks through the surface. Before anything has been dug there's no entrance to guard.
+pub fn get_nest_entrance(nest: &Nest, grid_elements: &GridElements<AtNest>) -> Option<Position> {
+    let entrance_level = nest.surface_level() + 1;
+
+    (0..)
+        .map(|x| Position::new(x, entrance_level))
+        .take_while(|position| grid_elements.get_entity(*position).is_some())
+        .find(|position| is_walkable(*position, grid_elements))
+}
+
 pub fn register_nest(app_type_registry: ResMut<AppTypeRegistry>) {
     app_type_registry.write().register::<Nest>();
     app_type_registry.write().register::<AtNest>();
@@ -66,9 +98,11 @@ pub fn spawn_nest(settings: Res<Settings>, mut commands: Commands) {
 pub fn spawn_nest_elements(
     nest_query: Query<&Nest>,
     settings: Res<Settings>,
+    mut rng: ResMut<GlobalRng>,
     mut commands: Commands,
 ) {
     let nest = nest_query.single();
+    let stone_positions = get_stone_positions(nest, &settings, &mut rng);
 
     for y in 0..settings.nest_height {
         for x in 0..settings.nest_width {
@@ -76,6 +110,8 @@ pub fn spawn_nest_elements(
 
             if y <= nest.surface_level {
                 commands.spawn(ElementBundle::new(Element::Air, position, AtNest));
+            } else if stone_positions.contains(&position) {
+                commands.spawn(ElementBundle::new(Element::Stone, position, AtNest));
             } else {
                 commands.spawn(ElementBundle::new(Element::Dirt, position, AtNest));
             }
@@ -83,30 +119,126 @@ pub fn spawn_nest_elements(
     }
 }
 
+/// Where veins of stone run through the dirt of a new nest. Veins start at random, deep enough underground, and wander
+/// off from there. Cells outside the nest are dropped so veins running off the edge are simply cut short.
+fn get_stone_positions(nest: &Nest, settings: &Settings, rng: &mut GlobalRng) -> HashSet<Position> {
+    let min_y = nest.surface_level() + STONE_VEIN_MIN_DEPTH;
+    if min_y >= settings.nest_height {
+        return HashSet::default();
+    }
+
+    let dirt_cells = settings.nest_width * (settings.nest_height - nest.surface_level() - 1);
+    let vein_count = dirt_cells / STONE_VEIN_DIRT_CELLS;
+
+    (0..vein_count)
+        .flat_map(|_| {
+            let start = Position::new(
+                rng.isize(0..settings.nest_width),
+                rng.isize(min_y..settings.nest_height),
+            );
+            let length = rng.isize(STONE_VEIN_MIN_LENGTH..=STONE_VEIN_MAX_LENGTH);
+
+            get_stone_vein(start, length, rng)
+        })
+        .filter(|position| {
+            (0..settings.nest_width).contains(&position.x)
+                && (min_y..settings.nest_height).contains(&position.y)
+        })
+        .collect()
+}
+
+/// A single vein of stone, `length` cells long, starting at `start`. Veins are two cells thick and run sideways,
+/// drifting up and down as they go, so they read as seams in the rock rather than as blobs.
+///
+/// ```
+/// use bevy_turborand::prelude::*;
+/// use simulation::{common::position::Position, nest_simulation::nest::get_stone_vein};
+///
+/// let mut rng = GlobalRng::with_seed(42);
+/// let vein = get_stone_vein(Position::new(20, 40), 10, &mut rng);
+///
+/// assert_eq!(vein.len(), 20);
+/// assert!(vein.contains(&Position::new(20, 40)));
+/// // Each step runs one cell further sideways, in the same direction, so the vein is unbroken.
+/// assert!(vein
+///     .chunks(2)
+///     .zip(vein.chunks(2).skip(1))
+///     .all(|(a, b)| (a[0].x - b[0].x).abs() == 1 && (a[0].y - b[0].y).abs() <= 1));
+/// ```
+pub fn get_stone_vein(start: Position, length: isize, rng: &mut GlobalRng) -> Vec<Position> {
+    let step_x = if rng.bool() { 1 } else { -1 };
+    let mut position = start;
+
+    (0..length)
+        .flat_map(|_| {
+            let cells = [position, position + Position::Y];
+
+            let drift = if rng.chance(STONE_VEIN_DRIFT_CHANCE) {
+                if rng.bool() {
+                    1
+                } else {
+                    -1
+                }
+            } else {
+                0
+            };
+            position = position + Position::new(step_x, drift);
+
+            cells
+        })
+        .collect()
+}
+
 pub fn spawn_nest_ants(
     nest_query: Query<&Nest>,
     settings: Res<Settings>,
+    balance_config: Res<BalanceConfig>,
+    content_packs: Res<ContentPacks>,
     mut rng: ResMut<GlobalRng>,
     mut commands: Commands,
 ) {
     let nest = nest_query.single();
     let mut rng = rng.reborrow();
 
+    // A realistic start has the queen land somewhere, after her nuptial flight, and found the colony alone.
+    // She's still in flight, above the center, if the player is to choose where she lands.
+    // Otherwise, she spawns in the center alongside the initial workers.
+    let queen_position = if settings.is_nuptial_flight {
+        Position::new(settings.nest_width / 2, get_flight_altitude(nest))
+    } else if settings.is_realistic_start {
+        Position::new(rng.isize(0..settings.nest_width), 0)
+    } else {
+        Position::new(settings.nest_width / 2, nest.surface_level)
+    };
+
     let queen_ant_bundle = AntBundle::new(
-        // Queen always spawns in the center. She'll fall from the sky in the future.
-        Position::new(settings.nest_width / 2, nest.surface_level),
+        queen_position,
         AntColor(settings.ant_color),
         AntOrientation::new(Facing::random(&mut rng), Angle::Zero),
-        AntInventory::default(),
+        AntInventory::new(AntRole::Queen),
         AntRole::Queen,
         AntName(String::from("Queen")),
         Initiative::new(&mut rng),
         AtNest,
-        Hunger::new(settings.max_hunger_time),
+        Hunger::new(
+            settings.max_hunger_time,
+            balance_config.appetite.get(AntRole::Queen),
+        ),
         Digestion::new(settings.max_digestion_time),
     );
 
-    commands.spawn(queen_ant_bundle);
+    let queen_entity = commands.spawn(queen_ant_bundle).id();
+
+    if settings.is_nuptial_flight {
+        commands.entity(queen_entity).insert(Winged::default());
+    }
+
+    if settings.is_realistic_start {
+        return;
+    }
+
+    // Initial workers are of all ages, short of old age, so there are nurses, diggers, and foragers from the start.
+    let max_initial_age_ticks = get_max_lifespan_ticks(&AntRole::Worker, &settings) * 2 / 3;
 
     let worker_ant_bundles = (0..settings.initial_ant_worker_count)
         .map(|_| {
@@ -114,18 +246,23 @@ pub fn spawn_nest_ants(
             let random_surface_position =
                 Position::new(rng.isize(0..settings.nest_width), nest.surface_level);
 
-            AntBundle::new(
+            let ant_bundle = AntBundle::new(
                 random_surface_position,
                 AntColor(settings.ant_color),
                 AntOrientation::new(Facing::random(&mut rng), Angle::Zero),
-                AntInventory::default(),
+                AntInventory::new(AntRole::Worker),
                 AntRole::Worker,
-                AntName::random(&mut rng),
+                AntName::random(&mut rng, &content_packs),
                 Initiative::new(&mut rng),
                 AtNest,
-                Hunger::new(settings.max_hunger_time),
+                Hunger::new(
+                    settings.max_hunger_time,
+                    balance_config.appetite.get(AntRole::Worker),
+                ),
                 Digestion::new(settings.max_digestion_time),
-            )
+            );
+
+            (ant_bundle, Age::new(rng.isize(0..max_initial_age_ticks)))
         })
         .collect::<Vec<_>>();
 
@@ -152,9 +289,11 @@ pub fn insert_nest_grid(
         elements_cache[position.y as usize][position.x as usize] = entity;
     }
 
-    commands.entity(nest_query.single()).insert(Grid::new(
-        settings.nest_width,
-        settings.nest_height,
-        elements_cache,
+    commands.entity(nest_query.single()).insert((
+        Grid::new(settings.nest_width, settings.nest_height, elements_cache),
+        FoodPheromoneLayer(PheromoneLayer::new(
+            settings.nest_width,
+            settings.nest_height,
+        )),
     ));
 }
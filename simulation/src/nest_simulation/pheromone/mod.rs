@@ -4,13 +4,14 @@ use self::commands::PheromoneCommandsExt;
 use crate::{
     common::position::Position,
     nest_simulation::nest::AtNest,
+    save::ReflectPersist,
     story_time::{DEFAULT_TICKS_PER_SECOND, SECONDS_PER_HOUR},
 };
 use bevy::{prelude::*, utils::HashMap};
 use serde::{Deserialize, Serialize};
 
 #[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
-#[reflect(Component)]
+#[reflect(Component, Persist)]
 pub enum Pheromone {
     #[default]
     Tunnel,
@@ -18,7 +19,7 @@ pub enum Pheromone {
 }
 
 #[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
-#[reflect(Component)]
+#[reflect(Component, Persist)]
 pub struct PheromoneStrength {
     value: isize,
     max: isize,
@@ -43,7 +44,7 @@ impl PheromoneStrength {
 }
 
 #[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect)]
-#[reflect(Component)]
+#[reflect(Component, Persist)]
 pub struct PheromoneDuration {
     value: f32,
     max: f32,
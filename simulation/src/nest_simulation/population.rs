@@ -0,0 +1,115 @@
+use super::{
+    ant::{brood::Brood, Ant, Dead},
+    element::{Air, Food},
+    nest::{AtNest, Nest},
+};
+use crate::{
+    balance_config::BalanceConfig,
+    common::{colony::ColonyId, position::Position},
+    story_time::DEFAULT_TICKS_PER_SECOND,
+};
+use bevy::prelude::*;
+
+// The nest is dug out, and food stored away, gradually so there's no need to take stock every tick. 10 seconds.
+const CARRYING_CAPACITY_UPDATE_TICKS: isize = 10 * DEFAULT_TICKS_PER_SECOND;
+
+/// How many ants the colony's nest, and its food stores, can support. Nothing stops a colony from growing past its
+/// capacity, but the queen lays fewer eggs as the colony nears it, so population levels off rather than growing forever.
+/// NOTE: Capacity isn't persisted. It's cheap enough to take stock again after loading.
+#[derive(Resource, Debug, PartialEq, Copy, Clone, Default)]
+pub struct CarryingCapacity {
+    capacity: usize,
+    population: usize,
+    ticks_until_update: isize,
+}
+
+impl CarryingCapacity {
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Brood count toward the population, alongside adults, because they'll be adults soon enough.
+    pub fn population(&self) -> usize {
+        self.population
+    }
+
+    pub fn fertility(&self) -> f32 {
+        get_fertility(self.population, self.capacity)
+    }
+}
+
+/// How readily the queen lays eggs, as a multiple of her usual rate. She lays freely while the colony is small, for
+/// its nest and food stores, less often as it nears capacity, and not at all once it's reached capacity.
+///
+/// ```
+/// use simulation::nest_simulation::population::get_fertility;
+///
+/// assert_eq!(get_fertility(0, 100), 1.0);
+/// assert!(get_fertility(50, 100) > get_fertility(90, 100));
+/// assert_eq!(get_fertility(100, 100), 0.0);
+/// assert_eq!(get_fertility(150, 100), 0.0);
+/// ```
+pub fn get_fertility(population: usize, capacity: usize) -> f32 {
+    if capacity == 0 {
+        return 0.0;
+    }
+
+    let crowding = population as f32 / capacity as f32;
+
+    (1.0 - crowding * crowding).max(0.0)
+}
+
+pub fn initialize_population_resources(mut commands: Commands) {
+    commands.init_resource::<CarryingCapacity>();
+}
+
+pub fn remove_population_resources(mut commands: Commands) {
+    commands.remove_resource::<CarryingCapacity>();
+}
+
+/// Take stock of how much room there is underground, and how much food has been stored there, to work out how many
+/// ants the colony can support. A nest is only as good as its weakest point. A huge, empty nest supports few ants and
+/// so does a cramped nest overflowing with food. Newly founded colonies are supported regardless so the queen can raise
+/// her first brood.
+pub fn update_carrying_capacity(
+    ants_query: Query<&ColonyId, (With<Ant>, Without<Dead>)>,
+    brood_query: Query<(), (With<Brood>, With<AtNest>)>,
+    air_query: Query<&Position, (With<Air>, With<AtNest>)>,
+    food_query: Query<&Position, (With<Food>, With<AtNest>)>,
+    nest_query: Query<&Nest>,
+    balance_config: Res<BalanceConfig>,
+    mut carrying_capacity: ResMut<CarryingCapacity>,
+) {
+    if carrying_capacity.ticks_until_update > 0 {
+        carrying_capacity.ticks_until_update -= 1;
+        return;
+    }
+
+    carrying_capacity.ticks_until_update = CARRYING_CAPACITY_UPDATE_TICKS;
+
+    let nest = nest_query.single();
+    let population_balance = &balance_config.population;
+
+    let open_tiles = air_query
+        .iter()
+        .filter(|position| nest.is_underground(position))
+        .count();
+
+    let stored_food = food_query
+        .iter()
+        .filter(|position| nest.is_underground(position))
+        .count();
+
+    let space_capacity = (open_tiles as f32 / population_balance.tiles_per_ant) as usize;
+    let food_capacity = (stored_food as f32 / population_balance.food_per_ant) as usize;
+
+    carrying_capacity.capacity = space_capacity
+        .min(food_capacity)
+        .max(population_balance.founding_capacity);
+
+    carrying_capacity.population = ants_query
+        .iter()
+        .filter(|colony_id| colony_id.is_player())
+        .count()
+        + brood_query.iter().count();
+}
@@ -0,0 +1,382 @@
+use super::{
+    ant::{
+        brood::Brood, commands::AntCommandsExt, digestion::Digestion, hunger::Hunger,
+        pathing::Path, soldier::Intruder, Angle, AntBundle, AntColor, AntInventory, AntName,
+        AntOrientation, AntRole, Dead, Facing, Initiative,
+    },
+    critical_event::CriticalEvent,
+    element::{commands::ElementCommandsExt, Element, Food},
+    nest::{get_nest_entrance, AtNest, Nest},
+};
+use crate::{
+    balance_config::BalanceConfig,
+    common::{
+        colony::ColonyId, grid::GridElements, pathfinding::find_path, position::Position,
+        reservation::ElementReservations,
+    },
+    content_pack::ContentPacks,
+    save::ReflectPersist,
+    settings::Settings,
+    story_time::{StoryTime, DEFAULT_TICKS_PER_SECOND, SECONDS_PER_HOUR},
+};
+use bevy::prelude::*;
+use bevy_turborand::{DelegatedRng, GlobalRng};
+use serde::{Deserialize, Serialize};
+
+// Raids arrive some time in the first few hours after nightfall rather than the moment it falls.
+const RAID_WINDOW_TICKS: isize = 3 * SECONDS_PER_HOUR * DEFAULT_TICKS_PER_SECOND;
+// A handful of raiders is enough to threaten a young colony without overwhelming an established one.
+const MIN_RAIDERS: isize = 2;
+const MAX_RAIDERS: isize = 4;
+// How much food, or brood, each raider makes off with before heading home.
+const RAIDER_MAX_LOOT: usize = 3;
+// Workers aren't built for fighting. They only drive off a raider now and then, where a soldier never misses.
+const WORKER_REPEL_CHANCE: f64 = 0.25;
+// Raiders bite whoever gets in their way, but they're here for food rather than a fight.
+const RAIDER_BITE_CHANCE: f64 = 0.1;
+// Raiders are darker than the colony's own ants so they stand out in the tunnels.
+const RAIDER_COLOR: Color = Color::rgb(0.25, 0.05, 0.05);
+
+/// When the next raid is due. Every night there's a chance that ants from a rival colony raid the nest, through its
+/// entrance, for stored food and brood.
+#[derive(Resource, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Resource, Persist)]
+pub struct RaidSchedule {
+    // The night, counted by the day on which it fell, last considered for a raid. Each night is only considered once.
+    last_night: Option<isize>,
+    // When tonight's raid reaches the nest, if one is coming.
+    raid_tick: Option<isize>,
+}
+
+/// An intruder raiding the nest. Raiders make for the nearest stored food, or brood, and carry off what they can before
+/// heading back out through the entrance. Anyone still inside at dawn leaves empty-handed.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Component, Persist)]
+pub struct Raider {
+    loot: usize,
+}
+
+impl Raider {
+    pub fn is_leaving(&self, story_time: &StoryTime) -> bool {
+        self.loot >= RAIDER_MAX_LOOT || !story_time.is_nighttime()
+    }
+}
+
+pub fn register_raid(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<RaidSchedule>();
+    app_type_registry.write().register::<Raider>();
+    app_type_registry.write().register::<Option<isize>>();
+}
+
+pub fn initialize_raid_resources(mut commands: Commands) {
+    commands.init_resource::<RaidSchedule>();
+}
+
+pub fn remove_raid_resources(mut commands: Commands) {
+    commands.remove_resource::<RaidSchedule>();
+}
+
+/// Nights run past midnight so the hours before dawn count toward the night which fell the day before.
+fn get_night(story_time: &StoryTime) -> isize {
+    let time_info = story_time.as_time_info();
+
+    if time_info.hours() < 12 {
+        time_info.days() - 1
+    } else {
+        time_info.days()
+    }
+}
+
+/// Decide, once per night, whether the nest is raided and, if so, when.
+pub fn schedule_night_raids(
+    mut raid_schedule: ResMut<RaidSchedule>,
+    story_time: Res<StoryTime>,
+    settings: Res<Settings>,
+    mut rng: ResMut<GlobalRng>,
+) {
+    if !settings.is_night_raids_enabled || !story_time.is_nighttime() {
+        return;
+    }
+
+    let night = get_night(&story_time);
+    if raid_schedule.last_night == Some(night) {
+        return;
+    }
+
+    raid_schedule.last_night = Some(night);
+
+    if rng.chance(settings.probabilities.night_raid.into()) {
+        raid_schedule.raid_tick =
+            Some(story_time.elapsed_ticks() + rng.isize(0..RAID_WINDOW_TICKS));
+    }
+}
+
+/// Raiders arrive at the nest entrance when their raid is due. Raids which are due after dawn, or after raids have been
+/// turned off, are called off, as are raids on nests without an entrance to get in through.
+pub fn raiders_arrive(
+    mut raid_schedule: ResMut<RaidSchedule>,
+    story_time: Res<StoryTime>,
+    settings: Res<Settings>,
+    balance_config: Res<BalanceConfig>,
+    content_packs: Res<ContentPacks>,
+    nest_query: Query<&Nest>,
+    grid_elements: GridElements<AtNest>,
+    mut rng: ResMut<GlobalRng>,
+    mut critical_events: EventWriter<CriticalEvent>,
+    mut commands: Commands,
+) {
+    let Some(raid_tick) = raid_schedule.raid_tick else {
+        return;
+    };
+
+    if story_time.elapsed_ticks() < raid_tick {
+        return;
+    }
+
+    raid_schedule.raid_tick = None;
+
+    if !settings.is_night_raids_enabled || !story_time.is_nighttime() {
+        return;
+    }
+
+    let Some(entrance_position) = get_nest_entrance(nest_query.single(), &grid_elements) else {
+        return;
+    };
+
+    let mut rng = rng.reborrow();
+    let raider_count = rng.isize(MIN_RAIDERS..=MAX_RAIDERS);
+
+    // Raiders are soldiers of a rival colony so they're left out of the colony's chores.
+    let role = AntRole::Soldier;
+
+    for _ in 0..raider_count {
+        let ant_bundle = AntBundle::new(
+            entrance_position,
+            AntColor(RAIDER_COLOR),
+            AntOrientation::new(Facing::random(&mut rng), Angle::Zero),
+            AntInventory::new(role),
+            role,
+            AntName::random(&mut rng, &content_packs),
+            Initiative::new(&mut rng),
+            AtNest,
+            Hunger::new(settings.max_hunger_time, balance_config.appetite.get(role)),
+            Digestion::new(settings.max_digestion_time),
+        );
+
+        commands.spawn((ant_bundle, ColonyId::RIVAL, Intruder, Raider::default()));
+    }
+
+    critical_events.send(CriticalEvent::NestRaided);
+}
+
+/// Raiders make for the nearest stored food, or brood, and head back to the entrance once they've had their fill.
+/// They aren't part of the colony so they find their own way rather than being given somewhere to be.
+pub fn raiders_find_path(
+    raiders_query: Query<
+        (Entity, &Position, &Raider, Option<&Path>),
+        (With<AtNest>, With<Initiative>, Without<Dead>),
+    >,
+    food_query: Query<&Position, (With<Food>, With<AtNest>)>,
+    brood_query: Query<&Position, (With<Brood>, With<AtNest>)>,
+    nest_query: Query<&Nest>,
+    grid_elements: GridElements<AtNest>,
+    story_time: Res<StoryTime>,
+    mut commands: Commands,
+) {
+    if raiders_query.is_empty() {
+        return;
+    }
+
+    let nest = nest_query.single();
+    let entrance_position = get_nest_entrance(nest, &grid_elements);
+
+    let loot_positions = food_query
+        .iter()
+        .filter(|position| nest.is_underground(position))
+        .chain(brood_query.iter())
+        .copied()
+        .collect::<Vec<_>>();
+
+    for (raider_entity, position, raider, path) in raiders_query.iter() {
+        let goal = if raider.is_leaving(&story_time) {
+            entrance_position
+        } else {
+            loot_positions
+                .iter()
+                .min_by_key(|loot_position| position.distance(loot_position))
+                .copied()
+        };
+
+        let Some(goal) = goal else {
+            continue;
+        };
+
+        if path.map_or(false, |path| path.goal() == goal) {
+            continue;
+        }
+
+        let positions = find_path(*position, goal, &grid_elements).unwrap_or_default();
+
+        commands
+            .entity(raider_entity)
+            .insert(Path::new(goal, positions));
+    }
+}
+
+/// Raiders bite nestmates who get in their way and otherwise carry off food, and brood, they reach. Raiders who've had
+/// their fill, or are caught out by the dawn, leave through the entrance.
+/// NOTE: Loot is gone for good. Raiders eat it on the spot rather than carrying it home.
+pub fn raiders_raid(
+    mut raiders_query: Query<
+        (
+            Entity,
+            &Position,
+            &AntOrientation,
+            &mut Initiative,
+            &mut Raider,
+        ),
+        (With<AtNest>, Without<Dead>),
+    >,
+    defenders_query: Query<
+        (Entity, &Position, &AntRole),
+        (With<AtNest>, Without<Intruder>, Without<Dead>),
+    >,
+    brood_query: Query<(Entity, &Position), (With<Brood>, With<AtNest>)>,
+    nest_query: Query<&Nest>,
+    grid_elements: GridElements<AtNest>,
+    story_time: Res<StoryTime>,
+    mut element_reservations: ResMut<ElementReservations>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    if raiders_query.is_empty() {
+        return;
+    }
+
+    let entrance_position = get_nest_entrance(nest_query.single(), &grid_elements);
+
+    let mut bitten_entities = vec![];
+    let mut looted_entities = vec![];
+
+    for (raider_entity, position, orientation, mut initiative, mut raider) in
+        raiders_query.iter_mut()
+    {
+        if !initiative.can_act() {
+            continue;
+        }
+
+        let is_leaving = raider.is_leaving(&story_time);
+
+        if is_leaving
+            && entrance_position.map_or(false, |entrance_position| {
+                position.distance(&entrance_position) <= 1
+            })
+        {
+            commands.despawn_ant(raider_entity, AtNest);
+            continue;
+        }
+
+        let ahead_position = orientation.get_ahead_position(position);
+        let is_within_reach = |target_position: &Position| {
+            *target_position == *position || *target_position == ahead_position
+        };
+
+        // The queen is guarded too closely for raiders to reach.
+        let defender_entity = defenders_query
+            .iter()
+            .find(|(defender_entity, defender_position, role)| {
+                **role != AntRole::Queen
+                    && !bitten_entities.contains(defender_entity)
+                    && is_within_reach(*defender_position)
+            })
+            .map(|(defender_entity, ..)| defender_entity);
+
+        if let Some(defender_entity) = defender_entity {
+            if rng.chance(RAIDER_BITE_CHANCE) {
+                commands.kill_ant(defender_entity);
+
+                bitten_entities.push(defender_entity);
+            }
+
+            initiative.consume();
+            continue;
+        }
+
+        if is_leaving {
+            continue;
+        }
+
+        let brood_entity = brood_query
+            .iter()
+            .find(|(brood_entity, brood_position)| {
+                !looted_entities.contains(brood_entity) && is_within_reach(*brood_position)
+            })
+            .map(|(brood_entity, _)| brood_entity);
+
+        if let Some(brood_entity) = brood_entity {
+            commands.entity(brood_entity).despawn();
+
+            looted_entities.push(brood_entity);
+            raider.loot += 1;
+            initiative.consume();
+            continue;
+        }
+
+        if grid_elements.is_food(ahead_position) {
+            let food_entity = *grid_elements.entity(ahead_position);
+            if !element_reservations.try_reserve(food_entity, raider_entity) {
+                continue;
+            }
+
+            commands.replace_element(ahead_position, Element::Air, food_entity, AtNest);
+
+            raider.loot += 1;
+            initiative.consume();
+        }
+    }
+}
+
+/// Workers fight back against raiders they run into. They only sometimes drive one off, unlike soldiers, who kill any
+/// intruder they reach. See `soldiers_attack`.
+pub fn ants_repel_raiders(
+    mut defenders_query: Query<
+        (&mut Initiative, &Position, &AntOrientation, &AntRole),
+        (With<AtNest>, Without<Intruder>, Without<Dead>),
+    >,
+    raiders_query: Query<(Entity, &Position), (With<Raider>, With<AtNest>, Without<Dead>)>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    if raiders_query.is_empty() {
+        return;
+    }
+
+    let mut repelled_raider_entities = vec![];
+
+    for (mut initiative, position, orientation, role) in defenders_query.iter_mut() {
+        if *role != AntRole::Worker || !initiative.can_act() {
+            continue;
+        }
+
+        let ahead_position = orientation.get_ahead_position(position);
+
+        let target_raider = raiders_query
+            .iter()
+            .find(|(raider_entity, raider_position)| {
+                !repelled_raider_entities.contains(raider_entity)
+                    && (**raider_position == *position || **raider_position == ahead_position)
+            });
+
+        let Some((raider_entity, _)) = target_raider else {
+            continue;
+        };
+
+        if rng.chance(WORKER_REPEL_CHANCE) {
+            commands.kill_ant(raider_entity);
+
+            repelled_raider_entities.push(raider_entity);
+        }
+
+        initiative.consume();
+    }
+}
@@ -0,0 +1,417 @@
+use crate::{
+    balance_config::BalanceConfig,
+    common::{
+        grid::{Grid, GridElements},
+        pathfinding::is_walkable,
+        position::Position,
+    },
+    nest_simulation::{
+        ant::{
+            age::{get_life_stage, get_max_lifespan_ticks, Age, LifeStage},
+            brood::{Brood, Larva},
+            dig_order::DigOrder,
+            hunger::Hunger,
+            midden::{get_midden_position, get_refuse_position, is_at_midden, is_at_refuse},
+            order::Order,
+            AntInventory, AntRole, Dead, Initiative,
+        },
+        colony_planner::ColonyPlanner,
+        element::{Corpse, Food, Waste},
+        nest::{AtNest, Nest},
+    },
+    save::ReflectPersist,
+    settings::Settings,
+    story_time::DEFAULT_TICKS_PER_SECOND,
+};
+use bevy::prelude::*;
+use bevy_turborand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Workers are only reshuffled every so often so they aren't pulled off a task the moment demand for it dips.
+const ALLOCATION_INTERVAL_TICKS: isize = 5 * DEFAULT_TICKS_PER_SECOND;
+
+/// The work the colony has set an idle worker to. Workers without a task wander until they're needed.
+/// Workers give up their task when they become hungry, when they're given an order, or when the work runs out.
+#[derive(
+    Component, Debug, PartialEq, Eq, Copy, Clone, Serialize, Deserialize, Reflect, Default,
+)]
+#[reflect(Component, Persist)]
+pub enum WorkerTask {
+    /// Fetch food lying on the surface.
+    #[default]
+    Foraging,
+    /// Feed hungry larvae, and a hungry queen.
+    Nursing,
+    /// Dig out dirt marked for digging.
+    Digging,
+    /// Carry stray corpses out to the midden, and stray waste to the refuse chamber.
+    WasteManagement,
+}
+
+impl WorkerTask {
+    pub fn all() -> [WorkerTask; 4] {
+        [
+            WorkerTask::Foraging,
+            WorkerTask::Nursing,
+            WorkerTask::Digging,
+            WorkerTask::WasteManagement,
+        ]
+    }
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+
+    /// The stage of life at which workers are best suited to the task. Workers of other ages only fill in when there
+    /// aren't enough of the right age.
+    pub fn get_preferred_life_stage(&self) -> Option<LifeStage> {
+        match self {
+            WorkerTask::Foraging => Some(LifeStage::Old),
+            WorkerTask::Nursing => Some(LifeStage::Young),
+            WorkerTask::Digging => Some(LifeStage::MiddleAged),
+            WorkerTask::WasteManagement => None,
+        }
+    }
+}
+
+/// How much work there is of one kind and how many workers are on it. Demand is counted in units of work, e.g. one
+/// growing larva or one tile of marked dirt, rather than in workers.
+#[derive(Debug, PartialEq, Copy, Clone, Default)]
+pub struct TaskStaffing {
+    pub demand: usize,
+    pub assigned: usize,
+}
+
+/// The colony's view of what needs doing, and who is doing it, as of the latest tick. Also keeps track of where the
+/// work is so workers know where to go.
+/// NOTE: Not persisted. Everything here is taken stock of again on the first tick after loading.
+#[derive(Resource, Debug, PartialEq, Clone, Default)]
+pub struct TaskAllocation {
+    staffing: [TaskStaffing; 4],
+    idle_count: usize,
+    dig_sites: Vec<Position>,
+    forage_sites: Vec<Position>,
+    refuse_sites: Vec<Position>,
+    ticks_until_allocation: isize,
+}
+
+impl TaskAllocation {
+    pub fn get_staffing(&self, task: WorkerTask) -> TaskStaffing {
+        self.staffing[task.index()]
+    }
+
+    /// Workers who are free to work but haven't been given a task.
+    pub fn idle_count(&self) -> usize {
+        self.idle_count
+    }
+
+    pub fn dig_sites(&self) -> &[Position] {
+        &self.dig_sites
+    }
+
+    pub fn forage_sites(&self) -> &[Position] {
+        &self.forage_sites
+    }
+
+    pub fn refuse_sites(&self) -> &[Position] {
+        &self.refuse_sites
+    }
+}
+
+pub fn register_task_allocation(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<WorkerTask>();
+}
+
+pub fn initialize_task_allocation_resources(mut commands: Commands) {
+    commands.init_resource::<TaskAllocation>();
+}
+
+pub fn remove_task_allocation_resources(mut commands: Commands) {
+    commands.remove_resource::<TaskAllocation>();
+}
+
+/// Where ants can stand to work on the elements at the given positions, e.g. to dig out marked dirt or to pick up a
+/// corpse. Elements which are buried, with nowhere beside them to stand, can't be reached until the dirt around them
+/// has been dug out first.
+pub fn get_work_sites(
+    positions: impl Iterator<Item = Position>,
+    grid_elements: &GridElements<AtNest>,
+) -> Vec<Position> {
+    let mut work_sites = positions
+        .flat_map(|position| position.get_adjacent_positions())
+        .filter(|&position| is_walkable(position, grid_elements))
+        .collect::<Vec<_>>();
+
+    // Sorted so ants pick between equally distant sites consistently.
+    work_sites.sort_by_key(|position| (position.y, position.x));
+    work_sites.dedup();
+
+    work_sites
+}
+
+/// Ants stick with the site they're walking to, so they don't dither between sites as they walk, but otherwise head for
+/// whichever site is closest.
+pub fn get_work_path_goal(
+    position: Position,
+    current_goal: Option<Position>,
+    work_sites: &[Position],
+) -> Option<Position> {
+    if let Some(current_goal) = current_goal.filter(|goal| work_sites.contains(goal)) {
+        return Some(current_goal);
+    }
+
+    work_sites
+        .iter()
+        .min_by_key(|work_site| position.distance(work_site))
+        .copied()
+}
+
+/// How many workers belong on each task, in the order of `WorkerTask::all()`. Brood care comes first, up to
+/// `max_nurse_count`, and the rest of the workers are shared between the other tasks in proportion to how much work
+/// there is. No task is given more workers than it has work for.
+///
+/// ```
+/// use simulation::nest_simulation::task_allocation::get_task_targets;
+///
+/// // Foraging, nursing, digging, and waste management.
+/// assert_eq!(get_task_targets(10, 2, [3, 5, 12, 0]), [2, 2, 6, 0]);
+///
+/// // Workers are left idle rather than crowding around a little work.
+/// assert_eq!(get_task_targets(10, 2, [1, 0, 0, 1]), [1, 0, 0, 1]);
+/// ```
+pub fn get_task_targets(
+    worker_count: usize,
+    max_nurse_count: usize,
+    demands: [usize; 4],
+) -> [usize; 4] {
+    let mut targets = [0; 4];
+
+    let nursing_index = WorkerTask::Nursing.index();
+    targets[nursing_index] = demands[nursing_index]
+        .min(max_nurse_count)
+        .min(worker_count);
+
+    let remaining_count = worker_count - targets[nursing_index];
+    let other_indices = WorkerTask::all()
+        .into_iter()
+        .map(|task| task.index())
+        .filter(|&index| index != nursing_index)
+        .collect::<Vec<_>>();
+    let other_demand = other_indices
+        .iter()
+        .map(|&index| demands[index])
+        .sum::<usize>();
+
+    if other_demand == 0 {
+        return targets;
+    }
+
+    for &index in other_indices.iter() {
+        targets[index] = (remaining_count * demands[index] / other_demand).min(demands[index]);
+    }
+
+    // Shares are rounded down so hand out whoever is left over to tasks with work to spare.
+    let mut leftover_count = remaining_count
+        - other_indices
+            .iter()
+            .map(|&index| targets[index])
+            .sum::<usize>();
+
+    for &index in other_indices.iter() {
+        let extra_count = (demands[index] - targets[index]).min(leftover_count);
+        targets[index] += extra_count;
+        leftover_count -= extra_count;
+    }
+
+    targets
+}
+
+/// Take stock of the work around the nest and set idle workers to it. Workers which can no longer work, or whose work
+/// has run out, are taken off their task straight away. Otherwise, workers are only reassigned periodically: surplus
+/// workers are taken off of over-staffed tasks and idle, empty-handed, workers are picked to make up any shortfall.
+/// Workers of the age best suited to a task are picked for it first, and taken off it last, so the colony's workers
+/// sort themselves into nurses, diggers, and foragers as they age. Otherwise, workers are picked at random.
+pub fn ants_allocate_tasks(
+    ants_query: Query<
+        (
+            Entity,
+            &AntRole,
+            &AntInventory,
+            &Hunger,
+            Option<&Age>,
+            Option<&Order>,
+            Option<&WorkerTask>,
+        ),
+        (With<AtNest>, With<Initiative>, Without<Dead>),
+    >,
+    larvae_query: Query<&Larva, (With<Brood>, With<AtNest>)>,
+    dig_orders_query: Query<&Position, (With<DigOrder>, With<AtNest>)>,
+    food_query: Query<&Position, (With<Food>, With<AtNest>)>,
+    corpses_query: Query<&Position, (With<Corpse>, With<AtNest>)>,
+    waste_query: Query<&Position, (With<Waste>, With<AtNest>)>,
+    nest_query: Query<(&Grid, &Nest)>,
+    grid_elements: GridElements<AtNest>,
+    colony_planner: Res<ColonyPlanner>,
+    balance_config: Res<BalanceConfig>,
+    settings: Res<Settings>,
+    mut task_allocation: ResMut<TaskAllocation>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    let hunger_balance = &balance_config.hunger;
+    let (grid, nest) = nest_query.single();
+    let midden_position = get_midden_position(grid, nest, &grid_elements);
+    let refuse_position = get_refuse_position(&colony_planner, midden_position, &grid_elements);
+
+    let is_queen_hungry = ants_query.iter().any(|(_, role, _, hunger, ..)| {
+        *role == AntRole::Queen && hunger.is_peckish(hunger_balance)
+    });
+    let growing_larva_count = larvae_query
+        .iter()
+        .filter(|larva| !larva.is_fully_grown())
+        .count();
+
+    let surface_food_positions = food_query
+        .iter()
+        .filter(|position| nest.is_aboveground(position))
+        .copied()
+        .collect::<Vec<_>>();
+    // Corpses can't be cleared away until there's a midden to take them to.
+    let stray_corpse_positions = corpses_query
+        .iter()
+        .filter(|position| midden_position.is_some() && !is_at_midden(**position, midden_position))
+        .copied()
+        .collect::<Vec<_>>();
+    let stray_waste_positions = waste_query
+        .iter()
+        .filter(|position| {
+            refuse_position.is_some() && !is_at_refuse(**position, &colony_planner, midden_position)
+        })
+        .copied()
+        .collect::<Vec<_>>();
+
+    task_allocation.dig_sites = get_work_sites(dig_orders_query.iter().copied(), &grid_elements);
+    task_allocation.forage_sites =
+        get_work_sites(surface_food_positions.iter().copied(), &grid_elements);
+    task_allocation.refuse_sites = get_work_sites(
+        stray_corpse_positions
+            .iter()
+            .chain(stray_waste_positions.iter())
+            .copied(),
+        &grid_elements,
+    );
+
+    // Work nobody can reach doesn't need anyone assigned to it.
+    let get_demand = |count: usize, sites: &[Position]| if sites.is_empty() { 0 } else { count };
+
+    let mut demands = [0; 4];
+    demands[WorkerTask::Foraging.index()] =
+        get_demand(surface_food_positions.len(), &task_allocation.forage_sites);
+    demands[WorkerTask::Nursing.index()] = growing_larva_count + is_queen_hungry as usize;
+    demands[WorkerTask::Digging.index()] =
+        get_demand(dig_orders_query.iter().count(), &task_allocation.dig_sites);
+    demands[WorkerTask::WasteManagement.index()] = get_demand(
+        stray_corpse_positions.len() + stray_waste_positions.len(),
+        &task_allocation.refuse_sites,
+    );
+
+    let mut worker_count = 0;
+    let mut available_count = 0;
+    let mut assigned_workers: [Vec<(Entity, Option<LifeStage>)>; 4] = Default::default();
+    let mut idle_workers = vec![];
+
+    for (ant_entity, role, inventory, hunger, age, order, worker_task) in ants_query.iter() {
+        if *role != AntRole::Worker {
+            continue;
+        }
+
+        worker_count += 1;
+
+        let life_stage =
+            age.map(|age| get_life_stage(age.ticks(), get_max_lifespan_ticks(role, &settings)));
+
+        let is_available = order.is_none() && !hunger.is_hungry(hunger_balance);
+        if !is_available {
+            if worker_task.is_some() {
+                commands.entity(ant_entity).remove::<WorkerTask>();
+            }
+
+            continue;
+        }
+
+        available_count += 1;
+
+        match worker_task {
+            Some(worker_task) if demands[worker_task.index()] > 0 => {
+                assigned_workers[worker_task.index()].push((ant_entity, life_stage));
+            }
+            Some(_) => {
+                commands.entity(ant_entity).remove::<WorkerTask>();
+
+                if inventory.is_empty() {
+                    idle_workers.push((ant_entity, life_stage));
+                }
+            }
+            None if inventory.is_empty() => idle_workers.push((ant_entity, life_stage)),
+            None => {}
+        }
+    }
+
+    if task_allocation.ticks_until_allocation > 0 {
+        task_allocation.ticks_until_allocation -= 1;
+    } else {
+        task_allocation.ticks_until_allocation = ALLOCATION_INTERVAL_TICKS;
+
+        let max_nurse_count = (worker_count as f32 * settings.nurse_ratio).ceil() as usize;
+        let targets = get_task_targets(available_count, max_nurse_count, demands);
+
+        for task in WorkerTask::all() {
+            let preferred_life_stage = task.get_preferred_life_stage();
+            let workers = &mut assigned_workers[task.index()];
+
+            // Workers are taken from the back so those of the wrong age are moved there to be taken off first.
+            workers.sort_by_key(|(_, life_stage)| {
+                preferred_life_stage.is_some() && *life_stage != preferred_life_stage
+            });
+
+            while workers.len() > targets[task.index()] {
+                let Some(worker) = workers.pop() else {
+                    break;
+                };
+
+                commands.entity(worker.0).remove::<WorkerTask>();
+                idle_workers.push(worker);
+            }
+        }
+
+        rng.shuffle(&mut idle_workers);
+
+        for task in WorkerTask::all() {
+            let preferred_life_stage = task.get_preferred_life_stage();
+            let workers = &mut assigned_workers[task.index()];
+
+            // Sorting is stable so workers of the right age are picked first, but still at random.
+            idle_workers.sort_by_key(|(_, life_stage)| {
+                preferred_life_stage.is_some() && *life_stage == preferred_life_stage
+            });
+
+            while workers.len() < targets[task.index()] {
+                let Some(worker) = idle_workers.pop() else {
+                    break;
+                };
+
+                commands.entity(worker.0).insert(task);
+                workers.push(worker);
+            }
+        }
+    }
+
+    for task in WorkerTask::all() {
+        task_allocation.staffing[task.index()] = TaskStaffing {
+            demand: demands[task.index()],
+            assigned: assigned_workers[task.index()].len(),
+        };
+    }
+
+    task_allocation.idle_count = idle_workers.len();
+}
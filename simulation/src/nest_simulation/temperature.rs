@@ -0,0 +1,209 @@
+use crate::{
+    common::{grid::Grid, position::Position},
+    nest_simulation::nest::{AtNest, Nest},
+    story_time::{Season, StoryTime, DEFAULT_TICKS_PER_SECOND, SECONDS_PER_DAY},
+};
+use bevy::prelude::*;
+use std::f32::consts::TAU;
+
+// Soil well below the surface stays at about the same temperature, day and night, in degrees Celsius.
+const DEEP_SOIL_TEMPERATURE: f32 = 18.0;
+// The surface is this much warmer than deep soil in the heat of the afternoon, and as much colder in the small hours.
+const SURFACE_DAILY_SWING: f32 = 12.0;
+// The surface is hottest mid-afternoon, once the ground has had all day to soak up the sun.
+const SURFACE_WARMEST_HOUR: f32 = 14.0;
+// Soil insulates. The daily swing shrinks to about a third every this many tiles further down.
+const SWING_DAMPING_DEPTH: f32 = 8.0;
+// How readily heat spreads from tile to tile each tick. Chosen so the daily swing, spreading down from the surface,
+// shrinks with depth as described by SWING_DAMPING_DEPTH.
+const SOIL_DIFFUSIVITY: f32 = SWING_DAMPING_DEPTH * SWING_DAMPING_DEPTH * TAU
+    / (2.0 * (SECONDS_PER_DAY * DEFAULT_TICKS_PER_SECOND) as f32);
+// Ants are cold-blooded. Below this temperature, in degrees Celsius, they slow down.
+const ACTIVITY_MIN_TEMPERATURE: f32 = 15.0;
+// Every this many degrees colder adds a tick to how long an ant waits between turns, up to a point.
+const ACTIVITY_DEGREES_PER_TICK: f32 = 3.0;
+const ACTIVITY_MAX_DELAY_TICKS: isize = 6;
+
+/// Seasons shift the surface, and the soil beneath it, warmer or colder.
+fn get_season_offset(season: Season) -> f32 {
+    match season {
+        Season::Spring => 0.0,
+        Season::Summer => 6.0,
+        Season::Autumn => -2.0,
+        Season::Winter => -10.0,
+    }
+}
+
+/// How warm soil `depth` tiles below the surface is, in degrees Celsius, at the given hour of the day. Shallow soil
+/// warms through the day and cools through the night, like the surface above it, while deep soil barely changes.
+///
+/// ```
+/// use simulation::{nest_simulation::temperature::get_soil_temperature, story_time::Season};
+///
+/// // Shallow soil is warmer than deep soil in the afternoon, and colder in the small hours.
+/// assert!(get_soil_temperature(1, 14.0, Season::Spring) > get_soil_temperature(20, 14.0, Season::Spring));
+/// assert!(get_soil_temperature(1, 2.0, Season::Spring) < get_soil_temperature(20, 2.0, Season::Spring));
+///
+/// // Deep soil is about as warm at night as it is during the day.
+/// let deep_swing = get_soil_temperature(60, 14.0, Season::Spring) - get_soil_temperature(60, 2.0, Season::Spring);
+/// assert!(deep_swing.abs() < 0.1);
+///
+/// // Winter is colder all the way down.
+/// assert!(get_soil_temperature(40, 14.0, Season::Winter) < get_soil_temperature(40, 14.0, Season::Summer));
+/// ```
+pub fn get_soil_temperature(depth: isize, decimal_hours: f32, season: Season) -> f32 {
+    let daily_cycle = (TAU * (decimal_hours - SURFACE_WARMEST_HOUR) / 24.0).cos();
+    let swing = SURFACE_DAILY_SWING * (-(depth.max(0) as f32) / SWING_DAMPING_DEPTH).exp();
+
+    DEEP_SOIL_TEMPERATURE + get_season_offset(season) + swing * daily_cycle
+}
+
+/// How many extra ticks an ant waits between turns at the given temperature. Ants are sluggish in the cold.
+///
+/// ```
+/// use simulation::nest_simulation::temperature::get_cold_delay_ticks;
+///
+/// assert_eq!(get_cold_delay_ticks(20.0), 0);
+/// assert_eq!(get_cold_delay_ticks(9.0), 2);
+/// // Even the coldest ants get a turn now and then.
+/// assert_eq!(get_cold_delay_ticks(-30.0), 6);
+/// ```
+pub fn get_cold_delay_ticks(temperature: f32) -> isize {
+    let chill = (ACTIVITY_MIN_TEMPERATURE - temperature).max(0.0);
+
+    ((chill / ACTIVITY_DEGREES_PER_TICK) as isize).min(ACTIVITY_MAX_DELAY_TICKS)
+}
+
+/// How warm each tile of the nest is, in degrees Celsius. The sky sets the temperature at the surface, following the
+/// time of day and the season, and heat spreads down through the soil from there, and back up again, a tile at a time.
+/// So, deep soil is slow to feel the heat of the day and is still warm from summer well into autumn.
+/// Note the intentional omission of reflection/serialization. The field is rebuilt, from the time of day and season,
+/// whenever a story is loaded.
+///
+/// ```
+/// use simulation::{
+///     common::position::Position,
+///     nest_simulation::temperature::TemperatureField,
+///     story_time::Season,
+/// };
+///
+/// let mut temperature_field = TemperatureField::new(10, 80, 5, 14.0, Season::Spring);
+/// let shallow_position = Position::new(5, 6);
+/// let deep_position = Position::new(5, 75);
+/// let shallow_temperature = temperature_field.temperature(shallow_position);
+/// let deep_temperature = temperature_field.temperature(deep_position);
+///
+/// // A scorching afternoon warms the shallow soil, but the heat hasn't had time to reach deeper down.
+/// for _ in 0..10_000 {
+///     temperature_field.tick(5, 60.0);
+/// }
+///
+/// assert!(temperature_field.temperature(shallow_position) > shallow_temperature + 5.0);
+/// assert!((temperature_field.temperature(deep_position) - deep_temperature).abs() < 0.01);
+/// ```
+#[derive(Component, Debug, Clone)]
+pub struct TemperatureField {
+    width: isize,
+    height: isize,
+    temperatures: Vec<f32>,
+    // Reused each tick to avoid allocating while diffusing.
+    scratch: Vec<f32>,
+}
+
+impl TemperatureField {
+    /// A field which starts out as if the soil had settled into its usual daily pattern for the given hour and season.
+    pub fn new(
+        width: isize,
+        height: isize,
+        surface_level: isize,
+        decimal_hours: f32,
+        season: Season,
+    ) -> Self {
+        let temperatures = (0..height)
+            .flat_map(|y| {
+                let temperature = get_soil_temperature(y - surface_level, decimal_hours, season);
+
+                (0..width).map(move |_| temperature)
+            })
+            .collect::<Vec<_>>();
+
+        Self {
+            width,
+            height,
+            scratch: temperatures.clone(),
+            temperatures,
+        }
+    }
+
+    fn get_index(&self, position: Position) -> usize {
+        let x = position.x.clamp(0, self.width - 1);
+        let y = position.y.clamp(0, self.height - 1);
+
+        (y * self.width + x) as usize
+    }
+
+    /// Positions outside of the nest are as warm as the nearest tile inside of it.
+    pub fn temperature(&self, position: Position) -> f32 {
+        self.temperatures[self.get_index(position)]
+    }
+
+    /// The sky, and everything in it, is as warm as the surface. Beneath the surface, each tile evens out its temperature
+    /// with its neighbors. Tiles along the edges of the nest neither gain nor lose heat through them.
+    pub fn tick(&mut self, surface_level: isize, surface_temperature: f32) {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let index = (y * self.width + x) as usize;
+
+                if y <= surface_level {
+                    self.scratch[index] = surface_temperature;
+                    continue;
+                }
+
+                let temperature = self.temperatures[index];
+                let neighbors = [
+                    Position::new(x - 1, y),
+                    Position::new(x + 1, y),
+                    Position::new(x, y - 1),
+                    Position::new(x, y + 1),
+                ];
+
+                let neighbor_difference = neighbors
+                    .iter()
+                    .map(|&neighbor| self.temperature(neighbor) - temperature)
+                    .sum::<f32>();
+
+                self.scratch[index] = temperature + SOIL_DIFFUSIVITY * neighbor_difference;
+            }
+        }
+
+        std::mem::swap(&mut self.temperatures, &mut self.scratch);
+    }
+}
+
+/// The nest's temperature field is built on the first tick after a story is created, or loaded, once the time of day
+/// is known. Afterward, it's advanced one tick at a time.
+pub fn update_temperature_field(
+    mut nest_query: Query<(Entity, &Grid, &Nest, Option<&mut TemperatureField>), With<AtNest>>,
+    story_time: Res<StoryTime>,
+    mut commands: Commands,
+) {
+    let (nest_entity, grid, nest, temperature_field) = nest_query.single_mut();
+    let decimal_hours = story_time.as_time_info().get_decimal_hours();
+    let season = story_time.season();
+
+    match temperature_field {
+        Some(mut temperature_field) => temperature_field.tick(
+            nest.surface_level(),
+            get_soil_temperature(0, decimal_hours, season),
+        ),
+        None => {
+            commands.entity(nest_entity).insert(TemperatureField::new(
+                grid.width(),
+                grid.height(),
+                nest.surface_level(),
+                decimal_hours,
+                season,
+            ));
+        }
+    }
+}
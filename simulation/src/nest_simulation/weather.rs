@@ -0,0 +1,162 @@
+use super::{
+    element::{commands::ElementCommandsExt, Element},
+    nest::AtNest,
+};
+use crate::{
+    common::{grid::GridElements, position::Position},
+    save::ReflectPersist,
+    settings::Settings,
+    story_time::{StoryTime, DEFAULT_TICKS_PER_SECOND, SECONDS_PER_HOUR},
+};
+use bevy::prelude::*;
+use bevy_turborand::{DelegatedRng, GlobalRng};
+use serde::{Deserialize, Serialize};
+
+// A share of rainy days bring storms rather than showers.
+const STORM_CHANCE: f64 = 0.25;
+// How likely a raindrop is to fall somewhere over the nest each tick at the height of a storm. Lighter rain is scaled
+// down by its intensity. A day of rain leaves a handful of drops per column of sky, which the dirt mostly soaks up.
+// A storm brings enough to flood shallow tunnels.
+const STORM_DROP_CHANCE: f64 = 0.004;
+// Rain builds up, and dies down, over about an hour rather than starting and stopping all at once.
+const INTENSITY_RAMP_TICKS: f32 = (DEFAULT_TICKS_PER_SECOND * SECONDS_PER_HOUR) as f32;
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+pub enum WeatherCondition {
+    #[default]
+    Clear,
+    Rain,
+    Storm,
+}
+
+impl WeatherCondition {
+    /// How hard it rains once the weather has fully set in.
+    pub fn target_intensity(&self) -> f32 {
+        match self {
+            WeatherCondition::Clear => 0.0,
+            WeatherCondition::Rain => 0.25,
+            WeatherCondition::Storm => 1.0,
+        }
+    }
+}
+
+/// What the sky is doing today. The weather is decided at the start of each day and lasts until the next. Rain falls on
+/// the surface as water, which soaks into the dirt or runs down into the tunnels.
+#[derive(Resource, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Resource, Persist)]
+pub struct Weather {
+    condition: WeatherCondition,
+    // The day the weather was last decided on. Each day is only considered once.
+    last_day: Option<isize>,
+    // How hard it's raining, from 0.0 (dry) to 1.0 (the height of a storm). Ramps toward the condition's target
+    // intensity. Persisted so reloading mid-storm picks up where the storm left off.
+    intensity: f32,
+}
+
+impl Weather {
+    pub fn condition(&self) -> WeatherCondition {
+        self.condition
+    }
+
+    pub fn intensity(&self) -> f32 {
+        self.intensity
+    }
+}
+
+pub fn register_weather(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<Weather>();
+    app_type_registry.write().register::<WeatherCondition>();
+}
+
+pub fn initialize_weather_resources(mut commands: Commands) {
+    commands.init_resource::<Weather>();
+}
+
+pub fn remove_weather_resources(mut commands: Commands) {
+    commands.remove_resource::<Weather>();
+}
+
+/// Decide, once per day, whether it's clear, raining, or storming. How often it rains is up to the player.
+pub fn update_weather(
+    mut weather: ResMut<Weather>,
+    story_time: Res<StoryTime>,
+    settings: Res<Settings>,
+    mut rng: ResMut<GlobalRng>,
+) {
+    let day = story_time.as_time_info().days();
+    if weather.last_day == Some(day) {
+        return;
+    }
+
+    weather.condition = if !rng.chance(settings.probabilities.rain.into()) {
+        WeatherCondition::Clear
+    } else if rng.chance(STORM_CHANCE) {
+        WeatherCondition::Storm
+    } else {
+        WeatherCondition::Rain
+    };
+    weather.last_day = Some(day);
+}
+
+/// Rain eases in, and out, as the weather changes rather than switching on and off between days.
+///
+/// ```
+/// use simulation::nest_simulation::weather::{step_intensity, WeatherCondition};
+///
+/// let intensity = step_intensity(0.0, WeatherCondition::Storm);
+/// assert!(intensity > 0.0 && intensity < WeatherCondition::Storm.target_intensity());
+///
+/// // Intensity settles on the target rather than overshooting it.
+/// assert_eq!(step_intensity(0.99999, WeatherCondition::Storm), 1.0);
+/// assert_eq!(step_intensity(0.25, WeatherCondition::Rain), 0.25);
+/// assert_eq!(step_intensity(0.00001, WeatherCondition::Clear), 0.0);
+/// ```
+pub fn step_intensity(intensity: f32, condition: WeatherCondition) -> f32 {
+    let target = condition.target_intensity();
+    let step = 1.0 / INTENSITY_RAMP_TICKS;
+
+    if intensity < target {
+        (intensity + step).min(target)
+    } else {
+        (intensity - step).max(target)
+    }
+}
+
+pub fn update_weather_intensity(mut weather: ResMut<Weather>) {
+    let intensity = step_intensity(weather.intensity, weather.condition);
+
+    // Only touch Weather while ramping, so views which react to weather changes aren't redrawn every tick.
+    if intensity != weather.intensity {
+        weather.intensity = intensity;
+    }
+}
+
+/// While it's raining, drops of water fall from the top of the sky, at random, wherever there's open air for them.
+/// The harder it rains, the more drops fall. Once spawned, they fall, and flow, like any other water.
+pub fn rain_fall(
+    weather: Res<Weather>,
+    settings: Res<Settings>,
+    grid_elements: GridElements<AtNest>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    if weather.intensity <= 0.0 {
+        return;
+    }
+
+    if !rng.chance(STORM_DROP_CHANCE * weather.intensity as f64) {
+        return;
+    }
+
+    let position = Position::new(rng.isize(0..settings.nest_width), 0);
+    if !grid_elements.is(position, Element::Air) {
+        return;
+    }
+
+    commands.replace_element(
+        position,
+        Element::Water,
+        *grid_elements.entity(position),
+        AtNest,
+    );
+}
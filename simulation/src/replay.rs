@@ -0,0 +1,271 @@
+use bevy::prelude::*;
+use bevy_turborand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    common::{position::Position, Zone},
+    crater_simulation::crater::AtCrater,
+    external_event::ExternalSimulationEvent,
+    headless::{create_headless_app, start_headless_story},
+    nest_simulation::{ant::Ant, nest::AtNest},
+    settings::Settings,
+    story_time::StoryTime,
+};
+
+/// Present in an app whose next story should be recorded so that it can be replayed by a spectator.
+/// The simulation is deterministic, so a story is fully described by its seed, its settings,
+/// and the external events which reached the simulation on each tick.
+#[derive(Resource, Default)]
+pub struct RecordingSimulation {
+    seed: u64,
+}
+
+/// Present in the view-only app replaying a recorded story. User input is discarded in favor of the recorded events.
+#[derive(Resource)]
+pub struct ReplayingSimulation {
+    seed: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RecordedEvent<Z: Zone> {
+    pub tick: isize,
+    pub event: ExternalSimulationEvent<Z>,
+    // Entities aren't the same from one app to the next, e.g. rendering spawns its own, so ordered ants are found
+    // again, when replayed, by where they stood when the order was given.
+    pub ordered_ant_positions: Vec<Position>,
+}
+
+/// External events in the order they were processed, alongside the tick they were processed on.
+#[derive(Resource)]
+pub struct ExternalEventLog<Z: Zone> {
+    events: Vec<RecordedEvent<Z>>,
+    // Replays release events as their tick is reached. Recordings only ever append.
+    next_index: usize,
+}
+
+impl<Z: Zone> Default for ExternalEventLog<Z> {
+    fn default() -> Self {
+        Self {
+            events: vec![],
+            next_index: 0,
+        }
+    }
+}
+
+impl<Z: Zone> ExternalEventLog<Z> {
+    fn new(events: Vec<RecordedEvent<Z>>) -> Self {
+        Self {
+            events,
+            next_index: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Events recorded since the first `start` events.
+    pub fn events_since(&self, start: usize) -> &[RecordedEvent<Z>] {
+        self.events.get(start..).unwrap_or_default()
+    }
+
+    /// Replays are extended as more of the story they're replaying is told.
+    pub fn extend(&mut self, events: Vec<RecordedEvent<Z>>) {
+        self.events.extend(events);
+    }
+}
+
+/// Everything a spectator needs to replay a recorded story.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SimulationRecording {
+    pub seed: u64,
+    pub settings: Settings,
+    pub nest_events: Vec<RecordedEvent<AtNest>>,
+    pub crater_events: Vec<RecordedEvent<AtCrater>>,
+}
+
+impl SimulationRecording {
+    /// Returns None if the story being told in `world` wasn't recorded from its beginning.
+    /// Settings are copied as they are now, so stories whose settings change part way through won't replay faithfully.
+    /// Neither will stories which follow real time because the replay's clock starts when the replay does.
+    pub fn from_world(world: &World) -> Option<Self> {
+        Some(Self {
+            seed: world.get_resource::<RecordingSimulation>()?.seed,
+            settings: *world.get_resource::<Settings>()?,
+            nest_events: world
+                .get_resource::<ExternalEventLog<AtNest>>()?
+                .events
+                .clone(),
+            crater_events: world
+                .get_resource::<ExternalEventLog<AtCrater>>()?
+                .events
+                .clone(),
+        })
+    }
+}
+
+/// Reseed the shared source of randomness, before the world is generated, so the story can be recreated from its seed.
+pub fn begin_recording(
+    mut recording: ResMut<RecordingSimulation>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    recording.seed = rng.u64(..);
+    *rng = GlobalRng::with_seed(recording.seed);
+
+    commands.init_resource::<ExternalEventLog<AtNest>>();
+    commands.init_resource::<ExternalEventLog<AtCrater>>();
+}
+
+/// Prepare `world` to replay `recording` when its next story is created.
+pub fn insert_replay_resources(world: &mut World, recording: SimulationRecording) {
+    world.insert_resource(recording.settings);
+    world.insert_resource(ReplayingSimulation {
+        seed: recording.seed,
+    });
+    world.insert_resource(ExternalEventLog::new(recording.nest_events));
+    world.insert_resource(ExternalEventLog::new(recording.crater_events));
+}
+
+pub fn begin_replay(replay: Res<ReplayingSimulation>, mut rng: ResMut<GlobalRng>) {
+    *rng = GlobalRng::with_seed(replay.seed);
+}
+
+pub fn remove_replay_resources(mut commands: Commands) {
+    commands.remove_resource::<RecordingSimulation>();
+    commands.remove_resource::<ReplayingSimulation>();
+    commands.remove_resource::<ExternalEventLog<AtNest>>();
+    commands.remove_resource::<ExternalEventLog<AtCrater>>();
+}
+
+/// Copy external events into the log before they're processed. Stories loaded from a save have no log
+/// because they weren't recorded from their beginning.
+pub fn record_external_events<Z: Zone + Clone>(
+    mut external_simulation_events: EventReader<ExternalSimulationEvent<Z>>,
+    mut external_event_log: ResMut<ExternalEventLog<Z>>,
+    story_time: Res<StoryTime>,
+    ants_query: Query<&Position, (With<Ant>, With<Z>)>,
+) {
+    for event in external_simulation_events.read() {
+        let ordered_ant_positions = match event {
+            ExternalSimulationEvent::OrderAnts { ant_entities, .. } => {
+                ants_query.iter_many(ant_entities).copied().collect()
+            }
+            _ => vec![],
+        };
+
+        external_event_log.events.push(RecordedEvent {
+            tick: story_time.elapsed_ticks(),
+            event: event.clone(),
+            ordered_ant_positions,
+        });
+    }
+}
+
+/// Replace whatever external events were sent with the recorded events for the current tick.
+pub fn replay_external_events<Z: Zone + Clone>(
+    mut external_simulation_events: ResMut<Events<ExternalSimulationEvent<Z>>>,
+    mut external_event_log: ResMut<ExternalEventLog<Z>>,
+    story_time: Res<StoryTime>,
+    ants_query: Query<(Entity, &Position), (With<Ant>, With<Z>)>,
+) {
+    external_simulation_events.clear();
+
+    while let Some(recorded_event) = external_event_log.events.get(external_event_log.next_index) {
+        if recorded_event.tick > story_time.elapsed_ticks() {
+            break;
+        }
+
+        let mut event = recorded_event.event.clone();
+        if let ExternalSimulationEvent::OrderAnts { ant_entities, .. } = &mut event {
+            *ant_entities = ants_query
+                .iter()
+                .filter(|(_, position)| recorded_event.ordered_ant_positions.contains(position))
+                .map(|(entity, _)| entity)
+                .collect();
+        }
+
+        external_simulation_events.send(event);
+        external_event_log.next_index += 1;
+    }
+}
+
+/// Create a separate, headless app which replays a recorded story.
+/// Run the `SimulationUpdate` schedule directly, rather than updating the app, to advance the replay a tick at a time.
+/// The host must not draw from `GlobalRng` outside of the simulation or the replay will drift from the original.
+/// Spectators in another browser replay the same way, see `broadcast`.
+///
+/// ```
+/// use bevy::prelude::*;
+/// use simulation::{
+///     common::position::Position,
+///     external_event::ExternalSimulationEvent,
+///     headless::{create_headless_app, start_headless_story},
+///     nest_simulation::{ant::Ant, nest::AtNest},
+///     replay::{create_replay_app, RecordingSimulation, SimulationRecording},
+///     settings::Settings,
+///     story_time::StoryTime,
+///     SimulationUpdate,
+/// };
+///
+/// fn ant_positions(app: &mut App) -> Vec<Position> {
+///     let mut positions = app
+///         .world
+///         .query_filtered::<&Position, With<Ant>>()
+///         .iter(&app.world)
+///         .copied()
+///         .collect::<Vec<_>>();
+///
+///     positions.sort_by_key(|position| (position.x, position.y));
+///     positions
+/// }
+///
+/// let settings = Settings {
+///     initial_ant_worker_count: 5,
+///     ..default()
+/// };
+///
+/// let mut host = create_headless_app(settings, 1);
+/// host.init_resource::<RecordingSimulation>();
+/// assert!(start_headless_story(&mut host));
+///
+/// for tick in 0..20 {
+///     // The top row of the nest is always open sky.
+///     if tick == 10 {
+///         host.world
+///             .resource_mut::<Events<ExternalSimulationEvent<AtNest>>>()
+///             .send(ExternalSimulationEvent::SpawnWorkerAnt(Position::new(10, 0), AtNest));
+///     }
+///
+///     host.world.run_schedule(SimulationUpdate);
+/// }
+///
+/// let recording = SimulationRecording::from_world(&host.world).unwrap();
+/// assert_eq!(recording.nest_events.len(), 1);
+///
+/// let mut replay = create_replay_app(recording).unwrap();
+///
+/// // Catch the replay up to the host.
+/// let host_elapsed_ticks = host.world.resource::<StoryTime>().elapsed_ticks();
+/// while replay.world.resource::<StoryTime>().elapsed_ticks() < host_elapsed_ticks {
+///     replay.world.run_schedule(SimulationUpdate);
+/// }
+///
+/// // The spectator sees the same colony, including the worker spawned part way through.
+/// assert_eq!(ant_positions(&mut replay), ant_positions(&mut host));
+/// ```
+pub fn create_replay_app(recording: SimulationRecording) -> Option<App> {
+    let mut app = create_headless_app(recording.settings, recording.seed);
+    insert_replay_resources(&mut app.world, recording);
+
+    if !start_headless_story(&mut app) {
+        warn!("Replayed story failed to start.");
+        return None;
+    }
+
+    Some(app)
+}
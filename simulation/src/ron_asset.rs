@@ -0,0 +1,121 @@
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+    reflect::TypePath,
+    utils::BoxedFuture,
+};
+use serde::de::DeserializeOwned;
+use std::{fmt::Debug, marker::PhantomData};
+use thiserror::Error;
+
+/// An asset which is deserialized, as is, from a RON file. Each kind of RON asset has its own extension, e.g.
+/// `balance.ron`, so the asset server knows which loader to use.
+pub trait RonAsset: Asset + DeserializeOwned {
+    const EXTENSIONS: &'static [&'static str];
+}
+
+/// A RON asset which is loaded once, from a fixed path, and copied into a resource of the same type. When built with
+/// `dev-hot-reload`, changes to the file are copied into the resource while the app is running.
+/// Systems read the resource, rather than the asset, so they don't need to handle the asset being unavailable.
+pub trait RonConfig: RonAsset + Resource + Default + Clone + Debug {
+    const PATH: &'static str;
+}
+
+pub struct RonAssetLoader<A: RonAsset> {
+    _phantom: PhantomData<fn() -> A>,
+}
+
+impl<A: RonAsset> Default for RonAssetLoader<A> {
+    fn default() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum RonAssetLoaderError {
+    #[error("Could not read asset: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse asset: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+}
+
+impl<A: RonAsset> AssetLoader for RonAssetLoader<A> {
+    type Asset = A;
+    type Settings = ();
+    type Error = RonAssetLoaderError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let asset = ron::de::from_bytes::<A>(&bytes)?;
+            Ok(asset)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        A::EXTENSIONS
+    }
+}
+
+/// Register a RON config, its loader, and the systems which copy it into its resource. Configs aren't tied to a story
+/// so they're loaded once and live for the lifetime of the app. The built-in default is used until the file loads.
+pub struct RonConfigPlugin<A: RonConfig> {
+    _phantom: PhantomData<fn() -> A>,
+}
+
+impl<A: RonConfig> Default for RonConfigPlugin<A> {
+    fn default() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: RonConfig> Plugin for RonConfigPlugin<A> {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<A>();
+        app.init_asset_loader::<RonAssetLoader<A>>();
+        app.init_resource::<A>();
+        app.add_systems(Startup, load_ron_config::<A>);
+        app.add_systems(Update, update_ron_config::<A>);
+    }
+}
+
+#[derive(Resource)]
+pub struct RonConfigHandle<A: RonConfig>(pub Handle<A>);
+
+pub fn load_ron_config<A: RonConfig>(asset_server: Res<AssetServer>, mut commands: Commands) {
+    commands.insert_resource(RonConfigHandle::<A>(asset_server.load(A::PATH)));
+}
+
+/// Copy the asset into its resource whenever it finishes loading or is modified on disk.
+pub fn update_ron_config<A: RonConfig>(
+    mut asset_events: EventReader<AssetEvent<A>>,
+    assets: Res<Assets<A>>,
+    handle: Res<RonConfigHandle<A>>,
+    mut config: ResMut<A>,
+) {
+    for asset_event in asset_events.read() {
+        let asset_id = match asset_event {
+            AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } => id,
+            _ => continue,
+        };
+
+        if *asset_id != handle.0.id() {
+            continue;
+        }
+
+        if let Some(new_config) = assets.get(*asset_id) {
+            info!("Applying {}: {:?}", A::short_type_path(), new_config);
+            *config = new_config.clone();
+        }
+    }
+}
@@ -9,3 +9,307 @@ pub use crate::save::save_web::*;
 
 #[cfg(not(target_arch = "wasm32"))]
 pub use crate::save::save_os::*;
+
+use crate::{
+    common::{grid::Grid, position::Position},
+    nest_simulation::{element::Element, nest::Nest},
+};
+use bevy::{
+    ecs::{system::Resource, world::World},
+    reflect::{FromType, Reflect, TypeRegistry},
+    scene::SceneFilter,
+};
+use serde::{Deserialize, Serialize};
+
+/// True when an autosave was read back and didn't match what was written. Storage can silently truncate writes,
+/// e.g. when it's nearly full, leaving a save which can't be loaded. Only saving on the web is verified.
+#[derive(Resource, Default, PartialEq, Debug)]
+pub struct IsSaveTruncated(pub bool);
+
+/// Set to save right away, rather than waiting for the next autosave, e.g. before a story is left paused and unattended.
+/// Only saving on the web is supported so the request is only honored there.
+#[derive(Resource, Default, PartialEq, Debug)]
+pub struct IsSaveRequested(pub bool);
+
+// Chunks are large enough that a typical nest is only a few dozen of them, but small enough that a huge crater doesn't
+// need to be deserialized all at once.
+pub const GRID_CHUNK_SIZE: isize = 32;
+
+// Thumbnails are small enough to store alongside a save without noticeably adding to its size.
+pub const SAVE_THUMBNAIL_SIZE: isize = 48;
+
+/// Type data marking a component, or resource, as part of a story's persistent state. Add it with `#[reflect(Persist)]`.
+/// Only persisted types are written to saves. Anything else, e.g. derived data like `ElementExposure` or `Stable`,
+/// render handles, and transient scratch state, is left out of saves and rebuilt after loading.
+#[derive(Clone)]
+pub struct ReflectPersist;
+
+impl<T: Reflect> FromType<T> for ReflectPersist {
+    fn from_type() -> Self {
+        Self
+    }
+}
+
+/// Allow only registered types which have opted into persistence.
+pub fn get_persistence_filter(type_registry: &TypeRegistry) -> SceneFilter {
+    type_registry
+        .iter()
+        .filter(|registration| registration.data::<ReflectPersist>().is_some())
+        .fold(SceneFilter::deny_all(), |filter, registration| {
+            filter.allow_by_id(registration.type_id())
+        })
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone, Serialize, Deserialize)]
+pub enum GridChunkZone {
+    Nest,
+    Crater,
+}
+
+/// Elements on a grid are saved in square chunks, rather than all at once, so they can be loaded a chunk at a time.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Serialize, Deserialize)]
+pub struct GridChunk {
+    pub zone: GridChunkZone,
+    pub x: isize,
+    pub y: isize,
+}
+
+impl GridChunk {
+    pub fn new(zone: GridChunkZone, position: Position) -> Self {
+        Self {
+            zone,
+            x: position.x.div_euclid(GRID_CHUNK_SIZE),
+            y: position.y.div_euclid(GRID_CHUNK_SIZE),
+        }
+    }
+
+    /// Stories open looking at the surface of the nest, at `focus`, so load the nest first, starting with what's in view
+    /// and working outward, and the crater last.
+    ///
+    /// ```
+    /// use simulation::{
+    ///     common::position::Position,
+    ///     save::{GridChunk, GridChunkZone},
+    /// };
+    ///
+    /// let focus = GridChunk::new(GridChunkZone::Nest, Position::new(72, 40));
+    /// let mut grid_chunks = vec![
+    ///     GridChunk::new(GridChunkZone::Crater, Position::new(72, 40)),
+    ///     GridChunk::new(GridChunkZone::Nest, Position::new(0, 0)),
+    ///     GridChunk::new(GridChunkZone::Nest, Position::new(72, 40)),
+    ///     GridChunk::new(GridChunkZone::Nest, Position::new(72, 100)),
+    /// ];
+    ///
+    /// grid_chunks.sort_by_key(|grid_chunk| grid_chunk.get_load_priority(&focus));
+    ///
+    /// assert_eq!(grid_chunks[0], focus);
+    /// assert_eq!(grid_chunks[3].zone, GridChunkZone::Crater);
+    /// ```
+    pub fn get_load_priority(&self, focus: &GridChunk) -> (GridChunkZone, isize) {
+        let distance = (self.x - focus.x).abs().max((self.y - focus.y).abs());
+
+        (self.zone, distance)
+    }
+}
+
+/// A tiny picture of the nest, stored alongside a save, so menus can preview a colony without loading it.
+/// Each pixel is the most common element in the block of the nest it covers. Coloring elements is left to the UI.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SaveThumbnail {
+    pub width: isize,
+    pub height: isize,
+    // The row of pixels containing the surface. Air beneath it is tunnel, rather than sky.
+    pub surface_level: isize,
+    pub elements: Vec<Element>,
+}
+
+impl SaveThumbnail {
+    pub fn get_element(&self, x: isize, y: isize) -> Element {
+        self.elements[(y * self.width + x) as usize]
+    }
+}
+
+/// Downsample the nest into a thumbnail no larger than `SAVE_THUMBNAIL_SIZE` along either side.
+pub fn create_save_thumbnail(world: &mut World) -> Option<SaveThumbnail> {
+    let mut nest_query = world.query::<(&Grid, &Nest)>();
+    let (grid, nest) = nest_query.get_single(world).ok()?;
+
+    // Every pixel covers the same square block of the nest so the thumbnail keeps the nest's proportions.
+    let scale = (grid.width().max(grid.height()) + SAVE_THUMBNAIL_SIZE - 1) / SAVE_THUMBNAIL_SIZE;
+    let width = (grid.width() + scale - 1) / scale;
+    let height = (grid.height() + scale - 1) / scale;
+
+    let mut elements = Vec::with_capacity((width * height) as usize);
+
+    for y in 0..height {
+        for x in 0..width {
+            let mut element_counts: Vec<(Element, usize)> = vec![];
+
+            for grid_y in (y * scale)..((y + 1) * scale).min(grid.height()) {
+                for grid_x in (x * scale)..((x + 1) * scale).min(grid.width()) {
+                    let element_entity = grid.elements()[grid_y as usize][grid_x as usize];
+                    let element = world
+                        .get::<Element>(element_entity)
+                        .copied()
+                        .unwrap_or_default();
+
+                    match element_counts
+                        .iter_mut()
+                        .find(|(counted_element, _)| *counted_element == element)
+                    {
+                        Some((_, count)) => *count += 1,
+                        None => element_counts.push((element, 1)),
+                    }
+                }
+            }
+
+            let element = element_counts
+                .into_iter()
+                .max_by_key(|(_, count)| *count)
+                .map_or(Element::Air, |(element, _)| element);
+
+            elements.push(element);
+        }
+    }
+
+    Some(SaveThumbnail {
+        width,
+        height,
+        surface_level: nest.surface_level() / scale,
+        elements,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup(width: isize, height: isize, surface_level: isize) -> World {
+        let mut world = World::new();
+
+        let elements_cache = (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|_| {
+                        let element = if y > surface_level {
+                            Element::Dirt
+                        } else {
+                            Element::Air
+                        };
+
+                        world.spawn(element).id()
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        world.spawn((
+            Grid::new(width, height, elements_cache),
+            Nest::new(surface_level),
+        ));
+
+        world
+    }
+
+    #[test]
+    fn grid_chunk_contains_positions_up_to_its_size() {
+        let first_chunk = GridChunk::new(GridChunkZone::Nest, Position::new(0, 0));
+
+        assert_eq!(
+            GridChunk::new(GridChunkZone::Nest, Position::new(31, 31)),
+            first_chunk
+        );
+        assert_eq!(
+            GridChunk::new(GridChunkZone::Nest, Position::new(32, 31)),
+            GridChunk {
+                zone: GridChunkZone::Nest,
+                x: 1,
+                y: 0,
+            }
+        );
+        assert_ne!(
+            GridChunk::new(GridChunkZone::Crater, Position::new(0, 0)),
+            first_chunk
+        );
+    }
+
+    #[test]
+    fn grid_chunk_of_negative_position_is_rounded_down() {
+        let grid_chunk = GridChunk::new(GridChunkZone::Crater, Position::new(-1, -33));
+
+        assert_eq!((grid_chunk.x, grid_chunk.y), (-1, -2));
+    }
+
+    #[test]
+    fn diagonal_grid_chunks_load_with_adjacent_grid_chunks() {
+        let focus = GridChunk::new(GridChunkZone::Nest, Position::new(64, 64));
+        let adjacent = GridChunk::new(GridChunkZone::Nest, Position::new(96, 64));
+        let diagonal = GridChunk::new(GridChunkZone::Nest, Position::new(96, 96));
+
+        assert_eq!(
+            adjacent.get_load_priority(&focus),
+            diagonal.get_load_priority(&focus)
+        );
+        assert!(focus.get_load_priority(&focus) < adjacent.get_load_priority(&focus));
+    }
+
+    #[test]
+    fn nearby_crater_grid_chunks_load_after_distant_nest_grid_chunks() {
+        let focus = GridChunk::new(GridChunkZone::Nest, Position::new(0, 0));
+        let distant_nest = GridChunk::new(GridChunkZone::Nest, Position::new(1_000, 1_000));
+        let nearby_crater = GridChunk::new(GridChunkZone::Crater, Position::new(0, 0));
+
+        assert!(distant_nest.get_load_priority(&focus) < nearby_crater.get_load_priority(&focus));
+    }
+
+    #[test]
+    fn small_nest_thumbnail_is_not_scaled() {
+        let mut world = setup(4, 3, 0);
+
+        let thumbnail = create_save_thumbnail(&mut world).unwrap();
+
+        assert_eq!((thumbnail.width, thumbnail.height), (4, 3));
+        assert_eq!(thumbnail.surface_level, 0);
+        assert_eq!(thumbnail.get_element(3, 0), Element::Air);
+        assert_eq!(thumbnail.get_element(3, 1), Element::Dirt);
+    }
+
+    #[test]
+    fn large_nest_thumbnail_keeps_proportions() {
+        let mut world = setup(SAVE_THUMBNAIL_SIZE * 2, SAVE_THUMBNAIL_SIZE, 10);
+
+        let thumbnail = create_save_thumbnail(&mut world).unwrap();
+
+        assert_eq!(
+            (thumbnail.width, thumbnail.height),
+            (SAVE_THUMBNAIL_SIZE, SAVE_THUMBNAIL_SIZE / 2)
+        );
+        assert_eq!(thumbnail.surface_level, 5);
+    }
+
+    #[test]
+    fn thumbnail_pixel_is_most_common_element_in_its_block() {
+        // Each pixel covers a 2x2 block of what starts out as all air.
+        let mut world = setup(SAVE_THUMBNAIL_SIZE * 2, 2, 1);
+        let elements_cache = world.query::<&Grid>().single(&world).elements().clone();
+        for (x, y) in [(0, 1), (2, 0), (2, 1), (3, 1)] {
+            *world.get_mut::<Element>(elements_cache[y][x]).unwrap() = Element::Dirt;
+        }
+
+        let thumbnail = create_save_thumbnail(&mut world).unwrap();
+
+        assert_eq!(
+            (thumbnail.width, thumbnail.height),
+            (SAVE_THUMBNAIL_SIZE, 1)
+        );
+        assert_eq!(thumbnail.get_element(0, 0), Element::Air);
+        assert_eq!(thumbnail.get_element(1, 0), Element::Dirt);
+    }
+
+    #[test]
+    fn thumbnail_requires_a_nest() {
+        let mut world = World::new();
+
+        assert_eq!(create_save_thumbnail(&mut world), None);
+    }
+}
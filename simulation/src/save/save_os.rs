@@ -1,8 +1,13 @@
 // TODO: Support saving on non-WASM targets.
 use bevy::prelude::*;
 
+use super::SaveThumbnail;
+use crate::{common::colony_stats::StoryRecord, device_quality::DeviceQuality};
+
 pub fn save() {}
 
+pub fn save_on_request() {}
+
 pub fn bind_save_onbeforeunload() {}
 
 pub fn unbind_save_onbeforeunload() {}
@@ -13,6 +18,54 @@ pub fn load(_world: &mut World) -> bool {
     false
 }
 
+pub fn load_grid_chunks() -> Option<bool> {
+    Some(false)
+}
+
+pub fn archive_save(_world: &mut World) {}
+
+// Ruins are read from archived saves so, like saving, they're only supported on the web.
+pub fn load_archived_story_ids() -> Vec<i64> {
+    vec![]
+}
+
+pub fn load_archived_save(_world: &mut World) -> bool {
+    false
+}
+
+pub fn load_save_thumbnail() -> Option<SaveThumbnail> {
+    None
+}
+
+pub fn load_archived_save_thumbnail(_story_id: i64) -> Option<SaveThumbnail> {
+    None
+}
+
+pub fn get_requested_ruins() -> Option<i64> {
+    None
+}
+
+pub fn open_ruins(_story_id: i64) {}
+
+pub fn close_ruins() {}
+
+pub fn load_story_records() -> Vec<StoryRecord> {
+    vec![]
+}
+
+pub fn write_story_record(_story_record: StoryRecord) {}
+
+// Nothing measured about the device could be kept so there's no point measuring it.
+pub fn is_device_quality_persisted() -> bool {
+    false
+}
+
+pub fn load_device_quality() -> Option<DeviceQuality> {
+    None
+}
+
+pub fn write_device_quality(_device_quality: &DeviceQuality) {}
+
 pub fn initialize_save_resources() {}
 
 pub fn remove_save_resources() {}
\ No newline at end of file
@@ -1,37 +1,127 @@
-use bevy::{ecs::query::WorldQuery, prelude::*};
+use bevy::{
+    ecs::query::WorldQuery,
+    prelude::*,
+    utils::{Duration, HashMap, Instant},
+};
 use bevy_save::{
-    Backend, DefaultDebugFormat, Error, Format, Pipeline, Snapshot, SnapshotBuilder,
+    Backend, DefaultDebugFormat, DespawnMode, Error, Format, Pipeline, Snapshot, SnapshotBuilder,
     SnapshotSerializer, WorldSaveableExt,
 };
 use brotli::enc::BrotliEncoderInitParams;
 use gloo_storage::{LocalStorage, Storage};
 use serde::de::DeserializeSeed;
 use serde::Serialize;
-use std::{cell::RefCell, io::Read, io::Write, sync::Mutex};
+use std::{
+    cell::RefCell,
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    io::Read,
+    io::Write,
+    sync::{Arc, Mutex},
+};
 use wasm_bindgen::{prelude::Closure, JsCast};
 use web_sys::BeforeUnloadEvent;
 
+use super::{
+    create_save_thumbnail, get_persistence_filter, GridChunk, GridChunkZone, IsSaveRequested,
+    IsSaveTruncated, SaveThumbnail,
+};
 use crate::{
-    crater_simulation::crater::Crater,
-    nest_simulation::{ant::Ant, element::Element, nest::Nest, pheromone::Pheromone},
+    app_state::ViewingRuins,
+    common::{
+        colony_stats::{ColonyStats, StoryRecord, MAX_STORY_RECORDS},
+        food_sharing::FoodSharingNetwork,
+        logistics::ColonyLogistics,
+        position::Position,
+    },
+    crater_simulation::{
+        aphid::Aphid,
+        crater::{AtCrater, Crater},
+    },
+    device_quality::DeviceQuality,
+    nest_simulation::{
+        ant::{brood::Brood, Ant},
+        colony_planner::ColonyPlanner,
+        element::Element,
+        nest::{AtNest, Nest},
+        pheromone::Pheromone,
+    },
     settings::Settings,
     story_time::{StoryRealWorldTime, StoryTime},
 };
 
 const LOCAL_STORAGE_KEY: &str = "world-save-state";
+// The final state of each story is kept, separately from the active save, so its ruins can be revisited after it's
+// over. Each story's archive is keyed by the story's id.
+const ARCHIVE_LOCAL_STORAGE_KEY: &str = "world-archive-state";
+// Which stories have been archived, most recent first.
+const ARCHIVED_STORIES_LOCAL_STORAGE_KEY: &str = "world-archived-stories";
+// Archives are as large as saves so only the most recent few are kept.
+const MAX_ARCHIVED_STORIES: usize = 3;
+// Ruins are viewed in an app of their own, opened by reloading the page with the story's id as this query parameter.
+const RUINS_QUERY_PARAMETER: &str = "ruins";
+// Records of completed stories outlive any one story so they're kept apart from world saves.
+const STORY_RECORDS_LOCAL_STORAGE_KEY: &str = "story-records";
+// What the device can handle doesn't change from one story to the next so it's kept apart from world saves, too.
+const DEVICE_QUALITY_LOCAL_STORAGE_KEY: &str = "device-quality";
 const LOAD_ERROR: &str = "Failed to load world state from local storage";
 const DECOMPRESS_ERROR: &str = "Failed to decompress data";
+// Grid chunks are loaded, and autosaved, a few at a time so a large grid doesn't stall a single frame. Each frame stops
+// taking on more chunks once it's spent this long on them.
+const GRID_CHUNK_FRAME_BUDGET: Duration = Duration::from_millis(4);
 
-static SAVE_SNAPSHOT: Mutex<Option<Vec<u8>>> = Mutex::new(None);
+static SAVE_SNAPSHOT: Mutex<Option<Arc<SaveSnapshot>>> = Mutex::new(None);
+static SAVE_WRITE: Mutex<Option<SaveWrite>> = Mutex::new(None);
+static SAVE_VERIFICATION: Mutex<SaveVerification> = Mutex::new(SaveVerification::new());
+
+/// A serialized snapshot of the world. Elements on the grid make up the bulk of a save so they're split into chunks,
+/// each serialized separately, and stored alongside the rest of the story.
+struct SaveSnapshot {
+    story: Vec<u8>,
+    grid_chunks: Vec<(GridChunk, Vec<u8>)>,
+    thumbnail: Option<Vec<u8>>,
+}
+
+/// An autosave which is being written across frames. Its grid chunks are written first, a few each frame, and the rest
+/// of the story last, so the list of grid chunks is never written before the chunks it lists.
+struct SaveWrite {
+    snapshot: Arc<SaveSnapshot>,
+    written_grid_chunks: usize,
+}
+
+/// Grid chunks of a loaded story which have yet to be loaded. The next chunk to load is last.
+#[derive(Resource)]
+struct PendingGridChunks {
+    key: String,
+    grid_chunks: Vec<GridChunk>,
+}
+
+/// Payloads from the most recent autosave which have yet to be read back and checked against what was written.
+/// Reading back and decompressing a payload isn't free so only one is checked per frame.
+struct SaveVerification {
+    pending_payloads: Vec<(String, u64)>,
+    is_truncated: bool,
+}
+
+impl SaveVerification {
+    const fn new() -> Self {
+        Self {
+            pending_payloads: Vec::new(),
+            is_truncated: false,
+        }
+    }
+}
 
 #[derive(WorldQuery)]
 struct PersistentModelQueryFilter {
     _or: Or<(
         With<Ant>,
+        With<Brood>,
         With<Element>,
         With<Crater>,
         With<Nest>,
         With<Pheromone>,
+        With<Aphid>,
     )>,
 }
 
@@ -47,34 +137,70 @@ pub struct LastSaveTime(f32);
 /// NOTE: intentionally don't run immediately on first run because it's expensive and nothing has changed.
 /// Let the full interval pass before creating anything rather than initializing on first run then waiting.
 pub fn save(world: &mut World) {
+    verify_save_payload(world);
+    continue_save_write();
+
     let current_time = world.resource::<Time<Real>>().elapsed_seconds();
+    let autosave_interval_multiplier = world
+        .get_resource::<DeviceQuality>()
+        .map_or(1, |device_quality| {
+            device_quality.level().autosave_interval_multiplier()
+        });
+
     let last_snapshot_time = world.resource::<LastSnapshotTime>();
-    let snapshot_interval = world.resource::<Settings>().snapshot_interval;
+    let snapshot_interval =
+        world.resource::<Settings>().snapshot_interval * autosave_interval_multiplier;
     if current_time - last_snapshot_time.0 < snapshot_interval as f32 {
         return;
     }
 
     if let Some(snapshot) = create_save_snapshot(world) {
-        *SAVE_SNAPSHOT.lock().unwrap() = Some(snapshot);
+        *SAVE_SNAPSHOT.lock().unwrap() = Some(Arc::new(snapshot));
         world.resource_mut::<LastSnapshotTime>().0 = current_time;
     } else {
         error!("Failed to create snapshot");
     }
 
-    let save_interval = world.resource::<Settings>().save_interval;
+    let save_interval = world.resource::<Settings>().save_interval * autosave_interval_multiplier;
     let last_save_time = world.resource::<LastSaveTime>();
     if current_time - last_save_time.0 < save_interval as f32 {
         return;
     }
 
+    if begin_save_write() {
+        world.resource_mut::<LastSaveTime>().0 = current_time;
+    }
+}
+
+/// Snapshot the world, and write it to local storage, without waiting for either interval to pass.
+pub fn save_on_request(world: &mut World) {
+    world.resource_mut::<IsSaveRequested>().0 = false;
+
+    let current_time = world.resource::<Time<Real>>().elapsed_seconds();
+
+    match create_save_snapshot(world) {
+        Some(snapshot) => {
+            *SAVE_SNAPSHOT.lock().unwrap() = Some(Arc::new(snapshot));
+            world.resource_mut::<LastSnapshotTime>().0 = current_time;
+        }
+        None => {
+            error!("Failed to create snapshot");
+            return;
+        }
+    }
+
     if write_save_snapshot() {
         world.resource_mut::<LastSaveTime>().0 = current_time;
     }
 }
 
-fn create_save_snapshot(world: &mut World) -> Option<Vec<u8>> {
-    let mut buffer: Vec<u8> = Vec::new();
-    let mut serde = rmp_serde::Serializer::new(&mut buffer);
+fn create_save_snapshot(world: &mut World) -> Option<SaveSnapshot> {
+    // A save without a thumbnail is still a good save so failing to create one isn't fatal.
+    let thumbnail = create_save_thumbnail(world).and_then(|thumbnail| {
+        rmp_serde::to_vec(&thumbnail)
+            .map_err(|error| error!("Failed to serialize save thumbnail: {:?}", error))
+            .ok()
+    });
 
     // Persistent entities must have an Id marker because Id is fit for uniquely identifying across sessions.
     // NOTE: Technically this could also include InventoryItem, but Element matches it (just by chance for now though?)
@@ -82,10 +208,43 @@ fn create_save_snapshot(world: &mut World) -> Option<Vec<u8>> {
 
     model_query.update_archetypes(world);
     let readonly_model_query = model_query.as_readonly();
-    let snapshot = build_snapshot(world, readonly_model_query);
+
+    let mut story_entities = vec![];
+    let mut grid_chunk_entities: HashMap<GridChunk, Vec<Entity>> = HashMap::new();
+
+    for entity in readonly_model_query.iter_manual(world) {
+        match get_grid_chunk(world, entity) {
+            Some(grid_chunk) => grid_chunk_entities
+                .entry(grid_chunk)
+                .or_default()
+                .push(entity),
+            None => story_entities.push(entity),
+        }
+    }
+
+    let story = serialize_snapshot(world, &build_story_snapshot(world, story_entities))?;
+
+    let grid_chunks = grid_chunk_entities
+        .into_iter()
+        .map(|(grid_chunk, entities)| {
+            let snapshot = build_grid_chunk_snapshot(world, entities);
+            Some((grid_chunk, serialize_snapshot(world, &snapshot)?))
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(SaveSnapshot {
+        story,
+        grid_chunks,
+        thumbnail,
+    })
+}
+
+fn serialize_snapshot(world: &World, snapshot: &Snapshot) -> Option<Vec<u8>> {
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut serde = rmp_serde::Serializer::new(&mut buffer);
 
     let registry: &AppTypeRegistry = world.resource::<AppTypeRegistry>();
-    let result = SnapshotSerializer::new(&snapshot, registry).serialize(&mut serde);
+    let result = SnapshotSerializer::new(snapshot, registry).serialize(&mut serde);
 
     if result.is_ok() {
         return Some(buffer);
@@ -96,15 +255,221 @@ fn create_save_snapshot(world: &mut World) -> Option<Vec<u8>> {
     None
 }
 
-fn write_save_snapshot() -> bool {
-    let save_snapshot = SAVE_SNAPSHOT.lock().unwrap();
+/// Elements which are on a grid belong to the chunk containing their position. Everything else, e.g. ants, or elements
+/// being carried by ants, belongs to the story.
+fn get_grid_chunk(world: &World, entity: Entity) -> Option<GridChunk> {
+    let entity_ref = world.entity(entity);
+
+    if !entity_ref.contains::<Element>() {
+        return None;
+    }
+
+    let position = entity_ref.get::<Position>()?;
+
+    let zone = if entity_ref.contains::<AtNest>() {
+        GridChunkZone::Nest
+    } else if entity_ref.contains::<AtCrater>() {
+        GridChunkZone::Crater
+    } else {
+        return None;
+    };
 
-    let buffer = match save_snapshot.as_ref() {
-        Some(buffer) => buffer,
+    Some(GridChunk::new(zone, *position))
+}
+
+fn get_grid_chunk_key(key: &str, grid_chunk: &GridChunk) -> String {
+    format!(
+        "{}-{:?}-{}-{}",
+        key, grid_chunk.zone, grid_chunk.x, grid_chunk.y
+    )
+}
+
+fn get_grid_chunks_key(key: &str) -> String {
+    format!("{}-grid-chunks", key)
+}
+
+fn get_thumbnail_key(key: &str) -> String {
+    format!("{}-thumbnail", key)
+}
+
+/// Saves written before grids were chunked have no chunks. Their elements are part of the story instead.
+fn get_grid_chunks(key: &str) -> Vec<GridChunk> {
+    LocalStorage::get::<Vec<GridChunk>>(get_grid_chunks_key(key)).unwrap_or_default()
+}
+
+/// Write the most recent snapshot all at once, e.g. because the page is unloading and there are no frames left to spread
+/// the write across. Any autosave still being written is superseded.
+fn write_save_snapshot() -> bool {
+    let snapshot = match SAVE_SNAPSHOT.lock().unwrap().clone() {
+        Some(snapshot) => snapshot,
         // SAVE_SNAPSHOT can be empty during the first few seconds of app load because snapshots are taken periodically.
         None => return false,
     };
 
+    *SAVE_WRITE.lock().unwrap() = None;
+
+    if !write_snapshot(LOCAL_STORAGE_KEY, &snapshot) {
+        return false;
+    }
+
+    begin_save_verification(&snapshot);
+
+    true
+}
+
+/// Start writing the most recent snapshot across the next few frames. An autosave which is still being written is left
+/// to finish rather than being restarted.
+fn begin_save_write() -> bool {
+    let mut save_write = SAVE_WRITE.lock().unwrap();
+    if save_write.is_some() {
+        return false;
+    }
+
+    let snapshot = match SAVE_SNAPSHOT.lock().unwrap().clone() {
+        Some(snapshot) => snapshot,
+        None => return false,
+    };
+
+    *save_write = Some(SaveWrite {
+        snapshot,
+        written_grid_chunks: 0,
+    });
+
+    true
+}
+
+/// Write as many grid chunks of the autosave in progress as fit in this frame and, once they're all written, the rest
+/// of the story.
+fn continue_save_write() {
+    let mut save_write_guard = SAVE_WRITE.lock().unwrap();
+    let save_write = match save_write_guard.as_mut() {
+        Some(save_write) => save_write,
+        None => return,
+    };
+
+    let start = Instant::now();
+    let grid_chunks = &save_write.snapshot.grid_chunks;
+
+    while let Some((grid_chunk, buffer)) = grid_chunks.get(save_write.written_grid_chunks) {
+        if start.elapsed() >= GRID_CHUNK_FRAME_BUDGET {
+            return;
+        }
+
+        if !write_compressed(&get_grid_chunk_key(LOCAL_STORAGE_KEY, grid_chunk), buffer) {
+            *save_write_guard = None;
+            return;
+        }
+
+        save_write.written_grid_chunks += 1;
+    }
+
+    if let Some(save_write) = save_write_guard.take() {
+        if finish_writing_snapshot(LOCAL_STORAGE_KEY, &save_write.snapshot) {
+            begin_save_verification(&save_write.snapshot);
+        }
+    }
+}
+
+/// Check, over the next few frames, that what was written can be read back.
+fn begin_save_verification(snapshot: &SaveSnapshot) {
+    // A newer save supersedes whatever was left to verify from the previous one.
+    let mut save_verification = SAVE_VERIFICATION.lock().unwrap();
+    save_verification.is_truncated = false;
+    save_verification.pending_payloads = snapshot
+        .grid_chunks
+        .iter()
+        .map(|(grid_chunk, buffer)| {
+            (
+                get_grid_chunk_key(LOCAL_STORAGE_KEY, grid_chunk),
+                get_payload_hash(buffer),
+            )
+        })
+        .chain(std::iter::once((
+            LOCAL_STORAGE_KEY.to_string(),
+            get_payload_hash(&snapshot.story),
+        )))
+        .collect();
+}
+
+fn get_payload_hash(buffer: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    buffer.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Read back, and decompress, one payload of the most recent autosave to confirm storage kept all of it.
+/// The user is alerted as soon as a payload doesn't match. The alert is cleared once a later save is fully verified.
+fn verify_save_payload(world: &mut World) {
+    let mut save_verification = SAVE_VERIFICATION.lock().unwrap();
+
+    let (key, expected_hash) = match save_verification.pending_payloads.pop() {
+        Some(pending_payload) => pending_payload,
+        None => return,
+    };
+
+    let is_intact =
+        read_compressed(&key).map_or(false, |buffer| get_payload_hash(&buffer) == expected_hash);
+
+    if !is_intact {
+        error!("Saved payload for {} doesn't match what was written", key);
+        save_verification.is_truncated = true;
+    }
+
+    if save_verification.is_truncated || save_verification.pending_payloads.is_empty() {
+        world.resource_mut::<IsSaveTruncated>().0 = save_verification.is_truncated;
+    }
+}
+
+/// Write the story, and each of its grid chunks, under their own keys, all at once.
+fn write_snapshot(key: &str, snapshot: &SaveSnapshot) -> bool {
+    let is_grid_chunks_written = snapshot
+        .grid_chunks
+        .iter()
+        .all(|(grid_chunk, buffer)| write_compressed(&get_grid_chunk_key(key, grid_chunk), buffer));
+
+    is_grid_chunks_written && finish_writing_snapshot(key, snapshot)
+}
+
+/// Write the rest of the story once its grid chunks have been written. The list of grid chunks is written last so it
+/// only ever lists chunks which have been written. Chunks which no longer exist, e.g. because they were part of a larger,
+/// previous story, are deleted afterward so they aren't loaded into this one.
+fn finish_writing_snapshot(key: &str, snapshot: &SaveSnapshot) -> bool {
+    // Clear any stale thumbnail, rather than leaving a picture of a previous story, when there's nothing to replace it.
+    match &snapshot.thumbnail {
+        Some(thumbnail) => {
+            write_compressed(&get_thumbnail_key(key), thumbnail);
+        }
+        None => LocalStorage::delete(get_thumbnail_key(key)),
+    }
+
+    if !write_compressed(key, &snapshot.story) {
+        return false;
+    }
+
+    let grid_chunks = snapshot
+        .grid_chunks
+        .iter()
+        .map(|(grid_chunk, _)| *grid_chunk)
+        .collect::<Vec<_>>();
+
+    let stale_grid_chunks = get_grid_chunks(key)
+        .into_iter()
+        .filter(|grid_chunk| !grid_chunks.contains(grid_chunk))
+        .collect::<Vec<_>>();
+
+    if let Err(error) = LocalStorage::set(get_grid_chunks_key(key), &grid_chunks) {
+        error!("Failed to save grid chunks to local storage: {:?}", error);
+        return false;
+    }
+
+    for stale_grid_chunk in stale_grid_chunks {
+        LocalStorage::delete(get_grid_chunk_key(key, &stale_grid_chunk));
+    }
+
+    true
+}
+
+fn write_compressed(key: &str, buffer: &[u8]) -> bool {
     // Compress snapshot using Brotli. In testing, this reduces a 4mb save file to 0.5mb with compression quality: 1.
     let mut params = BrotliEncoderInitParams();
     params.quality = 1; // Max compression (0-11 range)
@@ -114,7 +479,7 @@ fn write_save_snapshot() -> bool {
         .write_all(buffer)
         .expect("Failed to write to compressor");
 
-    let save_result = LocalStorage::set(LOCAL_STORAGE_KEY, compressed_data.into_inner());
+    let save_result = LocalStorage::set(key, compressed_data.into_inner());
 
     if save_result.is_err() {
         error!(
@@ -164,42 +529,273 @@ pub fn unbind_save_onbeforeunload() {
 }
 
 pub fn delete_save_file() {
-    LocalStorage::delete(LOCAL_STORAGE_KEY);
+    delete_snapshot(LOCAL_STORAGE_KEY);
+}
+
+fn delete_snapshot(key: &str) {
+    for grid_chunk in get_grid_chunks(key) {
+        LocalStorage::delete(get_grid_chunk_key(key, &grid_chunk));
+    }
+
+    LocalStorage::delete(get_grid_chunks_key(key));
+    LocalStorage::delete(get_thumbnail_key(key));
+    LocalStorage::delete(key);
+}
+
+/// Write the final state of the world to a dedicated archive so that the story's ruins can be viewed later.
+/// The archive is written immediately, rather than periodically, because the world stops changing once the story is over.
+pub fn archive_save(world: &mut World) {
+    let story_id = world.resource::<StoryRealWorldTime>().0;
+
+    let Some(snapshot) = create_save_snapshot(world) else {
+        error!("Failed to create archive snapshot");
+        return;
+    };
+
+    if !write_snapshot(&get_archive_key(story_id), &snapshot) {
+        return;
+    }
+
+    let mut archived_story_ids = load_archived_story_ids();
+    archived_story_ids.insert(0, story_id);
+
+    if archived_story_ids.len() > MAX_ARCHIVED_STORIES {
+        for stale_story_id in archived_story_ids.split_off(MAX_ARCHIVED_STORIES) {
+            delete_snapshot(&get_archive_key(stale_story_id));
+        }
+    }
+
+    if let Err(error) = LocalStorage::set(ARCHIVED_STORIES_LOCAL_STORAGE_KEY, &archived_story_ids) {
+        error!(
+            "Failed to save archived stories to local storage: {:?}",
+            error
+        );
+    }
+
+    // Before each story had an archive of its own, only the most recent story's archive was kept, without a story id.
+    delete_snapshot(ARCHIVE_LOCAL_STORAGE_KEY);
+}
+
+/// Most recent first. Only stories whose ruins are still kept are included. See `StoryRecord::id`.
+pub fn load_archived_story_ids() -> Vec<i64> {
+    LocalStorage::get::<Vec<i64>>(ARCHIVED_STORIES_LOCAL_STORAGE_KEY).unwrap_or_default()
+}
+
+/// Saves written before thumbnails existed don't have one.
+pub fn load_save_thumbnail() -> Option<SaveThumbnail> {
+    load_thumbnail(&get_thumbnail_key(LOCAL_STORAGE_KEY))
+}
+
+/// Archives written before thumbnails existed don't have one.
+pub fn load_archived_save_thumbnail(story_id: i64) -> Option<SaveThumbnail> {
+    load_thumbnail(&get_thumbnail_key(&get_archive_key(story_id)))
+}
+
+fn load_thumbnail(key: &str) -> Option<SaveThumbnail> {
+    LocalStorage::raw().get_item(key).ok().flatten()?;
+
+    let buffer = read_compressed(key).ok()?;
+
+    rmp_serde::from_slice(&buffer)
+        .map_err(|error| error!("Failed to deserialize save thumbnail: {:?}", error))
+        .ok()
+}
+
+/// Which story's ruins the page was opened to view, if any.
+pub fn get_requested_ruins() -> Option<i64> {
+    let search = web_sys::window()?.location().search().ok()?;
+
+    search
+        .trim_start_matches('?')
+        .split('&')
+        .find_map(|parameter| {
+            parameter
+                .strip_prefix(RUINS_QUERY_PARAMETER)?
+                .strip_prefix('=')?
+                .parse()
+                .ok()
+        })
+}
+
+/// Reload the page as the view-only app, showing the given story's ruins.
+pub fn open_ruins(story_id: i64) {
+    set_location_search(&format!("?{}={}", RUINS_QUERY_PARAMETER, story_id));
+}
+
+/// Reload the page as the full app, returning to the main menu.
+pub fn close_ruins() {
+    set_location_search("");
+}
+
+fn set_location_search(search: &str) {
+    let window = web_sys::window().expect("window not available");
+
+    if let Err(error) = window.location().set_search(search) {
+        error!("Failed to navigate: {:?}", error);
+    }
+}
+
+fn get_archive_key(story_id: i64) -> String {
+    format!("{}-{}", ARCHIVE_LOCAL_STORAGE_KEY, story_id)
+}
+
+/// Most recent first.
+pub fn load_story_records() -> Vec<StoryRecord> {
+    LocalStorage::get::<Vec<StoryRecord>>(STORY_RECORDS_LOCAL_STORAGE_KEY).unwrap_or_default()
+}
+
+pub fn write_story_record(story_record: StoryRecord) {
+    let mut story_records = load_story_records();
+    story_records.insert(0, story_record);
+    story_records.truncate(MAX_STORY_RECORDS);
+
+    if let Err(error) = LocalStorage::set(STORY_RECORDS_LOCAL_STORAGE_KEY, &story_records) {
+        error!("Failed to save story records to local storage: {:?}", error);
+    }
+}
+
+pub fn is_device_quality_persisted() -> bool {
+    true
+}
+
+pub fn load_device_quality() -> Option<DeviceQuality> {
+    LocalStorage::get::<DeviceQuality>(DEVICE_QUALITY_LOCAL_STORAGE_KEY).ok()
+}
+
+pub fn write_device_quality(device_quality: &DeviceQuality) {
+    if let Err(error) = LocalStorage::set(DEVICE_QUALITY_LOCAL_STORAGE_KEY, device_quality) {
+        error!(
+            "Failed to save device quality to local storage: {:?}",
+            error
+        );
+    }
 }
 
 pub fn initialize_save_resources(mut commands: Commands) {
     commands.init_resource::<CompressedWebStorageBackend>();
     commands.init_resource::<LastSnapshotTime>();
     commands.init_resource::<LastSaveTime>();
+    commands.init_resource::<IsSaveTruncated>();
+    commands.init_resource::<IsSaveRequested>();
 }
 
 pub fn remove_save_resources(mut commands: Commands) {
     commands.remove_resource::<CompressedWebStorageBackend>();
     commands.remove_resource::<LastSnapshotTime>();
     commands.remove_resource::<LastSaveTime>();
+    commands.remove_resource::<IsSaveTruncated>();
+    commands.remove_resource::<IsSaveRequested>();
+
+    *SAVE_WRITE.lock().unwrap() = None;
+    *SAVE_VERIFICATION.lock().unwrap() = SaveVerification::new();
 }
 
 pub fn load(world: &mut World) -> bool {
+    load_from_key(world, LOCAL_STORAGE_KEY)
+}
+
+pub fn load_archived_save(world: &mut World) -> bool {
+    let story_id = world.resource::<ViewingRuins>().story_id;
+
+    load_from_key(world, &get_archive_key(story_id))
+}
+
+/// Load the story and queue up its grid chunks, in priority order, to be streamed in by `load_grid_chunks`.
+fn load_from_key(world: &mut World, key: &str) -> bool {
+    let mut model_query = world.query_filtered::<Entity, PersistentModelQueryFilter>();
+    model_query.update_archetypes(world);
+
+    if world
+        .load(SaveLoadPipeline::new(key, model_query.as_readonly(), false))
+        .is_err()
+    {
+        return false;
+    }
+
+    let focus = get_grid_chunk_focus(world);
+
+    let mut grid_chunks = get_grid_chunks(key);
+    grid_chunks.sort_by_key(|grid_chunk| grid_chunk.get_load_priority(&focus));
+    grid_chunks.reverse();
+
+    world.insert_resource(PendingGridChunks {
+        key: key.to_string(),
+        grid_chunks,
+    });
+
+    true
+}
+
+/// Stories open looking at the middle of the nest's surface.
+fn get_grid_chunk_focus(world: &mut World) -> GridChunk {
+    let nest_width = world.resource::<Settings>().nest_width;
+    let surface_level = world
+        .query::<&Nest>()
+        .get_single(world)
+        .map_or(0, |nest| nest.surface_level());
+
+    GridChunk::new(
+        GridChunkZone::Nest,
+        Position::new(nest_width / 2, surface_level),
+    )
+}
+
+/// Load as many of the loaded story's grid chunks as fit in this frame. Returns None while there are chunks left to
+/// load, and whether every chunk loaded once there aren't. The grid is built from the loaded elements, during setup, so
+/// setup waits for the last of them.
+pub fn load_grid_chunks(world: &mut World) -> Option<bool> {
+    let Some(mut pending_grid_chunks) = world.remove_resource::<PendingGridChunks>() else {
+        return Some(true);
+    };
+
     let mut model_query = world.query_filtered::<Entity, PersistentModelQueryFilter>();
     model_query.update_archetypes(world);
 
     let readonly_model_query = model_query.as_readonly();
+    let start = Instant::now();
 
-    world
-        .load(SaveLoadPipeline::new(readonly_model_query))
-        .is_ok()
+    while start.elapsed() < GRID_CHUNK_FRAME_BUDGET {
+        let Some(grid_chunk) = pending_grid_chunks.grid_chunks.pop() else {
+            return Some(true);
+        };
+
+        let grid_chunk_key = get_grid_chunk_key(&pending_grid_chunks.key, &grid_chunk);
+
+        if world
+            .load(SaveLoadPipeline::new(
+                &grid_chunk_key,
+                readonly_model_query,
+                true,
+            ))
+            .is_err()
+        {
+            error!("Failed to load grid chunk {}", grid_chunk_key);
+            return Some(false);
+        }
+    }
+
+    world.insert_resource(pending_grid_chunks);
+
+    None
 }
 
 struct SaveLoadPipeline<'q> {
     key: String,
     readonly_model_query: &'q QueryState<Entity, PersistentModelQueryFilter>,
+    // Grid chunks are loaded into a world which already contains the rest of the story so nothing is despawned.
+    is_grid_chunk: bool,
 }
 
 impl<'q> SaveLoadPipeline<'q> {
-    pub fn new(readonly_model_query: &'q QueryState<Entity, PersistentModelQueryFilter>) -> Self {
+    pub fn new(
+        key: &str,
+        readonly_model_query: &'q QueryState<Entity, PersistentModelQueryFilter>,
+        is_grid_chunk: bool,
+    ) -> Self {
         Self {
-            key: LOCAL_STORAGE_KEY.to_string(),
+            key: key.to_string(),
             readonly_model_query,
+            is_grid_chunk,
         }
     }
 }
@@ -215,11 +811,16 @@ impl<'q> Pipeline for SaveLoadPipeline<'q> {
     }
 
     fn capture_seed(&self, builder: SnapshotBuilder) -> Snapshot {
-        build_snapshot(builder.world(), self.readonly_model_query)
+        let world = builder.world();
+        build_story_snapshot(world, self.readonly_model_query.iter_manual(world))
     }
 
     fn apply_seed(&self, world: &mut World, snapshot: &Snapshot) -> Result<(), bevy_save::Error> {
-        snapshot.applier(world).apply()
+        if self.is_grid_chunk {
+            snapshot.applier(world).despawn(DespawnMode::None).apply()
+        } else {
+            snapshot.applier(world).apply()
+        }
     }
 }
 
@@ -238,23 +839,7 @@ impl<'a> Backend<&'a str> for CompressedWebStorageBackend {
         key: &str,
         seed: S,
     ) -> Result<T, Error> {
-        // Attempt to retrieve the compressed state from local storage
-        let compressed_saved_state = LocalStorage::get::<Vec<u8>>(key).map_err(|e| {
-            error!("{}: {:?}", LOAD_ERROR, e);
-            Error::custom(LOAD_ERROR)
-        })?;
-
-        // Initialize the decompressor
-        let mut decompressor = brotli::Decompressor::new(&compressed_saved_state[..], 4096);
-        let mut decompressed_data = Vec::new();
-
-        // Attempt to decompress the data
-        decompressor
-            .read_to_end(&mut decompressed_data)
-            .map_err(|e| {
-                error!("{}: {:?}", DECOMPRESS_ERROR, e);
-                Error::custom(DECOMPRESS_ERROR)
-            })?;
+        let decompressed_data = read_compressed(key)?;
 
         // Deserialize the data
         let mut deserializer = rmp_serde::Deserializer::new(&decompressed_data[..]);
@@ -262,14 +847,190 @@ impl<'a> Backend<&'a str> for CompressedWebStorageBackend {
     }
 }
 
-fn build_snapshot(
-    world: &World,
-    readonly_model_query: &QueryState<Entity, PersistentModelQueryFilter>,
-) -> Snapshot {
+fn read_compressed(key: &str) -> Result<Vec<u8>, Error> {
+    // Attempt to retrieve the compressed state from local storage
+    let compressed_saved_state = LocalStorage::get::<Vec<u8>>(key).map_err(|e| {
+        error!("{}: {:?}", LOAD_ERROR, e);
+        Error::custom(LOAD_ERROR)
+    })?;
+
+    // Initialize the decompressor
+    let mut decompressor = brotli::Decompressor::new(&compressed_saved_state[..], 4096);
+    let mut decompressed_data = Vec::new();
+
+    // Attempt to decompress the data
+    decompressor
+        .read_to_end(&mut decompressed_data)
+        .map_err(|e| {
+            error!("{}: {:?}", DECOMPRESS_ERROR, e);
+            Error::custom(DECOMPRESS_ERROR)
+        })?;
+
+    Ok(decompressed_data)
+}
+
+fn build_story_snapshot(world: &World, entities: impl IntoIterator<Item = Entity>) -> Snapshot {
+    let persistence_filter = get_persistence_filter(&world.resource::<AppTypeRegistry>().read());
+
     Snapshot::builder(world)
-        .extract_entities(readonly_model_query.iter_manual(world))
+        .filter(persistence_filter)
+        .extract_entities(entities.into_iter())
         .extract_resource::<Settings>()
         .extract_resource::<StoryTime>()
         .extract_resource::<StoryRealWorldTime>()
+        .extract_resource::<ColonyPlanner>()
+        .extract_resource::<ColonyStats>()
+        .extract_resource::<ColonyLogistics>()
+        .extract_resource::<FoodSharingNetwork>()
+        .build()
+}
+
+fn build_grid_chunk_snapshot(world: &World, entities: Vec<Entity>) -> Snapshot {
+    let persistence_filter = get_persistence_filter(&world.resource::<AppTypeRegistry>().read());
+
+    Snapshot::builder(world)
+        .filter(persistence_filter)
+        .extract_entities(entities.into_iter())
         .build()
 }
+
+// Local storage is only available in a browser, e.g. `wasm-pack test --headless --firefox simulation`.
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    const TEST_KEY: &str = "test-world-save-state";
+
+    fn create_snapshot(grid_chunks: &[GridChunk]) -> SaveSnapshot {
+        SaveSnapshot {
+            story: vec![1, 2, 3],
+            grid_chunks: grid_chunks
+                .iter()
+                .map(|grid_chunk| (*grid_chunk, vec![grid_chunk.x as u8; 64]))
+                .collect(),
+            thumbnail: None,
+        }
+    }
+
+    fn setup_verification(key: &str, expected_hash: u64) -> World {
+        let mut world = World::new();
+        world.init_resource::<IsSaveTruncated>();
+
+        let mut save_verification = SAVE_VERIFICATION.lock().unwrap();
+        save_verification.is_truncated = false;
+        save_verification.pending_payloads = vec![(key.to_string(), expected_hash)];
+
+        world
+    }
+
+    #[wasm_bindgen_test]
+    fn compressed_payload_reads_back_as_written() {
+        let buffer = (0..10_000).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+
+        assert!(write_compressed(TEST_KEY, &buffer));
+        assert_eq!(read_compressed(TEST_KEY).unwrap(), buffer);
+
+        LocalStorage::delete(TEST_KEY);
+    }
+
+    #[wasm_bindgen_test]
+    fn grid_chunk_keys_are_distinct_per_zone() {
+        let nest_grid_chunk = GridChunk::new(GridChunkZone::Nest, Position::new(0, 0));
+        let crater_grid_chunk = GridChunk::new(GridChunkZone::Crater, Position::new(0, 0));
+
+        assert_ne!(
+            get_grid_chunk_key(TEST_KEY, &nest_grid_chunk),
+            get_grid_chunk_key(TEST_KEY, &crater_grid_chunk)
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn only_elements_on_a_grid_belong_to_a_grid_chunk() {
+        let mut world = World::new();
+        let nest_element = world
+            .spawn((Element::Dirt, Position::new(40, 3), AtNest))
+            .id();
+        let crater_element = world
+            .spawn((Element::Food, Position::new(3, 40), AtCrater))
+            .id();
+        // Carried elements have no position.
+        let carried_element = world.spawn((Element::Food, AtNest)).id();
+        let ant_position = world.spawn((Position::new(40, 3), AtNest)).id();
+
+        assert_eq!(
+            get_grid_chunk(&world, nest_element),
+            Some(GridChunk::new(GridChunkZone::Nest, Position::new(40, 3)))
+        );
+        assert_eq!(
+            get_grid_chunk(&world, crater_element),
+            Some(GridChunk::new(GridChunkZone::Crater, Position::new(3, 40)))
+        );
+        assert_eq!(get_grid_chunk(&world, carried_element), None);
+        assert_eq!(get_grid_chunk(&world, ant_position), None);
+    }
+
+    #[wasm_bindgen_test]
+    fn rewriting_snapshot_deletes_stale_grid_chunks() {
+        let kept_grid_chunk = GridChunk::new(GridChunkZone::Nest, Position::new(0, 0));
+        let stale_grid_chunk = GridChunk::new(GridChunkZone::Nest, Position::new(32, 0));
+
+        assert!(write_snapshot(
+            TEST_KEY,
+            &create_snapshot(&[kept_grid_chunk, stale_grid_chunk])
+        ));
+        assert_eq!(
+            get_grid_chunks(TEST_KEY),
+            vec![kept_grid_chunk, stale_grid_chunk]
+        );
+
+        assert!(write_snapshot(
+            TEST_KEY,
+            &create_snapshot(&[kept_grid_chunk])
+        ));
+        assert_eq!(get_grid_chunks(TEST_KEY), vec![kept_grid_chunk]);
+        assert!(read_compressed(&get_grid_chunk_key(TEST_KEY, &kept_grid_chunk)).is_ok());
+        assert!(read_compressed(&get_grid_chunk_key(TEST_KEY, &stale_grid_chunk)).is_err());
+
+        delete_snapshot(TEST_KEY);
+        assert!(get_grid_chunks(TEST_KEY).is_empty());
+        assert!(read_compressed(TEST_KEY).is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn intact_payload_clears_truncation() {
+        let buffer = vec![7; 256];
+        write_compressed(TEST_KEY, &buffer);
+        let mut world = setup_verification(TEST_KEY, get_payload_hash(&buffer));
+        world.resource_mut::<IsSaveTruncated>().0 = true;
+
+        verify_save_payload(&mut world);
+
+        assert_eq!(*world.resource::<IsSaveTruncated>(), IsSaveTruncated(false));
+        LocalStorage::delete(TEST_KEY);
+    }
+
+    #[wasm_bindgen_test]
+    fn mismatched_payload_is_truncated() {
+        let buffer = vec![7; 256];
+        write_compressed(TEST_KEY, &buffer[..128]);
+        let mut world = setup_verification(TEST_KEY, get_payload_hash(&buffer));
+
+        verify_save_payload(&mut world);
+
+        assert_eq!(*world.resource::<IsSaveTruncated>(), IsSaveTruncated(true));
+        LocalStorage::delete(TEST_KEY);
+    }
+
+    #[wasm_bindgen_test]
+    fn missing_payload_is_truncated() {
+        LocalStorage::delete(TEST_KEY);
+        let mut world = setup_verification(TEST_KEY, get_payload_hash(&[]));
+
+        verify_save_payload(&mut world);
+
+        assert_eq!(*world.resource::<IsSaveTruncated>(), IsSaveTruncated(true));
+    }
+}
@@ -1,6 +1,9 @@
+use crate::{crater_simulation::ant::wander::ExplorationStrategy, save::ReflectPersist};
 use bevy::{prelude::*, reflect::Reflect};
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, Reflect, Debug)]
+#[derive(Clone, Copy, Reflect, Debug, Serialize, Deserialize)]
+#[reflect(Default)]
 pub struct Probabilities {
     pub random_drop: f32,             // drop while wandering
     pub random_turn: f32,             // turn while wandering
@@ -16,10 +19,34 @@ pub struct Probabilities {
     pub below_surface_queen_nest_dig: f32,
     pub expand_nest: f32,
     pub sleep_emote: f32,
+    pub speech_bubble: f32,
+    pub crater_seed_spawn: f32,
+    pub cave_in: f32, // chance for an unsupported ceiling to cave in each time it's checked
+    pub infection_spread: f32, // chance to catch an infection, each tick, while touching an infected ant
+    pub corpse_infection: f32, // chance to catch an infection, each tick, while touching a corpse
+    pub waste_infection: f32, // chance to catch an infection, each tick, for each piece of waste near the brood being tended
+    pub meal_waste: f32,      // chance that eating leaves some waste behind
+    pub cursor_curiosity: f32, // chance, each tick, that an idle ant near the cursor goes to investigate it
+    pub night_raid: f32,       // chance, each night, that rival ants raid the nest
+    pub rain: f32,             // chance, each day, that it rains
 }
 
-#[derive(Resource, Copy, Clone, Reflect, Debug)]
-#[reflect(Resource)]
+/// Tuning for how quickly ants tire and recover. Costs are in units of energy spent per turn.
+#[derive(Clone, Copy, Reflect, Debug, Serialize, Deserialize)]
+#[reflect(Default)]
+pub struct EnergySettings {
+    pub max: f32,
+    pub recharge: f32,                    // energy regained every tick
+    pub resting_recharge_multiplier: f32, // recharge is multiplied by this while asleep
+    pub walk_cost: f32, // every turn spent moving, and the least energy needed to take a turn
+    pub carry_cost: f32, // extra cost of moving while carrying something
+    pub dig_cost: f32,  // extra cost of digging
+    pub hunger_per_energy: f32, // ticks worth of hunger added by each unit of energy spent
+}
+
+#[derive(Resource, Copy, Clone, Reflect, Debug, Serialize, Deserialize)]
+// Reflecting Default lets saves written before a setting existed be loaded, with the new setting taking its default value.
+#[reflect(Resource, Default, Persist)]
 pub struct Settings {
     pub snapshot_interval: isize,
     pub save_interval: isize,
@@ -29,15 +56,52 @@ pub struct Settings {
     pub crater_height: isize,
     pub initial_dirt_percent: f32,
     pub initial_ant_worker_count: isize,
+    pub soldier_spawn_ratio: f32,
+    pub nurse_ratio: f32,
     pub ant_color: Color,
     pub chamber_size: isize,
     pub tunnel_length: isize,
+    pub sand_repose_angle: f32,
     pub emote_duration: isize,
     pub max_hunger_time: isize,
+    pub max_thirst_time: isize,
     pub max_digestion_time: isize,
     pub max_birthing_time: isize,
+    pub max_worker_lifespan: isize,
+    pub max_queen_lifespan: isize,
     pub is_breathwork_scheduled: bool,
+    pub pause_on_queen_starving: bool,
+    pub pause_on_brood_flooded: bool,
+    pub pause_on_nest_raided: bool,
+    pub pause_when_idle: bool,
+    pub idle_pause_minutes: isize,
+    pub memory_budget_megabytes: isize,
+    pub is_realistic_start: bool,
+    pub is_nuptial_flight: bool,
+    pub is_story_mode: bool,
+    pub queen_protection_radius: isize,
+    pub is_sleeping_at_night: bool,
+    pub asleep_hunger_multiplier: f32,
+    pub is_reduced_motion: bool,
+    pub is_keyboard_navigation: bool,
+    pub is_disease_enabled: bool,
+    pub is_cursor_curiosity_enabled: bool,
+    pub is_night_raids_enabled: bool,
+    pub exploration_strategy: ExplorationStrategy,
     pub probabilities: Probabilities,
+    pub energy: EnergySettings,
+}
+
+impl Default for Probabilities {
+    fn default() -> Probabilities {
+        Settings::default().probabilities
+    }
+}
+
+impl Default for EnergySettings {
+    fn default() -> EnergySettings {
+        Settings::default().energy
+    }
 }
 
 impl Default for Settings {
@@ -55,15 +119,59 @@ impl Default for Settings {
             crater_height: 144,
             initial_dirt_percent: 2.0 / 4.0,
             initial_ant_worker_count: 0,
+            // The share of hatching ants which become soldiers rather than workers.
+            soldier_spawn_ratio: 0.1,
+            // The share of workers which seek out larvae, and the queen, to feed while any are hungry.
+            nurse_ratio: 0.2,
             ant_color: Color::rgb(0.584, 0.216, 0.859), // purple!
             chamber_size: 5,
             tunnel_length: 12,
+            // The steepest slope, in degrees, piles of sand settle at. Dry sand comes to rest at around 30 degrees.
+            sand_repose_angle: 30.0,
             emote_duration: 30,
-            max_hunger_time: 86_400, // 1 day
+            max_hunger_time: 86_400,  // 1 day
+            max_thirst_time: 259_200, // 3 days
             // NOTE: digestion_time must be quicker than hunger_time or ants hunger will never decrease.
-            max_digestion_time: 3_600, // 1 hour
-            max_birthing_time: 3_600,  // 1 hour
+            max_digestion_time: 3_600,      // 1 hour
+            max_birthing_time: 3_600,       // 1 hour
+            max_worker_lifespan: 2_592_000, // 30 days
+            max_queen_lifespan: 31_536_000, // 1 year
             is_breathwork_scheduled: false,
+            // Pausing is opt-in because it interrupts players who are happy to let the colony fend for itself.
+            pause_on_queen_starving: false,
+            pause_on_brood_flooded: false,
+            pause_on_nest_raided: false,
+            // A story left running in a forgotten tab pauses, and saves, once nobody has touched anything for this long,
+            // so the colony doesn't starve unattended.
+            pause_when_idle: false,
+            idle_pause_minutes: 30,
+            // Mobile browsers will kill a tab which uses too much memory. Stay well clear of that limit.
+            memory_budget_megabytes: 128,
+            // A lone queen founds the colony rather than starting with workers. Chosen from the main menu.
+            is_realistic_start: false,
+            // During a realistic start, the player chooses where the queen lands rather than her landing at random.
+            is_nuptial_flight: false,
+            // Story Mode keeps sandbox tools away from the queen's chamber so the colony can't simply be rescued, or
+            // ruined, by hand. Chosen from the main menu.
+            is_story_mode: false,
+            queen_protection_radius: 8,
+            // Workers head home at night and sleep until morning. Turn this off to keep the colony busy around the clock.
+            is_sleeping_at_night: true,
+            // Sleeping ants get hungry at half the usual pace.
+            asleep_hunger_multiplier: 0.5,
+            // Flourishes which move, flash, or shake things, and the camera moving on its own, are replaced with gentle
+            // fades. For players sensitive to motion or flashing lights.
+            is_reduced_motion: false,
+            // A cursor, moved with the arrow keys, acts on the grid in place of a pointer. Tab moves between menus.
+            is_keyboard_navigation: false,
+            // Infections spread between ants and must be groomed away, or waited out in isolation, before they kill.
+            is_disease_enabled: true,
+            // In Story Mode, idle ants on the surface come over to see what the player is pointing at.
+            is_cursor_curiosity_enabled: true,
+            // Rival ants raid the nest, at night, for stored food and brood. Soldiers are the colony's best defense.
+            is_night_raids_enabled: true,
+            // Foragers search the crater in Levy flights, like real ants looking for scattered food.
+            exploration_strategy: ExplorationStrategy::LevyFlight,
             probabilities: Probabilities {
                 random_drop: 0.003,
                 random_turn: 0.005,
@@ -83,6 +191,37 @@ impl Default for Settings {
                 // TODO: keep playing with this value. lower chance = more cramped nest, but less sand to manage.
                 expand_nest: 0.2,
                 sleep_emote: 0.001,
+                // Rare enough that a busy nest isn't a wall of chatter.
+                speech_bubble: 0.0002,
+                // Roughly one new seed every ten seconds so the crater restocks without being carpeted in seeds.
+                crater_seed_spawn: 0.01,
+                // Each row is checked every few seconds. An unsupported ceiling usually lasts several minutes.
+                cave_in: 0.01,
+                // An infected ant brushing past nestmates infects a few of them over the course of a busy day.
+                infection_spread: 0.0005,
+                // Corpses are carried off quickly so most ants handling them come away unharmed.
+                corpse_infection: 0.0002,
+                // A little waste near the brood is harmless. A neglected pile of it makes nursing risky.
+                waste_infection: 0.00005,
+                // Roughly every fourth meal leaves scraps behind.
+                meal_waste: 0.25,
+                // Ants drift toward the cursor over several seconds rather than making a beeline for it.
+                cursor_curiosity: 0.05,
+                // About one night in five.
+                night_raid: 0.2,
+                rain: 0.25,
+            },
+            energy: EnergySettings {
+                // A rested ant can haul, or dig, for a few minutes before it has to slow down.
+                max: 100.0,
+                // Walking costs about as much as is recharged between turns so wandering ants never tire.
+                recharge: 0.25,
+                resting_recharge_multiplier: 8.0,
+                walk_cost: 1.0,
+                carry_cost: 0.5,
+                dig_cost: 1.5,
+                // Each turn of hard work adds a little hunger on top of the steady pace it builds at.
+                hunger_per_energy: 0.5,
             },
         }
     }
@@ -91,6 +230,8 @@ impl Default for Settings {
 pub fn register_settings(app_type_registry: ResMut<AppTypeRegistry>) {
     app_type_registry.write().register::<Settings>();
     app_type_registry.write().register::<Probabilities>();
+    app_type_registry.write().register::<EnergySettings>();
+    app_type_registry.write().register::<ExplorationStrategy>();
 }
 
 pub fn initialize_settings_resources(mut commands: Commands) {
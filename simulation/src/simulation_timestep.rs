@@ -2,7 +2,7 @@
 use bevy::prelude::*;
 use bevy::utils::Duration;
 
-use super::SimulationUpdate;
+use super::{broadcast::is_waiting_for_host, SimulationUpdate};
 
 /// The amount of time that must pass before the fixed timestep schedule is run again.
 #[derive(Resource, Debug)]
@@ -32,6 +32,11 @@ impl SimulationTime {
         self.accumulated
     }
 
+    /// Discards any accumulated time without running the schedule for it.
+    pub fn discard_accumulated(&mut self) {
+        self.accumulated = Duration::ZERO;
+    }
+
     /// Expends one `period` of accumulated time.
     ///
     /// [`Err(SimulationUpdateError`)] will be returned if there is
@@ -76,8 +81,14 @@ pub fn run_simulation_update_schedule(world: &mut World) {
 
     // Run the schedule until we run out of accumulated time
     let _ = world.try_schedule_scope(SimulationUpdate, |world, schedule| {
-        while world.resource_mut::<SimulationTime>().expend().is_ok() {
+        while !is_waiting_for_host(world) && world.resource_mut::<SimulationTime>().expend().is_ok()
+        {
             schedule.run(world);
         }
     });
+
+    // Spectators follow the host rather than the clock so time spent waiting on the host isn't made up for afterward.
+    if is_waiting_for_host(world) {
+        world.resource_mut::<SimulationTime>().discard_accumulated();
+    }
 }
@@ -0,0 +1,288 @@
+use bevy::{ecs::system::RunSystemOnce, prelude::*, utils::Instant};
+use std::fmt;
+
+use crate::{
+    app_state::AppState,
+    benchmark::{get_device_info, Benchmarking},
+    common::{
+        entity_budget::{update_entity_budget, EntityBudget},
+        grid::Grid,
+        position::Position,
+    },
+    crater_simulation::crater::AtCrater,
+    headless::{create_headless_app, start_headless_story},
+    nest_simulation::{
+        ant::{Ant, AntInventory, Dead},
+        element::Element,
+        nest::AtNest,
+    },
+    settings::Settings,
+    story_time::{DEFAULT_TICKS_PER_SECOND, SECONDS_PER_DAY, SECONDS_PER_HOUR},
+    SimulationUpdate,
+};
+
+/// Two in-game weeks is long enough for the slow degradations players report after months of real-time play to show.
+pub const DEFAULT_SOAK_TEST_DAYS: usize = 14;
+// Soak tests use their own seed, separate from the benchmark's, so the two colonies can drift independently.
+const SOAK_TEST_SEED: u64 = 4_242;
+// Sample hourly so the population curve is smooth without checking invariants (which walks every grid) too often.
+const SAMPLE_TICKS: usize = (SECONDS_PER_HOUR * DEFAULT_TICKS_PER_SECOND) as usize;
+const TICKS_PER_DAY: usize = (SECONDS_PER_DAY * DEFAULT_TICKS_PER_SECOND) as usize;
+// Something which breaks usually breaks everywhere. A handful of examples is enough to diagnose it.
+const MAX_LISTED_VIOLATIONS: usize = 10;
+
+/// How the colony, and the simulation, were doing at one point during a soak test.
+#[derive(Clone, Debug)]
+pub struct SoakTestSample {
+    pub elapsed_ticks: usize,
+    pub alive_ant_count: usize,
+    pub entity_count: usize,
+    pub estimated_megabytes: f32,
+    /// Measured over the ticks since the previous sample.
+    pub ticks_per_second: f32,
+    pub violation_count: usize,
+}
+
+impl SoakTestSample {
+    pub fn elapsed_days(&self) -> f32 {
+        self.elapsed_ticks as f32 / TICKS_PER_DAY as f32
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SoakTestReport {
+    pub days: usize,
+    pub samples: Vec<SoakTestSample>,
+    /// The first few broken invariants found, labeled with when they were found.
+    pub violations: Vec<String>,
+    pub violation_count: usize,
+    /// The colony died out before the soak test finished.
+    pub is_story_over: bool,
+    pub device_info: String,
+}
+
+impl SoakTestReport {
+    /// How much faster, or slower, the simulation ran at the end compared to the start. Negative values are a slowdown.
+    pub fn ticks_per_second_drift(&self) -> Option<f32> {
+        let first = self.samples.first()?.ticks_per_second;
+        let last = self.samples.last()?.ticks_per_second;
+
+        Some((last - first) / first)
+    }
+}
+
+impl fmt::Display for SoakTestReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let simulated_days = self
+            .samples
+            .last()
+            .map_or(0.0, |sample| sample.elapsed_days());
+
+        writeln!(f, "Symbiants Soak Test")?;
+        writeln!(f, "Simulated {:.1} of {} days", simulated_days, self.days)?;
+
+        if self.is_story_over {
+            writeln!(f, "The colony died out, ending the story early.")?;
+        }
+
+        if let (Some(first), Some(last), Some(drift)) = (
+            self.samples.first(),
+            self.samples.last(),
+            self.ticks_per_second_drift(),
+        ) {
+            writeln!(
+                f,
+                "Ticks/sec: {:.0} -> {:.0} ({:+.1}%)",
+                first.ticks_per_second,
+                last.ticks_per_second,
+                drift * 100.0
+            )?;
+        }
+
+        let peak_megabytes = self
+            .samples
+            .iter()
+            .map(|sample| sample.estimated_megabytes)
+            .fold(0.0, f32::max);
+        writeln!(f, "Peak memory: ~{:.1}MB", peak_megabytes)?;
+
+        // The full curve is sampled hourly, but daily is enough to read at a glance.
+        writeln!(f, "Population:")?;
+        for sample in self
+            .samples
+            .iter()
+            .filter(|sample| sample.elapsed_ticks % TICKS_PER_DAY == 0)
+        {
+            writeln!(
+                f,
+                "  Day {:>3}: {:>5} ants, {:>7} entities, ~{:.1}MB, {:.0} ticks/sec",
+                sample.elapsed_days() as usize,
+                sample.alive_ant_count,
+                sample.entity_count,
+                sample.estimated_megabytes,
+                sample.ticks_per_second
+            )?;
+        }
+
+        writeln!(f, "Invariant violations: {}", self.violation_count)?;
+        for violation in self.violations.iter() {
+            writeln!(f, "  {}", violation)?;
+        }
+
+        write!(f, "Device: {}", self.device_info)
+    }
+}
+
+/// Simulate a new colony, unattended, for `days` of in-game time. The colony is sampled hourly: its population and
+/// memory are recorded, and the world is checked for states which should never occur. `on_sample` is called with each
+/// sample so long runs can report their progress. Stops early if the colony dies out.
+/// Returns None if the story failed to start.
+pub fn run_soak_test(
+    days: usize,
+    mut on_sample: impl FnMut(&SoakTestSample),
+) -> Option<SoakTestReport> {
+    let mut app = create_headless_app(Settings::default(), SOAK_TEST_SEED);
+    // Soak test colonies are throwaway, just like benchmark colonies, so there's nothing to save.
+    app.init_resource::<Benchmarking>();
+
+    if !start_headless_story(&mut app) {
+        warn!("Soak test story failed to start.");
+        return None;
+    }
+
+    let total_ticks = days * TICKS_PER_DAY;
+    let mut samples = vec![];
+    let mut violations = vec![];
+    let mut violation_count = 0;
+    let mut is_story_over = false;
+    let mut elapsed_ticks = 0;
+
+    while elapsed_ticks < total_ticks && !is_story_over {
+        let sample_ticks = SAMPLE_TICKS.min(total_ticks - elapsed_ticks);
+
+        let start = Instant::now();
+        for _ in 0..sample_ticks {
+            app.world.run_schedule(SimulationUpdate);
+        }
+        let elapsed_seconds = start.elapsed().as_secs_f32();
+        elapsed_ticks += sample_ticks;
+
+        // The story ending is only noticed, not acted upon, because the app's state isn't advanced between ticks.
+        is_story_over = app.world.resource::<NextState<AppState>>().0 == Some(AppState::EndStory);
+
+        let sample_violations = check_invariants(&mut app.world);
+        violation_count += sample_violations.len();
+        for violation in sample_violations {
+            if violations.len() < MAX_LISTED_VIOLATIONS {
+                violations.push(format!(
+                    "Day {:.1}: {}",
+                    elapsed_ticks as f32 / TICKS_PER_DAY as f32,
+                    violation
+                ));
+            }
+        }
+
+        let alive_ant_count = app
+            .world
+            .query_filtered::<(), (With<Ant>, Without<Dead>)>()
+            .iter(&app.world)
+            .count();
+
+        app.world.run_system_once(update_entity_budget);
+        let entity_budget = app.world.resource::<EntityBudget>();
+
+        let sample = SoakTestSample {
+            elapsed_ticks,
+            alive_ant_count,
+            entity_count: entity_budget.entity_count(),
+            estimated_megabytes: entity_budget.estimated_megabytes(),
+            ticks_per_second: sample_ticks as f32 / elapsed_seconds.max(f32::EPSILON),
+            violation_count,
+        };
+
+        on_sample(&sample);
+        samples.push(sample);
+    }
+
+    Some(SoakTestReport {
+        days,
+        samples,
+        violations,
+        violation_count,
+        is_story_over,
+        device_info: get_device_info(),
+    })
+}
+
+/// Look for states the simulation should never be in. These are the sort of slow corruptions which only show up
+/// after a colony has been running for a long time.
+///     1) Every tile of every grid refers to an element which is where the grid thinks it is.
+///     2) Everything an ant carries is an element.
+///     3) Every ant is within the bounds of the zone it's in.
+fn check_invariants(world: &mut World) -> Vec<String> {
+    let mut violations = vec![];
+
+    let mut grid_query = world.query::<(&Grid, Option<&AtNest>)>();
+    let mut element_query = world.query::<(&Element, &Position)>();
+
+    for (grid, at_nest) in grid_query.iter(world) {
+        let zone_name = if at_nest.is_some() { "Nest" } else { "Crater" };
+
+        for (y, row) in grid.elements().iter().enumerate() {
+            for (x, element_entity) in row.iter().enumerate() {
+                let grid_position = Position::new(x as isize, y as isize);
+
+                match element_query.get(world, *element_entity) {
+                    Ok((_, position)) if *position != grid_position => violations.push(format!(
+                        "{} grid has an element at {:?} which thinks it's at {:?}.",
+                        zone_name, grid_position, position
+                    )),
+                    Ok(_) => {}
+                    Err(_) => violations.push(format!(
+                        "{} grid refers to a missing element at {:?}.",
+                        zone_name, grid_position
+                    )),
+                }
+            }
+        }
+    }
+
+    let mut inventory_query = world.query::<(Entity, &AntInventory)>();
+    let mut inventory_element_query = world.query::<&Element>();
+
+    for (ant_entity, inventory) in inventory_query.iter(world) {
+        for &item_entity in inventory.items() {
+            if inventory_element_query.get(world, item_entity).is_err() {
+                violations.push(format!(
+                    "Ant {:?} is carrying {:?} which isn't an element.",
+                    ant_entity, item_entity
+                ));
+            }
+        }
+    }
+
+    violations.extend(get_out_of_bounds_ants::<AtNest>(world, "Nest"));
+    violations.extend(get_out_of_bounds_ants::<AtCrater>(world, "Crater"));
+
+    violations
+}
+
+fn get_out_of_bounds_ants<Z: Component>(world: &mut World, zone_name: &str) -> Vec<String> {
+    let mut grid_query = world.query_filtered::<&Grid, With<Z>>();
+    let mut ants_query = world.query_filtered::<(Entity, &Position), (With<Ant>, With<Z>)>();
+
+    let Ok(grid) = grid_query.get_single(world) else {
+        return vec![];
+    };
+
+    ants_query
+        .iter(world)
+        .filter(|(_, position)| !grid.is_within_bounds(position))
+        .map(|(ant_entity, position)| {
+            format!(
+                "Ant {:?} is outside of the {} at {:?}.",
+                ant_entity, zone_name, position
+            )
+        })
+        .collect()
+}
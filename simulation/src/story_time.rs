@@ -1,22 +1,29 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashSet};
 
 use chrono::Datelike;
-use chrono::{DateTime, LocalResult, NaiveDate, TimeZone, Timelike, Utc};
-use std::time::Duration;
+use chrono::{DateTime, LocalResult, TimeZone, Timelike, Utc};
+use chrono_tz::{Tz, TZ_VARIANTS};
+use std::{any::TypeId, time::Duration};
 
 use super::simulation_timestep::SimulationTime;
+use crate::{common::Zone, save::ReflectPersist};
 
 pub const DEFAULT_TICKS_PER_SECOND: isize = 10;
 pub const MAX_USER_TICKS_PER_SECOND: isize = 1_500;
 pub const MAX_SYSTEM_TICKS_PER_SECOND: isize = 50_000;
 pub const SECONDS_PER_HOUR: isize = 3_600;
 pub const SECONDS_PER_DAY: isize = 86_400;
+pub const DAYS_PER_SEASON: isize = 7;
+// Foragers still venture out at night, but rarely find anything in the dark.
+const NIGHT_FORAGING_EFFICIENCY: f32 = 0.1;
+// How much worse foraging is in the heat of midday compared to the cool of morning and evening.
+const MIDDAY_HEAT_FORAGING_PENALTY: f32 = 0.6;
 
 // NOTE: `bevy_reflect` doesn't support DateTime<Utc> without manually implement Reflect (which is hard)
 // So, use a timestamp instead and convert to DateTime<Utc> when needed.
 // Also, Time/Instant/Duration aren't serializable.
 #[derive(Resource, Clone, Reflect, Default)]
-#[reflect(Resource)]
+#[reflect(Resource, Persist)]
 pub struct StoryRealWorldTime(pub i64);
 
 impl StoryRealWorldTime {
@@ -38,6 +45,7 @@ pub struct TimeInfo {
     days: isize,
     hours: isize,
     minutes: isize,
+    season: Season,
 }
 
 impl TimeInfo {
@@ -53,20 +61,76 @@ impl TimeInfo {
         self.minutes
     }
 
+    pub fn season(&self) -> Season {
+        self.season
+    }
+
     pub fn get_decimal_hours(&self) -> f32 {
         self.hours() as f32 + self.minutes() as f32 / 60.0
     }
 }
 
+#[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Hash)]
+pub enum Season {
+    #[default]
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Season {
+    fn from_index(season_index: isize) -> Season {
+        match season_index.rem_euclid(4) {
+            0 => Season::Spring,
+            1 => Season::Summer,
+            2 => Season::Autumn,
+            _ => Season::Winter,
+        }
+    }
+}
+
+/// When the sun rises and sets, in decimal hours, when not following the real sun. Days are longest in Summer and
+/// shortest in Winter. Midday is always at 2PM so the colony's routine only stretches, or shrinks, around it.
+///
+/// ```
+/// use simulation::story_time::{get_seasonal_sunrise_sunset_decimal_hours, Season};
+///
+/// assert_eq!(get_seasonal_sunrise_sunset_decimal_hours(Season::Spring), (8.0, 20.0));
+/// assert_eq!(get_seasonal_sunrise_sunset_decimal_hours(Season::Autumn), (8.0, 20.0));
+///
+/// let (summer_sunrise, summer_sunset) = get_seasonal_sunrise_sunset_decimal_hours(Season::Summer);
+/// let (winter_sunrise, winter_sunset) = get_seasonal_sunrise_sunset_decimal_hours(Season::Winter);
+/// assert!(summer_sunset - summer_sunrise > winter_sunset - winter_sunrise);
+/// ```
+pub fn get_seasonal_sunrise_sunset_decimal_hours(season: Season) -> (f32, f32) {
+    match season {
+        Season::Spring | Season::Autumn => (8.0, 20.0),
+        Season::Summer => (6.5, 21.5),
+        Season::Winter => (9.5, 18.5),
+    }
+}
+
 #[derive(Resource, Clone, Reflect)]
-#[reflect(Resource)]
+// Reflecting Default lets saves written before `days_per_season` existed be loaded.
+#[reflect(Resource, Default, Persist)]
 pub struct StoryTime {
     elapsed_ticks: isize,
     pub is_real_time: bool,
     pub is_real_sun: bool,
+    /// How many days each season lasts when not following real time.
+    pub days_per_season: isize,
     pub latitude: f32,
     pub longitude: f32,
-    real_time_offset: isize,
+    /// The IANA name of the time zone real time follows, e.g. "America/Los_Angeles".
+    /// None means the device's time zone is detected each time the app starts so the clock follows players who travel.
+    time_zone_name: Option<String>,
+    #[reflect(ignore)]
+    resolved_time_zone: Option<Tz>,
+    /// When the story started, as a UTC timestamp in milliseconds. Real time is derived from this instant, rather than
+    /// from what the clock read when the story started, so daylight saving transitions are reflected correctly.
+    /// Zero until the story is set up, including for saves written before this was recorded, see `setup_story_time`.
+    real_time_start: i64,
     demo_time_offset: isize,
 }
 
@@ -76,11 +140,13 @@ impl Default for StoryTime {
             elapsed_ticks: 0,
             is_real_time: false,
             is_real_sun: false,
+            days_per_season: DAYS_PER_SEASON,
             // Might as well default to San Francisco
             latitude: 37.0,
             longitude: -122.0,
-            // Real time wants to know how many seconds into the real world day have passed when the story started.
-            real_time_offset: chrono::Local::now().time().num_seconds_from_midnight() as isize,
+            time_zone_name: None,
+            resolved_time_zone: None,
+            real_time_start: 0,
             // Offset by an assumption that, for Sandbox Mode, the story starts at 8AM the first day not at Midnight.
             demo_time_offset: 8 * SECONDS_PER_HOUR,
         }
@@ -92,15 +158,80 @@ impl StoryTime {
         self.elapsed_ticks
     }
 
-    pub fn as_time_info(&self) -> TimeInfo {
-        let start_time_offset = if self.is_real_time {
-            self.real_time_offset
+    pub fn time_zone_name(&self) -> Option<&str> {
+        self.time_zone_name.as_deref()
+    }
+
+    /// Follow the given IANA time zone, or None to follow the device's time zone.
+    pub fn set_time_zone_name(&mut self, time_zone_name: Option<String>) {
+        self.resolved_time_zone = Some(resolve_time_zone(time_zone_name.as_deref()));
+        self.time_zone_name = time_zone_name;
+    }
+
+    /// The time zone real time is shown in.
+    pub fn time_zone(&self) -> Tz {
+        self.resolved_time_zone
+            .unwrap_or_else(|| resolve_time_zone(self.time_zone_name.as_deref()))
+    }
+
+    /// When the story started, as a UTC timestamp in milliseconds. Stories which haven't recorded when they started are
+    /// assumed to have been running, in real time, up until now.
+    fn get_real_time_start(&self) -> i64 {
+        if self.real_time_start != 0 {
+            return self.real_time_start;
+        }
+
+        Utc::now().timestamp_millis() - ticks_to_milliseconds(self.elapsed_ticks)
+    }
+
+    /// The real-world date and time at which a given tick of the story occurs, in the story's time zone.
+    fn tick_as_real_datetime(&self, tick: isize) -> DateTime<Tz> {
+        let real_time_start = Utc
+            .timestamp_millis_opt(self.get_real_time_start())
+            .single()
+            .unwrap_or_else(Utc::now);
+
+        (real_time_start + chrono::Duration::milliseconds(ticks_to_milliseconds(tick)))
+            .with_timezone(&self.time_zone())
+    }
+
+    fn get_seconds_into_day(&self) -> isize {
+        if self.is_real_time {
+            self.tick_as_real_datetime(self.elapsed_ticks)
+                .num_seconds_from_midnight() as isize
         } else {
-            self.demo_time_offset
-        };
+            (self.elapsed_ticks / DEFAULT_TICKS_PER_SECOND + self.demo_time_offset)
+                % SECONDS_PER_DAY
+        }
+    }
+
+    pub fn as_time_info(&self) -> TimeInfo {
+        self.tick_as_time_info(self.elapsed_ticks)
+    }
+
+    /// Describe when a given tick of the story occurred, e.g. for showing when past events happened.
+    /// Real time reads the wall clock of the story's time zone so the hour jumps, as expected, when daylight saving
+    /// starts or ends.
+    pub fn tick_as_time_info(&self, tick: isize) -> TimeInfo {
+        if self.is_real_time {
+            let start_date = self.tick_as_real_datetime(0).date_naive();
+            let datetime = self.tick_as_real_datetime(tick);
+
+            // Meteorological seasons - Spring begins in March. Flipped for the southern hemisphere.
+            let month_index = datetime.month0() as isize;
+            let season_index = ((month_index + 10) % 12) / 3;
+            let hemisphere_offset = if self.latitude < 0.0 { 2 } else { 0 };
+
+            return TimeInfo {
+                days: (datetime.date_naive() - start_date).num_days() as isize,
+                hours: datetime.hour() as isize,
+                minutes: datetime.minute() as isize,
+                season: Season::from_index(season_index + hemisphere_offset),
+            };
+        }
 
         let seconds_total =
-            self.elapsed_ticks as f32 / DEFAULT_TICKS_PER_SECOND as f32 + start_time_offset as f32;
+            tick as f32 / DEFAULT_TICKS_PER_SECOND as f32 + self.demo_time_offset as f32;
         let days = (seconds_total / SECONDS_PER_DAY as f32).floor() as isize;
 
         // Calculate hours and minutes
@@ -112,9 +243,24 @@ impl StoryTime {
             days,
             hours,
             minutes,
+            season: Season::from_index(days / self.days_per_season.max(1)),
         }
     }
 
+    /// When following real time, the season is taken from the real-world calendar (flipped for the southern hemisphere).
+    /// Otherwise, the story starts in Spring and advances a season every `days_per_season` days.
+    pub fn season(&self) -> Season {
+        self.as_time_info().season()
+    }
+
+    /// How many ticks need to elapse before the clock next reads the start of the given hour.
+    pub fn get_ticks_until_hour(&self, hour: isize) -> isize {
+        let seconds_until_hour =
+            (hour * SECONDS_PER_HOUR - self.get_seconds_into_day()).rem_euclid(SECONDS_PER_DAY);
+
+        seconds_until_hour * DEFAULT_TICKS_PER_SECOND
+    }
+
     pub fn is_within_schedule_window(&self) -> bool {
         let time_info = self.as_time_info();
 
@@ -134,22 +280,41 @@ impl StoryTime {
         time_info.hours < (sunrise - 2.0) as isize || time_info.hours >= (sunset + 2.0) as isize
     }
 
-    // Use local because trying to reflect user's sunrise/sunset time not Greenwich's.
+    /// How successful foragers are at the current time of day, from 0.0 to 1.0.
+    /// Foraging is best in the morning and evening, poor in the heat of midday, and worst at night.
+    /// NOTE: There's no model of temperature, so the sun's position in the sky stands in for how hot it is.
+    pub fn get_foraging_efficiency(&self) -> f32 {
+        let (sunrise, sunset) = self.get_sunrise_sunset_decimal_hours();
+        let hours = self.as_time_info().get_decimal_hours();
+
+        if hours <= sunrise || hours >= sunset {
+            return NIGHT_FORAGING_EFFICIENCY;
+        }
+
+        // How far through the day it is, from 0.0 at sunrise to 1.0 at sunset.
+        let day_progress = (hours - sunrise) / (sunset - sunrise);
+        let daylight = (std::f32::consts::PI * day_progress).sin().sqrt();
+        let midday_heat = (-((day_progress - 0.5) / 0.15).powi(2)).exp();
+
+        (daylight * (1.0 - MIDDAY_HEAT_FORAGING_PENALTY * midday_heat))
+            .max(NIGHT_FORAGING_EFFICIENCY)
+    }
+
+    // Use the story's time zone because trying to reflect user's sunrise/sunset time not Greenwich's.
+    // The date is the story's, rather than today's, so the sun renders correctly when fast-forwarding.
     pub fn get_sunrise_sunset_decimal_hours(&self) -> (f32, f32) {
         if !self.is_real_time || !self.is_real_sun {
-            return (8.0, 20.0);
+            return get_seasonal_sunrise_sunset_decimal_hours(self.season());
         }
 
-        // TODO: Base this off of StoryTime's elapsed_ticks + time offset rather than current day so that sun renders correctly when fast-forwarding.
-        let today = chrono::Local::now().date_naive();
-
-        let date = NaiveDate::from_ymd_opt(today.year(), today.month(), today.day()).unwrap();
+        let time_zone = self.time_zone();
+        let date = self.tick_as_real_datetime(self.elapsed_ticks).date_naive();
 
         let sun_times =
             sun_times::sun_times(date, self.latitude as f64, self.longitude as f64, 0.0).unwrap();
 
-        let sunrise: DateTime<chrono::Local> = DateTime::from(sun_times.0);
-        let sunset: DateTime<chrono::Local> = DateTime::from(sun_times.1);
+        let sunrise = sun_times.0.with_timezone(&time_zone);
+        let sunset = sun_times.1.with_timezone(&time_zone);
 
         let sunrise_decimal_hours =
             sunrise.time().hour() as f32 + sunrise.time().minute() as f32 / 60.0;
@@ -161,6 +326,44 @@ impl StoryTime {
     }
 }
 
+/// The IANA name of the device's time zone, if it can be determined.
+pub fn get_detected_time_zone_name() -> Option<String> {
+    iana_time_zone::get_timezone().ok()
+}
+
+/// Every IANA time zone which can be followed, for choosing between them.
+pub fn get_time_zone_names() -> impl Iterator<Item = &'static str> {
+    TZ_VARIANTS.iter().map(|time_zone| time_zone.name())
+}
+
+/// Look up a time zone by its IANA name, detecting the device's time zone if there's no name.
+/// Falls back to UTC, rather than failing, because the clock is still usable even if it's in the wrong time zone.
+fn resolve_time_zone(time_zone_name: Option<&str>) -> Tz {
+    let time_zone_name = time_zone_name
+        .map(str::to_string)
+        .or_else(get_detected_time_zone_name);
+
+    match time_zone_name.as_deref().map(str::parse::<Tz>) {
+        Some(Ok(time_zone)) => time_zone,
+        Some(Err(_)) => {
+            warn!("Unrecognized time zone {:?}, using UTC.", time_zone_name);
+            Tz::UTC
+        }
+        None => Tz::UTC,
+    }
+}
+
+/// Sandbox tool for moving the in-game clock forward. Time isn't skipped, instead, it's fast-forwarded through
+/// so the colony lives through the time which passed. Only supported when not following real time.
+#[derive(Event, Clone, Copy, Debug)]
+pub enum TimeTravelEvent {
+    /// Advance until the clock next reads the start of the given hour of the day.
+    SetHourOfDay(isize),
+    AdvanceHours(isize),
+    /// Run an exact number of ticks as quickly as possible. Useful for reproducing performance issues.
+    AdvanceTicks(isize),
+}
+
 /// Store TicksPerSecond separately from SimulationTime because when we're fast forwarding time we won't update TicksPerSecond.
 /// This enables resetting back to a user-defined ticks-per-second (adjusted via UI) rather than the default ticks-per-second.
 #[derive(Resource)]
@@ -176,6 +379,8 @@ impl Default for TicksPerSecond {
 pub struct FastForwardingStateInfo {
     pub initial_pending_ticks: isize,
     pub pending_ticks: isize,
+    // Fast-forwarding either catches up on time missed while the app was closed, or is the result of time travel.
+    pub is_time_travelling: bool,
 }
 
 #[derive(States, Default, Hash, Clone, Copy, Eq, PartialEq, Debug)]
@@ -187,9 +392,37 @@ pub enum StoryPlaybackState {
     FastForwarding,
 }
 
+/// Zones can be paused independently of the story, e.g. freezing the crater while studying the nest.
+/// Paused zones don't tick at all, which also saves work on weak devices when only one zone matters.
+/// This is a viewing preference rather than part of the story so it isn't saved.
+#[derive(Resource, Default, Debug)]
+pub struct ZonePlayback {
+    paused_zones: HashSet<TypeId>,
+}
+
+impl ZonePlayback {
+    pub fn is_paused<Z: Zone>(&self) -> bool {
+        self.paused_zones.contains(&TypeId::of::<Z>())
+    }
+
+    pub fn set_paused<Z: Zone>(&mut self, is_paused: bool) {
+        if is_paused {
+            self.paused_zones.insert(TypeId::of::<Z>());
+        } else {
+            self.paused_zones.remove(&TypeId::of::<Z>());
+        }
+    }
+}
+
+/// Run condition for systems which simulate a single zone.
+pub fn is_zone_playing<Z: Zone>(zone_playback: Option<Res<ZonePlayback>>) -> bool {
+    zone_playback.map_or(true, |zone_playback| !zone_playback.is_paused::<Z>())
+}
+
 pub fn register_story_time(app_type_registry: ResMut<AppTypeRegistry>) {
     app_type_registry.write().register::<StoryRealWorldTime>();
     app_type_registry.write().register::<StoryTime>();
+    app_type_registry.write().register::<Option<String>>();
 }
 
 pub fn initialize_story_time_resources(mut commands: Commands) {
@@ -197,6 +430,7 @@ pub fn initialize_story_time_resources(mut commands: Commands) {
     commands.init_resource::<StoryTime>();
     commands.init_resource::<FastForwardingStateInfo>();
     commands.init_resource::<TicksPerSecond>();
+    commands.init_resource::<ZonePlayback>();
     commands.insert_resource(SimulationTime::new_from_secs(
         1.0 / DEFAULT_TICKS_PER_SECOND as f32,
     ));
@@ -207,6 +441,7 @@ pub fn remove_story_time_resources(mut commands: Commands) {
     commands.remove_resource::<StoryTime>();
     commands.remove_resource::<FastForwardingStateInfo>();
     commands.remove_resource::<TicksPerSecond>();
+    commands.remove_resource::<ZonePlayback>();
     commands.remove_resource::<SimulationTime>();
 }
 
@@ -221,6 +456,24 @@ pub fn setup_story_time(
     mut story_elapsed_ticks: ResMut<StoryTime>,
     ticks_per_second: Res<TicksPerSecond>,
 ) {
+    // New stories start now. Saves written before the start was recorded are backfilled from how long they'd run when
+    // they were last saved, rather than from now, so time missed while the app was closed isn't counted as time run.
+    if story_elapsed_ticks.real_time_start == 0 {
+        let last_real_world_time = if story_real_world_time.0 == 0 {
+            Utc::now().timestamp_millis()
+        } else {
+            story_real_world_time.0
+        };
+
+        story_elapsed_ticks.real_time_start =
+            last_real_world_time - ticks_to_milliseconds(story_elapsed_ticks.elapsed_ticks);
+    }
+
+    // The resolved time zone isn't saved so resolve it as the story is set up. Stories which follow the device's time
+    // zone pick up wherever the player is now, in case they've travelled since the app last ran.
+    let time_zone_name = story_elapsed_ticks.time_zone_name.clone();
+    story_elapsed_ticks.set_time_zone_name(time_zone_name);
+
     // Setup story_real_world_time here, rather than as a Default, so that delta_seconds doesn't grow while idling in main menu
     if story_real_world_time.0 == 0 {
         story_real_world_time.0 = Utc::now().timestamp_millis();
@@ -232,7 +485,8 @@ pub fn setup_story_time(
         let seconds_past_max = delta_seconds as isize - SECONDS_PER_DAY;
 
         if seconds_past_max > 0 {
-            // Increment elapsed ticks by the amount not being simulated to keep game clock synced with real-world clock
+            // Increment elapsed ticks by the amount not being simulated to keep game clock synced with real-world clock.
+            // Real time is measured from a UTC instant so skipping ahead across a daylight saving transition is safe.
             if story_elapsed_ticks.is_real_time {
                 let missed_ticks = seconds_past_max * ticks_per_second.0;
                 story_elapsed_ticks.elapsed_ticks += missed_ticks;
@@ -266,6 +520,7 @@ pub fn set_rate_of_time(
 
             next_story_playback_state.set(StoryPlaybackState::Playing);
             fast_forward_state_info.initial_pending_ticks = 0;
+            fast_forward_state_info.is_time_travelling = false;
         } else {
             let accumulated_time = simulation_time.accumulated();
 
@@ -293,6 +548,49 @@ pub fn set_rate_of_time(
     }
 }
 
+/// Fast-forward through the ticks time travel covers using the same machinery as catching up on time missed while closed.
+/// StoryRealWorldTime is intentionally left alone. It continues to track the real world so, if the app is closed
+/// while fast-forwarding, the time travelled isn't mistaken for time missed and caught up on a second time.
+pub fn process_time_travel_event(
+    mut time_travel_events: EventReader<TimeTravelEvent>,
+    story_time: Res<StoryTime>,
+    mut simulation_time: ResMut<SimulationTime>,
+    mut fast_forward_state_info: ResMut<FastForwardingStateInfo>,
+    mut next_story_playback_state: ResMut<NextState<StoryPlaybackState>>,
+) {
+    for time_travel_event in time_travel_events.read() {
+        if story_time.is_real_time {
+            warn!("Time travel isn't supported while following real time.");
+            continue;
+        }
+
+        let ticks = match *time_travel_event {
+            // Travel relative to the end of any fast-forward already underway.
+            TimeTravelEvent::SetHourOfDay(hour) => {
+                let mut future_story_time = story_time.clone();
+                future_story_time.elapsed_ticks += fast_forward_state_info.pending_ticks;
+
+                future_story_time.get_ticks_until_hour(hour)
+            }
+            TimeTravelEvent::AdvanceHours(hours) => {
+                hours * SECONDS_PER_HOUR * DEFAULT_TICKS_PER_SECOND
+            }
+            TimeTravelEvent::AdvanceTicks(ticks) => ticks,
+        };
+
+        if ticks <= 0 {
+            continue;
+        }
+
+        fast_forward_state_info.pending_ticks += ticks;
+        fast_forward_state_info.initial_pending_ticks += ticks;
+        fast_forward_state_info.is_time_travelling = true;
+        simulation_time.period =
+            Duration::from_secs_f32(1.0 / (MAX_SYSTEM_TICKS_PER_SECOND as f32));
+        next_story_playback_state.set(StoryPlaybackState::FastForwarding);
+    }
+}
+
 // TODO: Consider also running this inside FixedUpdate to have it remain accurate under heavy sim load.
 // Track real-world time to be able to derive how much time elapsed while app was closed.
 // Keep this updated, rather than capture JIT, because running Bevy systems JIT as app closing isn't viable.
@@ -321,8 +619,175 @@ pub fn update_time_scale(
     simulation_time.period = Duration::from_secs_f32(1.0 / (ticks_per_second.0 as f32));
 }
 
+fn ticks_to_milliseconds(ticks: isize) -> i64 {
+    ticks as i64 * 1_000 / DEFAULT_TICKS_PER_SECOND as i64
+}
+
 fn decimal_hours_to_hours_minutes(decimal_hours: f32) -> (f32, f32) {
     let hours = decimal_hours.trunc();
     let minutes = (decimal_hours.fract() * 60.0).round();
     (hours, minutes)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+
+    fn get_timestamp_millis(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> i64 {
+        Utc.with_ymd_and_hms(year, month, day, hour, minute, 0)
+            .unwrap()
+            .timestamp_millis()
+    }
+
+    fn create_real_time_story(real_time_start: i64, time_zone_name: &str) -> StoryTime {
+        let mut story_time = StoryTime {
+            is_real_time: true,
+            real_time_start,
+            ..default()
+        };
+        story_time.set_time_zone_name(Some(time_zone_name.to_string()));
+
+        story_time
+    }
+
+    fn setup(story_time: StoryTime, story_real_world_time: i64) -> World {
+        let mut world = World::new();
+        world.insert_resource(story_time);
+        world.insert_resource(StoryRealWorldTime(story_real_world_time));
+        world.init_resource::<SimulationTime>();
+        world.init_resource::<TicksPerSecond>();
+        world.init_resource::<NextState<StoryPlaybackState>>();
+
+        world
+    }
+
+    #[test]
+    fn ticks_to_milliseconds_converts_at_default_rate() {
+        assert_eq!(ticks_to_milliseconds(0), 0);
+        assert_eq!(ticks_to_milliseconds(DEFAULT_TICKS_PER_SECOND), 1_000);
+        assert_eq!(
+            ticks_to_milliseconds(DEFAULT_TICKS_PER_SECOND * SECONDS_PER_DAY),
+            86_400_000
+        );
+    }
+
+    #[test]
+    fn real_time_start_is_assumed_to_be_elapsed_ticks_ago() {
+        let story_time = StoryTime {
+            elapsed_ticks: DEFAULT_TICKS_PER_SECOND * SECONDS_PER_HOUR,
+            ..default()
+        };
+
+        let before = Utc::now().timestamp_millis() - 3_600_000;
+        let real_time_start = story_time.get_real_time_start();
+        let after = Utc::now().timestamp_millis() - 3_600_000;
+
+        assert!((before..=after).contains(&real_time_start));
+    }
+
+    #[test]
+    fn setup_backfills_real_time_start_from_last_save() {
+        let last_saved = Utc::now().timestamp_millis() - 3_600_000;
+        let story_time = StoryTime {
+            elapsed_ticks: DEFAULT_TICKS_PER_SECOND * 60,
+            ..default()
+        };
+        let mut world = setup(story_time, last_saved);
+
+        world.run_system_once(setup_story_time);
+
+        // Measured from when the story was last saved, not from now, so the hour it spent closed isn't counted as run.
+        assert_eq!(
+            world.resource::<StoryTime>().real_time_start,
+            last_saved - 60_000
+        );
+    }
+
+    #[test]
+    fn setup_keeps_recorded_real_time_start() {
+        let real_time_start = get_timestamp_millis(2024, 1, 1, 0, 0);
+        let story_time = StoryTime {
+            real_time_start,
+            ..default()
+        };
+        let mut world = setup(story_time, Utc::now().timestamp_millis());
+
+        world.run_system_once(setup_story_time);
+
+        assert_eq!(
+            world.resource::<StoryTime>().real_time_start,
+            real_time_start
+        );
+    }
+
+    #[test]
+    fn setup_starts_new_stories_now() {
+        let mut world = setup(StoryTime::default(), 0);
+
+        let before = Utc::now().timestamp_millis();
+        world.run_system_once(setup_story_time);
+        let after = Utc::now().timestamp_millis();
+
+        let story_time = world.resource::<StoryTime>();
+        assert!((before..=after).contains(&story_time.real_time_start));
+        assert_eq!(
+            world.resource::<NextState<StoryPlaybackState>>().0,
+            Some(StoryPlaybackState::Playing)
+        );
+    }
+
+    #[test]
+    fn real_time_clock_jumps_forward_when_daylight_saving_starts() {
+        // 1:30AM EST, half an hour before clocks in New York spring forward.
+        let story_time =
+            create_real_time_story(get_timestamp_millis(2024, 3, 10, 6, 30), "America/New_York");
+
+        let time_info = story_time.tick_as_time_info(DEFAULT_TICKS_PER_SECOND * SECONDS_PER_HOUR);
+
+        assert_eq!(time_info.days(), 0);
+        assert_eq!(time_info.hours(), 3);
+        assert_eq!(time_info.minutes(), 30);
+    }
+
+    #[test]
+    fn real_time_clock_repeats_an_hour_when_daylight_saving_ends() {
+        // 1:30AM EDT, half an hour before clocks in New York fall back.
+        let story_time =
+            create_real_time_story(get_timestamp_millis(2024, 11, 3, 5, 30), "America/New_York");
+
+        let time_info = story_time.tick_as_time_info(DEFAULT_TICKS_PER_SECOND * SECONDS_PER_HOUR);
+
+        assert_eq!(time_info.days(), 0);
+        assert_eq!(time_info.hours(), 1);
+        assert_eq!(time_info.minutes(), 30);
+    }
+
+    #[test]
+    fn real_time_days_count_calendar_days_in_story_time_zone() {
+        // 11PM in Los Angeles, which is already the next day in UTC.
+        let story_time = create_real_time_story(
+            get_timestamp_millis(2024, 6, 2, 6, 0),
+            "America/Los_Angeles",
+        );
+
+        let time_info = story_time.tick_as_time_info(DEFAULT_TICKS_PER_SECOND * SECONDS_PER_HOUR);
+
+        assert_eq!(time_info.days(), 1);
+        assert_eq!(time_info.hours(), 0);
+    }
+
+    #[test]
+    fn real_time_season_is_flipped_for_southern_hemisphere() {
+        let mut story_time = create_real_time_story(get_timestamp_millis(2024, 7, 1, 12, 0), "UTC");
+        assert_eq!(story_time.tick_as_time_info(0).season(), Season::Summer);
+
+        story_time.latitude = -33.9;
+        assert_eq!(story_time.tick_as_time_info(0).season(), Season::Winter);
+    }
+
+    #[test]
+    fn unrecognized_time_zone_falls_back_to_utc() {
+        assert_eq!(resolve_time_zone(Some("Not/A_Zone")), Tz::UTC);
+    }
+}
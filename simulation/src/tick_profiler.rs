@@ -0,0 +1,275 @@
+use bevy::{
+    prelude::*,
+    utils::{get_short_name, Duration, HashMap},
+};
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+/// Time spent inside each of the systems which run in the `SimulationUpdate` schedule.
+/// Includes time spent applying the commands each system queued up.
+#[derive(Default, Clone)]
+pub struct TickProfile {
+    ticks: usize,
+    system_durations: HashMap<String, Duration>,
+}
+
+impl TickProfile {
+    pub fn ticks(&self) -> usize {
+        self.ticks
+    }
+
+    pub fn total_duration(&self) -> Duration {
+        self.system_durations.values().sum()
+    }
+
+    /// Systems ordered by most time spent. Systems are named without their module path for readability.
+    pub fn sorted_system_durations(&self) -> Vec<(String, Duration)> {
+        let mut system_durations = self
+            .system_durations
+            .iter()
+            .map(|(name, &duration)| (get_short_name(name), duration))
+            .collect::<Vec<_>>();
+
+        system_durations.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+        system_durations
+    }
+}
+
+impl fmt::Display for TickProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let total_duration = self.total_duration();
+        let ticks = self.ticks.max(1) as f32;
+
+        writeln!(
+            f,
+            "Ticks: {}, Total: {:.2}ms",
+            self.ticks,
+            total_duration.as_secs_f32() * 1_000.0
+        )?;
+
+        for (name, duration) in self.sorted_system_durations() {
+            writeln!(
+                f,
+                "{:>5.1}% {:>8.3}ms/tick {}",
+                duration.as_secs_f32() / total_duration.as_secs_f32() * 100.0,
+                duration.as_secs_f32() * 1_000.0 / ticks,
+                name
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Shared with the tracing layer which records span timings. Only present when built with `tick-profiling`.
+#[derive(Resource, Default, Clone)]
+pub struct TickProfiler(pub Arc<Mutex<TickProfile>>);
+
+impl TickProfiler {
+    pub fn profile(&self) -> TickProfile {
+        self.0.lock().unwrap().clone()
+    }
+
+    pub fn reset(&self) {
+        *self.0.lock().unwrap() = TickProfile::default();
+    }
+}
+
+#[cfg(feature = "tick-profiling")]
+pub use self::tracing_layer::TickProfilerPlugin;
+
+#[cfg(feature = "tick-profiling")]
+mod tracing_layer {
+    use super::{TickProfile, TickProfiler};
+    use bevy::{
+        prelude::*,
+        utils::{
+            tracing::{
+                field::{Field, Visit},
+                span::{Attributes, Id},
+                subscriber::set_global_default,
+                Subscriber,
+            },
+            Instant,
+        },
+    };
+    use std::{
+        fmt,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, Mutex,
+        },
+    };
+    use tracing_log::LogTracer;
+    use tracing_subscriber::{
+        layer::Context, prelude::*, registry::LookupSpan, EnvFilter, Layer, Registry,
+    };
+
+    // Only systems belonging to the simulation are profiled. This excludes Bevy internals and the render world.
+    const SIMULATION_SYSTEM_PREFIX: &str = "simulation::";
+    const SIMULATION_UPDATE_SCHEDULE_NAME: &str = "SimulationUpdate";
+    // Mirror LogPlugin's defaults because this plugin replaces it.
+    const LOG_FILTER: &str = "info,wgpu=error,naga=warn";
+
+    /// Bevy 0.12's LogPlugin doesn't support adding layers to its subscriber so, instead, install a subscriber which
+    /// logs like LogPlugin, but also records span timings. LogPlugin must be disabled when adding this plugin.
+    /// Requires Bevy's `trace` feature which creates spans for each system run.
+    pub struct TickProfilerPlugin;
+
+    impl Plugin for TickProfilerPlugin {
+        fn build(&self, app: &mut App) {
+            let tick_profiler = TickProfiler::default();
+
+            let subscriber = Registry::default()
+                .with(EnvFilter::try_new(LOG_FILTER).unwrap())
+                .with(TickProfilerLayer {
+                    tick_profile: tick_profiler.0.clone(),
+                    is_in_simulation_update: AtomicBool::new(false),
+                });
+
+            #[cfg(not(target_arch = "wasm32"))]
+            let subscriber = subscriber
+                .with(tracing_subscriber::fmt::Layer::default().with_writer(std::io::stderr));
+
+            // Reporting spans as performance timings is very slow given every system has a span.
+            #[cfg(target_arch = "wasm32")]
+            let subscriber = subscriber.with(tracing_wasm::WASMLayer::new(
+                tracing_wasm::WASMLayerConfigBuilder::new()
+                    .set_report_logs_in_timings(false)
+                    .build(),
+            ));
+
+            if LogTracer::init().is_err() || set_global_default(subscriber).is_err() {
+                warn!("Could not install tick profiler. Is LogPlugin enabled?");
+                return;
+            }
+
+            app.insert_resource(tick_profiler);
+        }
+    }
+
+    enum ProfiledSpan {
+        SimulationUpdate,
+        System {
+            name: String,
+            entered_at: Option<Instant>,
+        },
+    }
+
+    struct TickProfilerLayer {
+        tick_profile: Arc<Mutex<TickProfile>>,
+        // Systems run across threads so they can't rely on span parentage to know which schedule they're running in.
+        // This is safe because schedules in the main world run one at a time.
+        is_in_simulation_update: AtomicBool,
+    }
+
+    #[derive(Default)]
+    struct NameVisitor(Option<String>);
+
+    impl Visit for NameVisitor {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            if field.name() == "name" {
+                self.0 = Some(value.to_string());
+            }
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            if field.name() == "name" {
+                self.0 = Some(format!("{:?}", value));
+            }
+        }
+    }
+
+    impl<S> Layer<S> for TickProfilerLayer
+    where
+        S: Subscriber + for<'a> LookupSpan<'a>,
+    {
+        fn on_new_span(&self, attributes: &Attributes<'_>, id: &Id, context: Context<'_, S>) {
+            let span_name = attributes.metadata().name();
+            if span_name != "schedule" && span_name != "system" && span_name != "system_commands" {
+                return;
+            }
+
+            let mut name_visitor = NameVisitor::default();
+            attributes.record(&mut name_visitor);
+            let name = match name_visitor.0 {
+                Some(name) => name,
+                None => return,
+            };
+
+            let profiled_span = if span_name == "schedule" {
+                if name != SIMULATION_UPDATE_SCHEDULE_NAME {
+                    return;
+                }
+
+                ProfiledSpan::SimulationUpdate
+            } else {
+                if !name.starts_with(SIMULATION_SYSTEM_PREFIX) {
+                    return;
+                }
+
+                ProfiledSpan::System {
+                    name,
+                    entered_at: None,
+                }
+            };
+
+            if let Some(span) = context.span(id) {
+                span.extensions_mut().insert(profiled_span);
+            }
+        }
+
+        fn on_enter(&self, id: &Id, context: Context<'_, S>) {
+            let span = match context.span(id) {
+                Some(span) => span,
+                None => return,
+            };
+
+            match span.extensions_mut().get_mut::<ProfiledSpan>() {
+                Some(ProfiledSpan::SimulationUpdate) => {
+                    self.is_in_simulation_update.store(true, Ordering::Relaxed);
+                }
+                Some(ProfiledSpan::System { entered_at, .. }) => {
+                    *entered_at = Some(Instant::now());
+                }
+                None => {}
+            }
+        }
+
+        fn on_exit(&self, id: &Id, context: Context<'_, S>) {
+            let span = match context.span(id) {
+                Some(span) => span,
+                None => return,
+            };
+
+            match span.extensions_mut().get_mut::<ProfiledSpan>() {
+                Some(ProfiledSpan::SimulationUpdate) => {
+                    self.is_in_simulation_update.store(false, Ordering::Relaxed);
+                    self.tick_profile.lock().unwrap().ticks += 1;
+                }
+                Some(ProfiledSpan::System { name, entered_at }) => {
+                    let entered_at = match entered_at.take() {
+                        Some(entered_at) => entered_at,
+                        None => return,
+                    };
+
+                    if !self.is_in_simulation_update.load(Ordering::Relaxed) {
+                        return;
+                    }
+
+                    *self
+                        .tick_profile
+                        .lock()
+                        .unwrap()
+                        .system_durations
+                        .entry(name.clone())
+                        .or_default() += entered_at.elapsed();
+                }
+                None => {}
+            }
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use bevy::{asset::AssetMetaCheck, prelude::*};
 use bevy_turborand::prelude::*;
 use rendering::RenderingPlugin;
-use simulation::SimulationPlugin;
+use simulation::{save::get_requested_ruins, SimulationPlugin, ViewOnlySimulationPlugin};
 use ui::UIPlugin;
 
 pub struct SymbiantsPlugin;
@@ -14,20 +14,31 @@ impl Plugin for SymbiantsPlugin {
         // See https://github.com/bevyengine/bevy/pull/10623 for details.
         app.insert_resource(AssetMetaCheck::Never);
 
-        app.add_plugins((
-            DefaultPlugins
-                .set(WindowPlugin {
-                    primary_window: Some(Window {
-                        fit_canvas_to_parent: true,
-                        ..default()
-                    }),
+        let default_plugins = DefaultPlugins
+            .set(WindowPlugin {
+                primary_window: Some(Window {
+                    fit_canvas_to_parent: true,
                     ..default()
-                })
-                .set(ImagePlugin::default_nearest()),
-            RngPlugin::default(),
-            UIPlugin,
-            SimulationPlugin,
-            RenderingPlugin,
-        ));
+                }),
+                ..default()
+            })
+            .set(ImagePlugin::default_nearest());
+
+        // The tick profiler installs its own logger, which records span timings, so LogPlugin must not install one.
+        #[cfg(feature = "tick-profiling")]
+        let default_plugins = {
+            app.add_plugins(simulation::tick_profiler::TickProfilerPlugin);
+            default_plugins.disable::<bevy::log::LogPlugin>()
+        };
+
+        app.add_plugins((default_plugins, RngPlugin::default(), UIPlugin));
+
+        // Ruins are viewed in an app of their own, without the simulation, so nothing about them can change.
+        match get_requested_ruins() {
+            Some(story_id) => app.add_plugins(ViewOnlySimulationPlugin { story_id }),
+            None => app.add_plugins(SimulationPlugin),
+        };
+
+        app.add_plugins(RenderingPlugin);
     }
 }
@@ -1,5 +1,6 @@
 mod main_menu;
 pub mod story;
+mod thumbnail;
 
 use self::{main_menu::MainMenuUIPlugin, story::StoryUIPlugin};
 use bevy::prelude::*;
@@ -8,7 +9,7 @@ use bevy_egui::{
     EguiContexts, EguiPlugin,
 };
 use egui::{FontFamily::Proportional, FontId};
-use rendering::common::pointer::IsPointerCaptured;
+use rendering::common::{keyboard_cursor::IsKeyboardCaptured, pointer::IsPointerCaptured};
 pub struct UIPlugin;
 
 impl Plugin for UIPlugin {
@@ -24,7 +25,10 @@ impl Plugin for UIPlugin {
 
         app.add_systems(
             PostUpdate,
-            is_pointer_captured.run_if(resource_exists::<IsPointerCaptured>()),
+            (
+                is_pointer_captured.run_if(resource_exists::<IsPointerCaptured>()),
+                is_keyboard_captured.run_if(resource_exists::<IsKeyboardCaptured>()),
+            ),
         );
     }
 }
@@ -88,3 +92,33 @@ pub fn is_pointer_captured(
     let context = contexts.ctx_mut();
     is_pointer_captured.0 = context.wants_pointer_input() || context.wants_keyboard_input();
 }
+
+/// egui already moves focus between menu widgets with Tab, activates the focused widget with Enter or space, and
+/// drops focus with Escape. Keys only reach the grid while no widget has focus.
+pub fn is_keyboard_captured(
+    mut is_keyboard_captured: ResMut<IsKeyboardCaptured>,
+    mut contexts: EguiContexts,
+) {
+    let context = contexts.ctx_mut();
+    is_keyboard_captured.0 =
+        context.memory(|memory| memory.focus().is_some()) || context.wants_keyboard_input();
+}
+
+/// egui and Bevy represent colors differently. Convert between them when editing a Bevy color with an egui widget.
+pub fn color32_to_bevy_color(color: egui::Color32) -> bevy::prelude::Color {
+    bevy::prelude::Color::rgba(
+        color.r() as f32 / 255.0,
+        color.g() as f32 / 255.0,
+        color.b() as f32 / 255.0,
+        color.a() as f32 / 255.0,
+    )
+}
+
+pub fn bevy_color_to_color32(color: bevy::prelude::Color) -> egui::Color32 {
+    egui::Color32::from_rgba_unmultiplied(
+        (color.r() * 255.0) as u8,
+        (color.g() * 255.0) as u8,
+        (color.b() * 255.0) as u8,
+        (color.a() * 255.0) as u8,
+    )
+}
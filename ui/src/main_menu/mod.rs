@@ -3,23 +3,182 @@ use bevy_egui::{
     egui::{self, Align2},
     EguiContexts,
 };
+use bevy_turborand::prelude::*;
 
-use simulation::app_state::AppState;
+use simulation::{
+    app_state::AppState,
+    benchmark::BenchmarkRun,
+    broadcast::{is_broadcast_supported, HostingBroadcast},
+    common::colony_stats::StoryRecord,
+    demo::{get_demo_settings, DemoStory, DEMO_SEED},
+    device_quality::{
+        get_detected_quality_level, DeviceQuality, QualityLevel, DEVICE_QUALITY_BENCHMARK_TICKS,
+        DEVICE_QUALITY_BENCHMARK_TICKS_PER_FRAME, DEVICE_QUALITY_SAMPLE_FRAMES,
+    },
+    nest_simulation::element::definition::ElementDefinitions,
+    replay::RecordingSimulation,
+    save::{
+        is_device_quality_persisted, load_archived_save_thumbnail, load_archived_story_ids,
+        load_story_records, open_ruins, write_device_quality,
+    },
+    settings::Settings,
+};
+
+use crate::{
+    bevy_color_to_color32, color32_to_bevy_color,
+    thumbnail::{
+        initialize_loading_thumbnail, remove_loading_thumbnail, LoadingThumbnail, ThumbnailPreview,
+    },
+};
 
 pub struct MainMenuUIPlugin;
 
 impl Plugin for MainMenuUIPlugin {
     fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::SelectStoryMode), initialize_main_menu);
+
+        app.add_systems(
+            Update,
+            (
+                update_main_menu,
+                // Devices which can't keep what's measured would be benchmarked on every run, so they're left at Medium.
+                detect_device_quality.run_if(is_device_quality_persisted),
+            )
+                .run_if(in_state(AppState::SelectStoryMode)),
+        );
+
+        app.add_systems(OnExit(AppState::SelectStoryMode), teardown_main_menu);
+
+        app.add_systems(
+            Update,
+            update_join_broadcast_dialog.run_if(in_state(AppState::JoinBroadcast)),
+        );
+
+        // The preview is kept after the colony loads so it can be shown while the colony catches up, too.
+        app.add_systems(
+            OnEnter(AppState::LoadGridChunks),
+            initialize_loading_thumbnail,
+        );
+
         app.add_systems(
             Update,
-            update_main_menu.run_if(in_state(AppState::SelectStoryMode)),
+            update_load_save_dialog.run_if(in_state(AppState::LoadGridChunks)),
         );
+
+        app.add_systems(OnEnter(AppState::Cleanup), remove_loading_thumbnail);
+    }
+}
+
+/// Stories whose ruins are kept, most recent first. Checking for archives reads from storage, which is too slow to do
+/// every frame, so check once when the menu opens.
+#[derive(Resource, Default, PartialEq, Clone, Debug)]
+pub struct ArchivedStories(pub Vec<i64>);
+
+/// A preview of the most recent ruins, shown in the menu, so players can see which colony they're about to revisit.
+#[derive(Resource, Default)]
+pub struct RuinsThumbnail(ThumbnailPreview);
+
+/// Summaries of completed stories, most recent first, so players can see how their colonies compare.
+#[derive(Resource, Default, PartialEq, Clone, Debug)]
+pub struct PastColonies(pub Vec<StoryRecord>);
+
+// Long-time players can have told a lot of stories. Scroll through them rather than growing the menu off screen.
+const PAST_COLONIES_MAX_HEIGHT: f32 = 200.0;
+
+/// The color chosen for the colony of the next story. Ants are shaded from it based on their role.
+#[derive(Resource, PartialEq, Copy, Clone, Debug)]
+pub struct ColonyColor(pub Color);
+
+impl Default for ColonyColor {
+    fn default() -> Self {
+        Self(Settings::default().ant_color)
+    }
+}
+
+pub fn initialize_main_menu(element_definitions: Res<ElementDefinitions>, mut commands: Commands) {
+    let archived_story_ids = load_archived_story_ids();
+
+    commands.insert_resource(RuinsThumbnail(ThumbnailPreview::new(
+        archived_story_ids
+            .first()
+            .and_then(|story_id| load_archived_save_thumbnail(*story_id)),
+        &element_definitions,
+    )));
+    commands.insert_resource(ArchivedStories(archived_story_ids));
+    commands.insert_resource(PastColonies(load_story_records()));
+    commands.init_resource::<ColonyColor>();
+}
+
+/// On first run, time the main menu's first few frames and then benchmark the simulation to choose quality settings
+/// which the device can sustain. The benchmark runs a few ticks each frame so the menu stays responsive meanwhile.
+pub fn detect_device_quality(
+    time: Res<Time<Real>>,
+    mut device_quality: ResMut<DeviceQuality>,
+    mut frame_seconds: Local<Vec<f32>>,
+    mut benchmark_run: Local<Option<BenchmarkRun>>,
+) {
+    if device_quality.is_detected() {
+        return;
     }
+
+    if frame_seconds.len() < DEVICE_QUALITY_SAMPLE_FRAMES {
+        frame_seconds.push(time.delta_seconds());
+
+        if frame_seconds.len() == DEVICE_QUALITY_SAMPLE_FRAMES {
+            *benchmark_run = BenchmarkRun::new(DEVICE_QUALITY_BENCHMARK_TICKS);
+        } else {
+            return;
+        }
+    }
+
+    let benchmark_report = match benchmark_run.as_mut() {
+        Some(benchmark_run) => {
+            match benchmark_run.run_ticks(DEVICE_QUALITY_BENCHMARK_TICKS_PER_FRAME) {
+                Some(benchmark_report) => Some(benchmark_report),
+                None => return,
+            }
+        }
+        None => None,
+    };
+
+    *benchmark_run = None;
+
+    // The first frame is slowed by loading everything else so it isn't representative.
+    let average_frame_seconds =
+        frame_seconds[1..].iter().sum::<f32>() / (frame_seconds.len() - 1) as f32;
+
+    let detected_level = match benchmark_report {
+        Some(benchmark_report) => {
+            get_detected_quality_level(benchmark_report.ticks_per_second(), average_frame_seconds)
+        }
+        None => QualityLevel::Medium,
+    };
+
+    info!(
+        "Detected {:?} quality from {:.1}ms frames.",
+        detected_level,
+        average_frame_seconds * 1_000.0
+    );
+
+    device_quality.set_detected_level(detected_level);
+    write_device_quality(&device_quality);
+}
+
+pub fn teardown_main_menu(mut commands: Commands) {
+    commands.remove_resource::<ArchivedStories>();
+    commands.remove_resource::<RuinsThumbnail>();
+    commands.remove_resource::<PastColonies>();
+    commands.remove_resource::<ColonyColor>();
 }
 
 pub fn update_main_menu(
     mut contexts: EguiContexts,
     mut next_app_state: ResMut<NextState<AppState>>,
+    archived_stories: Res<ArchivedStories>,
+    mut ruins_thumbnail: ResMut<RuinsThumbnail>,
+    past_colonies: Res<PastColonies>,
+    mut colony_color: ResMut<ColonyColor>,
+    mut commands: Commands,
 ) {
     let ctx = contexts.ctx_mut();
 
@@ -31,12 +190,239 @@ pub fn update_main_menu(
             ui.vertical_centered(|ui| {
                 ui.label("Welcome to Symbiants");
 
-                ui.add_enabled(false, egui::Button::new("Story Mode"))
-                    .on_disabled_hover_text("Coming soon™!");
+                ui.horizontal(|ui| {
+                    ui.label("Colony Color");
+
+                    let mut egui_color = bevy_color_to_color32(colony_color.0);
+                    egui::color_picker::color_edit_button_srgba(
+                        ui,
+                        &mut egui_color,
+                        egui::color_picker::Alpha::OnlyBlend,
+                    );
+                    let new_colony_color = color32_to_bevy_color(egui_color);
+
+                    if colony_color.0 != new_colony_color {
+                        colony_color.0 = new_colony_color;
+                    }
+                });
+
+                if ui
+                    .button("Story Mode")
+                    .on_hover_text(
+                        "A realistic start where sandbox tools can't be used near the queen. The colony must fend for itself.",
+                    )
+                    .clicked()
+                {
+                    commands.insert_resource(Settings {
+                        is_realistic_start: true,
+                        is_story_mode: true,
+                        ant_color: colony_color.0,
+                        ..default()
+                    });
+                    next_app_state.set(AppState::CreateNewStory);
+                }
 
                 if ui.button("Sandbox Mode").clicked() {
+                    // Settings are only initialized when missing so inserting them first overrides the defaults.
+                    commands.insert_resource(Settings {
+                        ant_color: colony_color.0,
+                        ..default()
+                    });
+                    next_app_state.set(AppState::CreateNewStory);
+                }
+
+                if ui
+                    .button("Realistic Start")
+                    .on_hover_text("A lone queen must dig a chamber and raise her first workers.")
+                    .clicked()
+                {
+                    // Settings are only initialized when missing so inserting them first overrides the defaults.
+                    commands.insert_resource(Settings {
+                        is_realistic_start: true,
+                        ant_color: colony_color.0,
+                        ..default()
+                    });
+                    next_app_state.set(AppState::CreateNewStory);
+                }
+
+                if ui
+                    .button("Nuptial Flight")
+                    .on_hover_text(
+                        "A realistic start where you choose where the queen lands and she seals herself in.",
+                    )
+                    .clicked()
+                {
+                    commands.insert_resource(Settings {
+                        is_realistic_start: true,
+                        is_nuptial_flight: true,
+                        ant_color: colony_color.0,
+                        ..default()
+                    });
                     next_app_state.set(AppState::CreateNewStory);
                 }
+
+                if ui
+                    .button("Demo")
+                    .on_hover_text(
+                        "A short, guided look at a busy colony. Nothing is saved and your own colony is left untouched.",
+                    )
+                    .clicked()
+                {
+                    commands.insert_resource(get_demo_settings(colony_color.0));
+                    commands.insert_resource(GlobalRng::with_seed(DEMO_SEED));
+                    commands.init_resource::<DemoStory>();
+                    next_app_state.set(AppState::CreateNewStory);
+                }
+
+                if ui
+                    .add_enabled(is_broadcast_supported(), egui::Button::new("Host Broadcast"))
+                    .on_hover_text(
+                        "Experimental. A sandbox story which others can watch, live, as it's told.",
+                    )
+                    .on_disabled_hover_text("Broadcasting is only supported on the web.")
+                    .clicked()
+                {
+                    commands.insert_resource(Settings {
+                        ant_color: colony_color.0,
+                        ..default()
+                    });
+                    // Spectators replay the story so it's recorded from its beginning.
+                    commands.init_resource::<RecordingSimulation>();
+                    commands.init_resource::<HostingBroadcast>();
+                    next_app_state.set(AppState::CreateNewStory);
+                }
+
+                if ui
+                    .add_enabled(is_broadcast_supported(), egui::Button::new("Watch Broadcast"))
+                    .on_hover_text("Experimental. Watch someone else's story, live, without being able to change it.")
+                    .on_disabled_hover_text("Broadcasting is only supported on the web.")
+                    .clicked()
+                {
+                    next_app_state.set(AppState::JoinBroadcast);
+                }
+
+                let latest_ruins = archived_stories.0.first().copied();
+
+                if latest_ruins.is_some() {
+                    ruins_thumbnail.0.show(ui, "ruins-thumbnail");
+                }
+
+                if ui
+                    .add_enabled(latest_ruins.is_some(), egui::Button::new("View Ruins"))
+                    .on_disabled_hover_text("Ruins are left behind when a story ends.")
+                    .clicked()
+                {
+                    if let Some(story_id) = latest_ruins {
+                        open_ruins(story_id);
+                    }
+                }
+
+                if !past_colonies.0.is_empty() {
+                    egui::CollapsingHeader::new("Past Colonies").show(ui, |ui| {
+                        show_past_colonies(ui, &past_colonies.0, &archived_stories.0);
+                    });
+                }
+            });
+        });
+}
+
+/// Previews the colony, as it was last saved, while it loads.
+pub fn update_load_save_dialog(
+    mut contexts: EguiContexts,
+    mut loading_thumbnail: ResMut<LoadingThumbnail>,
+) {
+    egui::Window::new("Loading")
+        .anchor(Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .resizable(false)
+        .collapsible(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.vertical_centered(|ui| {
+                loading_thumbnail.0.show(ui, "loading-thumbnail");
+
+                ui.label("Loading your colony...");
+            });
+        });
+}
+
+pub fn update_join_broadcast_dialog(
+    mut contexts: EguiContexts,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    egui::Window::new("Watch Broadcast")
+        .anchor(Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .resizable(false)
+        .collapsible(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.vertical_centered(|ui| {
+                ui.label("Waiting for the host to share their story...");
+
+                if ui.button("Cancel").clicked() {
+                    next_app_state.set(AppState::SelectStoryMode);
+                }
             });
         });
 }
+
+/// The best of each stat is highlighted so players can see which colony set the bar.
+/// Only the most recent few colonies leave ruins behind so older colonies can't be revisited.
+fn show_past_colonies(
+    ui: &mut egui::Ui,
+    story_records: &[StoryRecord],
+    archived_story_ids: &[i64],
+) {
+    let most_days_survived = story_records
+        .iter()
+        .map(|record| record.days_survived())
+        .max();
+    let highest_peak_population = story_records
+        .iter()
+        .map(|record| record.peak_population())
+        .max();
+    let most_food_gathered = story_records
+        .iter()
+        .map(|record| record.food_gathered())
+        .max();
+
+    egui::ScrollArea::vertical()
+        .max_height(PAST_COLONIES_MAX_HEIGHT)
+        .show(ui, |ui| {
+            egui::Grid::new("past_colonies")
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.strong("Ended");
+                    ui.strong("Days Survived");
+                    ui.strong("Peak Population");
+                    ui.strong("Food Gathered");
+                    ui.strong("Ruins");
+                    ui.end_row();
+
+                    for story_record in story_records {
+                        match story_record.ended_at() {
+                            Some(ended_at) => ui.label(ended_at.format("%Y-%m-%d").to_string()),
+                            None => ui.label("Unknown"),
+                        };
+
+                        show_stat(ui, story_record.days_survived(), most_days_survived);
+                        show_stat(ui, story_record.peak_population(), highest_peak_population);
+                        show_stat(ui, story_record.food_gathered(), most_food_gathered);
+
+                        if archived_story_ids.contains(&story_record.id()) {
+                            if ui.button("View").clicked() {
+                                open_ruins(story_record.id());
+                            }
+                        } else {
+                            ui.label("");
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+}
+
+fn show_stat<T: PartialEq + std::fmt::Display>(ui: &mut egui::Ui, value: T, best: Option<T>) {
+    if best.as_ref() == Some(&value) {
+        ui.strong(value.to_string());
+    } else {
+        ui.label(value.to_string());
+    }
+}
@@ -3,11 +3,13 @@
 use bevy::{prelude::*, window::PrimaryWindow};
 use bevy_egui::{egui, EguiContexts};
 use rendering::common::{
-    pointer::PointerAction,
+    pointer::{IsPaintMirrored, PaintShape, PointerAction},
     visible_grid::{VisibleGrid, VisibleGridState},
 };
 use simulation::{
-    crater_simulation::crater::Crater, nest_simulation::nest::Nest, settings::Settings,
+    crater_simulation::crater::Crater,
+    nest_simulation::{element::FoodKind, nest::Nest},
+    settings::Settings,
     story_time::StoryTime,
 };
 
@@ -25,6 +27,8 @@ pub fn teardown_action_menu(mut commands: Commands) {
 pub fn update_action_menu(
     mut contexts: EguiContexts,
     mut pointer_action: ResMut<PointerAction>,
+    mut paint_shape: ResMut<PaintShape>,
+    mut is_paint_mirrored: ResMut<IsPaintMirrored>,
     mut is_showing_breath_dialog: ResMut<IsShowingBreathDialog>,
     primary_window_query: Query<&Window, With<PrimaryWindow>>,
     settings: Res<Settings>,
@@ -47,7 +51,8 @@ pub fn update_action_menu(
                 && story_time.is_real_time
                 && !story_time.is_within_schedule_window();
 
-            ui.selectable_value(pointer_action.as_mut(), PointerAction::Select, "Select");
+            ui.selectable_value(pointer_action.as_mut(), PointerAction::Select, "Select")
+                .on_hover_text("Ctrl-click ants to select a group and give them orders.");
             ui.selectable_value(
                 pointer_action.as_mut(),
                 PointerAction::SpawnSand,
@@ -55,29 +60,76 @@ pub fn update_action_menu(
             );
 
             ui.add_enabled_ui(!food_disabled, |ui| {
-                ui.selectable_value(
-                    pointer_action.as_mut(),
-                    PointerAction::SpawnFood,
-                    "Place Food",
-                );
-            });
+                for food_kind in FoodKind::ALL {
+                    ui.selectable_value(
+                        pointer_action.as_mut(),
+                        PointerAction::SpawnFood(food_kind),
+                        format!("Place {}", food_kind.label()),
+                    );
+                }
+            })
+            .response
+            .on_hover_text("Larvae only grow when fed protein.");
+
+            ui.selectable_value(
+                pointer_action.as_mut(),
+                PointerAction::SpawnPrey,
+                "Place Prey",
+            )
+            .on_hover_text("Prey is too heavy for one ant. Workers carry it home together.");
 
             ui.selectable_value(
                 pointer_action.as_mut(),
                 PointerAction::SpawnDirt,
                 "Place Dirt",
             );
+            ui.selectable_value(
+                pointer_action.as_mut(),
+                PointerAction::SpawnWater,
+                "Place Water",
+            );
+            ui.selectable_value(
+                pointer_action.as_mut(),
+                PointerAction::SpawnStone,
+                "Place Stone",
+            )
+            .on_hover_text("Stone is too hard to dig. Ants tunnel around it.");
             ui.selectable_value(
                 pointer_action.as_mut(),
                 PointerAction::DespawnElement,
                 "Remove Element",
             );
+            ui.selectable_value(pointer_action.as_mut(), PointerAction::MarkDig, "Mark Dig")
+                .on_hover_text(
+                    "Mark dirt for idle workers to dig out. Shift-drag to mark an area.",
+                );
+            ui.selectable_value(
+                pointer_action.as_mut(),
+                PointerAction::CancelDig,
+                "Cancel Dig",
+            )
+            .on_hover_text("Unmark dirt so workers leave it be.");
+
+            ui.horizontal(|ui| {
+                ui.selectable_value(paint_shape.as_mut(), PaintShape::Line, "Line")
+                    .on_hover_text("Shift-drag to place or remove elements along a line.");
+                ui.selectable_value(paint_shape.as_mut(), PaintShape::Rectangle, "Rectangle")
+                    .on_hover_text("Shift-drag to fill a rectangle with elements.");
+            });
+
+            ui.checkbox(&mut is_paint_mirrored.0, "Mirror")
+                .on_hover_text("Repeat actions on the opposite side.");
 
             ui.selectable_value(
                 pointer_action.as_mut(),
                 PointerAction::SpawnWorkerAnt,
                 "Place Worker Ant",
             );
+            ui.selectable_value(
+                pointer_action.as_mut(),
+                PointerAction::SpawnSoldierAnt,
+                "Place Soldier Ant",
+            );
             ui.selectable_value(
                 pointer_action.as_mut(),
                 PointerAction::DespawnWorkerAnt,
@@ -86,6 +138,13 @@ pub fn update_action_menu(
 
             ui.selectable_value(pointer_action.as_mut(), PointerAction::KillAnt, "Kill Ant");
 
+            ui.selectable_value(
+                pointer_action.as_mut(),
+                PointerAction::TriggerCollapse,
+                "Cave In",
+            )
+            .on_hover_text("Click beneath a ceiling to bring it down.");
+
             ui.add_enabled_ui(!food_disabled, |ui| {
                 if ui.button("Breathe for Food").clicked() {
                     is_showing_breath_dialog.0 = true;
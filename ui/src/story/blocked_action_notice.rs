@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+use bevy_egui::{
+    egui::{self, Align2},
+    EguiContexts,
+};
+use simulation::{external_event::BlockedExternalEvent, settings::Settings};
+
+// Long enough to read, short enough that it's gone before the player has moved on.
+const NOTICE_SECONDS: f32 = 3.0;
+
+/// Explain why nothing happened when a sandbox tool was refused in Story Mode. Shown briefly, rather than as a dialog,
+/// because the player is likely mid-drag and doesn't need to stop what they're doing.
+pub fn update_blocked_action_notice(
+    mut contexts: EguiContexts,
+    mut blocked_events: EventReader<BlockedExternalEvent>,
+    settings: Res<Settings>,
+    time: Res<Time<Real>>,
+    mut remaining_seconds: Local<f32>,
+) {
+    if blocked_events.read().count() > 0 {
+        *remaining_seconds = NOTICE_SECONDS;
+    }
+
+    if *remaining_seconds <= 0.0 {
+        return;
+    }
+
+    *remaining_seconds -= time.delta_seconds();
+
+    egui::Window::new("Blocked Action")
+        .anchor(Align2::CENTER_TOP, egui::Vec2::new(0.0, 10.0))
+        .title_bar(false)
+        .collapsible(false)
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label(&format!(
+                "In Story Mode, the world can't be changed within {} tiles of the queen.",
+                settings.queen_protection_radius
+            ));
+        });
+}
@@ -1,19 +1,23 @@
 use super::action_menu::IsShowingBreathDialog;
-use bevy::{prelude::*, utils::HashSet};
+use bevy::prelude::*;
 use bevy_egui::{
     egui::{self, Align2},
     EguiContexts,
 };
 use bevy_turborand::{DelegatedRng, GlobalRng};
 use simulation::{
-    common::{grid::GridElements, position::Position},
+    common::{grid::Grid, position::Position},
+    external_event::ExternalSimulationEvent,
     nest_simulation::{
         ant::{hunger::Hunger, Dead},
-        element::{commands::ElementCommandsExt, Air, Element, Food},
-        nest::AtNest,
+        element::Food,
+        nest::{AtNest, Nest},
     },
 };
 
+// Drop food high in the sky so it can be seen falling onto the nest.
+const FOOD_DROP_HEIGHT: isize = 10;
+
 pub struct IsOpen(bool);
 
 impl Default for IsOpen {
@@ -28,10 +32,9 @@ pub fn update_breath_dialog(
     mut is_showing_breath_dialog: ResMut<IsShowingBreathDialog>,
     ant_query: Query<&Hunger, Without<Dead>>,
     food_query: Query<&Food>,
-    air_query: Query<&Position, With<Air>>,
+    nest_query: Query<&Grid, With<Nest>>,
     mut rng: ResMut<GlobalRng>,
-    grid_elements: GridElements<AtNest>,
-    mut commands: Commands,
+    mut external_simulation_event_writer: EventWriter<ExternalSimulationEvent<AtNest>>,
     mut is_running: Local<bool>,
     mut is_open: Local<IsOpen>,
     mut timer: Local<f32>,
@@ -121,30 +124,18 @@ pub fn update_breath_dialog(
                             *is_running = false;
                             *timer = 0.;
 
-                            let northern_air_positions = air_query
-                                .iter()
-                                .filter(|position| position.y < 20)
-                                .collect::<Vec<&Position>>();
-
-                            let mut spawn_positions: HashSet<Position> = HashSet::new();
-
-                            while spawn_positions.len() < ant_food_acquired as usize {
-                                let offset = rng.usize(0..northern_air_positions.len());
-                                let position = northern_air_positions[offset];
-
-                                spawn_positions.insert(*position);
-                            }
-
-                            for position in spawn_positions.iter() {
-                                if let Some(entity) = grid_elements.get_entity(*position) {
-                                    commands.replace_element(
-                                        *position,
-                                        Element::Food,
-                                        *entity,
-                                        AtNest,
-                                    );
-                                }
-                            }
+                            let grid = nest_query.single();
+
+                            external_simulation_event_writer.send(
+                                ExternalSimulationEvent::AirdropFood {
+                                    zone: AtNest,
+                                    center: Position::new(
+                                        rng.isize(0..grid.width()),
+                                        FOOD_DROP_HEIGHT,
+                                    ),
+                                    amount: ant_food_acquired,
+                                },
+                            );
                         }
                     });
                 } else {
@@ -0,0 +1,342 @@
+// Tools for testing the simulation and diagnosing problems on a user's device. Collapsed by default to stay out of the way.
+use bevy::{prelude::*, window::PrimaryWindow};
+use bevy_egui::{egui, EguiContexts};
+use simulation::{
+    benchmark::{
+        run_benchmark, run_exploration_benchmark, run_sand_benchmark, BenchmarkReport,
+        ExplorationBenchmarkReport, SandBenchmarkReport, BENCHMARK_TICKS,
+        EXPLORATION_BENCHMARK_STEPS, SAND_BENCHMARK_GRAINS,
+    },
+    common::{grid::Grid, position::Position},
+    crater_simulation::crater::AtCrater,
+    external_event::ExternalSimulationEvent,
+    nest_simulation::{
+        element::{Element, FoodKind},
+        nest::{AtNest, Nest},
+        population::CarryingCapacity,
+    },
+    settings::Settings,
+    story_time::{StoryTime, TimeTravelEvent, ZonePlayback},
+    tick_profiler::TickProfiler,
+};
+
+// Listing every system makes the console too tall. The full breakdown is available by copying it.
+const MAX_LISTED_SYSTEMS: usize = 10;
+// Enough to bring most devices to their knees without making the slider too fiddly to use.
+const MAX_STRESS_TEST_ANTS: isize = 1000;
+const MAX_STRESS_TEST_TICKS: isize = 100_000;
+const FILL_ELEMENTS: [Element; 7] = [
+    Element::Air,
+    Element::Dirt,
+    Element::Sand,
+    Element::Food,
+    Element::Water,
+    Element::Stone,
+    Element::Mud,
+];
+
+/// The rectangle of the nest to fill, in grid coordinates.
+pub struct FillRegion {
+    element: Element,
+    // Only used when filling with food.
+    food_kind: FoodKind,
+    x: isize,
+    y: isize,
+    width: isize,
+    height: isize,
+}
+
+impl Default for FillRegion {
+    fn default() -> Self {
+        Self {
+            element: Element::Dirt,
+            food_kind: FoodKind::default(),
+            x: 0,
+            y: 0,
+            width: 10,
+            height: 10,
+        }
+    }
+}
+
+/// The latest results of each benchmark, kept so they stay on screen until the benchmark is run again.
+#[derive(Default)]
+pub struct BenchmarkReports {
+    colony: Option<BenchmarkReport>,
+    exploration: Option<ExplorationBenchmarkReport>,
+    sand: Option<SandBenchmarkReport>,
+}
+
+impl FillRegion {
+    fn positions(&self) -> Vec<Position> {
+        (self.y..self.y + self.height)
+            .flat_map(|y| (self.x..self.x + self.width).map(move |x| Position::new(x, y)))
+            .collect()
+    }
+}
+
+pub fn update_debug_console(
+    mut contexts: EguiContexts,
+    primary_window_query: Query<&Window, With<PrimaryWindow>>,
+    mut benchmark_reports: Local<BenchmarkReports>,
+    tick_profiler: Option<Res<TickProfiler>>,
+    story_time: Res<StoryTime>,
+    mut time_travel_event_writer: EventWriter<TimeTravelEvent>,
+    mut time_travel_hours: Local<isize>,
+    mut external_simulation_event_writer: EventWriter<ExternalSimulationEvent<AtNest>>,
+    nest_query: Query<&Grid, With<Nest>>,
+    mut stress_test_ants: Local<isize>,
+    mut stress_test_ticks: Local<isize>,
+    mut fill_region: Local<FillRegion>,
+    zone_playback: Option<ResMut<ZonePlayback>>,
+    carrying_capacity: Option<Res<CarryingCapacity>>,
+    settings: Res<Settings>,
+) {
+    let window = primary_window_query.single();
+    let ctx = contexts.ctx_mut();
+
+    egui::Window::new("Debug Console")
+        .default_pos(egui::Pos2::new(0.0, window.height()))
+        .default_open(false)
+        .resizable(false)
+        .show(ctx, |ui| {
+            ui.label("Time Travel");
+
+            // Real time follows the real-world clock so it can't be moved.
+            ui.add_enabled_ui(!story_time.is_real_time, |ui| {
+                ui.add(egui::Slider::new(&mut *time_travel_hours, 0..=23).text("hours"));
+
+                ui.horizontal(|ui| {
+                    if ui.button("Go To Hour").clicked() {
+                        time_travel_event_writer
+                            .send(TimeTravelEvent::SetHourOfDay(*time_travel_hours));
+                    }
+
+                    if ui.button("Advance Hours").clicked() {
+                        time_travel_event_writer
+                            .send(TimeTravelEvent::AdvanceHours(*time_travel_hours));
+                    }
+                });
+
+                ui.add(
+                    egui::Slider::new(&mut *stress_test_ticks, 0..=MAX_STRESS_TEST_TICKS)
+                        .text("ticks"),
+                );
+
+                if ui.button("Advance Ticks").clicked() {
+                    time_travel_event_writer
+                        .send(TimeTravelEvent::AdvanceTicks(*stress_test_ticks));
+                }
+            });
+
+            ui.separator();
+
+            // Freeze one zone while the other keeps running. Frozen zones cost nothing to simulate.
+            if let Some(mut zone_playback) = zone_playback {
+                ui.label("Zone Playback");
+
+                let mut is_nest_paused = zone_playback.is_paused::<AtNest>();
+                if ui.checkbox(&mut is_nest_paused, "Pause Nest").changed() {
+                    zone_playback.set_paused::<AtNest>(is_nest_paused);
+                }
+
+                let mut is_crater_paused = zone_playback.is_paused::<AtCrater>();
+                if ui.checkbox(&mut is_crater_paused, "Pause Crater").changed() {
+                    zone_playback.set_paused::<AtCrater>(is_crater_paused);
+                }
+
+                ui.separator();
+            }
+
+            // Useful for tuning how large colonies grow before they level off.
+            if let Some(carrying_capacity) = carrying_capacity {
+                ui.label("Population");
+                ui.label(&format!(
+                    "{} of {} ants supported, {:.0}% fertility",
+                    carrying_capacity.population(),
+                    carrying_capacity.capacity(),
+                    carrying_capacity.fertility() * 100.0
+                ));
+
+                ui.separator();
+            }
+
+            // Everything is routed through the same events as the action menu so the simulation's rules still apply.
+            if let Ok(grid) = nest_query.get_single() {
+                ui.label("Stress Test");
+
+                ui.add(
+                    egui::Slider::new(&mut *stress_test_ants, 0..=MAX_STRESS_TEST_ANTS)
+                        .text("ants"),
+                );
+
+                if ui.button("Spawn Worker Ants").clicked() {
+                    external_simulation_event_writer.send(
+                        ExternalSimulationEvent::SpawnWorkerAnts {
+                            zone: AtNest,
+                            amount: *stress_test_ants,
+                        },
+                    );
+                }
+
+                egui::ComboBox::from_label("element")
+                    .selected_text(format!("{:?}", fill_region.element))
+                    .show_ui(ui, |ui| {
+                        for element in FILL_ELEMENTS {
+                            ui.selectable_value(
+                                &mut fill_region.element,
+                                element,
+                                format!("{:?}", element),
+                            );
+                        }
+                    });
+
+                if fill_region.element.is_food() {
+                    egui::ComboBox::from_label("food kind")
+                        .selected_text(fill_region.food_kind.label())
+                        .show_ui(ui, |ui| {
+                            for food_kind in FoodKind::ALL {
+                                ui.selectable_value(
+                                    &mut fill_region.food_kind,
+                                    food_kind,
+                                    food_kind.label(),
+                                );
+                            }
+                        });
+                }
+
+                ui.add(egui::Slider::new(&mut fill_region.x, 0..=grid.width() - 1).text("x"));
+                ui.add(egui::Slider::new(&mut fill_region.y, 0..=grid.height() - 1).text("y"));
+                ui.add(
+                    egui::Slider::new(&mut fill_region.width, 1..=grid.width()).text("width"),
+                );
+                ui.add(
+                    egui::Slider::new(&mut fill_region.height, 1..=grid.height())
+                        .text("height"),
+                );
+
+                // Like painting, filling only places elements into empty space and only removes elements which exist.
+                if ui.button("Fill Region").clicked() {
+                    external_simulation_event_writer.send(
+                        ExternalSimulationEvent::PaintElements {
+                            zone: AtNest,
+                            positions: fill_region
+                                .positions()
+                                .into_iter()
+                                .filter(|position| grid.is_within_bounds(position))
+                                .collect(),
+                            element: fill_region.element,
+                            food_kind: fill_region
+                                .element
+                                .is_food()
+                                .then_some(fill_region.food_kind),
+                        },
+                    );
+                }
+
+                ui.separator();
+            }
+
+            ui.label(&format!(
+                "Benchmark simulates {} ticks of a new colony. The app will freeze until it's done.",
+                BENCHMARK_TICKS
+            ));
+
+            if ui.button("Benchmark").clicked() {
+                benchmark_reports.colony = run_benchmark(BENCHMARK_TICKS);
+            }
+
+            if let Some(report) = benchmark_reports.colony.as_ref() {
+                let report_text = report.to_string();
+
+                ui.monospace(&report_text);
+
+                // Make it easy to include results in bug reports.
+                if ui.button("Copy Results").clicked() {
+                    ui.output_mut(|output| output.copied_text = report_text);
+                }
+            }
+
+            ui.label("Exploration Benchmark compares how much of the crater each exploration strategy covers.");
+
+            if ui.button("Exploration Benchmark").clicked() {
+                benchmark_reports.exploration =
+                    Some(run_exploration_benchmark(EXPLORATION_BENCHMARK_STEPS));
+            }
+
+            if let Some(report) = benchmark_reports.exploration.as_ref() {
+                let report_text = report.to_string();
+
+                ui.monospace(&report_text);
+
+                if ui.button("Copy Exploration Results").clicked() {
+                    ui.output_mut(|output| output.copied_text = report_text);
+                }
+            }
+
+            ui.label(&format!(
+                "Sand Benchmark pours {} grains of sand into a pile and waits for it to settle.",
+                SAND_BENCHMARK_GRAINS
+            ));
+
+            if ui.button("Sand Benchmark").clicked() {
+                benchmark_reports.sand = Some(run_sand_benchmark(
+                    SAND_BENCHMARK_GRAINS,
+                    settings.sand_repose_angle,
+                ));
+            }
+
+            if let Some(report) = benchmark_reports.sand.as_ref() {
+                let report_text = report.to_string();
+
+                ui.monospace(&report_text);
+
+                if ui.button("Copy Sand Results").clicked() {
+                    ui.output_mut(|output| output.copied_text = report_text);
+                }
+            }
+
+            ui.separator();
+            ui.label("Tick Budget");
+
+            let tick_profiler = match tick_profiler {
+                Some(tick_profiler) => tick_profiler,
+                None => {
+                    ui.label("Build with the `tick-profiling` feature to see time spent per system.");
+                    return;
+                }
+            };
+
+            let tick_profile = tick_profiler.profile();
+            let total_seconds = tick_profile.total_duration().as_secs_f32();
+            let ticks = tick_profile.ticks().max(1) as f32;
+
+            ui.label(&format!(
+                "{} ticks, {:.3}ms/tick",
+                tick_profile.ticks(),
+                total_seconds * 1_000.0 / ticks
+            ));
+
+            for (name, duration) in tick_profile
+                .sorted_system_durations()
+                .into_iter()
+                .take(MAX_LISTED_SYSTEMS)
+            {
+                ui.monospace(&format!(
+                    "{:>5.1}% {}",
+                    duration.as_secs_f32() / total_seconds * 100.0,
+                    name
+                ));
+            }
+
+            ui.horizontal(|ui| {
+                if ui.button("Copy Breakdown").clicked() {
+                    ui.output_mut(|output| output.copied_text = tick_profile.to_string());
+                }
+
+                if ui.button("Reset").clicked() {
+                    tick_profiler.reset();
+                }
+            });
+        });
+}
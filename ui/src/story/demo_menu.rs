@@ -0,0 +1,25 @@
+use bevy::prelude::*;
+use bevy_egui::{
+    egui::{self, Align2},
+    EguiContexts,
+};
+use simulation::app_state::AppState;
+
+/// The demo plays on its own, so the only thing to offer is a way out. Leaving the demo starts no story of its own.
+pub fn update_demo_menu(
+    mut contexts: EguiContexts,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    egui::Window::new("Demo")
+        .anchor(Align2::RIGHT_BOTTOM, egui::Vec2::new(-10.0, -10.0))
+        .collapsible(false)
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label("You're watching a demo colony.");
+
+            // Cleanup leaves the player's own save alone while the demo plays.
+            if ui.button("Return to Main Menu").clicked() {
+                next_app_state.set(AppState::Cleanup);
+            }
+        });
+}
@@ -0,0 +1,29 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use simulation::content_pack::ContentPacks;
+
+const ENCYCLOPEDIA_MAX_HEIGHT: f32 = 300.0;
+
+/// Entries come from content packs. The window starts collapsed so it stays out of the way until it's wanted.
+pub fn update_encyclopedia_window(mut contexts: EguiContexts, content_packs: Res<ContentPacks>) {
+    if content_packs.encyclopedia_entries().next().is_none() {
+        return;
+    }
+
+    egui::Window::new("Encyclopedia")
+        .default_pos(egui::Pos2::new(0.0, 400.0))
+        .default_open(false)
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            egui::ScrollArea::vertical()
+                .max_height(ENCYCLOPEDIA_MAX_HEIGHT)
+                .show(ui, |ui| {
+                    for entry in content_packs.encyclopedia_entries() {
+                        egui::CollapsingHeader::new(&entry.title).show(ui, |ui| {
+                            ui.set_max_width(300.0);
+                            ui.label(&entry.body);
+                        });
+                    }
+                });
+        });
+}
@@ -0,0 +1,124 @@
+use bevy::prelude::*;
+use bevy_egui::{
+    egui::{self, Align2},
+    EguiContexts,
+};
+use rendering::common::pointer::PointerAction;
+use simulation::{
+    nest_simulation::{
+        ant::{
+            birthing::Birthing,
+            nesting::{Nested, Nesting},
+            nuptial_flight::{Claustral, Winged},
+            AntRole, Dead,
+        },
+        nest::AtNest,
+    },
+    settings::Settings,
+};
+
+/// Walk the user through the opening hours of a realistic start. The queen founds the colony alone, so each step
+/// mirrors her progress, and the guide goes away once her first worker is born.
+/// After a nuptial flight the user chooses where the queen lands, and she seals herself in once her chamber is dug.
+pub fn update_founding_guide(
+    mut contexts: EguiContexts,
+    settings: Res<Settings>,
+    mut pointer_action: ResMut<PointerAction>,
+    queen_query: Query<
+        (
+            Option<&Nesting>,
+            Option<&Nested>,
+            Option<&Birthing>,
+            Option<&Winged>,
+            Option<&Claustral>,
+            &AntRole,
+        ),
+        // Rival colonies forage the crater so only look for the colony's own ants, in the nest.
+        (Without<Dead>, With<AtNest>),
+    >,
+) {
+    if !settings.is_realistic_start {
+        return;
+    }
+
+    let mut queen = None;
+
+    for (nesting, nested, birthing, winged, claustral, role) in queen_query.iter() {
+        match role {
+            AntRole::Queen => queen = Some((nesting, nested, birthing, winged, claustral)),
+            // The colony has been founded.
+            AntRole::Worker | AntRole::Soldier => return,
+        }
+    }
+
+    // Without a queen the story is over and there's nothing left to guide.
+    let (nesting, nested, birthing, winged, claustral) = match queen {
+        Some(queen) => queen,
+        None => return,
+    };
+
+    egui::Window::new("Founding")
+        .anchor(Align2::CENTER_TOP, egui::Vec2::new(0.0, 10.0))
+        .collapsible(true)
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            let is_chamber_started = matches!(nesting, Some(Nesting::Started(_)));
+            let is_chamber_dug = nested.is_some();
+
+            let mut steps = vec![];
+
+            if settings.is_nuptial_flight {
+                ui.label(
+                    "Your queen is returning from her nuptial flight. She must found the colony alone.",
+                );
+
+                steps.push(("Choose where to land", winged.is_none()));
+            } else {
+                ui.label(
+                    "Your queen has landed after her nuptial flight. She must found the colony alone.",
+                );
+            }
+
+            steps.push(("Find a place to dig", is_chamber_started || is_chamber_dug));
+            steps.push(("Dig a founding chamber", is_chamber_dug));
+
+            if settings.is_nuptial_flight {
+                steps.push(("Seal the chamber", claustral == Some(&Claustral::Sealed)));
+            }
+
+            steps.push(("Raise the first brood", false));
+
+            for (index, (label, is_complete)) in steps.into_iter().enumerate() {
+                founding_step(ui, &format!("{}. {}", index + 1, label), is_complete);
+            }
+
+            if let Some(winged) = winged {
+                if winged.is_landing() {
+                    ui.label("She's coming in to land.");
+                } else {
+                    ui.selectable_value(
+                        pointer_action.as_mut(),
+                        PointerAction::Land,
+                        "Choose Landing Site",
+                    )
+                    .on_hover_text("Tap where she should land. She'll dig her chamber nearby.");
+                }
+            }
+
+            if let Some(birthing) = birthing {
+                ui.label(&format!("Next egg: {:.0}%", birthing.value()));
+            }
+
+            ui.label(
+                "There are no workers to forage. Keep her fed, with protein for her larvae, so she can raise her brood.",
+            );
+        });
+}
+
+fn founding_step(ui: &mut egui::Ui, label: &str, is_complete: bool) {
+    if is_complete {
+        ui.label(egui::RichText::new(format!("✔ {}", label)).strikethrough());
+    } else {
+        ui.label(label);
+    }
+}
@@ -0,0 +1,143 @@
+use bevy::{
+    input::{mouse::MouseWheel, touch::TouchInput},
+    prelude::*,
+    window::PrimaryWindow,
+};
+use bevy_egui::{
+    egui::{self, Align2},
+    EguiContexts,
+};
+use simulation::{
+    save::IsSaveRequested,
+    settings::Settings,
+    story_time::{StoryPlaybackState, StoryTime},
+};
+
+/// The story was paused because nobody was around. Kept until the story resumes so the player is told why it stopped.
+#[derive(Resource, Debug)]
+pub struct IdlePause {
+    paused_tick: isize,
+    paused_seconds: f32,
+    idle_minutes: isize,
+    is_saved: bool,
+}
+
+/// Pause, and save, once the tab has been focused for a while without any input. Time spent away from the tab doesn't
+/// count because a story isn't left running unattended while its tab is in the background.
+/// NOTE: Moving the mouse isn't considered input because the cursor passes over the window when using another monitor.
+pub fn pause_when_idle(
+    keyboard_input: Res<Input<KeyCode>>,
+    mouse_input: Res<Input<MouseButton>>,
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    mut touch_events: EventReader<TouchInput>,
+    primary_window_query: Query<&Window, With<PrimaryWindow>>,
+    settings: Res<Settings>,
+    story_time: Res<StoryTime>,
+    time: Res<Time<Real>>,
+    story_playback_state: Res<State<StoryPlaybackState>>,
+    mut next_story_playback_state: ResMut<NextState<StoryPlaybackState>>,
+    is_save_requested: Option<ResMut<IsSaveRequested>>,
+    mut idle_seconds: Local<f32>,
+    mut commands: Commands,
+) {
+    let has_wheel_input = mouse_wheel_events.read().count() > 0;
+    let has_touch_input = touch_events.read().count() > 0;
+
+    let has_input = keyboard_input.get_just_pressed().next().is_some()
+        || mouse_input.get_just_pressed().next().is_some()
+        || has_wheel_input
+        || has_touch_input;
+
+    let is_focused = primary_window_query
+        .get_single()
+        .map_or(false, |window| window.focused);
+
+    if has_input
+        || !is_focused
+        || !settings.pause_when_idle
+        || *story_playback_state != StoryPlaybackState::Playing
+    {
+        *idle_seconds = 0.0;
+        return;
+    }
+
+    *idle_seconds += time.delta_seconds();
+
+    if *idle_seconds < (settings.idle_pause_minutes * 60) as f32 {
+        return;
+    }
+
+    *idle_seconds = 0.0;
+    next_story_playback_state.set(StoryPlaybackState::Paused);
+
+    let is_saved = match is_save_requested {
+        Some(mut is_save_requested) => {
+            is_save_requested.0 = true;
+            true
+        }
+        None => false,
+    };
+
+    commands.insert_resource(IdlePause {
+        paused_tick: story_time.elapsed_ticks(),
+        paused_seconds: time.elapsed_seconds(),
+        idle_minutes: settings.idle_pause_minutes,
+        is_saved,
+    });
+}
+
+/// Explain why the story stopped, and how long ago, until it's resumed. Resuming from anywhere else, e.g. the settings
+/// menu, dismisses the explanation too.
+pub fn update_idle_pause_dialog(
+    mut contexts: EguiContexts,
+    idle_pause: Option<Res<IdlePause>>,
+    story_time: Res<StoryTime>,
+    time: Res<Time<Real>>,
+    story_playback_state: Res<State<StoryPlaybackState>>,
+    mut next_story_playback_state: ResMut<NextState<StoryPlaybackState>>,
+    mut commands: Commands,
+) {
+    let idle_pause = match idle_pause {
+        Some(idle_pause) => idle_pause,
+        None => return,
+    };
+
+    // The story isn't paused until the frame after pausing is asked for so give it a frame to catch up.
+    if *story_playback_state != StoryPlaybackState::Paused && !idle_pause.is_added() {
+        commands.remove_resource::<IdlePause>();
+        return;
+    }
+
+    let time_info = story_time.tick_as_time_info(idle_pause.paused_tick);
+    let minutes_paused = ((time.elapsed_seconds() - idle_pause.paused_seconds) / 60.0).floor();
+
+    egui::Window::new("Paused Due To Inactivity")
+        .anchor(Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .collapsible(false)
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.set_max_width(300.0);
+
+            ui.label(&format!(
+                "Nothing was touched for {} minutes so the story was paused on day {}, at {:02}:{:02}, {:.0} minutes ago.",
+                idle_pause.idle_minutes,
+                time_info.days() + 1,
+                time_info.hours(),
+                time_info.minutes(),
+                minutes_paused,
+            ));
+
+            if idle_pause.is_saved {
+                ui.label("The colony was saved when it was paused.");
+            }
+
+            if ui.button("Resume").clicked() {
+                next_story_playback_state.set(StoryPlaybackState::Playing);
+                commands.remove_resource::<IdlePause>();
+            }
+        });
+}
+
+pub fn remove_idle_pause_resources(mut commands: Commands) {
+    commands.remove_resource::<IdlePause>();
+}
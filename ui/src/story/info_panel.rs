@@ -2,18 +2,34 @@ use bevy::prelude::*;
 use bevy_egui::{egui, EguiContexts};
 
 use simulation::{
+    common::entity_budget::EntityBudget,
     nest_simulation::{
-        ant::{birthing::Birthing, hunger::Hunger, AntRole, Dead},
+        ant::{
+            birthing::Birthing,
+            brood::{Brood, Egg, Larva, Pupa},
+            hunger::Hunger,
+            AntRole, Dead,
+        },
+        colony_status::ColonyStatus,
         element::Food,
+        nest::AtNest,
     },
-    story_time::StoryTime,
+    save::IsSaveTruncated,
+    settings::Settings,
+    story_time::{StoryTime, DEFAULT_TICKS_PER_SECOND},
 };
 
 pub fn update_info_window(
     mut contexts: EguiContexts,
-    ant_query: Query<(&AntRole, &Hunger, Option<&Birthing>), Without<Dead>>,
+    // Rival colonies forage the crater, and hunger only applies in the nest, so only summarize the nest.
+    ant_query: Query<(&AntRole, &Hunger, Option<&Birthing>), (Without<Dead>, With<AtNest>)>,
+    brood_query: Query<(Option<&Egg>, Option<&Larva>, Option<&Pupa>), With<Brood>>,
     food_query: Query<&Food>,
     story_time: Res<StoryTime>,
+    entity_budget: Res<EntityBudget>,
+    settings: Res<Settings>,
+    colony_status: Option<Res<ColonyStatus>>,
+    is_save_truncated: Option<Res<IsSaveTruncated>>,
 ) {
     let queen_ant = ant_query
         .iter()
@@ -45,7 +61,8 @@ pub fn update_info_window(
 
             // Construct the label string
             ui.label(&format!(
-                "Day: {:.0}, {:02.0}:{:02.0} {}",
+                "{:?}, Day: {:.0}, {:02.0}:{:02.0} {}",
+                time_info.season(),
                 // Add one to the days label because days don't start at 0 in real life
                 time_info.days() + 1,
                 hour_12,
@@ -60,6 +77,64 @@ pub fn update_info_window(
             ));
             ui.label(&format!("Queen Hunger: {:.0}%", queen_ant_hunger));
             ui.label(&format!("Queen Birthing: {:.0}%", queen_ant_birthing));
+            ui.label(&format!(
+                "Brood: {} eggs, {} larvae, {} pupae",
+                brood_query
+                    .iter()
+                    .filter(|(egg, _, _)| egg.is_some())
+                    .count(),
+                brood_query
+                    .iter()
+                    .filter(|(_, larva, _)| larva.is_some())
+                    .count(),
+                brood_query
+                    .iter()
+                    .filter(|(_, _, pupa)| pupa.is_some())
+                    .count()
+            ));
             ui.label(&format!("Food: {}", food_query.iter().count()));
+            // Make it clear why foragers seem to ignore food at certain times of day.
+            ui.label(&format!(
+                "Foraging Conditions: {:.0}%",
+                story_time.get_foraging_efficiency() * 100.0
+            ));
+            ui.label(&format!(
+                "Memory: ~{:.0}MB / {}MB",
+                entity_budget.estimated_megabytes(),
+                settings.memory_budget_megabytes
+            ));
+
+            match colony_status.as_deref() {
+                Some(ColonyStatus::Succession { remaining_ticks }) => {
+                    ui.colored_label(
+                        egui::Color32::YELLOW,
+                        &format!(
+                            "The queen has died! A new queen emerges in {:.0} minutes.",
+                            (*remaining_ticks as f32 / (60 * DEFAULT_TICKS_PER_SECOND) as f32).ceil()
+                        ),
+                    );
+                }
+                Some(ColonyStatus::Declining) => {
+                    ui.colored_label(
+                        egui::Color32::YELLOW,
+                        "The queen has died without an heir. The colony is in decline.",
+                    );
+                }
+                _ => {}
+            }
+
+            if entity_budget.is_exceeded() {
+                ui.colored_label(
+                    egui::Color32::YELLOW,
+                    "Colony is too large! New ants and emotes are paused until there's room.",
+                );
+            }
+
+            if is_save_truncated.map_or(false, |is_save_truncated| is_save_truncated.0) {
+                ui.colored_label(
+                    egui::Color32::RED,
+                    "Saving failed! Storage may be full. Progress since the last save could be lost.",
+                );
+            }
         });
 }
@@ -4,7 +4,12 @@ use bevy_egui::{
     EguiContexts,
 };
 
-use simulation::story_time::{FastForwardingStateInfo, TicksPerSecond, SECONDS_PER_DAY};
+use simulation::story_time::{
+    FastForwardingStateInfo, TicksPerSecond, DEFAULT_TICKS_PER_SECOND, SECONDS_PER_DAY,
+    SECONDS_PER_HOUR,
+};
+
+use crate::thumbnail::LoadingThumbnail;
 
 // Don't flicker the dialogs visibility when processing a small number of ticks
 const MIN_PENDING_TICKS: isize = 6000;
@@ -13,6 +18,7 @@ pub fn update_loading_dialog(
     mut contexts: EguiContexts,
     fast_forwarding_state_info: Res<FastForwardingStateInfo>,
     ticks_per_second: Res<TicksPerSecond>,
+    loading_thumbnail: Option<ResMut<LoadingThumbnail>>,
 ) {
     if fast_forwarding_state_info.initial_pending_ticks < MIN_PENDING_TICKS {
         return;
@@ -23,6 +29,28 @@ pub fn update_loading_dialog(
         .collapsible(false)
         .resizable(false)
         .show(contexts.ctx_mut(), |ui| {
+            if fast_forwarding_state_info.is_time_travelling {
+                let hours_travelled = fast_forwarding_state_info.initial_pending_ticks as f32
+                    / (DEFAULT_TICKS_PER_SECOND * SECONDS_PER_HOUR) as f32;
+
+                ui.label(&format!(
+                    "Travelling {:.1} hour{} into the future.",
+                    hours_travelled,
+                    pluralize(hours_travelled)
+                ));
+                ui.label(&format!(
+                    "Remaining ticks: {}",
+                    fast_forwarding_state_info.pending_ticks
+                ));
+
+                return;
+            }
+
+            // Remind players which colony they've come back to.
+            if let Some(mut loading_thumbnail) = loading_thumbnail {
+                loading_thumbnail.0.show(ui, "loading-thumbnail");
+            }
+
             let seconds_gone = (fast_forwarding_state_info.initial_pending_ticks as f32)
                 / ticks_per_second.0 as f32;
 
@@ -0,0 +1,88 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use simulation::{
+    common::{food_sharing::FoodSharingNetwork, logistics::ColonyLogistics},
+    nest_simulation::ant::AntRole,
+};
+
+/// Summarize how food moved through the colony over the last day. The window starts collapsed, like the encyclopedia,
+/// because it's for players digging into why their colony is going hungry.
+pub fn update_logistics_window(
+    mut contexts: EguiContexts,
+    colony_logistics: Option<Res<ColonyLogistics>>,
+    food_sharing_network: Option<Res<FoodSharingNetwork>>,
+) {
+    let Some(colony_logistics) = colony_logistics else {
+        return;
+    };
+
+    let tally = colony_logistics.last_day();
+
+    egui::Window::new("Logistics")
+        .default_pos(egui::Pos2::new(0.0, 350.0))
+        .default_open(false)
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label("Over the last day:");
+            ui.label(&format!(
+                "Foraged: {} ({} in the crater, {} above the nest)",
+                tally.foraged(),
+                tally.foraged_at_crater,
+                tally.foraged_at_surface
+            ));
+            ui.label(&format!("Delivered To Nest: {}", tally.delivered));
+            ui.label(&format!("Eaten: {}", tally.eaten));
+            ui.label(&format!("Spoiled: {}", tally.spoiled));
+
+            let net_change = tally.net_change();
+            let color = if net_change < 0 {
+                egui::Color32::RED
+            } else {
+                egui::Color32::GREEN
+            };
+
+            ui.colored_label(color, &format!("Net Change: {:+}", net_change));
+
+            // Food shared mouth-to-mouth never touches the ground so it's tallied apart from everything above.
+            if let Some(food_sharing_network) = food_sharing_network {
+                egui::CollapsingHeader::new("Food Sharing").show(ui, |ui| {
+                    show_food_sharing_network(ui, &food_sharing_network);
+                });
+            }
+        });
+}
+
+fn show_food_sharing_network(ui: &mut egui::Ui, food_sharing_network: &FoodSharingNetwork) {
+    ui.label("Since the colony was founded:");
+
+    let mut has_shared = false;
+
+    for (giver_role, receiver_role, count) in food_sharing_network.shares() {
+        has_shared = true;
+
+        ui.label(&format!(
+            "{} fed {}: {}",
+            get_role_name(giver_role),
+            get_role_name(receiver_role),
+            count
+        ));
+    }
+
+    if !has_shared {
+        ui.label("No food has been shared yet.");
+        return;
+    }
+
+    ui.label(&format!(
+        "Relayed To Hungrier Nestmates: {}",
+        food_sharing_network.relays()
+    ));
+}
+
+fn get_role_name(role: AntRole) -> &'static str {
+    match role {
+        AntRole::Worker => "Workers",
+        AntRole::Queen => "Queen",
+        AntRole::Soldier => "Soldiers",
+    }
+}
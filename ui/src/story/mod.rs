@@ -1,18 +1,53 @@
 mod action_menu;
+mod blocked_action_notice;
 mod breath_dialog;
+mod debug_console;
+mod demo_menu;
+mod encyclopedia_window;
+mod founding_guide;
+mod idle_pause_dialog;
 mod info_panel;
 mod loading_dialog;
+mod logistics_window;
+mod orders_menu;
+mod ruins_menu;
 mod selection_menu;
 mod settings_menu;
+mod spectator_menu;
 mod story_over_dialog;
+mod vignette_dialog;
+mod workforce_window;
 
 use self::{
-    action_menu::*, breath_dialog::update_breath_dialog, info_panel::*, loading_dialog::*,
-    selection_menu::update_selection_menu, settings_menu::update_settings_menu,
+    action_menu::*,
+    blocked_action_notice::update_blocked_action_notice,
+    breath_dialog::update_breath_dialog,
+    debug_console::update_debug_console,
+    demo_menu::update_demo_menu,
+    encyclopedia_window::update_encyclopedia_window,
+    founding_guide::update_founding_guide,
+    idle_pause_dialog::{pause_when_idle, remove_idle_pause_resources, update_idle_pause_dialog},
+    info_panel::*,
+    loading_dialog::*,
+    logistics_window::update_logistics_window,
+    orders_menu::update_orders_menu,
+    ruins_menu::update_ruins_menu,
+    selection_menu::update_selection_menu,
+    settings_menu::{pause_on_critical_event, update_settings_menu},
+    spectator_menu::update_spectator_menu,
     story_over_dialog::*,
+    vignette_dialog::update_vignette_dialog,
+    workforce_window::update_workforce_window,
 };
 use bevy::prelude::*;
-use simulation::{app_state::AppState, story_time::StoryPlaybackState};
+use rendering::common::visual_update::VisualUpdateSet;
+use simulation::{
+    app_state::{AppState, ViewingRuins},
+    broadcast::SpectatingBroadcast,
+    demo::DemoStory,
+    replay::ReplayingSimulation,
+    story_time::StoryPlaybackState,
+};
 
 pub struct StoryUIPlugin;
 
@@ -26,10 +61,29 @@ impl Plugin for StoryUIPlugin {
             (
                 update_info_window,
                 update_loading_dialog.run_if(in_state(StoryPlaybackState::FastForwarding)),
-                update_settings_menu,
-                update_action_menu,
-                update_selection_menu,
+                // Ruins and replays are view-only so hide menus which would modify the world.
+                (
+                    update_settings_menu,
+                    update_action_menu,
+                    update_orders_menu,
+                    pause_on_critical_event,
+                    pause_when_idle,
+                    update_idle_pause_dialog,
+                    update_blocked_action_notice,
+                )
+                    .run_if(not(is_view_only)),
+                update_ruins_menu.run_if(resource_exists::<ViewingRuins>()),
+                update_spectator_menu.run_if(resource_exists::<SpectatingBroadcast>()),
+                update_demo_menu.run_if(resource_exists::<DemoStory>()),
+                (update_selection_menu, update_debug_console).run_if(not(is_view_only)),
+                update_founding_guide.run_if(not(is_view_only)),
+                update_encyclopedia_window,
+                update_logistics_window,
+                update_workforce_window,
+                update_vignette_dialog.run_if(not(is_view_only)),
             )
+                // Menus can change which grid is visible. Views need to be synced, in the same frame, before that happens.
+                .before(VisualUpdateSet)
                 .run_if(
                     in_state(AppState::TellStory)
                         .and_then(not(resource_exists_and_equals(IsShowingBreathDialog(true)))),
@@ -41,7 +95,10 @@ impl Plugin for StoryUIPlugin {
             update_breath_dialog.run_if(resource_exists_and_equals(IsShowingBreathDialog(true))),
         );
 
-        app.add_systems(OnExit(AppState::TellStory), teardown_action_menu);
+        app.add_systems(
+            OnExit(AppState::TellStory),
+            (teardown_action_menu, remove_idle_pause_resources),
+        );
 
         app.add_systems(
             Update,
@@ -52,3 +109,11 @@ impl Plugin for StoryUIPlugin {
         );
     }
 }
+
+/// Ruins are read-only, and replays follow what was recorded, so nothing the player does can change either.
+fn is_view_only(
+    viewing_ruins: Option<Res<ViewingRuins>>,
+    replaying_simulation: Option<Res<ReplayingSimulation>>,
+) -> bool {
+    viewing_ruins.is_some() || replaying_simulation.is_some()
+}
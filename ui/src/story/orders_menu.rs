@@ -0,0 +1,68 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+use bevy_egui::{egui, EguiContexts};
+use rendering::common::{
+    pointer::PointerAction, selection::SelectedGroup, visible_grid::VisibleGrid,
+};
+use simulation::{
+    external_event::ExternalSimulationEvent,
+    nest_simulation::{
+        ant::order::OrderTask,
+        nest::{AtNest, Nest},
+    },
+    settings::Settings,
+};
+
+/// Give orders to a group of ants in sandbox mode. Ants only follow orders in the nest. The colony is left to its own devices
+/// in Story Mode so there are no orders to give.
+pub fn update_orders_menu(
+    mut contexts: EguiContexts,
+    primary_window_query: Query<&Window, With<PrimaryWindow>>,
+    mut pointer_action: ResMut<PointerAction>,
+    mut selected_group: ResMut<SelectedGroup>,
+    visible_grid: Res<VisibleGrid>,
+    nest_query: Query<&Nest>,
+    mut external_simulation_event_writer: EventWriter<ExternalSimulationEvent<AtNest>>,
+    settings: Res<Settings>,
+) {
+    if settings.is_story_mode || selected_group.0.is_empty() {
+        return;
+    }
+
+    let is_nest_visible = visible_grid.0.map_or(false, |visible_grid_entity| {
+        nest_query.get(visible_grid_entity).is_ok()
+    });
+
+    if !is_nest_visible {
+        return;
+    }
+
+    let window = primary_window_query.single();
+
+    egui::Window::new("Orders")
+        .default_pos(egui::Pos2::new(window.width(), window.height()))
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label(&format!(
+                "{} ant{} selected",
+                selected_group.0.len(),
+                if selected_group.0.len() == 1 { "" } else { "s" }
+            ));
+
+            ui.selectable_value(pointer_action.as_mut(), PointerAction::OrderDig, "Dig Here")
+                .on_hover_text("Tap an element for the group to dig out.");
+            ui.selectable_value(pointer_action.as_mut(), PointerAction::OrderGoTo, "Go Here")
+                .on_hover_text("Tap a position for the group to walk to.");
+
+            if ui.button("Return to Nest").clicked() {
+                external_simulation_event_writer.send(ExternalSimulationEvent::OrderAnts {
+                    zone: AtNest,
+                    ant_entities: selected_group.0.clone(),
+                    task: OrderTask::ReturnToNest,
+                });
+            }
+
+            if ui.button("Clear Selection").clicked() {
+                selected_group.0.clear();
+            }
+        });
+}
@@ -0,0 +1,44 @@
+// Replaces the action and settings menus while viewing ruins. Ruins are read-only, so the only actions available
+// are looking around and leaving.
+use bevy::{prelude::*, window::PrimaryWindow};
+use bevy_egui::{egui, EguiContexts};
+use rendering::common::visible_grid::{VisibleGrid, VisibleGridState};
+use simulation::{
+    crater_simulation::crater::Crater, nest_simulation::nest::Nest, save::close_ruins,
+};
+
+pub fn update_ruins_menu(
+    mut contexts: EguiContexts,
+    primary_window_query: Query<&Window, With<PrimaryWindow>>,
+    visible_grid: Res<VisibleGrid>,
+    nest_query: Query<&Nest>,
+    crater_query: Query<&Crater>,
+    mut next_visible_grid_state: ResMut<NextState<VisibleGridState>>,
+) {
+    let window = primary_window_query.single();
+    let ctx = contexts.ctx_mut();
+
+    egui::Window::new("Ruins")
+        .default_pos(egui::Pos2::new(window.width(), 0.0))
+        .resizable(false)
+        .show(ctx, |ui| {
+            ui.label("The colony has fallen silent.");
+
+            if let Some(visible_grid_entity) = visible_grid.0 {
+                if nest_query.get(visible_grid_entity).is_ok() {
+                    if ui.button("View Crater").clicked() {
+                        next_visible_grid_state.set(VisibleGridState::Crater);
+                    }
+                } else if crater_query.get(visible_grid_entity).is_ok() {
+                    if ui.button("View Nest").clicked() {
+                        next_visible_grid_state.set(VisibleGridState::Nest);
+                    }
+                }
+            }
+
+            // Ruins are viewed in an app of their own so return to the full app rather than cleaning up.
+            if ui.button("Return to Main Menu").clicked() {
+                close_ruins();
+            }
+        });
+}
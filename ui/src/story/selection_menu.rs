@@ -4,14 +4,32 @@ use bevy_egui::{egui, EguiContexts};
 use rendering::common::selection::SelectedEntity;
 
 use simulation::{
-    common::position::Position,
+    common::{intents::IntentsLog, position::Position},
     nest_simulation::{
         ant::{
-            birthing::Birthing, hunger::Hunger, sleep::Asleep, AntInventory, AntName, AntRole, Dead,
+            age::{get_life_stage, get_max_lifespan_ticks, Age, LifeStage},
+            birthing::Birthing,
+            collapse::Collapsed,
+            digestion::Digestion,
+            energy::Energy,
+            food_trail::ForagingTraits,
+            grooming::Groomed,
+            history::{AntHistory, AntHistoryEvent},
+            hunger::Hunger,
+            infection::Infected,
+            sleep::Asleep,
+            thirst::Thirst,
+            AntInventory, AntName, AntRole, Dead,
+        },
+        element::{
+            hardness::DigProgress,
+            water::{Moisture, MAX_MOISTURE},
+            Element, FoodKind,
         },
-        element::Element,
         pheromone::{Pheromone, PheromoneStrength},
     },
+    settings::Settings,
+    story_time::{StoryTime, DEFAULT_TICKS_PER_SECOND, SECONDS_PER_DAY},
 };
 
 #[derive(Component, Default, PartialEq, Copy, Clone, Debug)]
@@ -28,11 +46,24 @@ pub fn update_selection_menu(
         Option<&Birthing>,
         Option<&Dead>,
         Option<&Asleep>,
+        Option<&AntHistory>,
+        Option<&Collapsed>,
+        Option<&Age>,
+        Option<&Thirst>,
+        Option<&ForagingTraits>,
+        Option<&Energy>,
+        Option<&Infected>,
+        Option<&Groomed>,
     )>,
-    selected_element_query: Query<(&Element, &Position)>,
+    // Kept apart from `selected_ant_query` because a query can only hold so many components.
+    selected_digestion_query: Query<&Digestion>,
+    selected_element_query: Query<(&Element, &Position, Option<&Moisture>, Option<&DigProgress>)>,
     pheromone_query: Query<(&Position, &Pheromone, &PheromoneStrength)>,
     elements_query: Query<&Element>,
     selected_entity: Res<SelectedEntity>,
+    story_time: Res<StoryTime>,
+    settings: Res<Settings>,
+    mut intents_log: ResMut<IntentsLog>,
 ) {
     let window = primary_window_query.single();
     let ctx = contexts.ctx_mut();
@@ -53,10 +84,21 @@ pub fn update_selection_menu(
         .default_pos(egui::Pos2::new(0.0, window.height()))
         .resizable(false)
         .show(ctx, |ui| {
-            if let Ok((element, element_position)) = selected_element {
+            if let Ok((element, element_position, moisture, dig_progress)) = selected_element {
                 ui.label("Element");
                 ui.label(&format!("Type: {:?}", element));
 
+                if let Some(moisture) = moisture {
+                    ui.label(&format!(
+                        "Humidity: {:.0}%",
+                        (moisture.value() / MAX_MOISTURE) * 100.0
+                    ));
+                }
+
+                if let Some(dig_progress) = dig_progress {
+                    ui.label(&format!("Dug: {:.1} turns", dig_progress.value()));
+                }
+
                 // TODO: It's weird to show Pheromone here when they're tied to Tile not Element
                 if let Some((_, pheromone, pheromone_strength)) = pheromone_query
                     .iter()
@@ -68,17 +110,109 @@ pub fn update_selection_menu(
                         pheromone_strength.value()
                     ));
                 }
-            } else if let Ok((hunger, name, ant_role, inventory, birthing, dead, asleep)) =
-                selected_ant
+            } else if let Ok((
+                hunger,
+                name,
+                ant_role,
+                inventory,
+                birthing,
+                dead,
+                asleep,
+                history,
+                collapsed,
+                age,
+                thirst,
+                foraging_traits,
+                energy,
+                infected,
+                groomed,
+            )) = selected_ant
             {
                 ui.label("Ant");
                 ui.label(&format!("Name: {}", name.0));
                 ui.label(&format!("Role: {:?}", ant_role));
                 ui.label(&format!("Hunger: {:.0}%", hunger.value()));
 
-                if let Some(element_entity) = inventory.0 {
-                    let element = elements_query.get(element_entity).unwrap();
-                    ui.label(&format!("Carrying: {:?}", element));
+                if let Some(thirst) = thirst {
+                    ui.label(&format!("Thirst: {:.0}%", thirst.value()));
+                }
+
+                if let Ok(digestion) = selected_digestion_query.get(selected_entity) {
+                    ui.label(&format!(
+                        "Crop: {:.0}% full",
+                        digestion.undigested() / digestion.max() * 100.0
+                    ));
+
+                    // Show what the crop holds, by kind, so food can be followed as it's passed from ant to ant.
+                    if digestion.undigested() > 0.0 {
+                        let crop_contents = FoodKind::ALL
+                            .iter()
+                            .filter(|food_kind| digestion.food(**food_kind) > 0.0)
+                            .map(|food_kind| {
+                                format!(
+                                    "{:.0}% {}",
+                                    digestion.food(*food_kind) / digestion.undigested() * 100.0,
+                                    food_kind.label()
+                                )
+                            })
+                            .collect::<Vec<_>>();
+
+                        if !crop_contents.is_empty() {
+                            ui.label(&format!("Crop Contents: {}", crop_contents.join(", ")));
+                        }
+                    }
+                }
+
+                if let Some(energy) = energy {
+                    ui.label(&format!(
+                        "Energy: {:.0}%",
+                        energy.value() / energy.max() * 100.0
+                    ));
+                }
+
+                if let Some(age) = age {
+                    let ticks_per_day = (SECONDS_PER_DAY * DEFAULT_TICKS_PER_SECOND) as f32;
+                    let max_lifespan_ticks = get_max_lifespan_ticks(ant_role, &settings);
+
+                    ui.label(&format!(
+                        "Age: {:.0} of {:.0} days",
+                        (age.ticks() as f32 / ticks_per_day).floor(),
+                        max_lifespan_ticks as f32 / ticks_per_day
+                    ));
+
+                    // Workers take on different work as they age so show which work this worker is suited to.
+                    if *ant_role == AntRole::Worker {
+                        let life_stage = match get_life_stage(age.ticks(), max_lifespan_ticks) {
+                            LifeStage::Young => "Young (Nurse)",
+                            LifeStage::MiddleAged => "Middle-Aged (Digger)",
+                            LifeStage::Old => "Old (Forager)",
+                        };
+
+                        ui.label(&format!("Life Stage: {}", life_stage));
+                    }
+                }
+
+                if let Some(foraging_traits) = foraging_traits {
+                    ui.label(&format!(
+                        "Trail Sensitivity: {:.0}%",
+                        foraging_traits.pheromone_sensitivity * 100.0
+                    ));
+                    ui.label(&format!(
+                        "Exploration: {:.0}%",
+                        foraging_traits.exploration * 100.0
+                    ));
+                }
+
+                if !inventory.is_empty() {
+                    let carried_elements = inventory
+                        .items()
+                        .iter()
+                        .map(|&element_entity| {
+                            format!("{:?}", elements_query.get(element_entity).unwrap())
+                        })
+                        .collect::<Vec<_>>();
+
+                    ui.label(&format!("Carrying: {}", carried_elements.join(", ")));
                 }
 
                 if let Some(birthing) = birthing {
@@ -89,10 +223,95 @@ pub fn update_selection_menu(
                     ui.label(&format!("Sleeping"));
                 }
 
+                if let Some(collapsed) = collapsed {
+                    let remaining_minutes =
+                        collapsed.remaining_ticks() as f32 / (DEFAULT_TICKS_PER_SECOND * 60) as f32;
+
+                    ui.label(&format!(
+                        "Collapsed! Dies in {:.0} minutes unless fed",
+                        remaining_minutes.ceil()
+                    ));
+                }
+
+                if let Some(infected) = infected {
+                    let ticks_per_day = (SECONDS_PER_DAY * DEFAULT_TICKS_PER_SECOND) as f32;
+
+                    ui.label(&format!(
+                        "Infected! Dies in {:.1} days unless it recovers",
+                        infected.remaining_ticks() as f32 / ticks_per_day
+                    ));
+                }
+
+                if let Some(groomed) = groomed {
+                    let remaining_minutes =
+                        groomed.remaining_ticks() as f32 / (DEFAULT_TICKS_PER_SECOND * 60) as f32;
+
+                    ui.label(&format!(
+                        "Groomed: clean for {:.0} more minutes",
+                        remaining_minutes.ceil()
+                    ));
+                }
+
                 if let Some(_) = dead {
                     // TODO: Maybe have it say "Died at XXX"
                     ui.label("Dead");
                 }
+
+                if let Some(history) = history {
+                    ui.separator();
+                    ui.label("History");
+
+                    // Most recent first so the latest news is always at the top.
+                    for entry in history.entries().iter().rev() {
+                        let time_info = story_time.tick_as_time_info(entry.tick());
+
+                        ui.label(&format!(
+                            "Day {}, {:02}:{:02} - {}",
+                            time_info.days() + 1,
+                            time_info.hours(),
+                            time_info.minutes(),
+                            get_history_event_description(entry.event())
+                        ));
+                    }
+                }
+
+                ui.separator();
+
+                let mut is_logging_intents = intents_log.is_watching(selected_entity);
+                if ui
+                    .checkbox(&mut is_logging_intents, "Log Intents")
+                    .on_hover_text(
+                        "Describe the decisions this ant makes, and why, as it makes them.",
+                    )
+                    .changed()
+                {
+                    intents_log.watch(is_logging_intents.then_some(selected_entity));
+                }
+
+                if is_logging_intents {
+                    // Most recent first, like history, so the latest decision is always at the top.
+                    for entry in intents_log.entries().iter().rev() {
+                        let time_info = story_time.tick_as_time_info(entry.tick());
+
+                        ui.label(&format!(
+                            "{:02}:{:02} - {}",
+                            time_info.hours(),
+                            time_info.minutes(),
+                            entry.description()
+                        ));
+                    }
+                }
             }
         });
 }
+
+fn get_history_event_description(event: AntHistoryEvent) -> &'static str {
+    match event {
+        AntHistoryEvent::Born => "Born",
+        AntHistoryEvent::FirstForaged => "Foraged for the first time",
+        AntHistoryEvent::NearStarvation => "Nearly starved",
+        AntHistoryEvent::Died => "Died",
+        AntHistoryEvent::Collapsed => "Collapsed from hunger",
+        AntHistoryEvent::Revived => "Revived by a nestmate",
+    }
+}
@@ -1,28 +1,54 @@
-use bevy::{prelude::*, window::PrimaryWindow};
+use bevy::{ecs::system::SystemParam, prelude::*, window::PrimaryWindow};
 use bevy_egui::{egui, EguiContexts};
 
-use rendering::nest::pheromone::PheromoneVisibility;
+use rendering::{
+    common::{
+        camera::tour::CameraTour,
+        visual_update::{VisualUpdatesPerSecond, MAX_VISUAL_UPDATES_PER_SECOND},
+    },
+    crater::fog::FogVisibility,
+    nest::{humidity::HumidityVisibility, pheromone::PheromoneVisibility},
+};
 use simulation::{
     app_state::AppState,
-    nest_simulation::ant::AntColor,
+    content_pack::{ContentPackStatus, ContentPacks},
+    crater_simulation::ant::wander::ExplorationStrategy,
+    device_quality::{DeviceQuality, QualityLevel},
+    nest_simulation::{ant::AntColor, critical_event::CriticalEvent},
+    save::write_device_quality,
     settings::Settings,
     story_time::{
-        StoryPlaybackState, StoryTime, TicksPerSecond, DEFAULT_TICKS_PER_SECOND,
-        MAX_USER_TICKS_PER_SECOND,
+        get_time_zone_names, StoryPlaybackState, StoryTime, TicksPerSecond,
+        DEFAULT_TICKS_PER_SECOND, MAX_USER_TICKS_PER_SECOND,
     },
 };
 
+use crate::{bevy_color_to_color32, color32_to_bevy_color};
+
+/// The overlays which can be shown over, or hidden from, the nest and crater.
+#[derive(SystemParam)]
+pub struct OverlayVisibility<'w> {
+    pheromone: ResMut<'w, PheromoneVisibility>,
+    humidity: ResMut<'w, HumidityVisibility>,
+    fog: ResMut<'w, FogVisibility>,
+}
+
 pub fn update_settings_menu(
     mut contexts: EguiContexts,
     primary_window_query: Query<&Window, With<PrimaryWindow>>,
     mut next_app_state: ResMut<NextState<AppState>>,
     mut ticks_per_second: ResMut<TicksPerSecond>,
+    mut visual_updates_per_second: ResMut<VisualUpdatesPerSecond>,
     story_playback_state: Res<State<StoryPlaybackState>>,
     mut next_story_playback_state: ResMut<NextState<StoryPlaybackState>>,
-    mut pheromone_visibility: ResMut<PheromoneVisibility>,
+    mut overlay_visibility: OverlayVisibility,
     mut story_time: ResMut<StoryTime>,
     mut settings: ResMut<Settings>,
     mut ant_query: Query<&mut AntColor>,
+    camera_tour: Option<Res<CameraTour>>,
+    content_packs: Res<ContentPacks>,
+    mut device_quality: ResMut<DeviceQuality>,
+    mut commands: Commands,
 ) {
     let window = primary_window_query.single();
     let ctx = contexts.ctx_mut();
@@ -37,6 +63,14 @@ pub fn update_settings_menu(
                 ui.checkbox(&mut story_time.is_real_sun, "Use Real Sunrise/Sunset");
             });
 
+            // Real time follows the real-world calendar's seasons instead.
+            ui.add_enabled_ui(!story_time.is_real_time, |ui| {
+                ui.add(
+                    egui::Slider::new(&mut story_time.days_per_season, 1..=30)
+                        .text("days per season"),
+                );
+            });
+
             ui.add_enabled_ui(story_time.is_real_sun, |ui| {
                 ui.horizontal_top(|ui| {
                     // TODO: egui doesn't support numeric inputs
@@ -73,6 +107,38 @@ pub fn update_settings_menu(
                 });
             });
 
+            ui.add_enabled_ui(story_time.is_real_time, |ui| {
+                // Detecting the time zone follows the player when they travel. Choosing one pins the clock to it.
+                let mut is_time_zone_detected = story_time.time_zone_name().is_none();
+                ui.checkbox(&mut is_time_zone_detected, "Detect Time Zone");
+
+                if is_time_zone_detected != story_time.time_zone_name().is_none() {
+                    let time_zone_name =
+                        (!is_time_zone_detected).then(|| story_time.time_zone().name().to_string());
+                    story_time.set_time_zone_name(time_zone_name);
+                }
+
+                ui.add_enabled_ui(!is_time_zone_detected, |ui| {
+                    let selected_time_zone_name = story_time.time_zone().name();
+
+                    egui::ComboBox::from_label("Time Zone")
+                        .selected_text(selected_time_zone_name)
+                        .show_ui(ui, |ui| {
+                            for time_zone_name in get_time_zone_names() {
+                                if ui
+                                    .selectable_label(
+                                        time_zone_name == selected_time_zone_name,
+                                        time_zone_name,
+                                    )
+                                    .clicked()
+                                {
+                                    story_time.set_time_zone_name(Some(time_zone_name.to_string()));
+                                }
+                            }
+                        });
+                });
+            });
+
             ui.add_enabled_ui(story_time.is_real_time, |ui| {
                 ui.checkbox(
                     &mut settings.is_breathwork_scheduled,
@@ -95,6 +161,35 @@ pub fn update_settings_menu(
                 .text("ticks/sec"),
             );
 
+            // Views only need updating as often as they can be seen. Lower values keep high tick rates smooth on slow devices.
+            ui.add(
+                egui::Slider::new(
+                    &mut visual_updates_per_second.0,
+                    1..=MAX_VISUAL_UPDATES_PER_SECOND,
+                )
+                .text("visual updates/sec"),
+            );
+
+            ui.add(
+                egui::Slider::new(&mut settings.soldier_spawn_ratio, 0.0..=1.0)
+                    .text("soldier ratio"),
+            );
+
+            ui.add(egui::Slider::new(&mut settings.nurse_ratio, 0.0..=1.0).text("nurse ratio"));
+
+            ui.add(
+                egui::Slider::new(&mut settings.probabilities.speech_bubble, 0.0..=0.01)
+                    .text("speech frequency"),
+            );
+
+            show_device_quality(ui, &mut device_quality);
+
+            // Colony growth is throttled once estimated memory usage exceeds this budget.
+            ui.add(
+                egui::Slider::new(&mut settings.memory_budget_megabytes, 32..=1_024)
+                    .text("memory budget (MB)"),
+            );
+
             match story_playback_state.get() {
                 StoryPlaybackState::Playing => {
                     if ui.button("Pause").clicked() {
@@ -114,16 +209,114 @@ pub fn update_settings_menu(
                 }
             }
 
-            if pheromone_visibility.0 == Visibility::Hidden {
+            ui.checkbox(&mut settings.is_sleeping_at_night, "Sleep At Night");
+
+            ui.checkbox(
+                &mut settings.pause_on_queen_starving,
+                "Pause When Queen Starves",
+            );
+            ui.checkbox(
+                &mut settings.pause_on_brood_flooded,
+                "Pause When Brood Floods",
+            );
+            ui.checkbox(
+                &mut settings.pause_on_nest_raided,
+                "Pause When Nest Is Raided",
+            );
+
+            ui.checkbox(&mut settings.pause_when_idle, "Pause When Idle")
+                .on_hover_text("Pause, and save, after a while without any input.");
+
+            ui.add_enabled_ui(settings.pause_when_idle, |ui| {
+                ui.add(
+                    egui::Slider::new(&mut settings.idle_pause_minutes, 5..=120)
+                        .text("idle minutes"),
+                );
+            });
+
+            ui.checkbox(&mut settings.is_disease_enabled, "Disease")
+                .on_hover_text(
+                    "Infections spread between ants and can kill those which don't recover.",
+                );
+
+            ui.checkbox(&mut settings.is_night_raids_enabled, "Night Raids")
+                .on_hover_text("Rival ants raid the nest, now and then, at night for food and brood.");
+
+            ui.add(
+                egui::Slider::new(&mut settings.sand_repose_angle, 15.0..=45.0)
+                    .text("sand repose angle"),
+            )
+            .on_hover_text("How steep piles of sand get before sand slides down them.");
+
+            ui.add(egui::Slider::new(&mut settings.probabilities.rain, 0.0..=1.0).text("rainy days"))
+                .on_hover_text("How often it rains. Rain soaks the dirt and, in a storm, can flood shallow tunnels.");
+
+            ui.add_enabled_ui(settings.is_story_mode, |ui| {
+                ui.checkbox(&mut settings.is_cursor_curiosity_enabled, "Curious Ants")
+                    .on_hover_text(
+                        "Idle ants on the surface come over to investigate the cursor. Story Mode only.",
+                    );
+            });
+
+            egui::ComboBox::from_label("Forager Exploration")
+                .selected_text(format!("{:?}", settings.exploration_strategy))
+                .show_ui(ui, |ui| {
+                    for exploration_strategy in ExplorationStrategy::ALL {
+                        ui.selectable_value(
+                            &mut settings.exploration_strategy,
+                            exploration_strategy,
+                            format!("{:?}", exploration_strategy),
+                        );
+                    }
+                })
+                .response
+                .on_hover_text("How ants search the crater when they've nowhere in particular to be.");
+
+            ui.checkbox(&mut settings.is_reduced_motion, "Reduce Motion")
+                .on_hover_text("Replace moving and flashing effects with gentle fades.");
+
+            ui.checkbox(&mut settings.is_keyboard_navigation, "Keyboard Navigation")
+                .on_hover_text(
+                    "Arrow keys move a cursor over the grid and Enter acts on it. Tab moves through menus and Escape returns to the grid.",
+                );
+
+            if overlay_visibility.pheromone.0 == Visibility::Hidden {
                 if ui.button("Show Pheromones").clicked() {
-                    pheromone_visibility.0 = Visibility::Visible;
+                    overlay_visibility.pheromone.0 = Visibility::Visible;
                 }
-            } else if pheromone_visibility.0 == Visibility::Visible {
+            } else if overlay_visibility.pheromone.0 == Visibility::Visible {
                 if ui.button("Hide Pheromones").clicked() {
-                    pheromone_visibility.0 = Visibility::Hidden;
+                    overlay_visibility.pheromone.0 = Visibility::Hidden;
                 }
             }
 
+            if overlay_visibility.humidity.0 == Visibility::Hidden {
+                if ui.button("Show Humidity").clicked() {
+                    overlay_visibility.humidity.0 = Visibility::Visible;
+                }
+            } else if overlay_visibility.humidity.0 == Visibility::Visible {
+                if ui.button("Hide Humidity").clicked() {
+                    overlay_visibility.humidity.0 = Visibility::Hidden;
+                }
+            }
+
+            if overlay_visibility.fog.0 == Visibility::Hidden {
+                if ui.button("Show Crater Fog").clicked() {
+                    overlay_visibility.fog.0 = Visibility::Visible;
+                }
+            } else if overlay_visibility.fog.0 == Visibility::Visible {
+                if ui.button("Hide Crater Fog").clicked() {
+                    overlay_visibility.fog.0 = Visibility::Hidden;
+                }
+            }
+
+            // Touring stops as soon as the user interacts with anything, so there's no button to stop it.
+            if camera_tour.is_some() {
+                ui.add_enabled(false, egui::Button::new("Touring Colony"));
+            } else if ui.button("Tour Colony").clicked() {
+                commands.init_resource::<CameraTour>();
+            }
+
             ui.horizontal_top(|ui| {
                 ui.label("Ant Color");
 
@@ -144,28 +337,115 @@ pub fn update_settings_menu(
                 }
             });
 
+            egui::CollapsingHeader::new("Content Packs")
+                .default_open(false)
+                .show(ui, |ui| show_content_packs(ui, &content_packs));
+
             if ui.button("Reset Sandbox").clicked() {
                 next_app_state.set(AppState::Cleanup);
             }
         });
 }
 
-fn color32_to_bevy_color(color: egui::Color32) -> bevy::prelude::Color {
-    bevy::prelude::Color::rgba(
-        color.r() as f32 / 255.0,
-        color.g() as f32 / 255.0,
-        color.b() as f32 / 255.0,
-        color.a() as f32 / 255.0,
-    )
+/// Pause when something pivotal happens so it isn't missed while time passes quickly.
+/// Only pause while the tab is focused. Time spent away from the tab is caught up on when returning,
+/// and pausing then would leave the player to discover the moment long after it passed.
+pub fn pause_on_critical_event(
+    mut critical_events: EventReader<CriticalEvent>,
+    settings: Res<Settings>,
+    primary_window_query: Query<&Window, With<PrimaryWindow>>,
+    story_playback_state: Res<State<StoryPlaybackState>>,
+    mut next_story_playback_state: ResMut<NextState<StoryPlaybackState>>,
+) {
+    let should_pause = critical_events
+        .read()
+        .any(|critical_event| match critical_event {
+            CriticalEvent::QueenStarving => settings.pause_on_queen_starving,
+            CriticalEvent::BroodFlooded => settings.pause_on_brood_flooded,
+            CriticalEvent::NestRaided => settings.pause_on_nest_raided,
+        });
+
+    if !should_pause || *story_playback_state != StoryPlaybackState::Playing {
+        return;
+    }
+
+    let is_focused = primary_window_query
+        .get_single()
+        .map_or(false, |window| window.focused);
+
+    if is_focused {
+        next_story_playback_state.set(StoryPlaybackState::Paused);
+    }
+}
+
+/// Quality is measured on first run. Players who know better, e.g. those who'd trade battery life for flourishes, can
+/// choose a level for themselves. The choice is kept with the device, rather than with the story.
+fn show_device_quality(ui: &mut egui::Ui, device_quality: &mut ResMut<DeviceQuality>) {
+    let detected_level_name = device_quality
+        .detected_level()
+        .map_or(String::from("Not Yet Measured"), |detected_level| {
+            format!("{:?}", detected_level)
+        });
+    let auto_name = format!("Auto ({})", detected_level_name);
+
+    let mut chosen_level = device_quality.chosen_level();
+
+    egui::ComboBox::from_label("Quality")
+        .selected_text(chosen_level.map_or(auto_name.clone(), |level| format!("{:?}", level)))
+        .show_ui(ui, |ui| {
+            ui.selectable_value(&mut chosen_level, None, auto_name);
+
+            for level in QualityLevel::ALL {
+                ui.selectable_value(&mut chosen_level, Some(level), format!("{:?}", level));
+            }
+        });
+
+    if chosen_level != device_quality.chosen_level() {
+        device_quality.set_chosen_level(chosen_level);
+        write_device_quality(device_quality);
+    }
+
+    let max_world_size = device_quality.level().max_world_size();
+    ui.label(&format!(
+        "Recommended Max World Size: {}x{}",
+        max_world_size, max_world_size
+    ));
 }
 
-fn bevy_color_to_color32(color: bevy::prelude::Color) -> egui::Color32 {
-    egui::Color32::from_rgba_unmultiplied(
-        (color.r() * 255.0) as u8,
-        (color.g() * 255.0) as u8,
-        (color.b() * 255.0) as u8,
-        (color.a() * 255.0) as u8,
-    )
+fn show_content_packs(ui: &mut egui::Ui, content_packs: &ContentPacks) {
+    if content_packs.packs().is_empty() {
+        ui.label("No content packs found.");
+        return;
+    }
+
+    for loaded_content_pack in content_packs.packs() {
+        // Packs which failed to load have no name so fall back to where they were expected to be found.
+        let name = if loaded_content_pack.pack.name.is_empty() {
+            &loaded_content_pack.path
+        } else {
+            &loaded_content_pack.pack.name
+        };
+
+        match &loaded_content_pack.status {
+            ContentPackStatus::Loading => {
+                ui.label(format!("{} (Loading)", name));
+            }
+            ContentPackStatus::Loaded => {
+                ui.label(format!("✔ {}", name))
+                    .on_hover_text(&loaded_content_pack.pack.description);
+            }
+            ContentPackStatus::Failed => {
+                ui.colored_label(egui::Color32::RED, format!("✖ {} (Failed to load)", name));
+            }
+            ContentPackStatus::Invalid(problems) => {
+                ui.colored_label(egui::Color32::RED, format!("✖ {} (Invalid)", name));
+
+                for problem in problems {
+                    ui.label(format!("  • {}", problem));
+                }
+            }
+        }
+    }
 }
 
 fn decimal_hours_to_hours_minutes(decimal_hours: f32) -> (f32, f32) {
@@ -0,0 +1,45 @@
+// Replaces the action and settings menus while watching a broadcast story. The story is replayed as the host tells it,
+// so the only actions available are looking around and leaving.
+use bevy::{prelude::*, window::PrimaryWindow};
+use bevy_egui::{egui, EguiContexts};
+use rendering::common::visible_grid::{VisibleGrid, VisibleGridState};
+use simulation::{
+    app_state::AppState, crater_simulation::crater::Crater, nest_simulation::nest::Nest,
+};
+
+pub fn update_spectator_menu(
+    mut contexts: EguiContexts,
+    primary_window_query: Query<&Window, With<PrimaryWindow>>,
+    visible_grid: Res<VisibleGrid>,
+    nest_query: Query<&Nest>,
+    crater_query: Query<&Crater>,
+    mut next_visible_grid_state: ResMut<NextState<VisibleGridState>>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    let window = primary_window_query.single();
+    let ctx = contexts.ctx_mut();
+
+    egui::Window::new("Spectating")
+        .default_pos(egui::Pos2::new(window.width(), 0.0))
+        .resizable(false)
+        .show(ctx, |ui| {
+            ui.label("Watching someone else's colony as it happens.");
+
+            if let Some(visible_grid_entity) = visible_grid.0 {
+                if nest_query.get(visible_grid_entity).is_ok() {
+                    if ui.button("View Crater").clicked() {
+                        next_visible_grid_state.set(VisibleGridState::Crater);
+                    }
+                } else if crater_query.get(visible_grid_entity).is_ok() {
+                    if ui.button("View Nest").clicked() {
+                        next_visible_grid_state.set(VisibleGridState::Nest);
+                    }
+                }
+            }
+
+            // Replays are never saved so leaving doesn't lose anything.
+            if ui.button("Return to Main Menu").clicked() {
+                next_app_state.set(AppState::Cleanup);
+            }
+        });
+}
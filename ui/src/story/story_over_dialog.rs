@@ -17,7 +17,8 @@ pub fn update_story_over_dialog(
         .resizable(false)
         .collapsible(false)
         .show(ctx, |ui| {
-            ui.label("Queen has died. Sadge :(. Story over. Begin again?");
+            ui.label("The queen died without an heir and the colony has died out. Story over. Begin again?");
+            ui.label("The colony's ruins can be revisited from the main menu.");
 
             ui.vertical_centered(|ui| {
                 if ui.button("Begin New Story").clicked() {
@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+use bevy_egui::{
+    egui::{self, Align2},
+    EguiContexts,
+};
+use simulation::content_pack::StoryVignette;
+
+/// Show the most recent vignette until it's dismissed or replaced by the next one.
+pub fn update_vignette_dialog(
+    mut contexts: EguiContexts,
+    mut story_vignettes: EventReader<StoryVignette>,
+    mut current_vignette: Local<Option<StoryVignette>>,
+) {
+    if let Some(story_vignette) = story_vignettes.read().last() {
+        *current_vignette = Some(story_vignette.clone());
+    }
+
+    let Some(story_vignette) = current_vignette.as_ref() else {
+        return;
+    };
+
+    let mut is_dismissed = false;
+
+    egui::Window::new(story_vignette.title.as_deref().unwrap_or("Meanwhile..."))
+        .id(egui::Id::new("vignette_dialog"))
+        .anchor(Align2::CENTER_BOTTOM, egui::Vec2::new(0.0, -10.0))
+        .collapsible(false)
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.set_max_width(300.0);
+            ui.label(&story_vignette.text);
+
+            if ui.button("Dismiss").clicked() {
+                is_dismissed = true;
+            }
+        });
+
+    if is_dismissed {
+        *current_vignette = None;
+    }
+}
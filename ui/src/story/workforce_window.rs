@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use simulation::nest_simulation::task_allocation::{TaskAllocation, WorkerTask};
+
+/// Show how the colony has split its workers between the work that needs doing. The window starts collapsed, like the
+/// logistics window, because it's for players wondering why their workers are, or aren't, doing something.
+pub fn update_workforce_window(
+    mut contexts: EguiContexts,
+    task_allocation: Option<Res<TaskAllocation>>,
+) {
+    let Some(task_allocation) = task_allocation else {
+        return;
+    };
+
+    egui::Window::new("Workforce")
+        .default_pos(egui::Pos2::new(0.0, 450.0))
+        .default_open(false)
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            for task in WorkerTask::all() {
+                let staffing = task_allocation.get_staffing(task);
+                let name = match task {
+                    WorkerTask::Foraging => "Foraging",
+                    WorkerTask::Nursing => "Nursing",
+                    WorkerTask::Digging => "Digging",
+                    WorkerTask::WasteManagement => "Waste Management",
+                };
+
+                ui.label(&format!(
+                    "{}: {} workers ({} to do)",
+                    name, staffing.assigned, staffing.demand
+                ));
+            }
+
+            ui.label(&format!("Idle: {} workers", task_allocation.idle_count()));
+        });
+}
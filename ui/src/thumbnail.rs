@@ -0,0 +1,107 @@
+use bevy::prelude::*;
+use bevy_egui::egui;
+
+use simulation::{
+    app_state::ViewingRuins,
+    nest_simulation::element::{definition::ElementDefinitions, Element},
+    save::{load_archived_save_thumbnail, load_save_thumbnail, SaveThumbnail},
+};
+
+// Thumbnails are tiny so they're scaled up, keeping their pixels crisp, to be legible.
+const THUMBNAIL_SCALE: f32 = 3.0;
+
+/// A thumbnail, drawn in a menu, so players can see which colony they're about to revisit.
+/// The image is turned into a texture the first time it's shown because that requires the egui context.
+#[derive(Default)]
+pub struct ThumbnailPreview {
+    image: Option<egui::ColorImage>,
+    texture: Option<egui::TextureHandle>,
+}
+
+impl ThumbnailPreview {
+    pub fn new(thumbnail: Option<SaveThumbnail>, element_definitions: &ElementDefinitions) -> Self {
+        Self {
+            image: thumbnail.map(|thumbnail| get_thumbnail_image(&thumbnail, element_definitions)),
+            texture: None,
+        }
+    }
+
+    /// Saves without a thumbnail show nothing.
+    pub fn show(&mut self, ui: &mut egui::Ui, name: &str) {
+        if let Some(image) = self.image.take() {
+            self.texture = Some(
+                ui.ctx()
+                    .load_texture(name, image, egui::TextureOptions::NEAREST),
+            );
+        }
+
+        if let Some(texture) = &self.texture {
+            ui.image((texture.id(), texture.size_vec2() * THUMBNAIL_SCALE));
+        }
+    }
+}
+
+/// A preview of the colony being loaded, shown while it loads and while it catches up on the time since it was last
+/// played.
+#[derive(Resource, Default)]
+pub struct LoadingThumbnail(pub ThumbnailPreview);
+
+pub fn initialize_loading_thumbnail(
+    viewing_ruins: Option<Res<ViewingRuins>>,
+    element_definitions: Res<ElementDefinitions>,
+    mut commands: Commands,
+) {
+    let thumbnail = match viewing_ruins {
+        Some(viewing_ruins) => load_archived_save_thumbnail(viewing_ruins.story_id),
+        None => load_save_thumbnail(),
+    };
+
+    commands.insert_resource(LoadingThumbnail(ThumbnailPreview::new(
+        thumbnail,
+        &element_definitions,
+    )));
+}
+
+pub fn remove_loading_thumbnail(mut commands: Commands) {
+    commands.remove_resource::<LoadingThumbnail>();
+}
+
+fn get_thumbnail_image(
+    thumbnail: &SaveThumbnail,
+    element_definitions: &ElementDefinitions,
+) -> egui::ColorImage {
+    let pixels = (0..thumbnail.height)
+        .flat_map(|y| (0..thumbnail.width).map(move |x| (x, y)))
+        .map(|(x, y)| {
+            get_thumbnail_color(
+                thumbnail.get_element(x, y),
+                y > thumbnail.surface_level,
+                element_definitions,
+            )
+        })
+        .collect();
+
+    egui::ColorImage {
+        size: [thumbnail.width as usize, thumbnail.height as usize],
+        pixels,
+    }
+}
+
+/// Thumbnails are drawn in flat colors, roughly matching each element's sprite, rather than from the sprites themselves.
+/// Air is drawn as the tunnel it was dug from underground and as sky aboveground.
+fn get_thumbnail_color(
+    element: Element,
+    is_underground: bool,
+    element_definitions: &ElementDefinitions,
+) -> egui::Color32 {
+    match element {
+        Element::Air if is_underground => egui::Color32::from_rgb(61, 47, 27),
+        Element::Air => egui::Color32::from_rgb(135, 206, 235),
+        _ => element_definitions
+            .get(element)
+            .thumbnail_color()
+            .map_or(egui::Color32::BLACK, |(red, green, blue)| {
+                egui::Color32::from_rgb(red, green, blue)
+            }),
+    }
+}